@@ -3,12 +3,25 @@
 //! This crate provides functionality for generating and managing X.509 certificates
 //! used in Avalanche network communications.
 
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
 pub mod x509;
 
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Error, ErrorKind, Write},
+    path::Path,
+};
+
 /// 证书管理器配置
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CertConfig {
-    // ...
+    /// 质押 TLS 私钥路径 (PEM)
+    pub key_path: String,
+    /// 质押 TLS 证书路径 (PEM)
+    pub cert_path: String,
+    /// 轮换时是否保留旧的私钥和证书 (后缀 ".bak")
+    pub keep_backup: bool,
 }
 
 impl Default for CertConfig {
@@ -24,14 +37,44 @@ impl CertConfig {
     /// 返回新的 `CertConfig` 实例
     #[must_use]
     pub const fn new() -> Self {
-        Self { /* ... */ }
+        Self {
+            key_path: String::new(),
+            cert_path: String::new(),
+            keep_backup: true,
+        }
+    }
+
+    /// 设置私钥和证书路径
+    #[must_use]
+    pub fn paths(mut self, key_path: &str, cert_path: &str) -> Self {
+        self.key_path = key_path.to_string();
+        self.cert_path = cert_path.to_string();
+        self
     }
+
+    /// 设置是否保留旧的私钥和证书
+    #[must_use]
+    pub const fn keep_backup(mut self, keep_backup: bool) -> Self {
+        self.keep_backup = keep_backup;
+        self
+    }
+}
+
+/// 证书轮换结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotated {
+    pub key_path: String,
+    pub cert_path: String,
+    /// 新证书 (DER)，用于计算新的 `NodeId`
+    pub cert_der: Vec<u8>,
+    /// 旧私钥和证书的备份路径 (如有)
+    pub backup_paths: Option<(String, String)>,
 }
 
 /// 证书管理器
 #[derive(Debug)]
 pub struct CertManager {
-    // ...
+    config: CertConfig,
 }
 
 impl CertManager {
@@ -40,7 +83,189 @@ impl CertManager {
     /// # Returns
     /// 返回新的 `CertManager` 实例
     #[must_use]
-    pub const fn new(_config: &CertConfig) -> Self {
-        Self { /* ... */ }
+    pub fn new(config: &CertConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// 生成新的质押私钥和证书 (如提供 CA 则由 CA 签发)，
+    /// 先写入同目录的临时文件并校验密钥对，再通过 rename 替换原文件。
+    ///
+    /// # Returns
+    /// 返回新证书的路径和 DER 编码 (例如用于计算新的 `NodeId`)
+    ///
+    /// # Errors
+    /// 路径为空、证书生成失败、密钥对不匹配或文件操作失败时返回错误。
+    /// 替换证书失败时会恢复原私钥。
+    pub fn rotate(&self, ca: Option<&x509::Ca>) -> io::Result<Rotated> {
+        let (key_path, cert_path) = (&self.config.key_path, &self.config.cert_path);
+        if key_path.is_empty() || cert_path.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "empty key_path or cert_path",
+            ));
+        }
+        log::info!("rotating staking key '{key_path}' and cert '{cert_path}'");
+
+        let (key_pem, cert_pem) = if let Some(ca) = ca {
+            let csr_entity = x509::CsrEntity::new("avalanche-staking")?;
+            let cert_pem = ca.issue_cert(&csr_entity.csr_pem)?;
+            (csr_entity.cert.serialize_private_key_pem(), cert_pem)
+        } else {
+            let cert = x509::generate(None)?;
+            let cert_pem = cert.serialize_pem().map_err(|e| {
+                Error::new(ErrorKind::Other, format!("failed to serialize_pem {e}"))
+            })?;
+            (cert.serialize_private_key_pem(), cert_pem)
+        };
+
+        // same directory, so the renames never cross file systems
+        let suffix = format!(".rotate-{}", random_manager::secure_string(10));
+        let (tmp_key_path, tmp_cert_path) = (
+            format!("{key_path}{suffix}"),
+            format!("{cert_path}{suffix}"),
+        );
+        let res = write_synced(&tmp_key_path, key_pem.as_bytes(), KEY_MODE)
+            .and_then(|()| write_synced(&tmp_cert_path, cert_pem.as_bytes(), CERT_MODE))
+            .and_then(|()| x509::verify_pem_key_cert_pair(&tmp_key_path, &tmp_cert_path))
+            .and_then(|()| x509::load_pem_cert_to_der(&tmp_cert_path));
+        let cert_der = match res {
+            Ok(cert_der) => cert_der.to_vec(),
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_key_path);
+                let _ = fs::remove_file(&tmp_cert_path);
+                return Err(e);
+            }
+        };
+
+        let backup_paths = if self.config.keep_backup
+            && Path::new(key_path).exists()
+            && Path::new(cert_path).exists()
+        {
+            let (backup_key_path, backup_cert_path) =
+                (format!("{key_path}.bak"), format!("{cert_path}.bak"));
+            if let Err(e) = fs::copy(key_path, &backup_key_path)
+                .and_then(|_| fs::copy(cert_path, &backup_cert_path))
+            {
+                let _ = fs::remove_file(&tmp_key_path);
+                let _ = fs::remove_file(&tmp_cert_path);
+                return Err(e);
+            }
+            log::info!("backed up to '{backup_key_path}' and '{backup_cert_path}'");
+            Some((backup_key_path, backup_cert_path))
+        } else {
+            None
+        };
+
+        // each rename is atomic, restore the old key if the cert fails to swap
+        // so the key and cert on disk never mismatch
+        let old_key = fs::read(key_path).ok();
+        fs::rename(&tmp_key_path, key_path)?;
+        if let Err(e) = fs::rename(&tmp_cert_path, cert_path) {
+            log::warn!("failed to swap cert '{cert_path}' ({e}), restoring key '{key_path}'");
+            if let Some(old_key) = old_key {
+                write_synced(key_path, &old_key, KEY_MODE)?;
+            }
+            let _ = fs::remove_file(&tmp_cert_path);
+            return Err(e);
+        }
+        log::info!("rotated staking key '{key_path}' and cert '{cert_path}'");
+
+        Ok(Rotated {
+            key_path: key_path.clone(),
+            cert_path: cert_path.clone(),
+            cert_der,
+            backup_paths,
+        })
+    }
+}
+
+/// 私钥文件权限 (仅所有者可读写)
+const KEY_MODE: u32 = 0o600;
+/// 证书文件权限
+const CERT_MODE: u32 = 0o644;
+
+/// 写入文件并同步到磁盘，在 Unix 上以 `mode` 权限创建
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn write_synced(path: &str, contents: &[u8], mode: u32) -> io::Result<()> {
+    let mut opts = OpenOptions::new();
+    opts.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(mode);
+    }
+    let mut f = opts.open(path)?;
+    f.write_all(contents)?;
+    f.sync_all()
+}
+
+/// `RUST_LOG=debug` cargo test --all-features --lib -- `test_rotate` --exact
+/// --show-output
+#[test]
+fn test_rotate() {
+    let _ = env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .is_test(true)
+        .try_init();
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let key_path = tmp_dir.path().join("staker.key");
+    let key_path = key_path.to_str().unwrap();
+    let cert_path = tmp_dir.path().join("staker.crt");
+    let cert_path = cert_path.to_str().unwrap();
+
+    assert!(CertManager::new(&CertConfig::new()).rotate(None).is_err());
+
+    x509::generate_and_write_pem(None, key_path, cert_path).unwrap();
+    let old_cert = fs::read(cert_path).unwrap();
+
+    let manager = CertManager::new(&CertConfig::new().paths(key_path, cert_path));
+    let rotated = manager.rotate(None).unwrap();
+    assert_ne!(fs::read(cert_path).unwrap(), old_cert);
+    assert_eq!(
+        x509::load_pem_cert_to_der(cert_path).unwrap().to_vec(),
+        rotated.cert_der
+    );
+    x509::verify_pem_key_cert_pair(key_path, cert_path).unwrap();
+
+    // the new key is only readable by the owner
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(key_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, KEY_MODE);
     }
+
+    let (backup_key_path, backup_cert_path) = rotated.backup_paths.unwrap();
+    assert_eq!(fs::read(&backup_cert_path).unwrap(), old_cert);
+    x509::verify_pem_key_cert_pair(&backup_key_path, &backup_cert_path).unwrap();
+
+    // issued by the CA
+    let ca = x509::Ca::new("ca.hello.com").unwrap();
+    let manager = CertManager::new(
+        &CertConfig::new()
+            .paths(key_path, cert_path)
+            .keep_backup(false),
+    );
+    let rotated = manager.rotate(Some(&ca)).unwrap();
+    assert!(rotated.backup_paths.is_none());
+    x509::verify_pem_key_cert_pair(key_path, cert_path).unwrap();
+    let infos = x509::load_pem_cert_chain_info(cert_path).unwrap();
+    assert!(infos[0].issuer.contains("CN=ca.hello.com"));
+
+    // no temporary files left
+    let files = fs::read_dir(tmp_dir.path()).unwrap().count();
+    assert_eq!(files, 4);
+
+    // failing backup keeps the old key and cert, and no temporary files
+    fs::remove_file(&backup_key_path).unwrap();
+    fs::create_dir(&backup_key_path).unwrap();
+    let old_cert = fs::read(cert_path).unwrap();
+    let manager = CertManager::new(&CertConfig::new().paths(key_path, cert_path));
+    assert!(manager.rotate(None).is_err());
+    assert_eq!(fs::read(cert_path).unwrap(), old_cert);
+    let files = fs::read_dir(tmp_dir.path()).unwrap().count();
+    assert_eq!(files, 4);
 }
@@ -0,0 +1,392 @@
+//! Staking TLS keys held in a hardware security module (HSM) via PKCS#11.
+//!
+//! The private key never leaves the token: the certificate is signed
+//! on the token, so only the certificate is written to disk, and the
+//! node Id is derived from the certificate as usual.
+use std::{
+    collections::HashMap,
+    io::{self, Error, ErrorKind},
+    sync::{Mutex, OnceLock},
+};
+
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use rcgen::{Certificate, KeyPair, RemoteKeyPair, SignatureAlgorithm};
+use ring::digest::{digest, SHA256};
+
+/// DER-encoded OID of the NIST P-256 curve ("prime256v1"),
+/// the only curve "avalanchego" accepts for the ECDSA staking keys.
+const P256_EC_PARAMS: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+/// Length of the uncompressed P-256 point ("0x04 || x || y").
+const P256_POINT_LEN: usize = 65;
+
+/// Represents the PKCS#11 token and the key label to use.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Path to the PKCS#11 module (e.g., "/usr/lib/softhsm/libsofthsm2.so").
+    pub module_path: String,
+    /// Index of the slot among the slots with a token.
+    pub slot_index: usize,
+    /// User PIN to log in to the token.
+    pub pin: String,
+    /// Label of the key pair objects on the token.
+    pub label: String,
+}
+
+/// Represents the ECDSA P-256 staking key held in the token.
+pub struct Key {
+    session: Mutex<Session>,
+    private_key: ObjectHandle,
+    /// Uncompressed public key point.
+    public_key: Vec<u8>,
+}
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Key")
+            .field("private_key", &self.private_key)
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Key {
+    /// Generates a new key pair on the token with the configured label.
+    ///
+    /// # Errors
+    /// Returns error if the token fails to open or to generate the key pair,
+    /// or if a key with the label already exists
+    pub fn generate(cfg: &Config) -> io::Result<Self> {
+        let session = open_session(cfg)?;
+        if find_object(&session, ObjectClass::PRIVATE_KEY, &cfg.label)?.is_some() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("key '{}' already exists on the token", cfg.label),
+            ));
+        }
+
+        log::info!("generating ECDSA P-256 key '{}' on the token", cfg.label);
+        let public_template = vec![
+            Attribute::Token(true),
+            Attribute::KeyType(KeyType::EC),
+            Attribute::EcParams(P256_EC_PARAMS.to_vec()),
+            Attribute::Label(cfg.label.as_bytes().to_vec()),
+            Attribute::Verify(true),
+        ];
+        let private_template = vec![
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Label(cfg.label.as_bytes().to_vec()),
+            Attribute::Sign(true),
+        ];
+        let (public_key, private_key) = session
+            .generate_key_pair(
+                &Mechanism::EccKeyPairGen,
+                &public_template,
+                &private_template,
+            )
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed generate_key_pair {e}")))?;
+
+        let public_key = read_ec_point(&session, public_key)?;
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            public_key,
+        })
+    }
+
+    /// Loads the existing key pair with the configured label from the token.
+    ///
+    /// # Errors
+    /// Returns error if the token fails to open or the key pair is not found
+    pub fn load(cfg: &Config) -> io::Result<Self> {
+        let session = open_session(cfg)?;
+        let not_found = |class: &str| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("{class} key '{}' not found on the token", cfg.label),
+            )
+        };
+        let private_key = find_object(&session, ObjectClass::PRIVATE_KEY, &cfg.label)?
+            .ok_or_else(|| not_found("private"))?;
+        let public_key = find_object(&session, ObjectClass::PUBLIC_KEY, &cfg.label)?
+            .ok_or_else(|| not_found("public"))?;
+
+        let public_key = read_ec_point(&session, public_key)?;
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            public_key,
+        })
+    }
+}
+
+impl RemoteKeyPair for Key {
+    fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Signs the SHA256 digest of the message on the token
+    /// and returns the ASN.1 DER-encoded signature.
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, rcgen::Error> {
+        let hashed = digest(&SHA256, msg);
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| rcgen::Error::RemoteKeyError)?;
+        let sig = session
+            .sign(&Mechanism::Ecdsa, self.private_key, hashed.as_ref())
+            .map_err(|e| {
+                log::warn!("failed to sign on the token {e}");
+                rcgen::Error::RemoteKeyError
+            })?;
+        ecdsa_raw_to_der(&sig).map_err(|e| {
+            log::warn!("unexpected signature from the token {e}");
+            rcgen::Error::RemoteKeyError
+        })
+    }
+
+    fn algorithm(&self) -> &'static SignatureAlgorithm {
+        &rcgen::PKCS_ECDSA_P256_SHA256
+    }
+}
+
+/// Generates a X509 certificate signed by the key in the token
+/// and writes it as a PEM file. No private key file is written.
+///
+/// # Errors
+/// Returns error if the cert path exists, or if the signing or file operations fail
+pub fn generate_and_write_pem(key: Key, cert_path: &str) -> io::Result<()> {
+    log::info!("generating cert '{cert_path}' with the key in the token (PEM format)");
+    if std::path::Path::new(cert_path).exists() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("cert path '{cert_path}' already exists"),
+        ));
+    }
+
+    let mut params =
+        crate::x509::default_params(Some("PKCS_ECDSA_P256_SHA256".to_string()), None, false)?;
+    params.key_pair = Some(KeyPair::from_remote(Box::new(key)).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to load remote key pair {e}"),
+        )
+    })?);
+
+    let cert = Certificate::from_params(params).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to generate certificate {e}"),
+        )
+    })?;
+    let cert_contents = cert
+        .serialize_pem()
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize_pem {e}")))?;
+    std::fs::write(cert_path, cert_contents.as_bytes())?;
+    log::info!("saved cert '{cert_path}' ({}-byte)", cert_contents.len());
+
+    Ok(())
+}
+
+/// Returns the process-wide context of the PKCS#11 module, loaded and
+/// initialized on the first use. "C_Initialize" fails with
+/// "CKR_CRYPTOKI_ALREADY_INITIALIZED" while the module is initialized,
+/// so every session of a module (e.g., of a live [`Key`]) shares one context.
+fn context(module_path: &str) -> io::Result<Pkcs11> {
+    static CONTEXTS: OnceLock<Mutex<HashMap<String, Pkcs11>>> = OnceLock::new();
+
+    let mut contexts = CONTEXTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to lock contexts {e}")))?;
+    if let Some(pkcs11) = contexts.get(module_path) {
+        return Ok(pkcs11.clone());
+    }
+
+    let to_err = |e: cryptoki::error::Error| Error::new(ErrorKind::Other, format!("{e}"));
+    let pkcs11 = Pkcs11::new(module_path).map_err(to_err)?;
+    pkcs11
+        .initialize(CInitializeArgs::OsThreads)
+        .map_err(to_err)?;
+    contexts.insert(module_path.to_string(), pkcs11.clone());
+    Ok(pkcs11)
+}
+
+fn open_session(cfg: &Config) -> io::Result<Session> {
+    let to_err = |e: cryptoki::error::Error| Error::new(ErrorKind::Other, format!("{e}"));
+
+    let pkcs11 = context(&cfg.module_path)?;
+    let slots = pkcs11.get_slots_with_token().map_err(to_err)?;
+    let slot = slots.get(cfg.slot_index).copied().ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "slot index {} not found ({} slots with token)",
+                cfg.slot_index,
+                slots.len()
+            ),
+        )
+    })?;
+
+    let session = pkcs11.open_rw_session(slot).map_err(to_err)?;
+    session
+        .login(UserType::User, Some(&AuthPin::new(cfg.pin.clone())))
+        .map_err(to_err)?;
+    Ok(session)
+}
+
+fn find_object(
+    session: &Session,
+    class: ObjectClass,
+    label: &str,
+) -> io::Result<Option<ObjectHandle>> {
+    let found = session
+        .find_objects(&[
+            Attribute::Class(class),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed find_objects {e}")))?;
+    Ok(found.first().copied())
+}
+
+fn read_ec_point(session: &Session, public_key: ObjectHandle) -> io::Result<Vec<u8>> {
+    let attrs = session
+        .get_attributes(public_key, &[AttributeType::EcPoint])
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_attributes {e}")))?;
+    for attr in attrs {
+        if let Attribute::EcPoint(point) = attr {
+            return ec_point_from_der(&point);
+        }
+    }
+    Err(Error::new(
+        ErrorKind::NotFound,
+        "public key has no EC point",
+    ))
+}
+
+/// Unwraps the "CKA_EC_POINT" value, which is the uncompressed point
+/// in a DER OCTET STRING (some tokens return the raw point).
+fn ec_point_from_der(b: &[u8]) -> io::Result<Vec<u8>> {
+    match b.len() {
+        P256_POINT_LEN if b[0] == 0x04 => Ok(b.to_vec()),
+        // OCTET STRING tag and length, then the point
+        len if len == P256_POINT_LEN + 2 && b[0] == 0x04 && usize::from(b[1]) == P256_POINT_LEN => {
+            Ok(b[2..].to_vec())
+        }
+        len => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unexpected {len}-byte EC point"),
+        )),
+    }
+}
+
+/// Converts the PKCS#11 ECDSA signature ("r || s") to ASN.1 DER
+/// ("SEQUENCE { INTEGER r, INTEGER s }") as in X509 certificates.
+fn ecdsa_raw_to_der(sig: &[u8]) -> io::Result<Vec<u8>> {
+    if sig.is_empty() || sig.len() % 2 != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unexpected {}-byte signature", sig.len()),
+        ));
+    }
+    let (r, s) = sig.split_at(sig.len() / 2);
+    let (r, s) = (der_unsigned_integer(r), der_unsigned_integer(s));
+
+    let seq_len = r.len() + s.len();
+    let seq_len = u8::try_from(seq_len)
+        .ok()
+        .filter(|l| *l < 0x80)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "signature too long"))?;
+    let mut der = vec![0x30, seq_len];
+    der.extend_from_slice(&r);
+    der.extend_from_slice(&s);
+    Ok(der)
+}
+
+/// Encodes the big-endian unsigned integer in DER (minimal, non-negative).
+#[allow(clippy::cast_possible_truncation)]
+fn der_unsigned_integer(b: &[u8]) -> Vec<u8> {
+    let start = b.iter().position(|x| *x != 0).unwrap_or(b.len() - 1);
+    let b = &b[start..];
+    let pad = b[0] & 0x80 != 0;
+
+    let len = b.len() + usize::from(pad);
+    let mut der = vec![0x02, len as u8];
+    if pad {
+        der.push(0x00);
+    }
+    der.extend_from_slice(b);
+    der
+}
+
+/// `RUST_LOG=debug` cargo test --all-features --lib -- `pkcs11::test_encoding` --exact
+/// --show-output
+#[test]
+fn test_encoding() {
+    // high bit set, so padded with zero
+    let mut sig = vec![0x80; 32];
+    // leading zeros are stripped
+    sig.extend_from_slice(&[0x00, 0x00, 0x01]);
+    sig.extend_from_slice(&[0x02; 29]);
+    let der = ecdsa_raw_to_der(&sig).unwrap();
+    assert_eq!(&der[..5], &[0x30, 67, 0x02, 33, 0x00]);
+    assert_eq!(&der[5..37], &[0x80; 32]);
+    assert_eq!(&der[37..40], &[0x02, 30, 0x01]);
+    assert_eq!(der.len(), 2 + 35 + 32);
+    assert!(ecdsa_raw_to_der(&[0x01; 3]).is_err());
+
+    let mut point = vec![0x04];
+    point.extend_from_slice(&[0xab; 64]);
+    assert_eq!(ec_point_from_der(&point).unwrap(), point);
+    let mut wrapped = vec![0x04, 65];
+    wrapped.extend_from_slice(&point);
+    assert_eq!(ec_point_from_der(&wrapped).unwrap(), point);
+    assert!(ec_point_from_der(&point[..33]).is_err());
+}
+
+/// Requires a PKCS#11 token (e.g., SoftHSM) initialized with the user PIN:
+///
+/// `PKCS11_MODULE=/usr/lib/softhsm/libsofthsm2.so PKCS11_PIN=1234 RUST_LOG=debug`
+/// cargo test --all-features --lib -- `pkcs11::test_token` --exact --show-output
+#[test]
+fn test_token() {
+    let (Ok(module_path), Ok(pin)) = (std::env::var("PKCS11_MODULE"), std::env::var("PKCS11_PIN"))
+    else {
+        log::warn!("PKCS11_MODULE or PKCS11_PIN not set, skipping");
+        return;
+    };
+    let cfg = Config {
+        module_path,
+        slot_index: 0,
+        pin,
+        label: format!("staking-{}", random_manager::secure_string(8)),
+    };
+
+    // the sessions of the live keys share the initialized module
+    let key = Key::generate(&cfg).unwrap();
+    assert_eq!(
+        Key::generate(&cfg).unwrap_err().kind(),
+        ErrorKind::AlreadyExists
+    );
+    let loaded = Key::load(&cfg).unwrap();
+    assert_eq!(key.public_key, loaded.public_key);
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let cert_path = tmp_dir.path().join("staker.crt");
+    let cert_path = cert_path.to_str().unwrap();
+    generate_and_write_pem(loaded, cert_path).unwrap();
+
+    let cert = crate::x509::load_pem_cert_to_der(cert_path).unwrap();
+    let (_, parsed) = x509_parser::parse_x509_certificate(cert.as_ref()).unwrap();
+    let cert_pub_key: &[u8] = parsed.public_key().subject_public_key.data.as_ref();
+    assert_eq!(cert_pub_key, key.public_key.as_slice());
+}
@@ -1,6 +1,7 @@
 use std::{
     fs::{self, File},
     io::{self, BufReader, Error, ErrorKind, Read, Write},
+    net::IpAddr,
     path::Path,
 };
 
@@ -957,6 +958,243 @@ fn test_generate_der() {
     log::info!("cert: {} bytes", cert.len());
 }
 
+/// Represents the fields of a X509 certificate to sanity check
+/// (e.g., a staking certificate before deployment).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertInfo {
+    /// Subject distinguished name (e.g., "C=US, ST=NY, O=Test Org, CN=test common name").
+    pub subject: String,
+    /// Issuer distinguished name, same as the subject if self-signed.
+    pub issuer: String,
+    /// Serial number in big-endian bytes.
+    pub serial: Vec<u8>,
+    /// "`NotBefore`" in unix seconds.
+    pub not_before: i64,
+    /// "`NotAfter`" in unix seconds.
+    pub not_after: i64,
+    /// Subject alternative names (e.g., DNS names, IP addresses).
+    pub sans: Vec<String>,
+    pub is_ca: bool,
+}
+
+impl CertInfo {
+    /// Returns true if the certificate is valid at the unix timestamp (in seconds).
+    #[must_use]
+    pub const fn is_valid_at(&self, unix_secs: i64) -> bool {
+        self.not_before <= unix_secs && unix_secs <= self.not_after
+    }
+
+    /// Returns true if the subject is the issuer.
+    #[must_use]
+    pub fn is_self_signed(&self) -> bool {
+        self.subject == self.issuer
+    }
+}
+
+/// Parses the DER-encoded certificate.
+///
+/// # Errors
+/// Returns error if the certificate or its extensions fail to parse
+pub fn parse_der_cert_info(cert_der: &[u8]) -> io::Result<CertInfo> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert_der).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed parse_x509_certificate {e}"),
+        )
+    })?;
+
+    let mut sans = Vec::new();
+    let san = cert.subject_alternative_name().map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to parse subject alternative names {e}"),
+        )
+    })?;
+    if let Some(san) = san {
+        for name in &san.value.general_names {
+            let name = match name {
+                x509_parser::extensions::GeneralName::DNSName(s)
+                | x509_parser::extensions::GeneralName::RFC822Name(s)
+                | x509_parser::extensions::GeneralName::URI(s) => (*s).to_string(),
+                x509_parser::extensions::GeneralName::IPAddress(b) => match b.len() {
+                    4 => IpAddr::from(<[u8; 4]>::try_from(*b).unwrap_or_default()).to_string(),
+                    16 => IpAddr::from(<[u8; 16]>::try_from(*b).unwrap_or_default()).to_string(),
+                    _ => format!("{b:?}"),
+                },
+                other => format!("{other:?}"),
+            };
+            sans.push(name);
+        }
+    }
+
+    Ok(CertInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.serial.to_bytes_be(),
+        not_before: cert.validity().not_before.timestamp(),
+        not_after: cert.validity().not_after.timestamp(),
+        sans,
+        is_ca: cert.is_ca(),
+    })
+}
+
+/// Loads every certificate in the PEM-encoded file,
+/// in the file order (i.e., leaf first for a chain).
+///
+/// # Errors
+/// Returns error if file operations or certificate parsing fails,
+/// or if the file has no certificate
+pub fn load_pem_cert_chain_info(cert_path: &str) -> io::Result<Vec<CertInfo>> {
+    log::info!("loading PEM cert chain '{cert_path}'");
+    if !Path::new(cert_path).exists() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("cert path '{cert_path}' does not exists"),
+        ));
+    }
+
+    let cert_raw = read_vec(cert_path)?;
+    let mut infos = Vec::new();
+    for pem in x509_parser::pem::Pem::iter_from_buffer(&cert_raw) {
+        let pem =
+            pem.map_err(|e| Error::new(ErrorKind::InvalidData, format!("failed to read PEM {e}")))?;
+        if pem.label != "CERTIFICATE" {
+            log::warn!("cert path '{cert_path}' has unexpected '{}'", pem.label);
+            continue;
+        }
+        infos.push(parse_der_cert_info(&pem.contents)?);
+    }
+    if infos.is_empty() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("cert path '{cert_path}' found no cert"),
+        ));
+    }
+
+    Ok(infos)
+}
+
+/// Returns true if the private key is the pair of the certificate public key.
+/// Only supports PKCS8 keys (e.g., generated by this crate).
+///
+/// # Errors
+/// Returns error if the key or the certificate fails to parse
+pub fn key_matches_cert(key: &PrivateKeyDer, cert: &CertificateDer) -> io::Result<bool> {
+    let PrivateKeyDer::Pkcs8(key) = key else {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "only PKCS8 private keys are supported",
+        ));
+    };
+    let key_pair = KeyPair::from_der(key.secret_pkcs8_der()).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to load key pair {e}"),
+        )
+    })?;
+
+    let (_, parsed) = x509_parser::parse_x509_certificate(cert.as_ref()).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed parse_x509_certificate {e}"),
+        )
+    })?;
+    let cert_pub_key: &[u8] = parsed.public_key().subject_public_key.data.as_ref();
+
+    Ok(key_pair.public_key_raw() == cert_pub_key)
+}
+
+/// Makes sure the PEM-encoded key and certificate files are a pair
+/// (e.g., before deploying a staking key and certificate).
+///
+/// # Errors
+/// Returns error if the files fail to load, or if the key does not match
+/// the certificate public key
+pub fn verify_pem_key_cert_pair(key_path: &str, cert_path: &str) -> io::Result<()> {
+    let (key, cert) = load_pem_key_cert_to_der(key_path, cert_path)?;
+    if !key_matches_cert(&key, &cert)? {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("key '{key_path}' does not match the public key of cert '{cert_path}'"),
+        ));
+    }
+    log::info!("key '{key_path}' matches cert '{cert_path}'");
+    Ok(())
+}
+
+/// `RUST_LOG=debug` cargo test --all-features --lib -- `x509::test_inspect`
+/// --exact --show-output
+#[test]
+fn test_inspect() {
+    let _ = env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .is_test(true)
+        .try_init();
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| {
+        tmp_dir
+            .path()
+            .join(name)
+            .as_os_str()
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let mut params = default_params(None, Some("node.local".to_string()), false).unwrap();
+    params.subject_alt_names = vec![
+        rcgen::SanType::DnsName("node.local".to_string()),
+        rcgen::SanType::IpAddress(IpAddr::from([127, 0, 0, 1])),
+    ];
+    let (key_path, cert_path) = (path("1.key"), path("1.cert"));
+    generate_and_write_pem(Some(params), &key_path, &cert_path).unwrap();
+
+    let infos = load_pem_cert_chain_info(&cert_path).unwrap();
+    assert_eq!(infos.len(), 1);
+    let info = &infos[0];
+    log::info!("info: {info:?}");
+    assert!(info.subject.contains("CN=node.local"));
+    assert!(info.is_self_signed());
+    assert!(!info.is_ca);
+    assert_eq!(info.serial, load_pem_cert_serial(&cert_path).unwrap());
+    assert_eq!(info.not_before, 1_682_899_200); // 2023-05-01
+    assert!(info.is_valid_at(1_700_000_000));
+    assert!(!info.is_valid_at(1_600_000_000));
+    assert_eq!(
+        info.sans,
+        vec!["node.local".to_string(), "127.0.0.1".to_string()]
+    );
+
+    // matching pair
+    verify_pem_key_cert_pair(&key_path, &cert_path).unwrap();
+
+    // mismatched pair
+    let (other_key_path, other_cert_path) = (path("2.key"), path("2.cert"));
+    generate_and_write_pem(None, &other_key_path, &other_cert_path).unwrap();
+    assert!(verify_pem_key_cert_pair(&other_key_path, &cert_path).is_err());
+    let (key, _) = load_pem_key_cert_to_der(&key_path, &cert_path).unwrap();
+    let (_, other_cert) = load_pem_key_cert_to_der(&other_key_path, &other_cert_path).unwrap();
+    assert!(!key_matches_cert(&key, &other_cert).unwrap());
+
+    // leaf first, then the issuer
+    let ca = Ca::new("ca.hello.com").unwrap();
+    let csr_entity = CsrEntity::new("entity.hello.com").unwrap();
+    let mut chain = ca.issue_cert(&csr_entity.csr_pem).unwrap();
+    chain.push_str(&ca.cert.serialize_pem().unwrap());
+    let chain_path = path("chain.cert");
+    fs::write(&chain_path, chain).unwrap();
+
+    let infos = load_pem_cert_chain_info(&chain_path).unwrap();
+    assert_eq!(infos.len(), 2);
+    assert!(infos[0].subject.contains("CN=entity.hello.com"));
+    assert_eq!(infos[0].issuer, infos[1].subject);
+    assert!(infos[1].is_ca);
+    assert!(infos[1].is_self_signed());
+
+    assert!(load_pem_cert_chain_info(&key_path).is_err());
+}
+
 /// ref. <https://doc.rust-lang.org/std/fs/fn.read.html>
 fn read_vec(p: &str) -> io::Result<Vec<u8>> {
     let mut f = File::open(p)?;
@@ -0,0 +1,146 @@
+//! "balances" subcommand.
+use std::time::SystemTime;
+
+use avalanche_types::{
+    errors::{Error, Result},
+    jsonrpc::client::{evm as client_evm, p as client_p, x as client_x},
+    key::secp256k1,
+};
+use clap::Args;
+use primitive_types::{H160, U256};
+use serde::Serialize;
+
+/// Flags of the "balances" subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct Flags {
+    /// Keys to snapshot the balances of.
+    #[command(flatten)]
+    pub keys: crate::keys::KeyFlags,
+
+    /// Base HTTP URL of the node (e.g., "http://127.0.0.1:9650").
+    #[arg(long, default_value = "http://127.0.0.1:9650")]
+    pub http_rpc: String,
+
+    /// Network Id to derive the addresses for.
+    #[arg(long, default_value_t = 1)]
+    pub network_id: u32,
+}
+
+/// Balances of the keys at a point in time.
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    /// Base HTTP URL the balances were fetched from.
+    pub http_rpc: String,
+    /// Network Id of the addresses.
+    pub network_id: u32,
+    /// Unix timestamp in seconds when the snapshot was taken.
+    pub taken_at: u64,
+    /// Balances of each key, in the order of the flags.
+    pub balances: Vec<Balance>,
+}
+
+/// Balances of a single key across the X, P and C chains.
+#[derive(Debug, Clone, Serialize)]
+pub struct Balance {
+    /// Optional key identifier (e.g., AWS KMS Arn).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// "X-" address on the network.
+    pub x_address: String,
+    /// "P-" address on the network.
+    pub p_address: String,
+    /// C-chain EVM account address.
+    pub eth_address: String,
+
+    /// X-chain AVAX balance in nAVAX.
+    pub x: u64,
+    /// P-chain AVAX balance in nAVAX.
+    pub p: u64,
+    /// C-chain balance in wei.
+    pub c: U256,
+}
+
+/// Fetches the balances of the keys, and prints the snapshot.
+///
+/// # Errors
+///
+/// Returns an error if any key cannot be loaded or any balance cannot be fetched.
+pub async fn execute(flags: Flags) -> Result<()> {
+    let infos = crate::keys::load_infos(&flags.keys, flags.network_id).await?;
+
+    let taken_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("unexpected None duration_since")
+        .as_secs();
+    let c_rpc_ep = format!("{}/ext/bc/C/rpc", flags.http_rpc);
+
+    let balances = futures::future::try_join_all(
+        infos
+            .iter()
+            .map(|info| fetch_balance(&flags.http_rpc, &c_rpc_ep, flags.network_id, info)),
+    )
+    .await?;
+
+    crate::print_json(&Snapshot {
+        http_rpc: flags.http_rpc,
+        network_id: flags.network_id,
+        taken_at,
+        balances,
+    })
+}
+
+/// Fetches the X, P and C-chain balances of the key.
+async fn fetch_balance(
+    http_rpc: &str,
+    c_rpc_ep: &str,
+    network_id: u32,
+    info: &secp256k1::Info,
+) -> Result<Balance> {
+    let addrs = info
+        .addresses
+        .get(&network_id)
+        .ok_or_else(|| Error::UnexpectedNone(format!("addresses for network {network_id}")))?;
+
+    let (x, p, c) = tokio::try_join!(
+        fetch_x(http_rpc, &addrs.x),
+        fetch_p(http_rpc, &addrs.p),
+        fetch_c(c_rpc_ep, info.h160_address),
+    )?;
+    log::info!(
+        "fetched balances of {} (X {x}, P {p}, C {c})",
+        info.eth_address
+    );
+
+    Ok(Balance {
+        id: info.id.clone(),
+        x_address: addrs.x.clone(),
+        p_address: addrs.p.clone(),
+        eth_address: info.eth_address.clone(),
+        x,
+        p,
+        c,
+    })
+}
+
+/// Fetches the X-chain AVAX balance of the address.
+async fn fetch_x(http_rpc: &str, xaddr: &str) -> Result<u64> {
+    let resp = client_x::get_balance(http_rpc, xaddr).await?;
+    Ok(resp
+        .result
+        .ok_or_else(|| Error::UnexpectedNone("avm GetBalanceResult".to_string()))?
+        .balance)
+}
+
+/// Fetches the P-chain AVAX balance of the address.
+async fn fetch_p(http_rpc: &str, paddr: &str) -> Result<u64> {
+    let resp = client_p::get_balance(http_rpc, paddr).await?;
+    Ok(resp
+        .result
+        .ok_or_else(|| Error::UnexpectedNone("platformvm GetBalanceResult".to_string()))?
+        .balance)
+}
+
+/// Fetches the C-chain balance of the EVM account.
+async fn fetch_c(c_rpc_ep: &str, addr: H160) -> Result<U256> {
+    client_evm::get_balance(c_rpc_ep, addr).await
+}
@@ -0,0 +1,45 @@
+//! "create" subcommand.
+use std::collections::HashMap;
+
+use avalanche_types::{errors::Result, key::secp256k1::kms};
+use clap::Args;
+
+/// Flags of the "create" subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct Flags {
+    /// AWS region to create the keys in.
+    #[arg(long, default_value = "us-west-2")]
+    pub region: String,
+
+    /// Prefix of the "Name" tag, suffixed with the key index.
+    #[arg(long, default_value = "avalanche-kms")]
+    pub key_name_prefix: String,
+
+    /// Number of keys to create.
+    #[arg(long, default_value_t = 1)]
+    pub keys: usize,
+
+    /// Network Id to derive the addresses for.
+    #[arg(long, default_value_t = 1)]
+    pub network_id: u32,
+}
+
+/// Creates the KMS keys, and prints their info.
+///
+/// # Errors
+///
+/// Returns an error if any key cannot be created.
+pub async fn execute(flags: Flags) -> Result<()> {
+    let kms_manager = crate::keys::kms_manager(&flags.region).await;
+
+    let mut infos = Vec::with_capacity(flags.keys);
+    for i in 0..flags.keys {
+        let name = format!("{}-{i:02}", flags.key_name_prefix);
+        let tags = HashMap::from([("Name".to_string(), name.clone())]);
+
+        let key = kms::aws::Key::create(kms_manager.clone(), tags).await?;
+        log::info!("created KMS key '{name}' {}", key.arn);
+        infos.push(key.to_info(flags.network_id)?);
+    }
+    crate::print_json(&infos)
+}
@@ -0,0 +1,25 @@
+//! "info" subcommand.
+use avalanche_types::errors::Result;
+use clap::Args;
+
+/// Flags of the "info" subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct Flags {
+    /// Keys to list.
+    #[command(flatten)]
+    pub keys: crate::keys::KeyFlags,
+
+    /// Network Id to derive the addresses for.
+    #[arg(long, default_value_t = 1)]
+    pub network_id: u32,
+}
+
+/// Prints the info of the keys with the addresses on the network.
+///
+/// # Errors
+///
+/// Returns an error if any key cannot be loaded.
+pub async fn execute(flags: Flags) -> Result<()> {
+    let infos = crate::keys::load_infos(&flags.keys, flags.network_id).await?;
+    crate::print_json(&infos)
+}
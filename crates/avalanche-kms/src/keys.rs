@@ -0,0 +1,63 @@
+//! Loads the AWS KMS keys and the hot keys given by the flags.
+use avalanche_types::{
+    errors::Result,
+    key::secp256k1::{self, kms},
+};
+use aws_manager::kms as aws_kms;
+use tokio::time::Duration;
+
+/// Keys to load, shared by the subcommands.
+#[derive(Debug, Clone, clap::Args)]
+pub struct KeyFlags {
+    /// AWS region of the KMS keys.
+    #[arg(long, default_value = "us-west-2")]
+    pub region: String,
+
+    /// Arn or Id of the AWS KMS key (repeatable).
+    #[arg(long = "key-arn")]
+    pub key_arns: Vec<String>,
+
+    /// Hex-encoded private key (repeatable), for testing only.
+    #[arg(long = "private-key-hex")]
+    pub private_keys: Vec<String>,
+}
+
+/// Creates the AWS KMS API wrapper in the region.
+pub async fn kms_manager(region: &str) -> aws_kms::Manager {
+    let shared_config = aws_manager::load_config(
+        Some(region.to_string()),
+        None,
+        Some(Duration::from_secs(30)),
+    )
+    .await;
+    aws_kms::Manager::new(&shared_config)
+}
+
+/// Loads the info of the KMS keys followed by the hot keys,
+/// with the addresses on the network.
+///
+/// # Errors
+///
+/// Returns an error if any key cannot be loaded.
+pub async fn load_infos(flags: &KeyFlags, network_id: u32) -> Result<Vec<secp256k1::Info>> {
+    let mut infos = Vec::with_capacity(flags.key_arns.len() + flags.private_keys.len());
+
+    if !flags.key_arns.is_empty() {
+        let kms_manager = kms_manager(&flags.region).await;
+        for arn in &flags.key_arns {
+            let key = kms::aws::Key::from_arn(kms_manager.clone(), arn).await?;
+            infos.push(key.to_info(network_id)?);
+        }
+    }
+    for private_key in &flags.private_keys {
+        let key = secp256k1::private_key::Key::from_hex(private_key)?;
+        let mut info = key.to_info(network_id)?;
+        // never print the private key back
+        info.private_key_cb58 = None;
+        info.private_key_hex = None;
+        infos.push(info);
+    }
+
+    log::info!("loaded {} key(s)", infos.len());
+    Ok(infos)
+}
@@ -0,0 +1,97 @@
+//! Command-line tool to create AWS KMS keys, list the key addresses on
+//! a network, and snapshot the key balances across the X, P and C chains.
+mod balances;
+mod create;
+mod info;
+mod keys;
+
+use avalanche_types::errors::{Error, Result};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+/// Avalanche key and KMS utilities.
+#[derive(Debug, Parser)]
+#[command(name = "avalanche-kms", version, about)]
+struct Cli {
+    /// Log level (e.g., "debug", "info").
+    #[arg(long, default_value = "info")]
+    log_level: String,
+
+    /// Subcommand to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Subcommands of the CLI.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Creates AWS KMS secp256k1 keys, and prints their addresses.
+    Create(create::Flags),
+    /// Lists the addresses of the KMS and hot keys on the network.
+    Info(info::Flags),
+    /// Snapshots the balances of the keys across the X, P and C chains.
+    Balances(balances::Flags),
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // ref. <https://github.com/env-logger-rs/env_logger/issues/47>
+    env_logger::init_from_env(
+        env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, &cli.log_level),
+    );
+
+    let res = match cli.command {
+        Command::Create(flags) => create::execute(flags).await,
+        Command::Info(flags) => info::execute(flags).await,
+        Command::Balances(flags) => balances::execute(flags).await,
+    };
+    if let Err(e) = res {
+        eprintln!("failed to execute command '{e}'");
+        std::process::exit(1);
+    }
+}
+
+/// Prints the value as pretty JSON to stdout, so the output can be piped.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be serialized.
+fn print_json<T: Serialize>(v: &T) -> Result<()> {
+    let s = serde_json::to_string_pretty(v).map_err(|e| Error::Other {
+        message: format!("failed serde_json::to_string_pretty '{e}'"),
+        retryable: false,
+    })?;
+    println!("{s}");
+    Ok(())
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-kms --bin avalanche-kms -- `test_cli` --exact --show-output
+#[test]
+fn test_cli() {
+    use clap::CommandFactory;
+    Cli::command().debug_assert();
+
+    let cli = Cli::try_parse_from([
+        "avalanche-kms",
+        "balances",
+        "--http-rpc",
+        "http://127.0.0.1:9650",
+        "--private-key-hex",
+        "56289e99c94b6912bfc12adc093c9b51124f0dc54ac7a766b2bc5ccf558d8027",
+        "--key-arn",
+        "arn:aws:kms:us-west-2:123456789012:key/abc",
+    ])
+    .unwrap();
+    let Command::Balances(flags) = cli.command else {
+        panic!("unexpected command");
+    };
+    assert_eq!(flags.keys.private_keys.len(), 1);
+    assert_eq!(flags.keys.key_arns.len(), 1);
+    assert_eq!(flags.network_id, 1);
+
+    // no key to load
+    assert!(Cli::try_parse_from(["avalanche-kms", "create", "--keys", "0"]).is_ok());
+    assert!(Cli::try_parse_from(["avalanche-kms", "unknown"]).is_err());
+}
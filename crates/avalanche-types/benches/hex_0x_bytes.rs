@@ -0,0 +1,142 @@
+//! Compares deserializing a "platform.getTx"-style response with a multi-KB
+//! genesis blob through "`Hex0xBytes`" against the previous adapter, which
+//! copied the string into a "String" before decoding it into another buffer.
+//!
+//! cargo bench --package avalanche-types --bench hex_0x_bytes
+use std::borrow::Cow;
+
+use avalanche_types::{codec::serde::hex_0x_bytes::Hex0xBytes, jsonrpc::platformvm};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde::{Deserialize, Deserializer};
+use serde_with::{serde_as, DeserializeAs};
+
+/// The previous "`Hex0xBytes`" deserializer, as the baseline.
+struct LegacyHex0xBytes;
+
+impl<'de> DeserializeAs<'de, Vec<u8>> for LegacyHex0xBytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Cow<'de, str> as Deserialize<'de>>::deserialize(deserializer)
+            .and_then(|s| hex::decode(s.trim_start_matches("0x")).map_err(serde::de::Error::custom))
+    }
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Response {
+    result: TxResult,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TxResult {
+    #[serde_as(as = "Hex0xBytes")]
+    genesis_data: Vec<u8>,
+    #[serde_as(as = "Vec<Hex0xBytes>")]
+    signatures: Vec<Vec<u8>>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyResponse {
+    result: LegacyTxResult,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyTxResult {
+    #[serde_as(as = "LegacyHex0xBytes")]
+    genesis_data: Vec<u8>,
+    #[serde_as(as = "Vec<LegacyHex0xBytes>")]
+    signatures: Vec<Vec<u8>>,
+}
+
+/// Returns the response JSON with the genesis of the size and 16 signatures.
+fn response(genesis_size: usize) -> String {
+    let genesis: Vec<u8> = (0..genesis_size).map(|i| (i % 251) as u8).collect();
+    let signatures: Vec<String> = (0..16_u8)
+        .map(|i| format!("\"0x{}\"", hex::encode([i; 65])))
+        .collect();
+    format!(
+        "{{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{{\"genesisData\":\"0x{}\",\"signatures\":[{}]}}}}",
+        hex::encode(genesis),
+        signatures.join(",")
+    )
+}
+
+/// Returns the "platform.getTx" JSON response with the blob in the memo.
+fn get_tx_response(blob_size: usize) -> String {
+    let blob: Vec<u8> = (0..blob_size).map(|i| (i % 251) as u8).collect();
+    format!(
+        "{{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{{\"tx\":{{\"unsignedTx\":{{\"networkID\":1000000,\"blockchainID\":\"11111111111111111111111111111111LpoYY\",\"outputs\":[],\"inputs\":[],\"memo\":\"0x{}\",\"owner\":{{\"addresses\":[],\"locktime\":0,\"threshold\":1}}}},\"credentials\":[{{\"signatures\":[\"0x{}\"]}}]}},\"encoding\":\"json\"}}}}",
+        hex::encode(blob),
+        hex::encode([1_u8; 65])
+    )
+}
+
+fn bench_get_tx(c: &mut Criterion) {
+    let mut group = c.benchmark_group("platform.getTx");
+    for size in [4 * 1024, 64 * 1024] {
+        let resp = get_tx_response(size);
+        group.bench_with_input(BenchmarkId::new("GetTxResponse", size), &resp, |b, resp| {
+            b.iter(|| {
+                let r: platformvm::GetTxResponse = serde_json::from_str(black_box(resp)).unwrap();
+                black_box(r)
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("platform.getTx genesis");
+    for size in [4 * 1024, 64 * 1024] {
+        let resp = response(size);
+
+        group.bench_with_input(BenchmarkId::new("Hex0xBytes", size), &resp, |b, resp| {
+            b.iter(|| {
+                let r: Response = serde_json::from_str(black_box(resp)).unwrap();
+                black_box((r.result.genesis_data, r.result.signatures))
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("legacy", size), &resp, |b, resp| {
+            b.iter(|| {
+                let r: LegacyResponse = serde_json::from_str(black_box(resp)).unwrap();
+                black_box((r.result.genesis_data, r.result.signatures))
+            });
+        });
+
+        // readers never borrow, so the previous adapter always allocated the "String"
+        group.bench_with_input(
+            BenchmarkId::new("Hex0xBytes/from_reader", size),
+            &resp,
+            |b, resp| {
+                b.iter(|| {
+                    let r: Response = serde_json::from_reader(black_box(resp.as_bytes())).unwrap();
+                    black_box(r.result.genesis_data)
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("legacy/from_reader", size),
+            &resp,
+            |b, resp| {
+                b.iter(|| {
+                    let r: LegacyResponse =
+                        serde_json::from_reader(black_box(resp.as_bytes())).unwrap();
+                    black_box(r.result.genesis_data)
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_deserialize, bench_get_tx);
+criterion_main!(benches);
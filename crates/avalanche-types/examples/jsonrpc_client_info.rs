@@ -114,6 +114,13 @@ async fn main() -> io::Result<()> {
         serde_json::to_string_pretty(&resp).unwrap()
     );
 
+    println!();
+    let resp = jsonrpc_client_info::uptime(&url, None).await.unwrap();
+    log::info!(
+        "uptime response: {}",
+        serde_json::to_string_pretty(&resp).unwrap()
+    );
+
     println!();
     let resp = jsonrpc_client_info::peers(&url, None).await.unwrap();
     log::info!(
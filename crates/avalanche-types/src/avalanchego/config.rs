@@ -1,12 +1,12 @@
 //! `AvalancheGo` configuration.
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fs::{self, File},
     io::{self, Error, ErrorKind, Write},
     path::Path,
 };
 
-use crate::{avalanchego::genesis, constants, units};
+use crate::{avalanchego::genesis, constants, ids, units};
 use serde::{Deserialize, Serialize};
 
 /// Represents `AvalancheGo` configuration.
@@ -754,6 +754,273 @@ impl Config {
     }
 }
 
+/// Database backend of the node ("--db-type").
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DbType {
+    LevelDb,
+    MemDb,
+    PebbleDb,
+}
+
+/// Represents the `AvalancheGo` flags to launch a node with, either on the
+/// command line (see `to_args`) or in the config file (same keys in JSON).
+/// Unlike [`Config`], only the set flags are passed, so the node defaults
+/// apply to the rest. Use `extra` for the flags not modeled here.
+/// ref. <https://github.com/ava-labs/avalanchego/blob/master/config/keys.go>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Flags {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<u32>,
+    /// MUST BE NON-EMPTY for custom network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis_file: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_type: Option<DbType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_data_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_ip: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staking_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staking_tls_key_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staking_tls_cert_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staking_signer_key_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sybil_protection_enabled: Option<bool>,
+
+    /// Bootstrap beacon IPs (e.g., "127.0.0.1:9651"),
+    /// in the same order as `bootstrap_ids`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap_ips: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap_ids: Vec<ids::node::Id>,
+
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub track_subnets: BTreeSet<ids::Id>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet_config_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_config_dir: Option<String>,
+
+    /// Other flags by their names without "--" (e.g., "index-enabled").
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl Flags {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the flags are for a custom network
+    /// thus require a custom genesis file.
+    #[must_use]
+    pub fn is_custom_network(&self) -> bool {
+        self.network_id
+            .is_some_and(|network_id| network_id == 0 || network_id > 5)
+    }
+
+    /// Converts to the command line arguments (e.g., "--network-id=5"),
+    /// in the order of the flag names. The lists are comma-separated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flags fail to serialize.
+    pub fn to_args(&self) -> io::Result<Vec<String>> {
+        let v = serde_json::to_value(self)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize flags {e}")))?;
+        let serde_json::Value::Object(m) = v else {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "flags not serialized to an object",
+            ));
+        };
+
+        let mut flags = BTreeMap::new();
+        for (k, v) in m {
+            let v = match v {
+                serde_json::Value::String(s) => s,
+                serde_json::Value::Array(vs) => vs
+                    .into_iter()
+                    .map(|v| match v {
+                        serde_json::Value::String(s) => s,
+                        v => v.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+                v => v.to_string(),
+            };
+            flags.insert(k, v);
+        }
+        Ok(flags
+            .into_iter()
+            .map(|(k, v)| format!("--{k}={v}"))
+            .collect())
+    }
+
+    /// Converts to string with JSON encoder, to write as the config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails.
+    pub fn encode_json(&self) -> io::Result<String> {
+        serde_json::to_string(&self)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize JSON {e}")))
+    }
+
+    /// Validates the flags before launching the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flags are invalid.
+    pub fn validate(&self) -> io::Result<()> {
+        if self.is_custom_network() && self.genesis_file.is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "custom '--network-id={}' but empty '--genesis-file'",
+                    self.network_id.unwrap_or_default()
+                ),
+            ));
+        }
+        if !self.is_custom_network() && self.genesis_file.is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "non-empty '--genesis-file' for a known network",
+            ));
+        }
+
+        if self.staking_tls_key_file.is_some() != self.staking_tls_cert_file.is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "'--staking-tls-key-file' and '--staking-tls-cert-file' must be set together",
+            ));
+        }
+        if self.sybil_protection_enabled == Some(false) && self.is_mainnet_or_fuji() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "'--sybil-protection-enabled' must be true for mainnet and fuji",
+            ));
+        }
+
+        if self.bootstrap_ips.len() != self.bootstrap_ids.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} '--bootstrap-ips' != {} '--bootstrap-ids'",
+                    self.bootstrap_ips.len(),
+                    self.bootstrap_ids.len()
+                ),
+            ));
+        }
+        if self.http_port.is_some() && self.http_port == self.staking_port {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "'--http-port' and '--staking-port' must differ",
+            ));
+        }
+
+        for k in self.extra.keys() {
+            if k.starts_with('-') || k.contains('=') {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("invalid extra flag name '{k}'"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the network is mainnet or fuji.
+    fn is_mainnet_or_fuji(&self) -> bool {
+        matches!(
+            self.network_id,
+            Some(constants::MAINNET_NETWORK_ID | constants::FUJI_NETWORK_ID)
+        )
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features avalanchego -- `avalanchego::config::test_flags` --exact --show-output
+#[test]
+fn test_flags() {
+    use std::str::FromStr;
+
+    let node_id = ids::node::Id::from_str("NodeID-7Xhw2mDxuDS44j42TCB6U5579esbSt3Lg").unwrap();
+    let mut flags = Flags {
+        network_id: Some(1337),
+        genesis_file: Some("/data/genesis.json".to_string()),
+        db_type: Some(DbType::PebbleDb),
+        http_host: Some("0.0.0.0".to_string()),
+        http_port: Some(9650),
+        staking_port: Some(9651),
+        staking_tls_key_file: Some("/data/staking.key".to_string()),
+        staking_tls_cert_file: Some("/data/staking.crt".to_string()),
+        bootstrap_ips: vec!["127.0.0.1:9651".to_string()],
+        bootstrap_ids: vec![node_id],
+        track_subnets: BTreeSet::from([ids::Id::empty()]),
+        ..Flags::new()
+    };
+    flags
+        .extra
+        .insert("index-enabled".to_string(), "true".to_string());
+    flags.validate().unwrap();
+
+    assert_eq!(
+        flags.to_args().unwrap(),
+        vec![
+            format!("--bootstrap-ids={node_id}"),
+            "--bootstrap-ips=127.0.0.1:9651".to_string(),
+            "--db-type=pebbledb".to_string(),
+            "--genesis-file=/data/genesis.json".to_string(),
+            "--http-host=0.0.0.0".to_string(),
+            "--http-port=9650".to_string(),
+            "--index-enabled=true".to_string(),
+            "--network-id=1337".to_string(),
+            "--staking-port=9651".to_string(),
+            "--staking-tls-cert-file=/data/staking.crt".to_string(),
+            "--staking-tls-key-file=/data/staking.key".to_string(),
+            format!("--track-subnets={}", ids::Id::empty()),
+        ]
+    );
+
+    // round-trips as the config file
+    let decoded: Flags = serde_json::from_str(&flags.encode_json().unwrap()).unwrap();
+    assert_eq!(decoded, flags);
+
+    flags.genesis_file = None;
+    assert!(flags.validate().is_err());
+    flags.network_id = Some(constants::FUJI_NETWORK_ID);
+    flags.validate().unwrap();
+    flags.bootstrap_ids.clear();
+    assert!(flags.validate().is_err());
+}
+
 #[test]
 fn test_config() {
     use std::fs;
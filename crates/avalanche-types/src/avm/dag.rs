@@ -0,0 +1,166 @@
+//! In-memory DAG of the (pre-linearization) X-chain vertices, to replay the
+//! historical vertices and experiment with the avalanche consensus.
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    avm::txs::vertex::Vertex,
+    errors::{Error, Result},
+    ids::Id,
+};
+
+/// Stores the vertices whose parents are all known, by the vertex Id
+/// (SHA256 of the vertex bytes).
+#[derive(Debug, Clone, Default)]
+pub struct Dag {
+    vertices: HashMap<Id, Vertex>,
+    /// Vertices with no known children.
+    frontier: HashSet<Id>,
+}
+
+impl Dag {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses and adds the vertex bytes to the DAG, and returns the vertex Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vertex is invalid or cannot be added.
+    pub fn insert_bytes(&mut self, d: &[u8]) -> Result<Id> {
+        let vtx = Vertex::from_bytes(d)?;
+        let id = Id::sha256(d);
+        self.insert_with_id(id, vtx)?;
+        Ok(id)
+    }
+
+    /// Adds the vertex to the DAG, and returns the vertex Id.
+    /// No-op if already added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vertex is invalid, any parent is missing,
+    /// or the height is not above the parents.
+    pub fn insert(&mut self, mut vtx: Vertex) -> Result<Id> {
+        let id = Id::sha256(vtx.to_bytes()?);
+        vtx.verify()?;
+        self.insert_with_id(id, vtx)?;
+        Ok(id)
+    }
+
+    fn insert_with_id(&mut self, id: Id, vtx: Vertex) -> Result<()> {
+        if self.vertices.contains_key(&id) {
+            return Ok(());
+        }
+        for parent_id in &vtx.parent_ids {
+            let Some(parent) = self.vertices.get(parent_id) else {
+                return Err(Error::Other {
+                    message: format!("parent {parent_id} of vertex {id} not found"),
+                    retryable: true,
+                });
+            };
+            if parent.height >= vtx.height {
+                return Err(Error::Other {
+                    message: format!(
+                        "vertex {id} height {} not above parent height {}",
+                        vtx.height, parent.height
+                    ),
+                    retryable: false,
+                });
+            }
+        }
+
+        for parent_id in &vtx.parent_ids {
+            self.frontier.remove(parent_id);
+        }
+        self.frontier.insert(id);
+        self.vertices.insert(id, vtx);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &Id) -> Option<&Vertex> {
+        self.vertices.get(id)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Returns the vertices with no children, sorted by Id.
+    #[must_use]
+    pub fn frontier(&self) -> Vec<Id> {
+        let mut ids: Vec<Id> = self.frontier.iter().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Returns "true" if "ancestor" is reachable from "id" via the parents.
+    #[must_use]
+    pub fn is_ancestor(&self, ancestor: &Id, id: &Id) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![*id];
+        while let Some(cur) = stack.pop() {
+            let Some(vtx) = self.vertices.get(&cur) else {
+                continue;
+            };
+            for parent_id in &vtx.parent_ids {
+                if parent_id == ancestor {
+                    return true;
+                }
+                if visited.insert(*parent_id) {
+                    stack.push(*parent_id);
+                }
+            }
+        }
+        false
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::dag::test_dag` --exact --show-output
+#[test]
+fn test_dag() {
+    let chain_id = Id::from_slice(&[1; 32]);
+    let vtx = |height: u64, parent_ids: Vec<Id>, txs: Vec<Vec<u8>>| Vertex {
+        codec_version: crate::avm::txs::vertex::CODEC_VERSION,
+        chain_id,
+        height,
+        epoch: 0,
+        parent_ids,
+        txs,
+    };
+
+    let mut dag = Dag::new();
+    let mut genesis = vtx(0, Vec::new(), vec![vec![1]]);
+    let genesis_id = dag.insert_bytes(&genesis.to_bytes().unwrap()).unwrap();
+
+    let v1 = vtx(1, vec![genesis_id], vec![vec![2], vec![3]]);
+    let v2 = vtx(1, vec![genesis_id], vec![vec![4]]);
+    let v1_id = dag.insert(v1.clone()).unwrap();
+    let v2_id = dag.insert(v2).unwrap();
+    assert_eq!(dag.insert(v1).unwrap(), v1_id);
+    let mut expected = vec![v1_id, v2_id];
+    expected.sort();
+    assert_eq!(dag.frontier(), expected);
+
+    // missing parent, height not above the parent
+    assert!(dag
+        .insert(vtx(3, vec![Id::empty()], vec![vec![5]]))
+        .is_err());
+    assert!(dag.insert(vtx(1, vec![v1_id], vec![vec![5]])).is_err());
+
+    let stop = Vertex::new_stop_vertex(chain_id, 2, vec![v1_id, v2_id]);
+    let stop_id = dag.insert(stop).unwrap();
+    assert_eq!(dag.frontier(), vec![stop_id]);
+    assert_eq!(dag.len(), 4);
+    assert!(dag.is_ancestor(&genesis_id, &stop_id));
+    assert!(!dag.is_ancestor(&v1_id, &v2_id));
+    assert!(dag.get(&stop_id).unwrap().is_stop_vertex());
+}
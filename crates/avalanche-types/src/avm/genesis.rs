@@ -0,0 +1,260 @@
+//! Parses the X-chain genesis bytes back into the typed assets and
+//! their initial allocations, to verify a generated (e.g., custom network) genesis.
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Genesis>
+use std::collections::BTreeMap;
+
+use crate::{
+    codec,
+    errors::{Error, Result},
+    ids::{self, short},
+    key,
+    packer::Packer,
+};
+
+/// Represents the decoded "avm.Genesis".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Genesis {
+    pub assets: Vec<Asset>,
+}
+
+/// Represents the "avm.GenesisAsset", created with the embedded "`CreateAssetTx`".
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#GenesisAsset>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Asset {
+    pub alias: String,
+    pub network_id: u32,
+    pub blockchain_id: ids::Id,
+    pub memo: Vec<u8>,
+    pub name: String,
+    pub symbol: String,
+    pub denomination: u8,
+    pub initial_states: Vec<InitialState>,
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm/txs#InitialState>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InitialState {
+    pub fx_index: u32,
+    pub outputs: Vec<InitialOutput>,
+}
+
+/// Represents the initial output of the asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitialOutput {
+    /// "secp256k1fx.TransferOutput"
+    Transfer(key::secp256k1::txs::transfer::Output),
+    /// "secp256k1fx.MintOutput"
+    Mint(key::secp256k1::txs::OutputOwners),
+    /// "nftfx.MintOutput"
+    NftMint {
+        group_id: u32,
+        owners: key::secp256k1::txs::OutputOwners,
+    },
+    /// "nftfx.TransferOutput"
+    NftTransfer {
+        group_id: u32,
+        payload: Vec<u8>,
+        owners: key::secp256k1::txs::OutputOwners,
+    },
+}
+
+impl Genesis {
+    /// Decodes the X-chain genesis bytes (e.g., "`genesisBytes`" of the X-chain
+    /// "`CreateChainTx`" in the P-chain genesis).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid genesis.
+    pub fn from_bytes(d: &[u8]) -> Result<Self> {
+        let packer = Packer::load_bytes_for_unpack(d.len() + 1024, d);
+
+        codec::x_registry()?.unpack_version(&packer)?;
+
+        let n = packer.unpack_u32()?;
+        let mut assets = Vec::new();
+        for _ in 0..n {
+            assets.push(unpack_asset(&packer)?);
+        }
+        Ok(Self { assets })
+    }
+
+    /// Returns the asset of the alias (e.g., "AVAX").
+    #[must_use]
+    pub fn asset(&self, alias: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.alias == alias)
+    }
+}
+
+impl Asset {
+    /// Returns the initial transferable amounts by the owner addresses.
+    /// Multisig outputs are counted for every owner.
+    #[must_use]
+    pub fn allocations(&self) -> BTreeMap<short::Id, u64> {
+        let mut allocations: BTreeMap<short::Id, u64> = BTreeMap::new();
+        for state in &self.initial_states {
+            for output in &state.outputs {
+                let InitialOutput::Transfer(out) = output else {
+                    continue;
+                };
+                for addr in &out.output_owners.addresses {
+                    let amount = allocations.entry(addr.clone()).or_default();
+                    *amount = amount.saturating_add(out.amount);
+                }
+            }
+        }
+        allocations
+    }
+
+    /// Returns the total initial supply of the asset.
+    #[must_use]
+    pub fn initial_supply(&self) -> u64 {
+        self.initial_states
+            .iter()
+            .flat_map(|s| &s.outputs)
+            .fold(0, |total, output| match output {
+                InitialOutput::Transfer(out) => total.saturating_add(out.amount),
+                _ => total,
+            })
+    }
+}
+
+fn unpack_asset(packer: &Packer) -> Result<Asset> {
+    let alias = packer.unpack_str()?;
+
+    // "avm.BaseTx" embedded in "avm.CreateAssetTx"
+    let network_id = packer.unpack_u32()?;
+    let blockchain_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+    let n = packer.unpack_u32()?;
+    if n > 0 {
+        return Err(Error::Other {
+            message: format!("unexpected {n} outputs in genesis asset '{alias}'"),
+            retryable: false,
+        });
+    }
+    let n = packer.unpack_u32()?;
+    if n > 0 {
+        return Err(Error::Other {
+            message: format!("unexpected {n} inputs in genesis asset '{alias}'"),
+            retryable: false,
+        });
+    }
+    let memo = packer.unpack_bytes_with_header()?;
+
+    let name = packer.unpack_str()?;
+    let symbol = packer.unpack_str()?;
+    let denomination = packer.unpack_byte()?;
+
+    let n = packer.unpack_u32()?;
+    let mut initial_states = Vec::new();
+    for _ in 0..n {
+        let fx_index = packer.unpack_u32()?;
+        let outs = packer.unpack_u32()?;
+        let mut outputs = Vec::new();
+        for _ in 0..outs {
+            outputs.push(unpack_initial_output(packer)?);
+        }
+        initial_states.push(InitialState { fx_index, outputs });
+    }
+
+    Ok(Asset {
+        alias,
+        network_id,
+        blockchain_id,
+        memo,
+        name,
+        symbol,
+        denomination,
+        initial_states,
+    })
+}
+
+fn unpack_initial_output(packer: &Packer) -> Result<InitialOutput> {
+    let type_id = packer.unpack_u32()?;
+    let output = match type_id {
+        6 => InitialOutput::Mint(unpack_output_owners(packer)?),
+        7 => {
+            let amount = packer.unpack_u64()?;
+            InitialOutput::Transfer(key::secp256k1::txs::transfer::Output::new(
+                amount,
+                unpack_output_owners(packer)?,
+            ))
+        }
+        10 => InitialOutput::NftMint {
+            group_id: packer.unpack_u32()?,
+            owners: unpack_output_owners(packer)?,
+        },
+        11 => InitialOutput::NftTransfer {
+            group_id: packer.unpack_u32()?,
+            payload: packer.unpack_bytes_with_header()?,
+            owners: unpack_output_owners(packer)?,
+        },
+        _ => {
+            return Err(Error::Other {
+                message: format!("unsupported type ID for initial output {type_id}"),
+                retryable: false,
+            })
+        }
+    };
+    Ok(output)
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners>
+fn unpack_output_owners(packer: &Packer) -> Result<key::secp256k1::txs::OutputOwners> {
+    let locktime = packer.unpack_u64()?;
+    let threshold = packer.unpack_u32()?;
+    let n = packer.unpack_u32()?;
+    let mut addresses = Vec::new();
+    for _ in 0..n {
+        addresses.push(short::Id::from_slice(&packer.unpack_bytes(short::LEN)?));
+    }
+    Ok(key::secp256k1::txs::OutputOwners {
+        locktime,
+        threshold,
+        addresses,
+    })
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::genesis::test_genesis` --exact --show-output
+#[test]
+fn test_genesis() {
+    let addr1 = short::Id::from_slice(&[1; 20]);
+    let addr2 = short::Id::from_slice(&[2; 20]);
+
+    let p = Packer::new((1 << 31) - 1, 128);
+    p.pack_u16(codec::VERSION).unwrap();
+    p.pack_u32(1).unwrap();
+    p.pack_str("AVAX").unwrap();
+    p.pack_u32(12345).unwrap();
+    p.pack_bytes(&[9; 32]).unwrap();
+    p.pack_u32(0).unwrap();
+    p.pack_u32(0).unwrap();
+    p.pack_bytes_with_header(&[]).unwrap();
+    p.pack_str("Avalanche").unwrap();
+    p.pack_str("AVAX").unwrap();
+    p.pack_byte(9).unwrap();
+
+    p.pack_u32(1).unwrap();
+    p.pack_u32(0).unwrap();
+    p.pack_u32(3).unwrap();
+    for (amount, addr) in [(100_u64, &addr1), (200, &addr2), (300, &addr1)] {
+        p.pack_u32(7).unwrap();
+        p.pack_u64(amount).unwrap();
+        p.pack_u64(0).unwrap();
+        p.pack_u32(1).unwrap();
+        p.pack_u32(1).unwrap();
+        p.pack_bytes(addr.as_ref()).unwrap();
+    }
+
+    let genesis = Genesis::from_bytes(&p.take_bytes()).unwrap();
+    let avax = genesis.asset("AVAX").unwrap();
+    assert_eq!(avax.name, "Avalanche");
+    assert_eq!(avax.denomination, 9);
+    assert_eq!(avax.network_id, 12345);
+    assert_eq!(avax.initial_supply(), 600);
+
+    let allocations = avax.allocations();
+    assert_eq!(allocations.get(&addr1), Some(&400));
+    assert_eq!(allocations.get(&addr2), Some(&200));
+    assert!(genesis.asset("FOO").is_none());
+}
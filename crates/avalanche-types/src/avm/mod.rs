@@ -1,2 +1,4 @@
 //! Transaction types for the Avalanche Virtual Machine.
+pub mod dag;
+pub mod genesis;
 pub mod txs;
@@ -0,0 +1,262 @@
+//! Decoding of the X-chain transaction bytes (e.g., to scan the accepted
+//! transactions, or to review a transaction before signing it offline).
+use crate::{
+    codec,
+    errors::{Error, Result},
+    ids::{self, short},
+    packer::Packer,
+};
+
+/// Represents the transfer output of a decoded transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    pub asset_id: ids::Id,
+    pub amount: u64,
+    pub locktime: u64,
+    pub threshold: u32,
+    pub addresses: Vec<short::Id>,
+}
+
+/// Represents the transfer input of a decoded transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    /// UTXO consumed ("tx ID", "output index").
+    pub utxo: (ids::Id, u32),
+    pub asset_id: ids::Id,
+    pub amount: u64,
+    /// Indices of the UTXO owner addresses that sign the input.
+    pub sig_indices: Vec<u32>,
+}
+
+/// Represents the balance-affecting parts of an X-chain transaction.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm/txs#BaseTx>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DecodedTx {
+    /// Codec version the transaction was packed with.
+    pub codec_version: u16,
+    /// Type ID of the unsigned transaction (e.g., "avm.BaseTx" is 0).
+    pub type_id: u32,
+    pub network_id: u32,
+    pub blockchain_id: ids::Id,
+    /// Inputs consuming the UTXOs of this chain.
+    pub inputs: Vec<Input>,
+    /// Transfer outputs in the order of the output index.
+    /// "None" for the outputs other than "secp256k1fx.TransferOutput",
+    /// to keep the output indices.
+    pub outputs: Vec<Option<Output>>,
+    /// Destination chain of the exported outputs ("avm.ExportTx" only).
+    pub destination_chain_id: Option<ids::Id>,
+    /// Outputs exported to the destination chain ("avm.ExportTx" only).
+    pub exported_outputs: Vec<Option<Output>>,
+    /// Source chain of the imported inputs ("avm.ImportTx" only).
+    pub source_chain_id: Option<ids::Id>,
+    /// Inputs consuming the UTXOs in the shared memory ("avm.ImportTx" only),
+    /// signed after the inputs.
+    pub imported_inputs: Vec<Input>,
+}
+
+/// Decodes the signed (or unsigned) X-chain transaction bytes, up to the credentials.
+/// The outputs of the "avm.CreateAssetTx" initial states and the "avm.OperationTx"
+/// operations are not decoded.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm/txs#Tx>
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a valid transaction.
+pub fn decode_tx(d: &[u8]) -> Result<DecodedTx> {
+    let packer = Packer::load_bytes_for_unpack(d.len() + 1024, d);
+
+    let codec_version = codec::x_registry()?.unpack_version(&packer)?;
+    let type_id = packer.unpack_u32()?;
+    if type_id > 4 {
+        return Err(Error::Other {
+            message: format!("unknown type ID for avm unsigned tx {type_id}"),
+            retryable: false,
+        });
+    }
+
+    // "avm.BaseTx" embedded in all unsigned txs
+    let network_id = packer.unpack_u32()?;
+    let blockchain_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+    let outputs = unpack_outputs(&packer)?;
+    let inputs = unpack_inputs(&packer)?;
+    let _memo = packer.unpack_bytes_with_header()?;
+
+    let mut tx = DecodedTx {
+        codec_version,
+        type_id,
+        network_id,
+        blockchain_id,
+        inputs,
+        outputs,
+        ..Default::default()
+    };
+    match type_id {
+        // "avm.ImportTx" consumes the UTXOs in the shared memory, not of this chain
+        3 => {
+            let chain_id = packer.unpack_bytes(ids::LEN)?;
+            tx.source_chain_id = Some(ids::Id::from_slice(&chain_id));
+            tx.imported_inputs = unpack_inputs(&packer)?;
+        }
+        // "avm.ExportTx"
+        4 => {
+            let chain_id = packer.unpack_bytes(ids::LEN)?;
+            tx.destination_chain_id = Some(ids::Id::from_slice(&chain_id));
+            tx.exported_outputs = unpack_outputs(&packer)?;
+        }
+        _ => {}
+    }
+    Ok(tx)
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput>
+fn unpack_outputs(packer: &Packer) -> Result<Vec<Option<Output>>> {
+    let n = packer.unpack_u32()?;
+    let mut outputs = Vec::new();
+    for _ in 0..n {
+        let asset_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+        let type_id = packer.unpack_u32()?;
+        let output = match type_id {
+            // "secp256k1fx.TransferOutput"
+            7 => {
+                let amount = packer.unpack_u64()?;
+                let (locktime, threshold, addresses) = unpack_output_owners(packer)?;
+                Some(Output {
+                    asset_id,
+                    amount,
+                    locktime,
+                    threshold,
+                    addresses,
+                })
+            }
+            // "secp256k1fx.MintOutput", "nftfx.MintOutput", "nftfx.TransferOutput"
+            6 | 10 | 11 => {
+                if type_id != 6 {
+                    let _group_id = packer.unpack_u32()?;
+                }
+                if type_id == 11 {
+                    let _payload = packer.unpack_bytes_with_header()?;
+                }
+                unpack_output_owners(packer)?;
+                None
+            }
+            _ => {
+                return Err(Error::Other {
+                    message: format!("unsupported type ID for output {type_id}"),
+                    retryable: false,
+                })
+            }
+        };
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners>
+fn unpack_output_owners(packer: &Packer) -> Result<(u64, u32, Vec<short::Id>)> {
+    let locktime = packer.unpack_u64()?;
+    let threshold = packer.unpack_u32()?;
+    let n = packer.unpack_u32()?;
+    let mut addresses = Vec::new();
+    for _ in 0..n {
+        addresses.push(short::Id::from_slice(&packer.unpack_bytes(short::LEN)?));
+    }
+    Ok((locktime, threshold, addresses))
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput>
+fn unpack_inputs(packer: &Packer) -> Result<Vec<Input>> {
+    let n = packer.unpack_u32()?;
+    let mut inputs = Vec::new();
+    for _ in 0..n {
+        let tx_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+        let output_index = packer.unpack_u32()?;
+        let asset_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+
+        // "secp256k1fx.TransferInput"
+        let type_id = packer.unpack_u32()?;
+        if type_id != 5 {
+            return Err(Error::Other {
+                message: format!("unsupported type ID for input {type_id}"),
+                retryable: false,
+            });
+        }
+        let amount = packer.unpack_u64()?;
+        let n = packer.unpack_u32()?;
+        let mut sig_indices = Vec::new();
+        for _ in 0..n {
+            sig_indices.push(packer.unpack_u32()?);
+        }
+        inputs.push(Input {
+            utxo: (tx_id, output_index),
+            asset_id,
+            amount,
+            sig_indices,
+        });
+    }
+    Ok(inputs)
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::txs::decode::test_decode_tx` --exact --show-output
+#[test]
+fn test_decode_tx() {
+    use crate::{key, txs};
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let addr = short::Id::from_slice(&[1; 20]);
+    let asset_id = ids::Id::from_slice(&[9; 32]);
+    let mut tx = crate::avm::txs::import::Tx::new(txs::Tx {
+        network_id: 5,
+        blockchain_id: ids::Id::from_slice(&[3; 32]),
+        transferable_outputs: Some(vec![txs::transferable::Output {
+            asset_id,
+            transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                amount: 90,
+                output_owners: key::secp256k1::txs::OutputOwners {
+                    locktime: 0,
+                    threshold: 1,
+                    addresses: vec![addr.clone()],
+                },
+            }),
+            ..Default::default()
+        }]),
+        transferable_inputs: Some(Vec::new()),
+        memo: Some(Vec::new()),
+        ..Default::default()
+    });
+    tx.source_chain_id = ids::Id::from_slice(&[4; 32]);
+    tx.source_chain_transferable_inputs = Some(vec![txs::transferable::Input {
+        utxo_id: txs::utxo::Id::new(&[2; 32], 1, false).unwrap(),
+        asset_id,
+        transfer_input: Some(key::secp256k1::txs::transfer::Input {
+            amount: 100,
+            sig_indices: vec![0],
+        }),
+        ..Default::default()
+    }]);
+    ab!(tx.sign(Vec::<Vec<key::secp256k1::private_key::Key>>::new())).unwrap();
+
+    let decoded = decode_tx(&tx.base_tx.metadata.unwrap().tx_bytes_with_no_signature).unwrap();
+    assert_eq!(decoded.type_id, 3);
+    assert_eq!(decoded.network_id, 5);
+    assert_eq!(decoded.blockchain_id, ids::Id::from_slice(&[3; 32]));
+    assert!(decoded.inputs.is_empty());
+    assert_eq!(decoded.outputs[0].as_ref().unwrap().addresses, vec![addr]);
+    assert_eq!(decoded.source_chain_id, Some(ids::Id::from_slice(&[4; 32])));
+    assert_eq!(
+        decoded.imported_inputs,
+        vec![Input {
+            utxo: (ids::Id::from_slice(&[2; 32]), 1),
+            asset_id,
+            amount: 100,
+            sig_indices: vec![0],
+        }]
+    );
+
+    assert!(decode_tx(&[0, 0, 0, 0, 0, 9]).is_err());
+}
@@ -1,10 +1,5 @@
 //! Base export transaction type.
-use crate::{
-    avm::txs::fx,
-    codec,
-    errors::{Error, Result},
-    hash, ids, key, platformvm, txs,
-};
+use crate::{avm::txs::fx, codec, errors::Result, hash, ids, key, txs};
 use serde::{Deserialize, Serialize};
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx>
@@ -68,156 +63,81 @@ impl Tx {
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx>
     ///
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
-    /// # Panics
-    /// 当 Option 解包失败时 panic。
     /// # Errors
     /// 序列化、签名等失败时返回错误。
-    #[allow(clippy::too_many_lines)]
     pub async fn sign<T: key::secp256k1::SignOnly + Sync>(
         &mut self,
         signers: Vec<Vec<T>>,
     ) -> Result<()> {
-        // marshal "unsigned tx" with the codec version
-        let type_id = Self::type_id();
-        let packer = self.base_tx.pack(codec::VERSION, type_id)?;
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
 
-        // "avalanchego" marshals the whole struct again for signed bytes
-        // even when the underlying "unsigned_tx" is already once marshaled
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx
-        //
-        // reuse the underlying packer to avoid marshaling the unsigned tx twice
-        // just marshal the next fields in the struct and pack them all together
-        // in the existing packer
-        let b = packer.take_bytes();
-        packer.set_bytes(&b);
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::x_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
+
+            // "avalanchego" marshals the whole struct again for signed bytes
+            // even when the underlying "unsigned_tx" is already once marshaled
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx
+            //
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let b = packer.take_bytes();
+            packer.set_bytes(&b);
 
-        // pack the second field in the struct
-        packer.pack_bytes(self.destination_chain_id.as_ref())?;
+            // pack the second field in the struct
+            packer.pack_bytes(self.destination_chain_id.as_ref())?;
 
-        // pack the third field in the struct
-        if self.destination_chain_transferable_outputs.is_some() {
+            // pack the third field in the struct
             let destination_chain_transferable_outputs = self
                 .destination_chain_transferable_outputs
-                .as_ref()
-                .expect("Option is None in test_export_tx_serialization_with_two_signers");
+                .as_deref()
+                .unwrap_or_default();
             let outputs_len = u32::try_from(destination_chain_transferable_outputs.len())?;
             packer.pack_u32(outputs_len)?;
 
             for transferable_output in destination_chain_transferable_outputs {
-                // "TransferableOutput.Asset" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableOutput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#Asset
-                packer.pack_bytes(transferable_output.asset_id.as_ref())?;
-
-                // fx_id is serialize:"false" thus skipping serialization
-
-                // decide the type
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                if transferable_output.transfer_output.is_none()
-                    && transferable_output.stakeable_lock_out.is_none()
-                {
-                    return Err(Error::Other {
-                        message: "unexpected Nones in TransferableOutput transfer_output and \
-                                  stakeable_lock_out"
-                            .to_string(),
-                        retryable: false,
-                    });
-                }
-                let type_id_transferable_out = {
-                    if transferable_output.transfer_output.is_some() {
-                        key::secp256k1::txs::transfer::Output::type_id()
-                    } else {
-                        platformvm::txs::StakeableLockOut::type_id()
-                    }
-                };
-                // marshal type ID for "key::secp256k1::txs::transfer::Output" or
-                // "platformvm::txs::StakeableLockOut"
-                packer.pack_u32(type_id_transferable_out)?;
-
-                match type_id_transferable_out {
-                    7 => {
-                        // "key::secp256k1::txs::transfer::Output"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        let transfer_output = transferable_output.transfer_output.clone().unwrap();
-
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(transfer_output.amount)?;
-
-                        // "secp256k1fx.TransferOutput.OutputOwners" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferOutput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        packer.pack_u64(transfer_output.output_owners.locktime)?;
-                        packer.pack_u32(transfer_output.output_owners.threshold)?;
-                        let addrs_len =
-                            u32::try_from(transfer_output.output_owners.addresses.len())?;
-                        packer.pack_u32(addrs_len)?;
-                        for addr in transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    22 => {
-                        // "platformvm::txs::StakeableLockOut"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        let stakeable_lock_out =
-                            transferable_output.stakeable_lock_out.clone().unwrap();
-
-                        // marshal "platformvm::txs::StakeableLockOut.locktime" field
-                        packer.pack_u64(stakeable_lock_out.locktime)?;
-
-                        // secp256k1fx.TransferOutput type ID
-                        packer.pack_u32(7)?;
-
-                        // "platformvm.StakeableLockOut.TransferOutput" is struct and
-                        // serialize:"true" but embedded inline in the
-                        // struct "StakeableLockOut" so no need to encode
-                        // type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        //
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(stakeable_lock_out.transfer_output.amount)?;
-                        packer
-                            .pack_u64(stakeable_lock_out.transfer_output.output_owners.locktime)?;
-                        packer
-                            .pack_u32(stakeable_lock_out.transfer_output.output_owners.threshold)?;
-                        let addrs_len = u32::try_from(
-                            stakeable_lock_out
-                                .transfer_output
-                                .output_owners
-                                .addresses
-                                .len(),
-                        )?;
-                        packer.pack_u32(addrs_len)?;
-                        for addr in stakeable_lock_out.transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Other {
-                            message: format!("unexpected type ID {type_id_transferable_out} for TransferableOutput"),
-                            retryable: false,
-                        });
-                    }
-                }
+                transferable_output.pack_into(&packer, &registry, codec_version)?;
             }
-        } else {
-            packer.pack_u32(0_u32)?;
-        }
 
-        // take bytes just for hashing computation
-        let tx_bytes_with_no_signature = packer.take_bytes();
-        packer.set_bytes(&tx_bytes_with_no_signature);
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
 
-        // compute sha256 for marshaled "unsigned tx" bytes
-        // IMPORTANT: take the hash only for the type "avm.ExportTx" unsigned tx
-        // not other fields -- only hash "avm.ExportTx.*" but not "avm.Tx.Creds"
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#ExportTx
-        let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "avm.ExportTx" unsigned tx
+            // not other fields -- only hash "avm.ExportTx.*" but not "avm.Tx.Creds"
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#ExportTx
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
 
         // number of of credentials
         let fx_creds_len = u32::try_from(signers.len())?;
@@ -226,28 +146,18 @@ impl Tx {
 
         // sign the hash with the signers (in case of multi-sig)
         // and combine all signatures into a secp256k1fx credential
-        self.fx_creds = Vec::new();
-        for keys in signers {
-            let mut sigs: Vec<Vec<u8>> = Vec::new();
-            for k in keys {
-                let sig = k.sign_digest(&tx_bytes_hash).await?;
-                sigs.push(Vec::from(sig));
-            }
-
-            let cred = key::secp256k1::txs::Credential { signatures: sigs };
-
-            let fx_cred = fx::Credential {
-                cred,
-                ..Default::default()
-            };
-
-            // add a new credential to "Tx"
-            self.fx_creds.push(fx_cred);
-        }
+        self.fx_creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?
+                .into_iter()
+                .map(|cred| fx::Credential {
+                    cred,
+                    ..Default::default()
+                })
+                .collect();
         if fx_creds_len > 0 {
             // pack each "fx_cred" which is "secp256k1fx.Credential"
             // marshal type ID for "secp256k1fx.Credential"
-            let cred_type_id = key::secp256k1::txs::Credential::type_id();
             for fx_cred in &self.fx_creds {
                 packer.pack_u32(cred_type_id)?;
                 let sigs_len = u32::try_from(fx_cred.cred.signatures.len())?;
@@ -268,6 +178,8 @@ impl Tx {
             tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
             tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
         });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
 
         Ok(())
     }
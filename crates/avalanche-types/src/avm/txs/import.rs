@@ -1,10 +1,5 @@
 //! Base import transaction type.
-use crate::{
-    avm::txs::fx,
-    codec,
-    errors::{Error, Result},
-    hash, ids, key, platformvm, txs,
-};
+use crate::{avm::txs::fx, codec, errors::Result, hash, ids, key, txs};
 use serde::{Deserialize, Serialize};
 
 /// AVM Import Transaction
@@ -68,154 +63,81 @@ impl Tx {
 
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx>
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
-    /// # Panics
-    /// 当 Option 解包失败时 panic。
     /// # Errors
     /// 序列化、签名等失败时返回错误。
-    #[allow(clippy::too_many_lines)]
     pub async fn sign<T: key::secp256k1::SignOnly + Sync>(
         &mut self,
         signers: Vec<Vec<T>>,
     ) -> Result<()> {
-        // marshal "unsigned tx" with the codec version
-        let type_id = Self::type_id();
-        let packer = self.base_tx.pack(codec::VERSION, type_id)?;
-
-        // "avalanchego" marshals the whole struct again for signed bytes
-        // even when the underlying "unsigned_tx" is already once marshaled
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx
-        //
-        // reuse the underlying packer to avoid marshaling the unsigned tx twice
-        // just marshal the next fields in the struct and pack them all together
-        // in the existing packer
-        let b = packer.take_bytes();
-        packer.set_bytes(&b);
-
-        // pack the second field in the struct
-        packer.pack_bytes(self.source_chain_id.as_ref())?;
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
 
-        // pack the third field in the struct
-        if self.source_chain_transferable_inputs.is_some() {
-            let source_chain_transferable_inputs =
-                self.source_chain_transferable_inputs.as_ref().unwrap();
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::x_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
+
+            // "avalanchego" marshals the whole struct again for signed bytes
+            // even when the underlying "unsigned_tx" is already once marshaled
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx.SignSECP256K1Fx
+            //
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let b = packer.take_bytes();
+            packer.set_bytes(&b);
+
+            // pack the second field in the struct
+            packer.pack_bytes(self.source_chain_id.as_ref())?;
+
+            // pack the third field in the struct
+            let source_chain_transferable_inputs = self
+                .source_chain_transferable_inputs
+                .as_deref()
+                .unwrap_or_default();
             let inputs_len = u32::try_from(source_chain_transferable_inputs.len())?;
             packer.pack_u32(inputs_len)?;
 
             for transferable_input in source_chain_transferable_inputs {
-                // "TransferableInput.UTXOID" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableInput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#UTXOID
-                packer.pack_bytes(transferable_input.utxo_id.tx_id.as_ref())?;
-                packer.pack_u32(transferable_input.utxo_id.output_index)?;
-
-                // "TransferableInput.Asset" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableInput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#Asset
-                packer.pack_bytes(transferable_input.asset_id.as_ref())?;
-
-                // fx_id is serialize:"false" thus skipping serialization
-
-                // decide the type
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                if transferable_input.transfer_input.is_none()
-                    && transferable_input.stakeable_lock_in.is_none()
-                {
-                    return Err(Error::Other {
-                        message: "unexpected Nones in TransferableInput transfer_input and \
-                                  stakeable_lock_in"
-                            .to_string(),
-                        retryable: false,
-                    });
-                }
-
-                let type_id_transferable_in = {
-                    if transferable_input.transfer_input.is_some() {
-                        key::secp256k1::txs::transfer::Input::type_id()
-                    } else {
-                        platformvm::txs::StakeableLockIn::type_id()
-                    }
-                };
-
-                // marshal type ID for "key::secp256k1::txs::transfer::Input" or
-                // "platformvm::txs::StakeableLockIn"
-                packer.pack_u32(type_id_transferable_in)?;
-
-                match type_id_transferable_in {
-                    5 => {
-                        // "key::secp256k1::txs::transfer::Input"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        let transfer_input = transferable_input.transfer_input.clone().unwrap();
-
-                        // marshal "secp256k1fx.TransferInput.Amt" field
-                        packer.pack_u64(transfer_input.amount)?;
-
-                        // "secp256k1fx.TransferInput.Input" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferInput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        let indices_len = u32::try_from(transfer_input.sig_indices.len())?;
-                        packer.pack_u32(indices_len)?;
-                        for idx in transfer_input.sig_indices {
-                            packer.pack_u32(idx)?;
-                        }
-                    }
-                    21 => {
-                        // "platformvm::txs::StakeableLockIn"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockIn
-                        let stakeable_lock_in =
-                            transferable_input.stakeable_lock_in.clone().unwrap();
-
-                        // marshal "platformvm::txs::StakeableLockIn.locktime" field
-                        packer.pack_u64(stakeable_lock_in.locktime)?;
-
-                        // "platformvm.StakeableLockIn.TransferableIn" is struct and
-                        // serialize:"true" but embedded inline in the
-                        // struct "StakeableLockIn" so no need to encode
-                        // type ID ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockIn
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        //
-                        // marshal "secp256k1fx.TransferInput.Amt" field
-                        packer.pack_u64(stakeable_lock_in.transfer_input.amount)?;
-                        //
-                        // "secp256k1fx.TransferInput.Input" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferInput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        let indices_len =
-                            u32::try_from(stakeable_lock_in.transfer_input.sig_indices.len())?;
-                        packer.pack_u32(indices_len)?;
-                        for idx in stakeable_lock_in.transfer_input.sig_indices {
-                            packer.pack_u32(idx)?;
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Other {
-                            message: format!("unexpected type ID {type_id_transferable_in} for TransferableInput"),
-                            retryable: false,
-                        });
-                    }
-                }
+                transferable_input.pack_into(&packer, &registry, codec_version)?;
             }
-        } else {
-            packer.pack_u32(0_u32)?;
-        }
-
-        // take bytes just for hashing computation
-        let tx_bytes_with_no_signature = packer.take_bytes();
-        packer.set_bytes(&tx_bytes_with_no_signature);
 
-        // compute sha256 for marshaled "unsigned tx" bytes
-        // IMPORTANT: take the hash only for the type "avm.ImportTx" unsigned tx
-        // not other fields -- only hash "avm.ImportTx.*" but not "avm.Tx.Creds"
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#ImportTx
-        let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
+
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "avm.ImportTx" unsigned tx
+            // not other fields -- only hash "avm.ImportTx.*" but not "avm.Tx.Creds"
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#ImportTx
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
 
         // number of of credentials
         let fx_creds_len = u32::try_from(signers.len())?;
@@ -224,28 +146,18 @@ impl Tx {
 
         // sign the hash with the signers (in case of multi-sig)
         // and combine all signatures into a secp256k1fx credential
-        self.fx_creds = Vec::new();
-        for keys in signers {
-            let mut sigs: Vec<Vec<u8>> = Vec::new();
-            for k in keys {
-                let sig = k.sign_digest(&tx_bytes_hash).await?;
-                sigs.push(Vec::from(sig));
-            }
-
-            let cred = key::secp256k1::txs::Credential { signatures: sigs };
-
-            let fx_cred = fx::Credential {
-                cred,
-                ..Default::default()
-            };
-
-            // add a new credential to "Tx"
-            self.fx_creds.push(fx_cred);
-        }
+        self.fx_creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?
+                .into_iter()
+                .map(|cred| fx::Credential {
+                    cred,
+                    ..Default::default()
+                })
+                .collect();
         if fx_creds_len > 0 {
             // pack each "fx_cred" which is "secp256k1fx.Credential"
             // marshal type ID for "secp256k1fx.Credential"
-            let cred_type_id = key::secp256k1::txs::Credential::type_id();
             for fx_cred in &self.fx_creds {
                 packer.pack_u32(cred_type_id)?;
                 let sigs_len = u32::try_from(fx_cred.cred.signatures.len())?;
@@ -266,6 +178,8 @@ impl Tx {
             tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
             tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
         });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
 
         Ok(())
     }
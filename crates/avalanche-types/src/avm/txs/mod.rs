@@ -1,10 +1,12 @@
 //! Base transactions.
+pub mod decode;
 pub mod export;
 pub mod fx;
 pub mod import;
+pub mod partial;
 pub mod vertex;
 
-use crate::{codec, errors::Result, hash, ids, key, txs};
+use crate::{codec, errors::Result, hash, ids, key, packer, txs};
 use serde::{Deserialize, Serialize};
 
 /// Base transaction.
@@ -80,9 +82,31 @@ impl Tx {
         &mut self,
         signers: Vec<Vec<T>>,
     ) -> Result<()> {
-        // marshal "unsigned tx" with the codec version
-        let type_id = Self::type_id();
-        let packer = self.base_tx.pack(codec::VERSION, type_id)?;
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    #[allow(clippy::future_not_send)]
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let packer = {
+            let registry = codec::x_registry()?;
+            let type_id =
+                registry.resolve_type_id(self.base_tx.codec_version, &Self::type_name())?;
+            self.base_tx
+                .pack(&registry, self.base_tx.codec_version, type_id)?
+        };
 
         // "avalanchego" marshals the whole struct again for signed bytes
         // even when the underlying "unsigned_tx" is already once marshaled
@@ -100,44 +124,18 @@ impl Tx {
         // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/avm#Tx
         let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
 
-        // number of of credentials
-        let fx_creds_len = u32::try_from(signers.len())?;
-        // pack the second field in the struct
-        packer.pack_u32(fx_creds_len)?;
-
         // sign the hash with the signers (in case of multi-sig)
         // and combine all signatures into a secp256k1fx credential
-        self.fx_creds = Vec::new();
-        for keys in &signers {
-            let mut sigs: Vec<Vec<u8>> = Vec::new();
-            for k in keys {
-                let sig = k.sign_digest(&tx_bytes_hash).await?;
-                sigs.push(Vec::from(sig));
-            }
-
-            let cred = key::secp256k1::txs::Credential { signatures: sigs };
-
-            let fx_cred = fx::Credential {
-                cred,
-                ..Default::default()
-            };
-
-            // add a new credential to "Tx"
-            self.fx_creds.push(fx_cred);
-        }
-        if fx_creds_len > 0 {
-            // pack each "fx_cred" which is "secp256k1fx.Credential"
-            // marshal type ID for "secp256k1fx.Credential"
-            let cred_type_id = key::secp256k1::txs::Credential::type_id();
-            for fx_cred in &self.fx_creds {
-                packer.pack_u32(cred_type_id)?;
-                let sigs_len = u32::try_from(fx_cred.cred.signatures.len())?;
-                packer.pack_u32(sigs_len)?;
-                for sig in &fx_cred.cred.signatures {
-                    packer.pack_bytes(sig)?;
-                }
-            }
-        }
+        self.fx_creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?
+                .into_iter()
+                .map(|cred| fx::Credential {
+                    cred,
+                    ..Default::default()
+                })
+                .collect();
+        Self::pack_credentials(&packer, self.base_tx.codec_version, &self.fx_creds)?;
         let tx_bytes_with_signatures = packer.take_bytes();
         let tx_id = hash::sha256(&tx_bytes_with_signatures);
 
@@ -149,11 +147,84 @@ impl Tx {
             tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
             tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
         });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
+
+        Ok(())
+    }
+
+    /// Sets the credentials signed elsewhere (e.g., by the co-signers),
+    /// and updates the metadata with the signed bytes and the transaction ID.
+    /// The credentials must be in the same order as the inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to pack.
+    pub fn set_credentials(&mut self, fx_creds: Vec<fx::Credential>) -> Result<()> {
+        let packer = {
+            let registry = codec::x_registry()?;
+            let type_id =
+                registry.resolve_type_id(self.base_tx.codec_version, &Self::type_name())?;
+            self.base_tx
+                .pack(&registry, self.base_tx.codec_version, type_id)?
+        };
+        let tx_bytes_with_no_signature = packer.take_bytes();
+        packer.set_bytes(&tx_bytes_with_no_signature);
+
+        self.fx_creds = fx_creds;
+        Self::pack_credentials(&packer, self.base_tx.codec_version, &self.fx_creds)?;
+        let tx_bytes_with_signatures = packer.take_bytes();
+        let tx_id = hash::sha256(&tx_bytes_with_signatures);
+
+        self.base_tx.metadata = Some(txs::Metadata {
+            id: ids::Id::from_slice(&tx_id),
+            tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
+            tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
+        });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
+        Ok(())
+    }
+
+    /// Packs the credentials after the unsigned tx ("avm.Tx.Creds"),
+    /// with the type Id of the codec version.
+    pub(crate) fn pack_credentials(
+        packer: &packer::Packer,
+        codec_version: u16,
+        fx_creds: &[fx::Credential],
+    ) -> Result<()> {
+        // number of of credentials
+        packer.pack_u32(u32::try_from(fx_creds.len())?)?;
 
+        // pack each "fx_cred" which is "secp256k1fx.Credential"
+        // marshal type ID for "secp256k1fx.Credential"
+        let cred_type_id = codec::x_registry()?
+            .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+        for fx_cred in fx_creds {
+            packer.pack_u32(cred_type_id)?;
+            let sigs_len = u32::try_from(fx_cred.cred.signatures.len())?;
+            packer.pack_u32(sigs_len)?;
+            for sig in &fx_cred.cred.signatures {
+                packer.pack_bytes(sig)?;
+            }
+        }
         Ok(())
     }
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::txs::test_sign_is_send` --exact --show-output
+#[test]
+fn test_sign_is_send() {
+    use crate::key::secp256k1::private_key::Key;
+
+    // the registry lock must not be held across the remote signing
+    fn is_send<T: Send>(_: T) {}
+
+    is_send(Tx::default().sign::<Key>(vec![]));
+    is_send(export::Tx::default().sign::<Key>(vec![]));
+    is_send(import::Tx::default().sign::<Key>(vec![]));
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::txs::test_tx_serialization_with_two_signers` --exact --show-output
 /// ref. "avalanchego/vms/avm.TestBaseTxSerialization"
 #[test]
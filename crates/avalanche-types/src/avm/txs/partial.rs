@@ -0,0 +1,322 @@
+//! Partially signed transactions for co-signing the multisig inputs.
+use crate::{
+    avm::txs::{decode, fx, Tx},
+    codec::{self, serde::hex_0x_bytes::Hex0xBytes},
+    errors::{Error, Result},
+    hash,
+    ids::{self, short},
+    key, packer, txs,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Represents the transaction whose inputs need signatures from multiple
+/// parties (e.g., UTXOs owned by threshold > 1 output owners, or the keys
+/// kept on an air-gapped machine). Each party signs the inputs with its own
+/// keys, and passes the JSON-encoded transaction to the next signer. Once all
+/// signatures are collected, "`finalize`" returns the signed transaction to issue.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PartiallySignedTx {
+    /// Codec-marshaled unsigned transaction that every signer signs
+    /// (e.g., "avm.BaseTx", "avm.ImportTx" or "avm.ExportTx").
+    #[serde_as(as = "Hex0xBytes")]
+    pub unsigned_bytes: Vec<u8>,
+    /// Addresses expected to sign each input (the imported inputs after the inputs),
+    /// in the order of the signature indices.
+    pub signers: Vec<Vec<short::Id>>,
+    /// Signatures of each input in the same order as "signers",
+    /// empty if not signed yet.
+    #[serde_as(as = "Vec<Vec<Hex0xBytes>>")]
+    pub signatures: Vec<Vec<Vec<u8>>>,
+}
+
+impl PartiallySignedTx {
+    /// Creates the unsigned "avm.BaseTx".
+    /// The signers must be in the same order and of the same length as the inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to pack or the signers do not match the inputs.
+    pub fn new(base_tx: txs::Tx, signers: Vec<Vec<short::Id>>) -> Result<Self> {
+        let registry = codec::x_registry()?;
+        let type_id = registry.resolve_type_id(base_tx.codec_version, &Tx::type_name())?;
+        let packer = base_tx.pack(&registry, base_tx.codec_version, type_id)?;
+        drop(registry);
+        Self::from_unsigned_bytes(packer.take_bytes().to_vec(), signers)
+    }
+
+    /// Creates the transaction from the codec-marshaled unsigned transaction
+    /// of any type (e.g., "`txs::Metadata.tx_bytes_with_no_signature`").
+    /// The signers must be in the same order and of the same length as the inputs,
+    /// followed by the imported inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid transaction
+    /// or the signers do not match the inputs.
+    pub fn from_unsigned_bytes(
+        unsigned_bytes: Vec<u8>,
+        signers: Vec<Vec<short::Id>>,
+    ) -> Result<Self> {
+        let signatures = signers
+            .iter()
+            .map(|addrs| vec![Vec::new(); addrs.len()])
+            .collect();
+        let tx = Self {
+            unsigned_bytes,
+            signers,
+            signatures,
+        };
+        tx.validate()?;
+        Ok(tx)
+    }
+
+    /// Checks the signers match the inputs of the unsigned transaction,
+    /// and the signatures match the signers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid transaction
+    /// or the signers do not match the inputs.
+    pub fn validate(&self) -> Result<()> {
+        let decoded = self.decode()?;
+        let inputs: Vec<&decode::Input> = decoded
+            .inputs
+            .iter()
+            .chain(&decoded.imported_inputs)
+            .collect();
+        if inputs.len() != self.signers.len() {
+            return Err(Error::Other {
+                message: format!(
+                    "{} inputs but {} signers, should be same",
+                    inputs.len(),
+                    self.signers.len()
+                ),
+                retryable: false,
+            });
+        }
+        for (input, addrs) in inputs.iter().zip(&self.signers) {
+            if input.sig_indices.len() != addrs.len() {
+                return Err(Error::Other {
+                    message: format!(
+                        "input {} has {} signature indices but {} signers",
+                        input.utxo.0,
+                        input.sig_indices.len(),
+                        addrs.len()
+                    ),
+                    retryable: false,
+                });
+            }
+        }
+
+        let matched = self.signers.len() == self.signatures.len()
+            && self
+                .signers
+                .iter()
+                .zip(&self.signatures)
+                .all(|(addrs, sigs)| addrs.len() == sigs.len());
+        if !matched {
+            return Err(Error::Other {
+                message: "signatures do not match the signers".to_string(),
+                retryable: false,
+            });
+        }
+        Ok(())
+    }
+
+    /// Decodes the unsigned transaction (e.g., to review it before signing).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid transaction.
+    pub fn decode(&self) -> Result<decode::DecodedTx> {
+        decode::decode_tx(&self.unsigned_bytes)
+    }
+
+    /// Returns the SHA256 digest of the unsigned transaction that every signer signs.
+    #[must_use]
+    pub fn digest(&self) -> Vec<u8> {
+        hash::sha256(&self.unsigned_bytes)
+    }
+
+    /// Signs the inputs that expect the signatures from the keys,
+    /// and returns the number of the new signatures.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signing fails.
+    #[allow(clippy::future_not_send)]
+    pub async fn sign<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        keys: &[T],
+    ) -> Result<usize> {
+        let digest = self.digest();
+
+        let mut signed = 0;
+        for k in keys {
+            let addr = k.short_address()?;
+            for (addrs, sigs) in self.signers.iter().zip(self.signatures.iter_mut()) {
+                for (expected, sig) in addrs.iter().zip(sigs.iter_mut()) {
+                    if *expected != addr || !sig.is_empty() {
+                        continue;
+                    }
+                    *sig = Vec::from(k.sign_digest(&digest).await?);
+                    signed += 1;
+                }
+            }
+        }
+        log::info!(
+            "signed {signed} signature(s), {} missing",
+            self.missing_signers().len()
+        );
+        Ok(signed)
+    }
+
+    /// Returns the addresses whose signatures are still missing, deduplicated.
+    #[must_use]
+    pub fn missing_signers(&self) -> Vec<short::Id> {
+        let mut missing = Vec::new();
+        for (addrs, sigs) in self.signers.iter().zip(&self.signatures) {
+            for (addr, sig) in addrs.iter().zip(sigs) {
+                if sig.is_empty() && !missing.contains(addr) {
+                    missing.push(addr.clone());
+                }
+            }
+        }
+        missing.sort();
+        missing
+    }
+
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.signatures.iter().flatten().all(|sig| !sig.is_empty())
+    }
+
+    /// Encodes the transaction as JSON, to pass it to the next signer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| Error::Other {
+            message: format!("failed serde_json::to_string '{e}'"),
+            retryable: false,
+        })
+    }
+
+    /// Decodes the transaction from the JSON of "`to_json`".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or the signers do not match the inputs.
+    pub fn from_json(d: &str) -> Result<Self> {
+        let tx: Self = serde_json::from_str(d).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_str '{e}'"),
+            retryable: false,
+        })?;
+        tx.validate()?;
+        Ok(tx)
+    }
+
+    /// Returns the signed transaction bytes and Id with all signatures collected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any signature is missing.
+    pub fn finalize(&self) -> Result<txs::Metadata> {
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            return Err(Error::Other {
+                message: format!("missing signatures from {missing:?}"),
+                retryable: false,
+            });
+        }
+
+        let fx_creds: Vec<fx::Credential> = self
+            .signatures
+            .iter()
+            .map(|sigs| fx::Credential {
+                cred: key::secp256k1::txs::Credential {
+                    signatures: sigs.clone(),
+                },
+                ..Default::default()
+            })
+            .collect();
+
+        // ref. "math.MaxInt32" and "constants.DefaultByteSliceCap" in Go
+        let packer = packer::Packer::new((1 << 31) - 1, 128);
+        packer.set_bytes(&self.unsigned_bytes);
+        Tx::pack_credentials(&packer, self.decode()?.codec_version, &fx_creds)?;
+        let tx_bytes_with_signatures = packer.take_bytes().to_vec();
+        Ok(txs::Metadata {
+            id: ids::Id::from_slice(&hash::sha256(&tx_bytes_with_signatures)),
+            tx_bytes_with_no_signature: self.unsigned_bytes.clone(),
+            tx_bytes_with_signatures,
+        })
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `avm::txs::partial::test_partially_signed_tx` --exact --show-output
+#[test]
+fn test_partially_signed_tx() {
+    use crate::key::secp256k1::ReadOnly;
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let k1 = key::secp256k1::private_key::Key::generate().unwrap();
+    let k2 = key::secp256k1::private_key::Key::generate().unwrap();
+    let (addr1, addr2) = (k1.short_address().unwrap(), k2.short_address().unwrap());
+
+    let base_tx = txs::Tx {
+        network_id: 10,
+        blockchain_id: ids::Id::from_slice(&[1]),
+        transferable_inputs: Some(vec![txs::transferable::Input {
+            utxo_id: txs::utxo::Id::new(&[2; 32], 0, false).unwrap(),
+            asset_id: ids::Id::from_slice(&[3]),
+            transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                amount: 100,
+                sig_indices: vec![0, 1],
+            }),
+            ..Default::default()
+        }]),
+        transferable_outputs: Some(Vec::new()),
+        memo: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert!(PartiallySignedTx::new(base_tx.clone(), vec![vec![addr1.clone()]]).is_err());
+
+    let mut ptx =
+        PartiallySignedTx::new(base_tx.clone(), vec![vec![addr1.clone(), addr2.clone()]]).unwrap();
+    assert_eq!(ab!(ptx.sign(&[k1.clone()])).unwrap(), 1);
+    assert!(!ptx.is_complete());
+    assert_eq!(ptx.missing_signers(), vec![addr2.clone()]);
+    assert!(ptx.finalize().is_err());
+
+    // pass to the co-signer
+    let mut ptx = PartiallySignedTx::from_json(&ptx.to_json().unwrap()).unwrap();
+    assert_eq!(ab!(ptx.sign(&[k1.clone()])).unwrap(), 0);
+    assert_eq!(ab!(ptx.sign(&[k2.clone()])).unwrap(), 1);
+    assert!(ptx.is_complete());
+
+    // the transaction is packed with its codec version
+    assert!(PartiallySignedTx::new(
+        txs::Tx {
+            codec_version: 3,
+            ..base_tx.clone()
+        },
+        vec![vec![addr1, addr2]],
+    )
+    .is_err());
+
+    // same as signing with both keys at once
+    let signed = ptx.finalize().unwrap();
+    let mut expected = Tx::new(base_tx);
+    ab!(expected.sign(vec![vec![k1, k2]])).unwrap();
+    assert_eq!(signed, expected.base_tx.metadata.unwrap());
+}
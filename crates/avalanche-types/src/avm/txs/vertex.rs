@@ -1,5 +1,21 @@
 //! Vertex types used in the Avalanche X-chain.
-use crate::{errors::Result, ids, packer::Packer, txs::raw};
+use crate::{
+    errors::{Error, Result},
+    ids,
+    packer::Packer,
+    txs::raw,
+};
+
+/// Codec version of the vertex with transactions.
+pub const CODEC_VERSION: u16 = 0;
+/// Codec version of the "stop vertex" that linearizes the DAG,
+/// which serializes no epoch and no transactions.
+pub const CODEC_VERSION_WITH_STOP_VTX: u16 = 1;
+
+/// ref. "avalanchego/snow/engine/avalanche/vertex.maxNumParents"
+pub const MAX_NUM_PARENTS: usize = 128;
+/// ref. "avalanchego/snow/engine/avalanche/vertex.maxTxsPerVtx"
+pub const MAX_TXS_PER_VTX: usize = 128;
 
 /// Vertex represents a set of transactions for Avalanche X-chain.
 ///
@@ -14,6 +30,115 @@ pub struct Vertex {
     pub txs: Vec<Vec<u8>>,
 }
 
+impl Vertex {
+    /// Creates the stop vertex that linearizes the DAG.
+    #[must_use]
+    pub fn new_stop_vertex(chain_id: ids::Id, height: u64, parent_ids: Vec<ids::Id>) -> Self {
+        Self {
+            codec_version: CODEC_VERSION_WITH_STOP_VTX,
+            chain_id,
+            height,
+            epoch: 0,
+            parent_ids,
+            txs: Vec::new(),
+        }
+    }
+
+    /// Returns "true" if this is the stop vertex.
+    #[must_use]
+    pub const fn is_stop_vertex(&self) -> bool {
+        self.codec_version == CODEC_VERSION_WITH_STOP_VTX
+    }
+
+    /// Packs the vertex in the canonical order (sorts the parents and transactions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization fails.
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>> {
+        let packer = Packer::new((1 << 31) - 1, 128);
+        packer.pack_vertex(self)?;
+        Ok(packer.take_bytes().to_vec())
+    }
+
+    /// Unpacks and verifies the vertex bytes (e.g., historical X-chain vertices).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid vertex.
+    pub fn from_bytes(d: &[u8]) -> Result<Self> {
+        let packer = Packer::load_bytes_for_unpack(d.len() + 1024, d);
+        let vtx = packer.unpack_vertex()?;
+        if packer.get_offset() != d.len() {
+            return Err(Error::Other {
+                message: format!(
+                    "vertex has {} trailing bytes",
+                    d.len().saturating_sub(packer.get_offset())
+                ),
+                retryable: false,
+            });
+        }
+        vtx.verify()?;
+        Ok(vtx)
+    }
+
+    /// Verifies the vertex is well-formed: zero epoch, sorted unique parents,
+    /// and sorted unique transactions (none for the stop vertex).
+    ///
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/engine/avalanche/vertex#StatelessVertex>
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vertex is not well-formed.
+    pub fn verify(&self) -> Result<()> {
+        let invalid = |msg: String| Error::Other {
+            message: format!("invalid vertex: {msg}"),
+            retryable: false,
+        };
+
+        match self.codec_version {
+            CODEC_VERSION => {
+                if self.txs.is_empty() {
+                    return Err(invalid("no transactions".to_string()));
+                }
+            }
+            CODEC_VERSION_WITH_STOP_VTX => {
+                if !self.txs.is_empty() {
+                    return Err(invalid("stop vertex has transactions".to_string()));
+                }
+            }
+            v => return Err(invalid(format!("unknown codec version {v}"))),
+        }
+        if self.epoch != 0 {
+            return Err(invalid(format!("non-zero epoch {}", self.epoch)));
+        }
+        if self.parent_ids.len() > MAX_NUM_PARENTS {
+            return Err(invalid(format!("{} parents", self.parent_ids.len())));
+        }
+        if !self.parent_ids.windows(2).all(|w| w[0] < w[1]) {
+            return Err(invalid("parents not sorted and unique".to_string()));
+        }
+        if self.txs.len() > MAX_TXS_PER_VTX {
+            return Err(invalid(format!("{} transactions", self.txs.len())));
+        }
+        let txs: Vec<raw::Data> = self
+            .txs
+            .iter()
+            .map(|tx| raw::Data::from_slice(tx))
+            .collect();
+        if !txs.windows(2).all(|w| w[0] < w[1]) {
+            return Err(invalid("transactions not sorted and unique".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Returns the Ids of the transactions (SHA256 of each transaction).
+    #[must_use]
+    pub fn tx_ids(&self) -> Vec<ids::Id> {
+        self.txs.iter().map(ids::Id::sha256).collect()
+    }
+}
+
 impl Packer {
     /// Encodes vertex fields with codec version and packer.
     ///
@@ -36,7 +161,11 @@ impl Packer {
         self.pack_u16(vtx.codec_version)?;
         self.pack_bytes(vtx.chain_id.as_ref())?;
         self.pack_u64(vtx.height)?;
-        self.pack_u32(vtx.epoch)?;
+        // the stop vertex has no epoch and no txs
+        let with_txs = vtx.codec_version != CODEC_VERSION_WITH_STOP_VTX;
+        if with_txs {
+            self.pack_u32(vtx.epoch)?;
+        }
 
         let parent_ids_len = u32::try_from(vtx.parent_ids.len())?;
         self.pack_u32(parent_ids_len)?;
@@ -44,10 +173,12 @@ impl Packer {
             self.pack_bytes(id.as_ref())?;
         }
 
-        let txs_len = u32::try_from(vtx.txs.len())?;
-        self.pack_u32(txs_len)?;
-        for tx in &vtx.txs {
-            self.pack_bytes_with_header(tx.as_ref())?;
+        if with_txs {
+            let txs_len = u32::try_from(vtx.txs.len())?;
+            self.pack_u32(txs_len)?;
+            for tx in &vtx.txs {
+                self.pack_bytes_with_header(tx.as_ref())?;
+            }
         }
 
         Ok(())
@@ -67,9 +198,16 @@ impl Packer {
         let chain_id = ids::Id::from_slice(chain_id.as_ref());
 
         let height = self.unpack_u64()?;
-        let epoch = self.unpack_u32()?;
+        let with_txs = codec_version != CODEC_VERSION_WITH_STOP_VTX;
+        let epoch = if with_txs { self.unpack_u32()? } else { 0 };
 
         let parent_ids_size = self.unpack_u32()?;
+        if parent_ids_size as usize > MAX_NUM_PARENTS {
+            return Err(Error::Other {
+                message: format!("vertex has too many parents {parent_ids_size}"),
+                retryable: false,
+            });
+        }
         let mut parent_ids: Vec<ids::Id> = Vec::new();
         for _ in 0..parent_ids_size {
             let parent_id = self.unpack_bytes(ids::LEN)?;
@@ -77,7 +215,13 @@ impl Packer {
             parent_ids.push(parent_id);
         }
 
-        let txs_size = self.unpack_u32()?;
+        let txs_size = if with_txs { self.unpack_u32()? } else { 0 };
+        if txs_size as usize > MAX_TXS_PER_VTX {
+            return Err(Error::Other {
+                message: format!("vertex has too many txs {txs_size}"),
+                retryable: false,
+            });
+        }
         let mut txs: Vec<Vec<u8>> = Vec::new();
         for _ in 0..txs_size {
             let tx_size = self.unpack_u32()?;
@@ -197,3 +341,41 @@ fn test_pack_and_unpack() {
     let vtx_unpacked = packer.unpack_vertex().unwrap();
     assert!(vtx == vtx_unpacked);
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `avm::txs::vertex::test_stop_vertex` --exact --show-output
+#[test]
+fn test_stop_vertex() {
+    let chain_id = ids::Id::from_slice(&[1; 32]);
+
+    let mut vtx = Vertex {
+        codec_version: CODEC_VERSION,
+        chain_id,
+        height: 0,
+        epoch: 0,
+        parent_ids: Vec::new(),
+        txs: vec![vec![1, 2, 3]],
+    };
+    let b = vtx.to_bytes().unwrap();
+    assert_eq!(Vertex::from_bytes(&b).unwrap(), vtx);
+    assert_eq!(vtx.tx_ids(), vec![ids::Id::sha256([1, 2, 3])]);
+
+    let mut trailing = b.clone();
+    trailing.push(0);
+    assert!(Vertex::from_bytes(&trailing).is_err());
+
+    let parent_id = ids::Id::sha256(&b);
+    let mut stop = Vertex::new_stop_vertex(chain_id, 1, vec![parent_id]);
+    let b = stop.to_bytes().unwrap();
+    // version + chain ID + height + parents (no epoch and no txs)
+    assert_eq!(b.len(), 2 + 32 + 8 + 4 + 32);
+    let parsed = Vertex::from_bytes(&b).unwrap();
+    assert!(parsed.is_stop_vertex());
+    assert_eq!(parsed, stop);
+
+    // no txs, duplicate parents
+    vtx.txs.clear();
+    assert!(vtx.verify().is_err());
+    stop.parent_ids.push(parent_id);
+    assert!(stop.verify().is_err());
+}
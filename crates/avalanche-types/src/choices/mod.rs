@@ -1,4 +1,6 @@
 //! Consensus `choices`. interface for decidable problems.
 pub mod decidable;
 pub mod status;
+pub mod status_map;
 pub mod test_decidable;
+pub mod timestamp;
@@ -0,0 +1,183 @@
+//! Tracks the processing/accepted/rejected operations by Id,
+//! enforcing the valid status transitions of the consensus.
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    choices::status::Status,
+    errors::{Error, Result},
+    ids::Id,
+};
+
+/// Maintains the decision status of each operation with O(1) queries.
+/// Only "Processing" to "Accepted" or "Rejected" transitions are allowed,
+/// and decided operations never change (e.g., can't accept after reject).
+#[derive(Debug, Clone, Default)]
+pub struct StatusMap {
+    statuses: HashMap<Id, Status>,
+    processing: HashSet<Id>,
+    accepted_count: usize,
+    rejected_count: usize,
+}
+
+impl StatusMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the operation as "Processing".
+    /// No-op if already processing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation was already decided.
+    pub fn add(&mut self, id: Id) -> Result<()> {
+        match self.statuses.get(&id) {
+            Some(Status::Processing) => Ok(()),
+            Some(status) => Err(Error::Other {
+                message: format!("{id} already decided as {status}"),
+                retryable: false,
+            }),
+            None => {
+                self.statuses.insert(id, Status::Processing);
+                self.processing.insert(id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Marks the operation as "Accepted".
+    /// No-op if already accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation is unknown or was rejected.
+    pub fn accept(&mut self, id: &Id) -> Result<()> {
+        self.decide(id, Status::Accepted)
+    }
+
+    /// Marks the operation as "Rejected".
+    /// No-op if already rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation is unknown or was accepted.
+    pub fn reject(&mut self, id: &Id) -> Result<()> {
+        self.decide(id, Status::Rejected)
+    }
+
+    fn decide(&mut self, id: &Id, decision: Status) -> Result<()> {
+        let Some(status) = self.statuses.get_mut(id) else {
+            return Err(Error::Other {
+                message: format!("{id} not found to be {decision}"),
+                retryable: false,
+            });
+        };
+        match status {
+            Status::Processing => {}
+            s if *s == decision => return Ok(()),
+            s => {
+                return Err(Error::Other {
+                    message: format!("{id} already decided as {s}, cannot be {decision}"),
+                    retryable: false,
+                })
+            }
+        }
+
+        if decision == Status::Accepted {
+            self.accepted_count += 1;
+        } else {
+            self.rejected_count += 1;
+        }
+        *status = decision;
+        self.processing.remove(id);
+        Ok(())
+    }
+
+    /// Returns the status of the operation, or "Unknown" if not tracked.
+    #[must_use]
+    pub fn status(&self, id: &Id) -> Status {
+        self.statuses
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| Status::Unknown(String::new()))
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &Id) -> bool {
+        self.statuses.contains_key(id)
+    }
+
+    #[must_use]
+    pub fn is_processing(&self, id: &Id) -> bool {
+        self.processing.contains(id)
+    }
+
+    #[must_use]
+    pub fn is_accepted(&self, id: &Id) -> bool {
+        matches!(self.statuses.get(id), Some(Status::Accepted))
+    }
+
+    #[must_use]
+    pub fn is_rejected(&self, id: &Id) -> bool {
+        matches!(self.statuses.get(id), Some(Status::Rejected))
+    }
+
+    /// Returns the Ids of the undecided operations.
+    #[must_use]
+    pub const fn processing(&self) -> &HashSet<Id> {
+        &self.processing
+    }
+
+    #[must_use]
+    pub fn num_processing(&self) -> usize {
+        self.processing.len()
+    }
+
+    #[must_use]
+    pub const fn num_accepted(&self) -> usize {
+        self.accepted_count
+    }
+
+    #[must_use]
+    pub const fn num_rejected(&self) -> usize {
+        self.rejected_count
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `choices::status_map::test_status_map` --exact --show-output
+#[test]
+fn test_status_map() {
+    let id1 = Id::from_slice(&[1]);
+    let id2 = Id::from_slice(&[2]);
+    let id3 = Id::from_slice(&[3]);
+
+    let mut m = StatusMap::new();
+    assert_eq!(m.status(&id1), Status::Unknown(String::new()));
+    assert!(m.accept(&id1).is_err());
+
+    m.add(id1).unwrap();
+    m.add(id2).unwrap();
+    m.add(id3).unwrap();
+    m.add(id3).unwrap();
+    assert_eq!(m.num_processing(), 3);
+    assert!(m.is_processing(&id1));
+
+    m.accept(&id1).unwrap();
+    m.accept(&id1).unwrap();
+    m.reject(&id2).unwrap();
+    assert!(m.is_accepted(&id1));
+    assert!(m.is_rejected(&id2));
+    assert_eq!(m.status(&id2), Status::Rejected);
+    assert_eq!(m.num_accepted(), 1);
+    assert_eq!(m.num_rejected(), 1);
+    assert_eq!(m.processing().iter().collect::<Vec<_>>(), vec![&id3]);
+
+    // decided operations never change
+    assert!(m.accept(&id2).is_err());
+    assert!(m.reject(&id1).is_err());
+    assert!(m.add(id1).is_err());
+    assert!(m.is_rejected(&id2));
+    assert_eq!(m.num_accepted(), 1);
+}
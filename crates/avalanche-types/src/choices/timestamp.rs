@@ -0,0 +1,102 @@
+//! Block timestamp rules of the snowman consensus, for the block "verify"
+//! implementations of the VMs (timestamps in unix seconds).
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/proposervm>
+use std::time::Duration;
+
+use crate::errors::{Error, Result};
+
+/// Maximum duration the block timestamp can be ahead of the local clock.
+/// ref. "avalanchego/vms/proposervm.maxSkew"
+pub const MAX_SKEW: Duration = Duration::from_secs(10);
+
+/// Verifies the block timestamp is not before its parent
+/// and not more than [`MAX_SKEW`] ahead of the local clock "now".
+///
+/// # Errors
+///
+/// Returns an error if the timestamp violates any rule.
+pub fn verify(parent_timestamp: u64, timestamp: u64, now: u64) -> Result<()> {
+    verify_with_max_skew(parent_timestamp, timestamp, now, MAX_SKEW)
+}
+
+/// Same as [`verify`] but with the custom maximum skew.
+///
+/// # Errors
+///
+/// Returns an error if the timestamp violates any rule.
+pub fn verify_with_max_skew(
+    parent_timestamp: u64,
+    timestamp: u64,
+    now: u64,
+    max_skew: Duration,
+) -> Result<()> {
+    if timestamp < parent_timestamp {
+        return Err(Error::Other {
+            message: format!("block timestamp {timestamp} is before parent {parent_timestamp}"),
+            retryable: false,
+        });
+    }
+
+    let max_timestamp = now.saturating_add(max_skew.as_secs());
+    if timestamp > max_timestamp {
+        return Err(Error::Other {
+            message: format!(
+                "block timestamp {timestamp} is too far in the future (max {max_timestamp})"
+            ),
+            // may become valid as the local clock advances
+            retryable: true,
+        });
+    }
+    Ok(())
+}
+
+/// Verifies the proposer waited for its window after the parent block,
+/// where "`proposer_delay`" is from the "`proposervm::windower`" (e.g.,
+/// "`Windower::delay`" or "`Windower::min_delay_for_proposer`").
+///
+/// # Errors
+///
+/// Returns an error if the block is proposed before the window starts.
+pub fn verify_proposer_window(
+    parent_timestamp: u64,
+    timestamp: u64,
+    proposer_delay: Duration,
+) -> Result<()> {
+    let min_timestamp = parent_timestamp.saturating_add(proposer_delay.as_secs());
+    if timestamp < min_timestamp {
+        return Err(Error::Other {
+            message: format!(
+                "block timestamp {timestamp} is before the proposer window starts at {min_timestamp}"
+            ),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the timestamp of the new block built on the parent,
+/// never before the parent even if the local clock is behind.
+#[must_use]
+pub fn next(parent_timestamp: u64, now: u64) -> u64 {
+    parent_timestamp.max(now)
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `choices::timestamp::test_verify` --exact --show-output
+#[test]
+fn test_verify() {
+    assert!(verify(100, 100, 100).is_ok());
+    assert!(verify(100, 110, 100).is_ok());
+    assert!(verify(100, 99, 100).is_err());
+
+    let err = verify(100, 111, 100).unwrap_err();
+    assert!(err.retryable());
+    assert!(verify_with_max_skew(100, 111, 100, Duration::from_secs(11)).is_ok());
+    assert!(verify(u64::MAX - 1, u64::MAX, u64::MAX).is_ok());
+
+    assert!(verify_proposer_window(100, 110, Duration::from_secs(10)).is_ok());
+    assert!(verify_proposer_window(100, 109, Duration::from_secs(10)).is_err());
+
+    assert_eq!(next(100, 90), 100);
+    assert_eq!(next(100, 120), 120);
+}
@@ -1,10 +1,19 @@
 //! Implementation of the avalanchego codec.
 pub mod serde;
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+    sync::{RwLock, RwLockReadGuard},
+};
 
+use crate::{
+    errors::{Error, Result},
+    packer::Packer,
+};
 use lazy_static::lazy_static;
 
+/// Codec version that the transactions are packed with by default.
 pub const VERSION: u16 = 0;
 
 lazy_static! {
@@ -79,4 +88,290 @@ lazy_static! {
 
         m
     };
+
+    /// ref. <https://github.com/ava-labs/coreth/blob/master/plugin/evm/codec.go>
+    ///     (used for encoding the atomic transactions)
+    pub static ref C_TYPES: HashMap<String, usize> = {
+        let mut m = HashMap::new();
+        m.insert("evm.UnsignedImportTx".to_string(), 0);
+        m.insert("evm.UnsignedExportTx".to_string(), 1);
+
+        // skips 3 registrations to match the X-chain type Ids
+        m.insert("secp256k1fx.TransferInput".to_string(), 5);
+        m.insert("secp256k1fx.MintOutput".to_string(), 6);
+        m.insert("secp256k1fx.TransferOutput".to_string(), 7);
+        m.insert("secp256k1fx.MintOperation".to_string(), 8);
+        m.insert("secp256k1fx.Credential".to_string(), 9);
+        m.insert("secp256k1fx.Input".to_string(), 10);
+        m.insert("secp256k1fx.OutputOwners".to_string(), 11);
+        m
+    };
+
+    /// X-chain type registries of every supported codec version,
+    /// read via "`x_registry`" and extended via "`register_x_types`".
+    static ref X_REGISTRY: RwLock<Registry> =
+        RwLock::new(Registry::new().register(VERSION, X_TYPES.clone()));
+
+    /// P-chain type registries of every supported codec version,
+    /// read via "`p_registry`" and extended via "`register_p_types`".
+    static ref P_REGISTRY: RwLock<Registry> =
+        RwLock::new(Registry::new().register(VERSION, P_TYPES.clone()));
+
+    /// C-chain atomic type registries of every supported codec version,
+    /// read via "`c_registry`" and extended via "`register_c_types`".
+    static ref C_REGISTRY: RwLock<Registry> =
+        RwLock::new(Registry::new().register(VERSION, C_TYPES.clone()));
+}
+
+/// Locks the registry for reading.
+fn read(registry: &RwLock<Registry>) -> Result<RwLockReadGuard<'_, Registry>> {
+    registry.read().map_err(|e| Error::Other {
+        message: format!("failed to read codec registry '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Registers the types of the codec version in the registry.
+fn register<S: BuildHasher>(
+    registry: &RwLock<Registry>,
+    version: u16,
+    types: HashMap<String, usize, S>,
+) -> Result<()> {
+    let mut registry = registry.write().map_err(|e| Error::Other {
+        message: format!("failed to write codec registry '{e}'"),
+        retryable: false,
+    })?;
+    *registry = std::mem::take(&mut *registry).register(version, types);
+    Ok(())
+}
+
+/// Returns the X-chain type registries, to pack and unpack the transactions with.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn x_registry() -> Result<RwLockReadGuard<'static, Registry>> {
+    read(&X_REGISTRY)
+}
+
+/// Returns the P-chain type registries, to pack and unpack the transactions with.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn p_registry() -> Result<RwLockReadGuard<'static, Registry>> {
+    read(&P_REGISTRY)
+}
+
+/// Returns the C-chain atomic type registries, to pack and unpack the
+/// atomic transactions with.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn c_registry() -> Result<RwLockReadGuard<'static, Registry>> {
+    read(&C_REGISTRY)
+}
+
+/// Registers the X-chain types of the codec version (e.g., once avalanchego
+/// bumps the codec version), replacing the existing types of the version if any.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn register_x_types<S: BuildHasher>(
+    version: u16,
+    types: HashMap<String, usize, S>,
+) -> Result<()> {
+    register(&X_REGISTRY, version, types)
+}
+
+/// Registers the P-chain types of the codec version,
+/// replacing the existing types of the version if any.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn register_p_types<S: BuildHasher>(
+    version: u16,
+    types: HashMap<String, usize, S>,
+) -> Result<()> {
+    register(&P_REGISTRY, version, types)
+}
+
+/// Registers the C-chain atomic types of the codec version,
+/// replacing the existing types of the version if any.
+///
+/// # Errors
+///
+/// Returns an error if the registry lock is poisoned.
+pub fn register_c_types<S: BuildHasher>(
+    version: u16,
+    types: HashMap<String, usize, S>,
+) -> Result<()> {
+    register(&C_REGISTRY, version, types)
+}
+
+/// Type registries of each codec version, so the bytes packed with an
+/// older codec version can still be unpacked after avalanchego bumps the
+/// codec version (e.g., for historical transactions).
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/codec#Manager>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Registry {
+    versions: BTreeMap<u16, HashMap<String, usize>>,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the types of the codec version, replacing the
+    /// existing types of the version if any.
+    #[must_use]
+    pub fn register<S: BuildHasher>(
+        mut self,
+        version: u16,
+        types: HashMap<String, usize, S>,
+    ) -> Self {
+        self.versions.insert(version, types.into_iter().collect());
+        self
+    }
+
+    /// Returns the registered codec versions in ascending order.
+    pub fn versions(&self) -> impl Iterator<Item = u16> + '_ {
+        self.versions.keys().copied()
+    }
+
+    /// Returns the latest registered codec version.
+    #[must_use]
+    pub fn latest_version(&self) -> Option<u16> {
+        self.versions.keys().next_back().copied()
+    }
+
+    /// Returns "true" if the codec version is registered.
+    #[must_use]
+    pub fn is_supported(&self, version: u16) -> bool {
+        self.versions.contains_key(&version)
+    }
+
+    /// Returns the type Id of the type name in the codec version.
+    #[must_use]
+    pub fn type_id(&self, version: u16, type_name: &str) -> Option<u32> {
+        self.versions
+            .get(&version)?
+            .get(type_name)
+            .and_then(|id| u32::try_from(*id).ok())
+    }
+
+    /// Returns the type Id to pack the type name with in the codec version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version or the type name is not registered.
+    pub fn resolve_type_id(&self, version: u16, type_name: &str) -> Result<u32> {
+        if !self.is_supported(version) {
+            return Err(Error::Other {
+                message: format!("unknown codec version {version}"),
+                retryable: false,
+            });
+        }
+        self.type_id(version, type_name)
+            .ok_or_else(|| Error::Other {
+                message: format!("type '{type_name}' not registered in codec version {version}"),
+                retryable: false,
+            })
+    }
+
+    /// Returns the type name of the type Id in the codec version.
+    #[must_use]
+    pub fn type_name(&self, version: u16, type_id: u32) -> Option<&str> {
+        self.versions
+            .get(&version)?
+            .iter()
+            .find(|(_, id)| u32::try_from(**id).is_ok_and(|id| id == type_id))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Unpacks the codec version prefix, and returns the version
+    /// if registered.
+    /// ref. "avalanchego/codec.manager.Unmarshal"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version cannot be unpacked or is not registered.
+    pub fn unpack_version(&self, packer: &Packer) -> Result<u16> {
+        let version = packer.unpack_u16()?;
+        if !self.is_supported(version) {
+            return Err(Error::Other {
+                message: format!("unknown codec version {version}"),
+                retryable: false,
+            });
+        }
+        Ok(version)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `codec::test_registry` --exact --show-output
+#[test]
+fn test_registry() {
+    let x_registry = x_registry().unwrap().clone();
+    assert_eq!(x_registry.latest_version(), Some(VERSION));
+    assert_eq!(x_registry.type_id(VERSION, "avm.ExportTx"), Some(4));
+    assert_eq!(
+        p_registry().unwrap().type_name(VERSION, 17),
+        Some("platformvm.ImportTx")
+    );
+    assert_eq!(
+        c_registry()
+            .unwrap()
+            .resolve_type_id(VERSION, "evm.UnsignedExportTx")
+            .unwrap(),
+        1
+    );
+    assert!(x_registry.type_id(1, "avm.ExportTx").is_none());
+    assert!(x_registry.resolve_type_id(1, "avm.ExportTx").is_err());
+    assert!(x_registry.resolve_type_id(VERSION, "avm.NewTx").is_err());
+
+    // a bumped codec version keeps the older version unpackable
+    let mut v1 = X_TYPES.clone();
+    v1.insert("avm.NewTx".to_string(), 20);
+    let registry = x_registry.clone().register(1, v1);
+    assert_eq!(registry.versions().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(registry.latest_version(), Some(1));
+    assert_eq!(registry.type_id(1, "avm.NewTx"), Some(20));
+    assert!(registry.type_id(0, "avm.NewTx").is_none());
+
+    for version in [0_u16, 1] {
+        let packer = Packer::new(2, 0);
+        packer.pack_u16(version).unwrap();
+        let b = packer.take_bytes();
+        let packer = Packer::load_bytes_for_unpack(b.len() + 1024, &b);
+        assert_eq!(registry.unpack_version(&packer).unwrap(), version);
+
+        let packer = Packer::load_bytes_for_unpack(b.len() + 1024, &b);
+        assert_eq!(x_registry.unpack_version(&packer).is_ok(), version == 0);
+    }
+
+    // the transactions are packed with the type Ids of their codec version,
+    // including the nested types
+    let mut renumbered = P_TYPES.clone();
+    renumbered.insert("secp256k1fx.TransferOutput".to_string(), 30);
+    let registry = p_registry().unwrap().clone().register(7, renumbered);
+    let tx = crate::txs::Tx {
+        transferable_outputs: Some(vec![crate::txs::transferable::Output {
+            transfer_output: Some(crate::key::secp256k1::txs::transfer::Output::default()),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    assert!(tx.pack(&registry, 8, 17).is_err());
+    let b = tx.pack(&registry, 7, 17).unwrap().take_bytes();
+    assert_eq!(&b[..6], &[0, 7, 0, 0, 0, 17]);
+    // codec version, type Id, network Id, blockchain Id, number of outputs, asset Id
+    let offset = 2 + 4 + 4 + 32 + 4 + 32;
+    assert_eq!(&b[offset..offset + 4], &[0, 0, 0, 30]);
+    let b = tx.pack(&registry, VERSION, 17).unwrap().take_bytes();
+    assert_eq!(&b[offset..offset + 4], &[0, 0, 0, 7]);
 }
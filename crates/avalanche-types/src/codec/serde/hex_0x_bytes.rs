@@ -1,4 +1,9 @@
-use serde::{Deserialize, Deserializer, Serializer};
+//! Serde adapter for the "0x"-prefixed hex strings of the byte fields
+//! (e.g., signatures, genesis blobs), which encodes into and decodes from
+//! a pre-sized buffer without the intermediate "String" allocations.
+use std::fmt;
+
+use serde::{de, Deserializer, Serializer};
 use serde_with::{formats, DeserializeAs, SerializeAs};
 
 /// ref. `serde_with::hex::Hex`
@@ -18,8 +23,7 @@ where
     where
         S: Serializer,
     {
-        let s = hex::encode(x);
-        serializer.serialize_str(&format!("0x{s}"))
+        serialize_hex(x.as_ref(), false, serializer)
     }
 }
 
@@ -35,8 +39,7 @@ where
     where
         S: Serializer,
     {
-        let s = hex::encode_upper(x);
-        serializer.serialize_str(&format!("0x{s}"))
+        serialize_hex(x.as_ref(), true, serializer)
     }
 }
 
@@ -53,23 +56,69 @@ where
     where
         D: Deserializer<'de>,
     {
-        <std::borrow::Cow<'de, str> as Deserialize<'de>>::deserialize(deserializer)
-            .and_then(|s| hex::decode(s.trim_start_matches("0x")).map_err(serde::de::Error::custom))
-            .and_then(|vec: Vec<u8>| {
-                let length = vec.len();
-                vec.try_into().map_err(|_e: T::Error| {
-                    serde::de::Error::custom(format!(
-                        "Can't convert a Byte Vector of length {length} to the output type."
-                    ))
-                })
-            })
+        let vec = deserializer.deserialize_str(Hex0xVisitor)?;
+        let length = vec.len();
+        vec.try_into().map_err(|_e: T::Error| {
+            de::Error::custom(format!(
+                "Can't convert a Byte Vector of length {length} to the output type."
+            ))
+        })
+    }
+}
+
+/// Encodes the bytes into one buffer sized for the prefix and the hex digits,
+/// which is then passed to the serializer as a borrowed "str".
+fn serialize_hex<S>(b: &[u8], upper: bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use serde::ser::Error;
+
+    let mut buf = vec![0_u8; 2 + b.len() * 2];
+    buf[..2].copy_from_slice(b"0x");
+    hex::encode_to_slice(b, &mut buf[2..]).map_err(S::Error::custom)?;
+    if upper {
+        buf[2..].make_ascii_uppercase();
+    }
+    // hex digits are always ASCII
+    let s = std::str::from_utf8(&buf).map_err(S::Error::custom)?;
+    serializer.serialize_str(s)
+}
+
+/// Decodes the borrowed (or transient) string directly into the output
+/// buffer, instead of copying it into a "String" first.
+struct Hex0xVisitor;
+
+impl de::Visitor<'_> for Hex0xVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a hex string with the \"0x\" prefix")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        decode_hex(s.as_bytes())
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+        decode_hex(b)
     }
 }
 
+/// Decodes the hex digits into a buffer of the exact decoded length.
+fn decode_hex<E: de::Error>(mut s: &[u8]) -> Result<Vec<u8>, E> {
+    while let Some(rest) = s.strip_prefix(b"0x") {
+        s = rest;
+    }
+    let mut out = vec![0_u8; s.len() / 2];
+    hex::decode_to_slice(s, &mut out).map_err(E::custom)?;
+    Ok(out)
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `codec::serde::hex_0x_bytes::test_custom_de_serializer` --exact --show-output
 #[test]
 fn test_custom_de_serializer() {
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
     use serde_with::serde_as;
 
     #[serde_as]
@@ -105,3 +154,59 @@ fn test_custom_de_serializer() {
     .unwrap();
     assert_eq!(d, json_decoded_2);
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `codec::serde::hex_0x_bytes::test_hex_0x_bytes_formats` --exact --show-output
+#[test]
+fn test_hex_0x_bytes_formats() {
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+    struct Data {
+        #[serde_as(as = "Hex0xBytes")]
+        lower: Vec<u8>,
+        #[serde_as(as = "Hex0xBytes<formats::Uppercase>")]
+        upper: Vec<u8>,
+        #[serde_as(as = "Hex0xBytes")]
+        fixed: [u8; 2],
+    }
+
+    let d = Data {
+        lower: vec![0xab, 0xcd],
+        upper: vec![0xab, 0xcd],
+        fixed: [0x01, 0xef],
+    };
+    let encoded = serde_json::to_string(&d).unwrap();
+    assert_eq!(
+        encoded,
+        "{\"lower\":\"0xabcd\",\"upper\":\"0xABCD\",\"fixed\":\"0x01ef\"}"
+    );
+    assert_eq!(serde_json::from_str::<Data>(&encoded).unwrap(), d);
+    // owned strings from a reader are decoded the same
+    assert_eq!(
+        serde_json::from_reader::<_, Data>(encoded.as_bytes()).unwrap(),
+        d
+    );
+
+    // the prefix is optional, and the digits are case-insensitive
+    let decoded: Data =
+        serde_json::from_str("{\"lower\":\"ABCD\",\"upper\":\"0xabcd\",\"fixed\":\"0x01EF\"}")
+            .unwrap();
+    assert_eq!(decoded, d);
+
+    // empty
+    let decoded: Data =
+        serde_json::from_str("{\"lower\":\"0x\",\"upper\":\"\",\"fixed\":\"0x01ef\"}").unwrap();
+    assert!(decoded.lower.is_empty() && decoded.upper.is_empty());
+
+    // odd length, invalid digits, and the length mismatch of the fixed-size field
+    for s in [
+        "{\"lower\":\"0xabc\",\"upper\":\"0x\",\"fixed\":\"0x01ef\"}",
+        "{\"lower\":\"0xzz\",\"upper\":\"0x\",\"fixed\":\"0x01ef\"}",
+        "{\"lower\":\"0x\",\"upper\":\"0x\",\"fixed\":\"0x01\"}",
+        "{\"lower\":1,\"upper\":\"0x\",\"fixed\":\"0x01ef\"}",
+    ] {
+        assert!(serde_json::from_str::<Data>(s).is_err(), "{s}");
+    }
+}
@@ -1,6 +1,13 @@
 //! Constants for the Avalanche network.
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
 
+use crate::{
+    errors::{Error, Result},
+    ids,
+};
 use lazy_static::lazy_static;
 
 pub const DEFAULT_CUSTOM_NETWORK_ID: u32 = 1_000_000;
@@ -55,4 +62,215 @@ lazy_static! {
         m.insert("local", 12345);
         m
     };
+
+    /// Registry of the known networks, with the presets of
+    /// "mainnet", "fuji" and "local".
+    static ref NETWORKS: RwLock<HashMap<u32, Network>> = {
+        let mut m = HashMap::new();
+        for network in [Network::mainnet(), Network::fuji(), Network::local()] {
+            m.insert(network.network_id, network);
+        }
+        RwLock::new(m)
+    };
+}
+
+pub const MAINNET_NETWORK_ID: u32 = 1;
+pub const FUJI_NETWORK_ID: u32 = 5;
+pub const LOCAL_NETWORK_ID: u32 = 12345;
+
+/// ref. <https://docs.avax.network/reference/standards/guides/network-ids>
+pub const MAINNET_X_CHAIN_ID: ids::Id =
+    crate::id!("2oYMBNV4eNHyqk2fjjV5nVQLDbtmNJzq5s3qs3Lo6ftnC6FByM");
+pub const MAINNET_C_CHAIN_ID: ids::Id =
+    crate::id!("2q9e4r6Mu3U68nU1fYjgbR6JvwrRx36CohpAX5UQxse55x1Q5");
+pub const MAINNET_AVAX_ASSET_ID: ids::Id =
+    crate::id!("FvwEAhmxKfeiG8SnEvq42hc6whRyY3EFYAvebMqDNDGCgxN5Z");
+
+/// ref. <https://docs.avax.network/reference/standards/guides/network-ids>
+pub const FUJI_X_CHAIN_ID: ids::Id =
+    crate::id!("2JVSBoinj9C2J33VntvzYtVJNZdN2NKiwwKjcumHUWEb5DbBrm");
+pub const FUJI_C_CHAIN_ID: ids::Id =
+    crate::id!("yH8D7ThNJkxmtkuv2jgBa4P1Rn3Qpr4pPr7QYNfcdoS6k6HWp");
+pub const FUJI_AVAX_ASSET_ID: ids::Id =
+    crate::id!("U8iRqJoiJm8xZHAacmvYyZVwqQx6uDNtQeP3CQ6fcgQk3JqnK");
+
+/// Represents the well-known parameters of a network.
+/// The chain IDs and the AVAX asset ID are "None" if they vary by genesis
+/// (e.g., "local").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    pub network_id: u32,
+    pub name: String,
+    /// Human-readable part of the bech32 addresses.
+    pub hrp: String,
+    /// P-chain ID is the empty ID for all networks.
+    pub p_chain_id: ids::Id,
+    pub x_chain_id: Option<ids::Id>,
+    pub c_chain_id: Option<ids::Id>,
+    pub avax_asset_id: Option<ids::Id>,
+    /// Public API endpoint without the chain path.
+    pub rpc_url: Option<String>,
+}
+
+impl Network {
+    /// Creates a custom network with no well-known chain IDs.
+    #[must_use]
+    pub fn new(network_id: u32, name: &str, hrp: &str) -> Self {
+        Self {
+            network_id,
+            name: name.to_string(),
+            hrp: hrp.to_string(),
+            p_chain_id: ids::Id::empty(),
+            x_chain_id: None,
+            c_chain_id: None,
+            avax_asset_id: None,
+            rpc_url: None,
+        }
+    }
+
+    /// ref. <https://docs.avax.network/reference/standards/guides/network-ids>
+    #[must_use]
+    pub fn mainnet() -> Self {
+        Self {
+            x_chain_id: Some(MAINNET_X_CHAIN_ID),
+            c_chain_id: Some(MAINNET_C_CHAIN_ID),
+            avax_asset_id: Some(MAINNET_AVAX_ASSET_ID),
+            rpc_url: Some(String::from("https://api.avax.network")),
+            ..Self::new(MAINNET_NETWORK_ID, "mainnet", "avax")
+        }
+    }
+
+    /// ref. <https://docs.avax.network/reference/standards/guides/network-ids>
+    #[must_use]
+    pub fn fuji() -> Self {
+        Self {
+            x_chain_id: Some(FUJI_X_CHAIN_ID),
+            c_chain_id: Some(FUJI_C_CHAIN_ID),
+            avax_asset_id: Some(FUJI_AVAX_ASSET_ID),
+            rpc_url: Some(String::from("https://api.avax-test.network")),
+            ..Self::new(FUJI_NETWORK_ID, "fuji", "fuji")
+        }
+    }
+
+    /// The chain IDs of the local network depend on its genesis.
+    #[must_use]
+    pub fn local() -> Self {
+        Self {
+            rpc_url: Some(String::from("http://127.0.0.1:9650")),
+            ..Self::new(LOCAL_NETWORK_ID, "local", "local")
+        }
+    }
+}
+
+/// Returns the registered network.
+#[must_use]
+pub fn network(network_id: u32) -> Option<Network> {
+    NETWORKS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&network_id)
+        .cloned()
+}
+
+/// Returns the registered network of the name (e.g., "fuji").
+#[must_use]
+pub fn network_by_name(name: &str) -> Option<Network> {
+    NETWORKS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .find(|n| n.name == name)
+        .cloned()
+}
+
+/// Returns the registered network of the HRP (e.g., "avax").
+#[must_use]
+pub fn network_by_hrp(hrp: &str) -> Option<Network> {
+    NETWORKS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .find(|n| n.hrp == hrp)
+        .cloned()
+}
+
+/// Returns the HRP of the network for address formatting,
+/// "`FALLBACK_HRP`" if the network is unknown.
+#[must_use]
+pub fn hrp(network_id: u32) -> String {
+    network(network_id).map_or_else(
+        || {
+            NETWORK_ID_TO_HRP
+                .get(&network_id)
+                .map_or(FALLBACK_HRP, |v| v)
+                .to_string()
+        },
+        |n| n.hrp,
+    )
+}
+
+/// Registers the custom network (e.g., a devnet), or updates the one
+/// registered before.
+///
+/// # Errors
+///
+/// Returns an error if the network ID is a preset, or if the name
+/// or the HRP is already used by another network.
+pub fn register_network(network: Network) -> Result<()> {
+    if matches!(
+        network.network_id,
+        MAINNET_NETWORK_ID | FUJI_NETWORK_ID | LOCAL_NETWORK_ID
+    ) {
+        return Err(Error::Other {
+            message: format!("cannot override the preset network {}", network.network_id),
+            retryable: false,
+        });
+    }
+
+    let mut networks = NETWORKS.write().unwrap_or_else(PoisonError::into_inner);
+    if let Some(other) = networks.values().find(|n| {
+        n.network_id != network.network_id && (n.name == network.name || n.hrp == network.hrp)
+    }) {
+        return Err(Error::Other {
+            message: format!(
+                "network name '{}' or HRP '{}' already used by network {}",
+                network.name, network.hrp, other.network_id
+            ),
+            retryable: false,
+        });
+    }
+    log::info!(
+        "registering network {} ({})",
+        network.network_id,
+        network.name
+    );
+    networks.insert(network.network_id, network);
+    Ok(())
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `constants::test_networks` --exact --show-output
+#[test]
+fn test_networks() {
+    let mainnet = network(MAINNET_NETWORK_ID).unwrap();
+    assert_eq!(mainnet.hrp, "avax");
+    assert_eq!(mainnet.p_chain_id, ids::Id::empty());
+    assert_eq!(network_by_name("fuji").unwrap().network_id, FUJI_NETWORK_ID);
+    assert_eq!(
+        network_by_hrp("local").unwrap().network_id,
+        LOCAL_NETWORK_ID
+    );
+    assert_eq!(hrp(FUJI_NETWORK_ID), "fuji");
+    assert_eq!(hrp(4), "everest");
+    assert_eq!(hrp(999_999), FALLBACK_HRP);
+
+    assert!(register_network(Network::new(FUJI_NETWORK_ID, "fake", "fake")).is_err());
+    assert!(register_network(Network::new(999_998, "devnet", "avax")).is_err());
+
+    let devnet = Network {
+        rpc_url: Some(String::from("http://10.0.0.1:9650")),
+        ..Network::new(999_998, "devnet", "devnet")
+    };
+    register_network(devnet.clone()).unwrap();
+    assert_eq!(network(999_998), Some(devnet));
+    assert_eq!(hrp(999_998), "devnet");
 }
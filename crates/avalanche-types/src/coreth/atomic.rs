@@ -0,0 +1,487 @@
+//! C-chain atomic transactions that move AVAX between the C-chain and
+//! the X/P-chains through the shared memory.
+//! ref. <https://github.com/ava-labs/coreth/blob/master/plugin/evm/tx.go>
+use crate::{
+    codec,
+    errors::{Error, Result},
+    hash, ids, key, packer, txs,
+};
+use primitive_types::{H160, U256};
+use serde::{Deserialize, Serialize};
+
+/// Gas charged per byte of the signed atomic transaction.
+/// ref. "coreth/params.TxBytesGas"
+pub const TX_BYTES_GAS: u64 = 1;
+/// Fixed gas charged for every atomic transaction since Apricot Phase 5.
+/// ref. "coreth/params.AtomicTxBaseCost"
+pub const ATOMIC_TX_BASE_COST: u64 = 10_000;
+/// ref. "coreth/params.CostPerSignature"
+pub const COST_PER_SIGNATURE: u64 = 1_000;
+/// Number of wei in one nAVAX, the denomination of the atomic amounts.
+/// ref. "coreth/plugin/evm.X2CRate"
+pub const X2C_RATE: u64 = 1_000_000_000;
+
+/// Length of the recoverable secp256k1 signature.
+const SIGNATURE_LEN: usize = 65;
+
+/// Output of the import transaction, credited to the EVM account.
+/// ref. "coreth/plugin/evm.EVMOutput"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmOutput {
+    pub address: H160,
+    /// Amount in nAVAX.
+    pub amount: u64,
+    pub asset_id: ids::Id,
+}
+
+/// Input of the export transaction, debited from the EVM account.
+/// ref. "coreth/plugin/evm.EVMInput"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmInput {
+    pub address: H160,
+    /// Amount in nAVAX.
+    pub amount: u64,
+    pub asset_id: ids::Id,
+    pub nonce: u64,
+}
+
+/// Imports the UTXOs exported from the source chain into the EVM accounts.
+/// ref. "coreth/plugin/evm.UnsignedImportTx"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportTx {
+    pub network_id: u32,
+    pub blockchain_id: ids::Id,
+    pub source_chain: ids::Id,
+    pub imported_inputs: Vec<txs::transferable::Input>,
+    pub outs: Vec<EvmOutput>,
+
+    pub creds: Vec<key::secp256k1::txs::Credential>,
+    /// Updated with signing.
+    #[serde(skip)]
+    pub metadata: Option<txs::Metadata>,
+}
+
+impl ImportTx {
+    #[must_use]
+    pub fn type_name() -> String {
+        "evm.UnsignedImportTx".to_string()
+    }
+
+    /// Returns the "`codec.Marshal`" bytes of the unsigned transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any input is not a secp256k1fx transfer input.
+    pub fn unsigned_bytes(&self) -> Result<Vec<u8>> {
+        let packer = new_packer(&Self::type_name())?;
+        packer.pack_u32(self.network_id)?;
+        packer.pack_bytes(self.blockchain_id.as_ref())?;
+        packer.pack_bytes(self.source_chain.as_ref())?;
+
+        packer.pack_u32(u32::try_from(self.imported_inputs.len())?)?;
+        for input in &self.imported_inputs {
+            pack_transferable_input(&packer, input)?;
+        }
+
+        packer.pack_u32(u32::try_from(self.outs.len())?)?;
+        for out in &self.outs {
+            packer.pack_bytes(out.address.as_bytes())?;
+            packer.pack_u64(out.amount)?;
+            packer.pack_bytes(out.asset_id.as_ref())?;
+        }
+        Ok(packer.take_bytes().to_vec())
+    }
+
+    /// Returns the gas of the signed transaction, with the number of
+    /// signatures of each imported input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed.
+    pub fn gas_used(&self, num_signatures: &[usize]) -> Result<u64> {
+        gas_used(self.unsigned_bytes()?.len(), num_signatures)
+    }
+
+    /// Signs the transaction with the signers of each imported input,
+    /// and updates the credentials and metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed or signed.
+    pub async fn sign<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+    ) -> Result<()> {
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed or signed.
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        let (creds, metadata) = sign(self.unsigned_bytes()?, signers, cache).await?;
+        self.creds = creds;
+        self.metadata = Some(metadata);
+        Ok(())
+    }
+}
+
+/// Exports the AVAX of the EVM accounts to the destination chain.
+/// ref. "coreth/plugin/evm.UnsignedExportTx"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTx {
+    pub network_id: u32,
+    pub blockchain_id: ids::Id,
+    pub destination_chain: ids::Id,
+    pub ins: Vec<EvmInput>,
+    pub exported_outputs: Vec<txs::transferable::Output>,
+
+    pub creds: Vec<key::secp256k1::txs::Credential>,
+    /// Updated with signing.
+    #[serde(skip)]
+    pub metadata: Option<txs::Metadata>,
+}
+
+impl ExportTx {
+    #[must_use]
+    pub fn type_name() -> String {
+        "evm.UnsignedExportTx".to_string()
+    }
+
+    /// Returns the "`codec.Marshal`" bytes of the unsigned transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any output is not a secp256k1fx transfer output.
+    pub fn unsigned_bytes(&self) -> Result<Vec<u8>> {
+        let packer = new_packer(&Self::type_name())?;
+        packer.pack_u32(self.network_id)?;
+        packer.pack_bytes(self.blockchain_id.as_ref())?;
+        packer.pack_bytes(self.destination_chain.as_ref())?;
+
+        packer.pack_u32(u32::try_from(self.ins.len())?)?;
+        for input in &self.ins {
+            packer.pack_bytes(input.address.as_bytes())?;
+            packer.pack_u64(input.amount)?;
+            packer.pack_bytes(input.asset_id.as_ref())?;
+            packer.pack_u64(input.nonce)?;
+        }
+
+        packer.pack_u32(u32::try_from(self.exported_outputs.len())?)?;
+        for out in &self.exported_outputs {
+            pack_transferable_output(&packer, out)?;
+        }
+        Ok(packer.take_bytes().to_vec())
+    }
+
+    /// Returns the gas of the signed transaction, with the number of
+    /// signatures of each EVM input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed.
+    pub fn gas_used(&self, num_signatures: &[usize]) -> Result<u64> {
+        gas_used(self.unsigned_bytes()?.len(), num_signatures)
+    }
+
+    /// Signs the transaction with the signers of each EVM input,
+    /// and updates the credentials and metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed or signed.
+    pub async fn sign<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+    ) -> Result<()> {
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be packed or signed.
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        let (creds, metadata) = sign(self.unsigned_bytes()?, signers, cache).await?;
+        self.creds = creds;
+        self.metadata = Some(metadata);
+        Ok(())
+    }
+}
+
+/// Returns the gas of the signed atomic transaction, given the length of
+/// the unsigned bytes and the number of signatures in each credential.
+/// ref. "coreth/plugin/evm.UnsignedImportTx.GasUsed"
+///
+/// # Errors
+///
+/// Returns an error if the gas overflows.
+pub fn gas_used(unsigned_len: usize, num_signatures: &[usize]) -> Result<u64> {
+    // number of credentials, then the type Id, the number of signatures
+    // and the signatures of each credential
+    let mut signed_len = unsigned_len + 4;
+    let mut total_signatures = 0_usize;
+    for n in num_signatures {
+        signed_len += 4 + 4 + n * SIGNATURE_LEN;
+        total_signatures += n;
+    }
+
+    let bytes_gas = u64::try_from(signed_len)?.checked_mul(TX_BYTES_GAS);
+    let signatures_gas = u64::try_from(total_signatures)?.checked_mul(COST_PER_SIGNATURE);
+    bytes_gas
+        .zip(signatures_gas)
+        .and_then(|(b, s)| b.checked_add(s))
+        .and_then(|gas| gas.checked_add(ATOMIC_TX_BASE_COST))
+        .ok_or_else(|| Error::Other {
+            message: "atomic tx gas overflow".to_string(),
+            retryable: false,
+        })
+}
+
+/// Returns the fee in nAVAX to burn for the gas at the base fee (in wei),
+/// rounded up.
+/// ref. "coreth/plugin/evm.CalculateDynamicFee"
+///
+/// # Errors
+///
+/// Returns an error if the fee does not fit in u64.
+pub fn fee(gas: u64, base_fee: U256) -> Result<u64> {
+    let rate = U256::from(X2C_RATE);
+    let fee = U256::from(gas)
+        .checked_mul(base_fee)
+        .and_then(|v| v.checked_add(rate - 1))
+        .map(|v| v / rate)
+        .ok_or_else(|| Error::Other {
+            message: format!("atomic tx fee overflow for gas {gas} at base fee {base_fee}"),
+            retryable: false,
+        })?;
+    if fee > U256::from(u64::MAX) {
+        return Err(Error::Other {
+            message: format!("atomic tx fee {fee} exceeds u64"),
+            retryable: false,
+        });
+    }
+    Ok(fee.as_u64())
+}
+
+/// Creates the packer prefixed with the codec version and the type Id
+/// of the type name in the coreth codec.
+/// ref. "coreth/plugin/evm.Codec"
+fn new_packer(type_name: &str) -> Result<packer::Packer> {
+    let type_id = codec::c_registry()?.resolve_type_id(codec::VERSION, type_name)?;
+
+    // ref. "math.MaxInt32" and "constants.DefaultByteSliceCap" in Go
+    let packer = packer::Packer::new((1 << 31) - 1, 128);
+    packer.pack_u16(codec::VERSION)?;
+    packer.pack_u32(type_id)?;
+    Ok(packer)
+}
+
+fn pack_transferable_input(
+    packer: &packer::Packer,
+    input: &txs::transferable::Input,
+) -> Result<()> {
+    let Some(transfer_input) = &input.transfer_input else {
+        return Err(Error::Other {
+            message: "atomic tx only supports secp256k1fx transfer inputs".to_string(),
+            retryable: false,
+        });
+    };
+    packer.pack_bytes(input.utxo_id.tx_id.as_ref())?;
+    packer.pack_u32(input.utxo_id.output_index)?;
+    packer.pack_bytes(input.asset_id.as_ref())?;
+
+    packer.pack_u32(codec::c_registry()?.resolve_type_id(
+        codec::VERSION,
+        &key::secp256k1::txs::transfer::Input::type_name(),
+    )?)?;
+    packer.pack_u64(transfer_input.amount)?;
+    packer.pack_u32(u32::try_from(transfer_input.sig_indices.len())?)?;
+    for idx in &transfer_input.sig_indices {
+        packer.pack_u32(*idx)?;
+    }
+    Ok(())
+}
+
+fn pack_transferable_output(
+    packer: &packer::Packer,
+    output: &txs::transferable::Output,
+) -> Result<()> {
+    let Some(transfer_output) = &output.transfer_output else {
+        return Err(Error::Other {
+            message: "atomic tx only supports secp256k1fx transfer outputs".to_string(),
+            retryable: false,
+        });
+    };
+    packer.pack_bytes(output.asset_id.as_ref())?;
+
+    packer.pack_u32(codec::c_registry()?.resolve_type_id(
+        codec::VERSION,
+        &key::secp256k1::txs::transfer::Output::type_name(),
+    )?)?;
+    packer.pack_u64(transfer_output.amount)?;
+    packer.pack_u64(transfer_output.output_owners.locktime)?;
+    packer.pack_u32(transfer_output.output_owners.threshold)?;
+    packer.pack_u32(u32::try_from(
+        transfer_output.output_owners.addresses.len(),
+    )?)?;
+    for addr in &transfer_output.output_owners.addresses {
+        packer.pack_bytes(addr.as_ref())?;
+    }
+    Ok(())
+}
+
+/// Signs the hash of the unsigned bytes, and returns the credentials and
+/// the metadata with the signed bytes ("evm.Tx" with "Creds").
+async fn sign<T: key::secp256k1::SignOnly + Sync>(
+    unsigned_bytes: Vec<u8>,
+    signers: Vec<Vec<T>>,
+    cache: &mut key::secp256k1::txs::SignatureCache,
+) -> Result<(Vec<key::secp256k1::txs::Credential>, txs::Metadata)> {
+    let tx_bytes_hash = hash::sha256(&unsigned_bytes);
+
+    let packer = packer::pool::shared().get((1 << 31) - 1);
+    packer.pack_bytes(&unsigned_bytes)?;
+    packer.pack_u32(u32::try_from(signers.len())?)?;
+
+    let cred_type_id = codec::c_registry()?.resolve_type_id(
+        codec::VERSION,
+        &key::secp256k1::txs::Credential::type_name(),
+    )?;
+    let creds =
+        key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache).await?;
+    for cred in &creds {
+        packer.pack_u32(cred_type_id)?;
+        packer.pack_u32(u32::try_from(cred.signatures.len())?)?;
+        for sig in &cred.signatures {
+            packer.pack_bytes(sig)?;
+        }
+    }
+
+    let tx_bytes_with_signatures = packer.take_bytes();
+    let tx_id = hash::sha256(&tx_bytes_with_signatures);
+    let metadata = txs::Metadata {
+        id: ids::Id::from_slice(&tx_id),
+        tx_bytes_with_no_signature: unsigned_bytes,
+        tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
+    };
+    packer::pool::shared().recycle([tx_bytes_with_signatures]);
+    Ok((creds, metadata))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features coreth -- `coreth::atomic::test_atomic_txs` --exact --show-output
+#[test]
+fn test_atomic_txs() {
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let key = key::secp256k1::TEST_KEYS[0].clone();
+    let asset_id = ids::Id::from_slice(&[7; 32]);
+
+    let mut import_tx = ImportTx {
+        network_id: 1,
+        blockchain_id: ids::Id::from_slice(&[1; 32]),
+        source_chain: ids::Id::from_slice(&[2; 32]),
+        imported_inputs: vec![txs::transferable::Input {
+            utxo_id: txs::utxo::Id {
+                tx_id: ids::Id::from_slice(&[3; 32]),
+                output_index: 1,
+                ..Default::default()
+            },
+            asset_id,
+            transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                amount: 1_000,
+                sig_indices: vec![0],
+            }),
+            ..Default::default()
+        }],
+        outs: vec![EvmOutput {
+            address: H160::from_low_u64_be(1),
+            amount: 900,
+            asset_id,
+        }],
+        ..Default::default()
+    };
+
+    // version, type Id, network Id, chain Ids,
+    // one input (utxo Id, asset Id, type Id, amount, one sig index),
+    // one output (address, amount, asset Id)
+    let unsigned = import_tx.unsigned_bytes().unwrap();
+    assert_eq!(
+        unsigned.len(),
+        2 + 4 + 4 + 32 + 32 + 4 + (32 + 4 + 32 + 4 + 8 + 4 + 4) + 4 + (20 + 8 + 32)
+    );
+    assert_eq!(&unsigned[..6], &[0, 0, 0, 0, 0, 0]);
+
+    ab!(import_tx.sign(vec![vec![key.clone()]])).unwrap();
+    let metadata = import_tx.metadata.clone().unwrap();
+    assert_eq!(metadata.tx_bytes_with_no_signature, unsigned);
+    assert_eq!(
+        metadata.id,
+        ids::Id::from_slice(&hash::sha256(&metadata.tx_bytes_with_signatures))
+    );
+    let gas = import_tx.gas_used(&[1]).unwrap();
+    assert_eq!(
+        gas,
+        u64::try_from(metadata.tx_bytes_with_signatures.len()).unwrap()
+            + COST_PER_SIGNATURE
+            + ATOMIC_TX_BASE_COST
+    );
+
+    let mut export_tx = ExportTx {
+        network_id: 1,
+        blockchain_id: ids::Id::from_slice(&[1; 32]),
+        destination_chain: ids::Id::from_slice(&[2; 32]),
+        ins: vec![EvmInput {
+            address: key.to_public_key().to_h160(),
+            amount: 1_000,
+            asset_id,
+            nonce: 5,
+        }],
+        exported_outputs: vec![txs::transferable::Output {
+            asset_id,
+            transfer_output: Some(key::secp256k1::txs::transfer::Output::new(
+                1_000,
+                key::secp256k1::txs::OutputOwners::new(
+                    0,
+                    1,
+                    &[key.to_public_key().to_short_id().unwrap()],
+                ),
+            )),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let unsigned = export_tx.unsigned_bytes().unwrap();
+    assert_eq!(&unsigned[..6], &[0, 0, 0, 0, 0, 1]);
+    ab!(export_tx.sign(vec![vec![key]])).unwrap();
+    assert_eq!(export_tx.creds[0].signatures[0].len(), SIGNATURE_LEN);
+
+    // 25 nAVAX base fee
+    assert_eq!(fee(11_230, U256::from(25 * X2C_RATE)).unwrap(), 280_750);
+    // rounded up
+    assert_eq!(fee(1, U256::from(1)).unwrap(), 1);
+    assert!(fee(u64::MAX, U256::MAX).is_err());
+}
@@ -1,5 +1,6 @@
 //! Coreth types.
 //!
-//! Includes the chain config and genesis.
+//! Includes the chain config, genesis and atomic transactions.
+pub mod atomic;
 pub mod chain_config;
 pub mod genesis;
@@ -4,7 +4,11 @@ use std::{cell::RefCell, fmt, num::TryFromIntError, rc::Rc};
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Backing errors for all consensus operations.
+///
+/// Marked "`non_exhaustive`" so that new variants (e.g., "`JsonRpc`")
+/// do not break the downstream matches.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// `GetUtxosResult` 结果为 None
     UnexpectedNoneGetUtxosResult,
@@ -18,6 +22,13 @@ pub enum Error {
     API { message: String, retryable: bool },
     /// 其他错误
     Other { message: String, retryable: bool },
+    /// JSON-RPC 错误对象（保留 code/message/data）
+    JsonRpc {
+        code: i64,
+        message: String,
+        data: Option<String>,
+        kind: RpcErrorKind,
+    },
 }
 
 impl Error {
@@ -32,6 +43,15 @@ impl Error {
             }
             Self::UnexpectedNone(msg) => format!("Unexpected None: {msg}"),
             Self::IntConversion(msg) => format!("Integer conversion error: {msg}"),
+            Self::JsonRpc {
+                code,
+                message,
+                data,
+                ..
+            } => data.as_ref().map_or_else(
+                || format!("JSON-RPC error (code: {code}, message: {message})"),
+                |d| format!("JSON-RPC error (code: {code}, message: {message}, data: {d})"),
+            ),
         }
     }
 
@@ -40,9 +60,146 @@ impl Error {
     pub const fn retryable(&self) -> bool {
         match self {
             Self::API { retryable, .. } | Self::Other { retryable, .. } => *retryable,
+            Self::JsonRpc { kind, .. } => kind.retryable(),
             _ => false,
         }
     }
+
+    /// Creates an error from the JSON-RPC error object,
+    /// classifying it by its code and message.
+    #[must_use]
+    pub fn json_rpc(code: i64, message: &str, data: Option<String>) -> Self {
+        Self::JsonRpc {
+            code,
+            message: message.to_string(),
+            data,
+            kind: RpcErrorKind::classify(code, message),
+        }
+    }
+
+    /// Returns the kind of the JSON-RPC error, if the error came from one.
+    #[inline]
+    #[must_use]
+    pub const fn rpc_kind(&self) -> Option<RpcErrorKind> {
+        match self {
+            Self::JsonRpc { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+/// Classification of the JSON-RPC API errors, so callers can branch
+/// without matching on the error message.
+/// ref. <https://www.jsonrpc.org/specification#error_object>
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum RpcErrorKind {
+    /// Invalid JSON was received (-32700).
+    Parse,
+    /// The JSON sent is not a valid request object (-32600).
+    InvalidRequest,
+    /// The method does not exist (-32601).
+    MethodNotFound,
+    /// Invalid method parameters (-32602).
+    InvalidParams,
+    /// Internal JSON-RPC error (-32603).
+    Internal,
+    /// The transaction was dropped from the mempool.
+    TxDropped,
+    /// Not enough funds to pay for the transaction.
+    InsufficientFunds,
+    /// The UTXO being spent does not exist (or is already spent).
+    UtxoNotFound,
+    /// The nonce has already been used.
+    NonceTooLow,
+    /// The replacement transaction does not pay enough fees.
+    Underpriced,
+    /// The node is already a validator.
+    DuplicateValidation,
+    /// The request was rate limited by the server.
+    RateLimited,
+    /// Any other server error.
+    Unknown,
+}
+
+impl RpcErrorKind {
+    /// Classifies the error by its message first, since avalanchego returns
+    /// most application errors with the generic server code (-32000).
+    #[must_use]
+    pub fn classify(code: i64, message: &str) -> Self {
+        let msg = message.to_lowercase();
+        if msg.contains("dropped from mempool") || msg.contains("tx dropped") {
+            return Self::TxDropped;
+        }
+        if msg.contains("insufficient funds") || msg.contains("insufficient balance") {
+            return Self::InsufficientFunds;
+        }
+        if msg.contains("utxo not found")
+            || msg.contains("missing utxo")
+            || msg.contains("failed to get utxo")
+        {
+            return Self::UtxoNotFound;
+        }
+        if msg.contains("nonce too low") {
+            return Self::NonceTooLow;
+        }
+        if msg.contains("underpriced") {
+            return Self::Underpriced;
+        }
+        if msg.contains("duplicate validation") || msg.contains("already a validator") {
+            return Self::DuplicateValidation;
+        }
+        if msg.contains("too many requests") || msg.contains("rate limit") {
+            return Self::RateLimited;
+        }
+
+        match code {
+            -32700 => Self::Parse,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::Internal,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns true if the same request may succeed when retried
+    /// (possibly after refreshing the nonce or fees).
+    #[must_use]
+    pub const fn retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Internal
+                | Self::TxDropped
+                | Self::NonceTooLow
+                | Self::Underpriced
+                | Self::RateLimited
+        )
+    }
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::InvalidRequest => "invalid_request",
+            Self::MethodNotFound => "method_not_found",
+            Self::InvalidParams => "invalid_params",
+            Self::Internal => "internal",
+            Self::TxDropped => "tx_dropped",
+            Self::InsufficientFunds => "insufficient_funds",
+            Self::UtxoNotFound => "utxo_not_found",
+            Self::NonceTooLow => "nonce_too_low",
+            Self::Underpriced => "underpriced",
+            Self::DuplicateValidation => "duplicate_validation",
+            Self::RateLimited => "rate_limited",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for RpcErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 impl fmt::Display for Error {
@@ -51,6 +208,8 @@ impl fmt::Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
 #[derive(Debug)]
 pub struct AvalancheErrors {
     /// Collection of errors
@@ -96,3 +255,64 @@ impl From<TryFromIntError> for Error {
         Self::IntConversion(err.to_string())
     }
 }
+
+impl From<crate::jsonrpc::ResponseError> for Error {
+    fn from(err: crate::jsonrpc::ResponseError) -> Self {
+        Self::json_rpc(i64::from(err.code), &err.message, err.data)
+    }
+}
+
+impl From<crate::jsonrpc::common::JsonRpcError> for Error {
+    fn from(err: crate::jsonrpc::common::JsonRpcError) -> Self {
+        Self::json_rpc(err.code, &err.message, err.data.map(|d| d.to_string()))
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `errors::test_json_rpc_error` --exact --show-output
+#[test]
+fn test_json_rpc_error() {
+    let resp: crate::jsonrpc::ResponseError = serde_json::from_str(
+        r#"{"code":-32000,"message":"failed to get utxo: not found","data":"0x1234"}"#,
+    )
+    .unwrap();
+    let err = Error::from(resp);
+    assert_eq!(err.rpc_kind(), Some(RpcErrorKind::UtxoNotFound));
+    assert!(!err.retryable());
+    match &err {
+        Error::JsonRpc {
+            code,
+            message,
+            data,
+            ..
+        } => {
+            assert_eq!(*code, -32000);
+            assert_eq!(message, "failed to get utxo: not found");
+            assert_eq!(data.as_deref(), Some("0x1234"));
+        }
+        _ => panic!("unexpected error {err:?}"),
+    }
+    assert_eq!(
+        err.message(),
+        "JSON-RPC error (code: -32000, message: failed to get utxo: not found, data: 0x1234)"
+    );
+
+    let err = Error::json_rpc(-32000, "tx dropped from mempool", None);
+    assert_eq!(err.rpc_kind(), Some(RpcErrorKind::TxDropped));
+    assert!(err.retryable());
+
+    let err = Error::json_rpc(-32000, "insufficient funds for gas * price + value", None);
+    assert_eq!(err.rpc_kind(), Some(RpcErrorKind::InsufficientFunds));
+    assert!(!err.retryable());
+
+    let err = Error::json_rpc(-32601, "the method foo does not exist", None);
+    assert_eq!(err.rpc_kind(), Some(RpcErrorKind::MethodNotFound));
+
+    let err = Error::json_rpc(-32603, "internal error", None);
+    assert!(err.retryable());
+
+    let err = Error::API {
+        message: "x".to_string(),
+        retryable: false,
+    };
+    assert!(err.rpc_kind().is_none());
+}
@@ -14,6 +14,19 @@ pub fn encode_calldata(func: &Function, arg_tokens: &[Token]) -> io::Result<Vec<
         .map_err(|e| Error::new(ErrorKind::Other, format!("failed to encode_input {e}")))
 }
 
+/// Decodes the return data of the function call (e.g., "`eth_call`" result).
+///
+/// # Errors
+/// Returns an error if the output does not match the function outputs.
+pub fn decode_output(func: &Function, output: &[u8]) -> io::Result<Vec<Token>> {
+    func.decode_output(output).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to decode_output {e}"),
+        )
+    })
+}
+
 /// TODO: implement this with "foundry 4-byte decode"
 ///
 /// ref. <https://github.com/foundry-rs/foundry/blob/master/common/src/selectors.rs> "`decode_calldata`"
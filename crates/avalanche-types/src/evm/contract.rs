@@ -0,0 +1,313 @@
+//! Read-only contract calls via "`eth_call`", with the ABI encoding and decoding,
+//! and batching many reads into one "`eth_call`" with Multicall3.
+use std::str::FromStr;
+
+use crate::{
+    errors::{Error, Result},
+    evm::abi,
+    jsonrpc::client::http,
+};
+use ethers_core::{
+    abi::{Function, HumanReadableParser, Param, ParamType, StateMutability, Token},
+    types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, TransactionRequest},
+};
+use ethers_providers::{Http, Middleware, Provider};
+use primitive_types::H160;
+
+/// Multicall3 address, deployed at the same address on the C-chain and most EVM chains.
+/// ref. <https://github.com/mds1/multicall>
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// Parses the human-readable function signature,
+/// e.g., "function balanceOf(address) external view returns (uint256)".
+///
+/// # Errors
+///
+/// Returns an error if the signature is invalid.
+pub fn parse_function(signature: &str) -> Result<Function> {
+    HumanReadableParser::parse_function(signature).map_err(|e| Error::Other {
+        message: format!("failed to parse function '{signature}' '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Represents a read call in the multicall batch.
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub target: H160,
+    pub function: Function,
+    pub args: Vec<Token>,
+}
+
+impl Call {
+    #[must_use]
+    pub fn new(target: H160, function: Function, args: Vec<Token>) -> Self {
+        Self {
+            target,
+            function,
+            args,
+        }
+    }
+}
+
+/// Reads the contract state via "`eth_call`".
+#[derive(Debug, Clone)]
+pub struct Reader {
+    /// EVM endpoint the reads are sent to.
+    pub rpc_ep: String,
+    /// "ethers" provider routed via the HTTP client of the reader.
+    provider: Provider<Http>,
+    /// Multicall3 contract address for the batched reads.
+    multicall: H160,
+    /// Block number to read at, or the latest block if "None".
+    block: Option<u64>,
+}
+
+impl Reader {
+    /// Creates a new reader on the EVM endpoint, via the current client.
+    /// e.g., "`{http_rpc}/ext/bc/C/rpc`"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new(rpc_ep: &str) -> Result<Self> {
+        Self::new_with_http(&http::current()?, rpc_ep)
+    }
+
+    /// Creates a new reader on the EVM endpoint, via the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new_with_http(http: &http::Http, rpc_ep: &str) -> Result<Self> {
+        let multicall = H160::from_str(MULTICALL3_ADDRESS).map_err(|e| Error::Other {
+            message: format!("invalid multicall address '{e}'"),
+            retryable: false,
+        })?;
+        Ok(Self {
+            rpc_ep: rpc_ep.to_string(),
+            provider: http.provider(rpc_ep)?,
+            multicall,
+            block: None,
+        })
+    }
+
+    /// Sets the Multicall3 contract address, if not deployed at [`MULTICALL3_ADDRESS`].
+    #[must_use]
+    pub const fn multicall_address(mut self, addr: H160) -> Self {
+        self.multicall = addr;
+        self
+    }
+
+    /// Reads at the block number, instead of the latest block.
+    #[must_use]
+    pub const fn block(mut self, number: u64) -> Self {
+        self.block = Some(number);
+        self
+    }
+
+    /// Calls the function of the contract, and returns the decoded outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding fails, the call reverts, or the output is invalid.
+    pub async fn read(
+        &self,
+        target: H160,
+        function: &Function,
+        args: &[Token],
+    ) -> Result<Vec<Token>> {
+        let calldata = encode(function, args)?;
+        let output = self.call(target, calldata).await?;
+        decode(function, &output)
+    }
+
+    /// Calls all functions in a single "`eth_call`" via Multicall3 "aggregate3",
+    /// and returns the decoded outputs in the same order as the calls.
+    /// A reverted call does not fail the other calls in the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multicall itself fails.
+    pub async fn multicall(&self, calls: &[Call]) -> Result<Vec<Result<Vec<Token>>>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        log::info!(
+            "reading {} calls via multicall {:?}",
+            calls.len(),
+            self.multicall
+        );
+
+        let aggregate3 = aggregate3_function();
+        let calldata = encode_aggregate3(&aggregate3, calls)?;
+        let output = self.call(self.multicall, calldata).await?;
+        decode_aggregate3(&aggregate3, calls, &output)
+    }
+
+    /// Sends the "`eth_call`" to the target at the block of the reader,
+    /// and returns the raw output.
+    async fn call(&self, target: H160, calldata: Vec<u8>) -> Result<Vec<u8>> {
+        let tx: TypedTransaction = TransactionRequest::new().to(target).data(calldata).into();
+        let block = self
+            .block
+            .map_or(BlockNumber::Latest, |n| BlockNumber::Number(n.into()));
+
+        let output = self
+            .provider
+            .call(&tx, Some(BlockId::Number(block)))
+            .await
+            .map_err(|e|
+                // TODO: check retryable
+                Error::API {
+                    message: format!("failed eth_call to {target:?} '{e}'"),
+                    retryable: false,
+                })?;
+        Ok(output.to_vec())
+    }
+}
+
+/// ABI-encodes the calldata of the function call.
+fn encode(function: &Function, args: &[Token]) -> Result<Vec<u8>> {
+    abi::encode_calldata(function, args).map_err(|e| Error::Other {
+        message: e.to_string(),
+        retryable: false,
+    })
+}
+
+/// ABI-decodes the output of the function call.
+fn decode(function: &Function, output: &[u8]) -> Result<Vec<Token>> {
+    abi::decode_output(function, output).map_err(|e| Error::Other {
+        message: format!("{} output: {e}", function.name),
+        retryable: false,
+    })
+}
+
+/// "aggregate3((address target, bool allowFailure, bytes callData)[] calls)
+/// payable returns ((bool success, bytes returnData)[] returnData)"
+#[allow(deprecated)]
+fn aggregate3_function() -> Function {
+    let call3 = ParamType::Tuple(vec![ParamType::Address, ParamType::Bool, ParamType::Bytes]);
+    let result = ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes]);
+    Function {
+        name: "aggregate3".to_string(),
+        inputs: vec![Param {
+            name: "calls".to_string(),
+            kind: ParamType::Array(Box::new(call3)),
+            internal_type: None,
+        }],
+        outputs: vec![Param {
+            name: "returnData".to_string(),
+            kind: ParamType::Array(Box::new(result)),
+            internal_type: None,
+        }],
+        constant: None,
+        state_mutability: StateMutability::Payable,
+    }
+}
+
+/// Encodes the calls as the "aggregate3" calldata, allowing each call to fail.
+fn encode_aggregate3(aggregate3: &Function, calls: &[Call]) -> Result<Vec<u8>> {
+    let mut encoded = Vec::with_capacity(calls.len());
+    for call in calls {
+        encoded.push(Token::Tuple(vec![
+            Token::Address(call.target),
+            Token::Bool(true),
+            Token::Bytes(encode(&call.function, &call.args)?),
+        ]));
+    }
+    encode(aggregate3, &[Token::Array(encoded)])
+}
+
+/// Decodes the "aggregate3" output into the result of each call,
+/// where a reverted call is an error.
+fn decode_aggregate3(
+    aggregate3: &Function,
+    calls: &[Call],
+    output: &[u8],
+) -> Result<Vec<Result<Vec<Token>>>> {
+    let invalid = || Error::Other {
+        message: "unexpected aggregate3 output".to_string(),
+        retryable: false,
+    };
+
+    let tokens = decode(aggregate3, output)?;
+    let Some(Token::Array(results)) = tokens.into_iter().next() else {
+        return Err(invalid());
+    };
+    if results.len() != calls.len() {
+        return Err(Error::Other {
+            message: format!(
+                "aggregate3 returned {} results for {} calls",
+                results.len(),
+                calls.len()
+            ),
+            retryable: false,
+        });
+    }
+
+    let mut decoded = Vec::with_capacity(calls.len());
+    for (call, result) in calls.iter().zip(results) {
+        let Token::Tuple(fields) = result else {
+            return Err(invalid());
+        };
+        let [Token::Bool(success), Token::Bytes(data)] = fields.as_slice() else {
+            return Err(invalid());
+        };
+        if *success {
+            decoded.push(decode(&call.function, data));
+        } else {
+            decoded.push(Err(Error::API {
+                message: format!("{} call to {:?} reverted", call.function.name, call.target),
+                retryable: false,
+            }));
+        }
+    }
+    Ok(decoded)
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features evm -- `evm::contract::test_multicall_codec` --exact --show-output
+#[test]
+fn test_multicall_codec() {
+    use ethers_core::abi::encode as abi_encode;
+    use primitive_types::U256;
+
+    let balance_of =
+        parse_function("function balanceOf(address owner) external view returns (uint256)")
+            .unwrap();
+    assert_eq!(balance_of.short_signature(), [0x70, 0xa0, 0x82, 0x31]);
+
+    let token = H160::from_low_u64_be(1);
+    let owner = H160::from_low_u64_be(2);
+    let calls = vec![
+        Call::new(token, balance_of.clone(), vec![Token::Address(owner)]),
+        Call::new(token, balance_of, vec![Token::Address(token)]),
+    ];
+
+    let aggregate3 = aggregate3_function();
+    assert_eq!(aggregate3.short_signature(), [0x82, 0xad, 0x56, 0xcb]);
+    let calldata = encode_aggregate3(&aggregate3, &calls).unwrap();
+    let decoded = aggregate3.decode_input(&calldata[4..]).unwrap();
+    let Token::Array(encoded) = &decoded[0] else {
+        panic!("unexpected input");
+    };
+    assert_eq!(encoded.len(), 2);
+
+    // first call succeeds, second reverts
+    let output = abi_encode(&[Token::Array(vec![
+        Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(abi_encode(&[Token::Uint(U256::from(1234))])),
+        ]),
+        Token::Tuple(vec![Token::Bool(false), Token::Bytes(Vec::new())]),
+    ])]);
+    let results = decode_aggregate3(&aggregate3, &calls, &output).unwrap();
+    assert_eq!(
+        results[0].as_ref().unwrap(),
+        &vec![Token::Uint(U256::from(1234))]
+    );
+    assert!(results[1].is_err());
+
+    // result count mismatch
+    assert!(decode_aggregate3(&aggregate3, &calls[..1], &output).is_err());
+}
@@ -0,0 +1,325 @@
+//! Dynamic fee estimation for EIP-1559 transactions.
+//!
+//! Fixed fees either overpay when the chain is idle or get the transactions
+//! stuck when it is congested. The estimator instead samples the recent
+//! blocks via "`eth_feeHistory`", and suggests the priority fee from the
+//! percentile of the tips paid, with the max fee leaving headroom for the
+//! base fee to rise before the transaction is included.
+use std::sync::Arc;
+
+use crate::{
+    errors::{Error, Result},
+    jsonrpc::client::http,
+};
+use async_trait::async_trait;
+use ethers_core::types::{BlockNumber, FeeHistory};
+use ethers_providers::{Http, Middleware, Provider};
+use primitive_types::U256;
+
+/// Default number of the recent blocks to sample.
+pub const DEFAULT_BLOCK_COUNT: u64 = 20;
+/// Default percentile of the tips paid in each block.
+pub const DEFAULT_REWARD_PERCENTILE: f64 = 60.0;
+/// Default headroom for the base fee on top of the next base fee, in percent
+/// (i.e., max fee is twice the base fee plus the tip, which survives
+/// several full blocks of base fee increases).
+pub const DEFAULT_BASE_FEE_HEADROOM_PERCENT: u64 = 100;
+
+/// Source of the recent fees, as seen by the chain.
+#[async_trait]
+pub trait Source {
+    /// Returns the base fees and the tips at the percentiles
+    /// of the recent blocks, up to the pending block.
+    async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64])
+        -> Result<FeeHistory>;
+
+    /// Returns the priority fee the node suggests.
+    async fn max_priority_fee_per_gas(&self) -> Result<U256>;
+}
+
+/// Fetches the fees via "`eth_feeHistory`" and "`eth_maxPriorityFeePerGas`".
+#[derive(Debug, Clone)]
+pub struct RpcSource {
+    pub rpc_ep: String,
+    provider: Provider<Http>,
+}
+
+impl RpcSource {
+    /// Creates a new source on the EVM endpoint, via the current client.
+    /// e.g., "`{http_rpc}/ext/bc/C/rpc`"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new(rpc_ep: &str) -> Result<Self> {
+        Self::new_with_http(&http::current()?, rpc_ep)
+    }
+
+    /// Creates a new source on the EVM endpoint, via the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new_with_http(http: &http::Http, rpc_ep: &str) -> Result<Self> {
+        Ok(Self {
+            rpc_ep: rpc_ep.to_string(),
+            provider: http.provider(rpc_ep)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Source for RpcSource {
+    async fn fee_history(
+        &self,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory> {
+        log::debug!(
+            "getting fee history of {block_count} blocks via {}",
+            self.rpc_ep
+        );
+        self.provider
+            .fee_history(block_count, BlockNumber::Latest, reward_percentiles)
+            .await
+            .map_err(|e| Error::API {
+                message: format!("failed eth_feeHistory '{e}'"),
+                retryable: true,
+            })
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> Result<U256> {
+        self.provider
+            .request("eth_maxPriorityFeePerGas", ())
+            .await
+            .map_err(|e| Error::API {
+                message: format!("failed eth_maxPriorityFeePerGas '{e}'"),
+                retryable: true,
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub block_count: u64,
+    /// Percentile of the tips paid in each block, between 0 and 100.
+    /// The higher, the sooner the transaction is included.
+    pub reward_percentile: f64,
+    pub base_fee_headroom_percent: u64,
+    /// Lower bound of the suggested priority fee.
+    pub min_priority_fee_per_gas: U256,
+    /// Upper bound of the suggested max fee, if any.
+    pub max_fee_per_gas_cap: Option<U256>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            block_count: DEFAULT_BLOCK_COUNT,
+            reward_percentile: DEFAULT_REWARD_PERCENTILE,
+            base_fee_headroom_percent: DEFAULT_BASE_FEE_HEADROOM_PERCENT,
+            min_priority_fee_per_gas: U256::zero(),
+            max_fee_per_gas_cap: None,
+        }
+    }
+}
+
+/// Suggested fees of an EIP-1559 transaction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Estimate {
+    /// Base fee of the next block.
+    pub base_fee_per_gas: U256,
+    /// Maps to subnet-evm `DynamicFeeTx` "`GasTipCap`".
+    pub max_priority_fee_per_gas: U256,
+    /// Maps to subnet-evm `DynamicFeeTx` "`GasFeeCap`".
+    pub max_fee_per_gas: U256,
+}
+
+/// Suggests the fees from the recent blocks.
+#[derive(Debug)]
+pub struct Estimator<S: Source> {
+    source: Arc<S>,
+    pub config: Config,
+}
+
+impl Estimator<RpcSource> {
+    /// Creates a new estimator that fetches the fees from the EVM endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn from_rpc(rpc_ep: &str) -> Result<Self> {
+        Ok(Self::new(
+            Arc::new(RpcSource::new(rpc_ep)?),
+            Config::default(),
+        ))
+    }
+
+    /// Same as "`from_rpc`", but sends the requests via the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn from_rpc_with_http(http: &http::Http, rpc_ep: &str) -> Result<Self> {
+        Ok(Self::new(
+            Arc::new(RpcSource::new_with_http(http, rpc_ep)?),
+            Config::default(),
+        ))
+    }
+}
+
+impl<S> Estimator<S>
+where
+    S: Source + Send + Sync,
+{
+    #[must_use]
+    pub fn new(source: Arc<S>, config: Config) -> Self {
+        Self { source, config }
+    }
+
+    /// Returns the suggested fees for a transaction sent now.
+    /// Falls back to "`eth_maxPriorityFeePerGas`" if no recent block paid any tip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fee fetch fails.
+    pub async fn estimate(&self) -> Result<Estimate> {
+        let history = self
+            .source
+            .fee_history(self.config.block_count, &[self.config.reward_percentile])
+            .await?;
+
+        let tip = match median_reward(&history) {
+            Some(tip) => tip,
+            None => self.source.max_priority_fee_per_gas().await?,
+        };
+        let estimate = suggest(&history, tip, &self.config)?;
+        log::info!(
+            "estimated fees [base fee {}, max priority fee {}, max fee {}]",
+            estimate.base_fee_per_gas,
+            estimate.max_priority_fee_per_gas,
+            estimate.max_fee_per_gas
+        );
+        Ok(estimate)
+    }
+}
+
+/// Returns the median of the non-zero tips at the percentile across the blocks,
+/// or None if no block paid any tip (e.g., empty blocks).
+fn median_reward(history: &FeeHistory) -> Option<U256> {
+    let mut tips: Vec<U256> = history
+        .reward
+        .iter()
+        .filter_map(|r| r.first().copied())
+        .filter(|tip| !tip.is_zero())
+        .collect();
+    if tips.is_empty() {
+        return None;
+    }
+    tips.sort_unstable();
+    Some(tips[tips.len() / 2])
+}
+
+/// Computes the fees from the next base fee (the last entry of the history)
+/// and the tip, within the configured bounds.
+fn suggest(history: &FeeHistory, tip: U256, config: &Config) -> Result<Estimate> {
+    let base_fee_per_gas = *history.base_fee_per_gas.last().ok_or_else(|| Error::API {
+        message: "eth_feeHistory returned no base fee".to_string(),
+        retryable: true,
+    })?;
+
+    let mut max_priority_fee_per_gas = tip.max(config.min_priority_fee_per_gas);
+    let headroom = base_fee_per_gas.saturating_mul(U256::from(config.base_fee_headroom_percent))
+        / U256::from(100);
+    let mut max_fee_per_gas = base_fee_per_gas
+        .saturating_add(headroom)
+        .saturating_add(max_priority_fee_per_gas);
+
+    if let Some(cap) = config.max_fee_per_gas_cap {
+        max_fee_per_gas = max_fee_per_gas.min(cap);
+        max_priority_fee_per_gas = max_priority_fee_per_gas.min(max_fee_per_gas);
+    }
+
+    Ok(Estimate {
+        base_fee_per_gas,
+        max_priority_fee_per_gas,
+        max_fee_per_gas,
+    })
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features evm -- `evm::fees::test_estimator` --exact --show-output
+#[test]
+fn test_estimator() {
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    /// Source that returns fixed fees, for testing.
+    struct MockSource {
+        history: FeeHistory,
+        tip: U256,
+    }
+
+    #[async_trait]
+    impl Source for MockSource {
+        async fn fee_history(&self, _: u64, _: &[f64]) -> Result<FeeHistory> {
+            Ok(self.history.clone())
+        }
+        async fn max_priority_fee_per_gas(&self) -> Result<U256> {
+            Ok(self.tip)
+        }
+    }
+
+    let history = |base_fees: &[u64], tips: &[u64]| FeeHistory {
+        base_fee_per_gas: base_fees.iter().copied().map(U256::from).collect(),
+        gas_used_ratio: vec![0.5; tips.len()],
+        oldest_block: U256::from(1),
+        reward: tips.iter().map(|t| vec![U256::from(*t)]).collect(),
+    };
+
+    // median of the non-zero tips, and twice the next base fee
+    let estimator = Estimator::new(
+        Arc::new(MockSource {
+            history: history(&[25, 30, 40], &[0, 3, 1, 2]),
+            tip: U256::from(7),
+        }),
+        Config::default(),
+    );
+    let estimate = ab!(estimator.estimate()).unwrap();
+    assert_eq!(
+        estimate,
+        Estimate {
+            base_fee_per_gas: U256::from(40),
+            max_priority_fee_per_gas: U256::from(2),
+            max_fee_per_gas: U256::from(82),
+        }
+    );
+
+    // falls back to the node's suggestion on empty blocks, within the bounds
+    let estimator = Estimator::new(
+        Arc::new(MockSource {
+            history: history(&[25, 50], &[0, 0]),
+            tip: U256::from(7),
+        }),
+        Config {
+            base_fee_headroom_percent: 50,
+            min_priority_fee_per_gas: U256::from(10),
+            max_fee_per_gas_cap: Some(U256::from(80)),
+            ..Default::default()
+        },
+    );
+    let estimate = ab!(estimator.estimate()).unwrap();
+    assert_eq!(estimate.max_priority_fee_per_gas, U256::from(10));
+    assert_eq!(estimate.max_fee_per_gas, U256::from(80));
+
+    let estimator = Estimator::new(
+        Arc::new(MockSource {
+            history: history(&[], &[]),
+            tip: U256::from(7),
+        }),
+        Config::default(),
+    );
+    assert!(ab!(estimator.estimate()).is_err());
+}
@@ -1,7 +1,10 @@
 //! Modules for various EVM functionality.
 //!
-//! Includes the Ethereum ABI, EIP-1559, EIP-712, and Foundry.
+//! Includes the Ethereum ABI, contract reads, EIP-1559, EIP-712, fee estimation, Foundry, and nonce management.
 pub mod abi;
+pub mod contract;
 pub mod eip1559;
 pub mod eip712;
+pub mod fees;
 pub mod foundry;
+pub mod nonce;
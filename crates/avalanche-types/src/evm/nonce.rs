@@ -0,0 +1,324 @@
+//! Local nonce tracking for sending many EVM transactions concurrently.
+//!
+//! Relying on "`eth_getTransactionCount`" for every transaction breaks down
+//! when many transactions from the same address are in flight, since the
+//! node only learns about a nonce once the previous transaction reaches
+//! its mempool. The manager instead fetches the pending nonce once per
+//! address, hands out sequential nonces locally, and resyncs from the
+//! node when a transaction is rejected with "nonce too low".
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    errors::{Error, Result, RpcErrorKind},
+    jsonrpc::client::http,
+};
+use async_trait::async_trait;
+use ethers_core::types::BlockNumber;
+use ethers_providers::{Http, Middleware, Provider};
+use primitive_types::{H160, U256};
+use tokio::sync::Mutex;
+
+/// Source of the next nonce for an address, as seen by the chain.
+#[async_trait]
+pub trait Source {
+    /// Returns the number of transactions sent from the address,
+    /// including the ones still pending in the mempool.
+    async fn pending_nonce(&self, addr: H160) -> Result<U256>;
+}
+
+/// Fetches the pending nonce via "`eth_getTransactionCount`".
+#[derive(Debug, Clone)]
+pub struct RpcSource {
+    /// EVM endpoint, e.g., "`{http_rpc}/ext/bc/C/rpc`".
+    pub rpc_ep: String,
+    /// Provider on the endpoint, via the client.
+    provider: Provider<Http>,
+}
+
+impl RpcSource {
+    /// Creates a new source on the EVM endpoint, via the current client.
+    /// e.g., "`{http_rpc}/ext/bc/C/rpc`"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new(rpc_ep: &str) -> Result<Self> {
+        Self::new_with_http(&http::current()?, rpc_ep)
+    }
+
+    /// Creates a new source on the EVM endpoint, via the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn new_with_http(http: &http::Http, rpc_ep: &str) -> Result<Self> {
+        Ok(Self {
+            rpc_ep: rpc_ep.to_string(),
+            provider: http.provider(rpc_ep)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Source for RpcSource {
+    async fn pending_nonce(&self, addr: H160) -> Result<U256> {
+        log::info!("getting pending nonce for {addr} via {}", self.rpc_ep);
+        self.provider
+            .get_transaction_count(addr, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(|e| Error::API {
+                message: format!("failed get_transaction_count '{e}'"),
+                retryable: true,
+            })
+    }
+}
+
+/// Next nonce to hand out for a single address, None until fetched.
+type Slot = Arc<Mutex<Option<U256>>>;
+
+/// Hands out sequential nonces per address, safe to share across tasks.
+#[derive(Debug)]
+pub struct Manager<S: Source> {
+    /// Where the chain nonces are fetched from.
+    source: Arc<S>,
+
+    /// Next nonce to hand out per address, each behind its own lock,
+    /// so a slow fetch for one address does not block the others.
+    /// No lock is held while fetching from the source.
+    nonces: Mutex<HashMap<H160, Slot>>,
+}
+
+impl Manager<RpcSource> {
+    /// Creates a new manager that fetches nonces from the EVM endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn from_rpc(rpc_ep: &str) -> Result<Self> {
+        Ok(Self::new(Arc::new(RpcSource::new(rpc_ep)?)))
+    }
+
+    /// Same as "`from_rpc`", but sends the requests via the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn from_rpc_with_http(http: &http::Http, rpc_ep: &str) -> Result<Self> {
+        Ok(Self::new(Arc::new(RpcSource::new_with_http(http, rpc_ep)?)))
+    }
+}
+
+impl<S> Manager<S>
+where
+    S: Source + Send + Sync,
+{
+    #[must_use]
+    pub fn new(source: Arc<S>) -> Self {
+        Self {
+            source,
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the lock of the address, creating it if not yet used.
+    async fn slot(&self, addr: H160) -> Slot {
+        Arc::clone(self.nonces.lock().await.entry(addr).or_default())
+    }
+
+    /// Returns the next nonce for the address and reserves it.
+    /// The first call for an address fetches the pending nonce from the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial nonce fetch fails.
+    pub async fn next(&self, addr: H160) -> Result<U256> {
+        let slot = self.slot(addr).await;
+        {
+            let mut next = slot.lock().await;
+            if let Some(nonce) = *next {
+                *next = Some(nonce + U256::one());
+                return Ok(nonce);
+            }
+        }
+
+        let fetched = self.source.pending_nonce(addr).await?;
+
+        // another caller may have fetched (and handed out) the nonce meanwhile,
+        // in which case its local nonce is ahead of the fetched one
+        let mut next = slot.lock().await;
+        let nonce = next.unwrap_or(fetched);
+        *next = Some(nonce + U256::one());
+        Ok(nonce)
+    }
+
+    /// Returns the next nonce for the address without reserving it.
+    /// Returns None if the address has not been used yet.
+    pub async fn peek(&self, addr: H160) -> Option<U256> {
+        let slot = self.nonces.lock().await.get(&addr).cloned()?;
+        let next = *slot.lock().await;
+        next
+    }
+
+    /// Refetches the pending nonce from the source, discarding the local one.
+    /// Returns the nonce the next call to "next" will hand out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the nonce fetch fails.
+    pub async fn refresh(&self, addr: H160) -> Result<U256> {
+        let slot = self.slot(addr).await;
+        let nonce = self.source.pending_nonce(addr).await?;
+        log::info!("refreshed nonce for {addr} to {nonce}");
+        *slot.lock().await = Some(nonce);
+        Ok(nonce)
+    }
+
+    /// Drops the local nonce, so the next call to "next" refetches it.
+    /// The slot is reset in place rather than removed, so the concurrent
+    /// callers holding it keep serializing with the later ones.
+    pub async fn reset(&self, addr: H160) {
+        let Some(slot) = self.nonces.lock().await.get(&addr).cloned() else {
+            return;
+        };
+        *slot.lock().await = None;
+    }
+
+    /// Resyncs the nonce of the address if the error is "nonce too low".
+    /// Returns true if resynced, in which case the transaction should be
+    /// retried with a new nonce.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the nonce fetch fails.
+    pub async fn resync_on_error(&self, addr: H160, err: &Error) -> Result<bool> {
+        let nonce_too_low =
+            err.rpc_kind() == Some(RpcErrorKind::NonceTooLow) || is_nonce_too_low(&err.message());
+        if !nonce_too_low {
+            return Ok(false);
+        }
+        log::warn!("nonce too low for {addr}, resyncing ('{err}')");
+        self.refresh(addr).await?;
+        Ok(true)
+    }
+}
+
+/// Returns true if the error message indicates the nonce has already been used.
+/// e.g., "nonce too low: address 0xaa30... current nonce (1562) > tx nonce (1561)"
+#[must_use]
+pub fn is_nonce_too_low(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("nonce too low") || msg.contains("nonce has already been used")
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features evm -- `evm::nonce::test_manager` --exact --show-output
+#[test]
+fn test_manager() {
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    /// Source that returns a fixed chain nonce, for testing.
+    struct MockSource {
+        /// Nonce returned by "`pending_nonce`".
+        nonce: std::sync::Mutex<U256>,
+        /// Number of calls to "`pending_nonce`".
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Source for MockSource {
+        async fn pending_nonce(&self, _addr: H160) -> Result<U256> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(*self.nonce.lock().unwrap())
+        }
+    }
+
+    /// Source that never answers for the "stuck" address, for testing.
+    struct StuckSource {
+        /// Address whose fetch never completes.
+        stuck: H160,
+    }
+
+    #[async_trait]
+    impl Source for StuckSource {
+        async fn pending_nonce(&self, addr: H160) -> Result<U256> {
+            if addr == self.stuck {
+                std::future::pending::<()>().await;
+            }
+            Ok(U256::from(1))
+        }
+    }
+
+    let src = Arc::new(MockSource {
+        nonce: std::sync::Mutex::new(U256::from(5)),
+        calls: std::sync::atomic::AtomicUsize::new(0),
+    });
+    let mgr = Arc::new(Manager::new(Arc::clone(&src)));
+    let addr = H160::random();
+
+    assert!(ab!(mgr.peek(addr)).is_none());
+    assert_eq!(ab!(mgr.next(addr)).unwrap(), U256::from(5));
+    assert_eq!(ab!(mgr.next(addr)).unwrap(), U256::from(6));
+    assert_eq!(ab!(mgr.peek(addr)), Some(U256::from(7)));
+    assert_eq!(src.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // concurrent callers never get the same nonce
+    let mut nonces = ab!(async {
+        let mut handles = Vec::new();
+        for _ in 0..32 {
+            let mgr = Arc::clone(&mgr);
+            handles.push(tokio::spawn(async move { mgr.next(addr).await.unwrap() }));
+        }
+        let mut nonces = Vec::new();
+        for h in handles {
+            nonces.push(h.await.unwrap());
+        }
+        nonces
+    });
+    nonces.sort();
+    let expected: Vec<U256> = (7..39).map(U256::from).collect();
+    assert_eq!(nonces, expected);
+    assert_eq!(src.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // other errors do not resync
+    let err = Error::API {
+        message: "replacement transaction underpriced".to_string(),
+        retryable: true,
+    };
+    assert!(!ab!(mgr.resync_on_error(addr, &err)).unwrap());
+    assert_eq!(ab!(mgr.peek(addr)), Some(U256::from(39)));
+
+    // chain moved ahead (e.g., another process used the same key)
+    *src.nonce.lock().unwrap() = U256::from(100);
+    let err = Error::API {
+        message: "failed to send_transaction '(code: -32000, message: nonce too low: address 0xaa3033DB04bE0C31967bfC9D0D01bF04a0038526 current nonce (100) > tx nonce (39), data: None)'".to_string(),
+        retryable: true,
+    };
+    assert!(ab!(mgr.resync_on_error(addr, &err)).unwrap());
+    assert_eq!(ab!(mgr.next(addr)).unwrap(), U256::from(100));
+    assert_eq!(src.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+    ab!(mgr.reset(addr));
+    assert!(ab!(mgr.peek(addr)).is_none());
+    assert_eq!(ab!(mgr.next(addr)).unwrap(), U256::from(100));
+    assert_eq!(src.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+    // a stuck fetch for one address does not block the others
+    let stuck = H160::random();
+    let mgr = Arc::new(Manager::new(Arc::new(StuckSource { stuck })));
+    ab!(async {
+        let stuck_mgr = Arc::clone(&mgr);
+        let handle = tokio::spawn(async move { stuck_mgr.next(stuck).await });
+        tokio::task::yield_now().await;
+
+        let other = tokio::time::timeout(std::time::Duration::from_secs(5), mgr.next(addr))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(other, U256::from(1));
+        assert!(mgr.peek(stuck).await.is_none());
+        handle.abort();
+    });
+}
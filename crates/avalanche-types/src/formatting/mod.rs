@@ -51,6 +51,128 @@ pub fn decode_cb58_with_checksum(s: &str) -> Result<Vec<u8>, Error> {
         })
 }
 
+/// Base58 alphabet of CB58 ("bs58::Alphabet::DEFAULT").
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Largest number of bytes (with checksum) that "`decode_cb58_with_checksum_const`" decodes.
+const MAX_CONST_DECODE_LEN: usize = 128;
+
+/// Decodes CB58 with checksum into the N-byte array at compile time when
+/// used in the constants (e.g., the well-known IDs).
+/// Use [`decode_cb58_with_checksum`] at runtime.
+///
+/// # Panics
+///
+/// Panics (fails the compilation in the constants) if the input is not
+/// valid CB58 encoding of N bytes.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
+pub const fn decode_cb58_with_checksum_const<const N: usize>(s: &str) -> [u8; N] {
+    assert!(
+        N + CHECKSUM_LENGTH <= MAX_CONST_DECODE_LEN,
+        "decoded length too large"
+    );
+    let s = s.as_bytes();
+
+    // little-endian bytes of the base58 number
+    let mut num = [0u8; MAX_CONST_DECODE_LEN];
+    let mut num_len = 0;
+    let mut i = 0;
+    while i < s.len() {
+        let mut digit = 0;
+        while digit < BASE58_ALPHABET.len() && BASE58_ALPHABET[digit] != s[i] {
+            digit += 1;
+        }
+        assert!(digit < BASE58_ALPHABET.len(), "invalid base58 character");
+
+        let mut carry = digit as u32;
+        let mut j = 0;
+        while j < num_len {
+            carry += (num[j] as u32) * 58;
+            num[j] = (carry & 0xff) as u8;
+            carry >>= 8;
+            j += 1;
+        }
+        while carry > 0 {
+            assert!(num_len < MAX_CONST_DECODE_LEN, "base58 input too long");
+            num[num_len] = (carry & 0xff) as u8;
+            num_len += 1;
+            carry >>= 8;
+        }
+        i += 1;
+    }
+
+    // each leading "1" encodes a leading zero byte
+    let mut zeros = 0;
+    while zeros < s.len() && s[zeros] == b'1' {
+        zeros += 1;
+    }
+    assert!(
+        zeros + num_len == N + CHECKSUM_LENGTH,
+        "unexpected decoded length"
+    );
+
+    // big-endian bytes with the leading zeros
+    let mut decoded = [0u8; MAX_CONST_DECODE_LEN];
+    let mut i = 0;
+    while i < num_len {
+        decoded[zeros + i] = num[num_len - 1 - i];
+        i += 1;
+    }
+
+    let mut out = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = decoded[i];
+        i += 1;
+    }
+
+    // checksum is the last 4 bytes of the SHA256 digest
+    let digest = hash::sha256_const(&out);
+    let mut i = 0;
+    while i < CHECKSUM_LENGTH {
+        assert!(
+            decoded[N + i] == digest[32 - CHECKSUM_LENGTH + i],
+            "invalid checksum"
+        );
+        i += 1;
+    }
+    out
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `formatting::test_decode_cb58_with_checksum_const` --exact --show-output
+#[test]
+fn test_decode_cb58_with_checksum_const() {
+    const EMPTY: [u8; 0] = decode_cb58_with_checksum_const("45PJLL");
+    assert!(EMPTY.is_empty());
+
+    let d: [u8; 32] = random_manager::secure_bytes(32)
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let encoded = encode_cb58_with_checksum_string(&d);
+    assert_eq!(decode_cb58_with_checksum_const::<32>(&encoded), d);
+
+    let d: [u8; 20] = random_manager::secure_bytes(20)
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let encoded = encode_cb58_with_checksum_string(&d);
+    assert_eq!(decode_cb58_with_checksum_const::<20>(&encoded), d);
+
+    // leading zero bytes
+    let d = [0, 0, 1, 2, 3];
+    let encoded = encode_cb58_with_checksum_string(&d);
+    assert_eq!(decode_cb58_with_checksum_const::<5>(&encoded), d);
+
+    let bad_checksum = std::panic::catch_unwind(|| {
+        decode_cb58_with_checksum_const::<32>("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vreS")
+    });
+    assert!(bad_checksum.is_err());
+    let bad_len = std::panic::catch_unwind(|| decode_cb58_with_checksum_const::<20>("45PJLL"));
+    assert!(bad_len.is_err());
+}
+
 /// Tests encoding and decoding CB58 with checksum.
 #[test]
 fn test_encode_c58_with_checksum() {
@@ -170,6 +292,43 @@ fn test_encode_hex_with_checksum() {
     assert_eq!(d, decoded);
 }
 
+/// Encoding of the bytes in the API requests and responses (e.g., "issueTx").
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/formatting#Encoding>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Hex,
+    /// Deprecated in avalanchego, only for the nodes that still accept it.
+    Cb58,
+}
+
+impl Encoding {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Cb58 => "cb58",
+        }
+    }
+
+    /// Encodes the bytes with checksum, with the "0x" prefix for hex.
+    #[must_use]
+    pub fn encode(&self, d: &[u8]) -> String {
+        match self {
+            Self::Hex => format!("0x{}", encode_hex_with_checksum(d)),
+            Self::Cb58 => encode_cb58_with_checksum_string(d),
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `formatting::test_encoding` --exact --show-output
+#[test]
+fn test_encoding() {
+    assert_eq!(Encoding::default().as_str(), "hex");
+    assert_eq!(Encoding::Hex.encode(&[0]), "0x0017afa01d");
+    assert_eq!(Encoding::Cb58.encode(&[0]), "1c7hwa");
+}
+
 /// Formats an address with the given chain ID alias, HRP, and bytes.
 ///
 /// # Errors
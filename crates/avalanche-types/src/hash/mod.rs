@@ -1,13 +1,14 @@
 //! Hashing utilities.
 //!
-//! Includes SHA256, SHA256+RIPEMD160, and Keccak256.
-use std::io::{self, Error, ErrorKind};
+//! Includes SHA256, SHA256+RIPEMD160, and Keccak256,
+//! both one-shot and streaming.
+use std::io::{self, Error, ErrorKind, Write};
 
 use ripemd::{Digest, Ripemd160};
 use sha3::Keccak256;
 
 #[cfg(not(windows))]
-use ring::digest::{digest, SHA256};
+use ring::digest::{digest, Context, SHA256};
 
 #[cfg(not(windows))]
 pub const SHA256_OUTPUT_LEN: usize = ring::digest::SHA256_OUTPUT_LEN;
@@ -22,10 +23,271 @@ pub fn sha256(d: impl AsRef<[u8]>) -> Vec<u8> {
 }
 
 /// Returns SHA256 digest of the given data.
-/// TODO: implement this
 #[cfg(windows)]
-pub fn sha256(b: impl AsRef<[u8]>) -> Vec<u8> {
-    panic!("unimplemented")
+pub fn sha256(d: impl AsRef<[u8]>) -> Vec<u8> {
+    sha2::Sha256::digest(d.as_ref()).to_vec()
+}
+
+/// SHA256 round constants.
+/// ref. FIPS 180-4 section 4.2.2
+const SHA256_K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+/// Returns SHA256 digest of the given data, evaluated at compile time.
+///
+/// Used in the constants (e.g., to verify the CB58 checksums of the
+/// well-known IDs). Use [`sha256`] at runtime, which is much faster.
+#[must_use]
+// "a" to "h" (and "w", "t") are the names of FIPS 180-4, kept for review
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_lossless,
+    clippy::many_single_char_names
+)]
+pub const fn sha256_const(d: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+
+    // message, "0x80", zero padding and 64-bit big-endian bit length
+    let bit_len = (d.len() as u64) * 8;
+    let total = (d.len() + 9 + 63) / 64 * 64;
+
+    let mut offset = 0;
+    while offset < total {
+        let mut w = [0u32; 64];
+        let mut i = 0;
+        while i < 64 {
+            let pos = offset + i;
+            let b = if pos < d.len() {
+                d[pos]
+            } else if pos == d.len() {
+                0x80
+            } else if pos >= total - 8 {
+                (bit_len >> (8 * (total - 1 - pos))) as u8
+            } else {
+                0
+            };
+            w[i / 4] |= (b as u32) << (8 * (3 - i % 4));
+            i += 1;
+        }
+
+        let mut t = 16;
+        while t < 64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+            t += 1;
+        }
+
+        let (mut a, mut b, mut c, mut dd, mut e, mut f, mut g, mut hh) =
+            (h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7]);
+        let mut t = 0;
+        while t < 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[t])
+                .wrapping_add(w[t]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = dd.wrapping_add(temp1);
+            dd = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+            t += 1;
+        }
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(dd);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+
+        offset += 64;
+    }
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (h[i / 4] >> (8 * (3 - i % 4))) as u8;
+        i += 1;
+    }
+    out
+}
+
+/// Streaming SHA256 hasher, for data that does not fit in memory
+/// or arrives in chunks.
+///
+/// ```
+/// use avalanche_types::hash;
+///
+/// let mut h = hash::Sha256Hasher::new();
+/// h.update(b"a");
+/// h.update(b"bc");
+/// assert_eq!(h.finalize(), hash::sha256(b"abc"));
+/// ```
+#[derive(Clone)]
+pub struct Sha256Hasher {
+    /// running digest state
+    #[cfg(not(windows))]
+    ctx: Context,
+    /// running digest state
+    #[cfg(windows)]
+    ctx: sha2::Sha256,
+}
+
+impl Sha256Hasher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            #[cfg(not(windows))]
+            ctx: Context::new(&SHA256),
+            #[cfg(windows)]
+            ctx: sha2::Sha256::new(),
+        }
+    }
+
+    /// Feeds the data into the hasher.
+    pub fn update(&mut self, d: impl AsRef<[u8]>) {
+        self.ctx.update(d.as_ref());
+    }
+
+    /// Consumes the hasher and returns the SHA256 digest.
+    #[cfg(not(windows))]
+    #[must_use]
+    pub fn finalize(self) -> Vec<u8> {
+        self.ctx.finish().as_ref().to_vec()
+    }
+
+    /// Consumes the hasher and returns the SHA256 digest.
+    #[cfg(windows)]
+    #[must_use]
+    pub fn finalize(self) -> Vec<u8> {
+        self.ctx.finalize().to_vec()
+    }
+}
+
+impl Default for Sha256Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Allows "`io::copy`" from a reader into the hasher.
+impl Write for Sha256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `hash::test_sha256_hasher` --exact --show-output
+#[test]
+fn test_sha256_hasher() {
+    // ref. FIPS 180-2 "abc"
+    let expected =
+        hex::decode("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap();
+    assert_eq!(sha256(b"abc"), expected);
+
+    let mut h = Sha256Hasher::new();
+    h.update(b"a");
+    h.update(b"");
+    h.update(b"bc");
+    assert_eq!(h.finalize(), expected);
+
+    let data = random_manager::secure_bytes(4096).unwrap();
+    let mut h = Sha256Hasher::default();
+    io::copy(&mut io::Cursor::new(&data), &mut h).unwrap();
+    assert_eq!(h.finalize(), sha256(&data));
 }
 
 /// e.g., "hashing.PubkeyBytesToAddress" and "ids.ToShortID"
@@ -79,6 +341,67 @@ pub fn keccak256(b: impl AsRef<[u8]>) -> primitive_types::H256 {
     primitive_types::H256::from_slice(&Keccak256::digest(b.as_ref()))
 }
 
+/// Streaming Keccak256 hasher (the pre-standard SHA3 variant used by the EVM).
+#[derive(Clone, Default)]
+pub struct Keccak256Hasher {
+    /// running digest state
+    ctx: Keccak256,
+}
+
+impl Keccak256Hasher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the data into the hasher.
+    pub fn update(&mut self, d: impl AsRef<[u8]>) {
+        Digest::update(&mut self.ctx, d.as_ref());
+    }
+
+    /// Consumes the hasher and returns the Keccak256 digest.
+    #[must_use]
+    pub fn finalize(self) -> primitive_types::H256 {
+        primitive_types::H256::from_slice(&self.ctx.finalize())
+    }
+}
+
+/// Allows "`io::copy`" from a reader into the hasher.
+impl Write for Keccak256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `hash::test_keccak256_hasher` --exact --show-output
+#[test]
+fn test_keccak256_hasher() {
+    // empty input
+    assert_eq!(
+        hex::encode(keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+
+    // ERC-20 "Transfer" event log topic
+    let mut h = Keccak256Hasher::new();
+    h.update("Transfer(address,");
+    h.update("address,uint256)");
+    assert_eq!(
+        hex::encode(h.finalize()),
+        "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+    );
+
+    let data = random_manager::secure_bytes(4096).unwrap();
+    let mut h = Keccak256Hasher::default();
+    io::copy(&mut io::Cursor::new(&data), &mut h).unwrap();
+    assert_eq!(h.finalize(), keccak256(&data));
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `hash::test_keccak256` --exact --show-output
 #[test]
 fn test_keccak256() {
@@ -97,3 +420,20 @@ fn test_keccak256() {
     let d2 = primitive_types::H256::from_slice(&d2);
     assert_eq!(d1, d2);
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `hash::test_sha256_const` --exact --show-output
+#[test]
+fn test_sha256_const() {
+    // evaluated at compile time
+    const ABC: [u8; 32] = sha256_const(b"abc");
+    assert_eq!(
+        hex::encode(ABC),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    // around the padding boundaries
+    for n in [0, 1, 55, 56, 63, 64, 65, 119, 120, 1000] {
+        let d = random_manager::secure_bytes(n).unwrap();
+        assert_eq!(sha256_const(&d).to_vec(), sha256(&d), "length {n}");
+    }
+}
@@ -125,6 +125,23 @@ impl Bag {
         }
     }
 
+    /// Creates a new bag with each of the Ids added once.
+    /// ref. "avalanchego/utils/bag.Of"
+    #[must_use]
+    pub fn of(ids: &[Id]) -> Self {
+        let bag = Self::new();
+        bag.add(ids);
+        bag
+    }
+
+    /// Adds each of the Ids once.
+    /// ref. "avalanchego/utils/bag.Bag.Add"
+    pub fn add(&self, ids: &[Id]) {
+        for id in ids {
+            self.add_count(id, 1);
+        }
+    }
+
     pub fn add_count(&self, id: &Id, count: u32) {
         if count == 0 {
             return;
@@ -154,6 +171,29 @@ impl Bag {
         *current_count
     }
 
+    /// Removes all counts of the Id from the bag, and updates the mode
+    /// if the removed Id was the mode.
+    /// ref. "avalanchego/utils/bag.Bag.Remove"
+    pub fn remove(&self, id: &Id) {
+        let Some(count) = self.counts.borrow_mut().remove(id) else {
+            return;
+        };
+        self.size.set(self.size.get() - count);
+        self.met_threshold.borrow_mut().remove(id);
+
+        if self.mode.get() != *id {
+            return;
+        }
+        let (mode, mode_freq) = self
+            .counts
+            .borrow()
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map_or((Id::empty(), 0), |(id, count)| (*id, *count));
+        self.mode.set(mode);
+        self.mode_freq.set(mode_freq);
+    }
+
     #[must_use]
     pub fn equals(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -212,6 +252,20 @@ impl Bag {
     }
 }
 
+/// ref. <https://doc.rust-lang.org/std/fmt/trait.Display.html>
+/// Use `Self.to_string()` to directly invoke this.
+impl std::fmt::Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bag: (Size = {})", self.len())?;
+        let mut ids = self.list();
+        ids.sort();
+        for id in ids {
+            write!(f, "\n    ID[{}]: Count = {}", id, self.count(&id))?;
+        }
+        Ok(())
+    }
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
 /// `ids::bag::test_bag_add` --exact --show-output ref. "`TestBagAdd`"
 #[test]
@@ -342,6 +396,114 @@ fn test_bag_split() {
     assert_eq!(odds.count(&id2), 0);
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::bag::test_bag_of` --exact --show-output ref. "`TestBagOf`"
+#[test]
+fn test_bag_of() {
+    let id0 = Id::empty();
+    let id1 = Id::from_slice(&[1_u8]);
+
+    let bag = Bag::of(&[id0, id1, id1]);
+    assert_eq!(bag.len(), 3);
+    assert_eq!(bag.count(&id0), 1);
+    assert_eq!(bag.count(&id1), 2);
+    assert_eq!(bag.mode(), id1);
+    assert_eq!(bag.mode_frequency(), 2);
+
+    bag.add(&[id0, id0]);
+    assert_eq!(bag.count(&id0), 3);
+    assert_eq!(bag.mode(), id0);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::bag::test_bag_equals` --exact --show-output ref. "`TestBagEquals`"
+#[test]
+fn test_bag_equals() {
+    let id0 = Id::empty();
+    let id1 = Id::from_slice(&[1_u8]);
+
+    let bag1 = Bag::new();
+    let bag2 = Bag::new();
+    assert!(bag1.equals(&bag2));
+
+    bag1.add_count(&id0, 2);
+    assert!(!bag1.equals(&bag2));
+
+    bag2.add(&[id0, id0]);
+    assert!(bag1.equals(&bag2));
+
+    // same size, different Ids
+    bag1.add_count(&id1, 1);
+    bag2.add_count(&id0, 1);
+    assert!(!bag1.equals(&bag2));
+    assert!(!bag2.equals(&bag1));
+
+    let cloned = bag1.clone();
+    assert!(cloned.equals(&bag1));
+    cloned.add_count(&id1, 1);
+    assert!(!cloned.equals(&bag1));
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::bag::test_bag_remove` --exact --show-output ref. "`TestBagRemove`"
+#[test]
+fn test_bag_remove() {
+    let id0 = Id::empty();
+    let id1 = Id::from_slice(&[1_u8]);
+    let id2 = Id::from_slice(&[2_u8]);
+
+    let bag = Bag::new();
+    bag.set_threshold(2);
+
+    // removing from an empty bag is a no-op
+    bag.remove(&id0);
+    assert_eq!(bag.len(), 0);
+    assert_eq!(bag.mode(), Id::empty());
+    assert_eq!(bag.mode_frequency(), 0);
+
+    bag.add_count(&id0, 1);
+    bag.add_count(&id1, 2);
+    bag.add_count(&id2, 3);
+    assert_eq!(bag.len(), 6);
+    assert_eq!(bag.mode(), id2);
+    assert_eq!(bag.met_threshold().len(), 2);
+
+    // removing the mode picks the next highest count
+    bag.remove(&id2);
+    assert_eq!(bag.count(&id2), 0);
+    assert_eq!(bag.len(), 3);
+    assert_eq!(bag.mode(), id1);
+    assert_eq!(bag.mode_frequency(), 2);
+    assert_eq!(bag.met_threshold().len(), 1);
+    assert!(bag.met_threshold().contains(&id1));
+
+    // removing the non-mode keeps the mode
+    bag.remove(&id0);
+    assert_eq!(bag.len(), 2);
+    assert_eq!(bag.mode(), id1);
+    assert_eq!(bag.list(), vec![id1]);
+
+    bag.remove(&id1);
+    assert!(bag.is_empty());
+    assert_eq!(bag.mode(), Id::empty());
+    assert_eq!(bag.mode_frequency(), 0);
+    assert!(bag.met_threshold().is_empty());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::bag::test_bag_string` --exact --show-output ref. "`TestBagString`"
+#[test]
+fn test_bag_string() {
+    let id0 = Id::empty();
+
+    let bag = Bag::new();
+    bag.add_count(&id0, 1337);
+    assert_eq!(
+        bag.to_string(),
+        "Bag: (Size = 1337)\n    ID[11111111111111111111111111111111LpoYY]: Count = 1337"
+    );
+}
+
 const MIN_UNIQUE_BAG_SIZE: usize = 16;
 
 /// Maps from an Id to the `BitSet`.
@@ -422,6 +584,40 @@ impl Unique {
     pub fn clear(&self) {
         self.0.borrow_mut().clear();
     }
+
+    /// Returns true if both bags have the same sets for the same Ids.
+    /// Ids with empty sets are treated as missing.
+    #[must_use]
+    pub fn equals(&self, other: &Self) -> bool {
+        let this = self.0.borrow();
+        let that = other.0.borrow();
+        let non_empty = |m: &HashMap<Id, Rc<RefCell<bits::Set64>>>| {
+            m.values().filter(|s| !s.borrow().is_empty()).count()
+        };
+        if non_empty(&this) != non_empty(&that) {
+            return false;
+        }
+        this.iter()
+            .filter(|(_, s)| !s.borrow().is_empty())
+            .all(|(id, s)| other.get_set(id) == *s.borrow())
+    }
+
+    /// Copies the underlying sets, so the new bag can be
+    /// updated without affecting the original one.
+    #[must_use]
+    pub fn deep_copy(&self) -> Self {
+        let copied = Self::new();
+        for (id, set) in self.0.borrow().iter() {
+            copied.union_set(*id, *set.borrow());
+        }
+        copied
+    }
+}
+
+impl Clone for Unique {
+    fn clone(&self) -> Self {
+        self.deep_copy()
+    }
 }
 
 impl Default for Unique {
@@ -513,6 +709,37 @@ fn test_unique_bag() {
     assert!(ub6_id1.contains(2));
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::bag::test_unique_bag_equals` --exact --show-output
+#[test]
+fn test_unique_bag_equals() {
+    let id1 = Id::empty().prefix(&[1_u64]).unwrap();
+    let id2 = Id::empty().prefix(&[2_u64]).unwrap();
+
+    let b1 = Unique::new();
+    let b2 = Unique::new();
+    assert!(b1.equals(&b2));
+
+    b1.add(1, &[id1, id2]);
+    assert!(!b1.equals(&b2));
+
+    b2.add(1, &[id2]);
+    assert!(!b1.equals(&b2));
+    b2.add(1, &[id1]);
+    assert!(b1.equals(&b2));
+    assert!(b2.equals(&b1));
+
+    // empty sets are ignored
+    b2.union_set(Id::empty(), bits::Set64::new());
+    assert!(b1.equals(&b2));
+
+    let b3 = b1.clone();
+    assert!(b3.equals(&b1));
+    b3.add(2, &[id1]);
+    assert!(!b3.equals(&b1));
+    assert_eq!(b1.get_set(&id1).len(), 1);
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
 /// `ids::bag::test_unique_bag_clear` --exact --show-output
 /// ref. "`TestUniqueBagClear`"
@@ -12,13 +12,17 @@
 pub mod bag;
 pub mod bits;
 pub mod node;
+pub mod serde_cb58;
+pub mod serde_hex;
 pub mod short;
 
 use std::{
+    borrow::Borrow,
     cmp::Ordering,
     collections::HashSet,
     fmt,
     hash::{Hash, Hasher},
+    ops::Deref,
     str::FromStr,
 };
 
@@ -59,6 +63,24 @@ impl Id {
         (*self) == Self::empty()
     }
 
+    /// Creates the Id from the 32-byte array, usable in the constants.
+    #[must_use]
+    pub const fn from_bytes_const(b: [u8; LEN]) -> Self {
+        Self(b)
+    }
+
+    /// Parses the CB58-encoded Id, usable in the constants.
+    /// Prefer the [`id!`](crate::id) macro, which always parses at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics (fails the compilation in the constants) if the string is not
+    /// a valid CB58-encoded Id.
+    #[must_use]
+    pub const fn from_cb58_const(s: &str) -> Self {
+        Self(formatting::decode_cb58_with_checksum_const(s))
+    }
+
     #[must_use]
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_vec()
@@ -106,6 +128,36 @@ impl Id {
         Ok(Self::from_slice(&d))
     }
 
+    /// Parses the hex-encoded 32-byte Id, with or without the "0x" prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid hex or not 32 bytes.
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let d = hex::decode(s).map_err(|e| Error::Other {
+            message: format!("failed hex::decode '{e}'"),
+            retryable: false,
+        })?;
+        if d.len() != LEN {
+            return Err(Error::Other {
+                message: format!("invalid Id length {} (expected {LEN})", d.len()),
+                retryable: false,
+            });
+        }
+        Ok(Self::from_slice(&d))
+    }
+
+    /// Returns the "0x"-prefixed hex encoding of the Id.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.0))
+    }
+
     /// Returns the bit value at the i-th index of the byte array (0 or 1).
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/ids#ID.Bit>
     ///
@@ -135,12 +187,86 @@ impl Id {
     }
 }
 
+/// Parses the CB58-encoded [`Id`](crate::ids::Id) literal at compile time,
+/// so the well-known IDs can be embedded as constants. An invalid literal
+/// fails the compilation.
+///
+/// ```
+/// use avalanche_types::{id, ids};
+///
+/// const EMPTY: ids::Id = id!("11111111111111111111111111111111LpoYY");
+/// assert_eq!(EMPTY, ids::Id::empty());
+/// ```
+#[macro_export]
+macro_rules! id {
+    ($s:literal) => {{
+        const ID: $crate::ids::Id = $crate::ids::Id::from_cb58_const($s);
+        ID
+    }};
+}
+
 impl AsRef<[u8]> for Id {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
+/// Hashes and compares same as the bytes, so the byte slice can look up
+/// the Id keys (e.g., the raw Ids of the protobuf messages).
+impl Borrow<[u8]> for Id {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for Id {
+    type Target = [u8; LEN];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; LEN]> for Id {
+    fn from(b: [u8; LEN]) -> Self {
+        Self(b)
+    }
+}
+
+impl From<Id> for [u8; LEN] {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+/// Unlike `from_slice`, fails on the bytes of the wrong length.
+impl TryFrom<&[u8]> for Id {
+    type Error = Error;
+
+    fn try_from(b: &[u8]) -> Result<Self> {
+        let b: [u8; LEN] = b.try_into().map_err(|_| Error::Other {
+            message: format!("invalid Id length {} (expected {LEN})", b.len()),
+            retryable: false,
+        })?;
+        Ok(Self(b))
+    }
+}
+
+impl From<Id> for bytes::Bytes {
+    fn from(id: Id) -> Self {
+        Self::copy_from_slice(&id.0)
+    }
+}
+
+/// Zero-pads the short Id to 32 bytes, same as `from_slice`.
+impl From<short::Id> for Id {
+    fn from(id: short::Id) -> Self {
+        let mut b = [0; LEN];
+        b[..short::LEN].copy_from_slice(id.as_ref());
+        Self(b)
+    }
+}
+
 /// ref. <https://doc.rust-lang.org/std/string/trait.ToString.html>
 /// ref. <https://doc.rust-lang.org/std/fmt/trait.Display.html>
 /// Use `Self.to_string()` to directly invoke this.
@@ -257,6 +383,31 @@ fn test_custom_de_serializer() {
     assert_eq!(d, json_decoded_3);
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::test_hex` --exact --show-output
+#[test]
+fn test_hex() {
+    let id = Id::from_str("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres").unwrap();
+    let h = id.to_hex();
+    assert_eq!(h.len(), 2 + LEN * 2);
+    assert!(h.starts_with("0x"));
+    assert_eq!(Id::from_hex(&h).unwrap(), id);
+    assert_eq!(Id::from_hex(h.trim_start_matches("0x")).unwrap(), id);
+    assert_eq!(
+        Id::from_hex(&h.to_uppercase().replace("0X", "0x")).unwrap(),
+        id
+    );
+
+    assert_eq!(
+        Id::empty().to_hex(),
+        "0x0000000000000000000000000000000000000000000000000000000000000000"
+    );
+
+    // too short
+    assert!(Id::from_hex("0x0102").is_err());
+    // not hex
+    assert!(Id::from_hex("0xzz").is_err());
+}
+
 fn fmt_id<'de, D>(deserializer: D) -> std::result::Result<Id, D::Error>
 where
     D: Deserializer<'de>,
@@ -673,3 +824,41 @@ fn test_vm_id() {
     let v = encode_vm_name_to_id(&contents).expect("failed to generate id from str");
     info!("vm_id_from_str: {v}");
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::test_const` --exact --show-output
+#[test]
+fn test_const() {
+    const ID: Id = crate::id!("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres");
+    const EMPTY: Id = Id::from_bytes_const([0; LEN]);
+    assert_eq!(
+        ID,
+        Id::from_str("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres").unwrap()
+    );
+    assert_eq!(Id::from_bytes_const(ID.0), ID);
+    assert_eq!(EMPTY, crate::id!("11111111111111111111111111111111LpoYY"));
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::test_conversions` --exact --show-output
+#[test]
+fn test_conversions() {
+    use std::collections::HashMap;
+
+    let b = [7; LEN];
+    let id = Id::from(b);
+    assert_eq!(<[u8; LEN]>::from(id), b);
+    assert_eq!(*id, b);
+    assert_eq!(id.len(), LEN);
+    assert_eq!(Id::try_from(&b[..]).unwrap(), id);
+    assert!(Id::try_from(&b[..20]).is_err());
+    assert_eq!(bytes::Bytes::from(id), bytes::Bytes::copy_from_slice(&b));
+
+    // looks up by the raw bytes
+    let m = HashMap::from([(id, 1)]);
+    assert_eq!(m.get(&b[..]), Some(&1));
+
+    let short_id = short::Id::from([1; short::LEN]);
+    let padded = Id::from(short_id.clone());
+    assert_eq!(padded, Id::from_slice(&[1; short::LEN]));
+    assert_eq!(short::Id::try_from(padded).unwrap(), short_id);
+    assert!(short::Id::try_from(id).is_err());
+}
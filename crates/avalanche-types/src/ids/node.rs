@@ -1,10 +1,12 @@
 //! Node ID utilities.
 use std::{
+    borrow::Borrow,
     cmp::Ordering,
     collections::HashSet,
     fmt,
     hash::{Hash, Hasher},
     io::{self, Error, ErrorKind},
+    ops::Deref,
     path::Path,
     str::FromStr,
 };
@@ -94,6 +96,58 @@ impl Id {
         Ok(node_id)
     }
 
+    /// Encodes the PEM-encoded certificate (chain) in memory to a node ID,
+    /// with the first (leaf) certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM fails to parse or has no certificate.
+    pub fn from_cert_pem(cert_pem: &str) -> io::Result<Self> {
+        for pem in x509_parser::pem::Pem::iter_from_buffer(cert_pem.as_bytes()) {
+            let pem = pem.map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("failed to read PEM {e}"))
+            })?;
+            if pem.label == "CERTIFICATE" {
+                return Self::from_cert_der_bytes(&pem.contents);
+            }
+            log::warn!("skipping unexpected PEM '{}'", pem.label);
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            "no certificate found in PEM".to_string(),
+        ))
+    }
+
+    /// Encodes the DER-encoded certificate chain (e.g., the "rustls"
+    /// "`CertificateDer`" chain of the TLS handshake) to a node ID,
+    /// with the first (leaf) certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain is empty.
+    pub fn from_cert_der_chain<S>(chain: &[S]) -> io::Result<Self>
+    where
+        S: AsRef<[u8]>,
+    {
+        let leaf = chain.first().ok_or_else(|| {
+            Error::new(ErrorKind::NotFound, "empty certificate chain".to_string())
+        })?;
+        Self::from_cert_der_bytes(leaf)
+    }
+
+    /// Returns the node ID of the VM "Initialize" request, which carries
+    /// the node ID already derived from the staking certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node ID is not 20 bytes.
+    #[cfg(feature = "proto")]
+    pub fn from_initialize_request(
+        req: &crate::proto::pb::vm::InitializeRequest,
+    ) -> io::Result<Self> {
+        Self::try_from(req.node_id.as_ref())
+    }
+
     /// Loads the existing staking certificates if exists,
     /// and returns the loaded or generated node Id.
     /// Returns "true" if generated.
@@ -125,6 +179,28 @@ impl Id {
         Ok((node_id, generated))
     }
 
+    /// Rotates the staking certificates with a newly generated pair
+    /// (issued by the CA if given), keeping the old pair with the ".bak" suffix,
+    /// and returns the new node Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new pair fails to be generated or swapped.
+    pub fn rotate_pem(
+        key_path: &str,
+        cert_path: &str,
+        ca: Option<&cert_manager::x509::Ca>,
+    ) -> io::Result<Self> {
+        let manager = cert_manager::CertManager::new(
+            &cert_manager::CertConfig::new().paths(key_path, cert_path),
+        );
+        let rotated = manager.rotate(ca)?;
+
+        let node_id = Self::from_cert_der_bytes(&rotated.cert_der)?;
+        log::info!("rotated staking TLS certificates to node Id {node_id}");
+        Ok(node_id)
+    }
+
     #[must_use]
     pub fn short_id(&self) -> short::Id {
         short::Id::from_slice(&self.0)
@@ -137,6 +213,66 @@ impl AsRef<[u8]> for Id {
     }
 }
 
+impl Borrow<[u8]> for Id {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for Id {
+    type Target = [u8; LEN];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; LEN]> for Id {
+    fn from(b: [u8; LEN]) -> Self {
+        Self(b)
+    }
+}
+
+impl From<Id> for [u8; LEN] {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+/// Unlike `from_slice`, fails (instead of panics) on the bytes of the wrong length.
+impl TryFrom<&[u8]> for Id {
+    type Error = io::Error;
+
+    fn try_from(b: &[u8]) -> io::Result<Self> {
+        let b: [u8; LEN] = b.try_into().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid node Id length {} (expected {LEN})", b.len()),
+            )
+        })?;
+        Ok(Self(b))
+    }
+}
+
+impl From<Id> for bytes::Bytes {
+    fn from(id: Id) -> Self {
+        Self::copy_from_slice(&id.0)
+    }
+}
+
+/// Node Ids are the short Ids of the staking certificates.
+impl From<short::Id> for Id {
+    fn from(id: short::Id) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<Id> for short::Id {
+    fn from(id: Id) -> Self {
+        Self::from(id.0)
+    }
+}
+
 /// ref. <https://doc.rust-lang.org/std/string/trait.ToString.html>
 /// ref. <https://doc.rust-lang.org/std/fmt/trait.Display.html>
 /// Use `Self.to_string()` to directly invoke this.
@@ -431,6 +567,12 @@ mod cert_file_tests {
         assert_eq!(format!("{node_id}"), expected_id);
         assert_eq!(node_id.to_string(), expected_id);
 
+        // same from the PEM and the DER chain in memory
+        let cert_pem = std::fs::read_to_string(cert_path).unwrap();
+        assert_eq!(Id::from_cert_pem(&cert_pem).unwrap(), node_id);
+        let cert_der = cert_manager::x509::load_pem_cert_to_der(cert_path).unwrap();
+        assert_eq!(Id::from_cert_der_chain(&[cert_der]).unwrap(), node_id);
+
         // Test with and without prefix
         let short_id = expected_id.trim_start_matches("NodeID-");
         assert_eq!(node_id, Id::from_str(short_id).unwrap());
@@ -518,6 +660,29 @@ mod cert_file_tests {
             "NodeID-29HTAG5cfN2fw79A67Jd5zY9drcT51EBG",
         );
     }
+
+    /// Test node ID changes with the rotated staking certificates.
+    #[test]
+    fn test_rotate_pem() {
+        init_logger();
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let key_path = tmp_dir.path().join("staker.key");
+        let key_path = key_path.to_str().unwrap();
+        let cert_path = tmp_dir.path().join("staker.crt");
+        let cert_path = cert_path.to_str().unwrap();
+
+        let (old_node_id, generated) = Id::load_or_generate_pem(key_path, cert_path).unwrap();
+        assert!(generated);
+
+        let new_node_id = Id::rotate_pem(key_path, cert_path, None).unwrap();
+        assert_ne!(old_node_id, new_node_id);
+        assert_eq!(new_node_id, Id::from_cert_pem_file(cert_path).unwrap());
+        assert_eq!(
+            old_node_id,
+            Id::from_cert_pem_file(&format!("{cert_path}.bak")).unwrap()
+        );
+    }
 }
 
 impl Ord for Id {
@@ -674,3 +839,20 @@ mod sort_tests {
         assert_eq!(ids, expected);
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::node::test_conversions` --exact --show-output
+#[test]
+fn test_conversions() {
+    let b = [3; LEN];
+    let node_id = Id::from(b);
+    assert_eq!(<[u8; LEN]>::from(node_id), b);
+    assert_eq!(Id::try_from(&b[..]).unwrap(), node_id);
+    assert!(Id::try_from(&b[..LEN - 1]).is_err());
+
+    let short_id = short::Id::from(node_id);
+    assert_eq!(short_id, node_id.short_id());
+    assert_eq!(Id::from(short_id), node_id);
+
+    assert!(Id::from_cert_pem("").is_err());
+    assert!(Id::from_cert_der_chain::<Vec<u8>>(&[]).is_err());
+}
@@ -0,0 +1,111 @@
+//! Serializes "`ids::Id`" as the CB58 string with checksum,
+//! the default encoding of the Avalanche JSON-RPC APIs.
+//!
+//! Same as the default "Serialize" of "`ids::Id`", but can be selected
+//! explicitly per field next to "`ids::serde_hex`".
+//!
+//! ```
+//! use avalanche_types::ids;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Data {
+//!     #[serde(with = "ids::serde_cb58")]
+//!     id: ids::Id,
+//! }
+//! ```
+use std::str::FromStr;
+
+use serde::{self, Deserialize, Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::ids::Id;
+
+/// Serializes the Id as the CB58 string with checksum.
+///
+/// # Errors
+///
+/// Returns an error if the serializer fails.
+pub fn serialize<S>(x: &Id, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&x.to_string())
+}
+
+/// Deserializes the Id from the CB58 string with checksum.
+///
+/// # Errors
+///
+/// Returns an error if the string is not a valid CB58 string.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Id, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Id::from_str(&s).map_err(serde::de::Error::custom)
+}
+
+/// For use with "`serde_with`", e.g., "`#[serde_as(as = "Vec<Cb58Id>")]`".
+pub struct Cb58Id;
+
+impl SerializeAs<Id> for Cb58Id {
+    fn serialize_as<S>(x: &Id, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(x, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Id> for Cb58Id {
+    fn deserialize_as<D>(deserializer: D) -> Result<Id, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::serde_cb58::test_custom_de_serializer` --exact --show-output
+#[test]
+fn test_custom_de_serializer() {
+    use serde::Serialize;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+    struct Data {
+        #[serde(with = "crate::ids::serde_cb58")]
+        id: Id,
+        #[serde_as(as = "Option<Cb58Id>")]
+        maybe_id: Option<Id>,
+    }
+
+    let id = Id::from_str("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres").unwrap();
+    let d = Data {
+        id,
+        maybe_id: Some(id),
+    };
+
+    let json_encoded = serde_json::to_string(&d).unwrap();
+    assert_eq!(
+        json_encoded,
+        "{\"id\":\"g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres\",\"maybe_id\":\"g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres\"}"
+    );
+    let json_decoded: Data = serde_json::from_str(&json_encoded).unwrap();
+    assert_eq!(d, json_decoded);
+
+    // same as the default serializer
+    assert_eq!(
+        serde_json::to_string(&id).unwrap(),
+        "\"g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres\""
+    );
+
+    // hex is rejected
+    assert!(serde_json::from_str::<Data>(&format!(
+        "{{\"id\":\"{}\",\"maybe_id\":null}}",
+        id.to_hex()
+    ))
+    .is_err());
+}
@@ -0,0 +1,115 @@
+//! Serializes "`ids::Id`" as the "0x"-prefixed hex string,
+//! for EVM-facing APIs and indexers.
+//!
+//! ```
+//! use avalanche_types::ids;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Data {
+//!     #[serde(with = "ids::serde_hex")]
+//!     id: ids::Id,
+//! }
+//! ```
+use serde::{self, Deserialize, Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::ids::Id;
+
+/// Serializes the Id as the "0x"-prefixed hex string.
+///
+/// # Errors
+///
+/// Returns an error if the serializer fails.
+pub fn serialize<S>(x: &Id, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&x.to_hex())
+}
+
+/// Deserializes the Id from the hex string, with or without the "0x" prefix.
+///
+/// # Errors
+///
+/// Returns an error if the string is not a valid 32-byte hex string.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Id, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Id::from_hex(&s).map_err(serde::de::Error::custom)
+}
+
+/// For use with "`serde_with`", e.g., "`#[serde_as(as = "Vec<HexId>")]`".
+pub struct HexId;
+
+impl SerializeAs<Id> for HexId {
+    fn serialize_as<S>(x: &Id, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(x, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Id> for HexId {
+    fn deserialize_as<D>(deserializer: D) -> Result<Id, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `ids::serde_hex::test_custom_de_serializer` --exact --show-output
+#[test]
+fn test_custom_de_serializer() {
+    use serde::Serialize;
+    use serde_with::serde_as;
+    use std::str::FromStr;
+
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+    struct Data {
+        #[serde(with = "crate::ids::serde_hex")]
+        id: Id,
+        #[serde_as(as = "Vec<HexId>")]
+        ids: Vec<Id>,
+        /// Default CB58 encoding.
+        cb58: Id,
+    }
+
+    let id = Id::from_str("g25v3qDyAaHfR7kBev8tLUHouSgN5BJuZjy1BYS1oiHd2vres").unwrap();
+    let d = Data {
+        id,
+        ids: vec![id, Id::empty()],
+        cb58: id,
+    };
+
+    let json_encoded = serde_json::to_string(&d).unwrap();
+    println!("json_encoded:\n{json_encoded}");
+    assert_eq!(
+        json_encoded,
+        format!(
+            "{{\"id\":\"{}\",\"ids\":[\"{}\",\"0x{}\"],\"cb58\":\"{}\"}}",
+            id.to_hex(),
+            id.to_hex(),
+            "0".repeat(64),
+            id
+        )
+    );
+    let json_decoded: Data = serde_json::from_str(&json_encoded).unwrap();
+    assert_eq!(d, json_decoded);
+
+    let yaml_encoded = serde_yaml::to_string(&d).unwrap();
+    println!("yaml_encoded:\n{yaml_encoded}");
+    let yaml_decoded: Data = serde_yaml::from_str(&yaml_encoded).unwrap();
+    assert_eq!(d, yaml_decoded);
+
+    // CB58 is rejected
+    assert!(serde_json::from_str::<Data>(&format!(
+        "{{\"id\":\"{id}\",\"ids\":[],\"cb58\":\"{id}\"}}"
+    ))
+    .is_err());
+}
@@ -1,14 +1,17 @@
 //! Node short ID used in `AvalancheGo`.
 use std::{
+    borrow::Borrow,
     cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
     io::{self, Error, ErrorKind},
+    ops::Deref,
     str::FromStr,
 };
 
-use crate::{formatting, hash, key::secp256k1};
+use crate::{formatting, hash, ids, key::secp256k1};
 use lazy_static::lazy_static;
+use primitive_types::H160;
 use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
 use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 
@@ -74,6 +77,58 @@ impl Id {
         let encoded = formatting::encode_cb58_with_checksum_string(&hashed);
         Self::from_str(&encoded)
     }
+
+    /// Encodes the short Id as the bech32 address with the chain alias prefix.
+    /// e.g., "X-avax1..." for the chain alias "X" and HRP "avax".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bech32 encoding fails (e.g., invalid HRP).
+    pub fn to_address(&self, hrp: &str, chain_id_alias: &str) -> io::Result<String> {
+        formatting::address(chain_id_alias, hrp, &self.0)
+    }
+
+    /// Decodes the short Id from the bech32 address,
+    /// with or without the chain alias prefix (e.g., "P-avax1...").
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is not valid bech32 or not 20 bytes.
+    pub fn from_address(addr: &str) -> io::Result<Self> {
+        let addr = addr.trim();
+        let chain_alias = addr.split_once('-').map_or("", |(alias, _)| alias);
+        let (_, short_bytes) = secp256k1::address::avax_address_to_short_bytes(chain_alias, addr)?;
+        if short_bytes.len() != LEN {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "invalid short address length {} (expected {LEN})",
+                    short_bytes.len()
+                ),
+            ));
+        }
+        Ok(Self::from_slice(&short_bytes))
+    }
+
+    /// Returns the short Id as the EVM address bytes.
+    /// Note that the EVM address of a key is derived differently
+    /// (Keccak256 instead of SHA256+RIPEMD160), so this is a byte-level conversion.
+    #[must_use]
+    pub const fn to_h160(&self) -> H160 {
+        H160(self.0)
+    }
+}
+
+impl From<H160> for Id {
+    fn from(h: H160) -> Self {
+        Self(h.0)
+    }
+}
+
+impl From<Id> for H160 {
+    fn from(id: Id) -> Self {
+        id.to_h160()
+    }
 }
 
 impl AsRef<[u8]> for Id {
@@ -82,6 +137,71 @@ impl AsRef<[u8]> for Id {
     }
 }
 
+impl Borrow<[u8]> for Id {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for Id {
+    type Target = [u8; LEN];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; LEN]> for Id {
+    fn from(b: [u8; LEN]) -> Self {
+        Self(b)
+    }
+}
+
+impl From<Id> for [u8; LEN] {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+/// Unlike `from_slice`, fails on the bytes of the wrong length.
+impl TryFrom<&[u8]> for Id {
+    type Error = io::Error;
+
+    fn try_from(b: &[u8]) -> io::Result<Self> {
+        let b: [u8; LEN] = b.try_into().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid short Id length {} (expected {LEN})", b.len()),
+            )
+        })?;
+        Ok(Self(b))
+    }
+}
+
+impl From<Id> for bytes::Bytes {
+    fn from(id: Id) -> Self {
+        Self::copy_from_slice(&id.0)
+    }
+}
+
+/// Truncates the 32-byte Id to its first 20 bytes, only if the truncated
+/// bytes are all zeros (e.g., the short Id zero-padded with
+/// `ids::Id::from_slice`), so the conversion never drops the data.
+impl TryFrom<ids::Id> for Id {
+    type Error = io::Error;
+
+    fn try_from(id: ids::Id) -> io::Result<Self> {
+        let (b, truncated) = id.split_at(LEN);
+        if truncated.iter().any(|x| *x != 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Id {id} has non-zero bytes after the first {LEN} bytes"),
+            ));
+        }
+        Self::try_from(b)
+    }
+}
+
 /// ref. <https://doc.rust-lang.org/std/string/trait.ToString.html>
 /// ref. <https://doc.rust-lang.org/std/fmt/trait.Display.html>
 /// Use `Self.to_string()` to directly invoke this.
@@ -326,6 +446,34 @@ fn test_id() {
     assert_eq!(id, id_from_str);
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --
+/// `ids::short::test_address` --exact --show-output
+#[test]
+fn test_address() {
+    let pk = crate::key::secp256k1::private_key::Key::generate().unwrap();
+    let pubkey = pk.to_public_key();
+    let short_id = pubkey.to_short_id().unwrap();
+
+    for (network_id, hrp) in [(1, "avax"), (5, "fuji")] {
+        for chain in ["X", "P"] {
+            let addr = pubkey.to_hrp_address(network_id, chain).unwrap();
+            assert_eq!(short_id.to_address(hrp, chain).unwrap(), addr);
+            assert_eq!(Id::from_address(&addr).unwrap(), short_id);
+
+            // without the chain alias
+            let (_, bech32_addr) = addr.split_once('-').unwrap();
+            assert_eq!(Id::from_address(bech32_addr).unwrap(), short_id);
+        }
+    }
+
+    assert!(Id::from_address("X-avax1invalid").is_err());
+
+    let h = short_id.to_h160();
+    assert_eq!(h.as_bytes(), short_id.as_ref());
+    assert_eq!(Id::from(h), short_id);
+    assert_eq!(H160::from(short_id), h);
+}
+
 impl Ord for Id {
     fn cmp(&self, other: &Self) -> Ordering {
         self.0.cmp(&(other.0))
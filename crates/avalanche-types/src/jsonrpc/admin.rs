@@ -1,5 +1,8 @@
 //! Admin API requests and responses.
-use std::io::{self, Error as ioError, ErrorKind};
+use std::{
+    collections::BTreeMap,
+    io::{self, Error as ioError, ErrorKind},
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -61,9 +64,63 @@ pub struct ChainAliasResponse {
     pub id: u32,
 }
 
+/// Response for the requests without results
+/// (e.g., "admin.lockProfile", "admin.memoryProfile").
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct EmptyResponse {
+    /// Jsonrpc version
+    pub jsonrpc: String,
+    /// Id of request
+    pub id: u32,
+    /// Error, if the request failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<super::ResponseError>,
+}
+
+/// Response for the logger level requests.
+/// Ref: <https://docs.avax.network/apis/avalanchego/apis/admin#admingetloggerlevel>
+/// Ref: <https://docs.avax.network/apis/avalanchego/apis/admin#adminsetloggerlevel>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct LoggerLevelsResponse {
+    /// Jsonrpc version
+    pub jsonrpc: String,
+    /// Id of request
+    pub id: u32,
+    /// Logger levels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<LoggerLevelsResult>,
+    /// Error, if the request failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<super::ResponseError>,
+}
+
+/// Log levels per logger (e.g., "C", "P", "X", "http", "main").
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggerLevelsResult {
+    /// Levels keyed by the logger name
+    #[serde(default)]
+    pub logger_levels: BTreeMap<String, LoggerLevels>,
+}
+
+/// Log levels of a single logger.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggerLevels {
+    /// Level written to the log file (e.g., "DEBUG")
+    pub log_level: String,
+    /// Level displayed on the console (e.g., "INFO")
+    pub display_level: String,
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::jsonrpc::admin::{ChainAliasParams, ChainAliasRequest, ChainAliasResponse};
+    use std::collections::BTreeMap;
+
+    use crate::jsonrpc::admin::{
+        ChainAliasParams, ChainAliasRequest, ChainAliasResponse, EmptyResponse, LoggerLevels,
+        LoggerLevelsResponse, LoggerLevelsResult,
+    };
     use crate::jsonrpc::{DEFAULT_ID, DEFAULT_VERSION};
 
     #[test]
@@ -95,4 +152,59 @@ mod tests {
 
         assert_eq!(expected, deserialized);
     }
+
+    #[test]
+    fn test_empty_response() {
+        let response = r#"{"jsonrpc": "2.0","id": 1,"result": {}}"#.as_bytes();
+        let deserialized: EmptyResponse =
+            serde_json::from_slice(response).expect("failed deserialization");
+        assert!(deserialized.error.is_none());
+
+        let response = r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"the method admin.lockProfile does not exist/is not available","data":null},"id":1}"#.as_bytes();
+        let deserialized: EmptyResponse =
+            serde_json::from_slice(response).expect("failed deserialization");
+        assert_eq!(deserialized.error.unwrap().code, -32601);
+    }
+
+    #[test]
+    fn test_logger_levels() {
+        // ref. <https://docs.avax.network/apis/avalanchego/apis/admin#admingetloggerlevel>
+        let response = r#"{
+            "jsonrpc": "2.0",
+            "result": {
+                "loggerLevels": {
+                    "C": {
+                        "logLevel": "DEBUG",
+                        "displayLevel": "INFO"
+                    }
+                }
+            },
+            "id": 1
+        }"#
+        .as_bytes();
+        let deserialized: LoggerLevelsResponse =
+            serde_json::from_slice(response).expect("failed deserialization");
+
+        let expected = LoggerLevelsResponse {
+            jsonrpc: String::from(DEFAULT_VERSION),
+            id: DEFAULT_ID,
+            result: Some(LoggerLevelsResult {
+                logger_levels: BTreeMap::from([(
+                    String::from("C"),
+                    LoggerLevels {
+                        log_level: String::from("DEBUG"),
+                        display_level: String::from("INFO"),
+                    },
+                )]),
+            }),
+            error: None,
+        };
+        assert_eq!(expected, deserialized);
+
+        // older nodes return an empty result for "admin.setLoggerLevel"
+        let response = r#"{"jsonrpc": "2.0","id": 1,"result": {}}"#.as_bytes();
+        let deserialized: LoggerLevelsResponse =
+            serde_json::from_slice(response).expect("failed deserialization");
+        assert!(deserialized.result.unwrap().logger_levels.is_empty());
+    }
 }
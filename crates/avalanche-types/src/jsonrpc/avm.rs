@@ -228,7 +228,14 @@ impl GetUtxosRequest {
 pub struct GetUtxosParams {
     pub addresses: Vec<String>,
     pub limit: u32,
+    /// "endIndex" of the previous page to continue from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<super::EndIndex>,
     pub encoding: String,
+    /// Chain Id or alias the UTXOs were exported from, to fetch the atomic
+    /// UTXOs in the shared memory instead of the chain's own UTXOs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_chain: Option<String>,
 }
 
 /// ref. <https://docs.avax.network/apis/avalanchego/apis/x-chain/#avmgetutxos>
@@ -352,6 +359,34 @@ fn test_get_utxos_non_empty() {
     assert_eq!(resp, expected);
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::avm::test_get_utxos_start_index` --exact --show-output
+#[test]
+fn test_get_utxos_start_index() {
+    let mut req = GetUtxosRequest {
+        method: String::from("avm.getUTXOs"),
+        params: Some(GetUtxosParams {
+            addresses: vec![String::from("X-custom1")],
+            limit: 1024,
+            start_index: None,
+            encoding: String::from("hex"),
+            source_chain: None,
+        }),
+        ..Default::default()
+    };
+    assert!(!req.encode_json().unwrap().contains("startIndex"));
+    assert!(!req.encode_json().unwrap().contains("sourceChain"));
+
+    req.params.as_mut().unwrap().start_index = Some(jsonrpc::EndIndex {
+        address: String::from("X-custom1"),
+        utxo: String::from("kbUThAUfmBXUmRgTpgD6r3nLj7rJUGho6xyht5nouNNypH45j"),
+    });
+    let encoded: serde_json::Value = serde_json::from_str(&req.encode_json().unwrap()).unwrap();
+    assert_eq!(
+        encoded["params"]["startIndex"]["utxo"],
+        "kbUThAUfmBXUmRgTpgD6r3nLj7rJUGho6xyht5nouNNypH45j"
+    );
+}
+
 /// ref. <https://docs.avax.network/build/avalanchego-apis/x-chain#avmgetbalance>
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct GetBalanceResponse {
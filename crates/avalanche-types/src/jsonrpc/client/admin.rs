@@ -1,13 +1,15 @@
 //! Avalanche Admin RPC API endpoints.
-use std::time::Duration;
-
-use reqwest::{header::CONTENT_TYPE, ClientBuilder};
+use std::collections::HashMap;
 
 use crate::{
     errors::{Error, Result},
     jsonrpc::{
-        admin::{ChainAliasParams, ChainAliasRequest, ChainAliasResponse},
-        client::url,
+        self,
+        admin::{
+            ChainAliasParams, ChainAliasRequest, ChainAliasResponse, EmptyResponse,
+            LoggerLevelsResponse,
+        },
+        client::{http, metrics, url},
     },
     utils,
 };
@@ -47,27 +49,12 @@ pub async fn alias_chain(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
+    let req_cli_builder = http::current()?;
 
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let timer = metrics::Timer::start("admin.aliasChain", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
         // TODO: check retryable
         Error::API {
             message: format!("failed reqwest::Client.send '{e}'"),
@@ -91,3 +78,353 @@ pub async fn alias_chain(
 
     Ok(response)
 }
+
+/// Starts profiling the CPU utilization of the node.
+/// The profile is written to "`{profile-dir}/cpu.profile`" on stop.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#adminstartcpuprofiler>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn start_cpu_profiler(http_rpc: &str) -> Result<EmptyResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("calling admin.startCPUProfiler for {url}");
+
+    let data = jsonrpc::Request {
+        method: String::from("admin.startCPUProfiler"),
+        params: Some(HashMap::new()),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.startCPUProfiler", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Stops the CPU profile that was previously started.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#adminstopcpuprofiler>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn stop_cpu_profiler(http_rpc: &str) -> Result<EmptyResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("calling admin.stopCPUProfiler for {url}");
+
+    let data = jsonrpc::Request {
+        method: String::from("admin.stopCPUProfiler"),
+        params: Some(HashMap::new()),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.stopCPUProfiler", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Writes the mutex statistics of the node to "`{profile-dir}/lock.profile`".
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#adminlockprofile>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn lock_profile(http_rpc: &str) -> Result<EmptyResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("calling admin.lockProfile for {url}");
+
+    let data = jsonrpc::Request {
+        method: String::from("admin.lockProfile"),
+        params: Some(HashMap::new()),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.lockProfile", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Writes the memory profile of the node to "`{profile-dir}/mem.profile`".
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#adminmemoryprofile>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn memory_profile(http_rpc: &str) -> Result<EmptyResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("calling admin.memoryProfile for {url}");
+
+    let data = jsonrpc::Request {
+        method: String::from("admin.memoryProfile"),
+        params: Some(HashMap::new()),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.memoryProfile", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Gets the log and display levels of the loggers.
+/// Returns all loggers if the logger name is None.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#admingetloggerlevel>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_logger_level(
+    http_rpc: &str,
+    logger_name: Option<&str>,
+) -> Result<LoggerLevelsResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting logger level for {url}");
+
+    let mut params = HashMap::new();
+    if let Some(v) = logger_name {
+        params.insert(String::from("loggerName"), v.to_string());
+    }
+    let data = jsonrpc::Request {
+        method: String::from("admin.getLoggerLevel"),
+        params: Some(params),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.getLoggerLevel", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
+
+/// Sets the log and/or display levels of the loggers (e.g., "DEBUG", "INFO").
+/// Updates all loggers if the logger name is None, and leaves
+/// the level unchanged if None.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/admin#adminsetloggerlevel>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails, or if both levels are None.
+pub async fn set_logger_level(
+    http_rpc: &str,
+    logger_name: Option<&str>,
+    log_level: Option<&str>,
+    display_level: Option<&str>,
+) -> Result<LoggerLevelsResponse> {
+    let (scheme, host, port, ..) = utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc)
+        .map_err(|e| Error::Other {
+            message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+            retryable: false,
+        })?;
+
+    let url = url::try_create_url(&url::Path::Admin, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("setting logger level for {url}");
+
+    if log_level.is_none() && display_level.is_none() {
+        return Err(Error::Other {
+            message: String::from("log level or display level must be set"),
+            retryable: false,
+        });
+    }
+    let mut params = HashMap::new();
+    if let Some(v) = logger_name {
+        params.insert(String::from("loggerName"), v.to_string());
+    }
+    if let Some(v) = log_level {
+        params.insert(String::from("logLevel"), v.to_string());
+    }
+    if let Some(v) = display_level {
+        params.insert(String::from("displayLevel"), v.to_string());
+    }
+    let data = jsonrpc::Request {
+        method: String::from("admin.setLoggerLevel"),
+        params: Some(params),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+
+    let timer = metrics::Timer::start("admin.setLoggerLevel", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+        // TODO: check retryable
+        Error::API {
+            message: format!("failed reqwest::Client.send '{e}'"),
+            retryable: false,
+        })?;
+
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    serde_json::from_slice(out.as_ref()).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
@@ -0,0 +1,245 @@
+//! Avalanche C-Chain atomic ("avax") JSON-RPC client.
+use crate::{
+    errors::{Error, Result},
+    formatting, ids,
+    jsonrpc::client::{http, issued_tx_id, metrics, response_error, url, MAX_UTXOS_PAGE_SIZE},
+    jsonrpc::{self, avm, common},
+    utils,
+};
+
+/// e.g., "avax.issueTx" on "http://\[ADDR\]:9650" and "/ext/bc/C/avax" path,
+/// to issue the atomic (import/export) transaction to the C-chain.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/c-chain#avaxissuetx>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<avm::IssueTxResponse> {
+    issue_encoded_tx(
+        http_rpc,
+        prefix_manager::prepend_0x(tx),
+        formatting::Encoding::Hex,
+    )
+    .await
+}
+
+/// Issues the pre-signed atomic transaction bytes (e.g., signed offline) via "avax.issueTx"
+/// in the encoding, and returns the transaction Id.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the API returns an error.
+pub async fn issue_raw_tx(
+    http_rpc: &str,
+    tx_bytes: &[u8],
+    encoding: formatting::Encoding,
+) -> Result<ids::Id> {
+    let resp = issue_encoded_tx(http_rpc, encoding.encode(tx_bytes), encoding).await?;
+    issued_tx_id(resp.result.map(|r| r.tx_id))
+}
+
+/// e.g., "avax.getAtomicTxStatus" on "http://\[ADDR\]:9650" and "/ext/bc/C/avax" path.
+/// The status is one of "Accepted", "Processing", "Dropped" or "Unknown".
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/c-chain#avaxgetatomictxstatus>
+///
+/// # Errors
+///
+/// Returns an error if the request fails or if the response cannot be parsed.
+pub async fn get_atomic_tx_status(http_rpc: &str, tx_id: &str) -> Result<avm::GetTxStatusResponse> {
+    call(
+        http_rpc,
+        "avax.getAtomicTxStatus",
+        serde_json::json!({ "txID": tx_id }),
+    )
+    .await
+}
+
+/// e.g., "avax.getUTXOs" on "http://\[ADDR\]:9650" and "/ext/bc/C/avax" path.
+/// Fetches the atomic UTXOs of the "C-" address that were exported from
+/// "`source_chain`" (chain Id or alias) and are not imported yet,
+/// following the end-index cursors, and aggregates them into one response.
+/// Returns the error response as is if the first page fails.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/c-chain#avaxgetutxos>
+///
+/// # Errors
+///
+/// Returns an error if a request fails, if the response cannot be parsed,
+/// or if the API returns an error after the first page.
+pub async fn get_atomic_utxos(
+    http_rpc: &str,
+    caddr: &str,
+    source_chain: &str,
+) -> Result<avm::GetUtxosResponse> {
+    let mut aggregated = avm::GetUtxosResult {
+        encoding: Some(String::from("hex")),
+        ..Default::default()
+    };
+    let mut start_index = None;
+    let mut pages = 0_u32;
+    loop {
+        let resp =
+            get_atomic_utxos_page(http_rpc, caddr, source_chain, start_index.clone()).await?;
+        let Some(result) = resp.result else {
+            if pages == 0 {
+                return Ok(resp);
+            }
+            return Err(Error::API {
+                message: format!("failed avax.getUTXOs page {pages} (no result)"),
+                retryable: true,
+            });
+        };
+        pages += 1;
+
+        aggregated.num_fetched += result.num_fetched;
+        aggregated
+            .utxos
+            .get_or_insert_with(Vec::new)
+            .extend(result.utxos.unwrap_or_default());
+
+        let done = result.num_fetched < MAX_UTXOS_PAGE_SIZE
+            || result.end_index.is_none()
+            || result.end_index == start_index;
+        aggregated.end_index = result.end_index.clone();
+        if done {
+            log::info!(
+                "fetched {} atomic UTXOs in {pages} page(s)",
+                aggregated.num_fetched
+            );
+            return Ok(avm::GetUtxosResponse {
+                jsonrpc: resp.jsonrpc,
+                id: resp.id,
+                result: Some(aggregated),
+                error: None,
+            });
+        }
+        start_index = result.end_index;
+    }
+}
+
+/// Fetches one page of the atomic UTXOs, starting after "`start_index`".
+async fn get_atomic_utxos_page(
+    http_rpc: &str,
+    caddr: &str,
+    source_chain: &str,
+    start_index: Option<jsonrpc::EndIndex>,
+) -> Result<avm::GetUtxosResponse> {
+    let params = avm::GetUtxosParams {
+        addresses: vec![caddr.to_string()],
+        limit: MAX_UTXOS_PAGE_SIZE,
+        start_index,
+        encoding: String::from("hex"),
+        source_chain: Some(source_chain.to_string()),
+    };
+    let params = serde_json::to_value(params).map_err(|e| Error::Other {
+        message: format!("failed serde_json::to_value '{e}'"),
+        retryable: false,
+    })?;
+    call(http_rpc, "avax.getUTXOs", params).await
+}
+
+/// Sends the request to the "/ext/bc/C/avax" path,
+/// and decodes the whole response.
+async fn call<R: serde::de::DeserializeOwned>(
+    http_rpc: &str,
+    method: &'static str,
+    params: serde_json::Value,
+) -> Result<R> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(&url::Path::CAvax, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("{method} via {url}");
+
+    let d = serde_json::to_string(&common::Request::new(1, method, params)).map_err(|e| {
+        Error::Other {
+            message: format!("failed serde_json::to_string '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start(method, &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    if let Some(e) = response_error(&out) {
+        return Err(e.into());
+    }
+    serde_json::from_slice(&out).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice for {method} '{e}'"),
+        retryable: false,
+    })
+}
+
+async fn issue_encoded_tx(
+    http_rpc: &str,
+    tx: String,
+    encoding: formatting::Encoding,
+) -> Result<avm::IssueTxResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(&url::Path::CAvax, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("issuing a transaction via {url}");
+
+    // "avax.issueTx" has the same request and response as "avm.issueTx"
+    let method = String::from("avax.issueTx");
+    let params = avm::IssueTxParams {
+        tx,
+        encoding: encoding.as_str().to_string(),
+    }
+    .into();
+
+    let data = avm::IssueTxRequest {
+        method,
+        params,
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avax.issueTx", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
@@ -0,0 +1,237 @@
+//! Aggregated health checks across multiple Avalanche nodes.
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::jsonrpc::client::{health, info};
+use futures::{future, stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+/// Default chains to check for bootstrapping.
+pub const DEFAULT_CHAINS: [&str; 3] = ["X", "P", "C"];
+
+/// Default maximum number of nodes queried at a time.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Represents the health of a single node.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct NodeReport {
+    pub http_rpc: String,
+
+    /// Result of the health API, or None if the request failed.
+    pub healthy: Option<bool>,
+    /// Bootstrapped status per chain, or None if the request failed.
+    pub bootstrapped: BTreeMap<String, Option<bool>>,
+    /// Node version (e.g., "avalanche/1.10.1"), or None if the request failed.
+    pub version: Option<String>,
+
+    /// Errors from the failed requests.
+    pub errors: Vec<String>,
+}
+
+impl NodeReport {
+    /// Returns true if the node is healthy and all chains are bootstrapped.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.healthy == Some(true) && self.bootstrapped.values().all(|b| *b == Some(true))
+    }
+}
+
+/// Represents the health of a cluster (e.g., all nodes of a subnet).
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct Report {
+    /// Reports per node, in the order of the requested endpoints.
+    pub nodes: Vec<NodeReport>,
+}
+
+impl Report {
+    /// Returns true if all nodes are healthy and bootstrapped.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        !self.nodes.is_empty() && self.nodes.iter().all(NodeReport::is_healthy)
+    }
+
+    /// Returns the nodes that are unhealthy, not bootstrapped, or unreachable.
+    #[must_use]
+    pub fn unhealthy_nodes(&self) -> Vec<&NodeReport> {
+        self.nodes.iter().filter(|n| !n.is_healthy()).collect()
+    }
+
+    /// Returns true if the chain is bootstrapped on all nodes.
+    #[must_use]
+    pub fn is_chain_bootstrapped(&self, chain: &str) -> bool {
+        !self.nodes.is_empty()
+            && self
+                .nodes
+                .iter()
+                .all(|n| n.bootstrapped.get(chain) == Some(&Some(true)))
+    }
+
+    /// Returns the number of nodes that bootstrapped each chain.
+    #[must_use]
+    pub fn bootstrapped_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for n in &self.nodes {
+            for (chain, bootstrapped) in &n.bootstrapped {
+                let cnt = counts.entry(chain.clone()).or_insert(0);
+                if *bootstrapped == Some(true) {
+                    *cnt += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Returns the endpoints grouped by their node versions.
+    /// Nodes whose version could not be fetched are skipped.
+    #[must_use]
+    pub fn versions(&self) -> BTreeMap<String, Vec<String>> {
+        let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for n in &self.nodes {
+            if let Some(v) = &n.version {
+                versions
+                    .entry(v.clone())
+                    .or_default()
+                    .push(n.http_rpc.clone());
+            }
+        }
+        versions
+    }
+
+    /// Returns true if the nodes run more than one version.
+    #[must_use]
+    pub fn has_version_skew(&self) -> bool {
+        self.versions().len() > 1
+    }
+}
+
+/// Checks the health, the bootstrapped status of the chains, and the version
+/// of every node, with at most "`max_concurrent_requests`" nodes queried at a time.
+/// A failing node is recorded in its report and does not fail the check.
+pub async fn check(
+    http_rpcs: &[String],
+    chains: &[&str],
+    max_concurrent_requests: usize,
+) -> Report {
+    let nodes = stream::iter(http_rpcs)
+        .map(|http_rpc| check_node(http_rpc, chains))
+        .buffered(max_concurrent_requests.max(1))
+        .collect()
+        .await;
+    Report { nodes }
+}
+
+/// Checks a single node, querying all APIs concurrently.
+async fn check_node(http_rpc: &str, chains: &[&str]) -> NodeReport {
+    let (health_resp, version_resp, bootstrapped_resps) = future::join3(
+        health::check(Arc::new(http_rpc.to_string()), false),
+        info::get_node_version(http_rpc),
+        future::join_all(
+            chains
+                .iter()
+                .map(|chain| info::is_chain_bootstrapped(http_rpc, chain)),
+        ),
+    )
+    .await;
+
+    let mut report = NodeReport {
+        http_rpc: http_rpc.to_string(),
+        ..Default::default()
+    };
+
+    match health_resp {
+        Ok(resp) => report.healthy = Some(resp.healthy),
+        Err(e) => report.errors.push(format!("health: {e}")),
+    }
+
+    match version_resp {
+        Ok(resp) => report.version = resp.result.map(|r| r.version),
+        Err(e) => report.errors.push(format!("info.getNodeVersion: {e}")),
+    }
+
+    for (chain, resp) in chains.iter().zip(bootstrapped_resps) {
+        let bootstrapped = match resp {
+            Ok(resp) => resp.result.map(|r| r.is_bootstrapped),
+            Err(e) => {
+                report
+                    .errors
+                    .push(format!("info.isBootstrapped({chain}): {e}"));
+                None
+            }
+        };
+        report
+            .bootstrapped
+            .insert((*chain).to_string(), bootstrapped);
+    }
+
+    if !report.errors.is_empty() {
+        log::warn!("failed to check {http_rpc} ({:?})", report.errors);
+    }
+    report
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `jsonrpc::client::cluster::test_report` --exact --show-output
+#[test]
+fn test_report() {
+    let node = |http_rpc: &str, healthy: Option<bool>, c: Option<bool>, version: &str| NodeReport {
+        http_rpc: http_rpc.to_string(),
+        healthy,
+        bootstrapped: BTreeMap::from([(String::from("P"), Some(true)), (String::from("C"), c)]),
+        version: if version.is_empty() {
+            None
+        } else {
+            Some(version.to_string())
+        },
+        errors: Vec::new(),
+    };
+
+    let report = Report { nodes: Vec::new() };
+    assert!(!report.is_healthy());
+    assert!(!report.has_version_skew());
+
+    let report = Report {
+        nodes: vec![
+            node("http://a:9650", Some(true), Some(true), "avalanche/1.10.1"),
+            node("http://b:9650", Some(true), Some(true), "avalanche/1.10.1"),
+        ],
+    };
+    assert!(report.is_healthy());
+    assert!(report.unhealthy_nodes().is_empty());
+    assert!(report.is_chain_bootstrapped("P"));
+    assert!(report.is_chain_bootstrapped("C"));
+    assert!(!report.is_chain_bootstrapped("X"));
+    assert!(!report.has_version_skew());
+
+    let report = Report {
+        nodes: vec![
+            node("http://a:9650", Some(true), Some(true), "avalanche/1.10.1"),
+            node("http://b:9650", Some(true), Some(false), "avalanche/1.10.2"),
+            node("http://c:9650", None, None, ""),
+        ],
+    };
+    assert!(!report.is_healthy());
+    let unhealthy: Vec<&str> = report
+        .unhealthy_nodes()
+        .iter()
+        .map(|n| n.http_rpc.as_str())
+        .collect();
+    assert_eq!(unhealthy, vec!["http://b:9650", "http://c:9650"]);
+    assert!(report.is_chain_bootstrapped("P"));
+    assert!(!report.is_chain_bootstrapped("C"));
+    assert_eq!(
+        report.bootstrapped_counts(),
+        BTreeMap::from([(String::from("C"), 1), (String::from("P"), 3)])
+    );
+    assert!(report.has_version_skew());
+    assert_eq!(
+        report.versions(),
+        BTreeMap::from([
+            (
+                String::from("avalanche/1.10.1"),
+                vec![String::from("http://a:9650")]
+            ),
+            (
+                String::from("avalanche/1.10.2"),
+                vec![String::from("http://b:9650")]
+            ),
+        ])
+    );
+}
@@ -1,9 +1,14 @@
 //! EVM RPC client.
 use std::time::Duration;
 
-use crate::errors::{Error, Result};
-use ethers_providers::{Http, Middleware, Provider};
-use primitive_types::{H160, U256};
+use crate::{
+    codec::serde::hex_0x_primitive_types_u256,
+    errors::{Error, Result},
+    jsonrpc::{client::http, common, evm},
+};
+use ethers_providers::Middleware;
+use primitive_types::{H160, H256, U256};
+use serde_json::Value;
 
 /// Fetches the chain Id from the EVM endpoint.
 ///
@@ -14,15 +19,7 @@ use primitive_types::{H160, U256};
 ///
 /// Returns an error if the API request fails.
 pub async fn chain_id(rpc_ep: &str) -> Result<U256> {
-    let provider = Provider::<Http>::try_from(rpc_ep)
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::API {
-                message: format!("failed to create provider '{e}'"),
-                retryable: false,
-            }
-        })?
-        .interval(Duration::from_millis(2000u64));
+    let provider = http::current()?.provider(rpc_ep)?;
 
     log::info!("getting chain id via {rpc_ep}");
     provider.get_chainid().await.map_err(|e|
@@ -44,15 +41,7 @@ pub async fn chain_id(rpc_ep: &str) -> Result<U256> {
 ///
 /// Returns an error if the API request fails.
 pub async fn get_balance(rpc_ep: &str, eth_addr: H160) -> Result<U256> {
-    let provider = Provider::<Http>::try_from(rpc_ep)
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::API {
-                message: format!("failed to create provider '{e}'"),
-                retryable: false,
-            }
-        })?
-        .interval(Duration::from_millis(2000u64));
+    let provider = http::current()?.provider(rpc_ep)?;
 
     log::info!("getting balances for {eth_addr} via {rpc_ep}");
     provider.get_balance(eth_addr, None).await.map_err(|e|
@@ -62,3 +51,288 @@ pub async fn get_balance(rpc_ep: &str, eth_addr: H160) -> Result<U256> {
                 retryable: false,
             })
 }
+
+/// Sends the JSON-RPC requests of "(method, params)" in a single batch
+/// (one HTTP round trip), and returns the result of each request
+/// in the same order as the requests.
+/// A failed request does not fail the other requests in the batch.
+///
+/// ref. <https://www.jsonrpc.org/specification#batch>
+///
+/// # Errors
+///
+/// Returns an error if the HTTP request fails or the batch response cannot be parsed.
+pub async fn batch(rpc_ep: &str, requests: &[(&str, Value)]) -> Result<Vec<Result<Value>>> {
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+    let reqs: Vec<common::Request<'_, &Value>> = requests
+        .iter()
+        .zip(0_u64..)
+        .map(|((method, params), id)| common::Request::new(id, method, params))
+        .collect();
+    let d = serde_json::to_string(&reqs).map_err(|e| Error::Other {
+        message: format!("failed serde_json::to_string '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    log::info!("sending {} batched requests via {rpc_ep}", requests.len());
+    let resp = req_cli_builder
+        .post_json(rpc_ep, d)?
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| Error::Other {
+        message: format!("failed reqwest response bytes '{e}'"),
+        retryable: false,
+    })?;
+    let resps: Vec<common::Response<Value>> =
+        serde_json::from_slice(&out).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_slice '{e}'"),
+            retryable: false,
+        })?;
+
+    order_batch_responses(requests.len(), resps)
+}
+
+/// Orders the batch responses by the request Ids,
+/// since the server may respond in any order.
+fn order_batch_responses(
+    n: usize,
+    resps: Vec<common::Response<Value>>,
+) -> Result<Vec<Result<Value>>> {
+    let mut results: Vec<Option<Result<Value>>> = (0..n).map(|_| None).collect();
+    for resp in resps {
+        let Some(result) = usize::try_from(resp.id)
+            .ok()
+            .and_then(|i| results.get_mut(i))
+        else {
+            return Err(Error::API {
+                message: format!("unexpected batch response Id {}", resp.id),
+                retryable: false,
+            });
+        };
+        *result = Some(resp.data.into_result().map_err(Error::from));
+    }
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(id, result)| {
+            result.ok_or_else(|| Error::API {
+                message: format!("no batch response for request Id {id}"),
+                retryable: false,
+            })
+        })
+        .collect()
+}
+
+/// Fetches the balances of many addresses in a single batch request,
+/// in the same order as the addresses.
+///
+/// # Errors
+///
+/// Returns an error if the batch request fails or any balance is not returned.
+pub async fn get_balances(rpc_ep: &str, eth_addrs: &[H160]) -> Result<Vec<U256>> {
+    let requests: Vec<(&str, Value)> = eth_addrs
+        .iter()
+        .map(|addr| ("eth_getBalance", serde_json::json!([addr, "latest"])))
+        .collect();
+
+    log::info!(
+        "getting balances for {} addresses via {rpc_ep}",
+        eth_addrs.len()
+    );
+    batch(rpc_ep, &requests)
+        .await?
+        .into_iter()
+        .map(|result| {
+            hex_0x_primitive_types_u256::deserialize(result?).map_err(|e| Error::Other {
+                message: format!("failed to parse balance '{e}'"),
+                retryable: false,
+            })
+        })
+        .collect()
+}
+
+/// Sends a single JSON-RPC request, and decodes the result.
+async fn call<T: serde::de::DeserializeOwned>(
+    rpc_ep: &str,
+    method: &str,
+    params: Value,
+) -> Result<T> {
+    let d = serde_json::to_string(&common::Request::new(1, method, params)).map_err(|e| {
+        Error::Other {
+            message: format!("failed serde_json::to_string '{e}'"),
+            retryable: false,
+        }
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let resp = req_cli_builder
+        .post_json(rpc_ep, d)?
+        .send()
+        .await
+        .map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| Error::Other {
+        message: format!("failed reqwest response bytes '{e}'"),
+        retryable: false,
+    })?;
+    let resp: common::Response<T> = serde_json::from_slice(&out).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_slice for {method} '{e}'"),
+        retryable: false,
+    })?;
+    resp.data.into_result().map_err(Error::from)
+}
+
+/// Fetches the block by number ("None" for the latest block),
+/// with the full transactions if "`full_txs`" is "true".
+/// Returns "None" if the block does not exist yet.
+///
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_getblockbynumber>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_block_by_number(
+    rpc_ep: &str,
+    number: Option<u64>,
+    full_txs: bool,
+) -> Result<Option<evm::Block>> {
+    let tag = number.map_or_else(|| "latest".to_string(), |n| format!("0x{n:x}"));
+    log::info!("getting block {tag} via {rpc_ep}");
+    call(
+        rpc_ep,
+        "eth_getBlockByNumber",
+        serde_json::json!([tag, full_txs]),
+    )
+    .await
+}
+
+/// Fetches the block by hash.
+/// Returns "None" if the block is not found.
+///
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_getblockbyhash>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_block_by_hash(
+    rpc_ep: &str,
+    hash: H256,
+    full_txs: bool,
+) -> Result<Option<evm::Block>> {
+    log::info!("getting block {hash:?} via {rpc_ep}");
+    call(
+        rpc_ep,
+        "eth_getBlockByHash",
+        serde_json::json!([hash, full_txs]),
+    )
+    .await
+}
+
+/// Fetches the transaction by hash.
+/// Returns "None" if the transaction is not found.
+///
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_gettransactionbyhash>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_transaction_by_hash(rpc_ep: &str, hash: H256) -> Result<Option<evm::Transaction>> {
+    log::info!("getting transaction {hash:?} via {rpc_ep}");
+    call(
+        rpc_ep,
+        "eth_getTransactionByHash",
+        serde_json::json!([hash]),
+    )
+    .await
+}
+
+/// Fetches the receipt of the transaction.
+/// Returns "None" if the transaction is pending or not found.
+///
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_gettransactionreceipt>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_transaction_receipt(rpc_ep: &str, hash: H256) -> Result<Option<evm::Receipt>> {
+    log::info!("getting transaction receipt {hash:?} via {rpc_ep}");
+    call(
+        rpc_ep,
+        "eth_getTransactionReceipt",
+        serde_json::json!([hash]),
+    )
+    .await
+}
+
+/// Fetches the base fee (in wei) of the next block.
+/// e.g., C-chain atomic transactions burn the gas at this base fee.
+///
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/c-chain#eth_basefee>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn base_fee(rpc_ep: &str) -> Result<U256> {
+    log::info!("getting base fee via {rpc_ep}");
+    call(rpc_ep, "eth_baseFee", serde_json::json!([])).await
+}
+
+/// Fetches the nonce of the account at the latest block.
+///
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_gettransactioncount>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails or the nonce overflows u64.
+pub async fn get_transaction_count(rpc_ep: &str, eth_addr: H160) -> Result<u64> {
+    log::info!("getting nonce of {eth_addr:?} via {rpc_ep}");
+    let nonce: U256 = call(
+        rpc_ep,
+        "eth_getTransactionCount",
+        serde_json::json!([eth_addr, "latest"]),
+    )
+    .await?;
+    if nonce > U256::from(u64::MAX) {
+        return Err(Error::Other {
+            message: format!("nonce {nonce} overflows u64"),
+            retryable: false,
+        });
+    }
+    Ok(nonce.as_u64())
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `jsonrpc::client::evm::test_order_batch_responses` --exact --show-output
+#[test]
+fn test_order_batch_responses() {
+    let resps: Vec<common::Response<Value>> = serde_json::from_str(
+        r#"[
+    {"jsonrpc": "2.0", "id": 1, "error": {"code": -32000, "message": "oops"}},
+    {"jsonrpc": "2.0", "id": 2, "result": "0x2"},
+    {"jsonrpc": "2.0", "id": 0, "result": "0x0"}
+]"#,
+    )
+    .unwrap();
+    let results = order_batch_responses(3, resps.clone()).unwrap();
+    assert_eq!(results[0].as_ref().unwrap(), &Value::from("0x0"));
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &Value::from("0x2"));
+
+    // missing response
+    assert!(order_batch_responses(4, resps.clone()).is_err());
+    // unknown Id
+    assert!(order_batch_responses(2, resps).is_err());
+}
@@ -1,12 +1,14 @@
 //! Avalanche JSON-RPC Health API endpoints.
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use crate::{
     errors::{Error, Result},
-    jsonrpc::{client::url, health},
+    jsonrpc::{
+        client::{http, metrics, url},
+        health,
+    },
     utils::urls::extract_scheme_host_port_path_chain_alias,
 };
-use reqwest::ClientBuilder;
 
 /// Checks the health of an Avalanche node.
 ///
@@ -30,24 +32,14 @@ pub async fn check(http_rpc: Arc<String>, liveness: bool) -> Result<health::Resp
     }
     log::info!("getting network name for {url}");
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("health.health", &url);
     let resp = req_cli_builder
-        .get(url.to_string())
+        .request(reqwest::Method::GET, &url.to_string())
         .send()
-        .await
-        .map_err(|e|
+        .await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
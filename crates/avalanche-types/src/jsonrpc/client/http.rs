@@ -0,0 +1,783 @@
+//! Shared HTTP client of the JSON-RPC client methods.
+//!
+//! All client methods send the requests via the current client, so the
+//! connections are pooled and kept alive across the calls, instead of
+//! opening a new connection per call (e.g., for high-frequency tooling).
+//! The current client is the one injected with "`scope`" (e.g., by the
+//! wallet built with its own client), or the shared client otherwise.
+//! Use "`set_shared`" to tune the shared client before the first call.
+//! The EVM clients route their "ethers" providers via the same client,
+//! see "`Http::provider`".
+//!
+//! The client advertises and decodes the gzip/deflate responses
+//! (e.g., large "getUTXOs" responses from the gateways), and optionally
+//! compresses the large request bodies.
+//!
+//! Use "`Builder::auth`" (or "`Http::with_auth`") to authenticate the
+//! requests of a client to the endpoints (e.g., managed node providers
+//! that require the API keys), and "`TlsConfig`" to talk to the nodes
+//! behind the self-signed TLS. The server certificates are always verified,
+//! unless explicitly disabled for testing, in which case no credentials
+//! are sent.
+//!
+//! BREAKING: the clients used to accept the invalid server certificates,
+//! and now verify them by default, so the requests to the "https" nodes
+//! with the self-signed certificates fail. Trust the node certificates with
+//! "`TlsConfig::ca_cert_paths`", or restore the previous behavior (only for
+//! testing) with "`TlsConfig::accept_invalid_certs`", for example:
+//!
+//! ```no_run
+//! use avalanche_types::jsonrpc::client::http::{set_shared, Builder, TlsConfig};
+//!
+//! # fn main() -> avalanche_types::errors::Result<()> {
+//! let http = Builder::default()
+//!     .tls(TlsConfig {
+//!         accept_invalid_certs: true,
+//!         ..TlsConfig::default()
+//!     })
+//!     .build()?;
+//! set_shared(http)?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    future::Future,
+    io::Write,
+    net::SocketAddr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use crate::errors::{Error, Result};
+use ethers_providers::Provider;
+use flate2::{write::GzEncoder, Compression};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE},
+    redirect, Certificate, Client, ClientBuilder, Identity, Method, Proxy, RequestBuilder, Url,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default timeout of each request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Default timeout to establish a connection.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default duration to keep the idle connections in the pool.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Default maximum number of idle connections per host.
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 32;
+/// Default interval of the TCP keep-alive probes.
+pub const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+/// Polling interval of the "ethers" providers (e.g., for pending transactions).
+pub const PROVIDER_POLL_INTERVAL: Duration = Duration::from_millis(2000);
+
+static SHARED: OnceLock<Http> = OnceLock::new();
+
+tokio::task_local! {
+    /// Client injected for the requests of the current task, see "`scope`".
+    static SCOPED: Http;
+}
+
+/// Represents the authentication of the requests to an endpoint.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Auth {
+    /// "Authorization: Bearer {token}"
+    Bearer { token: String },
+    /// "Authorization: Basic ..."
+    Basic {
+        username: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+    },
+    /// Custom headers (e.g., "x-api-key").
+    Headers { headers: BTreeMap<String, String> },
+}
+
+impl Auth {
+    /// Validates the header names and values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any header is invalid.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Bearer { token } => header_value(&format!("Bearer {token}")).map(|_| ()),
+            Self::Basic { .. } => Ok(()),
+            Self::Headers { headers } => {
+                for (k, v) in headers {
+                    HeaderName::from_bytes(k.as_bytes()).map_err(|e| Error::Other {
+                        message: format!("invalid header name '{k}' ({e})"),
+                        retryable: false,
+                    })?;
+                    header_value(v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the headers to send with every request to the endpoint.
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        match self {
+            Self::Bearer { token } => {
+                headers.insert(AUTHORIZATION, header_value(&format!("Bearer {token}"))?);
+            }
+            Self::Basic { username, password } => {
+                let basic = ethers_providers::Authorization::basic(
+                    username,
+                    password.as_deref().unwrap_or_default(),
+                );
+                headers.insert(AUTHORIZATION, header_value(&basic.to_string())?);
+            }
+            Self::Headers { headers: custom } => {
+                for (k, v) in custom {
+                    let k = HeaderName::from_bytes(k.as_bytes()).map_err(|e| Error::Other {
+                        message: format!("invalid header name '{k}' ({e})"),
+                        retryable: false,
+                    })?;
+                    headers.insert(k, header_value(v)?);
+                }
+            }
+        }
+        Ok(headers)
+    }
+}
+
+/// Never prints the credentials.
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bearer { .. } => f.write_str("Bearer(***)"),
+            Self::Basic { username, .. } => write!(f, "Basic({username}:***)"),
+            Self::Headers { headers } => f
+                .debug_map()
+                .entries(headers.keys().map(|k| (k, "***")))
+                .finish(),
+        }
+    }
+}
+
+fn header_value(v: &str) -> Result<HeaderValue> {
+    let mut v = HeaderValue::from_str(v).map_err(|e| Error::Other {
+        message: format!("invalid header value ({e})"),
+        retryable: false,
+    })?;
+    // never logged
+    v.set_sensitive(true);
+    Ok(v)
+}
+
+/// Returns the "scheme://host:port" of the URL, with the default port of the scheme
+/// (e.g., "<https://api.example.com/ext/bc/C/rpc>" to "<https://api.example.com:443>"),
+/// so the credentials of an HTTPS endpoint are never sent over plain HTTP.
+fn endpoint_key(url: &str) -> Option<String> {
+    let u = Url::parse(url).ok()?;
+    let host = u.host_str()?;
+    let port = u.port_or_known_default()?;
+    Some(format!("{}://{host}:{port}", u.scheme()))
+}
+
+/// HTTP client with connection pooling, cheap to clone
+/// (all clones share the same connection pools and authentications).
+#[derive(Debug, Clone)]
+pub struct Http {
+    client: Client,
+    /// Clients sending the authentication headers of the endpoint
+    /// ("scheme://host:port"), with the same settings as "client"
+    /// except that the redirects are not followed.
+    auth_clients: Arc<HashMap<String, Client>>,
+    compress_requests_over: Option<usize>,
+    /// Settings of the client, to add more authenticated endpoints.
+    builder: Builder,
+}
+
+impl Http {
+    /// Creates the client with the default settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client fails to build (e.g., TLS backend failure).
+    pub fn new() -> Result<Self> {
+        Builder::default().build()
+    }
+
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    #[must_use]
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Returns the client of the URL, which sends the authentication
+    /// of its endpoint if any.
+    #[must_use]
+    pub fn client_for(&self, url: &str) -> &Client {
+        endpoint_key(url)
+            .and_then(|key| self.auth_clients.get(&key))
+            .unwrap_or(&self.client)
+    }
+
+    /// Returns a copy of the client that also authenticates the requests
+    /// to the endpoint (scheme, host and port of the URL, the path is
+    /// ignored), replacing the previous authentication of the endpoint.
+    /// This client and its other clones are not affected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL or the authentication headers are invalid,
+    /// or the client accepts the invalid server certificates.
+    pub fn with_auth(&self, endpoint: &str, auth: Auth) -> Result<Self> {
+        let (key, client) = self.builder.auth_client(endpoint, &auth)?;
+        let mut http = self.clone();
+        Arc::make_mut(&mut http.auth_clients).insert(key, client);
+        http.builder.auths.push((endpoint.to_string(), auth));
+        Ok(http)
+    }
+
+    /// Creates the request with the authentication of the endpoint, if any.
+    #[must_use]
+    pub fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client_for(url).request(method, url)
+    }
+
+    /// Returns the "ethers" transport to the EVM endpoint, sending the
+    /// requests via this client (e.g., for "`RetryClient`").
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn ethers_transport(&self, rpc_ep: &str) -> Result<ethers_providers::Http> {
+        let u = Url::parse(rpc_ep).map_err(|e| Error::Other {
+            message: format!("invalid EVM endpoint URL '{rpc_ep}' ({e})"),
+            retryable: false,
+        })?;
+        Ok(ethers_providers::Http::new_with_client(
+            u,
+            self.client_for(rpc_ep).clone(),
+        ))
+    }
+
+    /// Returns the "ethers" provider of the EVM endpoint,
+    /// sending the requests via this client.
+    /// e.g., "`{http_rpc}/ext/bc/C/rpc`"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL.
+    pub fn provider(&self, rpc_ep: &str) -> Result<Provider<ethers_providers::Http>> {
+        Ok(Provider::new(self.ethers_transport(rpc_ep)?).interval(PROVIDER_POLL_INTERVAL))
+    }
+
+    /// Creates the POST request with the JSON body,
+    /// gzip-compressed if larger than "`compress_requests_over`".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the compression fails.
+    pub fn post_json(&self, url: &str, body: String) -> Result<RequestBuilder> {
+        let req = self
+            .request(Method::POST, url)
+            .header(CONTENT_TYPE, "application/json");
+        match self.compress_requests_over {
+            Some(threshold) if body.len() > threshold => Ok(req
+                .header(CONTENT_ENCODING, "gzip")
+                .body(compress_gzip(body.as_bytes())?)),
+            _ => Ok(req.body(body)),
+        }
+    }
+}
+
+fn compress_gzip(d: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(d)
+        .and_then(|()| encoder.finish())
+        .map_err(|e| Error::Other {
+            message: format!("failed to gzip request body '{e}'"),
+            retryable: false,
+        })
+}
+
+/// TLS configuration for the endpoints behind the self-signed
+/// (or private CA) certificates, with rustls.
+/// Without it, the client verifies the server certificates
+/// with the system roots.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TlsConfig {
+    /// Paths of the PEM-encoded root CA certificates to trust,
+    /// in addition to the system roots.
+    pub ca_cert_paths: Vec<String>,
+    /// Path of the PEM-encoded client private key, for mutual TLS.
+    pub client_key_path: Option<String>,
+    /// Path of the PEM-encoded client certificate, for mutual TLS
+    /// (e.g., generated by "`cert_manager::x509::generate_and_write_pem`").
+    pub client_cert_path: Option<String>,
+    /// Connects to the address for the domain, while still verifying
+    /// the certificate and sending the SNI of the domain
+    /// (e.g., the node IP for "node.example.com" without DNS).
+    pub resolve: BTreeMap<String, SocketAddr>,
+    /// Disables the SNI extension (e.g., for the servers that reject it).
+    pub disable_sni: bool,
+    /// Accepts the invalid server certificates (only for testing),
+    /// which the authenticated clients refuse. The clients verify the
+    /// certificates unless set (BREAKING: previously always accepted).
+    pub accept_invalid_certs: bool,
+}
+
+impl TlsConfig {
+    fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder> {
+        builder = builder
+            .use_rustls_tls()
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .tls_sni(!self.disable_sni);
+
+        for ca_cert_path in &self.ca_cert_paths {
+            let pem = std::fs::read(ca_cert_path).map_err(|e| Error::Other {
+                message: format!("failed to read CA cert '{ca_cert_path}' ({e})"),
+                retryable: false,
+            })?;
+            let ca = Certificate::from_pem(&pem).map_err(|e| Error::Other {
+                message: format!("invalid CA cert '{ca_cert_path}' ({e})"),
+                retryable: false,
+            })?;
+            builder = builder.add_root_certificate(ca);
+        }
+
+        match (&self.client_key_path, &self.client_cert_path) {
+            (None, None) => {}
+            (Some(key_path), Some(cert_path)) => {
+                let (mut key, cert) = cert_manager::x509::load_pem_to_vec(key_path, cert_path)
+                    .map_err(|e| Error::Other {
+                        message: format!("failed to load client key and cert ({e})"),
+                        retryable: false,
+                    })?;
+                key.extend(cert);
+                let identity = Identity::from_pem(&key).map_err(|e| Error::Other {
+                    message: format!("invalid client key and cert ({e})"),
+                    retryable: false,
+                })?;
+                builder = builder.identity(identity);
+            }
+            _ => {
+                return Err(Error::Other {
+                    message: "both client key and cert paths must be set".to_string(),
+                    retryable: false,
+                })
+            }
+        }
+
+        for (domain, addr) in &self.resolve {
+            builder = builder.resolve(domain, *addr);
+        }
+        Ok(builder)
+    }
+}
+
+/// Configures the "`Http`" client.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    pub pool_idle_timeout: Duration,
+    pub pool_max_idle_per_host: usize,
+    /// Disabled if none.
+    pub tcp_keepalive: Option<Duration>,
+    /// Proxy URL for all requests (e.g., "<http://127.0.0.1:8080>").
+    pub proxy: Option<String>,
+    /// Advertises and decodes the gzip-compressed responses.
+    pub gzip: bool,
+    /// Advertises and decodes the deflate-compressed responses.
+    pub deflate: bool,
+    /// Gzip-compresses the request bodies larger than this many bytes,
+    /// disabled if none (not all nodes accept the compressed requests).
+    pub compress_requests_over: Option<usize>,
+    pub tls: Option<TlsConfig>,
+    /// Authentication of the requests to the endpoints, keyed by the URL.
+    pub auths: Vec<(String, Auth)>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            tcp_keepalive: Some(DEFAULT_TCP_KEEPALIVE),
+            proxy: None,
+            gzip: true,
+            deflate: true,
+            compress_requests_over: None,
+            tls: None,
+            auths: Vec::new(),
+        }
+    }
+}
+
+impl Builder {
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = pool_max_idle_per_host;
+        self
+    }
+
+    #[must_use]
+    pub const fn tcp_keepalive(mut self, tcp_keepalive: Option<Duration>) -> Self {
+        self.tcp_keepalive = tcp_keepalive;
+        self
+    }
+
+    #[must_use]
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
+        self
+    }
+
+    #[must_use]
+    pub const fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    #[must_use]
+    pub const fn deflate(mut self, deflate: bool) -> Self {
+        self.deflate = deflate;
+        self
+    }
+
+    #[must_use]
+    pub const fn compress_requests_over(mut self, compress_requests_over: Option<usize>) -> Self {
+        self.compress_requests_over = compress_requests_over;
+        self
+    }
+
+    #[must_use]
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the authentication of the requests to the endpoint
+    /// (scheme, host and port of the URL, the path is ignored).
+    #[must_use]
+    pub fn auth(mut self, endpoint: &str, auth: Auth) -> Self {
+        self.auths.push((endpoint.to_string(), auth));
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL is invalid, the TLS files fail to load,
+    /// any authentication is invalid or set on the client accepting
+    /// the invalid server certificates, or the client fails to build.
+    pub fn build(self) -> Result<Http> {
+        let client = self.client(HeaderMap::new())?;
+        let mut auth_clients = HashMap::new();
+        for (endpoint, auth) in &self.auths {
+            let (key, client) = self.auth_client(endpoint, auth)?;
+            auth_clients.insert(key, client);
+        }
+        Ok(Http {
+            client,
+            auth_clients: Arc::new(auth_clients),
+            compress_requests_over: self.compress_requests_over,
+            builder: self,
+        })
+    }
+
+    /// Builds the client sending the authentication headers of the endpoint,
+    /// and returns it with the endpoint key.
+    fn auth_client(&self, endpoint: &str, auth: &Auth) -> Result<(String, Client)> {
+        if self
+            .tls
+            .as_ref()
+            .is_some_and(|tls| tls.accept_invalid_certs)
+        {
+            return Err(Error::Other {
+                message: format!(
+                    "refusing to send the credentials of '{endpoint}' with a client accepting invalid certificates"
+                ),
+                retryable: false,
+            });
+        }
+        auth.validate()?;
+        let key = endpoint_key(endpoint).ok_or_else(|| Error::Other {
+            message: format!("invalid endpoint URL '{endpoint}'"),
+            retryable: false,
+        })?;
+        Ok((key, self.client(auth.headers()?)?))
+    }
+
+    /// Builds the client with the settings, sending the headers
+    /// with every request.
+    fn client(&self, headers: HeaderMap) -> Result<Client> {
+        // the default headers survive the redirects to the other hosts,
+        // thus never follow the redirects with the credentials
+        let has_credentials = !headers.is_empty();
+        let redirect = if has_credentials {
+            redirect::Policy::none()
+        } else {
+            redirect::Policy::default()
+        };
+        let mut builder = ClientBuilder::new()
+            .user_agent(env!("CARGO_PKG_NAME"))
+            .default_headers(headers)
+            .redirect(redirect)
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .tcp_keepalive(self.tcp_keepalive)
+            .gzip(self.gzip)
+            .deflate(self.deflate)
+            // the verbose connection logs dump the request headers
+            .connection_verbose(!has_credentials);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy).map_err(|e| Error::Other {
+                message: format!("invalid proxy '{proxy}' ({e})"),
+                retryable: false,
+            })?);
+        }
+        if let Some(tls) = &self.tls {
+            builder = tls.apply(builder)?;
+        }
+
+        builder.build().map_err(|e| {
+            // TODO: check retryable
+            Error::Other {
+                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
+                retryable: false,
+            }
+        })
+    }
+}
+
+/// Sets the shared client used by all client methods of this process.
+///
+/// # Errors
+///
+/// Returns an error if the shared client is already set or used by any call.
+pub fn set_shared(http: Http) -> Result<()> {
+    SHARED.set(http).map_err(|_| Error::Other {
+        message: "shared HTTP client is already initialized".to_string(),
+        retryable: false,
+    })
+}
+
+/// Returns the shared client, with the default settings
+/// unless set via "`set_shared`".
+///
+/// # Errors
+///
+/// Returns an error if the default client fails to build.
+pub fn shared() -> Result<&'static Http> {
+    if let Some(http) = SHARED.get() {
+        return Ok(http);
+    }
+    let http = Http::new()?;
+    Ok(SHARED.get_or_init(|| http))
+}
+
+/// Runs the future with the client as the current client, so all client
+/// methods called in the future send the requests via the client
+/// (e.g., with the authentication of a wallet), instead of the shared one.
+pub async fn scope<F: Future>(http: Http, f: F) -> F::Output {
+    SCOPED.scope(http, f).await
+}
+
+/// Returns the client injected with "`scope`" for the current task,
+/// or the shared client otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the default shared client fails to build.
+pub fn current() -> Result<Http> {
+    if let Ok(http) = SCOPED.try_with(Http::clone) {
+        return Ok(http);
+    }
+    shared().cloned()
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `jsonrpc::client::http::test_shared` --exact --show-output
+#[test]
+fn test_shared() {
+    assert!(Http::builder()
+        .timeout(Duration::from_secs(1))
+        .tcp_keepalive(None)
+        .proxy("http://127.0.0.1:8080")
+        .build()
+        .is_ok());
+
+    let http = Http::builder()
+        .compress_requests_over(Some(16))
+        .build()
+        .unwrap();
+    let small = http
+        .post_json("http://127.0.0.1:9650/ext/info", "{}".to_string())
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(small.headers().get(CONTENT_ENCODING).is_none());
+    let large = http
+        .post_json("http://127.0.0.1:9650/ext/info", "{".repeat(1024))
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(large.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+    assert!(large.body().unwrap().as_bytes().unwrap().len() < 1024);
+
+    let secret = Auth::Bearer {
+        token: "secret".to_string(),
+    };
+    assert!(http.with_auth("not a url", secret.clone()).is_err());
+    assert!(http
+        .with_auth(
+            "http://127.0.0.1:9651",
+            Auth::Bearer {
+                token: "bad\ntoken".to_string()
+            }
+        )
+        .is_err());
+    let authed = http
+        .with_auth("http://127.0.0.1:9651/ext/bc/X", secret.clone())
+        .unwrap();
+    assert!(!format!("{authed:?}").contains("secret"));
+    // the default headers are only added on send, so check the clients
+    assert!(std::ptr::eq(
+        authed.client_for("http://127.0.0.1:9652/ext/health"),
+        authed.client()
+    ));
+    assert!(!std::ptr::eq(
+        authed.client_for("http://127.0.0.1:9651/ext/info"),
+        authed.client()
+    ));
+    // the credentials are scoped to the scheme
+    assert!(std::ptr::eq(
+        authed.client_for("https://127.0.0.1:9651/ext/info"),
+        authed.client()
+    ));
+    // the original client and the other clients are not affected
+    assert!(std::ptr::eq(
+        http.client_for("http://127.0.0.1:9651/ext/info"),
+        http.client()
+    ));
+    assert!(std::ptr::eq(
+        shared()
+            .unwrap()
+            .client_for("http://127.0.0.1:9651/ext/info"),
+        shared().unwrap().client()
+    ));
+
+    let auth: Auth = serde_yaml::from_str(
+        "
+kind: headers
+headers:
+  x-api-key: abc
+",
+    )
+    .unwrap();
+    assert_eq!(auth.headers().unwrap().get("x-api-key").unwrap(), "abc");
+    let basic = Auth::Basic {
+        username: "user".to_string(),
+        password: Some("pass".to_string()),
+    };
+    assert_eq!(
+        basic.headers().unwrap().get(AUTHORIZATION).unwrap(),
+        "Basic dXNlcjpwYXNz"
+    );
+    assert_eq!(format!("{basic:?}"), "Basic(user:***)");
+    assert_eq!(
+        secret.headers().unwrap().get(AUTHORIZATION).unwrap(),
+        "Bearer secret"
+    );
+
+    // no credentials for the clients accepting invalid certificates
+    let insecure = TlsConfig {
+        accept_invalid_certs: true,
+        ..Default::default()
+    };
+    assert!(Http::builder()
+        .tls(insecure.clone())
+        .auth("https://api.example.com", secret.clone())
+        .build()
+        .is_err());
+    let insecure = Http::builder().tls(insecure).build().unwrap();
+    assert!(insecure
+        .with_auth("https://api.example.com", secret)
+        .is_err());
+
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = dir.path().join("key.pem").display().to_string();
+    let cert_path = dir.path().join("cert.pem").display().to_string();
+    cert_manager::x509::generate_and_write_pem(None, &key_path, &cert_path).unwrap();
+    let tls = TlsConfig {
+        ca_cert_paths: vec![cert_path.clone()],
+        client_key_path: Some(key_path),
+        client_cert_path: Some(cert_path.clone()),
+        resolve: BTreeMap::from([(
+            "node.example.com".to_string(),
+            "127.0.0.1:9650".parse().unwrap(),
+        )]),
+        ..Default::default()
+    };
+    assert!(Http::builder().tls(tls.clone()).build().is_ok());
+    assert!(Http::builder()
+        .tls(TlsConfig {
+            client_key_path: None,
+            ..tls.clone()
+        })
+        .build()
+        .is_err());
+    assert!(Http::builder()
+        .tls(TlsConfig {
+            ca_cert_paths: vec![format!("{cert_path}.missing")],
+            ..tls
+        })
+        .build()
+        .is_err());
+
+    let first = shared().unwrap();
+    let second = shared().unwrap();
+    assert!(std::ptr::eq(first, second));
+    assert!(set_shared(Http::new().unwrap()).is_err());
+
+    // the scoped client is current only within the scope
+    let scoped = Http::builder()
+        .compress_requests_over(Some(7))
+        .build()
+        .unwrap();
+    let inner = tokio_test::block_on(scope(scoped, async { current().unwrap() }));
+    assert_eq!(inner.compress_requests_over, Some(7));
+    assert_eq!(current().unwrap().compress_requests_over, None);
+
+    assert!(http.provider("not a url").is_err());
+    let provider = http.provider("http://127.0.0.1:9650/ext/bc/C/rpc").unwrap();
+    assert_eq!(
+        provider.url().as_str(),
+        "http://127.0.0.1:9650/ext/bc/C/rpc"
+    );
+}
@@ -0,0 +1,376 @@
+//! Avalanche JSON-RPC Index API.
+//! Requires the node to run with "--index-enabled".
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use crate::{
+    errors::{Error, Result},
+    ids,
+    jsonrpc::client::{http, metrics, url},
+    jsonrpc::{self, index},
+    utils,
+};
+use futures::{stream, Stream};
+use tokio::time::sleep;
+
+/// Maximum number of containers "index.getContainerRange" returns per call.
+pub const MAX_CONTAINERS_PER_RANGE: u64 = 1024;
+
+/// Gets the accepted containers in [`start_index`, `start_index` + `num_to_fetch`).
+/// The node returns at most 1024 containers per call.
+///
+/// Uses the "index.getContainerRange" API endpoint.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerrange>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_container_range(
+    http_rpc: &str,
+    index: index::Index,
+    start_index: u64,
+    num_to_fetch: u64,
+) -> Result<index::GetContainerRangeResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(
+        &url::Path::Custom(index.path().to_string()),
+        scheme.as_deref(),
+        host.as_str(),
+        port,
+    )?;
+    log::info!("getting containers [{start_index}, +{num_to_fetch}) for {url}");
+
+    let method = String::from("index.getContainerRange");
+    let params = HashMap::from([
+        (String::from("startIndex"), start_index.to_string()),
+        (String::from("numToFetch"), num_to_fetch.to_string()),
+        (String::from("encoding"), String::from("hex")),
+    ])
+    .into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("index.getContainerRange", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Gets the most recently accepted container.
+///
+/// Uses the "index.getLastAccepted" API endpoint.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetlastaccepted>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_last_accepted(
+    http_rpc: &str,
+    index: index::Index,
+) -> Result<index::GetContainerResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(
+        &url::Path::Custom(index.path().to_string()),
+        scheme.as_deref(),
+        host.as_str(),
+        port,
+    )?;
+    log::info!("getting last accepted container for {url}");
+
+    let method = String::from("index.getLastAccepted");
+    let params = HashMap::from([(String::from("encoding"), String::from("hex"))]).into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("index.getLastAccepted", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Gets the container by its Id (e.g., transaction Id or block Id).
+///
+/// Uses the "index.getContainerByID" API endpoint.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerbyid>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn get_container_by_id(
+    http_rpc: &str,
+    index: index::Index,
+    container_id: &ids::Id,
+) -> Result<index::GetContainerResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(
+        &url::Path::Custom(index.path().to_string()),
+        scheme.as_deref(),
+        host.as_str(),
+        port,
+    )?;
+    log::info!("getting container {container_id} for {url}");
+
+    let method = String::from("index.getContainerByID");
+    let params = HashMap::from([
+        (String::from("id"), container_id.to_string()),
+        (String::from("encoding"), String::from("hex")),
+    ])
+    .into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("index.getContainerByID", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Options of [`subscribe_accepted`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SubscribeOptions {
+    /// Index of the first container to stream (e.g., to resume after restart).
+    /// If "None", streams the containers accepted after the subscription starts.
+    pub start_index: Option<u64>,
+    /// Polling interval right after new containers are found.
+    pub min_interval: Duration,
+    /// Polling interval is doubled on each empty poll, up to this value.
+    pub max_interval: Duration,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> Self {
+        Self {
+            start_index: None,
+            min_interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+struct Subscription {
+    http_rpc: String,
+    index: index::Index,
+    opts: SubscribeOptions,
+    next_index: Option<u64>,
+    interval: Duration,
+    pending: VecDeque<index::Container>,
+    polled: bool,
+}
+
+/// Streams the newly accepted containers of the index in order (e.g., P-chain
+/// blocks with "`index::Index::PBlock`"), by polling "index.getLastAccepted" and
+/// fetching the new ones with "index.getContainerRange".
+/// The poll interval adapts between the min and max intervals of the options.
+///
+/// The stream never ends. Failed polls are yielded as errors and retried
+/// after the current interval, so the caller decides whether to stop.
+pub fn subscribe_accepted(
+    http_rpc: &str,
+    index: index::Index,
+    opts: SubscribeOptions,
+) -> impl Stream<Item = Result<index::Container>> {
+    let state = Subscription {
+        http_rpc: http_rpc.to_string(),
+        index,
+        opts,
+        next_index: opts.start_index,
+        interval: opts.min_interval,
+        pending: VecDeque::new(),
+        polled: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(container) = state.pending.pop_front() {
+                return Some((Ok(container), state));
+            }
+
+            // no wait for the first poll
+            if state.polled {
+                sleep(state.interval).await;
+            }
+            state.polled = true;
+
+            match poll_accepted(&state.http_rpc, state.index, state.next_index).await {
+                Ok((next_index, containers)) => {
+                    state.interval = next_interval(
+                        state.interval,
+                        state.opts.min_interval,
+                        state.opts.max_interval,
+                        !containers.is_empty(),
+                    );
+                    state.next_index = Some(next_index);
+                    state.pending.extend(containers);
+                }
+                Err(e) => {
+                    log::warn!("failed to poll accepted containers '{e}'");
+                    state.interval = next_interval(
+                        state.interval,
+                        state.opts.min_interval,
+                        state.opts.max_interval,
+                        false,
+                    );
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+/// Fetches the containers accepted since "`next_index`" (at most one range),
+/// and returns the index to poll next with the containers.
+/// If "`next_index`" is "None", starts after the last accepted container.
+async fn poll_accepted(
+    http_rpc: &str,
+    index: index::Index,
+    next_index: Option<u64>,
+) -> Result<(u64, Vec<index::Container>)> {
+    // an empty index fails with the JSON-RPC error object, which
+    // "get_last_accepted" returns as "Error::JsonRpc"
+    let resp = get_last_accepted(http_rpc, index).await?;
+    let Some(last) = resp.result else {
+        return Err(Error::API {
+            message: "failed index.getLastAccepted (no result)".to_string(),
+            retryable: true,
+        });
+    };
+    let Some(next_index) = next_index else {
+        return Ok((last.index.saturating_add(1), Vec::new()));
+    };
+    if last.index < next_index {
+        return Ok((next_index, Vec::new()));
+    }
+
+    let num_to_fetch = (last.index - next_index + 1).min(MAX_CONTAINERS_PER_RANGE);
+    let resp = get_container_range(http_rpc, index, next_index, num_to_fetch).await?;
+    let Some(result) = resp.result else {
+        return Err(Error::API {
+            message: "failed index.getContainerRange (no result)".to_string(),
+            retryable: true,
+        });
+    };
+    let next_index = result
+        .containers
+        .last()
+        .map_or(next_index, |c| c.index.saturating_add(1));
+    Ok((next_index, result.containers))
+}
+
+/// Resets to the min interval when new containers are found,
+/// otherwise doubles the interval up to the max.
+fn next_interval(cur: Duration, min: Duration, max: Duration, found: bool) -> Duration {
+    if found {
+        return min;
+    }
+    cur.saturating_mul(2).clamp(min, max.max(min))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `jsonrpc::client::index::test_next_interval` --exact --show-output
+#[test]
+fn test_next_interval() {
+    let min = Duration::from_millis(500);
+    let max = Duration::from_secs(3);
+
+    let mut cur = min;
+    let mut intervals = Vec::new();
+    for _ in 0..5 {
+        cur = next_interval(cur, min, max, false);
+        intervals.push(cur.as_millis());
+    }
+    assert_eq!(intervals, vec![1000, 2000, 3000, 3000, 3000]);
+    assert_eq!(next_interval(cur, min, max, true), min);
+
+    // max below min
+    assert_eq!(next_interval(min, min, Duration::ZERO, false), min);
+}
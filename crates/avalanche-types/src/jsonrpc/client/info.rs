@@ -1,14 +1,13 @@
 //! Avalanche JSON-RPC Info API.
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
 
 use crate::{
     errors::{Error, Result},
     ids,
-    jsonrpc::client::url,
+    jsonrpc::client::{http, metrics, url},
     jsonrpc::{self, info},
     utils,
 };
-use reqwest::{header::CONTENT_TYPE, ClientBuilder};
 
 /// Gets the network name from the Avalanche node.
 ///
@@ -39,26 +38,11 @@ pub async fn get_network_name(http_rpc: &str) -> Result<info::GetNetworkNameResp
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getNetworkName", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -73,10 +57,7 @@ pub async fn get_network_name(http_rpc: &str) -> Result<info::GetNetworkNameResp
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Gets the network ID from the Avalanche node.
@@ -108,26 +89,11 @@ pub async fn get_network_id(http_rpc: &str) -> Result<info::GetNetworkIdResponse
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getNetworkID", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -142,10 +108,7 @@ pub async fn get_network_id(http_rpc: &str) -> Result<info::GetNetworkIdResponse
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Gets the blockchain ID for a given chain alias.
@@ -183,26 +146,11 @@ pub async fn get_blockchain_id(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getBlockchainID", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -217,10 +165,7 @@ pub async fn get_blockchain_id(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Gets the node ID from the Avalanche node.
@@ -251,26 +196,11 @@ pub async fn get_node_id(http_rpc: &str) -> Result<info::GetNodeIdResponse> {
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getNodeID", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -285,10 +215,7 @@ pub async fn get_node_id(http_rpc: &str) -> Result<info::GetNodeIdResponse> {
     })?;
     let out: Vec<u8> = out.into();
 
-    let resp: info::GetNodeIdResponse = serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })?;
+    let resp: info::GetNodeIdResponse = super::decode_response(&out)?;
 
     if let Some(res) = &resp.result {
         if let Some(pop) = &res.node_pop {
@@ -350,26 +277,11 @@ pub async fn get_node_version(http_rpc: &str) -> Result<info::GetNodeVersionResp
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getNodeVersion", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -384,10 +296,7 @@ pub async fn get_node_version(http_rpc: &str) -> Result<info::GetNodeVersionResp
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Gets the available VMs from the Avalanche node.
@@ -419,26 +328,11 @@ pub async fn get_vms(http_rpc: &str) -> Result<info::GetVmsResponse> {
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getVMs", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -453,10 +347,7 @@ pub async fn get_vms(http_rpc: &str) -> Result<info::GetVmsResponse> {
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Checks if the node is bootstrapped.
@@ -488,26 +379,69 @@ pub async fn is_bootstrapped(http_rpc: &str) -> Result<info::IsBootstrappedRespo
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.isBootstrapped", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Checks if the chain is bootstrapped on the node.
+/// e.g., "X", "P", "C", or the blockchain Id.
+///
+/// Uses the "info.isBootstrapped" API endpoint.
+/// ref. <https://docs.avax.network/build/avalanchego-apis/info/#infoisbootstrapped>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn is_chain_bootstrapped(
+    http_rpc: &str,
+    chain: &str,
+) -> Result<info::IsBootstrappedResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
                 retryable: false,
             }
         })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let url = url::try_create_url(&url::Path::Info, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting bootstrapped for chain {chain} via {url}");
+
+    let method = String::from("info.isBootstrapped");
+    let params = HashMap::from([(String::from("chain"), String::from(chain))]).into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.isBootstrapped", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -522,10 +456,7 @@ pub async fn is_bootstrapped(http_rpc: &str) -> Result<info::IsBootstrappedRespo
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// Gets the transaction fee from the Avalanche node.
@@ -561,26 +492,11 @@ pub async fn get_tx_fee(http_rpc: &str) -> Result<info::GetTxFeeResponse> {
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.getTxFee", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -595,10 +511,7 @@ pub async fn get_tx_fee(http_rpc: &str) -> Result<info::GetTxFeeResponse> {
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "info.peers".
@@ -639,26 +552,66 @@ pub async fn peers(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.peers", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Gets the uptime of the node as seen by the network.
+/// Queries the primary network if the subnet Id is None.
+///
+/// Uses the "info.uptime" API endpoint, which fails if the node is not a validator.
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/info#infouptime>
+///
+/// # Errors
+///
+/// Returns an error if the API request fails.
+pub async fn uptime(http_rpc: &str, subnet_id: Option<ids::Id>) -> Result<info::UptimeResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
                 retryable: false,
             }
         })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let url = url::try_create_url(&url::Path::Info, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting uptime for {url}");
+
+    let method = String::from("info.uptime");
+    let params = subnet_id.map(|id| HashMap::from([(String::from("subnetID"), id.to_string())]));
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("info.uptime", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -673,8 +626,5 @@ pub async fn peers(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
@@ -0,0 +1,171 @@
+//! Request metrics of the JSON-RPC client.
+//!
+//! The metrics are only recorded with the "`jsonrpc_client_metrics`" feature,
+//! once registered via "`register`". Otherwise, the timers are no-ops.
+use std::time::Instant;
+
+use hyper::Uri;
+
+#[cfg(feature = "jsonrpc_client_metrics")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "jsonrpc_client_metrics")]
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+/// Namespace of the metrics (e.g., "`avalanche_jsonrpc_client_requests_total`").
+pub const NAMESPACE: &str = "avalanche_jsonrpc_client";
+
+/// Latency buckets in seconds, up to the 15-second request timeout.
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 15.0,
+];
+
+#[cfg(feature = "jsonrpc_client_metrics")]
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Request metrics labeled by the JSON-RPC method and the endpoint ("host:port").
+#[cfg(feature = "jsonrpc_client_metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc_client_metrics")))]
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    /// Total number of requests sent.
+    pub requests: IntCounterVec,
+    /// Total number of requests that failed to send or returned non-2xx status.
+    pub errors: IntCounterVec,
+    /// Request latency in seconds.
+    pub latency: HistogramVec,
+}
+
+#[cfg(feature = "jsonrpc_client_metrics")]
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let labels = &["method", "endpoint"];
+        Ok(Self {
+            requests: IntCounterVec::new(
+                Opts::new("requests_total", "Total number of JSON-RPC requests")
+                    .namespace(NAMESPACE),
+                labels,
+            )?,
+            errors: IntCounterVec::new(
+                Opts::new("errors_total", "Total number of failed JSON-RPC requests")
+                    .namespace(NAMESPACE),
+                labels,
+            )?,
+            latency: HistogramVec::new(
+                HistogramOpts::new("latency_seconds", "JSON-RPC request latency in seconds")
+                    .namespace(NAMESPACE)
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                labels,
+            )?,
+        })
+    }
+}
+
+/// Registers the client metrics to the registry, and starts recording them
+/// for all requests of this process.
+///
+/// # Errors
+///
+/// Returns an error if the metrics have already been registered
+/// (e.g., to another registry), or if the registry rejects them.
+#[cfg(feature = "jsonrpc_client_metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc_client_metrics")))]
+pub fn register(registry: &Registry) -> prometheus::Result<()> {
+    if METRICS.get().is_some() {
+        return Err(prometheus::Error::AlreadyReg);
+    }
+
+    let metrics = Metrics::new()?;
+    registry.register(Box::new(metrics.requests.clone()))?;
+    registry.register(Box::new(metrics.errors.clone()))?;
+    registry.register(Box::new(metrics.latency.clone()))?;
+
+    METRICS
+        .set(metrics)
+        .map_err(|_| prometheus::Error::AlreadyReg)
+}
+
+/// Returns the registered metrics, if any.
+#[cfg(feature = "jsonrpc_client_metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc_client_metrics")))]
+#[must_use]
+pub fn get() -> Option<&'static Metrics> {
+    METRICS.get()
+}
+
+/// Measures a single request, from before sending it to receiving the response headers.
+#[derive(Debug)]
+#[must_use]
+pub struct Timer {
+    method: &'static str,
+    endpoint: String,
+    start: Instant,
+}
+
+impl Timer {
+    pub fn start(method: &'static str, url: &Uri) -> Self {
+        Self {
+            method,
+            endpoint: url
+                .authority()
+                .map_or_else(String::new, ToString::to_string),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the request with its outcome.
+    pub fn observe(self, resp: &reqwest::Result<reqwest::Response>) {
+        let ok = resp.as_ref().is_ok_and(|r| r.status().is_success());
+        log::debug!(
+            "{} to {} took {:?} (ok {ok})",
+            self.method,
+            self.endpoint,
+            self.start.elapsed()
+        );
+
+        #[cfg(feature = "jsonrpc_client_metrics")]
+        if let Some(m) = METRICS.get() {
+            let labels = [self.method, self.endpoint.as_str()];
+            m.requests.with_label_values(&labels).inc();
+            if !ok {
+                m.errors.with_label_values(&labels).inc();
+            }
+            m.latency
+                .with_label_values(&labels)
+                .observe(self.start.elapsed().as_secs_f64());
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client,jsonrpc_client_metrics -- `jsonrpc::client::metrics::test_metrics` --exact --show-output
+#[cfg(feature = "jsonrpc_client_metrics")]
+#[test]
+fn test_metrics() {
+    let registry = Registry::new();
+    register(&registry).unwrap();
+    assert!(register(&Registry::new()).is_err());
+
+    let url = Uri::from_static("http://127.0.0.1:9650/ext/info");
+    let err = reqwest::Client::new().get("http://").build().unwrap_err();
+    Timer::start("info.getNetworkID", &url).observe(&Err(err));
+
+    let m = get().unwrap();
+    let labels = ["info.getNetworkID", "127.0.0.1:9650"];
+    assert_eq!(m.requests.with_label_values(&labels).get(), 1);
+    assert_eq!(m.errors.with_label_values(&labels).get(), 1);
+    assert_eq!(m.latency.with_label_values(&labels).get_sample_count(), 1);
+
+    let names: Vec<String> = registry
+        .gather()
+        .iter()
+        .map(|mf| mf.get_name().to_string())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "avalanche_jsonrpc_client_errors_total",
+            "avalanche_jsonrpc_client_latency_seconds",
+            "avalanche_jsonrpc_client_requests_total",
+        ]
+    );
+}
@@ -1,9 +1,59 @@
 //! JSON-RPC client methods and URL utilities.
+//!
+//! The client methods return the "error" object of the JSON-RPC responses
+//! as "`Error::JsonRpc`" (with the code, message and data), so the callers
+//! only handle the "result" of the "Ok" responses.
+//!
+//! BREAKING: the X/P/C-chain, info and index client methods used to return
+//! "Ok" with the "error" field of the response set. Match "`Error::JsonRpc`"
+//! (or "`Error::rpc_kind`") instead of checking the "error" field.
 
 pub mod admin;
+pub mod c;
+pub mod cluster;
 pub mod evm;
 pub mod health;
+pub mod http;
+pub mod index;
 pub mod info;
+pub mod metrics;
 pub mod p;
 pub mod url;
 pub mod x;
+
+/// Maximum number of UTXOs the node returns per "getUTXOs" call.
+/// ref. "maxPageSize" in avalanchego "vms/avm/service.go" and "vms/platformvm/service.go"
+pub const MAX_UTXOS_PAGE_SIZE: u32 = 1024;
+
+/// Returns the transaction Id of the "issueTx" response.
+fn issued_tx_id(tx_id: Option<crate::ids::Id>) -> crate::errors::Result<crate::ids::Id> {
+    let tx_id = tx_id.ok_or_else(|| crate::errors::Error::API {
+        message: "failed to issue tx (no result)".to_string(),
+        retryable: false,
+    })?;
+    log::info!("{tx_id} successfully issued");
+    Ok(tx_id)
+}
+
+/// Returns the "error" object of the JSON-RPC response body, if any.
+fn response_error(out: &[u8]) -> Option<crate::jsonrpc::ResponseError> {
+    /// Only the "error" field of any JSON-RPC response.
+    #[derive(serde::Deserialize)]
+    struct ErrorOnly {
+        /// The "error" object, "None" on success.
+        error: Option<crate::jsonrpc::ResponseError>,
+    }
+    serde_json::from_slice::<ErrorOnly>(out).ok()?.error
+}
+
+/// Decodes the JSON-RPC response body, and returns its "error" object
+/// as "Error::JsonRpc", so the callers only need to handle the "result".
+fn decode_response<T: serde::de::DeserializeOwned>(out: &[u8]) -> crate::errors::Result<T> {
+    if let Some(e) = response_error(out) {
+        return Err(e.into());
+    }
+    serde_json::from_slice(out).map_err(|e| crate::errors::Error::Other {
+        message: format!("failed serde_json::from_slice '{e}'"),
+        retryable: false,
+    })
+}
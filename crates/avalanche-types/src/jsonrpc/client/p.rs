@@ -3,12 +3,14 @@ use std::{collections::HashMap, time::Duration};
 
 use crate::{
     errors::{Error, Result},
-    ids,
-    jsonrpc::client::url,
+    formatting, ids,
+    jsonrpc::client::{http, issued_tx_id, metrics, url, MAX_UTXOS_PAGE_SIZE},
     jsonrpc::{self, platformvm},
-    utils,
+    platformvm::txs::status::Status as TxStatus,
+    txs, utils,
 };
-use reqwest::{header::CONTENT_TYPE, ClientBuilder};
+use futures::{stream, Stream};
+use tokio::time::{sleep, Instant};
 
 /// "platform.issueTx" on "http://`['ADDR']`:9650" and "/ext/P" path.
 /// ref. <https://docs.avax.network/build/avalanchego-apis/p-chain/#platformgetcurrentvalidators>
@@ -17,6 +19,34 @@ use reqwest::{header::CONTENT_TYPE, ClientBuilder};
 ///
 /// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
 pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<platformvm::IssueTxResponse> {
+    issue_encoded_tx(
+        http_rpc,
+        prefix_manager::prepend_0x(tx),
+        formatting::Encoding::Hex,
+    )
+    .await
+}
+
+/// Issues the pre-signed transaction bytes (e.g., signed offline) via "platform.issueTx"
+/// in the encoding, and returns the transaction Id.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the API returns an error.
+pub async fn issue_raw_tx(
+    http_rpc: &str,
+    tx_bytes: &[u8],
+    encoding: formatting::Encoding,
+) -> Result<ids::Id> {
+    let resp = issue_encoded_tx(http_rpc, encoding.encode(tx_bytes), encoding).await?;
+    issued_tx_id(resp.result.map(|r| r.tx_id))
+}
+
+async fn issue_encoded_tx(
+    http_rpc: &str,
+    tx: String,
+    encoding: formatting::Encoding,
+) -> Result<platformvm::IssueTxResponse> {
     let (scheme, host, port, _, _) =
         utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
@@ -29,8 +59,8 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<platformvm::IssueTxRes
 
     let method = String::from("platform.issueTx");
     let params = platformvm::IssueTxParams {
-        tx: prefix_manager::prepend_0x(tx),
-        encoding: String::from("hex"), // don't use "cb58"
+        tx,
+        encoding: encoding.as_str().to_string(),
     }
     .into();
 
@@ -45,26 +75,11 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<platformvm::IssueTxRes
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.issueTx", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -79,10 +94,7 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<platformvm::IssueTxRes
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getTx" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -120,26 +132,11 @@ pub async fn get_tx(http_rpc: &str, tx_id: &str) -> Result<platformvm::GetTxResp
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getTx", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -154,10 +151,7 @@ pub async fn get_tx(http_rpc: &str, tx_id: &str) -> Result<platformvm::GetTxResp
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getTxStatus" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -191,26 +185,11 @@ pub async fn get_tx_status(http_rpc: &str, tx_id: &str) -> Result<platformvm::Ge
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getTxStatus", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -225,10 +204,7 @@ pub async fn get_tx_status(http_rpc: &str, tx_id: &str) -> Result<platformvm::Ge
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getHeight" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -262,26 +238,11 @@ pub async fn get_height(http_rpc: &str) -> Result<platformvm::GetHeightResponse>
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getHeight", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -296,10 +257,7 @@ pub async fn get_height(http_rpc: &str) -> Result<platformvm::GetHeightResponse>
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getBalance" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -335,26 +293,11 @@ pub async fn get_balance(http_rpc: &str, paddr: &str) -> Result<platformvm::GetB
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getBalance", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -369,19 +312,181 @@ pub async fn get_balance(http_rpc: &str, paddr: &str) -> Result<platformvm::GetB
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getUTXOs" on "http://`['ADDR']`:9650" and "/ext/P" path.
+/// Fetches all UTXOs of the address, following the end-index cursors
+/// with the maximum page size.
 /// ref. <https://docs.avax.network/build/avalanchego-apis/p-chain/#platformgetutxos>
 ///
 /// # Errors
 ///
 /// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
 pub async fn get_utxos(http_rpc: &str, paddr: &str) -> Result<platformvm::GetUtxosResponse> {
+    get_utxos_with_page_size(http_rpc, paddr, MAX_UTXOS_PAGE_SIZE).await
+}
+
+/// Fetches all UTXOs of the address in pages of "`page_size`" UTXOs
+/// (at most "`MAX_UTXOS_PAGE_SIZE`"), and aggregates them into one response.
+/// Returns the error response as is if the first page fails.
+///
+/// # Errors
+///
+/// Returns an error if a request fails, or if the API returns an error after the first page.
+pub async fn get_utxos_with_page_size(
+    http_rpc: &str,
+    paddr: &str,
+    page_size: u32,
+) -> Result<platformvm::GetUtxosResponse> {
+    get_utxos_from(http_rpc, paddr, page_size, None).await
+}
+
+/// Fetches all UTXOs of the address (or the atomic UTXOs exported from
+/// "`source_chain`") page by page, and aggregates them into one response.
+async fn get_utxos_from(
+    http_rpc: &str,
+    paddr: &str,
+    page_size: u32,
+    source_chain: Option<String>,
+) -> Result<platformvm::GetUtxosResponse> {
+    let page_size = page_size.clamp(1, MAX_UTXOS_PAGE_SIZE);
+
+    let mut aggregated = platformvm::GetUtxosResult {
+        encoding: Some(String::from("hex")),
+        ..Default::default()
+    };
+    let mut start_index = None;
+    let mut pages = 0_u32;
+    loop {
+        let resp = get_utxos_page_from(
+            http_rpc,
+            paddr,
+            page_size,
+            start_index.clone(),
+            source_chain.clone(),
+        )
+        .await?;
+        let Some(result) = resp.result else {
+            if pages == 0 {
+                return Ok(resp);
+            }
+            return Err(Error::API {
+                message: format!("failed platform.getUTXOs page {pages} (no result)"),
+                retryable: true,
+            });
+        };
+        pages += 1;
+
+        aggregated.num_fetched += result.num_fetched;
+        aggregated
+            .utxos
+            .get_or_insert_with(Vec::new)
+            .extend(result.utxos.unwrap_or_default());
+
+        let done = result.num_fetched < page_size
+            || result.end_index.is_none()
+            || result.end_index == start_index;
+        aggregated.end_index = result.end_index.clone();
+        if done {
+            log::info!(
+                "fetched {} UTXOs in {pages} page(s)",
+                aggregated.num_fetched
+            );
+            return Ok(platformvm::GetUtxosResponse {
+                jsonrpc: resp.jsonrpc,
+                id: resp.id,
+                result: Some(aggregated),
+                error: None,
+            });
+        }
+        start_index = result.end_index;
+    }
+}
+
+/// Streams the UTXOs of the address page by page, following the end-index cursors.
+/// Each item is one page of at most "`page_size`" UTXOs.
+pub fn stream_utxos(
+    http_rpc: &str,
+    paddr: &str,
+    page_size: u32,
+) -> impl Stream<Item = Result<Vec<txs::utxo::Utxo>>> {
+    let page_size = page_size.clamp(1, MAX_UTXOS_PAGE_SIZE);
+    let http_rpc = http_rpc.to_string();
+    let paddr = paddr.to_string();
+
+    // "None" once the last page is fetched
+    let cursor: Option<Option<jsonrpc::EndIndex>> = Some(None);
+    stream::try_unfold(cursor, move |cursor| {
+        let http_rpc = http_rpc.clone();
+        let paddr = paddr.clone();
+        async move {
+            let Some(start_index) = cursor else {
+                return Ok(None);
+            };
+            let resp = get_utxos_page(&http_rpc, &paddr, page_size, start_index.clone()).await?;
+            let Some(result) = resp.result else {
+                return Err(Error::API {
+                    message: "failed platform.getUTXOs (no result)".to_string(),
+                    retryable: true,
+                });
+            };
+
+            let done = result.num_fetched < page_size
+                || result.end_index.is_none()
+                || result.end_index == start_index;
+            let next = if done { None } else { Some(result.end_index) };
+            Ok(Some((result.utxos.unwrap_or_default(), next)))
+        }
+    })
+}
+
+/// Fetches one page of UTXOs, starting after "`start_index`"
+/// (the "endIndex" of the previous page, or "None" for the first page).
+/// ref. <https://docs.avax.network/build/avalanchego-apis/p-chain/#platformgetutxos>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_utxos_page(
+    http_rpc: &str,
+    paddr: &str,
+    limit: u32,
+    start_index: Option<jsonrpc::EndIndex>,
+) -> Result<platformvm::GetUtxosResponse> {
+    get_utxos_page_from(http_rpc, paddr, limit, start_index, None).await
+}
+
+/// e.g., "platform.getUTXOs" with "sourceChain" on "http://\[ADDR\]:9650".
+/// Fetches the atomic UTXOs of the address that were exported from
+/// "`source_chain`" (chain Id or alias) and are not imported yet,
+/// following the end-index cursors like "`get_utxos`".
+/// ref. <https://docs.avax.network/build/avalanchego-apis/p-chain/#platformgetutxos>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_atomic_utxos(
+    http_rpc: &str,
+    paddr: &str,
+    source_chain: &str,
+) -> Result<platformvm::GetUtxosResponse> {
+    get_utxos_from(
+        http_rpc,
+        paddr,
+        MAX_UTXOS_PAGE_SIZE,
+        Some(source_chain.to_string()),
+    )
+    .await
+}
+
+async fn get_utxos_page_from(
+    http_rpc: &str,
+    paddr: &str,
+    limit: u32,
+    start_index: Option<jsonrpc::EndIndex>,
+    source_chain: Option<String>,
+) -> Result<platformvm::GetUtxosResponse> {
     let (scheme, host, port, _, _) =
         utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
@@ -395,8 +500,10 @@ pub async fn get_utxos(http_rpc: &str, paddr: &str) -> Result<platformvm::GetUtx
     let method = String::from("platform.getUTXOs");
     let params = platformvm::GetUtxosParams {
         addresses: vec![paddr.to_string()],
-        limit: 100,
+        limit,
+        start_index,
         encoding: String::from("hex"), // don't use "cb58"
+        source_chain,
     }
     .into();
 
@@ -410,26 +517,11 @@ pub async fn get_utxos(http_rpc: &str, paddr: &str) -> Result<platformvm::GetUtx
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getUTXOs", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -444,10 +536,7 @@ pub async fn get_utxos(http_rpc: &str, paddr: &str) -> Result<platformvm::GetUtx
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getCurrentValidators" on "http://\[ADDR\]:9650" and "/ext/P" path.
@@ -484,26 +573,11 @@ pub async fn get_primary_network_validators(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getCurrentValidators", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -518,10 +592,7 @@ pub async fn get_primary_network_validators(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getCurrentValidators" on "http://\[ADDR\]:9650" and "/ext/P" path.
@@ -560,26 +631,11 @@ pub async fn get_subnet_validators(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getCurrentValidators", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -594,10 +650,7 @@ pub async fn get_subnet_validators(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getSubnets" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -639,26 +692,11 @@ pub async fn get_subnets(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getSubnets", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -673,10 +711,7 @@ pub async fn get_subnets(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getBlockchains" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -709,26 +744,11 @@ pub async fn get_blockchains(http_rpc: &str) -> Result<platformvm::GetBlockchain
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getBlockchains", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -743,10 +763,7 @@ pub async fn get_blockchains(http_rpc: &str) -> Result<platformvm::GetBlockchain
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// "platform.getBlockchainStatus" on "http://`['ADDR']`:9650" and "/ext/P" path.
@@ -783,26 +800,64 @@ pub async fn get_blockchain_status(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getBlockchainStatus", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// "platform.getFeeConfig" on "http://`['ADDR']`:9650" and "/ext/P" path.
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeeconfig>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_fee_config(http_rpc: &str) -> Result<platformvm::GetFeeConfigResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
                 retryable: false,
             }
         })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let url = url::try_create_url(&url::Path::P, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting fee config via {url}");
+
+    let method = String::from("platform.getFeeConfig");
+    let params = HashMap::new().into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getFeeConfig", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -817,8 +872,253 @@ pub async fn get_blockchain_status(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
+    super::decode_response(&out)
+}
+
+/// "platform.getFeeState" on "http://`['ADDR']`:9650" and "/ext/P" path.
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeestate>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_fee_state(http_rpc: &str) -> Result<platformvm::GetFeeStateResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(&url::Path::P, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting fee state via {url}");
+
+    let method = String::from("platform.getFeeState");
+    let params = HashMap::new().into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
         retryable: false,
-    })
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getFeeState", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// "platform.getCurrentSupply" on "http://`['ADDR']`:9650" and "/ext/P" path.
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetcurrentsupply>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_current_supply(http_rpc: &str) -> Result<platformvm::GetCurrentSupplyResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(&url::Path::P, scheme.as_deref(), host.as_str(), port)?;
+    log::info!("getting current supply via {url}");
+
+    let method = String::from("platform.getCurrentSupply");
+    let params = HashMap::new().into();
+
+    let data = jsonrpc::Request {
+        method,
+        params,
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getCurrentSupply", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// "platform.getValidatorsAt" on "http://`['ADDR']`:9650" and "/ext/P" path.
+/// Returns the validators of the subnet (or the primary network if none)
+/// at the P-chain height.
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetvalidatorsat>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_validators_at(
+    http_rpc: &str,
+    height: u64,
+    subnet_id: Option<&ids::Id>,
+) -> Result<platformvm::GetValidatorsAtResponse> {
+    let (scheme, host, port, _, _) =
+        utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
+            Error::Other {
+                message: format!("failed extract_scheme_host_port_path_chain_alias '{e}'"),
+                retryable: false,
+            }
+        })?;
+    let url = url::try_create_url(&url::Path::P, scheme.as_deref(), host.as_str(), port)?;
+    log::debug!("getting validators at height {height} via {url}");
+
+    let method = String::from("platform.getValidatorsAt");
+    let mut params = HashMap::from([(String::from("height"), height.to_string())]);
+    if let Some(subnet_id) = subnet_id {
+        params.insert(String::from("subnetID"), subnet_id.to_string());
+    }
+
+    let data = jsonrpc::Request {
+        method,
+        params: params.into(),
+        ..Default::default()
+    };
+
+    let d = data.encode_json().map_err(|e| Error::Other {
+        message: format!("failed encode_json '{e}'"),
+        retryable: false,
+    })?;
+
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("platform.getValidatorsAt", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
+            // TODO: check retryable
+            Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: false,
+            })?;
+    let out = resp.bytes().await.map_err(|e| {
+        // TODO: check retryable
+        Error::Other {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        }
+    })?;
+    let out: Vec<u8> = out.into();
+
+    super::decode_response(&out)
+}
+
+/// Polls the transaction status until the transaction is decided,
+/// and returns the final status ("Committed", "Aborted", or "Dropped").
+/// Used to resume waiting for a transaction issued before a restart.
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the API returns an error,
+/// or if the transaction is not decided within the timeout.
+pub async fn wait_for_tx_decided(
+    http_rpc: &str,
+    tx_id: ids::Id,
+    poll_interval: Duration,
+    poll_timeout: Duration,
+) -> Result<TxStatus> {
+    log::info!("polling tx status for {tx_id} via {http_rpc}");
+    let start = Instant::now();
+    loop {
+        let resp = get_tx_status(http_rpc, &tx_id.to_string()).await?;
+        let status = resp.result.unwrap_or_default().status;
+        match status {
+            TxStatus::Committed | TxStatus::Aborted | TxStatus::Dropped => {
+                log::info!("{tx_id} decided as {status}");
+                return Ok(status);
+            }
+            _ => {}
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed.gt(&poll_timeout) {
+            return Err(Error::API {
+                message: format!(
+                    "tx {tx_id} not decided in time (status {status}, elapsed {elapsed:?})"
+                ),
+                retryable: true,
+            });
+        }
+        log::warn!("{tx_id} is {status} (not decided yet, elapsed {elapsed:?})");
+        sleep(poll_interval).await;
+    }
+}
+
+/// Polls "platform.getBlockchainStatus" until the node validates the blockchain,
+/// e.g., after creating a blockchain and adding the node as a subnet validator.
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the API returns an error,
+/// or if the blockchain is not validated within the timeout.
+pub async fn wait_for_blockchain_validating(
+    http_rpc: &str,
+    blockchain_id: ids::Id,
+    poll_interval: Duration,
+    poll_timeout: Duration,
+) -> Result<()> {
+    log::info!("polling blockchain status for {blockchain_id} via {http_rpc}");
+    let start = Instant::now();
+    loop {
+        let resp = get_blockchain_status(http_rpc, blockchain_id).await?;
+        let status = resp.result.unwrap_or_default().status;
+        if status == platformvm::BlockchainStatus::Validating {
+            log::info!("blockchain {blockchain_id} is now validating");
+            return Ok(());
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed.gt(&poll_timeout) {
+            return Err(Error::API {
+                message: format!(
+                    "blockchain {blockchain_id} not validating in time (status {status}, elapsed {elapsed:?})"
+                ),
+                retryable: true,
+            });
+        }
+        log::warn!(
+            "blockchain {blockchain_id} is {status} (not validating yet, elapsed {elapsed:?})"
+        );
+        sleep(poll_interval).await;
+    }
 }
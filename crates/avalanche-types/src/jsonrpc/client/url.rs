@@ -30,6 +30,9 @@ pub enum Path {
     /// The C-chain url path /ext/bc/C/rpc
     #[strum(to_string = "/ext/bc/C/rpc")]
     C,
+    /// The C-chain atomic (avax) url path /ext/bc/C/avax
+    #[strum(to_string = "/ext/bc/C/avax")]
+    CAvax,
     /// A custom path for a subnet rpc url for example.
     #[strum(to_string = "{0}")]
     Custom(String),
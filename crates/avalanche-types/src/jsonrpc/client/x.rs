@@ -1,13 +1,14 @@
 //! Avalanche X-Chain JSON-RPC client.
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
 
 use crate::{
     errors::{Error, Result},
-    jsonrpc::client::url,
+    formatting, ids,
+    jsonrpc::client::{http, issued_tx_id, metrics, url, MAX_UTXOS_PAGE_SIZE},
     jsonrpc::{self, avm},
-    utils,
+    txs, utils,
 };
-use reqwest::{header::CONTENT_TYPE, ClientBuilder};
+use futures::{stream, Stream};
 
 /// e.g., "avm.issueTx" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
 /// ref. <https://docs.avax.network/apis/avalanchego/apis/x-chain/#avmissuetx>
@@ -16,6 +17,34 @@ use reqwest::{header::CONTENT_TYPE, ClientBuilder};
 ///
 /// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
 pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<avm::IssueTxResponse> {
+    issue_encoded_tx(
+        http_rpc,
+        prefix_manager::prepend_0x(tx),
+        formatting::Encoding::Hex,
+    )
+    .await
+}
+
+/// Issues the pre-signed transaction bytes (e.g., signed offline) via "avm.issueTx"
+/// in the encoding, and returns the transaction Id.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the API returns an error.
+pub async fn issue_raw_tx(
+    http_rpc: &str,
+    tx_bytes: &[u8],
+    encoding: formatting::Encoding,
+) -> Result<ids::Id> {
+    let resp = issue_encoded_tx(http_rpc, encoding.encode(tx_bytes), encoding).await?;
+    issued_tx_id(resp.result.map(|r| r.tx_id))
+}
+
+async fn issue_encoded_tx(
+    http_rpc: &str,
+    tx: String,
+    encoding: formatting::Encoding,
+) -> Result<avm::IssueTxResponse> {
     let (scheme, host, port, _, _) =
         utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
@@ -28,8 +57,8 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<avm::IssueTxResponse>
 
     let method = String::from("avm.issueTx");
     let params = avm::IssueTxParams {
-        tx: prefix_manager::prepend_0x(tx),
-        encoding: String::from("hex"), // don't use "cb58"
+        tx,
+        encoding: encoding.as_str().to_string(),
     }
     .into();
 
@@ -44,26 +73,11 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<avm::IssueTxResponse>
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.issueTx", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -78,10 +92,7 @@ pub async fn issue_tx(http_rpc: &str, tx: &str) -> Result<avm::IssueTxResponse>
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "avm.getTxStatus" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
@@ -113,26 +124,11 @@ pub async fn get_tx_status(http_rpc: &str, tx_id: &str) -> Result<avm::GetTxStat
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.getTxStatus", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -147,10 +143,7 @@ pub async fn get_tx_status(http_rpc: &str, tx_id: &str) -> Result<avm::GetTxStat
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "avm.getBalance" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
@@ -186,26 +179,11 @@ pub async fn get_balance(http_rpc: &str, xaddr: &str) -> Result<avm::GetBalanceR
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.getBalance", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -220,10 +198,7 @@ pub async fn get_balance(http_rpc: &str, xaddr: &str) -> Result<avm::GetBalanceR
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "avm.getAssetDescription" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
@@ -259,26 +234,11 @@ pub async fn get_asset_description(
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.getAssetDescription", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -293,20 +253,181 @@ pub async fn get_asset_description(
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "avm.getUTXOs" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
-/// TODO: support paginated calls
+/// Fetches all UTXOs of the address, following the end-index cursors
+/// with the maximum page size.
 /// ref. <https://docs.avax.network/apis/avalanchego/apis/x-chain/#avmgetutxos>
 ///
 /// # Errors
 ///
 /// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
 pub async fn get_utxos(http_rpc: &str, xaddr: &str) -> Result<avm::GetUtxosResponse> {
+    get_utxos_with_page_size(http_rpc, xaddr, MAX_UTXOS_PAGE_SIZE).await
+}
+
+/// Fetches all UTXOs of the address in pages of "`page_size`" UTXOs
+/// (at most "`MAX_UTXOS_PAGE_SIZE`"), and aggregates them into one response.
+/// Returns the error response as is if the first page fails.
+///
+/// # Errors
+///
+/// Returns an error if a request fails, or if the API returns an error after the first page.
+pub async fn get_utxos_with_page_size(
+    http_rpc: &str,
+    xaddr: &str,
+    page_size: u32,
+) -> Result<avm::GetUtxosResponse> {
+    get_utxos_from(http_rpc, xaddr, page_size, None).await
+}
+
+/// Fetches all UTXOs of the address (or the atomic UTXOs exported from
+/// "`source_chain`") page by page, and aggregates them into one response.
+async fn get_utxos_from(
+    http_rpc: &str,
+    xaddr: &str,
+    page_size: u32,
+    source_chain: Option<String>,
+) -> Result<avm::GetUtxosResponse> {
+    let page_size = page_size.clamp(1, MAX_UTXOS_PAGE_SIZE);
+
+    let mut aggregated = avm::GetUtxosResult {
+        encoding: Some(String::from("hex")),
+        ..Default::default()
+    };
+    let mut start_index = None;
+    let mut pages = 0_u32;
+    loop {
+        let resp = get_utxos_page_from(
+            http_rpc,
+            xaddr,
+            page_size,
+            start_index.clone(),
+            source_chain.clone(),
+        )
+        .await?;
+        let Some(result) = resp.result else {
+            if pages == 0 {
+                return Ok(resp);
+            }
+            return Err(Error::API {
+                message: format!("failed avm.getUTXOs page {pages} (no result)"),
+                retryable: true,
+            });
+        };
+        pages += 1;
+
+        aggregated.num_fetched += result.num_fetched;
+        aggregated
+            .utxos
+            .get_or_insert_with(Vec::new)
+            .extend(result.utxos.unwrap_or_default());
+
+        let done = result.num_fetched < page_size
+            || result.end_index.is_none()
+            || result.end_index == start_index;
+        aggregated.end_index = result.end_index.clone();
+        if done {
+            log::info!(
+                "fetched {} UTXOs in {pages} page(s)",
+                aggregated.num_fetched
+            );
+            return Ok(avm::GetUtxosResponse {
+                jsonrpc: resp.jsonrpc,
+                id: resp.id,
+                result: Some(aggregated),
+                error: None,
+            });
+        }
+        start_index = result.end_index;
+    }
+}
+
+/// Streams the UTXOs of the address page by page, following the end-index cursors.
+/// Each item is one page of at most "`page_size`" UTXOs.
+pub fn stream_utxos(
+    http_rpc: &str,
+    xaddr: &str,
+    page_size: u32,
+) -> impl Stream<Item = Result<Vec<txs::utxo::Utxo>>> {
+    let page_size = page_size.clamp(1, MAX_UTXOS_PAGE_SIZE);
+    let http_rpc = http_rpc.to_string();
+    let xaddr = xaddr.to_string();
+
+    // "None" once the last page is fetched
+    let cursor: Option<Option<jsonrpc::EndIndex>> = Some(None);
+    stream::try_unfold(cursor, move |cursor| {
+        let http_rpc = http_rpc.clone();
+        let xaddr = xaddr.clone();
+        async move {
+            let Some(start_index) = cursor else {
+                return Ok(None);
+            };
+            let resp = get_utxos_page(&http_rpc, &xaddr, page_size, start_index.clone()).await?;
+            let Some(result) = resp.result else {
+                return Err(Error::API {
+                    message: "failed avm.getUTXOs (no result)".to_string(),
+                    retryable: true,
+                });
+            };
+
+            let done = result.num_fetched < page_size
+                || result.end_index.is_none()
+                || result.end_index == start_index;
+            let next = if done { None } else { Some(result.end_index) };
+            Ok(Some((result.utxos.unwrap_or_default(), next)))
+        }
+    })
+}
+
+/// Fetches one page of UTXOs, starting after "`start_index`"
+/// (the "endIndex" of the previous page, or "None" for the first page).
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/x-chain/#avmgetutxos>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_utxos_page(
+    http_rpc: &str,
+    xaddr: &str,
+    limit: u32,
+    start_index: Option<jsonrpc::EndIndex>,
+) -> Result<avm::GetUtxosResponse> {
+    get_utxos_page_from(http_rpc, xaddr, limit, start_index, None).await
+}
+
+/// e.g., "avm.getUTXOs" with "sourceChain" on "http://\[ADDR\]:9650".
+/// Fetches the atomic UTXOs of the address that were exported from
+/// "`source_chain`" (chain Id or alias) and are not imported yet,
+/// following the end-index cursors like "`get_utxos`".
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/x-chain/#avmgetutxos>
+///
+/// # Errors
+///
+/// Returns an error if the request fails, if the response cannot be parsed, or if the API returns an error.
+pub async fn get_atomic_utxos(
+    http_rpc: &str,
+    xaddr: &str,
+    source_chain: &str,
+) -> Result<avm::GetUtxosResponse> {
+    get_utxos_from(
+        http_rpc,
+        xaddr,
+        MAX_UTXOS_PAGE_SIZE,
+        Some(source_chain.to_string()),
+    )
+    .await
+}
+
+async fn get_utxos_page_from(
+    http_rpc: &str,
+    xaddr: &str,
+    limit: u32,
+    start_index: Option<jsonrpc::EndIndex>,
+    source_chain: Option<String>,
+) -> Result<avm::GetUtxosResponse> {
     let (scheme, host, port, _, _) =
         utils::urls::extract_scheme_host_port_path_chain_alias(http_rpc).map_err(|e| {
             Error::Other {
@@ -321,8 +442,10 @@ pub async fn get_utxos(http_rpc: &str, xaddr: &str) -> Result<avm::GetUtxosRespo
         method: String::from("avm.getUTXOs"),
         params: avm::GetUtxosParams {
             addresses: vec![xaddr.to_string()],
-            limit: 1024,
+            limit,
+            start_index,
             encoding: String::from("hex"), // don't use "cb58"
+            source_chain,
         }
         .into(),
         ..Default::default()
@@ -333,26 +456,11 @@ pub async fn get_utxos(http_rpc: &str, xaddr: &str) -> Result<avm::GetUtxosRespo
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.getUTXOs", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
@@ -367,10 +475,7 @@ pub async fn get_utxos(http_rpc: &str, xaddr: &str) -> Result<avm::GetUtxosRespo
     })?;
     let out: Vec<u8> = out.into();
 
-    serde_json::from_slice(&out).map_err(|e| Error::Other {
-        message: format!("failed serde_json::from_slice '{e}'"),
-        retryable: false,
-    })
+    super::decode_response(&out)
 }
 
 /// e.g., "avm.issueStopVertex" on "http://\[ADDR\]:9650" and "/ext/bc/X" path.
@@ -401,26 +506,11 @@ pub async fn issue_stop_vertex(http_rpc: &str) -> Result<()> {
         retryable: false,
     })?;
 
-    let req_cli_builder = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(15))
-        .connection_verbose(true)
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
-    let resp = req_cli_builder
-        .post(url.to_string())
-        .header(CONTENT_TYPE, "application/json")
-        .body(d)
-        .send()
-        .await
-        .map_err(|e|
+    let req_cli_builder = http::current()?;
+    let timer = metrics::Timer::start("avm.issueStopVertex", &url);
+    let resp = req_cli_builder.post_json(&url.to_string(), d)?.send().await;
+    timer.observe(&resp);
+    let resp = resp.map_err(|e|
             // TODO: check retryable
             Error::API {
                 message: format!("failed reqwest::Client.send '{e}'"),
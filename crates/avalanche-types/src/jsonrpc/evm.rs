@@ -1,5 +1,10 @@
 //! EVM JSON-RPC requests and responses.
-use crate::codec::serde::{hex_0x_bytes::Hex0xBytes, hex_0x_primitive_types_h256::Hex0xH256};
+use crate::codec::serde::{
+    hex_0x_bytes::Hex0xBytes, hex_0x_primitive_types_h160::Hex0xH160,
+    hex_0x_primitive_types_h256::Hex0xH256, hex_0x_primitive_types_u256::Hex0xU256,
+    hex_0x_u64::Hex0xU64,
+};
+use primitive_types::{H160, H256, U256};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -342,3 +347,378 @@ fn test_send_raw_transaction() {
     };
     assert_eq!(resp, expected);
 }
+
+/// Block returned by "`eth_getBlockByNumber`" and "`eth_getBlockByHash`",
+/// with the coreth fields for the atomic transactions and dynamic fees.
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_getblockbyhash>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/coreth/internal/ethapi#RPCMarshalHeader>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    #[serde_as(as = "Hex0xU64")]
+    pub number: u64,
+    #[serde_as(as = "Hex0xH256")]
+    pub hash: H256,
+    #[serde_as(as = "Hex0xH256")]
+    pub parent_hash: H256,
+    #[serde_as(as = "Hex0xH256")]
+    pub state_root: H256,
+    #[serde_as(as = "Hex0xH256")]
+    pub transactions_root: H256,
+    #[serde_as(as = "Hex0xH256")]
+    pub receipts_root: H256,
+    #[serde_as(as = "Hex0xH160")]
+    pub miner: H160,
+    #[serde_as(as = "Hex0xU64")]
+    pub timestamp: u64,
+    #[serde_as(as = "Hex0xU64")]
+    pub gas_limit: u64,
+    #[serde_as(as = "Hex0xU64")]
+    pub gas_used: u64,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde_as(as = "Hex0xBytes")]
+    pub extra_data: Vec<u8>,
+    #[serde_as(as = "Hex0xU64")]
+    pub size: u64,
+
+    /// Atomic transactions (imports/exports) in the block (coreth only).
+    #[serde_as(as = "Option<Hex0xBytes>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_extra_data: Option<Vec<u8>>,
+    /// Hash of the atomic transactions (coreth only).
+    #[serde_as(as = "Option<Hex0xH256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_data_hash: Option<H256>,
+    /// Gas used by the atomic transactions (coreth only).
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_data_gas_used: Option<U256>,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_gas_cost: Option<U256>,
+
+    #[serde(default)]
+    pub transactions: BlockTransactions,
+}
+
+/// Transactions in the block, either hashes or full objects
+/// depending on the "full transactions" request flag.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+    Hashes(#[serde_as(as = "Vec<Hex0xH256>")] Vec<H256>),
+    Full(Vec<Transaction>),
+}
+
+impl Default for BlockTransactions {
+    fn default() -> Self {
+        Self::Hashes(Vec::new())
+    }
+}
+
+impl BlockTransactions {
+    /// Returns the transaction hashes.
+    #[must_use]
+    pub fn hashes(&self) -> Vec<H256> {
+        match self {
+            Self::Hashes(hashes) => hashes.clone(),
+            Self::Full(txs) => txs.iter().map(|tx| tx.hash).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Hashes(hashes) => hashes.len(),
+            Self::Full(txs) => txs.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Transaction returned by "`eth_getTransactionByHash`" or in the full block.
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_gettransactionbyhash>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    #[serde_as(as = "Hex0xH256")]
+    pub hash: H256,
+    #[serde_as(as = "Hex0xU64")]
+    pub nonce: u64,
+    /// "None" if pending.
+    #[serde_as(as = "Option<Hex0xH256>")]
+    #[serde(default)]
+    pub block_hash: Option<H256>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+    #[serde_as(as = "Hex0xH160")]
+    pub from: H160,
+    /// "None" for the contract creation.
+    #[serde_as(as = "Option<Hex0xH160>")]
+    #[serde(default)]
+    pub to: Option<H160>,
+    #[serde_as(as = "Hex0xU256")]
+    pub value: U256,
+    #[serde_as(as = "Hex0xU64")]
+    pub gas: u64,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde_as(as = "Hex0xBytes")]
+    pub input: Vec<u8>,
+    /// Transaction type (0 for legacy, 2 for EIP-1559).
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub tx_type: Option<u64>,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U256>,
+}
+
+/// Receipt returned by "`eth_getTransactionReceipt`".
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_gettransactionreceipt>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    #[serde_as(as = "Hex0xH256")]
+    pub transaction_hash: H256,
+    #[serde_as(as = "Hex0xU64")]
+    pub transaction_index: u64,
+    #[serde_as(as = "Hex0xH256")]
+    pub block_hash: H256,
+    #[serde_as(as = "Hex0xU64")]
+    pub block_number: u64,
+    #[serde_as(as = "Hex0xH160")]
+    pub from: H160,
+    #[serde_as(as = "Option<Hex0xH160>")]
+    #[serde(default)]
+    pub to: Option<H160>,
+    #[serde_as(as = "Hex0xU64")]
+    pub cumulative_gas_used: u64,
+    #[serde_as(as = "Hex0xU64")]
+    pub gas_used: u64,
+    #[serde_as(as = "Option<Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_gas_price: Option<U256>,
+    /// Address of the created contract, if any.
+    #[serde_as(as = "Option<Hex0xH160>")]
+    #[serde(default)]
+    pub contract_address: Option<H160>,
+    pub logs: Vec<Log>,
+    #[serde_as(as = "Hex0xBytes")]
+    pub logs_bloom: Vec<u8>,
+    /// 1 for success, 0 for failure (post-Byzantium).
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u64>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub tx_type: Option<u64>,
+}
+
+impl Receipt {
+    /// Returns "true" if the transaction execution succeeded.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.status == Some(1)
+    }
+
+    /// Returns the fee paid (gas used times the effective gas price), if known.
+    #[must_use]
+    pub fn fee(&self) -> Option<U256> {
+        self.effective_gas_price
+            .map(|price| price.saturating_mul(U256::from(self.gas_used)))
+    }
+}
+
+/// Log emitted by the transaction.
+/// ref. <https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_getfilterchanges>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    #[serde_as(as = "Hex0xH160")]
+    pub address: H160,
+    #[serde_as(as = "Vec<Hex0xH256>")]
+    pub topics: Vec<H256>,
+    #[serde_as(as = "Hex0xBytes")]
+    pub data: Vec<u8>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    #[serde_as(as = "Option<Hex0xH256>")]
+    #[serde(default)]
+    pub block_hash: Option<H256>,
+    #[serde_as(as = "Option<Hex0xH256>")]
+    #[serde(default)]
+    pub transaction_hash: Option<H256>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+    #[serde_as(as = "Option<Hex0xU64>")]
+    #[serde(default)]
+    pub log_index: Option<u64>,
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::evm::test_block` --exact --show-output
+#[test]
+fn test_block() {
+    use std::str::FromStr;
+
+    let block: Block = serde_json::from_str(
+        r#"
+{
+    "baseFeePerGas": "0x5d21dba00",
+    "blockExtraData": "0x",
+    "blockGasCost": "0x0",
+    "difficulty": "0x1",
+    "extDataGasUsed": "0x0",
+    "extDataHash": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+    "extraData": "0x0000",
+    "gasLimit": "0x7a1200",
+    "gasUsed": "0x5208",
+    "hash": "0x0b1e6d5d5e7d2b0a2b0f8f1e4f0b7c1d9e7b5a3c1f0e9d8c7b6a594837261504",
+    "logsBloom": "0x00",
+    "miner": "0x0100000000000000000000000000000000000000",
+    "number": "0x2a",
+    "parentHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+    "receiptsRoot": "0x2222222222222222222222222222222222222222222222222222222222222222",
+    "size": "0x2c2",
+    "stateRoot": "0x3333333333333333333333333333333333333333333333333333333333333333",
+    "timestamp": "0x6385b3a0",
+    "transactions": [
+        {
+            "blockHash": "0x0b1e6d5d5e7d2b0a2b0f8f1e4f0b7c1d9e7b5a3c1f0e9d8c7b6a594837261504",
+            "blockNumber": "0x2a",
+            "from": "0x8db97c7cece249c2b98bdc0226cc4c2a57bf52fc",
+            "gas": "0x5208",
+            "gasPrice": "0x5d21dba00",
+            "maxFeePerGas": "0x5d21dba00",
+            "maxPriorityFeePerGas": "0x0",
+            "hash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+            "input": "0x",
+            "nonce": "0x1",
+            "to": "0xef14c4ee608e5c79bce97e3113401a360df809fb",
+            "transactionIndex": "0x0",
+            "value": "0xde0b6b3a7640000",
+            "type": "0x2",
+            "chainId": "0xa868",
+            "v": "0x0",
+            "r": "0x1",
+            "s": "0x2"
+        }
+    ],
+    "transactionsRoot": "0x5555555555555555555555555555555555555555555555555555555555555555",
+    "uncles": []
+}
+"#,
+    )
+    .unwrap();
+    assert_eq!(block.number, 42);
+    assert_eq!(block.gas_used, 21000);
+    assert_eq!(block.base_fee_per_gas, Some(U256::from(25_000_000_000_u64)));
+    assert_eq!(block.block_extra_data, Some(Vec::new()));
+    assert!(block.ext_data_hash.is_some());
+
+    let BlockTransactions::Full(txs) = &block.transactions else {
+        panic!("unexpected tx hashes");
+    };
+    assert_eq!(txs[0].nonce, 1);
+    assert_eq!(txs[0].tx_type, Some(2));
+    assert_eq!(
+        txs[0].to,
+        Some(H160::from_str("0xef14c4ee608e5c79bce97e3113401a360df809fb").unwrap())
+    );
+    assert_eq!(txs[0].value, U256::from(1_000_000_000_000_000_000_u64));
+    assert_eq!(block.transactions.hashes(), vec![txs[0].hash]);
+
+    // round-trip
+    let d = serde_json::to_string(&block).unwrap();
+    assert_eq!(serde_json::from_str::<Block>(&d).unwrap(), block);
+
+    // subnet-evm block with the tx hashes only
+    let block: Block = serde_json::from_str(
+        r#"
+{
+    "extraData": "0x",
+    "gasLimit": "0x7a1200",
+    "gasUsed": "0x0",
+    "hash": "0x0b1e6d5d5e7d2b0a2b0f8f1e4f0b7c1d9e7b5a3c1f0e9d8c7b6a594837261504",
+    "miner": "0x0100000000000000000000000000000000000000",
+    "number": "0x0",
+    "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "receiptsRoot": "0x2222222222222222222222222222222222222222222222222222222222222222",
+    "size": "0x1",
+    "stateRoot": "0x3333333333333333333333333333333333333333333333333333333333333333",
+    "timestamp": "0x0",
+    "transactions": ["0x4444444444444444444444444444444444444444444444444444444444444444"],
+    "transactionsRoot": "0x5555555555555555555555555555555555555555555555555555555555555555"
+}
+"#,
+    )
+    .unwrap();
+    assert!(block.ext_data_hash.is_none());
+    assert_eq!(block.transactions.len(), 1);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::evm::test_receipt` --exact --show-output
+#[test]
+fn test_receipt() {
+    let receipt: Receipt = serde_json::from_str(
+        r#"
+{
+    "blockHash": "0x0b1e6d5d5e7d2b0a2b0f8f1e4f0b7c1d9e7b5a3c1f0e9d8c7b6a594837261504",
+    "blockNumber": "0x2a",
+    "contractAddress": null,
+    "cumulativeGasUsed": "0x5208",
+    "effectiveGasPrice": "0x5d21dba00",
+    "from": "0x8db97c7cece249c2b98bdc0226cc4c2a57bf52fc",
+    "gasUsed": "0x5208",
+    "logs": [
+        {
+            "address": "0xef14c4ee608e5c79bce97e3113401a360df809fb",
+            "topics": ["0x4444444444444444444444444444444444444444444444444444444444444444"],
+            "data": "0x01",
+            "blockNumber": "0x2a",
+            "logIndex": "0x0",
+            "removed": false
+        }
+    ],
+    "logsBloom": "0x00",
+    "status": "0x1",
+    "to": "0xef14c4ee608e5c79bce97e3113401a360df809fb",
+    "transactionHash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+    "transactionIndex": "0x0",
+    "type": "0x2"
+}
+"#,
+    )
+    .unwrap();
+    assert!(receipt.succeeded());
+    assert_eq!(receipt.block_number, 42);
+    assert!(receipt.contract_address.is_none());
+    assert_eq!(receipt.logs[0].data, vec![1]);
+    assert_eq!(receipt.fee(), Some(U256::from(21000_u64 * 25_000_000_000)));
+}
@@ -0,0 +1,241 @@
+//! Avalanche JSON-RPC API index request and responses.
+use std::io::{self, Error, ErrorKind};
+
+use crate::{formatting, ids, jsonrpc};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// Represents the indexes served by the node
+/// (requires "--index-enabled").
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum Index {
+    /// Accepted X-chain transactions.
+    XTx,
+    /// Accepted X-chain vertices.
+    XVtx,
+    /// Accepted X-chain blocks.
+    XBlock,
+    /// Accepted P-chain blocks.
+    PBlock,
+    /// Accepted C-chain blocks.
+    CBlock,
+}
+
+impl Index {
+    /// Returns the URL path of the index.
+    #[must_use]
+    pub const fn path(&self) -> &'static str {
+        match self {
+            Self::XTx => "/ext/index/X/tx",
+            Self::XVtx => "/ext/index/X/vtx",
+            Self::XBlock => "/ext/index/X/block",
+            Self::PBlock => "/ext/index/P/block",
+            Self::CBlock => "/ext/index/C/block",
+        }
+    }
+}
+
+/// Represents an accepted container (e.g., transaction, vertex, or block).
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/indexer#FormattedContainer>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Container {
+    pub id: ids::Id,
+    /// Encoded bytes of the container (e.g., "0x" hex with checksum).
+    pub bytes: String,
+    /// Time when the node accepted the container.
+    #[serde_as(as = "crate::codec::serde::rfc_3339::DateTimeUtc")]
+    pub timestamp: DateTime<Utc>,
+    pub encoding: String,
+    /// Position of the container in the index, starting at 0.
+    #[serde_as(as = "DisplayFromStr")]
+    pub index: u64,
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self {
+            id: ids::Id::empty(),
+            bytes: String::new(),
+            timestamp: DateTime::<Utc>::MIN_UTC,
+            encoding: String::from("hex"),
+            index: 0,
+        }
+    }
+}
+
+impl Container {
+    /// Decodes the container bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding is not "hex" or the checksum does not match.
+    pub fn decode_bytes(&self) -> io::Result<Vec<u8>> {
+        if self.encoding != "hex" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unsupported encoding '{}'", self.encoding),
+            ));
+        }
+        let b = self.bytes.trim_start_matches("0x");
+        if b.len() < 8 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("container bytes '{}' too short for checksum", self.bytes),
+            ));
+        }
+        formatting::decode_hex_with_checksum(b.as_bytes())
+    }
+}
+
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerrange>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetContainerRangeResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetContainerRangeResult>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerrange>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct GetContainerRangeResult {
+    pub containers: Vec<Container>,
+}
+
+/// Response of "index.getLastAccepted" and "index.getContainerByID".
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetlastaccepted>
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerbyid>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetContainerResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Container>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::index::test_get_container_range` --exact --show-output
+#[test]
+fn test_get_container_range() {
+    use std::str::FromStr;
+
+    use chrono::TimeZone;
+
+    // ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetcontainerrange>
+    let resp: GetContainerRangeResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"containers\": [
+            {
+                \"id\": \"TtF4d2QWbk5vzQGTEPrN48x6vwgAoAmKQ9cbp79inpQmcRKES\",
+                \"bytes\": \"0x68656c6c6f938b9824\",
+                \"timestamp\": \"2021-04-02T15:34:00.262979-07:00\",
+                \"encoding\": \"hex\",
+                \"index\": \"0\"
+            }
+        ]
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+
+    let expected = GetContainerRangeResponse {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        result: Some(GetContainerRangeResult {
+            containers: vec![Container {
+                id: ids::Id::from_str("TtF4d2QWbk5vzQGTEPrN48x6vwgAoAmKQ9cbp79inpQmcRKES").unwrap(),
+                bytes: String::from("0x68656c6c6f938b9824"),
+                timestamp: Utc.from_utc_datetime(
+                    &DateTime::parse_from_rfc3339("2021-04-02T22:34:00.262979Z")
+                        .unwrap()
+                        .naive_utc(),
+                ),
+                encoding: String::from("hex"),
+                index: 0,
+            }],
+        }),
+        error: None,
+    };
+    assert_eq!(resp, expected);
+
+    let container = &resp.result.unwrap().containers[0];
+    assert_eq!(container.decode_bytes().unwrap(), b"hello".to_vec());
+
+    let mut corrupted = container.clone();
+    corrupted.bytes = String::from("0x68656c6c6f938b9825");
+    assert!(corrupted.decode_bytes().is_err());
+    corrupted.bytes = String::from("0x00");
+    assert!(corrupted.decode_bytes().is_err());
+    corrupted.encoding = String::from("cb58");
+    assert!(corrupted.decode_bytes().is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::index::test_get_container` --exact --show-output
+#[test]
+fn test_get_container() {
+    // ref. <https://docs.avax.network/apis/avalanchego/apis/index-api#indexgetlastaccepted>
+    let resp: GetContainerResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"id\": \"TtF4d2QWbk5vzQGTEPrN48x6vwgAoAmKQ9cbp79inpQmcRKES\",
+        \"bytes\": \"0x68656c6c6f938b9824\",
+        \"timestamp\": \"2021-04-02T15:34:00.262979-07:00\",
+        \"encoding\": \"hex\",
+        \"index\": \"7\"
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+    let container = resp.result.unwrap();
+    assert_eq!(container.index, 7);
+    assert_eq!(container.decode_bytes().unwrap(), b"hello".to_vec());
+
+    let resp: GetContainerResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"error\": {
+        \"code\": -32000,
+        \"message\": \"no containers have been accepted\",
+        \"data\": null
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+    assert!(resp.result.is_none());
+    assert_eq!(
+        resp.error.unwrap().message,
+        "no containers have been accepted"
+    );
+
+    assert_eq!(Index::XTx.path(), "/ext/index/X/tx");
+    assert_eq!(Index::CBlock.path(), "/ext/index/C/block");
+}
@@ -168,6 +168,51 @@ pub struct GetNodeIdResult {
     pub node_pop: Option<bls::ProofOfPossession>,
 }
 
+impl GetNodeIdResult {
+    /// Derives the node ID and proof of possession from the staking certificate
+    /// and the BLS signer key files, the same as "info.getNodeID" would return
+    /// for the node running with them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificate or the signer key fails to load.
+    pub fn from_staking_files(cert_path: &str, signer_key_path: &str) -> io::Result<Self> {
+        let node_id = node::Id::from_cert_pem_file(cert_path)?;
+        let node_pop = bls::ProofOfPossession::from_signer_key_file(signer_key_path)?;
+        Ok(Self {
+            node_id,
+            node_pop: Some(node_pop),
+        })
+    }
+
+    /// Encodes the node ID and the "signer" in JSON, as used for
+    /// "AddPermissionlessValidatorTx" to the primary network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof of possession is missing or invalid.
+    pub fn to_validator_json(&self) -> io::Result<String> {
+        let Some(node_pop) = &self.node_pop else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("no proof of possession for {}", self.node_id),
+            ));
+        };
+        if !node_pop.verify()? {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid proof of possession for {}", self.node_id),
+            ));
+        }
+
+        serde_json::to_string_pretty(&serde_json::json!({
+            "nodeID": self.node_id,
+            "signer": node_pop,
+        }))
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed serde_json {e}")))
+    }
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::info::test_get_node_id` --exact --show-output
 #[test]
 fn test_get_node_id() {
@@ -233,6 +278,44 @@ fn test_get_node_id() {
     assert_eq!(resp, expected);
 }
 
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::info::test_get_node_id_from_staking_files` --exact --show-output
+#[test]
+fn test_get_node_id_from_staking_files() {
+    let _ = env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .is_test(true)
+        .try_init();
+
+    let key_path = random_manager::tmp_path(10, None).unwrap();
+    let cert_path = random_manager::tmp_path(10, None).unwrap();
+    let signer_key_path = random_manager::tmp_path(10, None).unwrap();
+    let (node_id, _) = node::Id::load_or_generate_pem(&key_path, &cert_path).unwrap();
+    let sk = bls::private_key::Key::generate_to_file(&signer_key_path).unwrap();
+
+    let res = GetNodeIdResult::from_staking_files(&cert_path, &signer_key_path).unwrap();
+    assert_eq!(res.node_id, node_id);
+    assert_eq!(res.node_pop, Some(sk.to_proof_of_possession()));
+
+    let validator_json = res.to_validator_json().unwrap();
+    log::info!("validator: {validator_json}");
+    let v: serde_json::Value = serde_json::from_str(&validator_json).unwrap();
+    assert_eq!(v["nodeID"], node_id.to_string());
+    let signer: bls::ProofOfPossession = serde_json::from_value(v["signer"].clone()).unwrap();
+    assert!(signer.verify().unwrap());
+    assert_eq!(signer.public_key, sk.to_public_key().to_compressed_bytes());
+
+    assert!(GetNodeIdResult {
+        node_id,
+        node_pop: None,
+    }
+    .to_validator_json()
+    .is_err());
+
+    for p in [key_path, cert_path, signer_key_path] {
+        std::fs::remove_file(p).unwrap();
+    }
+}
+
 /// ref. <https://docs.avax.network/build/avalanchego-apis/info/#infogetnodeip>
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct GetNodeIpResponse {
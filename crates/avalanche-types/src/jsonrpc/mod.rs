@@ -4,6 +4,7 @@ pub mod avm;
 pub mod common;
 pub mod evm;
 pub mod health;
+pub mod index;
 pub mod info;
 pub mod platformvm;
 
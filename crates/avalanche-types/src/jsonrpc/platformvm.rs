@@ -386,7 +386,14 @@ impl GetUtxosRequest {
 pub struct GetUtxosParams {
     pub addresses: Vec<String>,
     pub limit: u32,
+    /// "endIndex" of the previous page to continue from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<super::EndIndex>,
     pub encoding: String,
+    /// Chain Id or alias the UTXOs were exported from, to fetch the atomic
+    /// UTXOs in the shared memory instead of the chain's own UTXOs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_chain: Option<String>,
 }
 
 /// ref. <https://docs.avax.network/apis/avalanchego/apis/p-chain#platformgetutxos>
@@ -1355,10 +1362,73 @@ impl Default for GetBlockchainStatusResponse {
     }
 }
 
-/// ref. <https://docs.avax.network/apis/avalanchego/apis/p-chain#platformissuetx>
+/// ref. <https://docs.avax.network/apis/avalanchego/apis/p-chain#platformgetblockchainstatus>
+#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
 pub struct GetBlockchainStatusResult {
-    pub status: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub status: BlockchainStatus,
+}
+
+/// Defines the blockchain status as seen by the node.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/status#BlockchainStatus>
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub enum BlockchainStatus {
+    /// The blockchain is being validated by the node.
+    Validating,
+    /// The blockchain exists, but is not validated by the node.
+    Created,
+    /// The blockchain creation is in the preferred (not yet accepted) block.
+    Preferred,
+    /// The node is syncing the blockchain.
+    Syncing,
+    /// The blockchain is unknown to the node.
+    Unknown(String),
+}
+
+impl Default for BlockchainStatus {
+    fn default() -> Self {
+        Self::Unknown(String::from("Unknown"))
+    }
+}
+
+impl std::convert::From<&str> for BlockchainStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "Validating" => Self::Validating,
+            "Created" => Self::Created,
+            "Preferred" => Self::Preferred,
+            "Syncing" => Self::Syncing,
+            other => Self::Unknown(other.to_owned()),
+        }
+    }
+}
+
+impl std::str::FromStr for BlockchainStatus {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl std::fmt::Display for BlockchainStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl BlockchainStatus {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Validating => "Validating",
+            Self::Created => "Created",
+            Self::Preferred => "Preferred",
+            Self::Syncing => "Syncing",
+            Self::Unknown(s) => s.as_ref(),
+        }
+    }
 }
 
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::platformvm::test_get_blockchain_status` --exact --show-output
@@ -1383,9 +1453,338 @@ fn test_get_blockchain_status() {
         jsonrpc: "2.0".to_string(),
         id: 1,
         result: Some(GetBlockchainStatusResult {
-            status: String::from("Created"),
+            status: BlockchainStatus::Created,
         }),
         error: None,
     };
     assert_eq!(resp, expected);
+
+    let resp: GetBlockchainStatusResponse = serde_json::from_str(
+        "
+
+    {
+        \"jsonrpc\": \"2.0\",
+        \"result\": {
+            \"status\": \"Unknown\"
+        },
+        \"id\": 1
+    }
+
+    ",
+    )
+    .unwrap();
+    assert_eq!(
+        resp.result.unwrap().status,
+        BlockchainStatus::Unknown(String::from("Unknown"))
+    );
+    assert_eq!(
+        BlockchainStatus::from("Validating").to_string(),
+        "Validating"
+    );
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeeconfig>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetFeeConfigResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<platformvm::fees::Config>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeestate>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetFeeStateResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetFeeStateResult>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeestate>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetFeeStateResult {
+    /// Amount of gas available to the chain.
+    pub capacity: u64,
+    /// Gas consumed above the target, which drives the gas price.
+    pub excess: u64,
+    /// Current price per unit of gas.
+    pub price: u64,
+    #[serde_as(as = "crate::codec::serde::rfc_3339::DateTimeUtc")]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl Default for GetFeeStateResult {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            excess: 0,
+            price: 0,
+            timestamp: chrono::DateTime::<chrono::Utc>::MIN_UTC,
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::platformvm::test_get_fee_config` --exact --show-output
+#[test]
+fn test_get_fee_config() {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    // ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeeconfig>
+    let resp: GetFeeConfigResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"weights\": [1, 1000, 1000, 4],
+        \"maxCapacity\": 1000000,
+        \"maxPerSecond\": 100000,
+        \"targetPerSecond\": 50000,
+        \"minPrice\": 1,
+        \"excessConversionConstant\": 2164043
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+    assert_eq!(resp.result.unwrap(), platformvm::fees::Config::default());
+
+    // ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetfeestate>
+    let resp: GetFeeStateResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"capacity\": 973044,
+        \"excess\": 26956,
+        \"price\": 1,
+        \"timestamp\": \"2024-12-16T17:19:07Z\"
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+
+    let expected = GetFeeStateResponse {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        result: Some(GetFeeStateResult {
+            capacity: 973_044,
+            excess: 26_956,
+            price: 1,
+            timestamp: Utc.from_utc_datetime(
+                &DateTime::parse_from_rfc3339("2024-12-16T17:19:07Z")
+                    .unwrap()
+                    .naive_utc(),
+            ),
+        }),
+        error: None,
+    };
+    assert_eq!(resp, expected);
+
+    // nodes without dynamic fees do not serve the method
+    let resp: GetFeeConfigResponse = serde_json::from_str(
+        "{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32601,\"message\":\"the method platform.getFeeConfig does not exist/is not available\",\"data\":null},\"id\":1}",
+    )
+    .unwrap();
+    assert!(resp.result.is_none());
+    assert_eq!(resp.error.unwrap().code, -32601);
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetcurrentsupply>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetCurrentSupplyResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetCurrentSupplyResult>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetcurrentsupply>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct GetCurrentSupplyResult {
+    /// Upper bound of the AVAX supply in nano-AVAX, including the staking
+    /// rewards that may not be minted.
+    #[serde_as(as = "DisplayFromStr")]
+    pub supply: u64,
+    /// Height of the P-chain that the supply is as of (only in newer versions).
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::platformvm::test_get_current_supply` --exact --show-output
+#[test]
+fn test_get_current_supply() {
+    // ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetcurrentsupply>
+    let resp: GetCurrentSupplyResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"supply\": \"365865167637779183\",
+        \"height\": \"1234\"
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+
+    let expected = GetCurrentSupplyResponse {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        result: Some(GetCurrentSupplyResult {
+            supply: 365_865_167_637_779_183,
+            height: Some(1234),
+        }),
+        error: None,
+    };
+    assert_eq!(resp, expected);
+
+    let resp: GetCurrentSupplyResponse = serde_json::from_str(
+        "{\"jsonrpc\":\"2.0\",\"result\":{\"supply\":\"365865167637779183\"},\"id\":1}",
+    )
+    .unwrap();
+    assert!(resp.result.unwrap().height.is_none());
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetvalidatorsat>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GetValidatorsAtResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetValidatorsAtResult>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc::ResponseError>,
+}
+
+/// ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetvalidatorsat>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct GetValidatorsAtResult {
+    #[serde(default)]
+    pub validators: HashMap<node::Id, ValidatorAt>,
+}
+
+/// Represents the validator of the subnet at the P-chain height.
+/// Older nodes only return the weight, newer nodes also return the
+/// BLS public key (if registered).
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/validators#GetValidatorOutput>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(from = "ValidatorAtRepr")]
+pub struct ValidatorAt {
+    /// Hex-encoded compressed BLS public key with the "0x" prefix.
+    #[serde(rename = "publicKey", skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub weight: u64,
+}
+
+/// Wire formats of the validator weight across the node versions.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ValidatorAtRepr {
+    /// Only the weight, returned by the older nodes.
+    Weight(u64),
+    /// "GetValidatorOutput" with the optional BLS public key.
+    Output {
+        /// Hex-encoded compressed BLS public key with the "0x" prefix.
+        #[serde(rename = "publicKey", default)]
+        public_key: Option<String>,
+        /// Weight of the validator.
+        #[serde_as(as = "DisplayFromStr")]
+        weight: u64,
+    },
+}
+
+impl From<ValidatorAtRepr> for ValidatorAt {
+    fn from(v: ValidatorAtRepr) -> Self {
+        match v {
+            ValidatorAtRepr::Weight(weight) => Self {
+                public_key: None,
+                weight,
+            },
+            ValidatorAtRepr::Output { public_key, weight } => Self { public_key, weight },
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `jsonrpc::platformvm::test_get_validators_at` --exact --show-output
+#[test]
+fn test_get_validators_at() {
+    use std::str::FromStr;
+
+    // ref. <https://docs.avax.network/reference/avalanchego/p-chain/api#platformgetvalidatorsat>
+    let resp: GetValidatorsAtResponse = serde_json::from_str(
+        "
+
+{
+    \"jsonrpc\": \"2.0\",
+    \"result\": {
+        \"validators\": {
+            \"NodeID-7Xhw2mDxuDS44j42TCB6U5579esbSt3Lg\": {
+                \"publicKey\": \"0x8f95423f7142d00a48e1014a3de8d28907d420dc33b3052a6dee03a3f2941a393c2351e354704ca66a3fc29870282e15\",
+                \"weight\": \"2000000000000000\"
+            },
+            \"NodeID-GWPcbFJZFfZreETSoWjPimr846mXEKCtu\": {
+                \"weight\": \"2000000000000\"
+            }
+        }
+    },
+    \"id\": 1
+}
+
+",
+    )
+    .unwrap();
+    let validators = resp.result.unwrap().validators;
+    assert_eq!(validators.len(), 2);
+    let v = &validators[&node::Id::from_str("NodeID-7Xhw2mDxuDS44j42TCB6U5579esbSt3Lg").unwrap()];
+    assert_eq!(v.weight, 2_000_000_000_000_000);
+    assert!(v.public_key.as_ref().unwrap().starts_with("0x8f95"));
+    let v = &validators[&node::Id::from_str("NodeID-GWPcbFJZFfZreETSoWjPimr846mXEKCtu").unwrap()];
+    assert_eq!(v.weight, 2_000_000_000_000);
+    assert!(v.public_key.is_none());
+
+    // older nodes only return the weights
+    let resp: GetValidatorsAtResponse = serde_json::from_str(
+        "{\"jsonrpc\":\"2.0\",\"result\":{\"validators\":{\"NodeID-7Xhw2mDxuDS44j42TCB6U5579esbSt3Lg\":20}},\"id\":1}",
+    )
+    .unwrap();
+    let validators = resp.result.unwrap().validators;
+    assert_eq!(validators.values().next().unwrap().weight, 20);
+
+    // serializes in the newer format
+    let v = ValidatorAt {
+        public_key: None,
+        weight: 20,
+    };
+    assert_eq!(serde_json::to_string(&v).unwrap(), "{\"weight\":\"20\"}");
 }
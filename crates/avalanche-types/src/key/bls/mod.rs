@@ -64,6 +64,17 @@ impl ProofOfPossession {
         Ok(pubkey.verify_proof_of_possession(&pubkey_bytes, &sig))
     }
 
+    /// Derives the proof of possession from the "avalanchego" "signer.key" file,
+    /// as surfaced in the "info.getNodeID" "nodePOP" field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signer key file fails to load.
+    pub fn from_signer_key_file(key_path: &str) -> io::Result<Self> {
+        let sk = private_key::Key::from_file(key_path)?;
+        Ok(sk.to_proof_of_possession())
+    }
+
     /// Loads the public key from the proof of possession.
     /// ref. "avalanchego/vms/platformvm/signer.ProofOfPossession.UnmarshalJSON"
     ///
@@ -109,6 +120,10 @@ fn test_proof_of_possession() {
     assert!(!generated);
     assert_eq!(loaded_key.to_bytes(), loaded_key_2.to_bytes());
 
+    let pop = ProofOfPossession::from_signer_key_file(&key_path).unwrap();
+    assert_eq!(pop, loaded_key.to_proof_of_possession());
+    assert!(pop.verify().unwrap());
+
     std::fs::remove_file(&key_path).unwrap();
 
     let pop = sk.to_proof_of_possession();
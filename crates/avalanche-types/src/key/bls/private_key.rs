@@ -89,25 +89,39 @@ impl Key {
         }
 
         let sk = Self::generate()?;
-        let key_contents = sk.to_bytes();
+        sk.to_file(key_path)?;
+
+        Ok(sk)
+    }
+
+    #[cfg(windows)]
+    pub fn generate_to_file(key_path: &str) -> io::Result<Self> {
+        unimplemented!("not implemented")
+    }
+
+    /// Writes the key to a file in the "avalanchego" "signer.key" format,
+    /// which is the raw secret key scalar bytes (in big endian).
+    /// Overwrites the file if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the file fails.
+    pub fn to_file(&self, key_path: &str) -> io::Result<()> {
+        let key_contents = self.to_bytes();
 
         let mut key_file = File::create(key_path)?;
         key_file.write_all(&key_contents)?;
+        key_file.sync_all()?;
         log::info!(
             "saved staking signer key {} ({}-byte)",
             key_path,
             key_contents.len()
         );
 
-        Ok(sk)
-    }
-
-    #[cfg(windows)]
-    pub fn generate_to_file(key_path: &str) -> io::Result<Self> {
-        unimplemented!("not implemented")
+        Ok(())
     }
 
-    /// Loads the key from a file.
+    /// Loads the key from a file in the "avalanchego" "signer.key" format.
     ///
     /// # Errors
     ///
@@ -122,6 +136,15 @@ impl Key {
         }
 
         let raw = read_vec(key_path)?;
+        if raw.len() != LEN {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "key path {key_path} has {}-byte, expected {LEN}-byte signer key",
+                    raw.len()
+                ),
+            ));
+        }
         Self::from_bytes(&raw)
     }
 
@@ -246,5 +269,13 @@ fn test_key() {
     assert!(!generated);
     assert_eq!(loaded_key.to_bytes(), loaded_key_2.to_bytes());
 
+    // overwrites the existing "signer.key"
+    sk.to_file(&key_path).unwrap();
+    assert_eq!(std::fs::read(&key_path).unwrap(), sk.to_bytes());
+    assert_eq!(Key::from_file(&key_path).unwrap().to_bytes(), sk.to_bytes());
+
+    std::fs::write(&key_path, [1u8; LEN + 1]).unwrap();
+    assert!(Key::from_file(&key_path).is_err());
+
     std::fs::remove_file(&key_path).unwrap();
 }
@@ -0,0 +1,645 @@
+//! Ledger hardware wallet support for secp256k1 keys.
+//!
+//! Note that the actual private key never leaves the device.
+//! Signing is done by exchanging APDU commands with the Ledger Avalanche app
+//! over a caller-provided [`Transport`] (e.g., USB HID, Speculos TCP).
+//!
+//! ref. <https://github.com/ava-labs/ledger-avalanche>
+//! ref. <https://github.com/LedgerHQ/ledgerjs/tree/master/packages/hw-app-avalanche>
+use std::{collections::HashMap, fmt, str::FromStr, sync::Arc};
+
+use crate::{
+    errors::{Error, Result},
+    hash,
+    ids::short,
+    key,
+};
+use async_trait::async_trait;
+
+/// Instruction class of the Ledger Avalanche app.
+pub const CLA: u8 = 0x80;
+
+/// Returns the app version.
+pub const INS_GET_VERSION: u8 = 0x00;
+/// Returns the secp256k1 public key for the derivation path.
+pub const INS_GET_PUBLIC_KEY: u8 = 0x02;
+/// Signs the 32-byte hash with the key at the derivation path.
+pub const INS_SIGN_HASH: u8 = 0x04;
+
+/// Marks the first chunk of a multi-APDU payload, or the only chunk
+/// of a single-APDU payload.
+pub const P1_FIRST: u8 = 0x00;
+/// Marks an intermediate chunk of a multi-APDU payload.
+pub const P1_NEXT: u8 = 0x01;
+/// Marks the last chunk of a multi-APDU payload.
+pub const P1_LAST: u8 = 0x02;
+
+/// Do not prompt the user on the device.
+pub const P2_NO_CONFIRM: u8 = 0x00;
+/// Prompt the user to confirm on the device.
+pub const P2_CONFIRM: u8 = 0x01;
+
+/// Status word for a successful command.
+pub const SW_OK: u16 = 0x9000;
+/// Status word when the user rejects the request on the device.
+pub const SW_USER_REJECTED: u16 = 0x6986;
+/// Status word when the app is not open (or the wrong app is open).
+pub const SW_APP_NOT_OPEN: u16 = 0x6e00;
+
+/// Maximum APDU payload size.
+pub const MAX_CHUNK_SIZE: usize = 255;
+
+/// BIP44 coin type for AVAX.
+/// ref. <https://github.com/satoshilabs/slips/blob/master/slip-0044.md>
+pub const AVAX_COIN_TYPE: u32 = 9000;
+
+/// The hardened bit for BIP32 derivation indices.
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// Exchanges raw APDU commands with the device.
+///
+/// Implemented outside this crate for the actual device connection
+/// (e.g., HID or Speculos emulator), so this crate does not depend on
+/// any USB library.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends the serialized APDU command and returns the raw response,
+    /// including the trailing 2-byte status word.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot be reached.
+    async fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Represents an APDU command.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Apdu {
+    pub cla: u8,
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+}
+
+impl Apdu {
+    /// Serializes the command in the "CLA INS P1 P2 Lc DATA" format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload exceeds `MAX_CHUNK_SIZE`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let lc = u8::try_from(self.data.len()).map_err(|_| Error::Other {
+            message: format!(
+                "APDU payload must be <={MAX_CHUNK_SIZE}-byte, got {}-byte",
+                self.data.len()
+            ),
+            retryable: false,
+        })?;
+
+        let mut b = Vec::with_capacity(5 + self.data.len());
+        b.extend_from_slice(&[self.cla, self.ins, self.p1, self.p2, lc]);
+        b.extend_from_slice(&self.data);
+        Ok(b)
+    }
+}
+
+/// Splits the device response into its payload and status word.
+///
+/// # Errors
+///
+/// Returns an error if the response is too short or the status word is not `SW_OK`.
+pub fn check_response(resp: &[u8]) -> Result<Vec<u8>> {
+    if resp.len() < 2 {
+        return Err(Error::API {
+            message: format!("ledger response must be >=2-byte, got {}-byte", resp.len()),
+            retryable: false,
+        });
+    }
+
+    let (payload, sw) = resp.split_at(resp.len() - 2);
+    let sw = u16::from_be_bytes([sw[0], sw[1]]);
+    match sw {
+        SW_OK => Ok(payload.to_vec()),
+        SW_USER_REJECTED => Err(Error::API {
+            message: "ledger request rejected by user".to_string(),
+            retryable: false,
+        }),
+        SW_APP_NOT_OPEN => Err(Error::API {
+            message: "ledger Avalanche app is not open".to_string(),
+            retryable: true,
+        }),
+        _ => Err(Error::API {
+            message: format!("ledger returned unexpected status word 0x{sw:04x}"),
+            retryable: false,
+        }),
+    }
+}
+
+/// Represents a BIP32 derivation path (e.g., "m/44'/9000'/0'/0/0").
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+    /// Returns the BIP44 path "m/44'/9000'/{account}'/0/{index}"
+    /// used by the Avalanche wallet for X/P-chain keys.
+    #[must_use]
+    pub fn avax(account: u32, index: u32) -> Self {
+        Self(vec![
+            0x2c | HARDENED, // purpose 44' (BIP44)
+            AVAX_COIN_TYPE | HARDENED,
+            account | HARDENED,
+            0,
+            index,
+        ])
+    }
+
+    /// Encodes the path as the 1-byte length followed by
+    /// the big-endian 4-byte derivation indices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path has more than 255 components.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let n = u8::try_from(self.0.len()).map_err(|_| Error::Other {
+            message: format!("derivation path too long ({} components)", self.0.len()),
+            retryable: false,
+        })?;
+
+        let mut b = Vec::with_capacity(1 + self.0.len() * 4);
+        b.push(n);
+        for idx in &self.0 {
+            b.extend_from_slice(&idx.to_be_bytes());
+        }
+        Ok(b)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::Other {
+                message: format!("derivation path '{s}' must start with 'm'"),
+                retryable: false,
+            });
+        }
+
+        let mut indices = Vec::new();
+        for part in parts {
+            let (num, hardened) = part
+                .strip_suffix('\'')
+                .map_or((part, false), |stripped| (stripped, true));
+            let idx = num.parse::<u32>().map_err(|e| Error::Other {
+                message: format!("invalid derivation path component '{part}' ({e})"),
+                retryable: false,
+            })?;
+            if idx >= HARDENED {
+                return Err(Error::Other {
+                    message: format!("derivation path component '{part}' out of range"),
+                    retryable: false,
+                });
+            }
+            indices.push(if hardened { idx | HARDENED } else { idx });
+        }
+        Ok(Self(indices))
+    }
+}
+
+/// ref. <https://doc.rust-lang.org/std/string/trait.ToString.html>
+/// ref. <https://doc.rust-lang.org/std/fmt/trait.Display.html>
+/// Use `Self.to_string()` to directly invoke this.
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for idx in &self.0 {
+            if idx & HARDENED == 0 {
+                write!(f, "/{idx}")?;
+            } else {
+                write!(f, "/{}'", idx & !HARDENED)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the chunked "sign hash" APDU commands for the digest.
+/// The payload is the encoded derivation path followed by the digest,
+/// split into `MAX_CHUNK_SIZE` chunks. The first (or only) chunk is marked
+/// with `P1_FIRST`, and the last of multiple chunks with `P1_LAST`.
+///
+/// # Errors
+///
+/// Returns an error if the digest is not 32-byte or the path cannot be encoded.
+pub fn sign_hash_apdus(path: &DerivationPath, digest: &[u8]) -> Result<Vec<Apdu>> {
+    if digest.len() != hash::SHA256_OUTPUT_LEN {
+        return Err(Error::Other {
+            message: format!(
+                "sign_digest only takes {}-byte, got {}-byte",
+                hash::SHA256_OUTPUT_LEN,
+                digest.len()
+            ),
+            retryable: false,
+        });
+    }
+
+    let mut payload = path.to_bytes()?;
+    payload.extend_from_slice(digest);
+
+    let chunks: Vec<&[u8]> = payload.chunks(MAX_CHUNK_SIZE).collect();
+    let last = chunks.len() - 1;
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| Apdu {
+            cla: CLA,
+            ins: INS_SIGN_HASH,
+            p1: if i == 0 {
+                P1_FIRST
+            } else if i == last {
+                P1_LAST
+            } else {
+                P1_NEXT
+            },
+            p2: P2_CONFIRM,
+            data: chunk.to_vec(),
+        })
+        .collect())
+}
+
+/// Represents a secp256k1 key held by the Ledger Avalanche app.
+#[derive(Debug, Clone)]
+pub struct Key<T: Transport> {
+    /// Device connection.
+    pub transport: Arc<T>,
+    /// BIP44 derivation path of the key.
+    pub path: DerivationPath,
+    /// Public key fetched from the device.
+    pub public_key: key::secp256k1::public_key::Key,
+}
+
+impl<T> Key<T>
+where
+    T: Transport,
+{
+    /// Loads the key at the derivation path, fetching its public key from the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device call fails or the public key is malformed.
+    pub async fn new(transport: Arc<T>, path: DerivationPath) -> Result<Self> {
+        let mut data = path.to_bytes()?;
+        // empty HRP and chain Id, the device only needs the path
+        data.extend_from_slice(&[0, 0]);
+        let apdu = Apdu {
+            cla: CLA,
+            ins: INS_GET_PUBLIC_KEY,
+            p1: P1_FIRST,
+            p2: P2_NO_CONFIRM,
+            data,
+        };
+        let resp = check_response(&transport.exchange(&apdu.to_bytes()?).await?)?;
+
+        // 1-byte length prefix followed by the SEC1-encoded public key
+        let Some((&n, key_bytes)) = resp.split_first() else {
+            return Err(Error::API {
+                message: "empty ledger public key response".to_string(),
+                retryable: false,
+            });
+        };
+        let n = usize::from(n);
+        if key_bytes.len() < n {
+            return Err(Error::API {
+                message: format!(
+                    "ledger public key response too short (expected {n}, got {})",
+                    key_bytes.len()
+                ),
+                retryable: false,
+            });
+        }
+        let public_key = key::secp256k1::public_key::Key::from_sec1_bytes(&key_bytes[..n])?;
+        log::info!(
+            "fetched ledger public key at '{path}' with ETH address '{}'",
+            public_key.to_eth_address(),
+        );
+
+        Ok(Self {
+            transport,
+            path,
+            public_key,
+        })
+    }
+
+    /// Loads the key at "m/44'/9000'/{account}'/0/{index}".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device call fails.
+    pub async fn from_avax_path(transport: Arc<T>, account: u32, index: u32) -> Result<Self> {
+        Self::new(transport, DerivationPath::avax(account, index)).await
+    }
+
+    /// Returns the app version as (major, minor, patch).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device call fails.
+    pub async fn version(&self) -> Result<(u8, u8, u8)> {
+        let apdu = Apdu {
+            cla: CLA,
+            ins: INS_GET_VERSION,
+            p1: 0,
+            p2: 0,
+            data: Vec::new(),
+        };
+        let resp = check_response(&self.transport.exchange(&apdu.to_bytes()?).await?)?;
+        if resp.len() < 3 {
+            return Err(Error::API {
+                message: format!("ledger version response too short ({}-byte)", resp.len()),
+                retryable: false,
+            });
+        }
+        Ok((resp[0], resp[1], resp[2]))
+    }
+
+    #[must_use]
+    pub const fn to_public_key(&self) -> key::secp256k1::public_key::Key {
+        self.public_key
+    }
+
+    /// Converts to Info.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the addresses cannot be generated.
+    pub fn to_info(&self, network_id: u32) -> Result<key::secp256k1::Info> {
+        let short_addr = self.public_key.to_short_id()?;
+        let eth_addr = self.public_key.to_eth_address();
+        let h160_addr = self.public_key.to_h160();
+
+        let mut addresses = HashMap::new();
+        addresses.insert(
+            network_id,
+            key::secp256k1::ChainAddresses {
+                x: self.public_key.to_hrp_address(network_id, "X")?,
+                p: self.public_key.to_hrp_address(network_id, "P")?,
+            },
+        );
+
+        Ok(key::secp256k1::Info {
+            id: Some(self.path.to_string()),
+            key_type: key::secp256k1::KeyType::Ledger,
+
+            addresses,
+
+            short_address: short_addr,
+            eth_address: eth_addr,
+            h160_address: h160_addr,
+
+            ..Default::default()
+        })
+    }
+
+    /// Signs the 32-byte digest on the device, which prompts the user for confirmation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device rejects the request or
+    /// the returned signature does not match the public key.
+    pub async fn sign_digest(&self, digest: &[u8]) -> Result<key::secp256k1::signature::Sig> {
+        let apdus = sign_hash_apdus(&self.path, digest)?;
+
+        let mut resp = Vec::new();
+        for apdu in &apdus {
+            resp = check_response(&self.transport.exchange(&apdu.to_bytes()?).await?)?;
+        }
+        if resp.len() != key::secp256k1::signature::LEN {
+            return Err(Error::API {
+                message: format!(
+                    "ledger signature must be {}-byte, got {}-byte",
+                    key::secp256k1::signature::LEN,
+                    resp.len()
+                ),
+                retryable: false,
+            });
+        }
+
+        // some app versions return Ethereum-style "v" (27 or 28)
+        if resp[64] >= 27 {
+            resp[64] -= 27;
+        }
+        let sig = key::secp256k1::signature::Sig::from_bytes(&resp)?;
+
+        let (recovered, _) = sig.recover_public_key(digest)?;
+        if recovered != self.public_key {
+            return Err(Error::Other {
+                message: format!(
+                    "ledger signature does not match the public key at '{}'",
+                    self.path
+                ),
+                retryable: false,
+            });
+        }
+        Ok(sig)
+    }
+}
+
+#[async_trait]
+impl<T> key::secp256k1::SignOnly for Key<T>
+where
+    T: Transport,
+{
+    fn signing_key(&self) -> Result<k256::ecdsa::SigningKey> {
+        Err(Error::Other {
+            message: "signing key is not exportable from ledger".to_string(),
+            retryable: false,
+        })
+    }
+
+    async fn sign_digest(&self, msg: &[u8]) -> Result<[u8; 65]> {
+        let sig = self.sign_digest(msg).await?;
+        Ok(sig.to_bytes())
+    }
+
+    /// Each signature needs a confirmation on the device, so caches by the public key.
+    fn key_id(&self) -> Option<Vec<u8>> {
+        Some(self.public_key.to_compressed_bytes().to_vec())
+    }
+}
+
+/// ref. <https://doc.rust-lang.org/book/ch10-02-traits.html>
+impl<T> key::secp256k1::ReadOnly for Key<T>
+where
+    T: Transport,
+{
+    fn key_type(&self) -> key::secp256k1::KeyType {
+        key::secp256k1::KeyType::Ledger
+    }
+
+    fn hrp_address(&self, network_id: u32, chain_id_alias: &str) -> Result<String> {
+        self.to_public_key()
+            .to_hrp_address(network_id, chain_id_alias)
+    }
+
+    fn short_address(&self) -> Result<short::Id> {
+        self.to_public_key().to_short_id()
+    }
+
+    fn short_address_bytes(&self) -> Result<Vec<u8>> {
+        self.to_public_key().to_short_bytes()
+    }
+
+    fn eth_address(&self) -> String {
+        self.to_public_key().to_eth_address()
+    }
+
+    fn h160_address(&self) -> primitive_types::H160 {
+        self.to_public_key().to_h160()
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features ledger -- `key::ledger::test_derivation_path` --exact --show-output
+#[test]
+fn test_derivation_path() {
+    let p = DerivationPath::from_str("m/44'/9000'/0'/0/7").unwrap();
+    assert_eq!(p, DerivationPath::avax(0, 7));
+    assert_eq!(p.to_string(), "m/44'/9000'/0'/0/7");
+    assert_eq!(
+        p.to_bytes().unwrap(),
+        vec![
+            5, //
+            0x80, 0x00, 0x00, 0x2c, //
+            0x80, 0x00, 0x23, 0x28, //
+            0x80, 0x00, 0x00, 0x00, //
+            0x00, 0x00, 0x00, 0x00, //
+            0x00, 0x00, 0x00, 0x07, //
+        ]
+    );
+
+    assert!(DerivationPath::from_str("44'/9000'").is_err());
+    assert!(DerivationPath::from_str("m/abc").is_err());
+    assert!(DerivationPath::from_str("m/2147483648").is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features ledger -- `key::ledger::test_check_response` --exact --show-output
+#[test]
+fn test_check_response() {
+    assert_eq!(check_response(&[1, 2, 0x90, 0x00]).unwrap(), vec![1, 2]);
+    assert!(check_response(&[0x90]).is_err());
+
+    let e = check_response(&[0x69, 0x86]).unwrap_err();
+    assert!(!e.retryable());
+    let e = check_response(&[0x6e, 0x00]).unwrap_err();
+    assert!(e.retryable());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features ledger -- `key::ledger::test_sign_hash_apdus` --exact --show-output
+#[test]
+fn test_sign_hash_apdus() {
+    let digest = hash::sha256(b"hello world");
+
+    // the payload of the BIP44 path fits in a single chunk
+    let path = DerivationPath::avax(0, 0);
+    let apdus = sign_hash_apdus(&path, &digest).unwrap();
+    assert_eq!(apdus.len(), 1);
+    assert_eq!(apdus[0].p1, P1_FIRST);
+    assert_eq!(apdus[0].p2, P2_CONFIRM);
+    let mut payload = path.to_bytes().unwrap();
+    payload.extend_from_slice(&digest);
+    assert_eq!(apdus[0].data, payload);
+    let b = apdus[0].to_bytes().unwrap();
+    assert_eq!(&b[..5], &[CLA, INS_SIGN_HASH, P1_FIRST, P2_CONFIRM, 53]);
+
+    // 1 + 4 * 60 + 32 = 273-byte payload spans two chunks
+    let path = DerivationPath(vec![0; 60]);
+    let p1s: Vec<u8> = sign_hash_apdus(&path, &digest)
+        .unwrap()
+        .iter()
+        .map(|apdu| apdu.p1)
+        .collect();
+    assert_eq!(p1s, vec![P1_FIRST, P1_LAST]);
+
+    assert!(sign_hash_apdus(&path, &digest[..10]).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features ledger -- `key::ledger::test_sign_with_emulated_device` --exact --show-output
+#[test]
+fn test_sign_with_emulated_device() {
+    use std::sync::Mutex;
+
+    use crate::key::secp256k1::{ReadOnly, SignOnly};
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    /// Emulates the device with a hot key.
+    #[derive(Debug)]
+    struct Emulator {
+        /// Key held by the emulated device.
+        sk: key::secp256k1::private_key::Key,
+        /// Buffers the chunked sign payload.
+        buf: Mutex<Vec<u8>>,
+    }
+
+    #[async_trait]
+    impl Transport for Emulator {
+        async fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+            let (ins, p1, data) = (apdu[1], apdu[2], &apdu[5..]);
+            let mut resp = match ins {
+                INS_GET_PUBLIC_KEY => {
+                    let pk = self.sk.to_public_key().to_compressed_bytes();
+                    let mut b = vec![u8::try_from(pk.len()).unwrap()];
+                    b.extend_from_slice(&pk);
+                    b
+                }
+                INS_SIGN_HASH => {
+                    let mut buf = self.buf.lock().unwrap();
+                    if p1 == P1_FIRST {
+                        buf.clear();
+                    }
+                    buf.extend_from_slice(data);
+                    // the payload is complete once it holds the path and the digest
+                    let complete = buf.first().is_some_and(|&n| {
+                        buf.len() == 1 + usize::from(n) * 4 + hash::SHA256_OUTPUT_LEN
+                    });
+                    if complete {
+                        let at = buf.len() - hash::SHA256_OUTPUT_LEN;
+                        let digest = buf.split_off(at);
+                        buf.clear();
+                        self.sk.sign_digest(&digest)?.to_bytes().to_vec()
+                    } else {
+                        Vec::new()
+                    }
+                }
+                _ => return Ok(vec![0x6d, 0x00]),
+            };
+            resp.extend_from_slice(&SW_OK.to_be_bytes());
+            Ok(resp)
+        }
+    }
+
+    let sk = key::secp256k1::private_key::Key::generate().unwrap();
+    let transport = Arc::new(Emulator {
+        sk: sk.clone(),
+        buf: Mutex::new(Vec::new()),
+    });
+
+    let k = ab!(Key::from_avax_path(transport, 0, 0)).unwrap();
+    assert_eq!(k.key_type(), key::secp256k1::KeyType::Ledger);
+    assert_eq!(k.eth_address(), sk.to_public_key().to_eth_address());
+    assert_eq!(
+        k.short_address().unwrap(),
+        sk.to_public_key().to_short_id().unwrap()
+    );
+
+    let digest = hash::sha256(b"hello world");
+    let sig = ab!(SignOnly::sign_digest(&k, &digest)).unwrap();
+    assert!(sk.to_public_key().verify(&digest, &sig).unwrap());
+
+    assert!(ab!(k.sign_digest(&[0u8; 10])).is_err());
+}
@@ -1,5 +1,9 @@
 //! APIs for cryptographic operations on Avalanche.
 //!
-//! Includes both BLS and secp256k1 keys.
+//! Includes both BLS and secp256k1 keys, and hardware-backed signers.
 pub mod bls;
 pub mod secp256k1;
+
+#[cfg(feature = "ledger")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ledger")))]
+pub mod ledger;
@@ -0,0 +1,153 @@
+//! Adapter to use the keys of this crate as ethers-rs signers.
+//!
+//! Wraps any "`SignOnly`" key (e.g., the hot key "`private_key::Key`" or the
+//! AWS KMS key "`kms::aws::Key`"), so the existing EVM tooling (e.g., the
+//! "`SignerMiddleware`") can sign with the keys managed by this crate.
+use crate::{
+    errors::{Error, Result},
+    key,
+};
+use async_trait::async_trait;
+use ethers_core::{
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature, H256,
+    },
+    utils::hash_message,
+};
+
+/// Offset of "v" in the signatures of the messages (i.e., non-transactions).
+/// ref. <https://eips.ethereum.org/EIPS/eip-191>
+const MESSAGE_V_OFFSET: u64 = 27;
+
+/// Implements "`ethers_signers::Signer`" on top of the "`SignOnly`" key,
+/// with the same signatures as "`ethers_signers::LocalWallet`".
+#[derive(Clone, Debug)]
+pub struct Signer<K> {
+    pub inner: K,
+    pub chain_id: u64,
+    pub address: Address,
+}
+
+impl<K> Signer<K>
+where
+    K: key::secp256k1::SignOnly + key::secp256k1::ReadOnly + Clone + std::fmt::Debug + Send + Sync,
+{
+    #[must_use]
+    pub fn new(inner: K, chain_id: u64) -> Self {
+        let address = Address::from(inner.h160_address().to_fixed_bytes());
+        Self {
+            inner,
+            chain_id,
+            address,
+        }
+    }
+
+    /// Signs the digest, returning "v" as the recovery ID (0 or 1).
+    async fn sign_hash(&self, digest: H256) -> Result<Signature> {
+        let b = self.inner.sign_digest(digest.as_ref()).await?;
+        Ok(Signature {
+            r: primitive_types::U256::from_big_endian(&b[0..32]),
+            s: primitive_types::U256::from_big_endian(&b[32..64]),
+            v: u64::from(b[64]),
+        })
+    }
+}
+
+#[async_trait]
+impl<K> ethers_signers::Signer for Signer<K>
+where
+    K: key::secp256k1::SignOnly + key::secp256k1::ReadOnly + Clone + std::fmt::Debug + Send + Sync,
+{
+    type Error = Error;
+
+    /// Implements "eth_sign" using "ethers_core::utils::hash_message".
+    /// ref. <https://eips.ethereum.org/EIPS/eip-191>
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(&self, message: S) -> Result<Signature> {
+        let mut sig = self.sign_hash(hash_message(message.as_ref())).await?;
+        sig.v += MESSAGE_V_OFFSET;
+        Ok(sig)
+    }
+
+    /// Signs the transaction with the EIP-155 "v",
+    /// defaulting to the signer chain ID if the transaction has none.
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature> {
+        let mut tx_with_chain = tx.clone();
+        let chain_id = tx_with_chain
+            .chain_id()
+            .map_or(self.chain_id, |id| id.as_u64());
+        tx_with_chain.set_chain_id(chain_id);
+
+        let mut sig = self.sign_hash(tx_with_chain.sighash()).await?;
+        key::secp256k1::signature::apply_eip155(&mut sig, chain_id);
+        Ok(sig)
+    }
+
+    /// Implements "eth_signTypedData".
+    /// ref. <https://eips.ethereum.org/EIPS/eip-712>
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(&self, payload: &T) -> Result<Signature> {
+        let digest = payload.encode_eip712().map_err(|e| Error::Other {
+            message: format!("failed encode_eip712 {e}"),
+            retryable: false,
+        })?;
+        let mut sig = self.sign_hash(H256::from(digest)).await?;
+        sig.v += MESSAGE_V_OFFSET;
+        Ok(sig)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features eth_signer -- `key::secp256k1::eth_signer::test_signer` --exact --show-output
+#[test]
+fn test_signer() {
+    use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+    use ethers_signers::{LocalWallet, Signer as _};
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let k = key::secp256k1::private_key::Key::generate().unwrap();
+    let signer = Signer::new(k.clone(), 43114);
+    let wallet = LocalWallet::from(k.to_ethers_core_signing_key()).with_chain_id(43114u64);
+    assert_eq!(signer.address(), wallet.address());
+
+    let msg = b"hello";
+    let sig = ab!(signer.sign_message(msg)).unwrap();
+    assert_eq!(sig, ab!(wallet.sign_message(msg)).unwrap());
+    assert_eq!(sig.recover(&msg[..]).unwrap(), signer.address());
+
+    let to = Address::random();
+    let txs: Vec<TypedTransaction> = vec![
+        TransactionRequest::new().to(to).value(1).nonce(0).into(),
+        Eip1559TransactionRequest::new()
+            .to(to)
+            .value(1)
+            .nonce(0)
+            .chain_id(1u64)
+            .into(),
+    ];
+    for tx in &txs {
+        assert_eq!(
+            ab!(signer.sign_transaction(tx)).unwrap(),
+            ab!(wallet.sign_transaction(tx)).unwrap()
+        );
+    }
+
+    let signer = signer.with_chain_id(1u64);
+    assert_eq!(signer.chain_id(), 1);
+}
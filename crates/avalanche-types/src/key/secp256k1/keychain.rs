@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::{ids::short, key};
+use crate::{errors::Result, ids::short, key};
 use serde::{Deserialize, Serialize};
 
 /// Support multiple keys as a chain.
@@ -37,6 +37,32 @@ where
         }
     }
 
+    /// Adds the key to the keychain. No-op if the key address is already held.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key's short address cannot be obtained.
+    pub fn add(&mut self, key: T) -> Result<()> {
+        let short_addr = key.short_address()?;
+        if self.short_addr_to_key_index.contains_key(&short_addr) {
+            return Ok(());
+        }
+
+        let pos = u32::try_from(self.keys.len())?;
+        self.short_addr_to_key_index.insert(short_addr, pos);
+        self.keys.push(key);
+        Ok(())
+    }
+
+    /// Returns the short addresses of all keys in the keychain.
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Keychain.Addresses>
+    #[must_use]
+    pub fn addresses(&self) -> Vec<short::Id> {
+        let mut addrs: Vec<short::Id> = self.short_addr_to_key_index.keys().cloned().collect();
+        addrs.sort();
+        addrs
+    }
+
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Keychain.Get>
     #[must_use]
     pub fn get(&self, short_addr: &short::Id) -> Option<T> {
@@ -92,6 +118,47 @@ where
         }
     }
 
+    /// Matches the threshold with the keys in the keychain and the other owners
+    /// (co-signers), for the outputs that need signatures from multiple parties.
+    /// Prefers the keys in the keychain, and fills the rest with the co-signers
+    /// in the order of the owner addresses.
+    /// Returns the sorted signature indices and the addresses expected to sign,
+    /// or "None" if locked, or if the keychain holds none of the owner keys.
+    #[must_use]
+    pub fn match_threshold_with_cosigners(
+        &self,
+        output_owners: &key::secp256k1::txs::OutputOwners,
+        time: u64,
+    ) -> Option<(Vec<u32>, Vec<short::Id>)> {
+        if output_owners.locktime > time {
+            return None;
+        }
+        let threshold = usize::try_from(output_owners.threshold).ok()?;
+        if threshold == 0 || threshold > output_owners.addresses.len() {
+            return None;
+        }
+
+        let (held, others): (Vec<usize>, Vec<usize>) = (0..output_owners.addresses.len())
+            .partition(|pos| {
+                self.short_addr_to_key_index
+                    .contains_key(&output_owners.addresses[*pos])
+            });
+        if held.is_empty() {
+            return None;
+        }
+
+        let mut positions: Vec<usize> = held.into_iter().chain(others).take(threshold).collect();
+        positions.sort_unstable();
+
+        let mut sig_indices = Vec::with_capacity(positions.len());
+        let mut addresses = Vec::with_capacity(positions.len());
+        for pos in positions {
+            sig_indices.push(u32::try_from(pos).ok()?);
+            addresses.push(output_owners.addresses[pos].clone());
+        }
+        Some((sig_indices, addresses))
+    }
+
     /// Returns "None" if the threshold is NOT met.
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Keychain.Spend>
     /// TODO: support spend on `secp256k1fx::MintOutput`
@@ -117,3 +184,58 @@ where
         ))
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::keychain::test_keychain_multisig` --exact --show-output
+#[test]
+fn test_keychain_multisig() {
+    use crate::key::secp256k1::{private_key::Key, ReadOnly};
+
+    let k1 = Key::generate().unwrap();
+    let k2 = Key::generate().unwrap();
+    let k3 = Key::generate().unwrap();
+
+    let mut kc = Keychain::new(vec![k1.clone()]);
+    kc.add(k3.clone()).unwrap();
+    kc.add(k3.clone()).unwrap();
+    assert_eq!(kc.keys.len(), 2);
+    assert_eq!(kc.addresses().len(), 2);
+
+    let output_owners = key::secp256k1::txs::OutputOwners {
+        locktime: 0,
+        threshold: 2,
+        addresses: vec![
+            k1.short_address().unwrap(),
+            k2.short_address().unwrap(),
+            k3.short_address().unwrap(),
+        ],
+    };
+    let (sig_indices, signers) = kc.match_threshold(&output_owners, 0).unwrap();
+    assert_eq!(sig_indices, vec![0, 2]);
+    assert_eq!(signers, vec![k1.clone(), k3]);
+
+    // threshold cannot be met with a single key
+    let kc = Keychain::new(vec![k1.clone()]);
+    assert!(kc.match_threshold(&output_owners, 0).is_none());
+
+    // still locked
+    let locked = key::secp256k1::txs::OutputOwners {
+        locktime: 100,
+        threshold: 1,
+        addresses: vec![k1.short_address().unwrap()],
+    };
+    assert!(kc.match_threshold(&locked, 99).is_none());
+    assert!(kc.match_threshold(&locked, 100).is_some());
+
+    // co-signing needs only one of the keys
+    let (sig_indices, addrs) = kc
+        .match_threshold_with_cosigners(&output_owners, 0)
+        .unwrap();
+    assert_eq!(sig_indices, vec![0, 1]);
+    assert_eq!(
+        addrs,
+        vec![k1.short_address().unwrap(), k2.short_address().unwrap()]
+    );
+    assert!(Keychain::new(vec![Key::generate().unwrap()])
+        .match_threshold_with_cosigners(&output_owners, 0)
+        .is_none());
+}
@@ -0,0 +1,485 @@
+//! Encrypted JSON keystore for secp256k1 private keys.
+//!
+//! Implements the scrypt + AES-128-CTR variant of the Web3 Secret Storage
+//! definition, so the files can be read by other Ethereum tooling.
+//! ref. <https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage>
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use crate::{
+    errors::{Error, Result},
+    hash,
+    key::secp256k1::private_key,
+};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_with::{hex::Hex, serde_as};
+use subtle::ConstantTimeEq;
+
+/// AES-128 in big-endian counter mode, as specified by Web3 Secret Storage.
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// The only supported keystore version.
+pub const VERSION: u32 = 3;
+
+pub const CIPHER_AES_128_CTR: &str = "aes-128-ctr";
+pub const KDF_SCRYPT: &str = "scrypt";
+
+/// Default scrypt cost parameter (log2(N)), same as "geth".
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 18;
+/// Maximum scrypt cost parameter (log2(N)) accepted, which bounds
+/// the memory and time spent on the untrusted keystore files
+/// (128 * r * N bytes, 1 GiB with the default "r").
+pub const MAX_SCRYPT_LOG_N: u8 = 20;
+pub const DEFAULT_SCRYPT_R: u32 = 8;
+/// Maximum scrypt block size accepted, which bounds the memory
+/// together with `MAX_SCRYPT_LOG_N`.
+pub const MAX_SCRYPT_R: u32 = 8;
+pub const DEFAULT_SCRYPT_P: u32 = 1;
+/// Maximum scrypt parallelization accepted, which bounds the number
+/// of sequential passes over the memory.
+pub const MAX_SCRYPT_P: u32 = 16;
+pub const DKLEN: usize = 32;
+
+/// Represents the encrypted keystore file.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct Keystore {
+    /// Hex-encoded Ethereum address without the "0x" prefix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(alias = "Crypto")]
+    pub crypto: Crypto,
+    pub id: String,
+    pub version: u32,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct Crypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    #[serde_as(as = "Hex")]
+    pub ciphertext: Vec<u8>,
+    pub kdf: String,
+    pub kdfparams: ScryptParams,
+    #[serde_as(as = "Hex")]
+    pub mac: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct CipherParams {
+    #[serde_as(as = "Hex")]
+    pub iv: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ScryptParams {
+    pub dklen: usize,
+    pub n: u64,
+    pub r: u32,
+    pub p: u32,
+    #[serde_as(as = "Hex")]
+    pub salt: Vec<u8>,
+}
+
+impl Keystore {
+    /// Encrypts the private key with the password, using the default scrypt parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key derivation fails.
+    pub fn encrypt(key: &private_key::Key, password: &str) -> Result<Self> {
+        Self::encrypt_with_params(
+            key,
+            password,
+            DEFAULT_SCRYPT_LOG_N,
+            DEFAULT_SCRYPT_R,
+            DEFAULT_SCRYPT_P,
+        )
+    }
+
+    /// Encrypts the private key with the password and the scrypt parameters.
+    /// Lower "`log_n`" speeds up the encryption at the cost of weaker protection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scrypt parameters are invalid
+    /// or exceed `MAX_SCRYPT_LOG_N`, `MAX_SCRYPT_R` or `MAX_SCRYPT_P`.
+    pub fn encrypt_with_params(
+        key: &private_key::Key,
+        password: &str,
+        log_n: u8,
+        r: u32,
+        p: u32,
+    ) -> Result<Self> {
+        check_scrypt_cost(log_n, r, p)?;
+        let mut rng = rand::thread_rng();
+
+        let mut salt = vec![0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let mut iv = vec![0u8; 16];
+        rng.fill_bytes(&mut iv);
+        let mut id = [0u8; 16];
+        rng.fill_bytes(&mut id);
+
+        let kdfparams = ScryptParams {
+            dklen: DKLEN,
+            n: 1_u64 << log_n,
+            r,
+            p,
+            salt,
+        };
+        let crypto = encrypt_with(&key.to_bytes(), password, kdfparams, iv)?;
+
+        let eth_addr = key.to_public_key().to_eth_address();
+        Ok(Self {
+            address: Some(eth_addr.trim_start_matches("0x").to_lowercase()),
+            crypto,
+            id: uuid_v4(id),
+            version: VERSION,
+        })
+    }
+
+    /// Decrypts the private key with the password.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the password is wrong or the keystore is not supported.
+    pub fn decrypt(&self, password: &str) -> Result<private_key::Key> {
+        if self.version != VERSION {
+            return Err(Error::Other {
+                message: format!("unsupported keystore version {}", self.version),
+                retryable: false,
+            });
+        }
+        if self.crypto.cipher != CIPHER_AES_128_CTR {
+            return Err(Error::Other {
+                message: format!("unsupported keystore cipher '{}'", self.crypto.cipher),
+                retryable: false,
+            });
+        }
+        if self.crypto.kdf != KDF_SCRYPT {
+            return Err(Error::Other {
+                message: format!("unsupported keystore kdf '{}'", self.crypto.kdf),
+                retryable: false,
+            });
+        }
+
+        let dk = derive_key(password, &self.crypto.kdfparams)?;
+        let mac = compute_mac(&dk, &self.crypto.ciphertext);
+        if !bool::from(mac.ct_eq(&self.crypto.mac)) {
+            return Err(Error::Other {
+                message: "keystore MAC mismatch (wrong password?)".to_string(),
+                retryable: false,
+            });
+        }
+
+        let mut plaintext = self.crypto.ciphertext.clone();
+        apply_aes_128_ctr(&dk, &self.crypto.cipherparams.iv, &mut plaintext)?;
+        private_key::Key::from_bytes(&plaintext)
+    }
+
+    /// Loads the keystore from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be parsed.
+    pub fn load(file_path: &str) -> Result<Self> {
+        log::info!("loading keystore from {file_path}");
+
+        if !Path::new(file_path).exists() {
+            return Err(Error::Other {
+                message: format!("file {file_path} does not exists"),
+                retryable: false,
+            });
+        }
+
+        let f = File::open(file_path).map_err(|e| Error::Other {
+            message: format!("failed to open {file_path} ({e})"),
+            retryable: false,
+        })?;
+        serde_json::from_reader(f).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_reader {e}"),
+            retryable: false,
+        })
+    }
+
+    /// Syncs the keystore to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written to.
+    pub fn sync(&self, file_path: &str) -> std::io::Result<()> {
+        log::info!("syncing keystore to '{file_path}'");
+        let path = Path::new(file_path);
+        let parent_dir = path.parent().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "failed to get parent directory",
+            )
+        })?;
+        fs::create_dir_all(parent_dir)?;
+
+        let d = serde_json::to_vec_pretty(self).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("failed to serialize JSON {e}"),
+            )
+        })?;
+
+        // the key file is only readable by the owner
+        let mut opts = OpenOptions::new();
+        opts.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+        let mut f = opts.open(file_path)?;
+        // "mode" only applies on creation, so tighten an existing file too
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            f.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        f.write_all(&d)?;
+
+        Ok(())
+    }
+}
+
+impl private_key::Key {
+    /// Encrypts the private key and writes it as a JSON keystore file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encryption or the file write fails.
+    pub fn to_keystore_file(&self, password: &str, file_path: &str) -> Result<Keystore> {
+        let ks = Keystore::encrypt(self, password)?;
+        ks.sync(file_path).map_err(|e| Error::Other {
+            message: format!("failed to sync keystore {e}"),
+            retryable: false,
+        })?;
+        Ok(ks)
+    }
+
+    /// Loads the private key from the JSON keystore file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or the password is wrong.
+    pub fn from_keystore_file(file_path: &str, password: &str) -> Result<Self> {
+        Keystore::load(file_path)?.decrypt(password)
+    }
+}
+
+/// Encrypts the plaintext with the key derived from the password.
+fn encrypt_with(
+    plaintext: &[u8],
+    password: &str,
+    kdfparams: ScryptParams,
+    iv: Vec<u8>,
+) -> Result<Crypto> {
+    let dk = derive_key(password, &kdfparams)?;
+
+    let mut ciphertext = plaintext.to_vec();
+    apply_aes_128_ctr(&dk, &iv, &mut ciphertext)?;
+    let mac = compute_mac(&dk, &ciphertext);
+
+    Ok(Crypto {
+        cipher: CIPHER_AES_128_CTR.to_string(),
+        cipherparams: CipherParams { iv },
+        ciphertext,
+        kdf: KDF_SCRYPT.to_string(),
+        kdfparams,
+        mac,
+    })
+}
+
+/// Rejects the scrypt parameters that would spend unbounded memory
+/// (128 * r * N bytes) or time (p passes) on an untrusted keystore.
+fn check_scrypt_cost(log_n: u8, r: u32, p: u32) -> Result<()> {
+    if log_n > MAX_SCRYPT_LOG_N {
+        return Err(Error::Other {
+            message: format!("scrypt n must be <=2^{MAX_SCRYPT_LOG_N}, got 2^{log_n}"),
+            retryable: false,
+        });
+    }
+    if r > MAX_SCRYPT_R {
+        return Err(Error::Other {
+            message: format!("scrypt r must be <={MAX_SCRYPT_R}, got {r}"),
+            retryable: false,
+        });
+    }
+    if p > MAX_SCRYPT_P {
+        return Err(Error::Other {
+            message: format!("scrypt p must be <={MAX_SCRYPT_P}, got {p}"),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
+/// Derives the scrypt key from the password.
+fn derive_key(password: &str, kdfparams: &ScryptParams) -> Result<Vec<u8>> {
+    if kdfparams.dklen < DKLEN || !kdfparams.n.is_power_of_two() {
+        return Err(Error::Other {
+            message: format!(
+                "invalid scrypt params (dklen {}, n {})",
+                kdfparams.dklen, kdfparams.n
+            ),
+            retryable: false,
+        });
+    }
+    let log_n = u8::try_from(kdfparams.n.trailing_zeros())?;
+    check_scrypt_cost(log_n, kdfparams.r, kdfparams.p)?;
+
+    let params =
+        scrypt::Params::new(log_n, kdfparams.r, kdfparams.p, kdfparams.dklen).map_err(|e| {
+            Error::Other {
+                message: format!("invalid scrypt params ({e})"),
+                retryable: false,
+            }
+        })?;
+
+    let mut dk = vec![0u8; kdfparams.dklen];
+    scrypt::scrypt(password.as_bytes(), &kdfparams.salt, &params, &mut dk).map_err(|e| {
+        Error::Other {
+            message: format!("failed scrypt ({e})"),
+            retryable: false,
+        }
+    })?;
+    Ok(dk)
+}
+
+/// The MAC is the Keccak256 of the second 16 bytes of the derived key
+/// followed by the ciphertext.
+fn compute_mac(dk: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    hash::keccak256([&dk[16..32], ciphertext].concat())
+        .as_bytes()
+        .to_vec()
+}
+
+/// The encryption key is the first 16 bytes of the derived key.
+fn apply_aes_128_ctr(dk: &[u8], iv: &[u8], buf: &mut [u8]) -> Result<()> {
+    let mut cipher = Aes128Ctr::new_from_slices(&dk[..16], iv).map_err(|e| Error::Other {
+        message: format!("invalid AES-128-CTR key or iv ({e})"),
+        retryable: false,
+    })?;
+    cipher.apply_keystream(buf);
+    Ok(())
+}
+
+/// Formats the random bytes as an RFC 4122 version 4 UUID.
+fn uuid_v4(mut b: [u8; 16]) -> String {
+    b[6] = (b[6] & 0x0f) | 0x40;
+    b[8] = (b[8] & 0x3f) | 0x80;
+    let h = hex::encode(b);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &h[0..8],
+        &h[8..12],
+        &h[12..16],
+        &h[16..20],
+        &h[20..32]
+    )
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features keystore -- `key::secp256k1::keystore::test_keystore` --exact --show-output
+#[test]
+fn test_keystore() {
+    let _ = env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .is_test(true)
+        .try_init();
+
+    // expected values are computed with an independent scrypt/AES implementation
+    let key = private_key::Key::from_hex(
+        "0x7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d",
+    )
+    .unwrap();
+    let crypto = encrypt_with(
+        &key.to_bytes(),
+        "testpassword",
+        ScryptParams {
+            dklen: 32,
+            n: 1024,
+            r: 8,
+            p: 1,
+            salt: (0..32).collect(),
+        },
+        (0..16).collect(),
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(&crypto.ciphertext),
+        "d013b2c4cf7a09f4dde745d58b371f718549cbb6db09bf803b240e2d69780423"
+    );
+    assert_eq!(
+        hex::encode(&crypto.mac),
+        "024a2f907aad7e965c60ae4f2a0105648e65a05792ea2ffc75f5e78409a06e7f"
+    );
+
+    let ks = Keystore {
+        address: None,
+        crypto,
+        id: uuid_v4([0u8; 16]),
+        version: VERSION,
+    };
+    assert_eq!(ks.id, "00000000-0000-4000-8000-000000000000");
+    assert_eq!(ks.decrypt("testpassword").unwrap(), key);
+    assert!(ks.decrypt("wrongpassword").is_err());
+
+    // the cost parameter from the untrusted file is bounded
+    let mut expensive = ks.clone();
+    expensive.crypto.kdfparams.n = 1 << 63;
+    assert!(expensive.decrypt("testpassword").is_err());
+    assert!(Keystore::encrypt_with_params(&key, "testpassword", 64, 8, 1).is_err());
+    let mut expensive = ks.clone();
+    expensive.crypto.kdfparams.r = MAX_SCRYPT_R + 1;
+    assert!(expensive.decrypt("testpassword").is_err());
+    let mut expensive = ks;
+    expensive.crypto.kdfparams.p = u32::MAX;
+    assert!(expensive.decrypt("testpassword").is_err());
+    assert!(Keystore::encrypt_with_params(&key, "testpassword", 10, 1024, 1).is_err());
+    assert!(Keystore::encrypt_with_params(&key, "testpassword", 10, 8, 1024).is_err());
+
+    // round-trip through the file
+    let ks = Keystore::encrypt_with_params(&key, "testpassword", 10, 8, 1).unwrap();
+    let d = serde_json::to_string(&ks).unwrap();
+    log::info!("keystore: {d}");
+    assert!(d.contains("\"kdf\":\"scrypt\""));
+
+    let f = tempfile::NamedTempFile::new().unwrap();
+    let p = f.path().to_str().unwrap().to_string();
+    ks.sync(&p).unwrap();
+    let loaded = Keystore::load(&p).unwrap();
+    assert_eq!(loaded, ks);
+    assert_eq!(
+        private_key::Key::from_keystore_file(&p, "testpassword").unwrap(),
+        key
+    );
+
+    // the key file is only readable by the owner, whether new or overwritten
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path().join("keys").join("key.json");
+        ks.sync(p.to_str().unwrap()).unwrap();
+        let mode = fs::metadata(&p).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::set_permissions(&p, fs::Permissions::from_mode(0o644)).unwrap();
+        ks.sync(p.to_str().unwrap()).unwrap();
+        let mode = fs::metadata(&p).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
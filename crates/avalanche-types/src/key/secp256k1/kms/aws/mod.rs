@@ -291,6 +291,11 @@ impl key::secp256k1::SignOnly for Key {
 
         Ok(b)
     }
+
+    /// Each KMS signature is a network round trip, so caches by the public key.
+    fn key_id(&self) -> Option<Vec<u8>> {
+        Some(self.public_key.to_compressed_bytes().to_vec())
+    }
 }
 
 /// ref. <https://doc.rust-lang.org/book/ch10-02-traits.html>
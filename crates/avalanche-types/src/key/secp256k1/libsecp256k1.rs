@@ -244,14 +244,12 @@ impl PublicKey {
     ///
     /// Returns an error if the address cannot be formatted.
     pub fn to_hrp_address(&self, network_id: u32, chain_id_alias: &str) -> Result<String> {
-        let hrp = constants::NETWORK_ID_TO_HRP
-            .get(&network_id)
-            .map_or(constants::FALLBACK_HRP, |v| v);
+        let hrp = constants::hrp(network_id);
         // ref. "pk.PublicKey().Address().Bytes()"
         let short_address_bytes = self.to_short_bytes()?;
 
         // ref. "formatting.FormatAddress(chainIDAlias, hrp, pubBytes)"
-        formatting::address(chain_id_alias, hrp, &short_address_bytes).map_err(|e| Error::Other {
+        formatting::address(chain_id_alias, &hrp, &short_address_bytes).map_err(|e| Error::Other {
             message: format!("failed formatting::address ({e})"),
             retryable: false,
         })
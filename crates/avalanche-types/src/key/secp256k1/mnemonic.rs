@@ -29,9 +29,78 @@ pub fn gen_24() -> String {
     String::from(s)
 }
 
+/// BIP39 passphrase used by the Avalanche wallet, which derives keys
+/// without any passphrase.
+/// ref. <https://github.com/ava-labs/avalanche-wallet/blob/v0.3.8/src/js/wallets/MnemonicWallet.ts>
+pub const AVAX_WALLET_PASSPHRASE: &str = "";
+
+/// BIP39 passphrase used by "`from_mnemonic_phrase`".
+/// Kept for backward compatibility with the keys derived by earlier versions.
+pub const LEGACY_PASSPHRASE: &str = "password";
+
+/// Returns the derivation path "m/44'/9000'/0'/0/{index}" of the external
+/// address at the index, as used by the Avalanche wallet for X/P-chain keys.
+#[must_use]
+pub fn avax_deriv_path(index: u32) -> String {
+    format!("{AVAX_ACCOUNT_DERIV_PATH}/0/{index}")
+}
+
+/// Validates the mnemonic phrase (word list and checksum).
+///
+/// # Errors
+///
+/// Returns an error if the phrase is not a valid 24-word English mnemonic.
+pub fn validate_phrase<S>(phrase: S) -> Result<()>
+where
+    S: AsRef<str>,
+{
+    Mnemonic::new(phrase, Language::English).map_err(|e| Error::Other {
+        message: format!("failed to read mnemonic phrase ({e})"),
+        retryable: false,
+    })?;
+    Ok(())
+}
+
+/// Derives "count" keys at "m/44'/9000'/0'/0/n" starting from the index "start",
+/// using the same seed as the Avalanche wallet, and returns their infos
+/// (with the mnemonic phrase, X/P-chain and C-chain addresses).
+///
+/// # Errors
+///
+/// Returns an error if the phrase is invalid or the derivation fails.
+pub fn derive_infos<S>(
+    phrase: S,
+    network_id: u32,
+    start: u32,
+    count: u32,
+) -> Result<Vec<crate::key::secp256k1::Info>>
+where
+    S: AsRef<str>,
+{
+    let mut infos = Vec::new();
+    for index in start..start.saturating_add(count) {
+        let deriv_path = avax_deriv_path(index);
+        let key = crate::key::secp256k1::private_key::Key::from_mnemonic_phrase_with_passphrase(
+            phrase.as_ref(),
+            AVAX_WALLET_PASSPHRASE,
+            &deriv_path,
+        )?;
+
+        let mut info = key.to_info(network_id)?;
+        info.id = Some(deriv_path);
+        info.mnemonic_phrase = Some(phrase.as_ref().to_string());
+        infos.push(info);
+    }
+    Ok(infos)
+}
+
 impl crate::key::secp256k1::private_key::Key {
     /// Loads the private key from the mnemonic phrase.
     ///
+    /// Note that this uses "`LEGACY_PASSPHRASE`" for the seed, thus the derived
+    /// keys differ from the Avalanche wallet. Use "`from_mnemonic_phrase_avax`"
+    /// for wallet-compatible keys.
+    ///
     /// # Errors
     ///
     /// Returns an error if the mnemonic phrase or derivation path parsing fails.
@@ -39,7 +108,41 @@ impl crate::key::secp256k1::private_key::Key {
     where
         S: AsRef<str>,
     {
-        let deriv: DerivationPath = derive_path.as_ref().parse().map_err(|e| Error::Other {
+        Self::from_mnemonic_phrase_with_passphrase(
+            phrase.as_ref(),
+            LEGACY_PASSPHRASE,
+            derive_path.as_ref(),
+        )
+    }
+
+    /// Loads the private key at "m/44'/9000'/0'/0/{index}" from the mnemonic phrase,
+    /// same as the Avalanche wallet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mnemonic phrase parsing or derivation fails.
+    pub fn from_mnemonic_phrase_avax<S>(phrase: S, index: u32) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Self::from_mnemonic_phrase_with_passphrase(
+            phrase.as_ref(),
+            AVAX_WALLET_PASSPHRASE,
+            &avax_deriv_path(index),
+        )
+    }
+
+    /// Loads the private key from the mnemonic phrase with the BIP39 passphrase.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mnemonic phrase or derivation path parsing fails.
+    pub fn from_mnemonic_phrase_with_passphrase(
+        phrase: &str,
+        passphrase: &str,
+        derive_path: &str,
+    ) -> Result<Self> {
+        let deriv: DerivationPath = derive_path.parse().map_err(|e| Error::Other {
             message: format!("failed to parse derive path ({e})"),
             retryable: false,
         })?;
@@ -48,7 +151,7 @@ impl crate::key::secp256k1::private_key::Key {
             message: format!("failed to read mnemonic phrase ({e})"),
             retryable: false,
         })?;
-        let seed = mnemonic.to_seed("password");
+        let seed = mnemonic.to_seed(passphrase);
 
         // ref. https://github.com/ava-labs/avalanche-wallet/blob/v0.3.8/src/js/wallets/MnemonicWallet.ts
         let child_xprv = XPrv::derive_from_path(&seed, &deriv).map_err(|e| Error::Other {
@@ -60,3 +163,48 @@ impl crate::key::secp256k1::private_key::Key {
         Self::from_bytes(&pk)
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features mnemonic -- `key::secp256k1::mnemonic::test_derive` --exact --show-output
+#[test]
+fn test_derive() {
+    let phrase = "vehicle arrive more spread busy regret onion fame argue nice grocery humble vocal slot quit toss learn artwork theory fault tip belt cloth disorder";
+    validate_phrase(phrase).unwrap();
+    assert!(validate_phrase("vehicle arrive more").is_err());
+    assert!(validate_phrase(gen_24()).is_ok());
+
+    assert_eq!(avax_deriv_path(3), "m/44'/9000'/0'/0/3");
+
+    // expected keys are computed with an independent BIP39/BIP32 implementation
+    let k0 = crate::key::secp256k1::private_key::Key::from_mnemonic_phrase_avax(phrase, 0).unwrap();
+    assert_eq!(
+        k0.to_hex(),
+        "0xc960efb06a74e53a3f7d2c4ab7aa3b3504457e37e54651f6591d615ff41315c1"
+    );
+    let k1 = crate::key::secp256k1::private_key::Key::from_mnemonic_phrase_avax(phrase, 1).unwrap();
+    assert_eq!(
+        k1.to_hex(),
+        "0x86967dc221fe6d88b7e6712fe6850f98f24a725a1df0e2480e5ef83c39f45396"
+    );
+
+    let legacy = crate::key::secp256k1::private_key::Key::from_mnemonic_phrase(
+        phrase,
+        AVAX_ACCOUNT_DERIV_PATH_0,
+    )
+    .unwrap();
+    assert_eq!(
+        legacy.to_hex(),
+        "0xf88975995ec2c83832dc7fb071b78d015ffc1bc4474810c1f05f60738f4ffd26"
+    );
+
+    let infos = derive_infos(phrase, 1, 0, 2).unwrap();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[0].id.as_deref(), Some("m/44'/9000'/0'/0/0"));
+    assert_eq!(infos[0].mnemonic_phrase.as_deref(), Some(phrase));
+    assert_eq!(
+        infos[0].short_address,
+        k0.to_public_key().to_short_id().unwrap()
+    );
+    assert_eq!(infos[1].eth_address, k1.to_public_key().to_eth_address());
+    assert!(infos[1].addresses[&1].x.starts_with("X-avax1"));
+    assert!(infos[1].addresses[&1].p.starts_with("P-avax1"));
+}
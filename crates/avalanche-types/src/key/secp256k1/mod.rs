@@ -1,5 +1,8 @@
 //! Modules for secp256k1 key management in Avalanche.
 pub mod address;
+#[cfg(feature = "eth_signer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "eth_signer")))]
+pub mod eth_signer;
 pub mod keychain;
 pub mod kms;
 pub mod private_key;
@@ -11,12 +14,16 @@ pub mod txs;
 #[cfg_attr(docsrs, doc(cfg(feature = "libsecp256k1")))]
 pub mod libsecp256k1;
 
+#[cfg(feature = "keystore")]
+#[cfg_attr(docsrs, doc(cfg(feature = "keystore")))]
+pub mod keystore;
+
 #[cfg(feature = "mnemonic")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mnemonic")))]
 pub mod mnemonic;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt,
     fs::{self, File},
     io::Write,
@@ -25,7 +32,9 @@ use std::{
 
 use crate::{
     codec::serde::hex_0x_primitive_types_h160::Hex0xH160,
+    constants,
     errors::{Error, Result},
+    formatting,
     ids::short,
 };
 use async_trait::async_trait;
@@ -55,6 +64,14 @@ pub trait SignOnly {
     /// ref. <https://docs.rs/secp256k1/latest/secp256k1/struct.Message.html>
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
     async fn sign_digest(&self, digest: &[u8]) -> Result<[u8; 65]>;
+
+    /// Returns the identifier of the key to cache its signatures under
+    /// within a signing session (e.g., to sign each digest once per remote
+    /// key, when the same key signs many inputs of a transaction).
+    /// Returns none by default, which disables the caching.
+    fn key_id(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 /// Key interface that "only" allows "read" operations.
@@ -143,6 +160,143 @@ fn test_keys() {
     log::info!("total {} test keys are found", TEST_KEYS.len());
 }
 
+/// Generates "n" private keys. If the seed is given, the keys are
+/// derived from the seed and the key index, so the same seed always
+/// generates the same keys (e.g., for test fixtures). Otherwise,
+/// generates the keys from random bytes.
+///
+/// NEVER use a seeded key to hold real funds.
+///
+/// # Errors
+///
+/// Returns an error if the key generation fails.
+pub fn generate_n(
+    n: usize,
+    seed: Option<[u8; 32]>,
+) -> Result<Vec<crate::key::secp256k1::private_key::Key>> {
+    let mut keys = Vec::with_capacity(n);
+    for i in 0..n {
+        let Some(seed) = seed else {
+            keys.push(crate::key::secp256k1::private_key::Key::generate()?);
+            continue;
+        };
+
+        // retry with the next nonce in the (negligible) case the digest
+        // is not a valid secp256k1 scalar (zero or greater than the order)
+        let mut nonce = 0_u32;
+        loop {
+            let mut b = Vec::with_capacity(32 + 8 + 4);
+            b.extend_from_slice(&seed);
+            b.extend_from_slice(&(i as u64).to_be_bytes());
+            b.extend_from_slice(&nonce.to_be_bytes());
+            if let Ok(k) =
+                crate::key::secp256k1::private_key::Key::from_bytes(&crate::hash::sha256(&b))
+            {
+                keys.push(k);
+                break;
+            }
+            nonce = nonce.checked_add(1).ok_or_else(|| Error::Other {
+                message: format!("failed to derive a valid key at index {i}"),
+                retryable: false,
+            })?;
+        }
+    }
+    Ok(keys)
+}
+
+/// Network Id of the local test networks (e.g., network-runner).
+pub const LOCAL_TEST_NETWORK_ID: u32 = 1337;
+
+/// Represents a test key that is pre-funded in the genesis of a test network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefundedKey {
+    pub name: &'static str,
+    /// CB58-encoded private key with the prefix "PrivateKey-".
+    pub private_key_cb58: &'static str,
+    /// Hex-encoded private key without the prefix "0x".
+    pub private_key_hex: &'static str,
+    pub short_address: &'static str,
+    pub eth_address: &'static str,
+}
+
+impl PrefundedKey {
+    /// Loads the private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the embedded private key is invalid.
+    pub fn to_private_key(&self) -> Result<crate::key::secp256k1::private_key::Key> {
+        crate::key::secp256k1::private_key::Key::from_cb58(self.private_key_cb58)
+    }
+}
+
+/// The "ewoq" key pre-funded in the local network genesis (also the first of "`TEST_KEYS`").
+/// ref. <https://github.com/ava-labs/avalanchego/blob/master/genesis/genesis_local.json>
+pub const EWOQ_KEY: PrefundedKey = PrefundedKey {
+    name: "ewoq",
+    private_key_cb58: "PrivateKey-ewoqjP7PxY4yr3iLTpLisriqt94hdyDFNgchSxGGztUrTXtNN",
+    private_key_hex: "56289e99c94b6912bfc12adc093c9b51124f0dc54ac7a766b2bc5ccf558d8027",
+    short_address: "6Y3kysjF9jnHnYkdS9yGAuoHyae2eNmeV",
+    eth_address: "0x8db97C7cEcE249c2b98bDC0226Cc4C2A57BF52FC",
+};
+
+/// Keys pre-funded in the genesis of the local test network ("`LOCAL_TEST_NETWORK_ID`").
+pub const LOCAL_TEST_NETWORK_PREFUNDED_KEYS: &[PrefundedKey] = &[EWOQ_KEY];
+
+/// Returns the keys pre-funded in the genesis of the network,
+/// or an empty list if the network has no known test keys.
+/// The avalanchego "local" network (12345) pre-funds the same keys.
+#[must_use]
+pub fn prefunded_keys(network_id: u32) -> &'static [PrefundedKey] {
+    match network_id {
+        LOCAL_TEST_NETWORK_ID | 12345 => LOCAL_TEST_NETWORK_PREFUNDED_KEYS,
+        _ => &[],
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::test_generate_n` --exact --show-output
+#[test]
+fn test_generate_n() {
+    let seeded1 = generate_n(5, Some([1_u8; 32])).unwrap();
+    let seeded2 = generate_n(5, Some([1_u8; 32])).unwrap();
+    assert_eq!(seeded1.len(), 5);
+    assert_eq!(seeded1, seeded2);
+
+    // prefix of the longer list
+    let seeded3 = generate_n(3, Some([1_u8; 32])).unwrap();
+    assert_eq!(seeded3, seeded1[..3].to_vec());
+
+    let other = generate_n(5, Some([2_u8; 32])).unwrap();
+    for k in &other {
+        assert!(!seeded1.contains(k));
+    }
+    let unique: std::collections::HashSet<[u8; 32]> = seeded1
+        .iter()
+        .map(crate::key::secp256k1::private_key::Key::to_bytes)
+        .collect();
+    assert_eq!(unique.len(), 5);
+
+    assert_eq!(generate_n(4, None).unwrap().len(), 4);
+    assert!(generate_n(0, Some([1_u8; 32])).unwrap().is_empty());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::test_prefunded_keys` --exact --show-output
+#[test]
+fn test_prefunded_keys() {
+    assert_eq!(prefunded_keys(LOCAL_TEST_NETWORK_ID), &[EWOQ_KEY]);
+    assert_eq!(prefunded_keys(12345), &[EWOQ_KEY]);
+    assert!(prefunded_keys(1).is_empty());
+
+    for k in prefunded_keys(LOCAL_TEST_NETWORK_ID) {
+        let sk = k.to_private_key().unwrap();
+        assert_eq!(sk.to_hex(), format!("0x{}", k.private_key_hex));
+        let info = sk.to_info(LOCAL_TEST_NETWORK_ID).unwrap();
+        assert_eq!(info.short_address.to_string(), k.short_address);
+        assert_eq!(info.eth_address, k.eth_address);
+    }
+    assert_eq!(EWOQ_KEY.to_private_key().unwrap(), TEST_KEYS[0]);
+}
+
 // test random keys generated by "avalanchego/utils/crypto.FactorySECP256K1R"
 // and make sure both generate the same addresses
 // use "avalanche-rs/avalanchego-conformance/key/secp256k1"
@@ -300,6 +454,8 @@ pub enum KeyType {
     Hot,
     #[serde(rename = "aws-kms")]
     AwsKms,
+    #[serde(rename = "ledger")]
+    Ledger,
     Unknown(String),
 }
 
@@ -308,6 +464,7 @@ impl std::convert::From<&str> for KeyType {
         match s {
             "hot" => Self::Hot,
             "aws-kms" | "aws_kms" => Self::AwsKms,
+            "ledger" => Self::Ledger,
 
             other => Self::Unknown(other.to_owned()),
         }
@@ -338,6 +495,7 @@ impl KeyType {
         match self {
             Self::Hot => "hot",
             Self::AwsKms => "aws-kms",
+            Self::Ledger => "ledger",
 
             Self::Unknown(s) => s.as_ref(),
         }
@@ -349,6 +507,7 @@ impl KeyType {
         &[
             "hot",     //
             "aws-kms", //
+            "ledger",  //
         ]
     }
 }
@@ -366,6 +525,100 @@ pub struct ChainAddresses {
     pub p: String,
 }
 
+/// Addresses of a key on all chains of a network.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct KeyAddresses {
+    pub short_address: short::Id,
+    /// e.g., "X-avax1..."
+    pub x: String,
+    /// e.g., "P-avax1..."
+    pub p: String,
+    /// Bech32 C-chain address for the atomic imports and exports (e.g., "C-avax1...").
+    pub c: String,
+    /// EVM address of the C-chain (e.g., "0x...").
+    pub eth: String,
+}
+
+impl KeyAddresses {
+    /// Derives the addresses on the network from the key information,
+    /// even if the network is not in its "addresses".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the addresses cannot be formatted.
+    pub fn from_info(info: &Info, network_id: u32) -> Result<Self> {
+        let hrp = constants::hrp(network_id);
+        let format = |chain_id_alias: &str| {
+            formatting::address(chain_id_alias, &hrp, info.short_address.as_ref()).map_err(|e| {
+                Error::Other {
+                    message: format!("failed formatting::address '{e}'"),
+                    retryable: false,
+                }
+            })
+        };
+
+        let eth = if info.eth_address.is_empty() {
+            address::h160_to_eth_address(&info.h160_address, None)
+        } else {
+            info.eth_address.clone()
+        };
+        Ok(Self {
+            short_address: info.short_address.clone(),
+            x: format("X")?,
+            p: format("P")?,
+            c: format("C")?,
+            eth,
+        })
+    }
+}
+
+/// Addresses of the keys per network ID, in the order of the keys.
+/// Replaces the parallel lists of X/P/C-chain addresses in the cluster specs.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(transparent)]
+pub struct AddressBook(pub BTreeMap<u32, Vec<KeyAddresses>>);
+
+impl AddressBook {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derives the addresses of all keys on all the networks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the addresses cannot be formatted.
+    pub fn from_infos(infos: &[Info], network_ids: &[u32]) -> Result<Self> {
+        let mut book = Self::new();
+        for network_id in network_ids {
+            let addrs = infos
+                .iter()
+                .map(|info| KeyAddresses::from_info(info, *network_id))
+                .collect::<Result<Vec<_>>>()?;
+            book.0.insert(*network_id, addrs);
+        }
+        Ok(book)
+    }
+
+    /// Returns the addresses of the keys on the network.
+    #[must_use]
+    pub fn get(&self, network_id: u32) -> Option<&[KeyAddresses]> {
+        self.0.get(&network_id).map(Vec::as_slice)
+    }
+
+    /// Appends the addresses of a key on the network.
+    pub fn push(&mut self, network_id: u32, addrs: KeyAddresses) {
+        self.0.entry(network_id).or_default().push(addrs);
+    }
+
+    /// Returns the network IDs in ascending order.
+    pub fn network_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.keys().copied()
+    }
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::test_keys_address` --exact --show-output
 #[test]
 fn test_keys_address() {
@@ -424,3 +677,40 @@ fn test_keys_address() {
         }
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::test_address_book` --exact --show-output
+#[test]
+fn test_address_book() {
+    let infos: Vec<Info> = TEST_INFOS.iter().take(3).cloned().collect();
+    let book = AddressBook::from_infos(&infos, &[1, 9999]).unwrap();
+    assert_eq!(book.network_ids().collect::<Vec<_>>(), vec![1, 9999]);
+
+    let mainnet = book.get(1).unwrap();
+    assert_eq!(mainnet.len(), 3);
+    for (info, addrs) in infos.iter().zip(mainnet) {
+        assert_eq!(addrs.short_address, info.short_address);
+        assert_eq!(addrs.x, info.addresses.get(&1).unwrap().x);
+        assert_eq!(addrs.p, info.addresses.get(&1).unwrap().p);
+        assert_eq!(
+            addrs.c,
+            info.to_private_key()
+                .to_public_key()
+                .to_hrp_address(1, "C")
+                .unwrap()
+        );
+        assert_eq!(addrs.eth, info.eth_address);
+    }
+
+    // derived with the fallback HRP for the unknown network
+    let custom = book.get(9999).unwrap();
+    assert!(custom[0].x.starts_with("X-custom1"));
+    assert_eq!(custom[0].x, infos[0].addresses.get(&9999).unwrap().x);
+    assert!(book.get(5).is_none());
+
+    let encoded = serde_json::to_string(&book).unwrap();
+    assert_eq!(serde_json::from_str::<AddressBook>(&encoded).unwrap(), book);
+
+    let mut book = AddressBook::new();
+    book.push(1, mainnet[0].clone());
+    assert_eq!(book.get(1).unwrap(), &mainnet[..1]);
+}
@@ -2,12 +2,16 @@
 pub mod transfer;
 
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::io;
 
 use crate::{
     codec::{self, serde::hex_0x_bytes::Hex0xBytes},
+    errors::{Error, Result},
     ids::short,
+    key::secp256k1::SignOnly,
 };
+use futures::{stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -54,6 +58,129 @@ impl Credential {
     }
 }
 
+/// Maximum number of "`sign_digest`" calls in flight while signing the
+/// credentials of a transaction.
+pub const MAX_CONCURRENT_SIGNATURES: usize = 16;
+
+/// Caches the signatures by the key identifier and the digest within a
+/// signing session, to skip the redundant remote signing calls (e.g., KMS
+/// or hardware wallet) when the same key signs the same digest more than
+/// once. Only the keys with a [`SignOnly::key_id`] are cached.
+/// The tx types take the cache with "`sign_with_cache`".
+#[derive(Debug, Clone, Default)]
+pub struct SignatureCache {
+    /// Signatures keyed by the key identifier and the digest.
+    sigs: HashMap<(Vec<u8>, Vec<u8>), [u8; 65]>,
+}
+
+impl SignatureCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached signature of the digest by the key.
+    #[must_use]
+    pub fn get(&self, key_id: &[u8], digest: &[u8]) -> Option<&[u8; 65]> {
+        self.sigs.get(&(key_id.to_vec(), digest.to_vec()))
+    }
+
+    pub fn insert(&mut self, key_id: Vec<u8>, digest: &[u8], sig: [u8; 65]) {
+        self.sigs.insert((key_id, digest.to_vec()), sig);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sigs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sigs.is_empty()
+    }
+}
+
+/// Signs the digest with the signers of each credential concurrently
+/// (e.g., each signature of a KMS key is a network round trip), and returns
+/// the credentials in the order of the signers, each with the signatures in
+/// the order of its keys. A key that signs many inputs of the transaction
+/// signs the digest only once.
+///
+/// # Errors
+///
+/// Returns an error if any key fails to sign.
+pub async fn sign_credentials<T: SignOnly + Sync>(
+    digest: &[u8],
+    signers: &[Vec<T>],
+) -> Result<Vec<Credential>> {
+    sign_credentials_with_cache(digest, signers, &mut SignatureCache::new()).await
+}
+
+/// Same as [`sign_credentials`] but reuses the signatures of the session
+/// cache, and caches the new signatures of the keys with an identifier.
+///
+/// # Errors
+///
+/// Returns an error if any key fails to sign.
+pub async fn sign_credentials_with_cache<T: SignOnly + Sync>(
+    digest: &[u8],
+    signers: &[Vec<T>],
+    cache: &mut SignatureCache,
+) -> Result<Vec<Credential>> {
+    let key_ids: Vec<Option<Vec<u8>>> = signers.iter().flatten().map(SignOnly::key_id).collect();
+
+    // signs the keys without an identifier, and each identified key
+    // missing from the cache once
+    let mut seen = HashSet::new();
+    let pending: Vec<(&T, Option<&Vec<u8>>)> = signers
+        .iter()
+        .flatten()
+        .zip(key_ids.iter())
+        .filter_map(|(k, key_id)| match key_id {
+            Some(id) if cache.get(id, digest).is_some() || !seen.insert(id) => None,
+            _ => Some((k, key_id.as_ref())),
+        })
+        .collect();
+
+    // "buffered" yields in the order of the keys, regardless of which
+    // signature completes first; the futures are collected first, as
+    // a mapping closure over the borrowed keys keeps the future from
+    // being "Send"
+    let futs: Vec<_> = pending.iter().map(|(k, _)| k.sign_digest(digest)).collect();
+    let sigs: Vec<[u8; 65]> = stream::iter(futs)
+        .buffered(MAX_CONCURRENT_SIGNATURES)
+        .try_collect()
+        .await?;
+    log::debug!(
+        "signed {} digest(s) for {} key(s)",
+        sigs.len(),
+        key_ids.len()
+    );
+
+    let mut uncached = Vec::new();
+    for ((_, key_id), sig) in pending.into_iter().zip(sigs) {
+        match key_id {
+            Some(id) => cache.insert(id.clone(), digest, sig),
+            None => uncached.push(sig),
+        }
+    }
+
+    let mut uncached = uncached.into_iter();
+    let mut sigs = Vec::with_capacity(key_ids.len());
+    for key_id in &key_ids {
+        let sig = key_id
+            .as_ref()
+            .map_or_else(|| uncached.next(), |id| cache.get(id, digest).copied());
+        sigs.push(sig.ok_or_else(|| Error::UnexpectedNone("signature".to_string()))?);
+    }
+
+    let mut sigs = sigs.into_iter();
+    Ok(signers
+        .iter()
+        .map(|keys| Credential::new(sigs.by_ref().take(keys.len()).map(Vec::from).collect()))
+        .collect())
+}
+
 impl Ord for Credential {
     fn cmp(&self, other: &Self) -> Ordering {
         Signatures::new(&self.signatures).cmp(&Signatures::new(&other.signatures))
@@ -453,3 +580,111 @@ fn test_sort_inputs() {
     assert!(cmp_manager::is_sorted_and_unique(&sorted_inputs));
     assert_eq!(inputs, sorted_inputs);
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::txs::test_sign_credentials` --exact --show-output
+#[test]
+fn test_sign_credentials() {
+    use crate::key::secp256k1::TEST_KEYS;
+
+    let digest = crate::hash::sha256(b"hello");
+    // more keys than the concurrency limit, with a multi-sig credential
+    let mut signers: Vec<Vec<_>> = (0..MAX_CONCURRENT_SIGNATURES + 2)
+        .map(|i| vec![TEST_KEYS[i % TEST_KEYS.len()].clone()])
+        .collect();
+    signers.insert(1, vec![TEST_KEYS[1].clone(), TEST_KEYS[2].clone()]);
+    signers.push(Vec::new());
+
+    let creds = tokio_test::block_on(sign_credentials(&digest, &signers)).unwrap();
+    assert_eq!(creds.len(), signers.len());
+    for (cred, keys) in creds.iter().zip(signers.iter()) {
+        assert_eq!(cred.signatures.len(), keys.len());
+        for (sig, k) in cred.signatures.iter().zip(keys.iter()) {
+            assert_eq!(sig, &k.sign_digest(&digest).unwrap().to_bytes().to_vec());
+        }
+    }
+    assert!(creds.last().unwrap().signatures.is_empty());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `key::secp256k1::txs::test_sign_credentials_with_cache` --exact --show-output
+#[test]
+fn test_sign_credentials_with_cache() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    };
+
+    use crate::key::secp256k1::{private_key, TEST_KEYS};
+    use async_trait::async_trait;
+
+    /// Counts the signing calls, as a remote key would.
+    #[derive(Clone)]
+    struct CountingKey {
+        /// Underlying key.
+        key: private_key::Key,
+        /// Number of "sign_digest" calls.
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SignOnly for CountingKey {
+        fn signing_key(&self) -> Result<k256::ecdsa::SigningKey> {
+            Ok(self.key.signing_key())
+        }
+
+        async fn sign_digest(&self, digest: &[u8]) -> Result<[u8; 65]> {
+            self.calls.fetch_add(1, AtomicOrdering::SeqCst);
+            SignOnly::sign_digest(&self.key, digest).await
+        }
+
+        fn key_id(&self) -> Option<Vec<u8>> {
+            Some(self.key.to_public_key().to_compressed_bytes().to_vec())
+        }
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (k1, k2) = (
+        CountingKey {
+            key: TEST_KEYS[1].clone(),
+            calls: calls.clone(),
+        },
+        CountingKey {
+            key: TEST_KEYS[2].clone(),
+            calls: calls.clone(),
+        },
+    );
+    // the same key signs every input
+    let signers = vec![
+        vec![k1.clone()],
+        vec![k1.clone(), k2.clone()],
+        vec![k1],
+        vec![k2],
+    ];
+
+    let digest = crate::hash::sha256(b"hello");
+    let mut cache = SignatureCache::new();
+    let creds =
+        tokio_test::block_on(sign_credentials_with_cache(&digest, &signers, &mut cache)).unwrap();
+    assert_eq!(calls.load(AtomicOrdering::SeqCst), 2);
+    assert_eq!(cache.len(), 2);
+    for (cred, keys) in creds.iter().zip(signers.iter()) {
+        assert_eq!(cred.signatures.len(), keys.len());
+        for (sig, k) in cred.signatures.iter().zip(keys.iter()) {
+            assert_eq!(
+                sig,
+                &k.key.sign_digest(&digest).unwrap().to_bytes().to_vec()
+            );
+        }
+    }
+
+    // the session cache skips the signing of the same digest
+    let again =
+        tokio_test::block_on(sign_credentials_with_cache(&digest, &signers, &mut cache)).unwrap();
+    assert_eq!(calls.load(AtomicOrdering::SeqCst), 2);
+    assert_eq!(again, creds);
+
+    // uncached keys always sign
+    let plain = vec![vec![TEST_KEYS[1].clone()], vec![TEST_KEYS[1].clone()]];
+    let mut cache = SignatureCache::new();
+    tokio_test::block_on(sign_credentials_with_cache(&digest, &plain, &mut cache)).unwrap();
+    assert!(cache.is_empty());
+}
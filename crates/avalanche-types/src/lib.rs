@@ -31,6 +31,7 @@ pub mod key;
 pub mod node;
 pub mod packer;
 pub mod platformvm;
+pub mod proposervm;
 pub mod txs;
 pub mod units;
 pub mod utils;
@@ -72,6 +73,14 @@ pub mod proto;
 #[cfg_attr(docsrs, doc(cfg(feature = "subnet")))]
 pub mod subnet;
 
+#[cfg(feature = "jsonrpc_client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc_client")))]
+pub mod tooling;
+
+#[cfg(feature = "testutils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testutils")))]
+pub mod testutils;
+
 /// 重要结构体
 #[derive(Debug)]
 #[must_use]
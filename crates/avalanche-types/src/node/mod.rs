@@ -1,4 +1,6 @@
-//! Defines the node type.
+//! Defines the node type and the uptime tracker.
+pub mod uptime;
+
 use serde::{Deserialize, Serialize};
 
 /// Defines the node type.
@@ -0,0 +1,396 @@
+//! Tracks how long each node has been connected, for validator monitoring.
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/uptime>
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    errors::{Error, Result},
+    ids::node,
+};
+
+/// Default duration of connection history kept per node.
+pub const DEFAULT_MAX_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Uptime state of a single node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct State {
+    /// Unix timestamp (in seconds) when the tracking started.
+    start_time: u64,
+    /// Unix timestamp (in seconds) of the last connect, if connected.
+    connected_since: Option<u64>,
+    /// Closed connection intervals within the retention window, oldest first.
+    intervals: VecDeque<(u64, u64)>,
+    /// Connected seconds of the intervals dropped from the retention window.
+    pruned_up: u64,
+    /// Unix timestamp (in seconds) of the latest event, so that
+    /// out-of-order timestamps never move the clock backwards.
+    last_updated: u64,
+}
+
+impl State {
+    /// Returns the connected seconds within [from, now].
+    fn up_since(&self, from: u64, now: u64) -> u64 {
+        let closed: u64 = self
+            .intervals
+            .iter()
+            .map(|(s, e)| (*e).min(now).saturating_sub((*s).max(from)))
+            .sum();
+        let open = self
+            .connected_since
+            .map_or(0, |s| now.saturating_sub(s.max(from)));
+        closed + open
+    }
+}
+
+/// Ingests connect/disconnect events per node and computes the uptime,
+/// either since the tracking started or over a rolling window.
+/// Timestamps are unix seconds, so the tracker can replay historical events.
+///
+/// ref. "avalanchego/snow/uptime.Manager"
+#[derive(Debug, Clone)]
+pub struct Tracker {
+    /// Duration of connection history kept per node,
+    /// which bounds the rolling window.
+    max_window: Duration,
+    /// Uptime state per node.
+    nodes: HashMap<node::Id, State>,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_WINDOW)
+    }
+}
+
+impl Tracker {
+    /// Creates a new tracker that keeps the connection history for "`max_window`".
+    #[must_use]
+    pub fn new(max_window: Duration) -> Self {
+        Self {
+            max_window,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Returns the duration of connection history kept per node.
+    #[must_use]
+    pub const fn max_window(&self) -> Duration {
+        self.max_window
+    }
+
+    /// Returns the node Ids being tracked.
+    pub fn tracked(&self) -> impl Iterator<Item = &node::Id> {
+        self.nodes.keys()
+    }
+
+    /// Returns true if the node is being tracked.
+    #[must_use]
+    pub fn is_tracked(&self, node_id: &node::Id) -> bool {
+        self.nodes.contains_key(node_id)
+    }
+
+    /// Starts tracking the node as disconnected.
+    /// No-op if the node is already tracked.
+    /// ref. "avalanchego/snow/uptime.Manager.StartTracking"
+    pub fn start_tracking(&mut self, node_id: node::Id, now: u64) {
+        self.nodes.entry(node_id).or_insert_with(|| State {
+            start_time: now,
+            last_updated: now,
+            ..Default::default()
+        });
+    }
+
+    /// Stops tracking the node, and returns its uptime since the tracking started.
+    /// ref. "avalanchego/snow/uptime.Manager.StopTracking"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked.
+    pub fn stop_tracking(&mut self, node_id: &node::Id, now: u64) -> Result<Duration> {
+        let up = self.uptime(node_id, now)?;
+        self.nodes.remove(node_id);
+        Ok(up)
+    }
+
+    /// Marks the node as connected, and starts tracking it if not yet.
+    /// No-op if the node is already connected.
+    /// ref. "avalanchego/snow/uptime.Manager.Connect"
+    pub fn connect(&mut self, node_id: node::Id, now: u64) {
+        self.start_tracking(node_id, now);
+        let max_window = self.max_window.as_secs();
+        if let Some(state) = self.nodes.get_mut(&node_id) {
+            let now = now.max(state.last_updated);
+            state.last_updated = now;
+            if state.connected_since.is_none() {
+                state.connected_since = Some(now);
+            }
+            prune(state, now, max_window);
+        }
+    }
+
+    /// Marks the node as disconnected.
+    /// ref. "avalanchego/snow/uptime.Manager.Disconnect"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked.
+    pub fn disconnect(&mut self, node_id: &node::Id, now: u64) -> Result<()> {
+        let max_window = self.max_window.as_secs();
+        let state = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or_else(|| not_tracked(node_id))?;
+        let now = now.max(state.last_updated);
+        state.last_updated = now;
+        if let Some(since) = state.connected_since.take() {
+            if now > since {
+                state.intervals.push_back((since, now));
+            }
+        }
+        prune(state, now, max_window);
+        Ok(())
+    }
+
+    /// Returns true if the node is tracked and connected.
+    /// ref. "avalanchego/snow/uptime.Manager.IsConnected"
+    #[must_use]
+    pub fn is_connected(&self, node_id: &node::Id) -> bool {
+        self.nodes
+            .get(node_id)
+            .is_some_and(|s| s.connected_since.is_some())
+    }
+
+    /// Returns the connected duration since the tracking started.
+    /// ref. "avalanchego/snow/uptime.Calculator.CalculateUptime"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked.
+    pub fn uptime(&self, node_id: &node::Id, now: u64) -> Result<Duration> {
+        let state = self
+            .nodes
+            .get(node_id)
+            .ok_or_else(|| not_tracked(node_id))?;
+        let now = now.max(state.last_updated);
+        let up = state.pruned_up + state.up_since(state.start_time, now);
+        Ok(Duration::from_secs(up))
+    }
+
+    /// Returns the fraction (between 0 and 1) of time connected
+    /// since the tracking started.
+    /// ref. "avalanchego/snow/uptime.Calculator.CalculateUptimePercent"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked.
+    pub fn uptime_percent(&self, node_id: &node::Id, now: u64) -> Result<f64> {
+        let state = self
+            .nodes
+            .get(node_id)
+            .ok_or_else(|| not_tracked(node_id))?;
+        let now = now.max(state.last_updated);
+        let up = self.uptime(node_id, now)?.as_secs();
+        Ok(ratio(up, now - state.start_time))
+    }
+
+    /// Returns the fraction (between 0 and 1) of time connected within
+    /// [`start_time`, now], e.g., over the staking period of a validator.
+    /// Time before the tracking started counts as disconnected.
+    /// ref. "avalanchego/snow/uptime.Calculator.CalculateUptimePercentFrom"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked, or if "`start_time`"
+    /// is older than the retained connection history.
+    pub fn uptime_percent_from(
+        &self,
+        node_id: &node::Id,
+        start_time: u64,
+        now: u64,
+    ) -> Result<f64> {
+        let state = self
+            .nodes
+            .get(node_id)
+            .ok_or_else(|| not_tracked(node_id))?;
+        let now = now.max(state.last_updated);
+        if start_time <= state.start_time {
+            // history since the tracking started is fully covered
+            let up = state.pruned_up + state.up_since(state.start_time, now);
+            return Ok(ratio(up, now.saturating_sub(start_time)));
+        }
+        if state.pruned_up > 0 && start_time < now.saturating_sub(self.max_window.as_secs()) {
+            return Err(Error::Other {
+                message: format!(
+                    "start time {start_time} is older than the retained history of {:?}",
+                    self.max_window
+                ),
+                retryable: false,
+            });
+        }
+        let up = state.up_since(start_time, now);
+        Ok(ratio(up, now.saturating_sub(start_time)))
+    }
+
+    /// Returns the fraction (between 0 and 1) of time connected
+    /// over the last "window" (e.g., the last hour).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not tracked, or if the window
+    /// is longer than the retained connection history.
+    pub fn window_uptime_percent(
+        &self,
+        node_id: &node::Id,
+        window: Duration,
+        now: u64,
+    ) -> Result<f64> {
+        if window > self.max_window {
+            return Err(Error::Other {
+                message: format!(
+                    "window {window:?} exceeds the retained history of {:?}",
+                    self.max_window
+                ),
+                retryable: false,
+            });
+        }
+        let state = self
+            .nodes
+            .get(node_id)
+            .ok_or_else(|| not_tracked(node_id))?;
+        let now = now.max(state.last_updated);
+        self.uptime_percent_from(node_id, now.saturating_sub(window.as_secs()), now)
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+#[must_use]
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Drops the intervals that ended before the retention window,
+/// keeping their connected duration in the running total.
+fn prune(state: &mut State, now: u64, max_window: u64) {
+    let cutoff = now.saturating_sub(max_window);
+    while let Some((s, e)) = state.intervals.front().copied() {
+        if e >= cutoff {
+            break;
+        }
+        state.pruned_up += e - s;
+        state.intervals.pop_front();
+    }
+}
+
+/// Returns "up" over "total", or 1 if no time has passed yet.
+#[allow(clippy::cast_precision_loss)]
+fn ratio(up: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 1.0;
+    }
+    (up as f64 / total as f64).min(1.0)
+}
+
+/// Returns the error for an untracked node.
+fn not_tracked(node_id: &node::Id) -> Error {
+    Error::Other {
+        message: format!("node {node_id} is not tracked"),
+        retryable: false,
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `node::uptime::test_tracker` --exact --show-output
+#[test]
+fn test_tracker() {
+    let node_id = node::Id::from_slice(&random_manager::secure_bytes(20).unwrap());
+    let mut tracker = Tracker::new(Duration::from_secs(100));
+
+    assert!(tracker.uptime(&node_id, 0).is_err());
+    assert!(tracker.disconnect(&node_id, 0).is_err());
+
+    tracker.start_tracking(node_id, 1000);
+    assert!(tracker.is_tracked(&node_id));
+    assert!(!tracker.is_connected(&node_id));
+    assert!((tracker.uptime_percent(&node_id, 1000).unwrap() - 1.0).abs() < f64::EPSILON);
+
+    // connected for 10 out of 20 seconds
+    tracker.connect(node_id, 1010);
+    assert!(tracker.is_connected(&node_id));
+    assert_eq!(
+        tracker.uptime(&node_id, 1020).unwrap(),
+        Duration::from_secs(10)
+    );
+    assert!((tracker.uptime_percent(&node_id, 1020).unwrap() - 0.5).abs() < f64::EPSILON);
+
+    // connecting twice does not reset the connected time
+    tracker.connect(node_id, 1015);
+    assert_eq!(
+        tracker.uptime(&node_id, 1020).unwrap(),
+        Duration::from_secs(10)
+    );
+
+    tracker.disconnect(&node_id, 1030).unwrap();
+    assert!(!tracker.is_connected(&node_id));
+    assert_eq!(
+        tracker.uptime(&node_id, 1040).unwrap(),
+        Duration::from_secs(20)
+    );
+    assert!((tracker.uptime_percent(&node_id, 1040).unwrap() - 0.5).abs() < f64::EPSILON);
+
+    // connected for 5 seconds within the last 20 seconds
+    assert!(
+        (tracker
+            .window_uptime_percent(&node_id, Duration::from_secs(20), 1045)
+            .unwrap()
+            - 0.25)
+            .abs()
+            < f64::EPSILON
+    );
+    assert!(tracker
+        .window_uptime_percent(&node_id, Duration::from_secs(101), 1045)
+        .is_err());
+
+    // time before the tracking started counts as disconnected
+    assert!(
+        (tracker.uptime_percent_from(&node_id, 960, 1040).unwrap() - 0.25).abs() < f64::EPSILON
+    );
+
+    // old intervals are pruned, but still count towards the total uptime
+    tracker.connect(node_id, 1200);
+    tracker.disconnect(&node_id, 1250).unwrap();
+    assert_eq!(
+        tracker.uptime(&node_id, 1250).unwrap(),
+        Duration::from_secs(70)
+    );
+    assert!(
+        (tracker
+            .window_uptime_percent(&node_id, Duration::from_secs(100), 1300)
+            .unwrap()
+            - 0.5)
+            .abs()
+            < f64::EPSILON
+    );
+    assert!(tracker.uptime_percent_from(&node_id, 1100, 1300).is_err());
+
+    // out-of-order timestamps never move the clock backwards
+    tracker.connect(node_id, 1260);
+    tracker.disconnect(&node_id, 1255).unwrap();
+    assert_eq!(
+        tracker.uptime(&node_id, 1250).unwrap(),
+        Duration::from_secs(70)
+    );
+
+    assert_eq!(
+        tracker.stop_tracking(&node_id, 1300).unwrap(),
+        Duration::from_secs(70)
+    );
+    assert!(!tracker.is_tracked(&node_id));
+
+    // connecting starts tracking
+    tracker.connect(node_id, now_unix());
+    assert!(tracker.is_connected(&node_id));
+}
@@ -140,6 +140,7 @@ fn test_pack_and_unpack() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     packer
@@ -163,6 +164,7 @@ fn test_pack_and_unpack() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_ip().unwrap();
     assert_eq!(packer.get_offset(), IP_LEN);
@@ -193,6 +195,7 @@ fn test_packs_and_unpacks() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     packer
@@ -222,6 +225,7 @@ fn test_packs_and_unpacks() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_ips().unwrap();
     assert_eq!(packer.get_offset(), packer::U32_LEN + IP_LEN * 3);
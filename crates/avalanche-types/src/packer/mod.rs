@@ -1,10 +1,12 @@
 //! Low-level byte-packing utilities.
 pub mod ip;
+pub mod pool;
 
 use std::cell::Cell;
 
 use crate::errors::{Error, Result};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
 
 pub const MAX_STR_LEN: u16 = u16::MAX - 1;
 
@@ -48,6 +50,10 @@ pub struct Packer {
     header: bool,
     /// offset that is being written to in the byte array
     offset: Cell<usize>,
+    /// first error hit while packing or unpacking, returned by every later
+    /// pack or unpack until "reset"
+    /// ref. "avalanchego/utils/wrappers.Errs"
+    err: Cell<Option<Error>>,
 }
 
 impl Packer {
@@ -59,6 +65,7 @@ impl Packer {
             bytes,
             header: false,
             offset: Cell::new(0),
+            err: Cell::new(None),
         }
     }
 
@@ -74,9 +81,46 @@ impl Packer {
             bytes,
             header: true,
             offset,
+            err: Cell::new(None),
         }
     }
 
+    /// Creates a new Packer that writes to the existing buffer (e.g., from
+    /// [`pool::Pool`]), retaining its capacity but not its contents.
+    #[must_use]
+    pub fn with_buffer(max_size: usize, b: BytesMut, header: bool) -> Self {
+        let packer = Self {
+            max_size,
+            bytes: Cell::new(b),
+            header,
+            offset: Cell::new(0),
+            err: Cell::new(None),
+        };
+        packer.reset();
+        packer
+    }
+
+    /// Clears the bytes array, the offset and the recorded error, retaining
+    /// the underlying capacity, so the packer can be reused for the next message.
+    pub fn reset(&self) {
+        let mut b = self.bytes.take();
+        b.clear();
+        if self.header {
+            b.put_slice(&[0x00, 0x00, 0x00, 0x00]);
+        }
+        self.set_offset(b.len());
+        self.bytes.set(b);
+        self.err.set(None);
+    }
+
+    /// Consumes the packer and returns the underlying buffer.
+    /// The buffer is empty (with no capacity) once the bytes are taken out
+    /// with `take_bytes`.
+    #[must_use]
+    pub fn into_buffer(self) -> BytesMut {
+        self.bytes.into_inner()
+    }
+
     /// Create a new packer from the existing bytes.
     /// Resets the offset to the end of the existing bytes.
     #[must_use]
@@ -86,6 +130,7 @@ impl Packer {
             bytes: Cell::new(BytesMut::from(b)),
             header: false,
             offset: Cell::new(b.len()),
+            err: Cell::new(None),
         }
     }
 
@@ -98,6 +143,7 @@ impl Packer {
             bytes: Cell::new(BytesMut::from(b)),
             header: false,
             offset: Cell::new(0),
+            err: Cell::new(None),
         }
     }
 
@@ -200,6 +246,8 @@ impl Packer {
     ///
     /// Returns an error if the needed size exceeds the maximum size.
     pub fn expand(&self, n: usize) -> Result<()> {
+        self.check_errored()?;
+
         // total number of bytes that must be remained in the bytes array
         let needed_size = self.get_offset() + n;
 
@@ -211,13 +259,13 @@ impl Packer {
 
         // byte slice would cause it to grow too large (out of bounds)
         if needed_size > self.max_size {
-            return Err(Error::Other {
+            return Err(self.record_err(Error::Other {
                 message: format!(
                     "needed_size {} exceeds max_size {}",
                     needed_size, self.max_size
                 ),
                 retryable: false,
-            });
+            }));
         }
 
         // has sufficient capacity to lengthen it without mem alloc
@@ -239,15 +287,17 @@ impl Packer {
     /// ref. "avalanchego/utils/wrappers.Packer.CheckSpace"
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/wrappers#Packer.CheckSpace>
     fn check_remaining_unpack(&self, bytes_to_read: usize) -> Result<()> {
+        self.check_errored()?;
+
         let needed_size = self.get_offset() + bytes_to_read;
         let bytes_n = self.bytes_len();
         if needed_size > bytes_n {
-            return Err(Error::Other {
+            return Err(self.record_err(Error::Other {
                 message: format!(
                     "bad length to read; offset + bytes ({needed_size}) to read exceeds current total bytes size {bytes_n}"
                 ), // ref. "errBadLength"
                 retryable: false,
-            });
+            }));
         }
         Ok(())
     }
@@ -512,10 +562,10 @@ impl Packer {
             0 => Ok(false),
             1 => Ok(true),
             _ => {
-                Err(Error::Other {
+                Err(self.record_err(Error::Other {
                     message: "unexpected value when unpacking bool".to_string(), // ref. "errBadBool"
                     retryable: false,
-                })
+                }))
             }
         }
     }
@@ -678,10 +728,10 @@ impl Packer {
     pub fn pack_str(&self, v: &str) -> Result<()> {
         let n = u16::try_from(v.len())?;
         if n > MAX_STR_LEN {
-            return Err(Error::Other {
+            return Err(self.record_err(Error::Other {
                 message: format!("str {n} > max_size {MAX_STR_LEN}"),
                 retryable: false,
-            });
+            }));
         }
         self.pack_u16(n)?;
         self.pack_bytes(v.as_bytes())
@@ -705,14 +755,169 @@ impl Packer {
         let s = match String::from_utf8(d) {
             Ok(v) => v,
             Err(e) => {
-                return Err(Error::Other {
+                return Err(self.record_err(Error::Other {
                     message: format!("failed String::from_utf8 {e}"),
                     retryable: false,
-                });
+                }));
             }
         };
         Ok(s)
     }
+
+    /// Unpacks the two-dimensional "u8" slice with length headers.
+    /// Same as "`unpack_2d_bytes_with_header`" under the avalanchego name.
+    /// ref. "avalanchego/utils/wrappers.Packer.Unpack2DByteSlice"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to unpack the bytes or if the length header is invalid.
+    pub fn unpack_2d_byte_slices(&self) -> Result<Vec<Vec<u8>>> {
+        self.unpack_2d_bytes_with_header()
+    }
+
+    /// Writes the timestamp as the "u64" Unix seconds.
+    /// ref. "avalanchego/codec/reflectcodec" "time.Time"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is before the Unix epoch
+    /// or the buffer cannot be expanded to accommodate the u64.
+    pub fn pack_time(&self, v: &DateTime<Utc>) -> Result<()> {
+        let secs = u64::try_from(v.timestamp()).map_err(|e| {
+            self.record_err(Error::Other {
+                message: format!("timestamp {v} before Unix epoch ({e})"),
+                retryable: false,
+            })
+        })?;
+        self.pack_u64(secs)
+    }
+
+    /// Unpacks the "u64" Unix seconds from the "offset" position as a timestamp,
+    /// and advances the cursor and offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to unpack a u64
+    /// or the value is out of the timestamp range.
+    pub fn unpack_time(&self) -> Result<DateTime<Utc>> {
+        let secs = self.unpack_u64()?;
+        i64::try_from(secs)
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .ok_or_else(|| {
+                self.record_err(Error::Other {
+                    message: format!("timestamp {secs} out of range"),
+                    retryable: false,
+                })
+            })
+    }
+
+    /// Returns the "n" bytes from the "offset" position
+    /// without advancing the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to read "n" bytes.
+    pub fn peek_bytes(&self, n: usize) -> Result<Vec<u8>> {
+        self.check_remaining_unpack(n)?;
+
+        let offset = self.get_offset();
+        let b = self.bytes.take();
+        let v = Vec::from(&b[offset..offset + n]);
+
+        // remember to put it back -- "take" leaves the field as "Default::default()"
+        self.bytes.set(b);
+        Ok(v)
+    }
+
+    /// Returns the byte in the "offset" position without advancing the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to read a byte.
+    pub fn peek_byte(&self) -> Result<u8> {
+        let b = self.peek_bytes(BYTE_LEN)?;
+        Ok(b[0])
+    }
+
+    /// Returns the u16 in the "offset" position without advancing the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to read a u16.
+    pub fn peek_u16(&self) -> Result<u16> {
+        let b = self.peek_bytes(U16_LEN)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    /// Returns the u32 in the "offset" position without advancing the offset.
+    /// Useful to look up the codec type Id before unpacking the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to read a u32.
+    pub fn peek_u32(&self) -> Result<u32> {
+        let b = self.peek_bytes(U32_LEN)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Returns the u64 in the "offset" position without advancing the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data to read a u64.
+    pub fn peek_u64(&self) -> Result<u64> {
+        let b = self.peek_bytes(U64_LEN)?;
+        Ok(u64::from_be_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    }
+
+    /// Returns the number of bytes left to unpack.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.bytes_len().saturating_sub(self.get_offset())
+    }
+
+    /// Returns true if all bytes have been unpacked.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns true if any pack or unpack has failed.
+    /// ref. "avalanchego/utils/wrappers.Errs.Errored"
+    #[must_use]
+    pub fn errored(&self) -> bool {
+        let err = self.err.take();
+        let errored = err.is_some();
+        self.err.set(err);
+        errored
+    }
+
+    /// Returns the first error from pack or unpack, if any.
+    /// ref. "avalanchego/utils/wrappers.Packer.Err"
+    #[must_use]
+    pub fn err(&self) -> Option<Error> {
+        let err = self.err.take();
+        let cloned = err.clone();
+        self.err.set(err);
+        cloned
+    }
+
+    /// Returns the recorded error, so the pack or unpack after a failure
+    /// does not touch the bytes.
+    /// ref. "avalanchego/utils/wrappers.Packer.Errored"
+    fn check_errored(&self) -> Result<()> {
+        self.err().map_or(Ok(()), Err)
+    }
+
+    /// Records the error if it is the first one, and returns it.
+    fn record_err(&self, e: Error) -> Error {
+        let err = self.err.take();
+        self.err.set(Some(err.unwrap_or_else(|| e.clone())));
+        e
+    }
 }
 
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_expand` --exact --show-output
@@ -726,6 +931,7 @@ fn test_expand() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(2),
+        err: Cell::new(None),
     };
     assert!(packer.expand(1).is_err());
 
@@ -736,6 +942,7 @@ fn test_expand() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     packer.expand(1).unwrap();
     assert_eq!(packer.bytes_len(), 3);
@@ -802,6 +1009,7 @@ fn test_unpack_byte() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_byte().unwrap();
     assert_eq!(b, 1);
@@ -819,6 +1027,7 @@ fn test_pack_u16() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     packer.pack_u16(0x0102).unwrap();
     assert_eq!(packer.bytes_len(), U16_LEN);
@@ -845,6 +1054,7 @@ fn test_unpack_u16() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_u16().unwrap();
     assert_eq!(b, 0x0102);
@@ -862,6 +1072,7 @@ fn test_pack_u16_short() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     packer.pack_u16(17).unwrap();
@@ -879,6 +1090,7 @@ fn test_pack_u16_short() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_u16().unwrap();
     assert_eq!(b, 17);
@@ -899,6 +1111,7 @@ fn test_pack_u32() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     packer.pack_u32(0x0102_0304).unwrap();
     assert_eq!(packer.bytes_len(), U32_LEN);
@@ -921,6 +1134,7 @@ fn test_unpack_u32() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     assert_eq!(packer.unpack_u32().unwrap(), 0x0102_0304);
     assert_eq!(packer.get_offset(), U32_LEN);
@@ -936,6 +1150,7 @@ fn test_pack_u64() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     packer.pack_u64(0x0102_030405060708).unwrap();
     assert_eq!(packer.bytes_len(), U64_LEN);
@@ -967,6 +1182,7 @@ fn test_unpack_u64() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     assert_eq!(packer.unpack_u64().unwrap(), 0x0102_030405060708);
     assert_eq!(packer.get_offset(), U64_LEN);
@@ -983,6 +1199,7 @@ fn test_pack_bool() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     packer.pack_bool(false).unwrap();
     packer.pack_bool(true).unwrap();
@@ -1002,6 +1219,7 @@ fn test_pack_bool() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     assert!(!packer.unpack_bool().unwrap());
     assert!(packer.unpack_bool().unwrap());
@@ -1027,6 +1245,7 @@ fn test_unpack_bool() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     assert!(packer.unpack_bool().unwrap());
     assert_eq!(packer.get_offset(), BOOL_LEN);
@@ -1042,6 +1261,7 @@ fn test_pack_bytes() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     let s = "Avax";
@@ -1077,6 +1297,7 @@ fn test_unpack_bytes() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_bytes(4).unwrap();
     assert_eq!(&b[..], b"Avax");
@@ -1093,6 +1314,7 @@ fn test_pack_bytes_with_header() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     let s = "Avax";
@@ -1126,6 +1348,7 @@ fn test_unpack_bytes_with_header() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_bytes_with_header().unwrap();
     assert_eq!(&b[..], b"Avax");
@@ -1142,6 +1365,7 @@ fn test_pack_2d_bytes() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     // first 4-byte is for length
@@ -1180,6 +1404,7 @@ fn test_unpack_2d_bytes() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_2d_bytes(4).unwrap();
     assert_eq!(&b[..], vec![Vec::from(b"Avax"), Vec::from(b"Evax"),]);
@@ -1195,6 +1420,7 @@ fn test_pack_2d_bytes_with_header() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     // first 4-byte is for length
@@ -1239,6 +1465,7 @@ fn test_unpack_2d_bytes_with_header() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_2d_bytes_with_header().unwrap();
     assert_eq!(&b[..], vec![Vec::from(b"Avax"), Vec::from(b"Evax"),]);
@@ -1256,6 +1483,7 @@ fn test_pack_2d_bytes_with_header_123() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let empty_data: Vec<Vec<u8>> = vec![];
     packer.pack_2d_bytes_with_header(&empty_data).unwrap();
@@ -1268,6 +1496,7 @@ fn test_pack_2d_bytes_with_header_123() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let data = vec![vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]];
     packer.pack_2d_bytes_with_header(&data).unwrap();
@@ -1284,6 +1513,7 @@ fn test_pack_2d_bytes_with_header_123() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_2d_bytes_with_header().unwrap();
     assert_eq!(&b[..], vec![vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]]);
@@ -1295,6 +1525,7 @@ fn test_pack_2d_bytes_with_header_123() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let data = vec![
         vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
@@ -1320,6 +1551,7 @@ fn test_pack_2d_bytes_with_header_123() {
         bytes: Cell::new(b),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
     let b = packer.unpack_2d_bytes_with_header().unwrap();
     assert_eq!(
@@ -1341,6 +1573,7 @@ fn test_pack_str() {
         bytes: Cell::new(BytesMut::with_capacity(0)),
         header: false,
         offset: Cell::new(0),
+        err: Cell::new(None),
     };
 
     let s = "Avax";
@@ -1360,3 +1593,163 @@ fn test_pack_str() {
     let expected = [0x00, 0x00, 0x00, 0x06, 0x00, 0x04, 65, 118, 97, 120];
     assert_eq!(&packer.take_bytes()[..], &expected[..]);
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_peek` --exact --show-output
+#[test]
+fn test_peek() {
+    let packer =
+        Packer::load_bytes_for_unpack(0, &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]);
+    assert_eq!(packer.remaining(), 9);
+
+    assert_eq!(packer.peek_byte().unwrap(), 0x01);
+    assert_eq!(packer.peek_u16().unwrap(), 0x0102);
+    assert_eq!(packer.peek_u32().unwrap(), 0x0102_0304);
+    assert_eq!(packer.peek_u64().unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(packer.peek_bytes(3).unwrap(), vec![0x01, 0x02, 0x03]);
+    assert_eq!(packer.get_offset(), 0);
+
+    assert_eq!(packer.unpack_byte().unwrap(), 0x01);
+    assert_eq!(packer.peek_u64().unwrap(), 0x0203_0405_0607_0809);
+    assert_eq!(packer.unpack_u64().unwrap(), 0x0203_0405_0607_0809);
+    assert_eq!(packer.remaining(), 0);
+    assert!(packer.is_done());
+
+    assert!(!packer.errored());
+    assert!(packer.peek_byte().is_err());
+    assert!(packer.errored());
+    assert_eq!(packer.get_offset(), 9);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_err` --exact --show-output
+/// ref. "avalanchego/utils/wrappers.TestPackerCheckSpace"
+#[test]
+fn test_err() {
+    let packer = Packer::load_bytes_for_unpack(0, &[0x02, 0x00]);
+    assert!(!packer.errored());
+    assert!(packer.err().is_none());
+
+    // bad length
+    assert!(packer.unpack_u32().is_err());
+    assert!(packer.errored());
+    assert!(packer.err().unwrap().message().contains("bad length"));
+    assert_eq!(packer.get_offset(), 0);
+
+    // later unpacks fail with the first error, without advancing
+    assert!(packer
+        .unpack_byte()
+        .unwrap_err()
+        .message()
+        .contains("bad length"));
+    assert!(packer.peek_byte().is_err());
+    assert_eq!(packer.get_offset(), 0);
+
+    // cleared on reset
+    packer.reset();
+    assert!(!packer.errored());
+
+    // packing beyond max size
+    let packer = Packer::new(2, 0);
+    packer.pack_u16(1).unwrap();
+    assert!(!packer.errored());
+    assert!(packer.pack_byte(1).is_err());
+    assert!(packer.err().unwrap().message().contains("exceeds max_size"));
+
+    // later packs fail with the first error, without writing
+    assert!(packer
+        .pack_bytes(&[])
+        .unwrap_err()
+        .message()
+        .contains("exceeds max_size"));
+    assert_eq!(&packer.take_bytes()[..], &[0x00, 0x01]);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_pack_and_unpack_time` --exact --show-output
+#[test]
+fn test_pack_and_unpack_time() {
+    let packer = Packer::new(1024, 0);
+
+    // 2022-01-01T00:00:00Z
+    let ts = Utc.timestamp_opt(1_640_995_200, 0).unwrap();
+    packer.pack_time(&ts).unwrap();
+    let b = packer.take_bytes();
+    assert_eq!(&b[..], &[0x00, 0x00, 0x00, 0x00, 0x61, 0xcf, 0x99, 0x80]);
+
+    let packer = Packer::load_bytes_for_unpack(1024, &b);
+    assert_eq!(packer.unpack_time().unwrap(), ts);
+
+    // before epoch
+    let packer = Packer::new(1024, 0);
+    let ts = Utc.timestamp_opt(-1, 0).unwrap();
+    assert!(packer.pack_time(&ts).is_err());
+
+    // out of range
+    let packer = Packer::load_bytes_for_unpack(1024, &u64::MAX.to_be_bytes());
+    assert!(packer.unpack_time().is_err());
+    assert!(packer.errored());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_unpack_2d_byte_slices` --exact --show-output
+/// ref. "avalanchego/utils/wrappers.TestPacker2DByteSlice"
+#[test]
+fn test_unpack_2d_byte_slices() {
+    let packer = Packer::load_bytes_for_unpack(
+        1024,
+        &[
+            0, 0, 0, 2, 0, 0, 0, 10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0, 0, 0, 10, 11, 12, 3, 4, 5,
+            6, 7, 8, 9, 10,
+        ],
+    );
+    assert_eq!(
+        packer.unpack_2d_byte_slices().unwrap(),
+        vec![
+            vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            vec![11, 12, 3, 4, 5, 6, 7, 8, 9, 10],
+        ]
+    );
+    assert!(packer.is_done());
+
+    // header claims more slices than encoded
+    let packer = Packer::load_bytes_for_unpack(1024, &[0, 0, 0, 2, 0, 0, 0, 1, 1]);
+    assert!(packer.unpack_2d_byte_slices().is_err());
+    assert!(packer.errored());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::test_unpack_mixed` --exact --show-output
+/// Decodes the hand-written blob, following the layout of avalanchego's
+/// "wrappers.Packer" for "PackByte(0x01)", "PackShort(0x0203)",
+/// "PackInt(0x04050607)", "PackLong(0x08090a0b0c0d0e0f)", "PackBool(true)",
+/// "PackStr("Avax")" and "PackBytes([]byte{0xde, 0xad})".
+/// The bytes are NOT generated by avalanchego.
+#[test]
+fn test_unpack_mixed() {
+    let b: Vec<u8> = vec![
+        0x01, // byte
+        0x02, 0x03, // short
+        0x04, 0x05, 0x06, 0x07, // int
+        0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, // long
+        0x01, // bool
+        0x00, 0x04, 65, 118, 97, 120, // str
+        0x00, 0x00, 0x00, 0x02, 0xde, 0xad, // bytes
+    ];
+    let packer = Packer::load_bytes_for_unpack(0, &b);
+    assert_eq!(packer.unpack_byte().unwrap(), 0x01);
+    assert_eq!(packer.unpack_u16().unwrap(), 0x0203);
+    assert_eq!(packer.unpack_u32().unwrap(), 0x0405_0607);
+    assert_eq!(packer.unpack_u64().unwrap(), 0x0809_0a0b_0c0d_0e0f);
+    assert!(packer.unpack_bool().unwrap());
+    assert_eq!(packer.unpack_str().unwrap(), "Avax");
+    assert_eq!(packer.unpack_bytes_with_header().unwrap(), vec![0xde, 0xad]);
+    assert!(packer.is_done());
+    assert!(!packer.errored());
+
+    // round-trip
+    let packer = Packer::new(b.len(), 0);
+    packer.pack_byte(0x01).unwrap();
+    packer.pack_u16(0x0203).unwrap();
+    packer.pack_u32(0x0405_0607).unwrap();
+    packer.pack_u64(0x0809_0a0b_0c0d_0e0f).unwrap();
+    packer.pack_bool(true).unwrap();
+    packer.pack_str("Avax").unwrap();
+    packer.pack_bytes_with_header(&[0xde, 0xad]).unwrap();
+    assert_eq!(&packer.take_bytes()[..], &b[..]);
+}
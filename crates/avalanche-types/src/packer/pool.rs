@@ -0,0 +1,157 @@
+//! Pool of packer buffers to reuse across many messages (e.g., bulk tooling
+//! that builds and signs thousands of transactions).
+use std::sync::{Mutex, PoisonError};
+
+use crate::packer::Packer;
+use bytes::{Bytes, BytesMut};
+
+/// Default number of buffers kept in the shared pool.
+pub const DEFAULT_MAX_BUFFERS: usize = 64;
+
+/// Default capacity of a new buffer.
+/// ref. "constants.DefaultByteSliceCap" in Go
+pub const DEFAULT_INITIAL_CAP: usize = 128;
+
+/// Thread-safe pool of packer buffers. Buffers keep their capacity across
+/// reuses, so the packers stop growing (reallocating) once the buffers are
+/// large enough for the messages.
+#[derive(Debug)]
+pub struct Pool {
+    /// Idle buffers.
+    buffers: Mutex<Vec<BytesMut>>,
+    /// Maximum number of idle buffers to keep, the rest are dropped.
+    max_buffers: usize,
+    /// Capacity of a new buffer when the pool is empty.
+    initial_cap: usize,
+}
+
+/// Pool shared by the transaction packing and signing.
+static SHARED: Pool = Pool::new(DEFAULT_MAX_BUFFERS, DEFAULT_INITIAL_CAP);
+
+/// Returns the pool shared by the transaction packing and signing.
+#[must_use]
+pub fn shared() -> &'static Pool {
+    &SHARED
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUFFERS, DEFAULT_INITIAL_CAP)
+    }
+}
+
+impl Pool {
+    #[must_use]
+    pub const fn new(max_buffers: usize, initial_cap: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            max_buffers,
+            initial_cap,
+        }
+    }
+
+    /// Returns a packer writing to an idle buffer, or to a new buffer
+    /// if none is idle.
+    #[must_use]
+    pub fn get(&self, max_size: usize) -> Packer {
+        Packer::with_buffer(max_size, self.take_buffer(), false)
+    }
+
+    /// Same as `get` but with 32-bit message length header.
+    #[must_use]
+    pub fn get_with_header(&self, max_size: usize) -> Packer {
+        Packer::with_buffer(max_size, self.take_buffer(), true)
+    }
+
+    /// Returns the buffer of the packer to the pool.
+    pub fn put(&self, packer: Packer) {
+        self.put_buffer(packer.into_buffer());
+    }
+
+    /// Returns the buffers of the bytes taken out of the packers (with
+    /// `take_bytes`) to the pool. The bytes still shared elsewhere are
+    /// dropped instead.
+    pub fn recycle<I: IntoIterator<Item = Bytes>>(&self, bytes: I) {
+        for b in bytes {
+            if let Ok(b) = b.try_into_mut() {
+                self.put_buffer(b);
+            }
+        }
+    }
+
+    /// Returns the number of idle buffers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Takes an idle buffer out of the pool, or creates a new one.
+    fn take_buffer(&self) -> BytesMut {
+        self.lock()
+            .pop()
+            .unwrap_or_else(|| BytesMut::with_capacity(self.initial_cap))
+    }
+
+    /// Keeps the buffer for reuse unless the pool is full or the buffer
+    /// has no capacity left.
+    fn put_buffer(&self, mut b: BytesMut) {
+        if b.capacity() == 0 {
+            return;
+        }
+        b.clear();
+
+        let mut buffers = self.lock();
+        if buffers.len() < self.max_buffers {
+            buffers.push(b);
+        }
+    }
+
+    /// Locks the idle buffers. The buffers are always cleared before reuse,
+    /// so a poisoned lock is safe to recover.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<BytesMut>> {
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `packer::pool::test_pool` --exact --show-output
+#[test]
+fn test_pool() {
+    let pool = Pool::new(1, 0);
+    assert!(pool.is_empty());
+
+    let packer = pool.get(1024);
+    packer.pack_u32(0x0102_0304).unwrap();
+    assert_eq!(&packer.take_bytes()[..], &[0x01, 0x02, 0x03, 0x04]);
+
+    // reuses the buffer, with its capacity but not its contents
+    packer.pack_u64(1).unwrap();
+    pool.put(packer);
+    assert_eq!(pool.len(), 1);
+    let packer = pool.get(1024);
+    assert!(pool.is_empty());
+    assert_eq!(packer.get_offset(), 0);
+    assert!(packer.bytes_cap() >= 8);
+    packer.pack_u16(0x0506).unwrap();
+    let b = packer.take_bytes();
+    assert_eq!(&b[..], &[0x05, 0x06]);
+
+    // the taken bytes are recycled once no longer shared
+    let shared = b.clone();
+    pool.recycle([b]);
+    assert!(pool.is_empty());
+    pool.recycle([shared]);
+    assert_eq!(pool.len(), 1);
+
+    // keeps up to the max number of buffers
+    pool.put(Packer::new(1024, 16));
+    assert_eq!(pool.len(), 1);
+
+    let packer = pool.get_with_header(1024);
+    packer.pack_byte(0x07).unwrap();
+    assert_eq!(&packer.take_bytes()[..], &[0x00, 0x00, 0x00, 0x01, 0x07]);
+}
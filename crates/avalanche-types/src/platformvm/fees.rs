@@ -0,0 +1,581 @@
+//! Dynamic fees of the P-chain.
+//!
+//! Each transaction has a multi-dimensional "complexity" (bandwidth, database
+//! reads, database writes, and compute), which is weighted into "gas" and
+//! multiplied by the current gas price to compute the fee to burn.
+//!
+//! ref. <https://github.com/avalanche-foundation/ACPs/tree/main/ACPs/103-dynamic-fees>
+//! ref. <https://github.com/ava-labs/avalanchego/tree/v1.12.0/vms/platformvm/txs/fee>
+use std::ops::Add;
+
+use crate::{
+    errors::{Error, Result},
+    key, txs,
+};
+use serde::{Deserialize, Serialize};
+
+/// Size of a "u32" in bytes (e.g., type IDs, lengths).
+const INT_LEN: u64 = 4;
+/// Size of a "u64" in bytes (e.g., amounts, locktimes).
+const LONG_LEN: u64 = 8;
+/// Size of an "ids::Id" in bytes.
+const ID_LEN: u64 = 32;
+/// Size of an "ids::short::Id" in bytes.
+const SHORT_ID_LEN: u64 = 20;
+/// Size of a recoverable secp256k1 signature in bytes.
+const SIGNATURE_LEN: u64 = 65;
+/// Size of a compressed BLS public key in bytes.
+const BLS_PUBLIC_KEY_LEN: u64 = 48;
+/// Size of a BLS signature in bytes.
+const BLS_SIGNATURE_LEN: u64 = 96;
+
+/// Bandwidth of the fields shared by all transactions:
+/// codec version, type ID, network ID, blockchain ID,
+/// number of outputs, number of inputs, memo length, and number of credentials.
+const BASE_TX_BANDWIDTH: u64 =
+    2 + INT_LEN + INT_LEN + ID_LEN + INT_LEN + INT_LEN + INT_LEN + INT_LEN;
+/// Bandwidth of a "platformvm::txs::Validator" (node ID, start, end, weight).
+const VALIDATOR_BANDWIDTH: u64 = SHORT_ID_LEN + LONG_LEN + LONG_LEN + LONG_LEN;
+
+/// Bandwidth of "`sig_indices`" and signatures length prefixes.
+const SECP256K1_INPUT_BANDWIDTH: u64 = INT_LEN + INT_LEN;
+/// Bandwidth of each signature including its index.
+const SECP256K1_SIGNATURE_BANDWIDTH: u64 = INT_LEN + SIGNATURE_LEN;
+/// Compute cost of recovering each signature.
+const SECP256K1_SIGNATURE_COMPUTE: u64 = 200;
+/// Compute cost of verifying a BLS proof of possession.
+const BLS_POP_VERIFY_COMPUTE: u64 = 1_050;
+
+/// Index of the bandwidth dimension (bytes of the transaction).
+pub const BANDWIDTH: usize = 0;
+/// Index of the database reads dimension.
+pub const DB_READ: usize = 1;
+/// Index of the database writes dimension.
+pub const DB_WRITE: usize = 2;
+/// Index of the compute dimension (e.g., signature verification).
+pub const COMPUTE: usize = 3;
+
+/// Number of complexity dimensions.
+pub const NUM_DIMENSIONS: usize = 4;
+
+/// Represents the complexity of a transaction (or the weights of each dimension),
+/// in the order of bandwidth, database reads, database writes, and compute.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/gas#Dimensions>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Default)]
+#[serde(from = "[u64; NUM_DIMENSIONS]", into = "[u64; NUM_DIMENSIONS]")]
+pub struct Dimensions(pub [u64; NUM_DIMENSIONS]);
+
+impl From<[u64; NUM_DIMENSIONS]> for Dimensions {
+    fn from(d: [u64; NUM_DIMENSIONS]) -> Self {
+        Self(d)
+    }
+}
+
+impl From<Dimensions> for [u64; NUM_DIMENSIONS] {
+    fn from(d: Dimensions) -> Self {
+        d.0
+    }
+}
+
+/// Adds each dimension, saturating at "`u64::MAX`".
+/// Use "`checked_add`" to detect overflows.
+impl Add for Dimensions {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let mut d = self.0;
+        for (i, v) in d.iter_mut().enumerate() {
+            *v = v.saturating_add(rhs.0[i]);
+        }
+        Self(d)
+    }
+}
+
+impl Dimensions {
+    #[must_use]
+    pub const fn new(bandwidth: u64, db_read: u64, db_write: u64, compute: u64) -> Self {
+        Self([bandwidth, db_read, db_write, compute])
+    }
+
+    #[must_use]
+    pub const fn bandwidth(&self) -> u64 {
+        self.0[BANDWIDTH]
+    }
+
+    #[must_use]
+    pub const fn db_read(&self) -> u64 {
+        self.0[DB_READ]
+    }
+
+    #[must_use]
+    pub const fn db_write(&self) -> u64 {
+        self.0[DB_WRITE]
+    }
+
+    #[must_use]
+    pub const fn compute(&self) -> u64 {
+        self.0[COMPUTE]
+    }
+
+    /// Adds each dimension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any dimension overflows.
+    pub fn checked_add(&self, rhs: &Self) -> Result<Self> {
+        let mut d = self.0;
+        for (i, v) in d.iter_mut().enumerate() {
+            *v = v
+                .checked_add(rhs.0[i])
+                .ok_or_else(|| overflow("complexity"))?;
+        }
+        Ok(Self(d))
+    }
+
+    /// Converts the complexity to gas, by taking the dot product with the weights.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the gas overflows.
+    pub fn to_gas(&self, weights: &Self) -> Result<u64> {
+        let mut gas = 0_u64;
+        for (v, w) in self.0.iter().zip(weights.0.iter()) {
+            gas = v
+                .checked_mul(*w)
+                .and_then(|g| gas.checked_add(g))
+                .ok_or_else(|| overflow("gas"))?;
+        }
+        Ok(gas)
+    }
+}
+
+/// Intrinsic complexity of "`platformvm::txs::import::Tx`"
+/// (source chain ID and number of imported inputs).
+pub const IMPORT_TX_COMPLEXITY: Dimensions =
+    Dimensions::new(BASE_TX_BANDWIDTH + ID_LEN + INT_LEN, 0, 0, 0);
+
+/// Intrinsic complexity of "`platformvm::txs::export::Tx`"
+/// (destination chain ID and number of exported outputs).
+pub const EXPORT_TX_COMPLEXITY: Dimensions =
+    Dimensions::new(BASE_TX_BANDWIDTH + ID_LEN + INT_LEN, 0, 0, 0);
+
+/// Intrinsic complexity of "`platformvm::txs::create_subnet::Tx`"
+/// (owner type ID).
+pub const CREATE_SUBNET_TX_COMPLEXITY: Dimensions =
+    Dimensions::new(BASE_TX_BANDWIDTH + INT_LEN, 0, 1, 0);
+
+/// Intrinsic complexity of "`platformvm::txs::create_chain::Tx`"
+/// (subnet ID, chain name length, VM ID, number of Fx IDs,
+/// genesis length, subnet auth type ID, and subnet auth credential type ID).
+pub const CREATE_CHAIN_TX_COMPLEXITY: Dimensions = Dimensions::new(
+    BASE_TX_BANDWIDTH + ID_LEN + 2 + ID_LEN + INT_LEN + INT_LEN + INT_LEN + INT_LEN,
+    1,
+    1,
+    0,
+);
+
+/// Intrinsic complexity of "`platformvm::txs::add_subnet_validator::Tx`"
+/// (validator, subnet ID, subnet auth type ID, and subnet auth credential type ID).
+pub const ADD_SUBNET_VALIDATOR_TX_COMPLEXITY: Dimensions = Dimensions::new(
+    BASE_TX_BANDWIDTH + VALIDATOR_BANDWIDTH + ID_LEN + INT_LEN + INT_LEN,
+    2,
+    1,
+    0,
+);
+
+/// Intrinsic complexity of "`platformvm::txs::remove_subnet_validator::Tx`"
+/// (node ID, subnet ID, subnet auth type ID, and subnet auth credential type ID).
+pub const REMOVE_SUBNET_VALIDATOR_TX_COMPLEXITY: Dimensions = Dimensions::new(
+    BASE_TX_BANDWIDTH + SHORT_ID_LEN + ID_LEN + INT_LEN + INT_LEN,
+    2,
+    1,
+    0,
+);
+
+/// Intrinsic complexity of "`platformvm::txs::add_permissionless_validator::Tx`"
+/// (validator, subnet ID, signer type ID, number of stake outputs,
+/// validator/delegator rewards owner type IDs, and delegation shares).
+pub const ADD_PERMISSIONLESS_VALIDATOR_TX_COMPLEXITY: Dimensions = Dimensions::new(
+    BASE_TX_BANDWIDTH
+        + VALIDATOR_BANDWIDTH
+        + ID_LEN
+        + INT_LEN
+        + INT_LEN
+        + INT_LEN
+        + INT_LEN
+        + INT_LEN,
+    1,
+    1,
+    0,
+);
+
+/// Intrinsic complexity of "AddPermissionlessDelegatorTx"
+/// (validator, subnet ID, number of stake outputs, and rewards owner type ID).
+pub const ADD_PERMISSIONLESS_DELEGATOR_TX_COMPLEXITY: Dimensions = Dimensions::new(
+    BASE_TX_BANDWIDTH + VALIDATOR_BANDWIDTH + ID_LEN + INT_LEN + INT_LEN,
+    1,
+    1,
+    0,
+);
+
+/// Returns the complexity of the transferable outputs.
+///
+/// # Errors
+///
+/// Returns an error if the complexity overflows.
+pub fn outputs_complexity(outs: &[txs::transferable::Output]) -> Result<Dimensions> {
+    let mut complexity = Dimensions::default();
+    for out in outs {
+        complexity = complexity.checked_add(&output_complexity(out)?)?;
+    }
+    Ok(complexity)
+}
+
+/// Returns the complexity of a transferable output
+/// (asset ID, type ID, amount, owners, and stakeable locktime if any).
+///
+/// # Errors
+///
+/// Returns an error if the output has neither transfer nor stakeable lock output.
+pub fn output_complexity(out: &txs::transferable::Output) -> Result<Dimensions> {
+    let (locked, owners) = if let Some(o) = &out.transfer_output {
+        (false, &o.output_owners)
+    } else if let Some(o) = &out.stakeable_lock_out {
+        (true, &o.transfer_output.output_owners)
+    } else {
+        return Err(Error::UnexpectedNone("transferable::Output".to_string()));
+    };
+
+    let mut bandwidth = ID_LEN + INT_LEN + LONG_LEN + owners_bandwidth(owners);
+    if locked {
+        bandwidth += INT_LEN + LONG_LEN;
+    }
+    Ok(Dimensions::new(bandwidth, 0, 1, 0))
+}
+
+/// Returns the complexity of the transferable inputs.
+///
+/// # Errors
+///
+/// Returns an error if the complexity overflows.
+pub fn inputs_complexity(ins: &[txs::transferable::Input]) -> Result<Dimensions> {
+    let mut complexity = Dimensions::default();
+    for input in ins {
+        complexity = complexity.checked_add(&input_complexity(input)?)?;
+    }
+    Ok(complexity)
+}
+
+/// Returns the complexity of a transferable input
+/// (UTXO ID, asset ID, input and credential type IDs, amount, signatures,
+/// and stakeable locktime if any).
+/// Each input reads and deletes its UTXO.
+///
+/// # Errors
+///
+/// Returns an error if the input has neither transfer nor stakeable lock input.
+pub fn input_complexity(input: &txs::transferable::Input) -> Result<Dimensions> {
+    let (locked, sigs) = if let Some(i) = &input.transfer_input {
+        (false, i.sig_indices.len() as u64)
+    } else if let Some(i) = &input.stakeable_lock_in {
+        (true, i.transfer_input.sig_indices.len() as u64)
+    } else {
+        return Err(Error::UnexpectedNone("transferable::Input".to_string()));
+    };
+
+    let mut bandwidth = ID_LEN
+        + INT_LEN
+        + ID_LEN
+        + INT_LEN
+        + INT_LEN
+        + LONG_LEN
+        + SECP256K1_INPUT_BANDWIDTH
+        + sigs * SECP256K1_SIGNATURE_BANDWIDTH;
+    if locked {
+        bandwidth += INT_LEN + LONG_LEN;
+    }
+    Ok(Dimensions::new(
+        bandwidth,
+        1,
+        1,
+        sigs * SECP256K1_SIGNATURE_COMPUTE,
+    ))
+}
+
+/// Returns the complexity of the output owners (e.g., subnet owners, rewards owners).
+#[must_use]
+pub fn owners_complexity(owners: &key::secp256k1::txs::OutputOwners) -> Dimensions {
+    Dimensions::new(owners_bandwidth(owners), 0, 0, 0)
+}
+
+/// Returns the complexity of the subnet authorization and its credential.
+#[must_use]
+pub fn auth_complexity(auth: &key::secp256k1::txs::Input) -> Dimensions {
+    let sigs = auth.sig_indices.len() as u64;
+    Dimensions::new(
+        SECP256K1_INPUT_BANDWIDTH + sigs * SECP256K1_SIGNATURE_BANDWIDTH,
+        0,
+        0,
+        sigs * SECP256K1_SIGNATURE_COMPUTE,
+    )
+}
+
+/// Returns the complexity of the BLS proof of possession.
+#[must_use]
+pub const fn signer_complexity() -> Dimensions {
+    Dimensions::new(
+        BLS_PUBLIC_KEY_LEN + BLS_SIGNATURE_LEN,
+        0,
+        0,
+        BLS_POP_VERIFY_COMPUTE,
+    )
+}
+
+/// Returns the complexity of the variable-length bytes (e.g., memo, genesis data).
+#[must_use]
+pub const fn bytes_complexity(b: &[u8]) -> Dimensions {
+    Dimensions::new(b.len() as u64, 0, 0, 0)
+}
+
+/// Locktime, threshold, number of addresses, and addresses.
+fn owners_bandwidth(owners: &key::secp256k1::txs::OutputOwners) -> u64 {
+    LONG_LEN + INT_LEN + INT_LEN + owners.addresses.len() as u64 * SHORT_ID_LEN
+}
+
+/// Returns the overflow error of the named value.
+fn overflow(name: &str) -> Error {
+    Error::Other {
+        message: format!("{name} overflow"),
+        retryable: false,
+    }
+}
+
+/// Represents the dynamic fee configuration from "platform.getFeeConfig".
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/gas#Config>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Weights to convert each complexity dimension to gas.
+    pub weights: Dimensions,
+    /// Maximum amount of gas the chain can consume at once.
+    pub max_capacity: u64,
+    /// Amount of gas added to the capacity per second.
+    pub max_per_second: u64,
+    /// Target amount of gas consumed per second.
+    pub target_per_second: u64,
+    /// Minimum price per unit of gas.
+    pub min_price: u64,
+    /// Constant to convert the excess gas into the gas price.
+    pub excess_conversion_constant: u64,
+}
+
+/// Defaults to the mainnet configuration.
+/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.12.0/genesis/genesis_mainnet.go>
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            weights: Dimensions::new(1, 1_000, 1_000, 4),
+            max_capacity: 1_000_000,
+            max_per_second: 100_000,
+            target_per_second: 50_000,
+            min_price: 1,
+            excess_conversion_constant: 2_164_043,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the gas price given the current excess gas.
+    #[must_use]
+    pub fn gas_price(&self, excess: u64) -> u64 {
+        gas_price(self.min_price, excess, self.excess_conversion_constant)
+    }
+
+    /// Returns the fee calculator at the given gas price.
+    #[must_use]
+    pub const fn calculator(&self, price: u64) -> Calculator {
+        Calculator {
+            weights: self.weights,
+            price,
+        }
+    }
+}
+
+/// Approximates "`min_price` * e^(excess / `conversion_constant`)"
+/// with the Taylor expansion used in EIP-4844, saturating at "`u64::MAX`".
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/gas#CalculatePrice>
+/// ref. <https://eips.ethereum.org/EIPS/eip-4844#helpers>
+#[must_use]
+pub fn gas_price(min_price: u64, excess: u64, conversion_constant: u64) -> u64 {
+    if conversion_constant == 0 {
+        return u64::MAX;
+    }
+
+    let factor = u128::from(min_price);
+    let numerator = u128::from(excess);
+    let denominator = u128::from(conversion_constant);
+
+    let mut output = 0_u128;
+    let mut accumulator = factor * denominator;
+    let mut i = 1_u128;
+    while accumulator > 0 {
+        output = match output.checked_add(accumulator) {
+            Some(v) => v,
+            None => return u64::MAX,
+        };
+        accumulator = match accumulator.checked_mul(numerator) {
+            Some(v) => v / (denominator * i),
+            None => return u64::MAX,
+        };
+        i += 1;
+    }
+    u64::try_from(output / denominator).unwrap_or(u64::MAX)
+}
+
+/// Computes the fee of the transaction complexity.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Calculator {
+    pub weights: Dimensions,
+    pub price: u64,
+}
+
+impl Calculator {
+    /// Returns the fee to burn for the complexity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fee overflows.
+    pub fn fee(&self, complexity: &Dimensions) -> Result<u64> {
+        complexity
+            .to_gas(&self.weights)?
+            .checked_mul(self.price)
+            .ok_or_else(|| overflow("fee"))
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::fees::test_gas_price` --exact --show-output
+#[test]
+fn test_gas_price() {
+    let cfg = Config::default();
+
+    // no excess gas charges the minimum price
+    assert_eq!(cfg.gas_price(0), 1);
+    assert_eq!(gas_price(25, 0, 2_164_043), 25);
+
+    // price grows exponentially with the excess
+    assert_eq!(gas_price(1, 2_164_043, 2_164_043), 2); // e^1 ~= 2.718
+    assert_eq!(gas_price(1_000_000, 2_164_043, 2_164_043), 2_718_281);
+    assert!(gas_price(1, 10 * 2_164_043, 2_164_043) >= 22_026); // e^10
+
+    // saturates instead of overflowing
+    assert_eq!(gas_price(u64::MAX, u64::MAX, 1), u64::MAX);
+    assert_eq!(gas_price(1, 1, 0), u64::MAX);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::fees::test_complexity` --exact --show-output
+#[test]
+fn test_complexity() {
+    use crate::{ids, platformvm};
+
+    assert_eq!(BASE_TX_BANDWIDTH, 58);
+    assert_eq!(IMPORT_TX_COMPLEXITY, Dimensions::new(94, 0, 0, 0));
+    assert_eq!(CREATE_CHAIN_TX_COMPLEXITY, Dimensions::new(140, 1, 1, 0));
+    assert_eq!(
+        ADD_PERMISSIONLESS_VALIDATOR_TX_COMPLEXITY,
+        Dimensions::new(154, 1, 1, 0)
+    );
+    assert_eq!(
+        ADD_SUBNET_VALIDATOR_TX_COMPLEXITY,
+        Dimensions::new(142, 2, 1, 0)
+    );
+    assert_eq!(
+        REMOVE_SUBNET_VALIDATOR_TX_COMPLEXITY,
+        Dimensions::new(118, 2, 1, 0)
+    );
+
+    let owners = key::secp256k1::txs::OutputOwners {
+        locktime: 0,
+        threshold: 1,
+        addresses: vec![ids::short::Id::empty()],
+    };
+    let out = txs::transferable::Output {
+        transfer_output: Some(key::secp256k1::txs::transfer::Output {
+            amount: 1,
+            output_owners: owners.clone(),
+        }),
+        ..txs::transferable::Output::default()
+    };
+    assert_eq!(
+        output_complexity(&out).unwrap(),
+        Dimensions::new(80, 0, 1, 0)
+    );
+
+    let locked_out = txs::transferable::Output {
+        stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
+            locktime: 1,
+            transfer_output: key::secp256k1::txs::transfer::Output {
+                amount: 1,
+                output_owners: owners.clone(),
+            },
+        }),
+        ..txs::transferable::Output::default()
+    };
+    assert_eq!(
+        outputs_complexity(&[out, locked_out]).unwrap(),
+        Dimensions::new(80 + 92, 0, 2, 0)
+    );
+    assert!(output_complexity(&txs::transferable::Output::default()).is_err());
+
+    let input = txs::transferable::Input {
+        transfer_input: Some(key::secp256k1::txs::transfer::Input {
+            amount: 1,
+            sig_indices: vec![0],
+        }),
+        ..txs::transferable::Input::default()
+    };
+    assert_eq!(
+        inputs_complexity(&[input]).unwrap(),
+        Dimensions::new(161, 1, 1, 200)
+    );
+    assert!(input_complexity(&txs::transferable::Input::default()).is_err());
+
+    assert_eq!(owners_complexity(&owners), Dimensions::new(36, 0, 0, 0));
+    assert_eq!(
+        auth_complexity(&key::secp256k1::txs::Input {
+            sig_indices: vec![0, 1]
+        }),
+        Dimensions::new(146, 0, 0, 400)
+    );
+    assert_eq!(signer_complexity(), Dimensions::new(144, 0, 0, 1_050));
+    assert_eq!(bytes_complexity(b"hello"), Dimensions::new(5, 0, 0, 0));
+
+    assert_eq!(
+        Dimensions::new(1, 2, 3, 4) + Dimensions::new(u64::MAX, 1, 1, 1),
+        Dimensions::new(u64::MAX, 3, 4, 5)
+    );
+    assert!(Dimensions::new(u64::MAX, 0, 0, 0)
+        .checked_add(&Dimensions::new(1, 0, 0, 0))
+        .is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::fees::test_calculator` --exact --show-output
+#[test]
+fn test_calculator() {
+    let cfg: Config = serde_json::from_str(
+        "{\"weights\":[1,1000,1000,4],\"maxCapacity\":1000000,\"maxPerSecond\":100000,\"targetPerSecond\":50000,\"minPrice\":1,\"excessConversionConstant\":2164043}",
+    )
+    .unwrap();
+    assert_eq!(cfg, Config::default());
+
+    // base tx with a single input and output
+    let complexity = Dimensions::new(58, 0, 0, 0)
+        + Dimensions::new(161, 1, 1, 200)
+        + Dimensions::new(80, 0, 1, 0);
+    assert_eq!(complexity.to_gas(&cfg.weights).unwrap(), 299 + 3_000 + 800);
+
+    let calc = cfg.calculator(25);
+    assert_eq!(calc.fee(&complexity).unwrap(), 4_099 * 25);
+
+    let calc = cfg.calculator(u64::MAX);
+    assert!(calc.fee(&complexity).is_err());
+}
@@ -0,0 +1,458 @@
+//! Parses the P-chain genesis bytes back into the typed allocations and
+//! the initial stakers, to verify a generated (e.g., custom network) genesis.
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/genesis#Genesis>
+use std::collections::BTreeMap;
+
+use crate::{
+    codec,
+    errors::{Error, Result},
+    ids::{self, node, short},
+    key,
+    packer::Packer,
+};
+
+/// Represents the decoded "platformvm/genesis.Genesis".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Genesis {
+    pub utxos: Vec<Utxo>,
+    pub validators: Vec<Validator>,
+    pub chains: Vec<Chain>,
+    pub timestamp: u64,
+    pub initial_supply: u64,
+    pub message: String,
+}
+
+/// Represents the genesis UTXO allocated to the address.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/genesis#UTXO>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Utxo {
+    pub tx_id: ids::Id,
+    pub output_index: u32,
+    pub asset_id: ids::Id,
+    /// Non-zero if the output is "platformvm.StakeableLockOut".
+    pub stakeable_locktime: u64,
+    pub output: key::secp256k1::txs::transfer::Output,
+    pub message: Vec<u8>,
+}
+
+/// Represents the initial staker from the genesis "`AddValidatorTx`"
+/// (or "`AddPermissionlessValidatorTx`").
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Validator {
+    pub node_id: node::Id,
+    pub start: u64,
+    pub end: u64,
+    pub weight: u64,
+    pub stake_outputs: Vec<Utxo>,
+    pub rewards_owner: key::secp256k1::txs::OutputOwners,
+    /// In the units of "`reward::PERCENT_DENOMINATOR`".
+    pub delegation_shares: u32,
+    /// BLS public key from the proof of possession, if any.
+    pub bls_public_key: Option<Vec<u8>>,
+}
+
+/// Represents the genesis "`CreateChainTx`".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Chain {
+    pub subnet_id: ids::Id,
+    pub name: String,
+    pub vm_id: ids::Id,
+    pub fx_ids: Vec<ids::Id>,
+    pub genesis_data: Vec<u8>,
+}
+
+/// Represents the genesis balances of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Balance {
+    pub unlocked: u64,
+    /// Locked with "`StakeableLockOut`" (e.g., vesting allocations).
+    pub locked: u64,
+}
+
+impl Genesis {
+    /// Decodes the P-chain genesis bytes (e.g., "genesis.Bytes").
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid genesis.
+    pub fn from_bytes(d: &[u8]) -> Result<Self> {
+        let packer = Packer::load_bytes_for_unpack(d.len() + 1024, d);
+
+        codec::p_registry()?.unpack_version(&packer)?;
+
+        let n = packer.unpack_u32()?;
+        let mut utxos = Vec::new();
+        for _ in 0..n {
+            let tx_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+            let output_index = packer.unpack_u32()?;
+            let mut utxo = unpack_output(&packer)?;
+            utxo.tx_id = tx_id;
+            utxo.output_index = output_index;
+            utxo.message = packer.unpack_bytes_with_header()?;
+            utxos.push(utxo);
+        }
+
+        let n = packer.unpack_u32()?;
+        let mut validators = Vec::new();
+        for _ in 0..n {
+            validators.push(unpack_validator_tx(&packer)?);
+        }
+
+        let n = packer.unpack_u32()?;
+        let mut chains = Vec::new();
+        for _ in 0..n {
+            chains.push(unpack_chain_tx(&packer)?);
+        }
+
+        Ok(Self {
+            utxos,
+            validators,
+            chains,
+            timestamp: packer.unpack_u64()?,
+            initial_supply: packer.unpack_u64()?,
+            message: packer.unpack_str()?,
+        })
+    }
+
+    /// Returns the genesis balances by the owner addresses,
+    /// including the stake of the initial validators.
+    /// Multisig outputs are counted for every owner.
+    #[must_use]
+    pub fn allocations(&self) -> BTreeMap<short::Id, Balance> {
+        let mut allocations: BTreeMap<short::Id, Balance> = BTreeMap::new();
+        let stakes = self.validators.iter().flat_map(|v| &v.stake_outputs);
+        for utxo in self.utxos.iter().chain(stakes) {
+            for addr in &utxo.output.output_owners.addresses {
+                let balance = allocations.entry(addr.clone()).or_default();
+                if utxo.stakeable_locktime > 0 {
+                    balance.locked = balance.locked.saturating_add(utxo.output.amount);
+                } else {
+                    balance.unlocked = balance.unlocked.saturating_add(utxo.output.amount);
+                }
+            }
+        }
+        allocations
+    }
+
+    /// Returns the total stake weight of the initial validators.
+    #[must_use]
+    pub fn total_weight(&self) -> u64 {
+        self.validators
+            .iter()
+            .fold(0, |total, v| total.saturating_add(v.weight))
+    }
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput>
+fn unpack_output(packer: &Packer) -> Result<Utxo> {
+    let asset_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+
+    let mut type_id = packer.unpack_u32()?;
+    let mut stakeable_locktime = 0;
+    // "platformvm.StakeableLockOut" wraps "secp256k1fx.TransferOutput"
+    if type_id == 22 {
+        stakeable_locktime = packer.unpack_u64()?;
+        type_id = packer.unpack_u32()?;
+    }
+    if type_id != 7 {
+        return Err(Error::Other {
+            message: format!("unsupported type ID for output {type_id}"),
+            retryable: false,
+        });
+    }
+    let amount = packer.unpack_u64()?;
+    let output_owners = unpack_output_owners(packer)?;
+
+    Ok(Utxo {
+        asset_id,
+        stakeable_locktime,
+        output: key::secp256k1::txs::transfer::Output::new(amount, output_owners),
+        ..Default::default()
+    })
+}
+
+fn unpack_outputs(packer: &Packer) -> Result<Vec<Utxo>> {
+    let n = packer.unpack_u32()?;
+    let mut outputs = Vec::new();
+    for _ in 0..n {
+        outputs.push(unpack_output(packer)?);
+    }
+    Ok(outputs)
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners>
+fn unpack_output_owners(packer: &Packer) -> Result<key::secp256k1::txs::OutputOwners> {
+    let locktime = packer.unpack_u64()?;
+    let threshold = packer.unpack_u32()?;
+    let n = packer.unpack_u32()?;
+    let mut addresses = Vec::new();
+    for _ in 0..n {
+        addresses.push(short::Id::from_slice(&packer.unpack_bytes(short::LEN)?));
+    }
+    Ok(key::secp256k1::txs::OutputOwners {
+        locktime,
+        threshold,
+        addresses,
+    })
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/fx#Owner>
+fn unpack_owner(packer: &Packer) -> Result<key::secp256k1::txs::OutputOwners> {
+    expect_type_id(packer, 11, "rewards owner")?;
+    unpack_output_owners(packer)
+}
+
+fn unpack_sig_indices(packer: &Packer) -> Result<()> {
+    let n = packer.unpack_u32()?;
+    for _ in 0..n {
+        packer.unpack_u32()?;
+    }
+    Ok(())
+}
+
+/// Skips the "platformvm.BaseTx" fields, only with the outputs and inputs
+/// of "secp256k1fx" (and "platformvm.Stakeable*").
+fn skip_base_tx(packer: &Packer) -> Result<()> {
+    let _network_id = packer.unpack_u32()?;
+    let _blockchain_id = packer.unpack_bytes(ids::LEN)?;
+    unpack_outputs(packer)?;
+
+    let n = packer.unpack_u32()?;
+    for _ in 0..n {
+        let _utxo_id = packer.unpack_bytes(ids::LEN + 4)?;
+        let _asset_id = packer.unpack_bytes(ids::LEN)?;
+        let mut type_id = packer.unpack_u32()?;
+        // "platformvm.StakeableLockIn" wraps "secp256k1fx.TransferInput"
+        if type_id == 21 {
+            let _locktime = packer.unpack_u64()?;
+            type_id = packer.unpack_u32()?;
+        }
+        if type_id != 5 {
+            return Err(Error::Other {
+                message: format!("unsupported type ID for input {type_id}"),
+                retryable: false,
+            });
+        }
+        let _amount = packer.unpack_u64()?;
+        unpack_sig_indices(packer)?;
+    }
+
+    let _memo = packer.unpack_bytes_with_header()?;
+    Ok(())
+}
+
+/// Skips the "secp256k1fx.Credential"s at the end of "txs.Tx".
+fn skip_credentials(packer: &Packer) -> Result<()> {
+    let n = packer.unpack_u32()?;
+    for _ in 0..n {
+        expect_type_id(packer, 9, "credential")?;
+        let sigs = packer.unpack_u32()?;
+        for _ in 0..sigs {
+            packer.unpack_bytes(65)?;
+        }
+    }
+    Ok(())
+}
+
+fn expect_type_id(packer: &Packer, expected: u32, name: &str) -> Result<()> {
+    let type_id = packer.unpack_u32()?;
+    if type_id != expected {
+        return Err(Error::Other {
+            message: format!("unexpected type ID for {name} {type_id} (expected {expected})"),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#AddValidatorTx>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#AddPermissionlessValidatorTx>
+fn unpack_validator_tx(packer: &Packer) -> Result<Validator> {
+    let type_id = packer.unpack_u32()?;
+    if type_id != 12 && type_id != 25 {
+        return Err(Error::Other {
+            message: format!("unsupported type ID for genesis validator tx {type_id}"),
+            retryable: false,
+        });
+    }
+    skip_base_tx(packer)?;
+
+    let node_id = node::Id::from_slice(&packer.unpack_bytes(node::LEN)?);
+    let start = packer.unpack_u64()?;
+    let end = packer.unpack_u64()?;
+    let weight = packer.unpack_u64()?;
+
+    let mut bls_public_key = None;
+    if type_id == 25 {
+        let subnet_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+        if subnet_id != ids::Id::empty() {
+            return Err(Error::Other {
+                message: format!("unexpected subnet {subnet_id} for genesis validator"),
+                retryable: false,
+            });
+        }
+        match packer.unpack_u32()? {
+            // "signer.Empty"
+            27 => {}
+            // "signer.ProofOfPossession"
+            28 => {
+                bls_public_key = Some(packer.unpack_bytes(48)?);
+                let _proof_of_possession = packer.unpack_bytes(96)?;
+            }
+            signer => {
+                return Err(Error::Other {
+                    message: format!("unsupported type ID for signer {signer}"),
+                    retryable: false,
+                })
+            }
+        }
+    }
+
+    let stake_outputs = unpack_outputs(packer)?;
+    let rewards_owner = unpack_owner(packer)?;
+    if type_id == 25 {
+        let _delegator_rewards_owner = unpack_owner(packer)?;
+    }
+    let delegation_shares = packer.unpack_u32()?;
+    skip_credentials(packer)?;
+
+    Ok(Validator {
+        node_id,
+        start,
+        end,
+        weight,
+        stake_outputs,
+        rewards_owner,
+        delegation_shares,
+        bls_public_key,
+    })
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#CreateChainTx>
+fn unpack_chain_tx(packer: &Packer) -> Result<Chain> {
+    expect_type_id(packer, 15, "genesis chain tx")?;
+    skip_base_tx(packer)?;
+
+    let subnet_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+    let name = packer.unpack_str()?;
+    let vm_id = ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?);
+    let n = packer.unpack_u32()?;
+    let mut fx_ids = Vec::new();
+    for _ in 0..n {
+        fx_ids.push(ids::Id::from_slice(&packer.unpack_bytes(ids::LEN)?));
+    }
+    let genesis_data = packer.unpack_bytes_with_header()?;
+
+    // "secp256k1fx.Input" for the subnet auth
+    expect_type_id(packer, 10, "subnet auth")?;
+    unpack_sig_indices(packer)?;
+    skip_credentials(packer)?;
+
+    Ok(Chain {
+        subnet_id,
+        name,
+        vm_id,
+        fx_ids,
+        genesis_data,
+    })
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::genesis::test_genesis` --exact --show-output
+#[test]
+fn test_genesis() {
+    let addr = short::Id::from_slice(&[1; 20]);
+    let owners = |p: &Packer| {
+        p.pack_u64(0).unwrap();
+        p.pack_u32(1).unwrap();
+        p.pack_u32(1).unwrap();
+        p.pack_bytes(addr.as_ref()).unwrap();
+    };
+    let empty_base_tx = |p: &Packer| {
+        p.pack_u32(12345).unwrap();
+        p.pack_bytes(ids::Id::empty().as_ref()).unwrap();
+        p.pack_u32(0).unwrap();
+        p.pack_u32(0).unwrap();
+        p.pack_bytes_with_header(&[]).unwrap();
+    };
+
+    let p = Packer::new((1 << 31) - 1, 128);
+    p.pack_u16(codec::VERSION).unwrap();
+
+    // unlocked and stakeable locked UTXOs
+    p.pack_u32(2).unwrap();
+    for (i, locktime) in [0_u64, 100].iter().enumerate() {
+        p.pack_bytes(&[2; 32]).unwrap();
+        p.pack_u32(u32::try_from(i).unwrap()).unwrap();
+        p.pack_bytes(&[3; 32]).unwrap();
+        if *locktime > 0 {
+            p.pack_u32(22).unwrap();
+            p.pack_u64(*locktime).unwrap();
+        }
+        p.pack_u32(7).unwrap();
+        p.pack_u64(1000).unwrap();
+        owners(&p);
+        p.pack_bytes_with_header(b"hi").unwrap();
+    }
+
+    // one validator
+    p.pack_u32(1).unwrap();
+    p.pack_u32(12).unwrap();
+    empty_base_tx(&p);
+    p.pack_bytes(&[4; 20]).unwrap();
+    p.pack_u64(1).unwrap();
+    p.pack_u64(2).unwrap();
+    p.pack_u64(2000).unwrap();
+    p.pack_u32(1).unwrap();
+    p.pack_bytes(&[3; 32]).unwrap();
+    p.pack_u32(7).unwrap();
+    p.pack_u64(2000).unwrap();
+    owners(&p);
+    p.pack_u32(11).unwrap();
+    owners(&p);
+    p.pack_u32(20_000).unwrap();
+    p.pack_u32(0).unwrap();
+
+    // one chain
+    p.pack_u32(1).unwrap();
+    p.pack_u32(15).unwrap();
+    empty_base_tx(&p);
+    p.pack_bytes(ids::Id::empty().as_ref()).unwrap();
+    p.pack_str("X-Chain").unwrap();
+    p.pack_bytes(&[5; 32]).unwrap();
+    p.pack_u32(1).unwrap();
+    p.pack_bytes(&[6; 32]).unwrap();
+    p.pack_bytes_with_header(&[7, 8]).unwrap();
+    p.pack_u32(10).unwrap();
+    p.pack_u32(0).unwrap();
+    p.pack_u32(0).unwrap();
+
+    p.pack_u64(1_600_000_000).unwrap();
+    p.pack_u64(5000).unwrap();
+    p.pack_str("genesis").unwrap();
+
+    let genesis = Genesis::from_bytes(&p.take_bytes()).unwrap();
+    assert_eq!(genesis.utxos.len(), 2);
+    assert_eq!(genesis.utxos[1].output_index, 1);
+    assert_eq!(genesis.utxos[1].stakeable_locktime, 100);
+    assert_eq!(genesis.utxos[0].message, b"hi".to_vec());
+    assert_eq!(
+        genesis.validators[0].node_id,
+        node::Id::from_slice(&[4; 20])
+    );
+    assert_eq!(genesis.validators[0].delegation_shares, 20_000);
+    assert_eq!(genesis.total_weight(), 2000);
+    assert_eq!(genesis.chains[0].name, "X-Chain");
+    assert_eq!(genesis.chains[0].genesis_data, vec![7, 8]);
+    assert_eq!(genesis.initial_supply, 5000);
+    assert_eq!(genesis.message, "genesis");
+    assert_eq!(
+        genesis.allocations().get(&addr),
+        Some(&Balance {
+            unlocked: 3000,
+            locked: 1000,
+        })
+    );
+
+    assert!(Genesis::from_bytes(&[0, 1]).is_err());
+}
@@ -1,4 +1,7 @@
 //! Avalanche platformvm utilities.
+pub mod fees;
+pub mod genesis;
+pub mod reward;
 pub mod txs;
 
 use crate::ids;
@@ -0,0 +1,389 @@
+//! Staking reward calculation and staking parameters of the primary network.
+//!
+//! ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.0/vms/platformvm/reward/calculator.go>
+use std::time::Duration;
+
+use crate::{
+    errors::{Error, Result},
+    units,
+};
+use primitive_types::U256;
+
+/// Denominator of the consumption rates and the delegation shares
+/// (e.g., 20,000 shares is 2%).
+pub const PERCENT_DENOMINATOR: u64 = 1_000_000;
+
+/// Length of a year used in the staking parameters.
+pub const YEAR: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Represents the reward configuration of the primary network.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/reward#Config>
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Config {
+    /// Maximum percentage of the remaining supply minted per year
+    /// (for stakers of the maximum duration), out of "`PERCENT_DENOMINATOR`".
+    pub max_consumption_rate: u64,
+    /// Minimum percentage of the remaining supply minted per year
+    /// (for stakers of no duration), out of "`PERCENT_DENOMINATOR`".
+    pub min_consumption_rate: u64,
+    /// Duration that the consumption rates are applied over.
+    pub minting_period: Duration,
+    /// Maximum supply in nano-AVAX.
+    pub supply_cap: u64,
+}
+
+/// Defaults to the mainnet (and fuji) configuration.
+/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.0/genesis/genesis_mainnet.go>
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_consumption_rate: 120_000, // 12%
+            min_consumption_rate: 100_000, // 10%
+            minting_period: YEAR,
+            supply_cap: 720 * units::MEGA_AVAX,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the reward for staking "`staked_amount`" for "`staked_duration`",
+    /// given the "`current_supply`" (e.g., from "platform.getCurrentSupply").
+    ///
+    /// The reward is computed as follows, capped at the remaining supply:
+    ///
+    /// ```text
+    /// RemainingSupply = SupplyCap - CurrentSupply
+    /// PortionOfExistingSupply = StakedAmount / CurrentSupply
+    /// PortionOfStakingDuration = StakedDuration / MintingPeriod
+    /// MintingRate = MinConsumptionRate + (MaxConsumptionRate - MinConsumptionRate) * PortionOfStakingDuration
+    /// Reward = RemainingSupply * PortionOfExistingSupply * MintingRate * PortionOfStakingDuration
+    /// ```
+    ///
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/reward#Calculator>
+    #[must_use]
+    pub fn calculate(
+        &self,
+        staked_duration: Duration,
+        staked_amount: u64,
+        current_supply: u64,
+    ) -> u64 {
+        let remaining_supply = self.supply_cap.saturating_sub(current_supply);
+        if remaining_supply == 0 || current_supply == 0 || self.minting_period.is_zero() {
+            return 0;
+        }
+
+        let staked_duration = U256::from(staked_duration.as_nanos());
+        let minting_period = U256::from(self.minting_period.as_nanos());
+        let max_sub_min_rate = U256::from(
+            self.max_consumption_rate
+                .saturating_sub(self.min_consumption_rate),
+        );
+
+        let rate_numerator = max_sub_min_rate * staked_duration
+            + U256::from(self.min_consumption_rate) * minting_period;
+        let rate_denominator = minting_period * U256::from(PERCENT_DENOMINATOR);
+
+        // cannot overflow 256 bits for 64-bit amounts and durations within centuries,
+        // but saturate in case of malformed inputs
+        let reward = U256::from(remaining_supply)
+            .checked_mul(rate_numerator)
+            .and_then(|r| r.checked_mul(U256::from(staked_amount)))
+            .and_then(|r| r.checked_mul(staked_duration))
+            .map_or(U256::MAX, |r| {
+                r / rate_denominator / U256::from(current_supply) / minting_period
+            });
+
+        if reward > U256::from(remaining_supply) {
+            return remaining_supply;
+        }
+        reward.as_u64()
+    }
+}
+
+/// Splits the "`total_amount`" of rewards into the amount for the staker
+/// and the amount for the "shares" (e.g., delegation fee to the validator),
+/// where "shares" is out of "`PERCENT_DENOMINATOR`".
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/reward#Split>
+#[must_use]
+pub fn split(total_amount: u64, shares: u32) -> (u64, u64) {
+    let remainder_shares = PERCENT_DENOMINATOR.saturating_sub(u64::from(shares));
+
+    // delay rounding as long as possible for small numbers
+    let remainder_amount = remainder_shares.checked_mul(total_amount).map_or_else(
+        || remainder_shares * (total_amount / PERCENT_DENOMINATOR),
+        |r| r / PERCENT_DENOMINATOR,
+    );
+
+    (remainder_amount, total_amount - remainder_amount)
+}
+
+/// Represents the staking parameters of the primary network.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/genesis#StakingConfig>
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct StakingConfig {
+    /// Minimum stake of a validator in nano-AVAX.
+    pub min_validator_stake: u64,
+    /// Maximum stake of a validator in nano-AVAX, including the delegations.
+    pub max_validator_stake: u64,
+    /// Minimum stake of a delegator in nano-AVAX.
+    pub min_delegator_stake: u64,
+    /// Minimum delegation fee, out of "`PERCENT_DENOMINATOR`".
+    pub min_delegation_fee: u32,
+    /// Minimum staking duration.
+    pub min_stake_duration: Duration,
+    /// Maximum staking duration.
+    pub max_stake_duration: Duration,
+    /// Maximum total delegations relative to the validator stake
+    /// (e.g., 5 allows delegations up to 4x of the validator stake).
+    pub max_validator_weight_factor: u64,
+    pub reward_config: Config,
+}
+
+impl StakingConfig {
+    /// Returns the staking parameters of the mainnet.
+    /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.0/genesis/genesis_mainnet.go>
+    #[must_use]
+    pub fn mainnet() -> Self {
+        Self {
+            min_validator_stake: 2 * units::KILO_AVAX,
+            max_validator_stake: 3 * units::MEGA_AVAX,
+            min_delegator_stake: 25 * units::AVAX,
+            min_delegation_fee: 20_000, // 2%
+            min_stake_duration: Duration::from_secs(14 * 24 * 60 * 60),
+            max_stake_duration: YEAR,
+            max_validator_weight_factor: 5,
+            reward_config: Config::default(),
+        }
+    }
+
+    /// Returns the staking parameters of the fuji testnet.
+    /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.0/genesis/genesis_fuji.go>
+    #[must_use]
+    pub fn fuji() -> Self {
+        Self {
+            min_validator_stake: units::AVAX,
+            max_validator_stake: 3 * units::MEGA_AVAX,
+            min_delegator_stake: units::AVAX,
+            min_delegation_fee: 20_000, // 2%
+            min_stake_duration: Duration::from_secs(24 * 60 * 60),
+            max_stake_duration: YEAR,
+            max_validator_weight_factor: 5,
+            reward_config: Config::default(),
+        }
+    }
+
+    /// Returns the staking parameters of the known public networks,
+    /// or none for custom networks whose parameters are set in their genesis.
+    #[must_use]
+    pub fn for_network(network_id: u32) -> Option<Self> {
+        match network_id {
+            1 => Some(Self::mainnet()),
+            5 => Some(Self::fuji()),
+            _ => None,
+        }
+    }
+
+    /// Validates the stake amount, duration, and delegation fee of a new validator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them is out of the allowed range.
+    pub fn validate_validator(
+        &self,
+        stake_amount: u64,
+        stake_duration: Duration,
+        delegation_fee: u32,
+    ) -> Result<()> {
+        if stake_amount < self.min_validator_stake || stake_amount > self.max_validator_stake {
+            return Err(invalid(format!(
+                "validator stake amount {stake_amount} must be within [{}, {}]",
+                self.min_validator_stake, self.max_validator_stake
+            )));
+        }
+        if u64::from(delegation_fee) < u64::from(self.min_delegation_fee)
+            || u64::from(delegation_fee) > PERCENT_DENOMINATOR
+        {
+            return Err(invalid(format!(
+                "delegation fee {delegation_fee} must be within [{}, {PERCENT_DENOMINATOR}]",
+                self.min_delegation_fee
+            )));
+        }
+        self.validate_duration(stake_duration)
+    }
+
+    /// Validates the stake amount and duration of a new delegator, given the
+    /// validator's own stake and its current weight (including existing delegations).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them is out of the allowed range,
+    /// or if the delegation would exceed the maximum validator weight.
+    pub fn validate_delegator(
+        &self,
+        stake_amount: u64,
+        stake_duration: Duration,
+        validator_stake: u64,
+        validator_weight: u64,
+    ) -> Result<()> {
+        if stake_amount < self.min_delegator_stake {
+            return Err(invalid(format!(
+                "delegator stake amount {stake_amount} must be at least {}",
+                self.min_delegator_stake
+            )));
+        }
+
+        let max_weight = validator_stake
+            .saturating_mul(self.max_validator_weight_factor)
+            .min(self.max_validator_stake);
+        let new_weight = validator_weight.saturating_add(stake_amount);
+        if new_weight > max_weight {
+            return Err(invalid(format!(
+                "validator weight {new_weight} after delegation exceeds the maximum {max_weight}"
+            )));
+        }
+        self.validate_duration(stake_duration)
+    }
+
+    /// Returns the expected reward for staking "`stake_amount`" for
+    /// "`stake_duration`", before splitting any delegation fee.
+    #[must_use]
+    pub fn expected_reward(
+        &self,
+        stake_amount: u64,
+        stake_duration: Duration,
+        current_supply: u64,
+    ) -> u64 {
+        self.reward_config
+            .calculate(stake_duration, stake_amount, current_supply)
+    }
+
+    /// Validates the staking duration.
+    fn validate_duration(&self, stake_duration: Duration) -> Result<()> {
+        if stake_duration < self.min_stake_duration || stake_duration > self.max_stake_duration {
+            return Err(invalid(format!(
+                "stake duration {stake_duration:?} must be within [{:?}, {:?}]",
+                self.min_stake_duration, self.max_stake_duration
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the non-retryable error for invalid staking parameters.
+const fn invalid(message: String) -> Error {
+    Error::Other {
+        message,
+        retryable: false,
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::reward::test_calculate` --exact --show-output
+#[test]
+fn test_calculate() {
+    // ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.0/vms/platformvm/reward/calculator_test.go>
+    let cfg = Config {
+        max_consumption_rate: 120_000,
+        min_consumption_rate: 100_000,
+        minting_period: YEAR,
+        supply_cap: 720 * units::MEGA_AVAX,
+    };
+    let current_supply = 360 * units::MEGA_AVAX;
+
+    // staking half of the supply for the full minting period
+    // mints 12% of the remaining supply, split in half
+    assert_eq!(
+        cfg.calculate(YEAR, current_supply / 2, current_supply),
+        (360 * units::MEGA_AVAX) * 12 / 100 / 2
+    );
+
+    // staking the whole supply for the full minting period
+    assert_eq!(
+        cfg.calculate(YEAR, current_supply, current_supply),
+        (360 * units::MEGA_AVAX) * 12 / 100
+    );
+
+    // shorter durations earn proportionally less at a lower rate
+    let half_year = cfg.calculate(YEAR / 2, current_supply, current_supply);
+    assert_eq!(half_year, (360 * units::MEGA_AVAX) * 11 / 100 / 2);
+
+    // no reward once the supply cap is reached
+    assert_eq!(cfg.calculate(YEAR, units::AVAX, cfg.supply_cap), 0);
+    assert_eq!(cfg.calculate(YEAR, units::AVAX, 0), 0);
+
+    // capped at the remaining supply
+    assert_eq!(cfg.calculate(YEAR, u64::MAX, cfg.supply_cap - 1), 1);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::reward::test_split` --exact --show-output
+#[test]
+fn test_split() {
+    assert_eq!(split(1_000, 20_000), (980, 20));
+    assert_eq!(split(1_000, 0), (1_000, 0));
+    assert_eq!(split(1_000, 1_000_000), (0, 1_000));
+    assert_eq!(split(1, 20_000), (0, 1));
+
+    // large amounts do not overflow
+    let (staker, shares) = split(u64::MAX, 20_000);
+    assert_eq!(staker + shares, u64::MAX);
+    assert_eq!(shares, 368_934_881_474_731_615);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::reward::test_staking_config` --exact --show-output
+#[test]
+fn test_staking_config() {
+    let day = Duration::from_secs(24 * 60 * 60);
+
+    let cfg = StakingConfig::for_network(1).unwrap();
+    assert_eq!(cfg, StakingConfig::mainnet());
+    assert!(StakingConfig::for_network(12345).is_none());
+
+    assert!(cfg
+        .validate_validator(2 * units::KILO_AVAX, 14 * day, 20_000)
+        .is_ok());
+    assert!(cfg
+        .validate_validator(units::KILO_AVAX, 14 * day, 20_000)
+        .is_err());
+    assert!(cfg
+        .validate_validator(4 * units::MEGA_AVAX, 14 * day, 20_000)
+        .is_err());
+    assert!(cfg
+        .validate_validator(2 * units::KILO_AVAX, 13 * day, 20_000)
+        .is_err());
+    assert!(cfg
+        .validate_validator(2 * units::KILO_AVAX, 366 * day, 20_000)
+        .is_err());
+    assert!(cfg
+        .validate_validator(2 * units::KILO_AVAX, 14 * day, 10_000)
+        .is_err());
+
+    let validator_stake = 2 * units::KILO_AVAX;
+    assert!(cfg
+        .validate_delegator(25 * units::AVAX, 14 * day, validator_stake, validator_stake)
+        .is_ok());
+    assert!(cfg
+        .validate_delegator(units::AVAX, 14 * day, validator_stake, validator_stake)
+        .is_err());
+    // at most 4x of the validator stake can be delegated
+    assert!(cfg
+        .validate_delegator(
+            8 * units::KILO_AVAX,
+            14 * day,
+            validator_stake,
+            validator_stake
+        )
+        .is_ok());
+    assert!(cfg
+        .validate_delegator(
+            8 * units::KILO_AVAX + 1,
+            14 * day,
+            validator_stake,
+            validator_stake
+        )
+        .is_err());
+
+    let fuji = StakingConfig::fuji();
+    assert!(fuji.validate_validator(units::AVAX, day, 20_000).is_ok());
+    assert!(fuji
+        .expected_reward(units::AVAX, YEAR, 400 * units::MEGA_AVAX)
+        .gt(&0));
+}
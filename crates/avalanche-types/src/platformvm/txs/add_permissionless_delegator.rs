@@ -0,0 +1,488 @@
+use crate::{
+    codec,
+    errors::Result,
+    hash, ids, key,
+    platformvm::{self, txs::add_validator},
+    txs,
+};
+use serde::{Deserialize, Serialize};
+
+/// `AddPermissionlessDelegatorTx` is a transaction that delegates the stake to a
+/// permissionless validator of a subnet.
+///
+/// ref. <https://github.com/ava-labs/avalanchego/blob/master/vms/platformvm/txs/add_permissionless_delegator_tx.go>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#AddPermissionlessDelegatorTx>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#Tx>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#UnsignedTx>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Tx {
+    /// The transaction ID is empty for unsigned tx
+    /// as long as "avax.BaseTx.Metadata" is "None".
+    /// Once Metadata is updated with signing and "Tx.Initialize",
+    /// `Tx.ID()` is non-empty.
+    pub base_tx: txs::Tx,
+    pub validator: platformvm::txs::Validator,
+
+    /// ID of the subnet the delegated validator is validating.
+    /// ref. "github.com/ava-labs/avalanchego/utils/constants.PrimaryNetworkID" (ids.Empty).
+    #[serde(rename = "subnetID")]
+    pub subnet_id: ids::Id,
+
+    #[serde(rename = "stake")]
+    pub stake_transferable_outputs: Option<Vec<txs::transferable::Output>>,
+
+    #[serde(rename = "rewardsOwner")]
+    pub delegation_rewards_owner: key::secp256k1::txs::OutputOwners,
+
+    /// To be updated after signing.
+    pub creds: Vec<key::secp256k1::txs::Credential>,
+}
+
+impl Tx {
+    #[must_use]
+    pub fn new(base_tx: txs::Tx) -> Self {
+        Self {
+            base_tx,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the transaction ID.
+    /// Only non-empty if the embedded metadata is updated
+    /// with the signing process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metadata is Some but contains invalid data.
+    #[must_use]
+    pub fn tx_id(&self) -> ids::Id {
+        if self.base_tx.metadata.is_some() {
+            let m = self.base_tx.metadata.clone().unwrap();
+            m.id
+        } else {
+            ids::Id::default()
+        }
+    }
+
+    #[must_use]
+    pub fn type_name() -> String {
+        "platformvm.AddPermissionlessDelegatorTx".to_string()
+    }
+
+    /// Returns the type ID for this transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type name is not found in the codec registry.
+    #[must_use]
+    pub fn type_id() -> u32 {
+        u32::try_from(*(codec::P_TYPES.get(&Self::type_name()).unwrap())).unwrap()
+    }
+
+    /// Signs the transaction with the provided signers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if signing fails.
+    ///
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#Tx.Sign>
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
+    pub async fn sign<T: key::secp256k1::SignOnly + Clone + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+    ) -> Result<()> {
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Clone + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::p_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
+
+            // "avalanchego" marshals the whole struct again for signed bytes
+            // even when the underlying "unsigned_tx" is already once marshaled
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#Tx.Sign
+            //
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let unsigned_tx_bytes = packer.take_bytes();
+            packer.set_bytes(&unsigned_tx_bytes);
+
+            // pack the second field "validator" in the struct
+            add_validator::pack_validator(&packer, &self.validator)?;
+
+            // pack the third field "subnet_id" in the struct
+            packer.pack_bytes(self.subnet_id.as_ref())?;
+
+            // pack the fourth field "stake" in the struct
+            add_validator::pack_stake(
+                &packer,
+                &registry,
+                codec_version,
+                self.stake_transferable_outputs.as_deref(),
+            )?;
+
+            // pack the fifth field "delegation_rewards_owner" in the struct
+            add_validator::pack_rewards_owner(
+                &packer,
+                &registry,
+                codec_version,
+                &self.delegation_rewards_owner,
+            )?;
+
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
+
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "platformvm.AddPermissionlessDelegatorTx" unsigned tx
+            // not other fields -- only hash "platformvm.AddPermissionlessDelegatorTx.*" but not "platformvm.Tx.Creds"
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#AddPermissionlessDelegatorTx
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
+
+        // number of of credentials
+        let creds_len = u32::try_from(signers.len())?;
+        // pack the fourth field in the struct
+        packer.pack_u32(creds_len)?;
+
+        // sign the hash with the signers (in case of multi-sig)
+        // and combine all signatures into a secp256k1fx credential
+        self.creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?;
+        if creds_len > 0 {
+            // pack each "cred" which is "secp256k1fx.Credential"
+            // marshal type ID for "secp256k1fx.Credential"
+            for cred in &self.creds {
+                // marshal type ID for "secp256k1fx.Credential"
+                packer.pack_u32(cred_type_id)?;
+
+                // marshal fields for "secp256k1fx.Credential"
+                packer.pack_u32(u32::try_from(cred.signatures.len())?)?;
+                for sig in &cred.signatures {
+                    packer.pack_bytes(sig)?;
+                }
+            }
+        }
+        let tx_bytes_with_signatures = packer.take_bytes();
+        let tx_id = hash::sha256(&tx_bytes_with_signatures);
+
+        // update "BaseTx.Metadata" with id/unsigned bytes/bytes
+        // ref. "avalanchego/vms/platformvm.Tx.Sign"
+        // ref. "avalanchego/vms/components/avax.BaseTx.Metadata.Initialize"
+        self.base_tx.metadata = Some(txs::Metadata {
+            id: ids::Id::from_slice(&tx_id),
+            tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
+            tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
+        });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
+
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::txs::add_permissionless_delegator::test_add_permissionless_delegator_tx_serialization_with_one_signer` --exact --show-output
+#[test]
+#[allow(clippy::too_many_lines)]
+fn test_add_permissionless_delegator_tx_serialization_with_one_signer() {
+    use crate::ids::{node, short};
+    use std::str::FromStr;
+
+    let _ = env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .is_test(true)
+        .try_init();
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let sk = key::secp256k1::private_key::Key::from_cb58(
+        "24jUJ9vZexUM6expyMcT48LBx27k1m7xpraoV62oSQAHdziao5",
+    )
+    .unwrap();
+
+    let mut tx = Tx {
+        base_tx: txs::Tx {
+            network_id: 1_000_000,
+            transferable_outputs: Some(vec![txs::transferable::Output {
+                asset_id: ids::Id::from_slice(&<Vec<u8>>::from([
+                    0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, //
+                    0xe6, 0x89, 0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, //
+                    0xe8, 0x5e, 0xa5, 0x74, 0xc7, 0xa1, 0x5a, 0x79, //
+                    0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, 0x80, 0x14, //
+                ])),
+                transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                    amount: 0x02c6_874d_687f_c000,
+                    output_owners: key::secp256k1::txs::OutputOwners {
+                        locktime: 0,
+                        threshold: 1,
+                        addresses: vec![sk.to_public_key().to_short_id().unwrap()],
+                    },
+                }),
+                ..txs::transferable::Output::default()
+            }]),
+            transferable_inputs: Some(vec![txs::transferable::Input {
+                utxo_id: txs::utxo::Id {
+                    tx_id: ids::Id::from_slice(&<Vec<u8>>::from([0x74, 0x78, 0x49, 0x44])),
+                    output_index: 2,
+                    ..txs::utxo::Id::default()
+                },
+                asset_id: ids::Id::from_slice(&<Vec<u8>>::from([
+                    0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, //
+                    0xe6, 0x89, 0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, //
+                    0xe8, 0x5e, 0xa5, 0x74, 0xc7, 0xa1, 0x5a, 0x79, //
+                    0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, 0x80, 0x14, //
+                ])),
+                transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                    amount: 5678,
+                    sig_indices: vec![0],
+                }),
+                ..txs::transferable::Input::default()
+            }]),
+            ..txs::Tx::default()
+        },
+        validator: platformvm::txs::Validator {
+            node_id: node::Id::from_slice(&<Vec<u8>>::from([
+                0x9c, 0xd7, 0xb3, 0xe4, 0x79, 0x04, 0xf6, 0x7c, 0xc4, 0x8e, //
+                0xb5, 0xb9, 0xaf, 0xdb, 0x03, 0xe6, 0xd1, 0x8a, 0xcf, 0x6c, //
+            ])),
+            start: 0x623d_7267,
+            end: 0x63c9_1062,
+            weight: 0x7e7,
+        },
+        subnet_id: ids::Id::from_str("2u5EYNkXMDFNi4pL9eGBt2F5DnXLGriecu7Ctje8jK155FFkPx").unwrap(),
+        stake_transferable_outputs: Some(vec![txs::transferable::Output {
+            asset_id: ids::Id::from_slice(&<Vec<u8>>::from([
+                0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, //
+                0xe6, 0x89, 0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, //
+                0xe8, 0x5e, 0xa5, 0x74, 0xc7, 0xa1, 0x5a, 0x79, //
+                0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, 0x80, 0x14, //
+            ])),
+            stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
+                locktime: 0,
+                transfer_output: key::secp256k1::txs::transfer::Output {
+                    amount: 0x7e7,
+                    output_owners: key::secp256k1::txs::OutputOwners {
+                        locktime: 0,
+                        threshold: 1,
+                        addresses: vec![short::Id::from_slice(&<Vec<u8>>::from([
+                            0xfc, 0xed, 0xa8, 0xf9, 0x0f, 0xcb, 0x5d, 0x30, 0x61, 0x4b, //
+                            0x99, 0xd7, 0x9f, 0xc4, 0xba, 0xa2, 0x93, 0x07, 0x76, 0x26, //
+                        ]))],
+                    },
+                },
+            }),
+            ..txs::transferable::Output::default()
+        }]),
+        delegation_rewards_owner: key::secp256k1::txs::OutputOwners {
+            locktime: 0,
+            threshold: 1,
+            addresses: vec![short::Id::from_slice(&<Vec<u8>>::from([
+                0xfc, 0xed, 0xa8, 0xf9, 0x0f, 0xcb, 0x5d, 0x30, 0x61, 0x4b, //
+                0x99, 0xd7, 0x9f, 0xc4, 0xba, 0xa2, 0x93, 0x07, 0x76, 0x26, //
+            ]))],
+        },
+        ..Tx::default()
+    };
+
+    let test_key = key::secp256k1::private_key::Key::from_cb58(
+        "PrivateKey-24jUJ9vZexUM6expyMcT48LBx27k1m7xpraoV62oSQAHdziao5",
+    )
+    .expect("failed to load private key");
+    let keys1: Vec<key::secp256k1::private_key::Key> = vec![test_key];
+    let signers: Vec<Vec<key::secp256k1::private_key::Key>> = vec![keys1];
+    ab!(tx.sign(signers)).expect("failed to sign");
+    let tx_metadata = tx.base_tx.metadata.clone().unwrap();
+    // NOT marshaled by avalanchego: derived from the "AddPermissionlessValidatorTx"
+    // vector with the type id of "AddPermissionlessDelegatorTx" and without the
+    // signer, the validator rewards owner and the delegation shares
+    let expected_unsigned_bytes: &[u8] = &[
+        // codec version
+        0x00, 0x00, //
+        //
+        // platformvm.AddPermissionlessDelegatorTx type ID
+        0x00, 0x00, 0x00, 0x1a, //
+        //
+        // network id
+        0x00, 0x0f, 0x42, 0x40, //
+        //
+        // blockchain id
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, //
+        //
+        // outs.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "outs[0]" TransferableOutput.asset_id
+        0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, 0xe6, 0x89, //
+        0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, 0xe8, 0x5e, 0xa5, 0x74, //
+        0xc7, 0xa1, 0x5a, 0x79, 0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, //
+        0x80, 0x14, //
+        //
+        // "outs[0]" secp256k1fx.TransferOutput type ID
+        0x00, 0x00, 0x00, 0x07, //
+        //
+        // "outs[0]" TransferableOutput.out.key::secp256k1::txs::transfer::Output.amount
+        0x02, 0xc6, 0x87, 0x4d, 0x68, 0x7f, 0xc0, 0x00, //
+        //
+        // "outs[0]" TransferableOutput.out.key::secp256k1::txs::transfer::Output.output_owners.locktime
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        //
+        // "outs[0]" TransferableOutput.out.key::secp256k1::txs::transfer::Output.output_owners.threshold
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "outs[0]" TransferableOutput.out.key::secp256k1::txs::transfer::Output.output_owners.addrs.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "outs[0]" TransferableOutput.out.key::secp256k1::txs::transfer::Output.output_owners.addrs[0]
+        0xfc, 0xed, 0xa8, 0xf9, 0x0f, 0xcb, 0x5d, 0x30, 0x61, 0x4b, //
+        0x99, 0xd7, 0x9f, 0xc4, 0xba, 0xa2, 0x93, 0x07, 0x76, 0x26, //
+        //
+        // ins.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "ins[0]" TransferableInput.utxo_id.tx_id
+        0x74, 0x78, 0x49, 0x44, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, //
+        //
+        // "ins[0]" TransferableInput.utxo_id.output_index
+        0x00, 0x00, 0x00, 0x02, //
+        //
+        // "ins[0]" TransferableInput.asset_id
+        0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, 0xe6, 0x89, //
+        0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, 0xe8, 0x5e, 0xa5, 0x74, //
+        0xc7, 0xa1, 0x5a, 0x79, 0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, //
+        0x80, 0x14, //
+        //
+        // "ins[0]" secp256k1fx.TransferInput type ID
+        0x00, 0x00, 0x00, 0x05, //
+        //
+        // "ins[0]" TransferableInput.input.key::secp256k1::txs::transfer::Input.amount
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x2e, //
+        //
+        // "ins[0]" TransferableInput.input.key::secp256k1::txs::transfer::Input.sig_indices.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "ins[0]" TransferableInput.input.key::secp256k1::txs::transfer::Input.sig_indices[0]
+        0x00, 0x00, 0x00, 0x00, //
+        //
+        // memo.len()
+        0x00, 0x00, 0x00, 0x00, //
+        //
+        // Validator.validator.node_id
+        0x9c, 0xd7, 0xb3, 0xe4, 0x79, 0x04, 0xf6, 0x7c, 0xc4, 0x8e, //
+        0xb5, 0xb9, 0xaf, 0xdb, 0x03, 0xe6, 0xd1, 0x8a, 0xcf, 0x6c, //
+        //
+        // Validator.validator.start
+        0x00, 0x00, 0x00, 0x00, 0x62, 0x3d, 0x72, 0x67, //
+        //
+        // Validator.validator.end
+        0x00, 0x00, 0x00, 0x00, 0x63, 0xc9, 0x10, 0x62, //
+        //
+        // Validator.validator.weight
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0xe7, //
+        //
+        // subnet id
+        0xf9, 0xef, 0x27, 0x25, 0xf6, 0x61, 0x9b, 0x92, 0x3f, 0x1e, //
+        0x84, 0xbf, 0x34, 0x81, 0xd5, 0x3f, 0xd0, 0x7e, 0x2b, 0xa4, //
+        0xbc, 0x49, 0xcc, 0xf5, 0xa6, 0x9e, 0x9a, 0xc7, 0x36, 0x73, //
+        0x4e, 0x1a, //
+        //
+        // stake_outputs.len
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // stake_outputs[0].asset_id
+        0x88, 0xee, 0xc2, 0xe0, 0x99, 0xc6, 0xa5, 0x28, 0xe6, 0x89, //
+        0x61, 0x8e, 0x87, 0x21, 0xe0, 0x4a, 0xe8, 0x5e, 0xa5, 0x74, //
+        0xc7, 0xa1, 0x5a, 0x79, 0x68, 0x64, 0x4d, 0x14, 0xd5, 0x47, //
+        0x80, 0x14, //
+        //
+        // platformvm.StakeableLockOut type ID
+        0x00, 0x00, 0x00, 0x16, //
+        //
+        // locktime
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        //
+        // secp256k1fx.TransferOutput type ID
+        0x00, 0x00, 0x00, 0x07, //
+        //
+        // amount
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0xe7, //
+        //
+        // secp256k1fx.OutputOwners.locktime
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        //
+        // secp256k1fx.OutputOwners.threshold
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // secp256k1fx.OutputOwners.addrs.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // secp256k1fx.OutputOwners.addrs[0]
+        0xfc, 0xed, 0xa8, 0xf9, 0x0f, 0xcb, 0x5d, 0x30, 0x61, 0x4b, //
+        0x99, 0xd7, 0x9f, 0xc4, 0xba, 0xa2, 0x93, 0x07, 0x76, 0x26, //
+        //
+        // delegation rewards owner, secp256k1fx.OutputOwners type id
+        0x00, 0x00, 0x00, 0x0b, //
+        //
+        // secp256k1fx.OutputOwners locktime
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        //
+        // secp256k1fx.OutputOwners threshold
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // secp256k1fx.OutputOwners.addrs.len()
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // secp256k1fx.OutputOwners.addrs[0]
+        0xfc, 0xed, 0xa8, 0xf9, 0x0f, 0xcb, 0x5d, 0x30, 0x61, 0x4b, //
+        0x99, 0xd7, 0x9f, 0xc4, 0xba, 0xa2, 0x93, 0x07, 0x76, 0x26, //
+    ];
+    assert!(cmp_manager::eq_vectors(
+        expected_unsigned_bytes,
+        &tx_metadata.tx_bytes_with_no_signature
+    ));
+
+    // followed by a single credential with a single signature
+    let signed = &tx_metadata.tx_bytes_with_signatures;
+    assert!(signed.starts_with(expected_unsigned_bytes));
+    assert_eq!(signed.len(), expected_unsigned_bytes.len() + 4 + 4 + 4 + 65);
+    assert_eq!(tx.creds.len(), 1);
+}
@@ -1,7 +1,7 @@
 use crate::{
     codec,
     errors::{Error, Result},
-    hash, ids, key, platformvm, txs,
+    hash, ids, key, packer, platformvm, txs,
 };
 use serde::{Deserialize, Serialize};
 
@@ -71,195 +71,98 @@ impl Tx {
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
     /// Signs the transaction with the provided signers.
     ///
-    /// # Panics
-    ///
-    /// Panics if `self.stake_transferable_outputs` is `Some` but cannot be unwrapped.
-    ///
     /// # Errors
     ///
     /// Returns an error if the signing process fails.
-    #[allow(clippy::too_many_lines)]
     pub async fn sign<T: key::secp256k1::SignOnly + Clone + Send + Sync>(
         &mut self,
         signers: Vec<Vec<T>>,
     ) -> Result<()> {
-        // marshal "unsigned tx" with the codec version
-        let type_id = Self::type_id();
-        let packer = self.base_tx.pack(codec::VERSION, type_id)?;
-
-        // "avalanchego" marshals the whole struct again for signed bytes
-        // even when the underlying "unsigned_tx" is already once marshaled
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#Tx.Sign
-        //
-        // reuse the underlying packer to avoid marshaling the unsigned tx twice
-        // just marshal the next fields in the struct and pack them all together
-        // in the existing packer
-        let unsigned_tx_bytes = packer.take_bytes();
-        packer.set_bytes(&unsigned_tx_bytes);
-
-        // pack the second field "validator" in the struct
-        packer.pack_bytes(self.validator.node_id.as_ref())?;
-        packer.pack_u64(self.validator.start)?;
-        packer.pack_u64(self.validator.end)?;
-        packer.pack_u64(self.validator.weight)?;
-
-        // pack the third field "stake" in the struct
-        if self.stake_transferable_outputs.is_some() {
-            let stake_transferable_outputs = self.stake_transferable_outputs.as_ref().unwrap();
-            packer.pack_u32(u32::try_from(stake_transferable_outputs.len()).unwrap())?;
-
-            for transferable_output in stake_transferable_outputs {
-                // "TransferableOutput.Asset" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableOutput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#Asset
-                packer.pack_bytes(transferable_output.asset_id.as_ref())?;
-
-                // fx_id is serialize:"false" thus skipping serialization
-
-                // decide the type
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                if transferable_output.transfer_output.is_none()
-                    && transferable_output.stakeable_lock_out.is_none()
-                {
-                    return Err(Error::Other {
-                        message: "unexpected Nones in TransferableOutput transfer_output and stakeable_lock_out".to_string(),
-                        retryable: false,
-                    });
-                }
-                let type_id_transferable_out = {
-                    if transferable_output.transfer_output.is_some() {
-                        key::secp256k1::txs::transfer::Output::type_id()
-                    } else {
-                        platformvm::txs::StakeableLockOut::type_id()
-                    }
-                };
-                // marshal type ID for "key::secp256k1::txs::transfer::Output" or "platformvm::txs::StakeableLockOut"
-                packer.pack_u32(type_id_transferable_out)?;
-
-                match type_id_transferable_out {
-                    7 => {
-                        // "key::secp256k1::txs::transfer::Output"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        let transfer_output = transferable_output.transfer_output.clone().unwrap();
-
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(transfer_output.amount)?;
-
-                        // "secp256k1fx.TransferOutput.OutputOwners" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferOutput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        packer.pack_u64(transfer_output.output_owners.locktime)?;
-                        packer.pack_u32(transfer_output.output_owners.threshold)?;
-                        packer.pack_u32(
-                            u32::try_from(transfer_output.output_owners.addresses.len()).unwrap(),
-                        )?;
-                        for addr in &transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    22 => {
-                        // "platformvm::txs::StakeableLockOut"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        let stakeable_lock_out =
-                            transferable_output.stakeable_lock_out.clone().unwrap();
-
-                        // marshal "platformvm::txs::StakeableLockOut.locktime" field
-                        packer.pack_u64(stakeable_lock_out.locktime)?;
-
-                        // secp256k1fx.TransferOutput type ID
-                        packer.pack_u32(7)?;
-
-                        // "platformvm.StakeableLockOut.TransferOutput" is struct and serialize:"true"
-                        // but embedded inline in the struct "StakeableLockOut"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        //
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(stakeable_lock_out.transfer_output.amount)?;
-                        packer
-                            .pack_u64(stakeable_lock_out.transfer_output.output_owners.locktime)?;
-                        packer
-                            .pack_u32(stakeable_lock_out.transfer_output.output_owners.threshold)?;
-                        packer.pack_u32(
-                            u32::try_from(
-                                stakeable_lock_out
-                                    .transfer_output
-                                    .output_owners
-                                    .addresses
-                                    .len(),
-                            )
-                            .unwrap(),
-                        )?;
-                        for addr in &stakeable_lock_out.transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Other {
-                            message: format!("unexpected type ID {type_id_transferable_out} for TransferableOutput"),
-                            retryable: false,
-                        });
-                    }
-                }
-            }
-        } else {
-            packer.pack_u32(0_u32)?;
-        }
-
-        // pack the fourth field "reward_owner" in the struct
-        // not embedded thus encode struct type id
-        let output_owners_type_id = key::secp256k1::txs::OutputOwners::type_id();
-        packer.pack_u32(output_owners_type_id)?;
-        packer.pack_u64(self.rewards_owner.locktime)?;
-        packer.pack_u32(self.rewards_owner.threshold)?;
-        packer.pack_u32(u32::try_from(self.rewards_owner.addresses.len()).unwrap())?;
-        for addr in &self.rewards_owner.addresses {
-            packer.pack_bytes(addr.as_ref())?;
-        }
-
-        // pack the fifth field "shares" in the struct
-        packer.pack_u32(self.shares)?;
-
-        // take bytes just for hashing computation
-        let tx_bytes_with_no_signature = packer.take_bytes();
-        packer.set_bytes(&tx_bytes_with_no_signature);
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
 
-        // compute sha256 for marshaled "unsigned tx" bytes
-        // IMPORTANT: take the hash only for the type "platformvm.AddValidatorTx" unsigned tx
-        // not other fields -- only hash "platformvm.AddValidatorTx.*" but not "platformvm.Tx.Creds"
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#UnsignedAddValidatorTx
-        let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Clone + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::p_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
+
+            // "avalanchego" marshals the whole struct again for signed bytes
+            // even when the underlying "unsigned_tx" is already once marshaled
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#Tx.Sign
+            //
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let unsigned_tx_bytes = packer.take_bytes();
+            packer.set_bytes(&unsigned_tx_bytes);
+
+            // pack the second field "validator" in the struct
+            pack_validator(&packer, &self.validator)?;
+
+            // pack the third field "stake" in the struct
+            pack_stake(
+                &packer,
+                &registry,
+                codec_version,
+                self.stake_transferable_outputs.as_deref(),
+            )?;
+
+            // pack the fourth field "reward_owner" in the struct
+            pack_rewards_owner(&packer, &registry, codec_version, &self.rewards_owner)?;
+
+            // pack the fifth field "shares" in the struct
+            packer.pack_u32(self.shares)?;
+
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
+
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "platformvm.AddValidatorTx" unsigned tx
+            // not other fields -- only hash "platformvm.AddValidatorTx.*" but not "platformvm.Tx.Creds"
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#UnsignedAddValidatorTx
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
 
         // number of of credentials
-        let creds_len = u32::try_from(signers.len()).unwrap();
+        let creds_len = u32::try_from(signers.len())?;
         // pack the fourth field in the struct
         packer.pack_u32(creds_len)?;
 
         // sign the hash with the signers (in case of multi-sig)
         // and combine all signatures into a secp256k1fx credential
-        self.creds = Vec::new();
-        for keys in &signers {
-            let mut sigs: Vec<Vec<u8>> = Vec::new();
-            for k in keys {
-                let sig = k.sign_digest(&tx_bytes_hash).await?;
-                sigs.push(Vec::from(sig));
-            }
-
-            let cred = key::secp256k1::txs::Credential { signatures: sigs };
-
-            // add a new credential to "Tx"
-            self.creds.push(cred);
-        }
+        self.creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?;
         if creds_len > 0 {
             // pack each "cred" which is "secp256k1fx.Credential"
             // marshal type ID for "secp256k1fx.Credential"
-            let cred_type_id = key::secp256k1::txs::Credential::type_id();
             for cred in &self.creds {
                 // marshal type ID for "secp256k1fx.Credential"
                 packer.pack_u32(cred_type_id)?;
@@ -287,11 +190,75 @@ impl Tx {
             tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
             tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
         });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
 
         Ok(())
     }
 }
 
+/// Packs the "validator" field of the staking txs.
+///
+/// # Errors
+///
+/// Returns an error if the packer fails.
+pub(crate) fn pack_validator(
+    packer: &packer::Packer,
+    validator: &platformvm::txs::Validator,
+) -> Result<()> {
+    packer.pack_bytes(validator.node_id.as_ref())?;
+    packer.pack_u64(validator.start)?;
+    packer.pack_u64(validator.end)?;
+    packer.pack_u64(validator.weight)?;
+    Ok(())
+}
+
+/// Packs the "stake" outputs of the staking txs, where "None" is
+/// packed as no outputs.
+///
+/// # Errors
+///
+/// Returns an error if an output cannot be packed.
+pub(crate) fn pack_stake(
+    packer: &packer::Packer,
+    registry: &codec::Registry,
+    codec_version: u16,
+    stake_transferable_outputs: Option<&[txs::transferable::Output]>,
+) -> Result<()> {
+    let stake_transferable_outputs = stake_transferable_outputs.unwrap_or_default();
+    packer.pack_u32(u32::try_from(stake_transferable_outputs.len())?)?;
+    for transferable_output in stake_transferable_outputs {
+        transferable_output.pack_into(packer, registry, codec_version)?;
+    }
+    Ok(())
+}
+
+/// Packs a rewards owner of the staking txs, which is not embedded
+/// thus encodes the struct type id.
+///
+/// # Errors
+///
+/// Returns an error if the owner type is not registered or the packer fails.
+pub(crate) fn pack_rewards_owner(
+    packer: &packer::Packer,
+    registry: &codec::Registry,
+    codec_version: u16,
+    rewards_owner: &key::secp256k1::txs::OutputOwners,
+) -> Result<()> {
+    let output_owners_type_id = registry.resolve_type_id(
+        codec_version,
+        &key::secp256k1::txs::OutputOwners::type_name(),
+    )?;
+    packer.pack_u32(output_owners_type_id)?;
+    packer.pack_u64(rewards_owner.locktime)?;
+    packer.pack_u32(rewards_owner.threshold)?;
+    packer.pack_u32(u32::try_from(rewards_owner.addresses.len())?)?;
+    for addr in &rewards_owner.addresses {
+        packer.pack_bytes(addr.as_ref())?;
+    }
+    Ok(())
+}
+
 /// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::txs::add_validator::test_add_validator_tx_serialization_with_one_signer` --exact --show-output
 #[test]
 fn test_add_validator_tx_serialization_with_one_signer() {
@@ -64,83 +64,101 @@ impl Tx {
     /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/crypto#PrivateKeyED25519.SignHash>
     /// Signs the transaction with the provided signers.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if `u32::try_from` fails when converting collection lengths.
+    /// Returns an error if the signing process fails.
+    pub async fn sign<T: key::secp256k1::SignOnly + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+    ) -> Result<()> {
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
     ///
     /// # Errors
     ///
-    /// Returns an error if the signing process fails.
+    /// Returns an error if the serialization or signing fails.
     #[allow(clippy::too_many_lines)]
-    pub async fn sign<T: key::secp256k1::SignOnly + Send + Sync>(
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Send + Sync>(
         &mut self,
         signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
     ) -> Result<()> {
-        // marshal "unsigned tx" with the codec version
-        let type_id = Self::type_id();
-        let packer = self.base_tx.pack(codec::VERSION, type_id)?;
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::p_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
 
-        // "avalanchego" marshals the whole struct again for signed bytes
-        // even when the underlying "unsigned_tx" is already once marshaled
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#Tx.Sign
-        //
-        // reuse the underlying packer to avoid marshaling the unsigned tx twice
-        // just marshal the next fields in the struct and pack them all together
-        // in the existing packer
-        let base = packer.take_bytes();
-        packer.set_bytes(&base);
+            // "avalanchego" marshals the whole struct again for signed bytes
+            // even when the underlying "unsigned_tx" is already once marshaled
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#Tx.Sign
+            //
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let base = packer.take_bytes();
+            packer.set_bytes(&base);
 
-        // pack the second field "owner" in the struct
-        // not embedded thus encode struct type id
-        let output_owners_type_id = key::secp256k1::txs::OutputOwners::type_id();
-        packer.pack_u32(output_owners_type_id)?;
-        packer.pack_u64(self.owner.locktime)?;
-        packer.pack_u32(self.owner.threshold)?;
-        packer.pack_u32(u32::try_from(self.owner.addresses.len()).unwrap())?;
-        for addr in &self.owner.addresses {
-            packer.pack_bytes(addr.as_ref())?;
-        }
+            // pack the second field "owner" in the struct
+            // not embedded thus encode struct type id
+            let output_owners_type_id = registry.resolve_type_id(
+                codec_version,
+                &key::secp256k1::txs::OutputOwners::type_name(),
+            )?;
+            packer.pack_u32(output_owners_type_id)?;
+            packer.pack_u64(self.owner.locktime)?;
+            packer.pack_u32(self.owner.threshold)?;
+            packer.pack_u32(u32::try_from(self.owner.addresses.len())?)?;
+            for addr in &self.owner.addresses {
+                packer.pack_bytes(addr.as_ref())?;
+            }
 
-        // take bytes just for hashing computation
-        let tx_bytes_with_no_signature = packer.take_bytes();
-        packer.set_bytes(&tx_bytes_with_no_signature);
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
 
-        // compute sha256 for marshaled "unsigned tx" bytes
-        // IMPORTANT: take the hash only for the type "platformvm.AddValidatorTx" unsigned tx
-        // not other fields -- only hash "platformvm.AddValidatorTx.*" but not "platformvm.Tx.Creds"
-        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#UnsignedAddValidatorTx
-        let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "platformvm.AddValidatorTx" unsigned tx
+            // not other fields -- only hash "platformvm.AddValidatorTx.*" but not "platformvm.Tx.Creds"
+            // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#UnsignedAddValidatorTx
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
 
         // number of of credentials
-        let creds_len = u32::try_from(signers.len()).unwrap();
+        let creds_len = u32::try_from(signers.len())?;
         // pack the fourth field in the struct
         packer.pack_u32(creds_len)?;
 
         // sign the hash with the signers (in case of multi-sig)
         // and combine all signatures into a secp256k1fx credential
-        self.creds = Vec::new();
-        for keys in &signers {
-            let mut sigs: Vec<Vec<u8>> = Vec::new();
-            for k in keys {
-                let sig = k.sign_digest(&tx_bytes_hash).await?;
-                sigs.push(Vec::from(sig));
-            }
-
-            let cred = key::secp256k1::txs::Credential { signatures: sigs };
-
-            // add a new credential to "Tx"
-            self.creds.push(cred);
-        }
+        self.creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?;
         if creds_len > 0 {
             // pack each "cred" which is "secp256k1fx.Credential"
             // marshal type ID for "secp256k1fx.Credential"
-            let cred_type_id = key::secp256k1::txs::Credential::type_id();
             for cred in &self.creds {
                 // marshal type ID for "secp256k1fx.Credential"
                 packer.pack_u32(cred_type_id)?;
 
                 // marshal fields for "secp256k1fx.Credential"
-                packer.pack_u32(u32::try_from(cred.signatures.len()).unwrap())?;
+                packer.pack_u32(u32::try_from(cred.signatures.len())?)?;
                 for sig in &cred.signatures {
                     packer.pack_bytes(sig)?;
                 }
@@ -157,6 +175,8 @@ impl Tx {
             tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
             tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
         });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
 
         Ok(())
     }
@@ -1,3 +1,5 @@
+pub mod add_delegator;
+pub mod add_permissionless_delegator;
 pub mod add_permissionless_validator;
 pub mod add_subnet_validator;
 pub mod add_validator;
@@ -5,6 +7,7 @@ pub mod create_chain;
 pub mod create_subnet;
 pub mod export;
 pub mod import;
+pub mod remove_subnet_validator;
 pub mod status;
 
 use std::cmp::Ordering;
@@ -493,3 +496,23 @@ impl Default for Validator {
         }
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::txs::test_sign_is_send` --exact --show-output
+#[test]
+fn test_sign_is_send() {
+    use crate::key::secp256k1::private_key::Key;
+
+    // the registry lock must not be held across the remote signing
+    fn is_send<T: Send>(_: T) {}
+
+    is_send(add_delegator::Tx::default().sign::<Key>(vec![]));
+    is_send(add_permissionless_delegator::Tx::default().sign::<Key>(vec![]));
+    is_send(add_permissionless_validator::Tx::default().sign::<Key>(vec![]));
+    is_send(add_subnet_validator::Tx::default().sign::<Key>(vec![]));
+    is_send(add_validator::Tx::default().sign::<Key>(vec![]));
+    is_send(create_chain::Tx::default().sign::<Key>(vec![]));
+    is_send(create_subnet::Tx::default().sign::<Key>(vec![]));
+    is_send(export::Tx::default().sign::<Key>(vec![]));
+    is_send(import::Tx::default().sign::<Key>(vec![]));
+    is_send(remove_subnet_validator::Tx::default().sign::<Key>(vec![]));
+}
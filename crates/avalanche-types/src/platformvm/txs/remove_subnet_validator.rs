@@ -0,0 +1,279 @@
+use crate::{
+    codec,
+    errors::Result,
+    hash,
+    ids::{self, node},
+    key, txs,
+};
+use serde::{Deserialize, Serialize};
+
+/// Removes a validator from a permissioned subnet before its end time.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#RemoveSubnetValidatorTx>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#Tx>
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#UnsignedTx>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct Tx {
+    /// The transaction ID is empty for unsigned tx
+    /// as long as "avax.BaseTx.Metadata" is "None".
+    /// Once Metadata is updated with signing and "Tx.Initialize",
+    /// `Tx.ID()` is non-empty.
+    pub base_tx: txs::Tx,
+    /// The node to remove from the subnet validator set.
+    pub node_id: node::Id,
+    pub subnet_id: ids::Id,
+    pub subnet_auth: key::secp256k1::txs::Input,
+
+    /// To be updated after signing.
+    pub creds: Vec<key::secp256k1::txs::Credential>,
+}
+
+impl Default for Tx {
+    fn default() -> Self {
+        Self {
+            base_tx: txs::Tx::default(),
+            node_id: node::Id::empty(),
+            subnet_id: ids::Id::empty(),
+            subnet_auth: key::secp256k1::txs::Input::default(),
+            creds: Vec::new(),
+        }
+    }
+}
+
+impl Tx {
+    #[must_use]
+    pub fn new(base_tx: txs::Tx) -> Self {
+        Self {
+            base_tx,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the transaction ID.
+    /// Only non-empty if the embedded metadata is updated
+    /// with the signing process.
+    #[must_use]
+    pub fn tx_id(&self) -> ids::Id {
+        self.base_tx
+            .metadata
+            .as_ref()
+            .map_or_else(ids::Id::default, |m| m.id)
+    }
+
+    #[must_use]
+    pub fn type_name() -> String {
+        "platformvm.RemoveSubnetValidatorTx".to_string()
+    }
+
+    /// Returns the type ID for this transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type name is not found in the codec registry.
+    #[must_use]
+    pub fn type_id() -> u32 {
+        u32::try_from(*(codec::P_TYPES.get(&Self::type_name()).unwrap())).unwrap()
+    }
+
+    /// Signs the transaction with the provided signers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if packing or signing fails.
+    ///
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm/txs#Tx.Sign>
+    pub async fn sign<T: key::secp256k1::SignOnly + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+    ) -> Result<()> {
+        self.sign_with_cache(signers, &mut key::secp256k1::txs::SignatureCache::new())
+            .await
+    }
+
+    /// Same as "sign" but with the [`key::secp256k1::txs::SignatureCache`]
+    /// of the signing session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serialization or signing fails.
+    pub async fn sign_with_cache<T: key::secp256k1::SignOnly + Send + Sync>(
+        &mut self,
+        signers: Vec<Vec<T>>,
+        cache: &mut key::secp256k1::txs::SignatureCache,
+    ) -> Result<()> {
+        // marshal "unsigned tx" with the type Ids of the codec version,
+        // including the nested types
+        let codec_version = self.base_tx.codec_version;
+        // marshal under the registry lock, which must not be held
+        // across the remote signing
+        let (packer, tx_bytes_with_no_signature, tx_bytes_hash, cred_type_id) = {
+            let registry = codec::p_registry()?;
+            let type_id = registry.resolve_type_id(codec_version, &Self::type_name())?;
+            let cred_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Credential::type_name())?;
+            let packer = self.base_tx.pack(&registry, codec_version, type_id)?;
+
+            // reuse the underlying packer to avoid marshaling the unsigned tx twice
+            // just marshal the next fields in the struct and pack them all together
+            // in the existing packer
+            let unsigned_tx_bytes = packer.take_bytes();
+            packer.set_bytes(&unsigned_tx_bytes);
+
+            // pack the second field "node_id" in the struct
+            packer.pack_bytes(self.node_id.as_ref())?;
+
+            // pack the third field "subnet_id" in the struct
+            packer.pack_bytes(self.subnet_id.as_ref())?;
+
+            // pack the fourth field "subnet_auth" in the struct
+            let subnet_auth_type_id = registry
+                .resolve_type_id(codec_version, &key::secp256k1::txs::Input::type_name())?;
+            packer.pack_u32(subnet_auth_type_id)?;
+            packer.pack_u32(u32::try_from(self.subnet_auth.sig_indices.len())?)?;
+            for sig_idx in &self.subnet_auth.sig_indices {
+                packer.pack_u32(*sig_idx)?;
+            }
+
+            // take bytes just for hashing computation
+            let tx_bytes_with_no_signature = packer.take_bytes();
+            packer.set_bytes(&tx_bytes_with_no_signature);
+
+            // compute sha256 for marshaled "unsigned tx" bytes
+            // IMPORTANT: take the hash only for the type "platformvm.RemoveSubnetValidatorTx" unsigned tx
+            // not other fields -- only hash "platformvm.RemoveSubnetValidatorTx.*" but not "platformvm.Tx.Creds"
+            let tx_bytes_hash = hash::sha256(&tx_bytes_with_no_signature);
+            (
+                packer,
+                tx_bytes_with_no_signature,
+                tx_bytes_hash,
+                cred_type_id,
+            )
+        };
+
+        // number of of credentials
+        let creds_len = u32::try_from(signers.len())?;
+        // pack the fifth field in the struct
+        packer.pack_u32(creds_len)?;
+
+        // sign the hash with the signers (in case of multi-sig)
+        // and combine all signatures into a secp256k1fx credential
+        self.creds =
+            key::secp256k1::txs::sign_credentials_with_cache(&tx_bytes_hash, &signers, cache)
+                .await?;
+        if creds_len > 0 {
+            // pack each "cred" which is "secp256k1fx.Credential"
+            for cred in &self.creds {
+                // marshal type ID for "secp256k1fx.Credential"
+                packer.pack_u32(cred_type_id)?;
+
+                // marshal fields for "secp256k1fx.Credential"
+                packer.pack_u32(u32::try_from(cred.signatures.len())?)?;
+                for sig in &cred.signatures {
+                    packer.pack_bytes(sig)?;
+                }
+            }
+        }
+        let tx_bytes_with_signatures = packer.take_bytes();
+        let tx_id = hash::sha256(&tx_bytes_with_signatures);
+
+        // update "BaseTx.Metadata" with id/unsigned bytes/bytes
+        // ref. "avalanchego/vms/platformvm.Tx.Sign"
+        // ref. "avalanchego/vms/components/avax.BaseTx.Metadata.Initialize"
+        self.base_tx.metadata = Some(txs::Metadata {
+            id: ids::Id::from_slice(&tx_id),
+            tx_bytes_with_no_signature: tx_bytes_with_no_signature.to_vec(),
+            tx_bytes_with_signatures: tx_bytes_with_signatures.to_vec(),
+        });
+        crate::packer::pool::shared()
+            .recycle([tx_bytes_with_no_signature, tx_bytes_with_signatures]);
+
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `platformvm::txs::remove_subnet_validator::test_remove_subnet_validator_tx_serialization` --exact --show-output
+#[test]
+fn test_remove_subnet_validator_tx_serialization() {
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let mut tx = Tx {
+        base_tx: txs::Tx {
+            network_id: 1_000_000,
+            ..txs::Tx::default()
+        },
+        node_id: node::Id::from_slice(&[
+            0xca, 0xc3, 0x1b, 0x23, 0x7f, 0x96, 0x40, 0xd5, 0x01, 0x11, //
+            0xbe, 0x86, 0xb9, 0x58, 0x73, 0x0a, 0xfb, 0x70, 0x5e, 0x0f, //
+        ]),
+        subnet_id: ids::Id::from_slice(&[0xdd; 32]),
+        subnet_auth: key::secp256k1::txs::Input {
+            sig_indices: vec![0_u32],
+        },
+        ..Tx::default()
+    };
+    assert_eq!(tx.tx_id(), ids::Id::default());
+
+    let test_key = key::secp256k1::private_key::Key::from_cb58(
+        "PrivateKey-2kqWNDaqUKQyE4ZsV5GLCGeizE6sHAJVyjnfjXoXrtcZpK9M67",
+    )
+    .expect("failed to load private key");
+    ab!(tx.sign(vec![vec![test_key]])).expect("failed to sign");
+    let tx_metadata = tx.base_tx.metadata.clone().unwrap();
+
+    let mut expected_unsigned_bytes: Vec<u8> = vec![
+        // codec version
+        0x00, 0x00, //
+        //
+        // platformvm.RemoveSubnetValidatorTx type ID
+        0x00, 0x00, 0x00, 0x17, //
+        //
+        // network id
+        0x00, 0x0f, 0x42, 0x40, //
+    ];
+    // blockchain id
+    expected_unsigned_bytes.extend_from_slice(&[0x00; 32]);
+    expected_unsigned_bytes.extend_from_slice(&[
+        // outs.len()
+        0x00, 0x00, 0x00, 0x00, //
+        //
+        // ins.len()
+        0x00, 0x00, 0x00, 0x00, //
+        //
+        // memo.len()
+        0x00, 0x00, 0x00, 0x00, //
+        //
+        // node_id
+        0xca, 0xc3, 0x1b, 0x23, 0x7f, 0x96, 0x40, 0xd5, 0x01, 0x11, //
+        0xbe, 0x86, 0xb9, 0x58, 0x73, 0x0a, 0xfb, 0x70, 0x5e, 0x0f, //
+    ]);
+    // subnet_id
+    expected_unsigned_bytes.extend_from_slice(&[0xdd; 32]);
+    expected_unsigned_bytes.extend_from_slice(&[
+        // "secp256k1fx.Input" type ID
+        0x00, 0x00, 0x00, 0x0a, //
+        //
+        // "secp256k1fx.Input.sig_indices.len()"
+        0x00, 0x00, 0x00, 0x01, //
+        //
+        // "secp256k1fx.Input.sig_indices[0]"
+        0x00, 0x00, 0x00, 0x00, //
+    ]);
+    assert!(cmp_manager::eq_vectors(
+        &expected_unsigned_bytes,
+        &tx_metadata.tx_bytes_with_no_signature
+    ));
+
+    // one credential with one 65-byte signature
+    assert_eq!(tx.creds.len(), 1);
+    assert_eq!(tx.creds[0].signatures[0].len(), 65);
+    let signed_len = expected_unsigned_bytes.len() + 4 + 4 + 4 + 65;
+    assert_eq!(tx_metadata.tx_bytes_with_signatures.len(), signed_len);
+    assert_eq!(
+        tx.tx_id(),
+        ids::Id::from_slice(&hash::sha256(&tx_metadata.tx_bytes_with_signatures))
+    );
+}
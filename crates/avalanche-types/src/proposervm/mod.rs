@@ -0,0 +1,2 @@
+//! Snowman++ ("proposervm") utilities.
+pub mod windower;
@@ -0,0 +1,703 @@
+//! Proposer windows of the Snowman++ "proposervm", to predict which validators
+//! may propose the next block and how long each has to wait.
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/proposervm/proposer>
+use std::{collections::HashMap, time::Duration};
+
+use crate::{
+    errors::{Error, Result},
+    ids::{self, node},
+};
+
+/// Duration of each proposer window (slot).
+/// ref. "avalanchego/vms/proposervm/proposer.WindowDuration"
+pub const WINDOW_DURATION: Duration = Duration::from_secs(5);
+
+/// Number of windows a block is verified against before anyone can propose
+/// (pre-Durango).
+/// ref. "avalanchego/vms/proposervm/proposer.MaxVerifyWindows"
+pub const MAX_VERIFY_WINDOWS: usize = 6;
+
+/// Number of windows the proposers are computed for when building
+/// (pre-Durango).
+/// ref. "avalanchego/vms/proposervm/proposer.MaxBuildWindows"
+pub const MAX_BUILD_WINDOWS: usize = 60;
+
+/// Number of slots looked ahead for the next slot of a proposer (post-Durango).
+/// ref. "avalanchego/vms/proposervm/proposer.MaxLookAheadSlots"
+pub const MAX_LOOK_AHEAD_SLOTS: u64 = 720;
+
+/// Delay of the slots looked ahead from the first slot.
+/// ref. "avalanchego/vms/proposervm/proposer.MaxLookAheadWindow"
+pub const MAX_LOOK_AHEAD_WINDOW: Duration =
+    Duration::from_secs(WINDOW_DURATION.as_secs() * MAX_LOOK_AHEAD_SLOTS);
+
+/// Computes the proposers of a chain from the validator set at the P-chain height
+/// of the parent block, the same as the "proposervm" does.
+///
+/// The validator set is given as the node IDs and their weights
+/// (e.g., from "`validators::State::get_validator_set`").
+///
+/// ref. "avalanchego/vms/proposervm/proposer.windower"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Windower {
+    /// Seed derived from the chain ID, so each chain has its own proposers.
+    chain_source: u64,
+}
+
+impl Windower {
+    /// ref. "avalanchego/vms/proposervm/proposer.New"
+    #[must_use]
+    pub fn new(chain_id: &ids::Id) -> Self {
+        let mut b = [0_u8; 8];
+        b.copy_from_slice(&chain_id.as_ref()[..8]);
+        Self {
+            chain_source: u64::from_be_bytes(b),
+        }
+    }
+
+    /// Returns the proposers of the first "`max_windows`" windows for the block height,
+    /// where the i-th proposer may build after "i * [`WINDOW_DURATION`]" (pre-Durango).
+    /// A validator may appear multiple times, in proportion to its weight.
+    /// ref. "avalanchego/vms/proposervm/proposer.Windower.Proposers"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn proposers(
+        &self,
+        validators: &[(node::Id, u64)],
+        block_height: u64,
+        max_windows: usize,
+    ) -> Result<Vec<node::Id>> {
+        let (node_ids, sampler) = make_sampler(validators)?;
+        let num_to_sample = u64::try_from(max_windows)
+            .unwrap_or(u64::MAX)
+            .min(sampler.total_weight());
+
+        // the 32-bit source is kept for the legacy proposers
+        let mut source = Mt19937::new(self.chain_source ^ block_height);
+        let mut uniform = UniformReplacer::new(sampler.total_weight());
+        let mut proposers = Vec::with_capacity(usize::try_from(num_to_sample).unwrap_or(0));
+        for _ in 0..num_to_sample {
+            let value = uniform.next(&mut source)?;
+            proposers.push(node_ids[sampler.sample(value)?]);
+        }
+        Ok(proposers)
+    }
+
+    /// Returns how long the validator must wait after the parent block
+    /// before proposing the block at the height (pre-Durango).
+    /// Validators not among the proposers wait for all "`max_windows`".
+    /// ref. "avalanchego/vms/proposervm/proposer.Windower.Delay"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn delay(
+        &self,
+        validators: &[(node::Id, u64)],
+        block_height: u64,
+        node_id: &node::Id,
+        max_windows: usize,
+    ) -> Result<Duration> {
+        let max_delay = WINDOW_DURATION * u32::try_from(max_windows).unwrap_or(u32::MAX);
+        if node_id.is_empty() {
+            return Ok(max_delay);
+        }
+
+        let proposers = self.proposers(validators, block_height, max_windows)?;
+        let window = proposers
+            .iter()
+            .position(|p| p == node_id)
+            .unwrap_or(proposers.len());
+        Ok(WINDOW_DURATION * u32::try_from(window).unwrap_or(u32::MAX))
+    }
+
+    /// Returns the only validator allowed to propose the block at the height
+    /// in the slot (post-Durango), or "None" if the validator set is empty
+    /// so anyone can propose.
+    /// ref. "avalanchego/vms/proposervm/proposer.Windower.ExpectedProposer"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows or is zero.
+    pub fn expected_proposer(
+        &self,
+        validators: &[(node::Id, u64)],
+        block_height: u64,
+        slot: u64,
+    ) -> Result<Option<node::Id>> {
+        if validators.is_empty() {
+            return Ok(None);
+        }
+        let (node_ids, sampler) = make_sampler(validators)?;
+        self.sample_proposer(&node_ids, &sampler, block_height, slot)
+            .map(Some)
+    }
+
+    /// Returns how long after the parent block the validator can first propose
+    /// the block at the height, looking ahead from the start slot (post-Durango).
+    /// Returns the delay of the slot after the last one looked ahead if the
+    /// validator is not expected within [`MAX_LOOK_AHEAD_SLOTS`], and zero
+    /// if anyone can propose.
+    /// ref. "avalanchego/vms/proposervm/proposer.Windower.MinDelayForProposer"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows or is zero.
+    pub fn min_delay_for_proposer(
+        &self,
+        validators: &[(node::Id, u64)],
+        block_height: u64,
+        node_id: &node::Id,
+        start_slot: u64,
+    ) -> Result<Duration> {
+        if validators.is_empty() {
+            return Ok(Duration::ZERO);
+        }
+        let (node_ids, sampler) = make_sampler(validators)?;
+
+        let max_slot = start_slot.saturating_add(MAX_LOOK_AHEAD_SLOTS);
+        for slot in start_slot..max_slot {
+            if self.sample_proposer(&node_ids, &sampler, block_height, slot)? == *node_id {
+                return Ok(slot_delay(slot));
+            }
+        }
+        Ok(slot_delay(max_slot))
+    }
+
+    /// Samples the only proposer of the slot (post-Durango).
+    /// ref. "avalanchego/vms/proposervm/proposer.windower.expectedProposer"
+    fn sample_proposer(
+        self,
+        node_ids: &[node::Id],
+        sampler: &WeightedHeap,
+        block_height: u64,
+        slot: u64,
+    ) -> Result<node::Id> {
+        let mut source = Mt19937_64::new(self.chain_source ^ block_height ^ slot.reverse_bits());
+        let value = UniformReplacer::new(sampler.total_weight()).next(&mut source)?;
+        Ok(node_ids[sampler.sample(value)?])
+    }
+}
+
+/// Returns the slot of the time since the parent block timestamp
+/// (both in unix seconds), or zero if the time is before the parent.
+/// ref. "avalanchego/vms/proposervm/proposer.TimeToSlot"
+#[must_use]
+pub const fn time_to_slot(parent_timestamp: u64, now: u64) -> u64 {
+    if now < parent_timestamp {
+        return 0;
+    }
+    (now - parent_timestamp) / WINDOW_DURATION.as_secs()
+}
+
+/// Returns the delay from the parent block until the slot.
+const fn slot_delay(slot: u64) -> Duration {
+    Duration::from_secs(WINDOW_DURATION.as_secs().saturating_mul(slot))
+}
+
+/// Sorts the validators by node ID, as the "proposervm" samples from
+/// the validators in that order.
+fn make_sampler(validators: &[(node::Id, u64)]) -> Result<(Vec<node::Id>, WeightedHeap)> {
+    let mut sorted = validators.to_vec();
+    sorted.sort_by_key(|(node_id, _)| *node_id);
+
+    let weights: Vec<u64> = sorted.iter().map(|(_, w)| *w).collect();
+    let sampler = WeightedHeap::new(&weights)?;
+    Ok((sorted.into_iter().map(|(id, _)| id).collect(), sampler))
+}
+
+/// Deterministic source of the "proposervm" sampler.
+/// ref. "avalanchego/utils/sampler.Source"
+trait Source {
+    /// Returns the next pseudo-random value.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly distributed value in [0, n].
+    /// ref. "avalanchego/utils/sampler.rng.Uint64Inclusive"
+    fn uint64_inclusive(&mut self, n: u64) -> u64 {
+        if n & n.wrapping_add(1) == 0 {
+            // n+1 is a power of two
+            return self.next_u64() & n;
+        }
+        if n > i64::MAX as u64 {
+            loop {
+                let v = self.next_u64();
+                if v <= n {
+                    return v;
+                }
+            }
+        }
+
+        let max = i64::MAX as u64 - (1_u64 << 63) % (n + 1);
+        loop {
+            let v = self.next_u64() & i64::MAX as u64;
+            if v <= max {
+                return v % (n + 1);
+            }
+        }
+    }
+}
+
+/// 32-bit Mersenne Twister, the source of the pre-Durango proposers.
+/// ref. `gonum.org/v1/gonum/mathext/prng.MT19937`
+struct Mt19937 {
+    /// State vector.
+    mt: [u32; Self::N],
+    /// Index of the next state to temper, "N" to regenerate the state.
+    mti: usize,
+}
+
+impl Mt19937 {
+    /// Degree of recurrence (number of states).
+    const N: usize = 624;
+    /// Middle word offset of the recurrence.
+    const M: usize = 397;
+    /// Coefficients of the rational normal form twist matrix.
+    const MATRIX_A: u32 = 0x9908_B0DF;
+    /// Most significant bit.
+    const UPPER_MASK: u32 = 0x8000_0000;
+    /// Least significant 31 bits.
+    const LOWER_MASK: u32 = 0x7FFF_FFFF;
+
+    /// Initializes the state with the lower 32 bits of the seed.
+    /// ref. `gonum.org/v1/gonum/mathext/prng.MT19937.Seed`
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(seed: u64) -> Self {
+        let mut mt = [0_u32; Self::N];
+        mt[0] = seed as u32;
+        for i in 1..Self::N {
+            mt[i] = 1_812_433_253_u32
+                .wrapping_mul(mt[i - 1] ^ (mt[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        Self { mt, mti: Self::N }
+    }
+
+    /// Returns the next pseudo-random 32-bit value, regenerating the state
+    /// every "N" values.
+    /// ref. `gonum.org/v1/gonum/mathext/prng.MT19937.Uint32`
+    fn next_u32(&mut self) -> u32 {
+        let mag01 = [0, Self::MATRIX_A];
+        if self.mti >= Self::N {
+            for i in 0..Self::N {
+                let y = (self.mt[i] & Self::UPPER_MASK)
+                    | (self.mt[(i + 1) % Self::N] & Self::LOWER_MASK);
+                self.mt[i] =
+                    self.mt[(i + Self::M) % Self::N] ^ (y >> 1) ^ mag01[usize::from(y & 1 == 1)];
+            }
+            self.mti = 0;
+        }
+
+        let mut y = self.mt[self.mti];
+        self.mti += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9D2C_5680;
+        y ^= (y << 15) & 0xEFC6_0000;
+        y ^= y >> 18;
+        y
+    }
+}
+
+impl Source for Mt19937 {
+    /// Concatenates two 32-bit values, the first in the upper bits.
+    /// ref. `gonum.org/v1/gonum/mathext/prng.MT19937.Uint64`
+    fn next_u64(&mut self) -> u64 {
+        let high = u64::from(self.next_u32());
+        let low = u64::from(self.next_u32());
+        (high << 32) | low
+    }
+}
+
+/// 64-bit Mersenne Twister, the source of the post-Durango proposers.
+/// ref. `gonum.org/v1/gonum/mathext/prng.MT19937_64`
+struct Mt19937_64 {
+    /// State vector.
+    mt: [u64; Self::NN],
+    /// Index of the next state to temper, "NN" to regenerate the state.
+    mti: usize,
+}
+
+impl Mt19937_64 {
+    /// Degree of recurrence (number of states).
+    const NN: usize = 312;
+    /// Middle word offset of the recurrence.
+    const MM: usize = 156;
+    /// Coefficients of the rational normal form twist matrix.
+    const MATRIX_A: u64 = 0xB502_6F5A_A966_19E9;
+    /// Most significant 33 bits.
+    const UPPER_MASK: u64 = 0xFFFF_FFFF_8000_0000;
+    /// Least significant 31 bits.
+    const LOWER_MASK: u64 = 0x7FFF_FFFF;
+
+    /// Initializes the state with the seed.
+    /// ref. `gonum.org/v1/gonum/mathext/prng.MT19937_64.Seed`
+    fn new(seed: u64) -> Self {
+        let mut mt = [0_u64; Self::NN];
+        mt[0] = seed;
+        for i in 1..Self::NN {
+            mt[i] = 6_364_136_223_846_793_005_u64
+                .wrapping_mul(mt[i - 1] ^ (mt[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+        Self { mt, mti: Self::NN }
+    }
+}
+
+impl Source for Mt19937_64 {
+    /// Returns the next pseudo-random value, regenerating the state
+    /// every "NN" values.
+    /// ref. `gonum.org/v1/gonum/mathext/prng.MT19937_64.Uint64`
+    fn next_u64(&mut self) -> u64 {
+        let mag01 = [0, Self::MATRIX_A];
+        if self.mti >= Self::NN {
+            for i in 0..Self::NN {
+                let x = (self.mt[i] & Self::UPPER_MASK)
+                    | (self.mt[(i + 1) % Self::NN] & Self::LOWER_MASK);
+                self.mt[i] =
+                    self.mt[(i + Self::MM) % Self::NN] ^ (x >> 1) ^ mag01[usize::from(x & 1 == 1)];
+            }
+            self.mti = 0;
+        }
+
+        let mut x = self.mt[self.mti];
+        self.mti += 1;
+        x ^= (x >> 29) & 0x5555_5555_5555_5555;
+        x ^= (x << 17) & 0x71D6_7FFF_EDA6_0000;
+        x ^= (x << 37) & 0xFFF7_EEE0_0000_0000;
+        x ^= x >> 43;
+        x
+    }
+}
+
+/// Draws the units of weight without replacement.
+/// Unlike [`crate::utils::sampler::Uniform`], the draws follow the
+/// "proposervm" source so the proposers match the other nodes.
+/// ref. "avalanchego/utils/sampler.uniformReplacer"
+struct UniformReplacer {
+    /// Number of values to draw from, i.e., [0, length).
+    length: u64,
+    /// Values swapped in for the drawn ones.
+    drawn: HashMap<u64, u64>,
+    /// Number of values drawn so far.
+    drawn_count: u64,
+}
+
+impl UniformReplacer {
+    /// Creates a sampler of the values in [0, length).
+    fn new(length: u64) -> Self {
+        Self {
+            length,
+            drawn: HashMap::new(),
+            drawn_count: 0,
+        }
+    }
+
+    /// Draws the next value not drawn yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all values have been drawn.
+    fn next<S: Source>(&mut self, source: &mut S) -> Result<u64> {
+        if self.drawn_count >= self.length {
+            return Err(Error::Other {
+                message: format!("out of range; all {} values drawn", self.length),
+                retryable: false,
+            });
+        }
+
+        // draws from the values not drawn yet in [drawn_count, length),
+        // and swaps the first of them in for the drawn value
+        let draw = source.uint64_inclusive(self.length - 1 - self.drawn_count) + self.drawn_count;
+        let ret = self.drawn.get(&draw).copied().unwrap_or(draw);
+        let replace = self
+            .drawn
+            .get(&self.drawn_count)
+            .copied()
+            .unwrap_or(self.drawn_count);
+        self.drawn.insert(draw, replace);
+        self.drawn_count += 1;
+
+        Ok(ret)
+    }
+}
+
+/// Maps a unit of weight to the index of its weight, where the weights
+/// are kept in a heap by descending weight.
+/// ref. "avalanchego/utils/sampler.weightedHeap"
+struct WeightedHeap {
+    /// (weight, cumulative weight of the subtree, index)
+    heap: Vec<(u64, u64, usize)>,
+}
+
+impl WeightedHeap {
+    /// Builds the heap of the weights.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    fn new(weights: &[u64]) -> Result<Self> {
+        let mut heap: Vec<(u64, u64, usize)> = weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (*w, *w, i))
+            .collect();
+        heap.sort_by(|a, b| b.0.cmp(&a.0).then(a.2.cmp(&b.2)));
+
+        for i in (1..heap.len()).rev() {
+            let parent = (i - 1) / 2;
+            heap[parent].1 = heap[parent]
+                .1
+                .checked_add(heap[i].1)
+                .ok_or_else(|| Error::Other {
+                    message: "total weight overflows u64".to_string(),
+                    retryable: false,
+                })?;
+        }
+        Ok(Self { heap })
+    }
+
+    /// Returns the sum of the weights.
+    fn total_weight(&self) -> u64 {
+        self.heap.first().map_or(0, |e| e.1)
+    }
+
+    /// Returns the index of the weight the unit of weight "value" falls in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not less than the total weight.
+    fn sample(&self, mut value: u64) -> Result<usize> {
+        if value >= self.total_weight() {
+            return Err(Error::Other {
+                message: format!(
+                    "out of range; value {value} >= total weight {}",
+                    self.total_weight()
+                ),
+                retryable: false,
+            });
+        }
+
+        let mut i = 0;
+        loop {
+            let (weight, _, index) = self.heap[i];
+            if value < weight {
+                return Ok(index);
+            }
+            value -= weight;
+
+            // left child, or the right child past its subtree
+            i = i * 2 + 1;
+            let left = self.heap[i].1;
+            if left <= value {
+                value -= left;
+                i += 1;
+            }
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_mt19937_64` --exact --show-output
+#[test]
+fn test_mt19937_64() {
+    // reference output of "init_genrand64(5489)"
+    let mut source = Mt19937_64::new(5489);
+    assert_eq!(source.next_u64(), 14_514_284_786_278_117_030);
+    assert_eq!(source.next_u64(), 4_620_546_740_167_642_908);
+
+    for n in [0, 1, 3, 5, 1_000, u64::MAX - 1] {
+        for _ in 0..100 {
+            assert!(source.uint64_inclusive(n) <= n);
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_mt19937` --exact --show-output
+#[test]
+fn test_mt19937() {
+    // reference output of "init_genrand(5489)"
+    let mut source = Mt19937::new(5489);
+    assert_eq!(source.next_u32(), 3_499_211_612);
+    assert_eq!(source.next_u32(), 581_869_302);
+    assert_eq!(source.next_u64(), (0xE7E1_FAEE_u64 << 32) | 0xD5C3_1F79);
+
+    // only the lower 32 bits of the seed (5489) are used
+    assert_eq!(Mt19937::new(0x1_0000_1571).next_u32(), 3_499_211_612);
+}
+
+/// Validators of the avalanchego windower tests, the node IDs
+/// "{1}", "{2}", ... each of weight 1.
+#[cfg(test)]
+fn test_validators(n: u8) -> Vec<(node::Id, u64)> {
+    (1..=n)
+        .map(|i| {
+            let mut b = [0_u8; node::LEN];
+            b[0] = i;
+            (node::Id::from_slice(&b), 1)
+        })
+        .collect()
+}
+
+/// Windower of the chain ID "{0, 2}" of the avalanchego windower tests.
+#[cfg(test)]
+fn test_windower_of_chain() -> Windower {
+    let mut chain_id = [0_u8; ids::LEN];
+    chain_id[1] = 2;
+    Windower::new(&ids::Id::from_slice(&chain_id))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_windower_change_by_height` --exact --show-output
+/// ref. "avalanchego/vms/proposervm/proposer.TestWindowerChangeByHeight"
+#[test]
+fn test_windower_change_by_height() {
+    let w = test_windower_of_chain();
+    let validators = test_validators(6);
+
+    for (block_height, expected_windows) in [(1, [2, 5, 3, 4, 0, 1]), (2, [5, 1, 3, 4, 0, 2])] {
+        for ((node_id, _), expected) in validators.iter().zip(expected_windows) {
+            assert_eq!(
+                w.delay(&validators, block_height, node_id, MAX_VERIFY_WINDOWS)
+                    .unwrap(),
+                WINDOW_DURATION * expected
+            );
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_expected_proposer_change` --exact --show-output
+/// ref. "avalanchego/vms/proposervm/proposer.TestExpectedProposerChangeByHeight"
+/// ref. "avalanchego/vms/proposervm/proposer.TestExpectedProposerChangeBySlot"
+#[test]
+fn test_expected_proposer_change() {
+    let w = test_windower_of_chain();
+    let validators = test_validators(10);
+
+    for (block_height, expected) in [(1, 2), (2, 1)] {
+        assert_eq!(
+            w.expected_proposer(&validators, block_height, 0).unwrap(),
+            Some(validators[expected].0)
+        );
+    }
+    for (slot, expected) in [2, 0, 9, 7, 0, 3, 3, 3, 3, 3].into_iter().enumerate() {
+        assert_eq!(
+            w.expected_proposer(&validators, 1, slot as u64).unwrap(),
+            Some(validators[expected].0)
+        );
+    }
+
+    // the first slot of the proposer from the start slot, or the slot
+    // after the last one looked ahead
+    let proposer = validators[3].0;
+    assert_eq!(
+        w.min_delay_for_proposer(&validators, 1, &proposer, 0)
+            .unwrap(),
+        WINDOW_DURATION * 5
+    );
+    assert_eq!(
+        w.min_delay_for_proposer(&validators, 1, &proposer, 6)
+            .unwrap(),
+        WINDOW_DURATION * 6
+    );
+    let not_validator = node::Id::from_slice(&[0xff; node::LEN]);
+    assert_eq!(
+        w.min_delay_for_proposer(&validators, 1, &not_validator, 10)
+            .unwrap(),
+        WINDOW_DURATION * (10 + 720)
+    );
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_windower` --exact --show-output
+#[test]
+fn test_windower() {
+    let w = Windower::new(&ids::Id::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]));
+    let node_ids: Vec<node::Id> = (1..=10_u8)
+        .map(|i| node::Id::from_slice(&[i; 20]))
+        .collect();
+    let validators: Vec<(node::Id, u64)> = node_ids.iter().map(|id| (*id, 1)).collect();
+
+    // every unit of weight is drawn once
+    let proposers = w.proposers(&validators, 1, MAX_BUILD_WINDOWS).unwrap();
+    assert_eq!(proposers.len(), 10);
+    let mut sorted = proposers.clone();
+    sorted.sort();
+    assert_eq!(sorted, node_ids);
+
+    // deterministic regardless of the input order
+    let mut reversed = validators.clone();
+    reversed.reverse();
+    assert_eq!(
+        w.proposers(&reversed, 1, MAX_BUILD_WINDOWS).unwrap(),
+        proposers
+    );
+    assert_eq!(
+        w.proposers(&validators, 1, MAX_VERIFY_WINDOWS).unwrap(),
+        proposers[..MAX_VERIFY_WINDOWS]
+    );
+
+    for (i, p) in proposers[..MAX_VERIFY_WINDOWS].iter().enumerate() {
+        let delay = w.delay(&validators, 1, p, MAX_VERIFY_WINDOWS).unwrap();
+        assert_eq!(delay, WINDOW_DURATION * u32::try_from(i).unwrap());
+    }
+    let not_validator = node::Id::from_slice(&[0xff; 20]);
+    assert_eq!(
+        w.delay(&validators, 1, &not_validator, MAX_VERIFY_WINDOWS)
+            .unwrap(),
+        WINDOW_DURATION * 6
+    );
+
+    // post-Durango
+    let expected = w.expected_proposer(&validators, 1, 0).unwrap().unwrap();
+    assert!(node_ids.contains(&expected));
+    assert_eq!(
+        w.min_delay_for_proposer(&validators, 1, &expected, 0)
+            .unwrap(),
+        Duration::ZERO
+    );
+    assert_eq!(
+        w.min_delay_for_proposer(&validators, 1, &not_validator, 0)
+            .unwrap(),
+        MAX_LOOK_AHEAD_WINDOW
+    );
+    assert!(w.expected_proposer(&[], 1, 0).unwrap().is_none());
+
+    // zero weights are never sampled
+    let weighted = vec![(node_ids[0], 0), (node_ids[1], 3)];
+    for slot in 0..10 {
+        assert_eq!(
+            w.expected_proposer(&weighted, 1, slot).unwrap(),
+            Some(node_ids[1])
+        );
+    }
+    assert!(w.expected_proposer(&[(node_ids[0], 0)], 1, 0).is_err());
+
+    assert_eq!(time_to_slot(100, 99), 0);
+    assert_eq!(time_to_slot(100, 104), 0);
+    assert_eq!(time_to_slot(100, 112), 2);
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `proposervm::windower::test_windower_repeated_validator` --exact --show-output
+/// ref. "avalanchego/vms/proposervm/proposer.TestWindowerRepeatedValidator"
+#[test]
+fn test_windower_repeated_validator() {
+    let w = test_windower_of_chain();
+    let validator = node::Id::from_slice(&[1; node::LEN]);
+    let validators = vec![(validator, 10)];
+
+    assert_eq!(
+        w.proposers(&validators, 1, MAX_VERIFY_WINDOWS).unwrap(),
+        vec![validator; MAX_VERIFY_WINDOWS]
+    );
+    assert_eq!(
+        w.delay(&validators, 1, &validator, MAX_VERIFY_WINDOWS)
+            .unwrap(),
+        Duration::ZERO
+    );
+    let not_validator = node::Id::from_slice(&[2; node::LEN]);
+    assert_eq!(
+        w.delay(&validators, 1, &not_validator, MAX_VERIFY_WINDOWS)
+            .unwrap(),
+        WINDOW_DURATION * 6
+    );
+}
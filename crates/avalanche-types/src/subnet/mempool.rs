@@ -0,0 +1,429 @@
+//! Fee-ordered transaction mempool for the VMs.
+//!
+//! Deduplicates the transactions by ID, evicts the lowest-fee transactions
+//! once full, notifies the consensus engine of the pending transactions, and
+//! gossips the locally submitted transactions to the peers.
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, Error, ErrorKind},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use tokio::sync::mpsc;
+
+use crate::{
+    ids,
+    subnet::rpc::snow::engine::common::{appsender::AppSender, message::Message},
+};
+
+/// Default maximum number of transactions in the mempool.
+pub const DEFAULT_MAX_TXS: usize = 4096;
+/// Default maximum total size of the transactions in the mempool.
+pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Transaction that can be held in the mempool.
+pub trait Tx: Send + Sync {
+    /// Unique ID of the transaction, which the mempool deduplicates by.
+    fn id(&self) -> ids::Id;
+    /// Fee the transaction pays per byte (or gas), the higher the sooner
+    /// it's included in a block. The ties are ordered first-in-first-out.
+    fn fee(&self) -> u64;
+    /// Encoded transaction, which is gossiped to the peers.
+    fn bytes(&self) -> &[u8];
+    /// Size of the transaction counted against "`Config::max_bytes`".
+    fn size(&self) -> usize {
+        self.bytes().len()
+    }
+}
+
+/// Limits of the mempool, beyond which the lowest-fee transactions are evicted.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Config {
+    /// Maximum number of transactions.
+    pub max_txs: usize,
+    /// Maximum total size of the transactions.
+    pub max_bytes: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_txs: DEFAULT_MAX_TXS,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+/// Orders the transactions by fee, then by arrival (the lower the sequence, the earlier),
+/// so the ties are built first-in-first-out and evicted last-in-first-out.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+struct Priority {
+    /// Fee of the transaction, see "`Tx::fee`".
+    fee: u64,
+    /// Arrival sequence, reversed so the earlier orders higher.
+    seq: std::cmp::Reverse<u64>,
+    /// Transaction ID, to tell apart the same fee and sequence.
+    id: ids::Id,
+}
+
+/// Transactions of the mempool, guarded by one lock.
+struct Inner<T> {
+    /// Transactions by ID, with their priority in "`by_priority`".
+    txs: HashMap<ids::Id, (Priority, T)>,
+    /// Ascending, so the first is evicted first and the last is built first.
+    by_priority: BTreeSet<Priority>,
+    /// Total size of the transactions.
+    bytes: usize,
+    /// Sequence of the next arriving transaction.
+    seq: u64,
+}
+
+impl<T: Tx> Inner<T> {
+    /// Removes the transaction from both indexes.
+    fn remove(&mut self, id: &ids::Id) -> Option<T> {
+        let (priority, tx) = self.txs.remove(id)?;
+        self.by_priority.remove(&priority);
+        self.bytes -= tx.size();
+        Some(tx)
+    }
+
+    /// Returns "true" if adding a transaction of the size exceeds the limits.
+    fn is_full(&self, config: &Config, size: usize) -> bool {
+        self.txs.len() >= config.max_txs || self.bytes + size > config.max_bytes
+    }
+}
+
+/// Mempool of the transactions pending to be included in a block.
+/// Clones share the same transactions.
+pub struct Mempool<T: Tx> {
+    /// Limits of the mempool.
+    config: Config,
+    /// Transactions shared by the clones.
+    inner: Arc<Mutex<Inner<T>>>,
+    /// Channel to notify the engine of the pending transactions.
+    to_engine: Option<mpsc::Sender<Message>>,
+    /// Sender to gossip the locally submitted transactions.
+    app_sender: Option<Box<dyn AppSender + Send + Sync>>,
+}
+
+impl<T: Tx> Clone for Mempool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config,
+            inner: Arc::clone(&self.inner),
+            to_engine: self.to_engine.clone(),
+            app_sender: self.app_sender.clone(),
+        }
+    }
+}
+
+impl<T: Tx> Default for Mempool<T> {
+    fn default() -> Self {
+        Self::new_unchecked(Config::default())
+    }
+}
+
+impl<T: Tx> Mempool<T> {
+    /// Creates an empty mempool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if "`max_txs`" or "`max_bytes`" is zero,
+    /// which would not hold any transaction.
+    pub fn new(config: Config) -> io::Result<Self> {
+        if config.max_txs == 0 || config.max_bytes == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "mempool limits must be non-zero (max_txs {}, max_bytes {})",
+                    config.max_txs, config.max_bytes
+                ),
+            ));
+        }
+        Ok(Self::new_unchecked(config))
+    }
+
+    /// Creates an empty mempool without validating the limits.
+    fn new_unchecked(config: Config) -> Self {
+        Self {
+            config,
+            inner: Arc::new(Mutex::new(Inner {
+                txs: HashMap::new(),
+                by_priority: BTreeSet::new(),
+                bytes: 0,
+                seq: 0,
+            })),
+            to_engine: None,
+            app_sender: None,
+        }
+    }
+
+    /// Locks the transactions, recovering from a poisoned lock.
+    fn lock(&self) -> MutexGuard<'_, Inner<T>> {
+        // the state is consistent between the operations, so a panic cannot corrupt it
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sends "`Message::PendingTxs`" to the engine on the new transactions
+    /// (i.e., the channel passed to "`CommonVm::initialize`").
+    #[must_use]
+    pub fn with_engine(mut self, to_engine: mpsc::Sender<Message>) -> Self {
+        self.to_engine = Some(to_engine);
+        self
+    }
+
+    /// Gossips the locally submitted transactions via the app sender.
+    #[must_use]
+    pub fn with_app_sender(mut self, app_sender: Box<dyn AppSender + Send + Sync>) -> Self {
+        self.app_sender = Some(app_sender);
+        self
+    }
+
+    /// Adds the transaction submitted to this node (e.g., via the API),
+    /// and gossips it to the peers.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`ErrorKind::AlreadyExists`" if the transaction is already in the mempool,
+    /// or "`ErrorKind::OutOfMemory`" if the mempool is full of higher-fee transactions.
+    pub async fn add(&self, tx: T) -> io::Result<()> {
+        let bytes = tx.bytes().to_vec();
+        self.insert(tx)?;
+        self.notify();
+
+        if let Some(app_sender) = &self.app_sender {
+            if let Err(e) = app_sender.send_app_gossip(bytes).await {
+                log::warn!("failed to gossip tx: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the transaction gossiped from a peer, without gossiping it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`ErrorKind::AlreadyExists`" if the transaction is already in the mempool,
+    /// or "`ErrorKind::OutOfMemory`" if the mempool is full of higher-fee transactions.
+    pub fn add_remote(&self, tx: T) -> io::Result<()> {
+        self.insert(tx)?;
+        self.notify();
+        Ok(())
+    }
+
+    /// Inserts the transaction, evicting the lower-fee transactions if full.
+    fn insert(&self, tx: T) -> io::Result<()> {
+        let id = tx.id();
+        let size = tx.size();
+        let fee = tx.fee();
+        if size > self.config.max_bytes {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "tx {id} size {size} exceeds the mempool limit {}",
+                    self.config.max_bytes
+                ),
+            ));
+        }
+
+        let mut inner = self.lock();
+        if inner.txs.contains_key(&id) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("tx {id} already in mempool"),
+            ));
+        }
+
+        // only evicts if the new transaction pays more than all the evicted ones
+        let mut evict = Vec::new();
+        let (mut count, mut bytes) = (inner.txs.len(), inner.bytes);
+        for p in &inner.by_priority {
+            if count < self.config.max_txs && bytes + size <= self.config.max_bytes {
+                break;
+            }
+            if p.fee >= fee {
+                return Err(Error::new(
+                    ErrorKind::OutOfMemory,
+                    format!("mempool is full, tx {id} fee {fee} too low"),
+                ));
+            }
+            count -= 1;
+            bytes -= inner.txs[&p.id].1.size();
+            evict.push(p.id);
+        }
+        for evicted in &evict {
+            inner.remove(evicted);
+            log::debug!("evicted tx {evicted} for tx {id}");
+        }
+        debug_assert!(!inner.is_full(&self.config, size));
+
+        let priority = Priority {
+            fee,
+            seq: std::cmp::Reverse(inner.seq),
+            id,
+        };
+        inner.seq += 1;
+        inner.bytes += size;
+        inner.by_priority.insert(priority);
+        inner.txs.insert(id, (priority, tx));
+        Ok(())
+    }
+
+    /// Notifies the engine of the pending transactions, if any.
+    /// Call after building a block with the transactions left.
+    pub fn notify(&self) {
+        let Some(to_engine) = &self.to_engine else {
+            return;
+        };
+        if self.is_empty() {
+            return;
+        }
+        // a full channel already has the notification pending
+        if let Err(mpsc::error::TrySendError::Closed(_)) = to_engine.try_send(Message::PendingTxs) {
+            log::warn!("engine channel closed, dropping pending txs notification");
+        }
+    }
+
+    #[must_use]
+    pub fn has(&self, id: &ids::Id) -> bool {
+        self.lock().txs.contains_key(id)
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &ids::Id) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.lock().txs.get(id).map(|(_, tx)| tx.clone())
+    }
+
+    /// Removes the transaction (e.g., once accepted in a block).
+    pub fn remove(&self, id: &ids::Id) -> Option<T> {
+        self.lock().remove(id)
+    }
+
+    /// Removes the transactions, ignoring the ones not in the mempool.
+    pub fn remove_all<'a>(&self, ids: impl IntoIterator<Item = &'a ids::Id>) {
+        let mut inner = self.lock();
+        for id in ids {
+            inner.remove(id);
+        }
+    }
+
+    /// Removes and returns the highest-fee transactions for the next block,
+    /// up to "`max_txs`" and "`max_bytes`" in total.
+    /// A transaction that does not fit is left for the next block.
+    #[must_use]
+    pub fn take(&self, max_txs: usize, max_bytes: usize) -> Vec<T> {
+        let mut inner = self.lock();
+        let mut picked = Vec::new();
+        let mut bytes = 0;
+        for p in inner.by_priority.iter().rev() {
+            if picked.len() >= max_txs {
+                break;
+            }
+            let size = inner.txs[&p.id].1.size();
+            if bytes + size > max_bytes {
+                continue;
+            }
+            bytes += size;
+            picked.push(p.id);
+        }
+        picked.iter().filter_map(|id| inner.remove(id)).collect()
+    }
+
+    /// Returns the number of transactions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().txs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total size of the transactions.
+    #[must_use]
+    pub fn bytes(&self) -> usize {
+        self.lock().bytes
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::mempool::test_mempool` --exact --show-output
+#[tokio::test]
+async fn test_mempool() {
+    use crate::subnet::rpc::testing;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct TestTx {
+        id: ids::Id,
+        fee: u64,
+        bytes: Vec<u8>,
+    }
+
+    impl Tx for TestTx {
+        fn id(&self) -> ids::Id {
+            self.id
+        }
+        fn fee(&self) -> u64 {
+            self.fee
+        }
+        fn bytes(&self) -> &[u8] {
+            &self.bytes
+        }
+    }
+
+    let tx = |i: u8, fee: u64| TestTx {
+        id: ids::Id::from_slice(&[i; 32]),
+        fee,
+        bytes: vec![i; 10],
+    };
+
+    let (to_engine, mut from_vm) = mpsc::channel(1);
+    let sender = testing::AppSender::new();
+    for (max_txs, max_bytes) in [(0, 1024), (3, 0)] {
+        let err = Mempool::<TestTx>::new(Config { max_txs, max_bytes })
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    let mempool = Mempool::new(Config {
+        max_txs: 3,
+        max_bytes: 1024,
+    })
+    .unwrap()
+    .with_engine(to_engine)
+    .with_app_sender(Box::new(sender.clone()));
+
+    mempool.add(tx(1, 10)).await.unwrap();
+    assert_eq!(from_vm.try_recv().unwrap(), Message::PendingTxs);
+    assert_eq!(sender.take_gossip(), vec![vec![1; 10]]);
+
+    let err = mempool.add(tx(1, 10)).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    mempool.add_remote(tx(2, 30)).unwrap();
+    mempool.add_remote(tx(3, 10)).unwrap();
+    assert!(sender.take_gossip().is_empty());
+    assert_eq!(mempool.len(), 3);
+    assert_eq!(mempool.bytes(), 30);
+
+    // full, and the new tx pays no more than the lowest
+    let err = mempool.add_remote(tx(4, 10)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfMemory);
+
+    // evicts the latest of the lowest-fee txs
+    mempool.add_remote(tx(5, 20)).unwrap();
+    assert!(mempool.has(&tx(1, 10).id));
+    assert!(!mempool.has(&tx(3, 10).id));
+    assert_eq!(mempool.get(&tx(5, 20).id), Some(tx(5, 20)));
+
+    let ids = |txs: Vec<TestTx>| txs.into_iter().map(|t| t.id).collect::<Vec<_>>();
+    assert_eq!(ids(mempool.take(2, 1024)), vec![tx(2, 30).id, tx(5, 20).id]);
+    assert_eq!(mempool.take(10, 5), Vec::new());
+    mempool.remove_all([&tx(1, 10).id]);
+    assert!(mempool.is_empty());
+    assert_eq!(mempool.bytes(), 0);
+}
@@ -5,7 +5,9 @@
 //! * Complex Rust VM: [SpacesVM](https://github.com/ava-labs/spacesvm-rs)
 
 pub mod config;
+pub mod mempool;
 pub mod rpc;
+pub mod state;
 
 use std::io::{self, Error, ErrorKind};
 
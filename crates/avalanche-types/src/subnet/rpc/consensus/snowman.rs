@@ -1,6 +1,6 @@
 use std::io::Result;
 
-use crate::{choices::status::Status, ids::Id};
+use crate::{choices::status::Status, ids::Id, subnet::rpc::snowman::block::WithVerifyContext};
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/consensus/snowman#Block>
 #[tonic::async_trait]
@@ -25,6 +25,13 @@ pub trait Block: Decidable + Send + Sync {
 
     /// Returns error if the block can not be verified.
     async fn verify(&mut self) -> Result<()>;
+
+    /// Returns this block as [`WithVerifyContext`] if it implements the
+    /// verification against the P-chain context (e.g., for warp messages).
+    /// Returns "None" by default, so the block is always verified with [`Block::verify`].
+    fn with_verify_context(&mut self) -> Option<&mut dyn WithVerifyContext> {
+        None
+    }
 }
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/choices#Decidable>
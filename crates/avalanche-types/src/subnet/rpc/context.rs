@@ -1,16 +1,24 @@
 //! Snow Context.
+use std::sync::Arc;
+
 use crate::{
+    constants::DEFAULT_CUSTOM_NETWORK_ID,
     ids::node::Id as NodeId,
     ids::Id,
-    proto::pb::{
-        aliasreader::alias_reader_client::AliasReaderClient,
-        keystore::keystore_client::KeystoreClient,
-        sharedmemory::shared_memory_client::SharedMemoryClient,
-    },
+    proto::pb::{self, google::protobuf::Timestamp},
 };
-use tonic::transport::Channel;
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::snow::{alias_reader, keystore, shared_memory, validators};
+
+/// Helper type which defines a thread safe shared keystore.
+pub type SharedKeystore = Arc<dyn keystore::Keystore + Send + Sync>;
 
-use super::snow::validators;
+/// Helper type which defines a thread safe shared memory of the chains.
+pub type SharedSharedMemory = Arc<dyn shared_memory::SharedMemory + Send + Sync>;
+
+/// Helper type which defines a thread safe shared alias reader.
+pub type SharedAliasReader = Arc<dyn alias_reader::AliasReader + Send + Sync>;
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow#Context>
 #[derive(Debug, Clone)]
@@ -22,10 +30,367 @@ pub struct Context<S: validators::State> {
     pub x_chain_id: Id,
     pub c_chain_id: Id,
     pub avax_asset_id: Id,
-    pub keystore: KeystoreClient<Channel>,
-    pub shared_memory: SharedMemoryClient<Channel>,
-    pub bc_lookup: AliasReaderClient<Channel>,
+    pub keystore: SharedKeystore,
+    pub shared_memory: SharedSharedMemory,
+    pub bc_lookup: SharedAliasReader,
     pub chain_data_dir: String,
     pub validator_state: S,
+    pub network_upgrades: NetworkUpgrades,
     // TODO metrics
 }
+
+/// Network upgrades in activation order.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/upgrade#Config>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upgrade {
+    ApricotPhase1,
+    ApricotPhase2,
+    ApricotPhase3,
+    ApricotPhase4,
+    ApricotPhase5,
+    ApricotPhasePre6,
+    ApricotPhase6,
+    ApricotPhasePost6,
+    Banff,
+    Cortina,
+    Durango,
+    Etna,
+}
+
+/// Activation times of the network upgrades, passed by avalanchego on initialize.
+/// "None" if the upgrade is not scheduled, so it never activates.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/upgrade#Config>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkUpgrades {
+    pub apricot_phase_1_time: Option<DateTime<Utc>>,
+    pub apricot_phase_2_time: Option<DateTime<Utc>>,
+    pub apricot_phase_3_time: Option<DateTime<Utc>>,
+    pub apricot_phase_4_time: Option<DateTime<Utc>>,
+    pub apricot_phase_4_min_p_chain_height: u64,
+    pub apricot_phase_5_time: Option<DateTime<Utc>>,
+    pub apricot_phase_pre_6_time: Option<DateTime<Utc>>,
+    pub apricot_phase_6_time: Option<DateTime<Utc>>,
+    pub apricot_phase_post_6_time: Option<DateTime<Utc>>,
+    pub banff_time: Option<DateTime<Utc>>,
+    pub cortina_time: Option<DateTime<Utc>>,
+    pub cortina_x_chain_stop_vertex_id: Id,
+    pub durango_time: Option<DateTime<Utc>>,
+    pub etna_time: Option<DateTime<Utc>>,
+}
+
+impl NetworkUpgrades {
+    /// Returns the activation time of the upgrade, if scheduled.
+    #[must_use]
+    pub const fn activation_time(&self, upgrade: Upgrade) -> Option<DateTime<Utc>> {
+        match upgrade {
+            Upgrade::ApricotPhase1 => self.apricot_phase_1_time,
+            Upgrade::ApricotPhase2 => self.apricot_phase_2_time,
+            Upgrade::ApricotPhase3 => self.apricot_phase_3_time,
+            Upgrade::ApricotPhase4 => self.apricot_phase_4_time,
+            Upgrade::ApricotPhase5 => self.apricot_phase_5_time,
+            Upgrade::ApricotPhasePre6 => self.apricot_phase_pre_6_time,
+            Upgrade::ApricotPhase6 => self.apricot_phase_6_time,
+            Upgrade::ApricotPhasePost6 => self.apricot_phase_post_6_time,
+            Upgrade::Banff => self.banff_time,
+            Upgrade::Cortina => self.cortina_time,
+            Upgrade::Durango => self.durango_time,
+            Upgrade::Etna => self.etna_time,
+        }
+    }
+
+    /// Returns true if the upgrade is activated at the time
+    /// (e.g., the block timestamp).
+    /// ref. "avalanchego/upgrade.Config.IsDurangoActivated"
+    #[must_use]
+    pub fn is_activated(&self, upgrade: Upgrade, t: DateTime<Utc>) -> bool {
+        self.activation_time(upgrade).is_some_and(|at| t >= at)
+    }
+}
+
+impl From<&pb::vm::NetworkUpgrades> for NetworkUpgrades {
+    fn from(u: &pb::vm::NetworkUpgrades) -> Self {
+        Self {
+            apricot_phase_1_time: time_from_timestamp(u.apricot_phase_1_time.as_ref()),
+            apricot_phase_2_time: time_from_timestamp(u.apricot_phase_2_time.as_ref()),
+            apricot_phase_3_time: time_from_timestamp(u.apricot_phase_3_time.as_ref()),
+            apricot_phase_4_time: time_from_timestamp(u.apricot_phase_4_time.as_ref()),
+            apricot_phase_4_min_p_chain_height: u.apricot_phase_4_min_p_chain_height,
+            apricot_phase_5_time: time_from_timestamp(u.apricot_phase_5_time.as_ref()),
+            apricot_phase_pre_6_time: time_from_timestamp(u.apricot_phase_pre_6_time.as_ref()),
+            apricot_phase_6_time: time_from_timestamp(u.apricot_phase_6_time.as_ref()),
+            apricot_phase_post_6_time: time_from_timestamp(u.apricot_phase_post_6_time.as_ref()),
+            banff_time: time_from_timestamp(u.banff_time.as_ref()),
+            cortina_time: time_from_timestamp(u.cortina_time.as_ref()),
+            cortina_x_chain_stop_vertex_id: if u.cortina_x_chain_stop_vertex_id.is_empty() {
+                Id::empty()
+            } else {
+                Id::from_slice(&u.cortina_x_chain_stop_vertex_id)
+            },
+            durango_time: time_from_timestamp(u.durango_time.as_ref()),
+            etna_time: time_from_timestamp(u.etna_time.as_ref()),
+        }
+    }
+}
+
+fn time_from_timestamp(ts: Option<&Timestamp>) -> Option<DateTime<Utc>> {
+    let ts = ts?;
+    Utc.timestamp_opt(ts.seconds, u32::try_from(ts.nanos).unwrap_or(0))
+        .single()
+}
+
+impl Context<validators::memory::State> {
+    /// Returns the builder of the context for unit tests, which defaults to
+    /// the in-memory validator state, keystore, shared memory, and alias reader.
+    #[must_use]
+    pub fn builder() -> Builder<validators::memory::State> {
+        Builder::default()
+    }
+}
+
+/// Builds the context without a running avalanchego.
+#[derive(Debug, Clone)]
+pub struct Builder<S: validators::State> {
+    network_id: u32,
+    subnet_id: Id,
+    chain_id: Id,
+    node_id: NodeId,
+    x_chain_id: Id,
+    c_chain_id: Id,
+    avax_asset_id: Id,
+    keystore: Option<SharedKeystore>,
+    shared_memory: Option<SharedSharedMemory>,
+    bc_lookup: Option<SharedAliasReader>,
+    chain_data_dir: String,
+    validator_state: S,
+    network_upgrades: NetworkUpgrades,
+}
+
+impl Default for Builder<validators::memory::State> {
+    fn default() -> Self {
+        Self {
+            network_id: DEFAULT_CUSTOM_NETWORK_ID,
+            subnet_id: Id::empty(),
+            chain_id: Id::empty(),
+            node_id: NodeId::empty(),
+            x_chain_id: Id::empty(),
+            c_chain_id: Id::empty(),
+            avax_asset_id: Id::empty(),
+            keystore: None,
+            shared_memory: None,
+            bc_lookup: None,
+            chain_data_dir: String::new(),
+            validator_state: validators::memory::State::new(),
+            network_upgrades: NetworkUpgrades::default(),
+        }
+    }
+}
+
+impl<S: validators::State> Builder<S> {
+    #[must_use]
+    pub const fn network_id(mut self, network_id: u32) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn subnet_id(mut self, subnet_id: Id) -> Self {
+        self.subnet_id = subnet_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn chain_id(mut self, chain_id: Id) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = node_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn x_chain_id(mut self, x_chain_id: Id) -> Self {
+        self.x_chain_id = x_chain_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn c_chain_id(mut self, c_chain_id: Id) -> Self {
+        self.c_chain_id = c_chain_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn avax_asset_id(mut self, avax_asset_id: Id) -> Self {
+        self.avax_asset_id = avax_asset_id;
+        self
+    }
+
+    #[must_use]
+    pub fn keystore(mut self, keystore: SharedKeystore) -> Self {
+        self.keystore = Some(keystore);
+        self
+    }
+
+    #[must_use]
+    pub fn shared_memory(mut self, shared_memory: SharedSharedMemory) -> Self {
+        self.shared_memory = Some(shared_memory);
+        self
+    }
+
+    #[must_use]
+    pub fn bc_lookup(mut self, bc_lookup: SharedAliasReader) -> Self {
+        self.bc_lookup = Some(bc_lookup);
+        self
+    }
+
+    #[must_use]
+    pub fn chain_data_dir(mut self, chain_data_dir: impl Into<String>) -> Self {
+        self.chain_data_dir = chain_data_dir.into();
+        self
+    }
+
+    #[must_use]
+    pub fn network_upgrades(mut self, network_upgrades: NetworkUpgrades) -> Self {
+        self.network_upgrades = network_upgrades;
+        self
+    }
+
+    /// Replaces the validator state (e.g., with a custom mock).
+    #[must_use]
+    pub fn validator_state<T: validators::State>(self, validator_state: T) -> Builder<T> {
+        Builder {
+            network_id: self.network_id,
+            subnet_id: self.subnet_id,
+            chain_id: self.chain_id,
+            node_id: self.node_id,
+            x_chain_id: self.x_chain_id,
+            c_chain_id: self.c_chain_id,
+            avax_asset_id: self.avax_asset_id,
+            keystore: self.keystore,
+            shared_memory: self.shared_memory,
+            bc_lookup: self.bc_lookup,
+            chain_data_dir: self.chain_data_dir,
+            validator_state,
+            network_upgrades: self.network_upgrades,
+        }
+    }
+
+    /// Builds the context, with the empty in-memory keystore, shared memory,
+    /// and alias reader unless set.
+    #[must_use]
+    pub fn build(self) -> Context<S> {
+        Context {
+            network_id: self.network_id,
+            subnet_id: self.subnet_id,
+            chain_id: self.chain_id,
+            node_id: self.node_id,
+            x_chain_id: self.x_chain_id,
+            c_chain_id: self.c_chain_id,
+            avax_asset_id: self.avax_asset_id,
+            keystore: self
+                .keystore
+                .unwrap_or_else(|| Arc::new(keystore::memory::Keystore::new())),
+            shared_memory: self
+                .shared_memory
+                .unwrap_or_else(|| Arc::new(shared_memory::memory::SharedMemory::new())),
+            bc_lookup: self
+                .bc_lookup
+                .unwrap_or_else(|| Arc::new(alias_reader::memory::AliasReader::new())),
+            chain_data_dir: self.chain_data_dir,
+            validator_state: self.validator_state,
+            network_upgrades: self.network_upgrades,
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::context::test_builder` --exact --show-output
+#[test]
+fn test_builder() {
+    use validators::State as _;
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let chain_id = Id::sha256("chain");
+    let subnet_id = Id::sha256("subnet");
+    let node_id = NodeId::from_slice(&[1; 20]);
+
+    let bc_lookup = alias_reader::memory::AliasReader::new();
+    let ctx = Context::builder()
+        .network_id(12345)
+        .chain_id(chain_id)
+        .subnet_id(subnet_id)
+        .chain_data_dir("/tmp/chain")
+        .bc_lookup(Arc::new(bc_lookup.clone()))
+        .build();
+    assert_eq!(ctx.network_id, 12345);
+    assert_eq!(ctx.chain_id, chain_id);
+    assert_eq!(ctx.subnet_id, subnet_id);
+    assert_eq!(ctx.node_id, NodeId::empty());
+    assert_eq!(ctx.chain_data_dir, "/tmp/chain");
+
+    // the in-memory validator state is shared with the clones
+    let state = ctx.validator_state.clone();
+    state.set_heights(5, 10);
+    state.set_subnet_id(chain_id, subnet_id);
+    state.set_validator_set(
+        subnet_id,
+        8,
+        vec![validators::GetValidatorOutput {
+            node_id,
+            public_key: None,
+            weight: 100,
+        }],
+    );
+    let vs = &ctx.validator_state;
+    assert_eq!(ab!(vs.get_current_height()).unwrap(), 10);
+    assert_eq!(ab!(vs.get_subnet_id(chain_id)).unwrap(), subnet_id);
+    assert!(ab!(vs.get_subnet_id(subnet_id)).is_err());
+    assert!(ab!(vs.get_validator_set(7, subnet_id)).unwrap().is_empty());
+    assert_eq!(
+        ab!(vs.get_validator_set(10, subnet_id)).unwrap()[&node_id].weight,
+        100
+    );
+
+    // the in-memory defaults need no runtime nor avalanchego
+    assert!(ab!(ctx.bc_lookup.lookup("X")).is_err());
+    bc_lookup.alias(ctx.x_chain_id, "X").unwrap();
+    assert_eq!(ab!(ctx.bc_lookup.lookup("X")).unwrap(), ctx.x_chain_id);
+    assert!(ab!(ctx.keystore.get_database("alice", "password")).is_err());
+    assert!(ab!(ctx.shared_memory.get(chain_id, &[b"k".to_vec()])).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::context::test_network_upgrades` --exact --show-output
+#[test]
+fn test_network_upgrades() {
+    let durango = Utc.timestamp_opt(1_709_740_800, 0).unwrap();
+    let upgrades = NetworkUpgrades::from(&pb::vm::NetworkUpgrades {
+        banff_time: Some(Timestamp {
+            seconds: 1_665_417_600,
+            nanos: 0,
+        }),
+        durango_time: Some(Timestamp {
+            seconds: durango.timestamp(),
+            nanos: 0,
+        }),
+        apricot_phase_4_min_p_chain_height: 793_005,
+        ..Default::default()
+    });
+    assert_eq!(upgrades.durango_time, Some(durango));
+    assert_eq!(upgrades.apricot_phase_4_min_p_chain_height, 793_005);
+    assert_eq!(upgrades.cortina_x_chain_stop_vertex_id, Id::empty());
+
+    assert!(upgrades.is_activated(Upgrade::Banff, durango));
+    assert!(upgrades.is_activated(Upgrade::Durango, durango));
+    assert!(!upgrades.is_activated(Upgrade::Durango, durango - chrono::Duration::seconds(1)));
+    // not scheduled
+    assert!(upgrades.activation_time(Upgrade::Etna).is_none());
+    assert!(!upgrades.is_activated(Upgrade::Etna, Utc::now()));
+}
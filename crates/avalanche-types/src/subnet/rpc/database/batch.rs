@@ -60,6 +60,35 @@ impl Clone for BoxedBatch {
     }
 }
 
+/// Represents a single write operation to add to a batch
+/// (e.g., alongside a versiondb commit).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Op {
+    Put { key: Vec<u8>, value: Vec<u8> },
+    Delete { key: Vec<u8> },
+}
+
+impl Op {
+    /// Returns the key the operation writes to.
+    #[must_use]
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::Put { key, .. } | Self::Delete { key } => key,
+        }
+    }
+
+    /// Adds the operation to the batch.
+    ///
+    /// # Errors
+    /// Returns error if the batch rejects the operation.
+    pub async fn apply(&self, batch: &mut BoxedBatch) -> Result<()> {
+        match self {
+            Self::Put { key, value } => batch.put(key, value).await,
+            Self::Delete { key } => batch.delete(key).await,
+        }
+    }
+}
+
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/database#Batcher>
 #[tonic::async_trait]
 pub trait Batcher {
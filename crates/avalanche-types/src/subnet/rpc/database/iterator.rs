@@ -1,4 +1,14 @@
-use std::io::Result;
+use std::{
+    cmp,
+    collections::{BTreeMap, VecDeque},
+    io::{self, Result},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::subnet::rpc::errors::Error;
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/database#Iterator>
 #[tonic::async_trait]
@@ -53,3 +63,214 @@ pub trait Iteratee {
         prefix: &[u8],
     ) -> Result<BoxedIterator>;
 }
+
+/// Iterates over an in-memory delta overlaid on another iterator, in the key
+/// order (e.g., the pending changes over the committed state). The delta
+/// takes precedence on the same key, and a deleted key (`None` value) shadows
+/// the key of the underlying iterator.
+pub struct MergedIterator {
+    /// The underlying iterator
+    iterator: BoxedIterator,
+    /// Remaining delta in the key order, `None` for the deleted keys
+    delta: VecDeque<(Vec<u8>, Option<Vec<u8>>)>,
+    /// Set if the iteration must stop when the database is closed
+    closed: Option<Arc<AtomicBool>>,
+    /// Error that occurred during iteration
+    error: Option<io::Error>,
+    /// Current key
+    key: Vec<u8>,
+    /// Current value
+    value: Vec<u8>,
+    /// Whether the underlying iterator has been advanced to its first pair
+    initialized: bool,
+    /// Whether the underlying iterator has been exhausted
+    exhausted: bool,
+}
+
+impl MergedIterator {
+    /// Overlays the delta on the iterator. The delta must cover the same key
+    /// range as the iterator, see `with_start_and_prefix`.
+    #[must_use]
+    pub fn new(delta: BTreeMap<Vec<u8>, Option<Vec<u8>>>, iterator: BoxedIterator) -> Self {
+        // "Iterator" is the database trait in this module
+        let mut pending = VecDeque::with_capacity(delta.len());
+        pending.extend(delta);
+        Self {
+            iterator,
+            delta: pending,
+            closed: None,
+            error: None,
+            key: vec![],
+            value: vec![],
+            initialized: false,
+            exhausted: false,
+        }
+    }
+
+    /// Drops the delta outside of the range of an iterator created with
+    /// the start and prefix.
+    #[must_use]
+    pub fn with_start_and_prefix(mut self, start: &[u8], prefix: &[u8]) -> Self {
+        self.delta
+            .retain(|(k, _)| k.starts_with(prefix) && k.as_slice() >= start);
+        self
+    }
+
+    /// Stops the iteration with [`Error::DatabaseClosed`] once the flag is set.
+    #[must_use]
+    pub fn with_closed(mut self, closed: Arc<AtomicBool>) -> Self {
+        self.closed = Some(closed);
+        self
+    }
+
+    #[must_use]
+    pub fn into_boxed(self) -> BoxedIterator {
+        Box::new(self)
+    }
+
+    /// Advances the underlying iterator.
+    async fn advance(&mut self) -> Result<()> {
+        self.exhausted = !self.iterator.next().await?;
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl Iterator for MergedIterator {
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn next(&mut self) -> Result<bool> {
+        // set an error if the underlying database has been closed
+        if self
+            .closed
+            .as_ref()
+            .is_some_and(|closed| closed.load(Ordering::Relaxed))
+        {
+            self.delta.clear();
+            self.key.clear();
+            self.value.clear();
+            self.error = Some(Error::DatabaseClosed.to_err());
+            return Ok(false);
+        }
+
+        if !self.initialized {
+            self.advance().await?;
+            self.initialized = true;
+        }
+
+        loop {
+            let db_key = if self.exhausted {
+                None
+            } else {
+                Some(self.iterator.key().await?.to_vec())
+            };
+
+            let order = match (self.delta.front(), &db_key) {
+                (None, None) => {
+                    self.key.clear();
+                    self.value.clear();
+                    return Ok(false);
+                }
+                (Some(_), None) => cmp::Ordering::Less,
+                (None, Some(_)) => cmp::Ordering::Greater,
+                (Some((k, _)), Some(db_key)) => k.as_slice().cmp(db_key),
+            };
+
+            if order == cmp::Ordering::Greater {
+                self.key = db_key.unwrap_or_default();
+                self.value = self.iterator.value().await?.to_vec();
+                self.advance().await?;
+                return Ok(true);
+            }
+            if order == cmp::Ordering::Equal {
+                // shadowed by the delta
+                self.advance().await?;
+            }
+            if let Some((k, Some(v))) = self.delta.pop_front() {
+                self.key = k;
+                self.value = v;
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn error(&mut self) -> Result<()> {
+        if let Some(err) = &self.error {
+            return Err(io::Error::new(err.kind(), err.to_string()));
+        }
+
+        self.iterator.error().await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn key(&self) -> Result<&[u8]> {
+        Ok(&self.key)
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn value(&self) -> Result<&[u8]> {
+        Ok(&self.value)
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn release(&mut self) {
+        self.key.clear();
+        self.value.clear();
+        self.delta.clear();
+        self.iterator.release().await;
+    }
+}
+
+#[tokio::test]
+async fn merged_iterator_test() {
+    use crate::subnet::rpc::database::memdb;
+
+    let mut db = memdb::Database::new_boxed();
+    for (k, v) in [("a", "1"), ("b", "1"), ("c", "1"), ("e", "1")] {
+        db.put(k.as_bytes(), v.as_bytes()).await.unwrap();
+    }
+
+    let delta = BTreeMap::from([
+        (b"b".to_vec(), None),
+        (b"c".to_vec(), Some(b"2".to_vec())),
+        (b"d".to_vec(), Some(b"2".to_vec())),
+        (b"f".to_vec(), None),
+        (b"g".to_vec(), Some(b"2".to_vec())),
+    ]);
+    let collect = |mut iter: BoxedIterator| async move {
+        let mut kvs = Vec::new();
+        while iter.next().await.unwrap() {
+            kvs.push((
+                String::from_utf8(iter.key().await.unwrap().to_vec()).unwrap(),
+                String::from_utf8(iter.value().await.unwrap().to_vec()).unwrap(),
+            ));
+        }
+        iter.error().await.unwrap();
+        assert!(iter.key().await.unwrap().is_empty());
+        kvs
+    };
+
+    let iter = MergedIterator::new(delta.clone(), db.new_iterator().await.unwrap()).into_boxed();
+    assert_eq!(
+        collect(iter).await,
+        [("a", "1"), ("c", "2"), ("d", "2"), ("e", "1"), ("g", "2")]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+    );
+
+    let iter = MergedIterator::new(
+        delta.clone(),
+        db.new_iterator_with_start(b"c").await.unwrap(),
+    )
+    .with_start_and_prefix(b"c", &[])
+    .into_boxed();
+    assert_eq!(collect(iter).await.len(), 4);
+
+    let closed = Arc::new(AtomicBool::new(false));
+    let mut iter = MergedIterator::new(delta, db.new_iterator().await.unwrap())
+        .with_closed(Arc::clone(&closed))
+        .into_boxed();
+    assert!(iter.next().await.unwrap());
+    closed.store(true, Ordering::Relaxed);
+    assert!(!iter.next().await.unwrap());
+    assert!(iter.error().await.is_err());
+}
@@ -0,0 +1,385 @@
+//! Linked list on top of a key-value database.
+//!
+//! Keeps the insertion order of the keys, so the values can be iterated
+//! newest-first (e.g., for mempools and indexes). Uses the same layout as
+//! "avalanchego/database/linkeddb", so the lists are readable by both.
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/database/linkeddb>
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    packer::Packer,
+    subnet::rpc::{
+        database::{batch::BoxedBatch, BoxedDatabase},
+        errors,
+    },
+};
+
+/// Prefix of the keys of the list nodes.
+const NODE_KEY_PREFIX: u8 = 0x00;
+/// Key of the most recently inserted key.
+const HEAD_KEY: u8 = 0x01;
+
+/// Version of the node encoding, same as avalanchego's codec version.
+const CODEC_VERSION: u16 = 0;
+/// Maximum size of an encoded node.
+const MAX_NODE_SIZE: usize = u32::MAX as usize;
+
+/// ref. <https://github.com/ava-labs/avalanchego/blob/master/database/linkeddb/linkeddb.go>
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Node {
+    /// Value of the key.
+    value: Vec<u8>,
+    /// Next (older) key, "None" at the tail.
+    next: Option<Vec<u8>>,
+    /// Previous (newer) key, "None" at the head.
+    previous: Option<Vec<u8>>,
+}
+
+impl Node {
+    /// Encodes the node as avalanchego's codec does.
+    fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let packer = Packer::new(MAX_NODE_SIZE, 0);
+        let pack = || -> crate::errors::Result<()> {
+            packer.pack_u16(CODEC_VERSION)?;
+            packer.pack_bytes_with_header(&self.value)?;
+            packer.pack_bool(self.next.is_some())?;
+            packer.pack_bytes_with_header(self.next.as_deref().unwrap_or_default())?;
+            packer.pack_bool(self.previous.is_some())?;
+            packer.pack_bytes_with_header(self.previous.as_deref().unwrap_or_default())
+        };
+        pack().map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        Ok(packer.take_bytes().to_vec())
+    }
+
+    /// Decodes the node encoded by "`to_bytes`" (or avalanchego).
+    fn from_bytes(b: &[u8]) -> io::Result<Self> {
+        let packer = Packer::load_bytes_for_unpack(MAX_NODE_SIZE, b);
+        let unpack = || -> crate::errors::Result<Self> {
+            let version = packer.unpack_u16()?;
+            if version != CODEC_VERSION {
+                return Err(crate::errors::Error::Other {
+                    message: format!("unknown codec version {version}"),
+                    retryable: false,
+                });
+            }
+            let value = packer.unpack_bytes_with_header()?;
+            let has_next = packer.unpack_bool()?;
+            let next = packer.unpack_bytes_with_header()?;
+            let has_previous = packer.unpack_bool()?;
+            let previous = packer.unpack_bytes_with_header()?;
+            Ok(Self {
+                value,
+                next: has_next.then_some(next),
+                previous: has_previous.then_some(previous),
+            })
+        };
+        unpack().map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+/// Linked list of the key-value pairs, with the most recently inserted key at the head.
+/// Clones share the same list, and the writes are serialized.
+#[derive(Clone)]
+pub struct LinkedDb {
+    /// Underlying database of the nodes and the head key.
+    db: BoxedDatabase,
+    /// Prefix of all keys of the list.
+    prefix: Vec<u8>,
+    /// Serializes the writes, which read the neighbor nodes first.
+    lock: Arc<Mutex<()>>,
+}
+
+impl LinkedDb {
+    /// Creates the list with its keys at the root of the database.
+    #[must_use]
+    pub fn new(db: BoxedDatabase) -> Self {
+        Self::new_with_prefix(db, &[])
+    }
+
+    /// Creates the list with all its keys under the prefix,
+    /// so multiple lists can share the same database.
+    #[must_use]
+    pub fn new_with_prefix(db: BoxedDatabase, prefix: &[u8]) -> Self {
+        Self {
+            db,
+            prefix: prefix.to_vec(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Returns "true" if the key is in the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn has(&self, key: &[u8]) -> io::Result<bool> {
+        self.db.has(&self.node_key(key)).await
+    }
+
+    /// Returns the value of the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`errors::Error::NotFound`" if the key is not in the list.
+    pub async fn get(&self, key: &[u8]) -> io::Result<Vec<u8>> {
+        Ok(self.get_node(key).await?.value)
+    }
+
+    /// Inserts the key at the head of the list. If the key is already in the list,
+    /// updates its value in place, keeping its position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read or write.
+    pub async fn put(&self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut batch = self.db.new_batch().await?;
+        match self.get_node(key).await {
+            Ok(mut node) => {
+                node.value = value.to_vec();
+                self.put_node(&mut batch, key, &node).await?;
+            }
+            Err(e) if errors::is_not_found(&e) => {
+                let head = self.head_key_opt().await?;
+                if let Some(head) = &head {
+                    let mut head_node = self.get_node(head).await?;
+                    head_node.previous = Some(key.to_vec());
+                    self.put_node(&mut batch, head, &head_node).await?;
+                }
+
+                let node = Node {
+                    value: value.to_vec(),
+                    next: head,
+                    previous: None,
+                };
+                self.put_node(&mut batch, key, &node).await?;
+                batch.put(&self.head_key_key(), key).await?;
+            }
+            Err(e) => return Err(e),
+        }
+        batch.write().await
+    }
+
+    /// Removes the key from the list, linking its neighbors.
+    /// Deleting a missing key is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read or write.
+    pub async fn delete(&self, key: &[u8]) -> io::Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let node = match self.get_node(key).await {
+            Ok(node) => node,
+            Err(e) if errors::is_not_found(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let mut batch = self.db.new_batch().await?;
+        batch.delete(&self.node_key(key)).await?;
+
+        if let Some(previous) = &node.previous {
+            let mut previous_node = self.get_node(previous).await?;
+            previous_node.next.clone_from(&node.next);
+            self.put_node(&mut batch, previous, &previous_node).await?;
+        } else {
+            // the deleted key was the head
+            match &node.next {
+                Some(next) => batch.put(&self.head_key_key(), next).await?,
+                None => batch.delete(&self.head_key_key()).await?,
+            }
+        }
+
+        if let Some(next) = &node.next {
+            let mut next_node = self.get_node(next).await?;
+            next_node.previous.clone_from(&node.previous);
+            self.put_node(&mut batch, next, &next_node).await?;
+        }
+
+        batch.write().await
+    }
+
+    /// Returns "true" if the list has no keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn is_empty(&self) -> io::Result<bool> {
+        Ok(self.head_key_opt().await?.is_none())
+    }
+
+    /// Returns the most recently inserted key.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`errors::Error::NotFound`" if the list is empty.
+    pub async fn head_key(&self) -> io::Result<Vec<u8>> {
+        self.db.get(&self.head_key_key()).await
+    }
+
+    /// Returns the most recently inserted key and its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`errors::Error::NotFound`" if the list is empty.
+    pub async fn head(&self) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let key = self.head_key().await?;
+        let value = self.get(&key).await?;
+        Ok((key, value))
+    }
+
+    /// Returns the iterator from the most recently inserted key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn new_iterator(&self) -> io::Result<Iter> {
+        Ok(Iter {
+            list: self.clone(),
+            next: self.head_key_opt().await?,
+        })
+    }
+
+    /// Returns up to "limit" key-value pairs, newest-first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn list(&self, limit: usize) -> io::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut iter = self.new_iterator().await?;
+        let mut kvs = Vec::new();
+        while kvs.len() < limit {
+            match iter.next().await? {
+                Some(kv) => kvs.push(kv),
+                None => break,
+            }
+        }
+        Ok(kvs)
+    }
+
+    /// Returns the database key of the node of the key.
+    fn node_key(&self, key: &[u8]) -> Vec<u8> {
+        let mut k = Vec::with_capacity(self.prefix.len() + 1 + key.len());
+        k.extend_from_slice(&self.prefix);
+        k.push(NODE_KEY_PREFIX);
+        k.extend_from_slice(key);
+        k
+    }
+
+    /// Returns the database key holding the head key.
+    fn head_key_key(&self) -> Vec<u8> {
+        let mut k = self.prefix.clone();
+        k.push(HEAD_KEY);
+        k
+    }
+
+    /// Returns the head key, or "None" if the list is empty.
+    async fn head_key_opt(&self) -> io::Result<Option<Vec<u8>>> {
+        match self.head_key().await {
+            Ok(key) => Ok(Some(key)),
+            Err(e) if errors::is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads the node of the key.
+    async fn get_node(&self, key: &[u8]) -> io::Result<Node> {
+        let b = self.db.get(&self.node_key(key)).await?;
+        Node::from_bytes(&b)
+    }
+
+    /// Writes the node of the key to the batch.
+    async fn put_node(&self, batch: &mut BoxedBatch, key: &[u8], node: &Node) -> io::Result<()> {
+        batch.put(&self.node_key(key), &node.to_bytes()?).await
+    }
+}
+
+/// Iterates the list newest-first. Keys inserted after the iterator is created
+/// are not returned, and keys deleted during the iteration may end it early.
+pub struct Iter {
+    /// List being iterated.
+    list: LinkedDb,
+    /// Key to return next, "None" at the end.
+    next: Option<Vec<u8>>,
+}
+
+impl Iter {
+    /// Returns the next key-value pair, or "None" at the end of the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn next(&mut self) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let Some(key) = self.next.take() else {
+            return Ok(None);
+        };
+        match self.list.get_node(&key).await {
+            Ok(node) => {
+                self.next = node.next;
+                Ok(Some((key, node.value)))
+            }
+            Err(e) if errors::is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::database::linkeddb::test_linkeddb` --exact --show-output
+#[tokio::test]
+async fn test_linkeddb() {
+    use crate::subnet::rpc::database::memdb;
+
+    let node = Node {
+        value: vec![1, 2],
+        next: Some(vec![3]),
+        previous: None,
+    };
+    assert_eq!(Node::from_bytes(&node.to_bytes().unwrap()).unwrap(), node);
+
+    let db = memdb::Database::new_boxed();
+    let list = LinkedDb::new(db.clone());
+    assert!(list.is_empty().await.unwrap());
+    assert!(errors::is_not_found(&list.head().await.unwrap_err()));
+
+    list.put(b"a", b"1").await.unwrap();
+    list.put(b"b", b"2").await.unwrap();
+    list.put(b"c", b"3").await.unwrap();
+    // updates in place
+    list.put(b"b", b"22").await.unwrap();
+    assert!(!list.is_empty().await.unwrap());
+    assert!(list.has(b"b").await.unwrap());
+    assert_eq!(list.get(b"b").await.unwrap(), b"22");
+    assert_eq!(list.head().await.unwrap(), (b"c".to_vec(), b"3".to_vec()));
+
+    let keys = |kvs: Vec<(Vec<u8>, Vec<u8>)>| kvs.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(
+        keys(list.list(10).await.unwrap()),
+        vec![b"c".to_vec(), b"b".to_vec(), b"a".to_vec()]
+    );
+    assert_eq!(list.list(2).await.unwrap().len(), 2);
+
+    // middle, head, then tail
+    list.delete(b"b").await.unwrap();
+    assert_eq!(
+        keys(list.list(10).await.unwrap()),
+        vec![b"c".to_vec(), b"a".to_vec()]
+    );
+    list.delete(b"c").await.unwrap();
+    assert_eq!(list.head_key().await.unwrap(), b"a");
+    list.delete(b"missing").await.unwrap();
+    list.delete(b"a").await.unwrap();
+    assert!(list.is_empty().await.unwrap());
+    assert!(list.list(10).await.unwrap().is_empty());
+
+    // lists under different prefixes do not collide
+    let other = LinkedDb::new_with_prefix(db, b"other");
+    other.put(b"a", b"1").await.unwrap();
+    assert!(!other.is_empty().await.unwrap());
+    assert!(list.is_empty().await.unwrap());
+}
@@ -0,0 +1,362 @@
+//! Database wrapper which records the operation metrics.
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use tokio::sync::Mutex;
+
+use super::{
+    batch::{self as database_batch, BoxedBatch},
+    iterator::{self as database_iterator, BoxedIterator},
+    BoxedDatabase,
+};
+
+/// Latency buckets in seconds of the database operations.
+pub const LATENCY_BUCKETS: [f64; 12] = [
+    0.000_01, 0.000_05, 0.000_1, 0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0,
+];
+
+/// Operation metrics labeled by the method (e.g., "get", "`batch_write`", "`iterator_next`").
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/database/meterdb>
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    /// Total number of calls.
+    pub calls: IntCounterVec,
+    /// Total number of calls that returned an error.
+    pub errors: IntCounterVec,
+    /// Total number of bytes of the keys and values read or written.
+    pub size: IntCounterVec,
+    /// Call latency in seconds.
+    pub duration: HistogramVec,
+}
+
+impl Metrics {
+    /// Creates the metrics in the namespace (e.g., "`meterdb`"),
+    /// and registers them to the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry rejects the metrics
+    /// (e.g., the namespace is already registered).
+    pub fn new(namespace: &str, registry: &Registry) -> prometheus::Result<Self> {
+        let labels = &["method"];
+        let metrics = Self {
+            calls: IntCounterVec::new(
+                Opts::new("calls", "Total number of database calls").namespace(namespace),
+                labels,
+            )?,
+            errors: IntCounterVec::new(
+                Opts::new("errors", "Total number of failed database calls").namespace(namespace),
+                labels,
+            )?,
+            size: IntCounterVec::new(
+                Opts::new("size", "Total bytes of the keys and values").namespace(namespace),
+                labels,
+            )?,
+            duration: HistogramVec::new(
+                HistogramOpts::new("duration_seconds", "Database call latency in seconds")
+                    .namespace(namespace)
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                labels,
+            )?,
+        };
+        registry.register(Box::new(metrics.calls.clone()))?;
+        registry.register(Box::new(metrics.errors.clone()))?;
+        registry.register(Box::new(metrics.size.clone()))?;
+        registry.register(Box::new(metrics.duration.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Records a single call.
+    pub fn observe(&self, method: &str, ok: bool, size: usize, elapsed: Duration) {
+        let labels = [method];
+        self.calls.with_label_values(&labels).inc();
+        if !ok {
+            self.errors.with_label_values(&labels).inc();
+        }
+        if size > 0 {
+            self.size
+                .with_label_values(&labels)
+                .inc_by(u64::try_from(size).unwrap_or(u64::MAX));
+        }
+        self.duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    fn record<T>(&self, method: &str, start: Instant, size: usize, result: &io::Result<T>) {
+        self.observe(method, result.is_ok(), size, start.elapsed());
+    }
+}
+
+/// Database wrapper which records the calls, including the batches and iterators
+/// it creates, to the metrics.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/database/meterdb#Database>
+#[derive(Clone)]
+pub struct Database {
+    /// The underlying database
+    db: BoxedDatabase,
+    metrics: Arc<Metrics>,
+}
+
+impl Database {
+    #[must_use]
+    pub fn new_boxed(db: BoxedDatabase, metrics: Arc<Metrics>) -> BoxedDatabase {
+        Box::new(Self { db, metrics })
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::database::KeyValueReaderWriterDeleter for Database {
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn has(&self, key: &[u8]) -> io::Result<bool> {
+        let start = Instant::now();
+        let resp = self.db.has(key).await;
+        self.metrics.record("has", start, key.len(), &resp);
+        resp
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn get(&self, key: &[u8]) -> io::Result<Vec<u8>> {
+        let start = Instant::now();
+        let resp = self.db.get(key).await;
+        let size = key.len() + resp.as_ref().map_or(0, Vec::len);
+        self.metrics.record("get", start, size, &resp);
+        resp
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn put(&mut self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.db.put(key, value).await;
+        self.metrics
+            .record("put", start, key.len() + value.len(), &resp);
+        resp
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn delete(&mut self, key: &[u8]) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.db.delete(key).await;
+        self.metrics.record("delete", start, key.len(), &resp);
+        resp
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::database::Closer for Database {
+    /// Implements the [`crate::subnet::rpc::database::Closer`] trait.
+    async fn close(&self) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.db.close().await;
+        self.metrics.record("close", start, 0, &resp);
+        resp
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::health::Checkable for Database {
+    /// Implements the [`crate::subnet::rpc::health::Checkable`] trait.
+    async fn health_check(&self) -> io::Result<Vec<u8>> {
+        let start = Instant::now();
+        let resp = self.db.health_check().await;
+        self.metrics.record("health_check", start, 0, &resp);
+        resp
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::database::iterator::Iteratee for Database {
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator(&self) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(&[], &[]).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_start(&self, start: &[u8]) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(start, &[]).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_prefix(&self, prefix: &[u8]) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(&[], prefix).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_start_and_prefix(
+        &self,
+        start: &[u8],
+        prefix: &[u8],
+    ) -> io::Result<BoxedIterator> {
+        let started = Instant::now();
+        let resp = self
+            .db
+            .new_iterator_with_start_and_prefix(start, prefix)
+            .await;
+        self.metrics.record("new_iterator", started, 0, &resp);
+
+        Ok(Box::new(Iterator {
+            iter: resp?,
+            metrics: Arc::clone(&self.metrics),
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::database::batch::Batcher for Database {
+    /// Implements the [`crate::subnet::rpc::database::batch::Batcher`] trait.
+    async fn new_batch(&self) -> io::Result<BoxedBatch> {
+        let start = Instant::now();
+        let resp = self.db.new_batch().await;
+        self.metrics.record("new_batch", start, 0, &resp);
+
+        Ok(Box::new(Batch {
+            batch: resp?,
+            metrics: Arc::clone(&self.metrics),
+        }))
+    }
+}
+
+impl crate::subnet::rpc::database::Database for Database {}
+
+/// Batch wrapper which records the calls to the metrics.
+#[derive(Clone)]
+pub struct Batch {
+    batch: BoxedBatch,
+    metrics: Arc<Metrics>,
+}
+
+#[tonic::async_trait]
+impl database_batch::Batch for Batch {
+    async fn put(&mut self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.batch.put(key, value).await;
+        self.metrics
+            .record("batch_put", start, key.len() + value.len(), &resp);
+        resp
+    }
+
+    async fn delete(&mut self, key: &[u8]) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.batch.delete(key).await;
+        self.metrics.record("batch_delete", start, key.len(), &resp);
+        resp
+    }
+
+    async fn size(&self) -> io::Result<usize> {
+        self.batch.size().await
+    }
+
+    async fn write(&self) -> io::Result<()> {
+        let start = Instant::now();
+        let size = self.batch.size().await.unwrap_or_default();
+        let resp = self.batch.write().await;
+        self.metrics.record("batch_write", start, size, &resp);
+        resp
+    }
+
+    async fn reset(&mut self) {
+        let start = Instant::now();
+        self.batch.reset().await;
+        self.metrics
+            .observe("batch_reset", true, 0, start.elapsed());
+    }
+
+    async fn replay(&self, k: Arc<Mutex<BoxedDatabase>>) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.batch.replay(k).await;
+        self.metrics.record("batch_replay", start, 0, &resp);
+        resp
+    }
+}
+
+/// Iterator wrapper which records the calls to the metrics.
+pub struct Iterator {
+    iter: BoxedIterator,
+    metrics: Arc<Metrics>,
+}
+
+#[tonic::async_trait]
+impl database_iterator::Iterator for Iterator {
+    async fn next(&mut self) -> io::Result<bool> {
+        let start = Instant::now();
+        let resp = self.iter.next().await;
+        let size = if matches!(resp, Ok(true)) {
+            self.iter.key().await.map_or(0, <[u8]>::len)
+                + self.iter.value().await.map_or(0, <[u8]>::len)
+        } else {
+            0
+        };
+        self.metrics.record("iterator_next", start, size, &resp);
+        resp
+    }
+
+    async fn error(&mut self) -> io::Result<()> {
+        let start = Instant::now();
+        let resp = self.iter.error().await;
+        self.metrics.record("iterator_error", start, 0, &resp);
+        resp
+    }
+
+    async fn key(&self) -> io::Result<&[u8]> {
+        self.iter.key().await
+    }
+
+    async fn value(&self) -> io::Result<&[u8]> {
+        self.iter.value().await
+    }
+
+    async fn release(&mut self) {
+        let start = Instant::now();
+        self.iter.release().await;
+        self.metrics
+            .observe("iterator_release", true, 0, start.elapsed());
+    }
+}
+
+#[tokio::test]
+async fn test_meterdb() {
+    use crate::subnet::rpc::database::memdb;
+
+    let registry = Registry::new();
+    let metrics = Arc::new(Metrics::new("meterdb", &registry).unwrap());
+    assert!(Metrics::new("meterdb", &registry).is_err());
+
+    let mut db = Database::new_boxed(memdb::Database::new_boxed(), Arc::clone(&metrics));
+    db.put(b"foo", b"bar").await.unwrap();
+    assert_eq!(db.get(b"foo").await.unwrap(), b"bar");
+    assert!(db.get(b"notfound").await.is_err());
+
+    let mut batch = db.new_batch().await.unwrap();
+    batch.put(b"baz", b"qux").await.unwrap();
+    batch.write().await.unwrap();
+
+    let mut iter = db.new_iterator().await.unwrap();
+    while iter.next().await.unwrap() {}
+    iter.release().await;
+
+    let calls = |method: &str| metrics.calls.with_label_values(&[method]).get();
+    let size = |method: &str| metrics.size.with_label_values(&[method]).get();
+    assert_eq!(calls("put"), 1);
+    assert_eq!(size("put"), 6);
+    assert_eq!(calls("get"), 2);
+    assert_eq!(metrics.errors.with_label_values(&["get"]).get(), 1);
+    assert_eq!(size("get"), 6 + 8);
+    assert_eq!(calls("batch_put"), 1);
+    assert_eq!(calls("batch_write"), 1);
+    assert_eq!(calls("iterator_next"), 3);
+    assert_eq!(size("iterator_next"), 12);
+    assert_eq!(calls("iterator_release"), 1);
+    assert_eq!(
+        metrics
+            .duration
+            .with_label_values(&["get"])
+            .get_sample_count(),
+        2
+    );
+}
@@ -1,10 +1,15 @@
 pub mod batch;
 pub mod corruptabledb;
 pub mod iterator;
+pub mod linkeddb;
 pub mod manager;
 pub mod memdb;
+#[cfg(feature = "subnet_metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subnet_metrics")))]
+pub mod meterdb;
 pub mod nodb;
 pub mod rpcdb;
+pub mod snapshot;
 pub mod versiondb;
 
 use std::io::Result;
@@ -41,9 +46,8 @@ pub trait KeyValueReaderWriterDeleter {
 // Trait that specifies that something may be
 // committed.
 #[tonic::async_trait]
-#[allow(dead_code)]
 /// A trait for databases that support committing changes.
-trait Commitable {
+pub trait Commitable {
     /// Writes all the operations of this database to the underlying database.
     async fn commit(&mut self) -> Result<()>;
     /// Abort all changes to the underlying database.
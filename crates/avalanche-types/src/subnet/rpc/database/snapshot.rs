@@ -0,0 +1,264 @@
+//! Deterministic snapshots of the database state, so bug reports from
+//! testnets can ship the exact state of the VM to reproduce locally.
+//!
+//! The snapshot lists all key-value pairs in the key order, followed by
+//! the SHA256 checksum of the preceding bytes. The same state always
+//! encodes to the same bytes (also when gzip-compressed), so two snapshots
+//! can be compared by their checksums.
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Cursor, Error, ErrorKind, Read},
+    path::Path,
+};
+
+use flate2::{
+    bufread::{GzDecoder, GzEncoder},
+    Compression,
+};
+
+use crate::{
+    hash,
+    packer::Packer,
+    subnet::rpc::database::{BoxedDatabase, MAX_BATCH_SIZE},
+};
+
+/// Leading bytes of the snapshot encoding.
+const MAGIC: &[u8; 8] = b"avadbsnp";
+/// Version of the snapshot encoding.
+const VERSION: u16 = 0;
+/// Leading bytes of the gzip encoding.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const MAX_SNAPSHOT_SIZE: usize = u32::MAX as usize;
+
+/// Represents the key-value pairs of a database, ordered by the key.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Snapshot {
+    pub entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Snapshot {
+    /// Walks the whole database and returns its key-value pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the iteration fails.
+    pub async fn capture(db: &BoxedDatabase) -> io::Result<Self> {
+        Self::capture_with_prefix(db, &[]).await
+    }
+
+    /// Walks the keys with the prefix (e.g., of a single "prefixdb"),
+    /// and returns their key-value pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the iteration fails.
+    pub async fn capture_with_prefix(db: &BoxedDatabase, prefix: &[u8]) -> io::Result<Self> {
+        let mut iter = db.new_iterator_with_prefix(prefix).await?;
+        let mut entries = BTreeMap::new();
+        while iter.next().await? {
+            entries.insert(iter.key().await?.to_vec(), iter.value().await?.to_vec());
+        }
+        iter.error().await?;
+        iter.release().await;
+
+        log::info!("captured snapshot of {} key(s)", entries.len());
+        Ok(Self { entries })
+    }
+
+    /// Writes all the key-value pairs to the database, in batches.
+    /// The keys not in the snapshot are left as they are,
+    /// so restore to an empty database to reproduce the exact state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any batch fails to be written.
+    pub async fn restore(&self, db: &BoxedDatabase) -> io::Result<()> {
+        let mut batch = db.new_batch().await?;
+        for (k, v) in &self.entries {
+            batch.put(k, v).await?;
+            if batch.size().await? >= MAX_BATCH_SIZE {
+                batch.write().await?;
+                batch.reset().await;
+            }
+        }
+        batch.write().await?;
+
+        log::info!("restored snapshot of {} key(s)", self.entries.len());
+        Ok(())
+    }
+
+    /// Returns the SHA256 checksum of the uncompressed encoding,
+    /// which identifies the state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding fails.
+    pub fn checksum(&self) -> io::Result<Vec<u8>> {
+        let b = self.encode_body()?;
+        Ok(hash::sha256(b))
+    }
+
+    /// Encodes the snapshot, gzip-compressed if `compress` is true.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding fails.
+    pub fn to_bytes(&self, compress: bool) -> io::Result<Vec<u8>> {
+        let mut b = self.encode_body()?;
+        let checksum = hash::sha256(&b);
+        b.extend_from_slice(&checksum);
+        if !compress {
+            return Ok(b);
+        }
+
+        // no modification time in the header, so the output is deterministic
+        let mut gz = GzEncoder::new(Cursor::new(b), Compression::default());
+        let mut encoded = Vec::new();
+        gz.read_to_end(&mut encoded)?;
+        Ok(encoded)
+    }
+
+    /// Decodes the snapshot, decompressing first if gzip-compressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid snapshot
+    /// or the checksum mismatches.
+    pub fn from_bytes(b: &[u8]) -> io::Result<Self> {
+        let decoded;
+        let b = if b.starts_with(&GZIP_MAGIC) {
+            let mut gz = GzDecoder::new(Cursor::new(b));
+            let mut d = Vec::new();
+            gz.read_to_end(&mut d)?;
+            decoded = d;
+            &decoded[..]
+        } else {
+            b
+        };
+
+        if b.len() < MAGIC.len() + hash::SHA256_OUTPUT_LEN || !b.starts_with(MAGIC) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "not a database snapshot",
+            ));
+        }
+        let (body, checksum) = b.split_at(b.len() - hash::SHA256_OUTPUT_LEN);
+        if hash::sha256(body) != checksum {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "database snapshot checksum mismatch",
+            ));
+        }
+
+        let packer = Packer::load_bytes_for_unpack(MAX_SNAPSHOT_SIZE, &body[MAGIC.len()..]);
+        let unpack = || -> crate::errors::Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+            let version = packer.unpack_u16()?;
+            if version != VERSION {
+                return Err(crate::errors::Error::Other {
+                    message: format!("unknown snapshot version {version}"),
+                    retryable: false,
+                });
+            }
+            let n = packer.unpack_u64()?;
+            let mut entries = BTreeMap::new();
+            for _ in 0..n {
+                let k = packer.unpack_bytes_with_header()?;
+                let v = packer.unpack_bytes_with_header()?;
+                entries.insert(k, v);
+            }
+            Ok(entries)
+        };
+        let entries = unpack().map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        if packer.get_offset() != body.len() - MAGIC.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "trailing bytes in database snapshot",
+            ));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Writes the snapshot to the file, and overwrites the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn sync(&self, file_path: &str, compress: bool) -> io::Result<()> {
+        log::info!("syncing database snapshot to '{file_path}'");
+        if let Some(parent_dir) = Path::new(file_path).parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        fs::write(file_path, self.to_bytes(compress)?)
+    }
+
+    /// Loads the snapshot from the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid snapshot.
+    pub fn load(file_path: &str) -> io::Result<Self> {
+        log::info!("loading database snapshot from '{file_path}'");
+        Self::from_bytes(&fs::read(file_path)?)
+    }
+
+    /// Encodes the magic, version and key-value pairs.
+    fn encode_body(&self) -> io::Result<Vec<u8>> {
+        let packer = Packer::new(MAX_SNAPSHOT_SIZE, 0);
+        let pack = || -> crate::errors::Result<()> {
+            packer.pack_bytes(MAGIC)?;
+            packer.pack_u16(VERSION)?;
+            packer.pack_u64(self.entries.len() as u64)?;
+            for (k, v) in &self.entries {
+                packer.pack_bytes_with_header(k)?;
+                packer.pack_bytes_with_header(v)?;
+            }
+            Ok(())
+        };
+        pack().map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        Ok(packer.take_bytes().to_vec())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::database::snapshot::test_snapshot` --exact --show-output
+#[tokio::test]
+async fn test_snapshot() {
+    use crate::subnet::rpc::database::memdb;
+
+    let db = memdb::Database::new_boxed();
+    let mut w = db.clone();
+    for (k, v) in [(b"foo", b"bar"), (b"baz", b"qux"), (b"fo2", b"ba2")] {
+        w.put(k, v).await.unwrap();
+    }
+
+    let snapshot = Snapshot::capture(&db).await.unwrap();
+    assert_eq!(snapshot.entries.len(), 3);
+    let prefixed = Snapshot::capture_with_prefix(&db, b"fo").await.unwrap();
+    assert_eq!(
+        prefixed.entries.keys().collect::<Vec<_>>(),
+        vec![b"fo2", b"foo"]
+    );
+
+    // deterministic, with or without compression
+    for compress in [false, true] {
+        let b = snapshot.to_bytes(compress).unwrap();
+        assert_eq!(b, snapshot.to_bytes(compress).unwrap());
+        assert_eq!(Snapshot::from_bytes(&b).unwrap(), snapshot);
+    }
+    let mut corrupted = snapshot.to_bytes(false).unwrap();
+    corrupted[12] ^= 1;
+    assert!(Snapshot::from_bytes(&corrupted).is_err());
+    assert!(Snapshot::from_bytes(b"foo").is_err());
+
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("snapshot.gz");
+    let file_path = file_path.to_str().unwrap();
+    snapshot.sync(file_path, true).unwrap();
+    let loaded = Snapshot::load(file_path).unwrap();
+
+    let restored = memdb::Database::new_boxed();
+    loaded.restore(&restored).await.unwrap();
+    let recaptured = Snapshot::capture(&restored).await.unwrap();
+    assert_eq!(recaptured, snapshot);
+    assert_eq!(recaptured.checksum().unwrap(), snapshot.checksum().unwrap());
+}
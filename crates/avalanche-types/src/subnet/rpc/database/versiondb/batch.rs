@@ -39,14 +39,14 @@ pub struct Batch {
     /// Total size of all keys and values in the batch
     size: usize,
     /// Reference to the in-memory database
-    db_mem: Arc<RwLock<HashMap<Vec<u8>, ValueDelete>>>,
+    db_mem: Arc<RwLock<Arc<HashMap<Vec<u8>, ValueDelete>>>>,
     /// Whether the database is closed
     db_closed: Arc<AtomicBool>,
 }
 
 impl Batch {
     pub fn new(
-        db_mem: Arc<RwLock<HashMap<Vec<u8>, ValueDelete>>>,
+        db_mem: Arc<RwLock<Arc<HashMap<Vec<u8>, ValueDelete>>>>,
         db_closed: Arc<AtomicBool>,
     ) -> Self {
         Self {
@@ -95,6 +95,7 @@ impl crate::subnet::rpc::database::batch::Batch for Batch {
 
         let writes = self.writes.write().await;
         let mut mem = self.db_mem.write().await;
+        let mem = Arc::make_mut(&mut mem);
         // Drop writes and mem after the function completes
         for kv in writes.iter() {
             mem.insert(
@@ -2,18 +2,24 @@
 //! `commit()`.
 pub mod batch;
 pub mod iterator;
+pub mod snapshot;
 
 use std::{
-    collections::HashMap,
-    io,
+    collections::{BTreeSet, HashMap},
+    io::{self, ErrorKind},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Weak,
     },
 };
 
 use crate::subnet::rpc::{
-    database::{self, batch::BoxedBatch, iterator::BoxedIterator, BoxedDatabase},
+    database::{
+        self,
+        batch::{BoxedBatch, Op},
+        iterator::BoxedIterator,
+        BoxedDatabase,
+    },
     errors::Error,
 };
 
@@ -28,26 +34,148 @@ use tokio::sync::RwLock;
 pub struct Database {
     /// The underlying database
     db: BoxedDatabase,
-    /// In-memory storage for uncommitted changes
-    mem: Arc<RwLock<HashMap<Vec<u8>, iterator::ValueDelete>>>,
+    /// In-memory storage for uncommitted changes, shared with the snapshots
+    /// until the next mutation (copy-on-write)
+    mem: Arc<RwLock<Arc<HashMap<Vec<u8>, iterator::ValueDelete>>>>,
     /// Batch for committing changes
-    #[allow(dead_code)] // 这个字段在将来可能会用到
     batch: BoxedBatch,
+    /// Called on every commit to add more operations to the same batch.
+    hooks: Arc<RwLock<Vec<CommitHook>>>,
+    /// Snapshots to preserve the committed keys for, held for writing
+    /// across preserving and writing a commit so no snapshot is taken
+    /// in between.
+    snapshots: Arc<RwLock<Vec<Weak<snapshot::State>>>>,
     /// True if the database is closed.
     closed: Arc<AtomicBool>,
 }
 
+/// Returns the operations to write atomically with every commit
+/// (e.g., the indexes derived from the committed state).
+pub type CommitHook = Arc<dyn Fn() -> Vec<Op> + Send + Sync>;
+
 impl Database {
     /// Creates a new versiondb database
     #[must_use]
     pub fn new(db: BoxedDatabase, batch: BoxedBatch) -> Self {
         Self {
             db,
-            mem: Arc::new(RwLock::new(HashMap::new())),
+            mem: Arc::new(RwLock::new(Arc::new(HashMap::new()))),
             batch,
+            hooks: Arc::new(RwLock::new(Vec::new())),
+            snapshots: Arc::new(RwLock::new(Vec::new())),
             closed: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Returns the underlying batch that commits write to,
+    /// which is shared with this database.
+    #[must_use]
+    pub fn batch(&self) -> BoxedBatch {
+        self.batch.clone()
+    }
+
+    /// Registers the hook whose operations are written in the same batch
+    /// as every subsequent commit.
+    pub async fn on_commit<F>(&self, hook: F)
+    where
+        F: Fn() -> Vec<Op> + Send + Sync + 'static,
+    {
+        self.hooks.write().await.push(Arc::new(hook));
+    }
+
+    /// Commits the uncommitted changes together with the operations and
+    /// the operations of the registered hooks, in a single write of the
+    /// underlying batch. The operations are applied after the uncommitted
+    /// changes in order, so they take precedence on the same key.
+    /// Nothing is written if any operation fails, and the uncommitted changes
+    /// are kept.
+    ///
+    /// # Errors
+    /// Returns error if the database is closed, or the batch fails.
+    pub async fn commit_with(&mut self, ops: &[Op]) -> io::Result<()> {
+        let hooks = self.hooks.read().await.clone();
+        let mut ops = ops.to_vec();
+        for hook in hooks {
+            ops.extend(hook());
+        }
+
+        let mut batch = database::Commitable::commit_batch(self).await?;
+        for op in &ops {
+            op.apply(&mut batch).await?;
+        }
+
+        // no snapshot is taken until the commit completes, and the values are
+        // preserved before they are overwritten, so the snapshot reads (which
+        // read the underlying database before the preserved values) see the
+        // state before the commit without waiting for it
+        let mut snapshots = self.snapshots.write().await;
+        snapshots.retain(|s| s.strong_count() > 0);
+        self.preserve(&snapshots, &ops).await?;
+
+        batch.write().await?;
+        batch.reset().await;
+        database::Commitable::abort(self).await?;
+        drop(snapshots);
+        Ok(())
+    }
+
+    /// Returns a read-only view of the current state (including the
+    /// uncommitted changes), which is not affected by the subsequent
+    /// mutations and commits of this database.
+    ///
+    /// Taking a snapshot only shares the uncommitted changes, which are
+    /// copied on the next mutation. Each commit while the snapshot is alive
+    /// reads the committed keys from the underlying database first, so keep
+    /// the snapshots short-lived (e.g., per API request). Writes to the
+    /// underlying database outside of this database are not isolated.
+    ///
+    /// # Errors
+    /// Returns error if the database is closed.
+    pub async fn snapshot(&self) -> io::Result<snapshot::Snapshot> {
+        if self.closed.load(Ordering::Relaxed) {
+            return Err(Error::DatabaseClosed.to_err());
+        }
+
+        let mut snapshots = self.snapshots.write().await;
+        let mem = Arc::clone(&*self.mem.read().await);
+        let s = snapshot::Snapshot::new(self.db.clone(), mem, Arc::clone(&self.closed));
+        snapshots.push(s.state());
+        drop(snapshots);
+        Ok(s)
+    }
+
+    /// Preserves the values of the keys about to be committed
+    /// for the live snapshots.
+    async fn preserve(&self, snapshots: &[Weak<snapshot::State>], ops: &[Op]) -> io::Result<()> {
+        let live: Vec<_> = snapshots.iter().filter_map(Weak::upgrade).collect();
+        if live.is_empty() {
+            return Ok(());
+        }
+
+        let mem = Arc::clone(&*self.mem.read().await);
+        let keys: BTreeSet<&[u8]> = mem
+            .keys()
+            .map(Vec::as_slice)
+            .chain(ops.iter().map(Op::key))
+            .collect();
+        for key in keys {
+            let value = match self.db.get(key).await {
+                Ok(value) => iterator::ValueDelete {
+                    value,
+                    delete: false,
+                },
+                Err(e) if e.kind() == ErrorKind::NotFound => iterator::ValueDelete {
+                    value: vec![],
+                    delete: true,
+                },
+                Err(e) => return Err(e),
+            };
+            for s in &live {
+                s.preserve(key, &value).await;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[tonic::async_trait]
@@ -72,6 +200,9 @@ impl database::KeyValueReaderWriterDeleter for Database {
         }
 
         if let Some(val) = self.mem.read().await.get(key) {
+            if val.delete {
+                return Err(Error::NotFound.to_err());
+            }
             return Ok(val.value.clone());
         }
 
@@ -84,7 +215,7 @@ impl database::KeyValueReaderWriterDeleter for Database {
             return Err(Error::DatabaseClosed.to_err());
         }
 
-        self.mem.write().await.insert(
+        Arc::make_mut(&mut *self.mem.write().await).insert(
             key.to_vec(),
             iterator::ValueDelete {
                 value: value.to_vec(),
@@ -102,6 +233,7 @@ impl database::KeyValueReaderWriterDeleter for Database {
         }
 
         let mut mem = self.mem.write().await;
+        let mem = Arc::make_mut(&mut mem);
         if let Some(val) = mem.get_mut(key) {
             val.delete = true;
         }
@@ -112,7 +244,6 @@ impl database::KeyValueReaderWriterDeleter for Database {
                 delete: true,
             },
         );
-        drop(mem);
 
         Ok(())
     }
@@ -170,27 +301,11 @@ impl database::iterator::Iteratee for Database {
             return Err(Error::DatabaseClosed.to_err());
         }
 
-        let mem = self.mem.read().await;
-        let mut keys: Vec<Vec<u8>> = Vec::with_capacity(mem.len());
-        for (k, _v) in mem.iter() {
-            if k.starts_with(prefix) && k >= &start.to_vec() {
-                keys.push(k.to_owned());
-            }
-        }
-
-        // keys need to be in sorted order
-        keys.sort();
-
-        let mut values: Vec<iterator::ValueDelete> = Vec::with_capacity(keys.len());
-        for key in &keys {
-            if let Some(v) = mem.get(key) {
-                values.push(v.to_owned());
-            }
-        }
-
-        Ok(iterator::Iterator::new_boxed(
-            keys,
-            values,
+        let mem = Arc::clone(&*self.mem.read().await);
+        Ok(iterator::new_boxed(
+            mem.iter(),
+            start,
+            prefix,
             Arc::clone(&self.closed),
             self.db
                 .new_iterator_with_start_and_prefix(start, prefix)
@@ -214,16 +329,12 @@ impl database::batch::Batcher for Database {
 impl database::Commitable for Database {
     /// Implements the [`crate::subnet::rpc::database::Commitable`] trait.
     async fn commit(&mut self) -> io::Result<()> {
-        let mut batch = self.commit_batch().await?;
-        batch.write().await?;
-        batch.reset().await;
-        self.abort().await?;
-        Ok(())
+        self.commit_with(&[]).await
     }
 
     /// Implements the [`crate::subnet::rpc::database::Commitable`] trait.
     async fn abort(&self) -> io::Result<()> {
-        self.mem.write().await.clear();
+        *self.mem.write().await = Arc::new(HashMap::new());
         Ok(())
     }
 
@@ -294,6 +405,11 @@ async fn iterate_test() {
     assert!(iterator.error().await.is_ok());
 
     db.delete(key1).await.unwrap();
+    assert_eq!(
+        db.get(key1).await.unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+    assert!(!db.has(key1).await.unwrap());
 
     let mut iterator = db.new_iterator().await.unwrap();
     assert!(iterator.next().await.unwrap());
@@ -331,3 +447,46 @@ async fn iterate_test() {
     assert!(iterator.value().await.unwrap().is_empty());
     assert!(iterator.error().await.is_ok());
 }
+
+#[tokio::test]
+async fn commit_with_test() {
+    use crate::subnet::rpc::database::{memdb, Closer, KeyValueReaderWriterDeleter};
+
+    let base_db = memdb::Database::new_boxed();
+    let batch = base_db.new_batch().await.unwrap();
+    let mut db = Database::new(base_db.clone(), batch);
+
+    db.on_commit(|| {
+        vec![Op::Put {
+            key: b"index".to_vec(),
+            value: b"1".to_vec(),
+        }]
+    })
+    .await;
+
+    db.put(b"foo", b"bar").await.unwrap();
+    db.put(b"stale", b"value").await.unwrap();
+    db.commit_with(&[
+        Op::Put {
+            key: b"baz".to_vec(),
+            value: b"qux".to_vec(),
+        },
+        Op::Delete {
+            key: b"stale".to_vec(),
+        },
+    ])
+    .await
+    .unwrap();
+
+    assert_eq!(base_db.get(b"foo").await.unwrap(), b"bar");
+    assert_eq!(base_db.get(b"baz").await.unwrap(), b"qux");
+    assert_eq!(base_db.get(b"index").await.unwrap(), b"1");
+    assert!(!base_db.has(b"stale").await.unwrap());
+
+    // nothing is written once closed, and the changes are kept
+    db.put(b"pending", b"value").await.unwrap();
+    db.close().await.unwrap();
+    assert!(db.commit_with(&[]).await.is_err());
+    assert!(!base_db.has(b"pending").await.unwrap());
+    assert_eq!(db.mem.read().await.len(), 1);
+}
@@ -0,0 +1,521 @@
+//! Read-only point-in-time view of a versiondb.
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, ErrorKind},
+    ops::Bound,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+};
+
+use tokio::sync::RwLock;
+
+use crate::subnet::rpc::{
+    database::{self, batch::BoxedBatch, iterator::BoxedIterator, BoxedDatabase},
+    errors::Error,
+};
+
+use super::iterator::{self, ValueDelete};
+
+/// Shared state of the snapshot, also referenced (weakly) by the versiondb
+/// to preserve the keys overwritten by its commits.
+///
+/// The commits preserve the values before writing them, so the reads of
+/// the underlying database are followed by the lookup of the preserved
+/// values: a value written after the snapshot was taken is always found
+/// preserved, and the reads never wait for the commits.
+pub(super) struct State {
+    /// The underlying database of the versiondb
+    db: BoxedDatabase,
+    /// Uncommitted changes of the versiondb when the snapshot was taken
+    mem: Arc<HashMap<Vec<u8>, ValueDelete>>,
+    /// Values of the underlying database before the commits since
+    /// the snapshot was taken, in the key order for the iterators
+    preserved: RwLock<BTreeMap<Vec<u8>, ValueDelete>>,
+    /// Whether the versiondb is closed
+    closed: Arc<AtomicBool>,
+}
+
+impl State {
+    /// Records the value of the underlying database before a commit,
+    /// unless the key is already shadowed.
+    pub(super) async fn preserve(&self, key: &[u8], value: &ValueDelete) {
+        if self.mem.contains_key(key) {
+            return;
+        }
+        self.preserved
+            .write()
+            .await
+            .entry(key.to_vec())
+            .or_insert_with(|| value.clone());
+    }
+
+    /// Returns the value preserved for the key, if any.
+    async fn preserved(&self, key: &[u8]) -> Option<ValueDelete> {
+        self.preserved.read().await.get(key).cloned()
+    }
+
+    /// Returns the first preserved key after the previous key (or from
+    /// the start), with the prefix and up to the bound, if any.
+    async fn next_preserved(
+        &self,
+        after: Option<&[u8]>,
+        start: &[u8],
+        prefix: &[u8],
+        bound: Option<&[u8]>,
+    ) -> Option<(Vec<u8>, ValueDelete)> {
+        let lower = after.map_or(Bound::Included(start), Bound::Excluded);
+        self.preserved
+            .read()
+            .await
+            .range::<[u8], _>((lower, Bound::Unbounded))
+            .take_while(|(k, _)| bound.map_or(true, |b| k.as_slice() <= b))
+            .find(|(k, _)| k.starts_with(prefix) && k.as_slice() >= start)
+            .map(|(k, v)| (k.clone(), v.clone()))
+    }
+}
+
+/// Read-only view of a versiondb, taken with
+/// [`crate::subnet::rpc::database::versiondb::Database::snapshot`].
+/// Writes and batches fail with [`ErrorKind::PermissionDenied`].
+#[derive(Clone)]
+pub struct Snapshot {
+    state: Arc<State>,
+}
+
+impl Snapshot {
+    pub(super) fn new(
+        db: BoxedDatabase,
+        mem: Arc<HashMap<Vec<u8>, ValueDelete>>,
+        closed: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            state: Arc::new(State {
+                db,
+                mem,
+                preserved: RwLock::new(BTreeMap::new()),
+                closed,
+            }),
+        }
+    }
+
+    pub(super) fn state(&self) -> Weak<State> {
+        Arc::downgrade(&self.state)
+    }
+
+    /// Returns the error of the writes to the snapshot.
+    fn read_only() -> io::Error {
+        io::Error::new(ErrorKind::PermissionDenied, "read-only snapshot")
+    }
+}
+
+#[tonic::async_trait]
+impl database::KeyValueReaderWriterDeleter for Snapshot {
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn has(&self, key: &[u8]) -> io::Result<bool> {
+        if self.state.closed.load(Ordering::Relaxed) {
+            return Err(Error::DatabaseClosed.to_err());
+        }
+
+        if let Some(v) = self.state.mem.get(key) {
+            return Ok(!v.delete);
+        }
+        let has = self.state.db.has(key).await;
+        if let Some(v) = self.state.preserved(key).await {
+            return Ok(!v.delete);
+        }
+        has
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn get(&self, key: &[u8]) -> io::Result<Vec<u8>> {
+        if self.state.closed.load(Ordering::Relaxed) {
+            return Err(Error::DatabaseClosed.to_err());
+        }
+
+        let value = match self.state.mem.get(key) {
+            Some(v) => Some(v.clone()),
+            None => {
+                let value = self.state.db.get(key).await;
+                match self.state.preserved(key).await {
+                    Some(v) => Some(v),
+                    None => return value,
+                }
+            }
+        };
+        match value {
+            Some(v) if !v.delete => Ok(v.value),
+            _ => Err(Error::NotFound.to_err()),
+        }
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn put(&mut self, _key: &[u8], _value: &[u8]) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::KeyValueReaderWriterDeleter`] trait.
+    async fn delete(&mut self, _key: &[u8]) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+}
+
+#[tonic::async_trait]
+impl database::Closer for Snapshot {
+    /// Implements the [`crate::subnet::rpc::database::Closer`] trait.
+    /// No-op, the snapshot is closed with the versiondb.
+    async fn close(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl crate::subnet::rpc::health::Checkable for Snapshot {
+    /// Implements the [`crate::subnet::rpc::health::Checkable`] trait.
+    async fn health_check(&self) -> io::Result<Vec<u8>> {
+        if self.state.closed.load(Ordering::Relaxed) {
+            return Err(Error::DatabaseClosed.to_err());
+        }
+
+        self.state.db.health_check().await
+    }
+}
+
+#[tonic::async_trait]
+impl database::iterator::Iteratee for Snapshot {
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator(&self) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(&[], &[]).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_start(&self, start: &[u8]) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(start, &[]).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_prefix(&self, prefix: &[u8]) -> io::Result<BoxedIterator> {
+        self.new_iterator_with_start_and_prefix(&[], prefix).await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iteratee`] trait.
+    async fn new_iterator_with_start_and_prefix(
+        &self,
+        start: &[u8],
+        prefix: &[u8],
+    ) -> io::Result<BoxedIterator> {
+        if self.state.closed.load(Ordering::Relaxed) {
+            return Err(Error::DatabaseClosed.to_err());
+        }
+
+        let db_iterator = self
+            .state
+            .db
+            .new_iterator_with_start_and_prefix(start, prefix)
+            .await?;
+        let preserved = PreservedIterator {
+            state: Arc::clone(&self.state),
+            iterator: db_iterator,
+            start: start.to_vec(),
+            prefix: prefix.to_vec(),
+            next: None,
+            exhausted: false,
+            last: None,
+            key: vec![],
+            value: vec![],
+        };
+
+        // the uncommitted changes take precedence over the preserved values
+        Ok(iterator::new_boxed(
+            self.state.mem.iter(),
+            start,
+            prefix,
+            Arc::clone(&self.state.closed),
+            Box::new(preserved),
+        ))
+    }
+}
+
+/// Iterates over the live iterator of the underlying database, with the
+/// values preserved since the snapshot was taken looked up as it goes.
+/// Each pair of the underlying iterator is fetched before the lookup, so
+/// the pairs written by the concurrent commits are shadowed.
+struct PreservedIterator {
+    /// Snapshot state
+    state: Arc<State>,
+    /// Iterator of the underlying database
+    iterator: BoxedIterator,
+    /// Start of the iteration
+    start: Vec<u8>,
+    /// Prefix of the iteration
+    prefix: Vec<u8>,
+    /// Next pair of the underlying iterator, not yet returned
+    next: Option<(Vec<u8>, Vec<u8>)>,
+    /// Whether the underlying iterator has been exhausted
+    exhausted: bool,
+    /// Last key passed, including the deleted ones
+    last: Option<Vec<u8>>,
+    /// Current key
+    key: Vec<u8>,
+    /// Current value
+    value: Vec<u8>,
+}
+
+#[tonic::async_trait]
+impl database::iterator::Iterator for PreservedIterator {
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn next(&mut self) -> io::Result<bool> {
+        loop {
+            if self.next.is_none() && !self.exhausted {
+                if self.iterator.next().await? {
+                    self.next = Some((
+                        self.iterator.key().await?.to_vec(),
+                        self.iterator.value().await?.to_vec(),
+                    ));
+                } else {
+                    self.exhausted = true;
+                }
+            }
+
+            let preserved = self
+                .state
+                .next_preserved(
+                    self.last.as_deref(),
+                    &self.start,
+                    &self.prefix,
+                    self.next.as_ref().map(|(k, _)| k.as_slice()),
+                )
+                .await;
+
+            let (key, value) = match preserved {
+                Some((key, value)) => {
+                    if self.next.as_ref().is_some_and(|(k, _)| *k == key) {
+                        // written after the snapshot was taken
+                        self.next = None;
+                    }
+                    (key, value)
+                }
+                None => match self.next.take() {
+                    Some((key, value)) => (
+                        key,
+                        ValueDelete {
+                            value,
+                            delete: false,
+                        },
+                    ),
+                    None => {
+                        self.key.clear();
+                        self.value.clear();
+                        return Ok(false);
+                    }
+                },
+            };
+
+            self.last = Some(key.clone());
+            if !value.delete {
+                self.key = key;
+                self.value = value.value;
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn error(&mut self) -> io::Result<()> {
+        self.iterator.error().await
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn key(&self) -> io::Result<&[u8]> {
+        Ok(&self.key)
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn value(&self) -> io::Result<&[u8]> {
+        Ok(&self.value)
+    }
+
+    /// Implements the [`crate::subnet::rpc::database::iterator::Iterator`] trait.
+    async fn release(&mut self) {
+        self.next = None;
+        self.last = None;
+        self.key.clear();
+        self.value.clear();
+        self.iterator.release().await;
+    }
+}
+
+#[tonic::async_trait]
+impl database::batch::Batcher for Snapshot {
+    /// Implements the [`crate::subnet::rpc::database::batch::Batcher`] trait.
+    async fn new_batch(&self) -> io::Result<BoxedBatch> {
+        Err(Self::read_only())
+    }
+}
+
+impl database::Database for Snapshot {}
+
+#[tokio::test]
+async fn snapshot_test() {
+    use crate::subnet::rpc::database::{
+        iterator::Iteratee, memdb, Commitable, KeyValueReaderWriterDeleter,
+    };
+
+    let base_db = memdb::Database::new_boxed();
+    let batch = base_db.new_batch().await.unwrap();
+    let mut db = super::Database::new(base_db, batch);
+
+    db.put(b"committed", b"v1").await.unwrap();
+    db.put(b"removed", b"v1").await.unwrap();
+    db.commit().await.unwrap();
+    db.put(b"pending", b"v1").await.unwrap();
+
+    let mut snapshot = db.snapshot().await.unwrap();
+
+    // mutations and commits after the snapshot are not visible
+    db.put(b"pending", b"v2").await.unwrap();
+    db.put(b"committed", b"v2").await.unwrap();
+    db.put(b"added", b"v2").await.unwrap();
+    db.delete(b"removed").await.unwrap();
+    db.commit().await.unwrap();
+    db.put(b"committed", b"v3").await.unwrap();
+    db.commit().await.unwrap();
+
+    assert_eq!(snapshot.get(b"committed").await.unwrap(), b"v1");
+    assert_eq!(snapshot.get(b"pending").await.unwrap(), b"v1");
+    assert_eq!(snapshot.get(b"removed").await.unwrap(), b"v1");
+    assert!(!snapshot.has(b"added").await.unwrap());
+    assert_eq!(
+        snapshot.get(b"added").await.unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+
+    let mut iterator = snapshot.new_iterator().await.unwrap();
+    let mut kvs = Vec::new();
+    while iterator.next().await.unwrap() {
+        kvs.push((
+            iterator.key().await.unwrap().to_vec(),
+            iterator.value().await.unwrap().to_vec(),
+        ));
+    }
+    assert_eq!(
+        kvs,
+        vec![
+            (b"committed".to_vec(), b"v1".to_vec()),
+            (b"pending".to_vec(), b"v1".to_vec()),
+            (b"removed".to_vec(), b"v1".to_vec()),
+        ]
+    );
+
+    // the latest state is visible to the new snapshots
+    let latest = db.snapshot().await.unwrap();
+    assert_eq!(latest.get(b"committed").await.unwrap(), b"v3");
+    assert!(!latest.has(b"removed").await.unwrap());
+
+    assert_eq!(
+        snapshot.put(b"foo", b"bar").await.unwrap_err().kind(),
+        ErrorKind::PermissionDenied
+    );
+
+    // dropped snapshots (and their iterators) are no longer tracked
+    drop(iterator);
+    drop(snapshot);
+    drop(latest);
+    db.put(b"committed", b"v4").await.unwrap();
+    db.commit().await.unwrap();
+    assert!(db.snapshots.read().await.is_empty());
+}
+
+#[tokio::test]
+async fn snapshot_iterator_commit_test() {
+    use crate::subnet::rpc::database::{
+        iterator::Iteratee, memdb, Commitable, KeyValueReaderWriterDeleter,
+    };
+
+    let base_db = memdb::Database::new_boxed();
+    let batch = base_db.new_batch().await.unwrap();
+    let mut db = super::Database::new(base_db, batch);
+    for k in ["a", "b", "c", "d"] {
+        db.put(k.as_bytes(), b"v1").await.unwrap();
+    }
+    db.commit().await.unwrap();
+
+    let snapshot = db.snapshot().await.unwrap();
+    let mut iterator = snapshot.new_iterator().await.unwrap();
+    assert!(iterator.next().await.unwrap());
+    assert_eq!(iterator.key().await.unwrap(), b"a");
+
+    // commits while iterating are shadowed by the preserved values
+    db.delete(b"b").await.unwrap();
+    db.put(b"bb", b"v2").await.unwrap();
+    db.put(b"c", b"v2").await.unwrap();
+    db.commit().await.unwrap();
+    db.delete(b"d").await.unwrap();
+    db.commit().await.unwrap();
+
+    let mut kvs = Vec::new();
+    while iterator.next().await.unwrap() {
+        kvs.push((
+            iterator.key().await.unwrap().to_vec(),
+            iterator.value().await.unwrap().to_vec(),
+        ));
+    }
+    iterator.error().await.unwrap();
+    assert_eq!(
+        kvs,
+        vec![
+            (b"b".to_vec(), b"v1".to_vec()),
+            (b"c".to_vec(), b"v1".to_vec()),
+            (b"d".to_vec(), b"v1".to_vec()),
+        ]
+    );
+
+    let mut iterator = snapshot.new_iterator_with_prefix(b"b").await.unwrap();
+    assert!(iterator.next().await.unwrap());
+    assert_eq!(iterator.key().await.unwrap(), b"b");
+    assert!(!iterator.next().await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn snapshot_concurrent_commit_test() {
+    use crate::subnet::rpc::database::{
+        iterator::Iteratee, memdb, Commitable, KeyValueReaderWriterDeleter,
+    };
+
+    const COMMITS: usize = 200;
+
+    let base_db = memdb::Database::new_boxed();
+    let batch = base_db.new_batch().await.unwrap();
+    let mut db = super::Database::new(base_db, batch);
+    db.put(b"k", b"v0").await.unwrap();
+    db.commit().await.unwrap();
+
+    let snapshot = db.snapshot().await.unwrap();
+    let writer = tokio::spawn(async move {
+        for i in 1..=COMMITS {
+            db.put(b"k", format!("v{i}").as_bytes()).await.unwrap();
+            db.commit().await.unwrap();
+        }
+        db
+    });
+
+    // every read sees the state when the snapshot was taken
+    while !writer.is_finished() {
+        assert!(snapshot.has(b"k").await.unwrap());
+        assert_eq!(snapshot.get(b"k").await.unwrap(), b"v0");
+
+        let mut iterator = snapshot.new_iterator().await.unwrap();
+        assert!(iterator.next().await.unwrap());
+        assert_eq!(iterator.key().await.unwrap(), b"k");
+        assert_eq!(iterator.value().await.unwrap(), b"v0");
+        assert!(!iterator.next().await.unwrap());
+    }
+
+    let db = writer.await.unwrap();
+    assert_eq!(snapshot.get(b"k").await.unwrap(), b"v0");
+    assert_eq!(
+        db.get(b"k").await.unwrap(),
+        format!("v{COMMITS}").as_bytes()
+    );
+}
@@ -1,5 +1,19 @@
 //! Provides health checking.
-use std::io::Result;
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind, Result},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_with::serde_as;
+use tokio::{
+    sync::{broadcast, RwLock},
+    task::JoinHandle,
+    time,
+};
 
 /// Checkable can have its health checked
 ///
@@ -8,3 +22,369 @@ use std::io::Result;
 pub trait Checkable {
     async fn health_check(&self) -> Result<Vec<u8>>;
 }
+
+#[tonic::async_trait]
+impl<T> Checkable for Arc<RwLock<T>>
+where
+    T: Checkable + Send + Sync,
+{
+    /// Implements the [`crate::subnet::rpc::health::Checkable`] trait.
+    async fn health_check(&self) -> Result<Vec<u8>> {
+        self.read().await.health_check().await
+    }
+}
+
+/// Helper type which defines a thread safe shared Checkable trait.
+pub type SharedCheck = Arc<dyn Checkable + Send + Sync + 'static>;
+
+/// Default interval between the background checks.
+/// ref. "--health-check-frequency" in avalanchego
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default timeout of a single check.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Represents the latest result of a check.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/api/health#Result>
+#[serde_as]
+#[derive(Debug, Serialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    /// Details returned by the check, as JSON if valid JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde_as(as = "crate::codec::serde::rfc_3339::DateTimeUtc")]
+    pub timestamp: DateTime<Utc>,
+    /// Duration of the check in nanoseconds.
+    pub duration: i64,
+    pub contiguous_failures: i64,
+    #[serde_as(as = "Option<crate::codec::serde::rfc_3339::DateTimeUtc>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_of_first_failure: Option<DateTime<Utc>>,
+}
+
+impl CheckResult {
+    /// Returns the result of a registered check that has not run yet.
+    fn not_yet_run(now: DateTime<Utc>) -> Self {
+        Self {
+            message: None,
+            error: Some("not yet run".to_string()),
+            timestamp: now,
+            duration: 0,
+            contiguous_failures: 1,
+            time_of_first_failure: Some(now),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Represents the aggregated results of all checks.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/api/health#APIReply>
+#[derive(Debug, Serialize, Eq, PartialEq, Clone)]
+pub struct Report {
+    pub checks: BTreeMap<String, CheckResult>,
+    pub healthy: bool,
+}
+
+/// Registry of the named checks (e.g., "database", "mempool"), which runs
+/// them on an interval in the background and aggregates their latest
+/// results into a [`Report`]. The registry itself is [`Checkable`], so it
+/// can serve the VM "health" RPC.
+#[derive(Clone)]
+pub struct Registry {
+    checks: Arc<RwLock<BTreeMap<String, SharedCheck>>>,
+    results: Arc<RwLock<BTreeMap<String, CheckResult>>>,
+    /// Interval between the background checks.
+    interval: Duration,
+    /// Timeout of a single check, which fails once expired.
+    timeout: Duration,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            checks: Arc::new(RwLock::new(BTreeMap::new())),
+            results: Arc::new(RwLock::new(BTreeMap::new())),
+            interval: DEFAULT_CHECK_INTERVAL,
+            timeout: DEFAULT_CHECK_TIMEOUT,
+        }
+    }
+
+    #[must_use]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Registers the named check, which reports unhealthy until its first run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is already registered.
+    pub async fn register(&self, name: &str, check: SharedCheck) -> Result<()> {
+        let mut checks = self.checks.write().await;
+        if checks.contains_key(name) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("health check '{name}' already registered"),
+            ));
+        }
+        checks.insert(name.to_string(), check);
+        drop(checks);
+
+        log::info!("registered health check '{name}'");
+        Ok(())
+    }
+
+    /// Removes the named check and its result.
+    pub async fn deregister(&self, name: &str) {
+        self.checks.write().await.remove(name);
+        self.results.write().await.remove(name);
+    }
+
+    /// Returns the number of registered checks.
+    pub async fn len(&self) -> usize {
+        self.checks.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.checks.read().await.is_empty()
+    }
+
+    /// Runs all checks concurrently, and records their results.
+    pub async fn run_once(&self) {
+        let checks = self.checks.read().await.clone();
+        let outcomes = futures::future::join_all(checks.into_iter().map(|(name, check)| {
+            let timeout = self.timeout;
+            async move {
+                let start = Instant::now();
+                let outcome = time::timeout(timeout, check.health_check())
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(Error::new(
+                            ErrorKind::TimedOut,
+                            format!("timed out after {timeout:?}"),
+                        ))
+                    });
+                (name, outcome, start.elapsed())
+            }
+        }))
+        .await;
+
+        let now = Utc::now();
+        let mut results = self.results.write().await;
+        for (name, outcome, elapsed) in outcomes {
+            let prev = results.remove(&name);
+            let result = match outcome {
+                Ok(details) => CheckResult {
+                    message: to_message(&details),
+                    error: None,
+                    timestamp: now,
+                    duration: duration_nanos(elapsed),
+                    contiguous_failures: 0,
+                    time_of_first_failure: None,
+                },
+                Err(e) => {
+                    log::warn!("health check '{name}' failed '{e}'");
+                    let (contiguous_failures, time_of_first_failure) = match prev {
+                        Some(prev) if !prev.is_healthy() => (
+                            prev.contiguous_failures + 1,
+                            prev.time_of_first_failure.or(Some(now)),
+                        ),
+                        _ => (1, Some(now)),
+                    };
+                    CheckResult {
+                        message: None,
+                        error: Some(e.to_string()),
+                        timestamp: now,
+                        duration: duration_nanos(elapsed),
+                        contiguous_failures,
+                        time_of_first_failure,
+                    }
+                }
+            };
+            results.insert(name, result);
+        }
+    }
+
+    /// Spawns the task running all checks on the interval (first run
+    /// immediately) until the stop signal.
+    #[must_use]
+    pub fn spawn(&self, mut stop_ch: broadcast::Receiver<()>) -> JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = time::interval(registry.interval);
+            ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => registry.run_once().await,
+                    _ = stop_ch.recv() => {
+                        log::info!("stopping health checks");
+                        return;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns the latest results of all registered checks.
+    pub async fn report(&self) -> Report {
+        let names: Vec<String> = self.checks.read().await.keys().cloned().collect();
+        let results = self.results.read().await;
+
+        let now = Utc::now();
+        let checks: BTreeMap<String, CheckResult> = names
+            .into_iter()
+            .map(|name| {
+                let result = results
+                    .get(&name)
+                    .cloned()
+                    .unwrap_or_else(|| CheckResult::not_yet_run(now));
+                (name, result)
+            })
+            .collect();
+        drop(results);
+
+        let healthy = checks.values().all(CheckResult::is_healthy);
+        Report { checks, healthy }
+    }
+}
+
+#[tonic::async_trait]
+impl Checkable for Registry {
+    /// Implements the [`crate::subnet::rpc::health::Checkable`] trait.
+    /// Returns the report in JSON, as the error message if unhealthy.
+    async fn health_check(&self) -> Result<Vec<u8>> {
+        let report = self.report().await;
+        let b = serde_json::to_vec(&report).map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to serialize health report {e}"),
+            )
+        })?;
+        if report.healthy {
+            return Ok(b);
+        }
+        Err(Error::new(
+            ErrorKind::Other,
+            String::from_utf8_lossy(&b).to_string(),
+        ))
+    }
+}
+
+/// Returns the check details as JSON if valid, or as a string otherwise.
+fn to_message(details: &[u8]) -> Option<serde_json::Value> {
+    if details.is_empty() {
+        return None;
+    }
+    Some(serde_json::from_slice(details).unwrap_or_else(|_| {
+        serde_json::Value::String(String::from_utf8_lossy(details).to_string())
+    }))
+}
+
+/// Returns the duration in nanoseconds, same as Go's "time.Duration".
+fn duration_nanos(d: Duration) -> i64 {
+    i64::try_from(d.as_nanos()).unwrap_or(i64::MAX)
+}
+
+#[tokio::test]
+async fn registry_test() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct Check {
+        failing: AtomicBool,
+        slow: bool,
+    }
+
+    #[tonic::async_trait]
+    impl Checkable for Check {
+        async fn health_check(&self) -> std::io::Result<Vec<u8>> {
+            if self.slow {
+                time::sleep(Duration::from_secs(5)).await;
+            }
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(Error::new(ErrorKind::Other, "failing"));
+            }
+            Ok(br#"{"pending":1}"#.to_vec())
+        }
+    }
+
+    let registry = Registry::new().timeout(Duration::from_millis(100));
+    let check = Arc::new(Check::default());
+    registry.register("mempool", check.clone()).await.unwrap();
+    assert!(registry.register("mempool", check.clone()).await.is_err());
+
+    // unhealthy until the first run
+    let report = registry.report().await;
+    assert!(!report.healthy);
+    assert_eq!(
+        report.checks["mempool"].error.as_deref(),
+        Some("not yet run")
+    );
+
+    registry.run_once().await;
+    let report = registry.report().await;
+    assert!(report.healthy);
+    assert_eq!(
+        report.checks["mempool"].message,
+        Some(serde_json::json!({ "pending": 1 }))
+    );
+    assert!(registry.health_check().await.is_ok());
+
+    check.failing.store(true, Ordering::Relaxed);
+    registry.run_once().await;
+    registry.run_once().await;
+    let report = registry.report().await;
+    assert!(!report.healthy);
+    assert_eq!(report.checks["mempool"].contiguous_failures, 2);
+    let err = registry.health_check().await.unwrap_err();
+    assert!(err.to_string().contains(r#""healthy":false"#));
+
+    let slow = Arc::new(Check {
+        failing: AtomicBool::new(false),
+        slow: true,
+    });
+    registry.deregister("mempool").await;
+    registry.register("network", slow).await.unwrap();
+    registry.run_once().await;
+    let report = registry.report().await;
+    assert_eq!(report.checks.len(), 1);
+    assert!(report.checks["network"]
+        .error
+        .as_deref()
+        .unwrap()
+        .starts_with("timed out"));
+
+    // the background task runs the checks right away
+    let registry = Registry::new().interval(Duration::from_millis(10));
+    registry
+        .register("mempool", Arc::new(Check::default()))
+        .await
+        .unwrap();
+    let (stop_tx, stop_rx) = broadcast::channel(1);
+    let handle = registry.spawn(stop_rx);
+    time::sleep(Duration::from_millis(50)).await;
+    assert!(registry.report().await.healthy);
+    stop_tx.send(()).unwrap();
+    handle.await.unwrap();
+}
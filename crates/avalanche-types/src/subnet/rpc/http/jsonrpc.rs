@@ -0,0 +1,375 @@
+//! JSON-RPC 2.0 server for the VM HTTP APIs, so a [`Router`] can be returned
+//! from "`create_handlers`" as the chain handler.
+//!
+//! ref. <https://www.jsonrpc.org/specification>
+use std::{collections::HashMap, fmt, future::Future, io, sync::Arc};
+
+use bytes::Bytes;
+use futures::future::{join_all, BoxFuture, FutureExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::handle::Handle;
+use crate::proto::http::Element;
+
+pub const VERSION: &str = "2.0";
+
+/// Invalid JSON was received by the server.
+pub const PARSE_ERROR: i32 = -32700;
+/// The JSON sent is not a valid request object.
+pub const INVALID_REQUEST: i32 = -32600;
+/// The method does not exist.
+pub const METHOD_NOT_FOUND: i32 = -32601;
+/// Invalid method parameters.
+pub const INVALID_PARAMS: i32 = -32602;
+/// Internal JSON-RPC error.
+pub const INTERNAL_ERROR: i32 = -32603;
+
+/// ref. <https://www.jsonrpc.org/specification#error_object>
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Error {
+    pub code: i32,
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl Error {
+    #[must_use]
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    #[must_use]
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    #[must_use]
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(INTERNAL_ERROR, message)
+    }
+
+    /// Attaches the additional information about the error.
+    #[must_use]
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::internal(e.to_string())
+    }
+}
+
+/// ref. <https://www.jsonrpc.org/specification#request_object>
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+    /// "None" for notifications, which get no response.
+    #[serde(default, deserialize_with = "deserialize_id")]
+    id: Option<Value>,
+}
+
+/// Distinguishes the explicit "null" id from the missing id (notification).
+fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+/// ref. <https://www.jsonrpc.org/specification#response_object>
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Response {
+    pub jsonrpc: String,
+    pub id: Value,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Error>,
+}
+
+impl Response {
+    fn new(id: Value, result: Result<Value, Error>) -> Self {
+        let (result, error) = match result {
+            Ok(v) => (Some(v), None),
+            Err(e) => (None, Some(e)),
+        };
+        Self {
+            jsonrpc: VERSION.to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+type Method = Arc<dyn Fn(Option<Value>) -> BoxFuture<'static, Result<Value, Error>> + Send + Sync>;
+
+/// Dispatches the JSON-RPC method calls (single or batched) to the registered handlers.
+#[derive(Clone, Default)]
+pub struct Router {
+    methods: HashMap<String, Method>,
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut methods: Vec<&String> = self.methods.keys().collect();
+        methods.sort();
+        f.debug_struct("Router").field("methods", &methods).finish()
+    }
+}
+
+impl Router {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler for the method, with the params deserialized to "P".
+    /// The params can be given as is, or as the single-element array
+    /// (e.g., "[{...}]" as sent to the "avalanchego" APIs).
+    /// Missing params are deserialized from "null" (e.g., for "()" or "Option").
+    #[must_use]
+    pub fn method<P, R, F, Fut>(mut self, name: &str, handler: F) -> Self
+    where
+        P: DeserializeOwned + Send + 'static,
+        R: Serialize + 'static,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, Error>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let method: Method = Arc::new(move |params: Option<Value>| {
+            let handler = Arc::clone(&handler);
+            async move {
+                let params: P = parse_params(params.unwrap_or(Value::Null))?;
+                let result = handler(params).await?;
+                serde_json::to_value(result)
+                    .map_err(|e| Error::internal(format!("failed to serialize result {e}")))
+            }
+            .boxed()
+        });
+        self.methods.insert(name.to_string(), method);
+        self
+    }
+
+    /// Returns the names of the registered methods.
+    #[must_use]
+    pub fn methods(&self) -> Vec<String> {
+        let mut methods: Vec<String> = self.methods.keys().cloned().collect();
+        methods.sort();
+        methods
+    }
+
+    /// Handles the JSON-RPC request body, and returns the response body.
+    /// Returns "None" if there is nothing to respond (e.g., only notifications).
+    pub async fn handle_request(&self, body: &[u8]) -> Option<Vec<u8>> {
+        let resp = match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Array(reqs)) if reqs.is_empty() => Some(serde_json::json!(Response::new(
+                Value::Null,
+                Err(Error::new(INVALID_REQUEST, "empty batch")),
+            ))),
+            Ok(Value::Array(reqs)) => {
+                let resps: Vec<Response> = join_all(reqs.into_iter().map(|req| self.call(req)))
+                    .await
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                if resps.is_empty() {
+                    None
+                } else {
+                    Some(serde_json::json!(resps))
+                }
+            }
+            Ok(req) => self.call(req).await.map(|resp| serde_json::json!(resp)),
+            Err(e) => Some(serde_json::json!(Response::new(
+                Value::Null,
+                Err(Error::new(PARSE_ERROR, format!("parse error {e}"))),
+            ))),
+        };
+        resp.map(|v| serde_json::to_vec(&v).unwrap_or_default())
+    }
+
+    async fn call(&self, req: Value) -> Option<Response> {
+        let req: Request = match serde_json::from_value(req) {
+            Ok(req) => req,
+            Err(e) => {
+                return Some(Response::new(
+                    Value::Null,
+                    Err(Error::new(INVALID_REQUEST, format!("invalid request {e}"))),
+                ))
+            }
+        };
+        let id = req.id.clone();
+        if req.jsonrpc != VERSION {
+            return Some(Response::new(
+                id.unwrap_or(Value::Null),
+                Err(Error::new(
+                    INVALID_REQUEST,
+                    format!("unsupported jsonrpc version '{}'", req.jsonrpc),
+                )),
+            ));
+        }
+
+        log::debug!("jsonrpc method '{}' called", req.method);
+        let result = match self.methods.get(&req.method) {
+            Some(method) => method(req.params).await,
+            None => Err(Error::new(
+                METHOD_NOT_FOUND,
+                format!("method '{}' not found", req.method),
+            )),
+        };
+        if let Err(e) = &result {
+            log::debug!("jsonrpc method '{}' failed: {e}", req.method);
+        }
+
+        // no response to notifications
+        id.map(|id| Response::new(id, result))
+    }
+}
+
+fn parse_params<P: DeserializeOwned>(params: Value) -> Result<P, Error> {
+    match serde_json::from_value(params.clone()) {
+        Ok(p) => Ok(p),
+        Err(e) => match params {
+            Value::Array(mut arr) if arr.len() == 1 => serde_json::from_value(arr.remove(0))
+                .map_err(|e| Error::invalid_params(format!("invalid params {e}"))),
+            _ => Err(Error::invalid_params(format!("invalid params {e}"))),
+        },
+    }
+}
+
+#[tonic::async_trait]
+impl Handle for Router {
+    async fn request(
+        &self,
+        req: &Bytes,
+        _headers: &[Element],
+    ) -> io::Result<(Bytes, Vec<Element>)> {
+        let body = self.handle_request(req).await.unwrap_or_default();
+        Ok((
+            Bytes::from(body),
+            vec![Element {
+                key: "Content-Type".to_string(),
+                values: vec!["application/json".to_string()],
+            }],
+        ))
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::http::jsonrpc::test_router` --exact --show-output
+#[tokio::test]
+async fn test_router() {
+    #[derive(Deserialize)]
+    struct AddArgs {
+        a: u64,
+        b: u64,
+    }
+
+    let router = Router::new()
+        .method("math.add", |args: AddArgs| async move {
+            args.a
+                .checked_add(args.b)
+                .ok_or_else(|| Error::invalid_params("overflow"))
+        })
+        .method("math.ping", |(): ()| async move { Ok("pong") });
+    assert_eq!(router.methods(), vec!["math.add", "math.ping"]);
+
+    let call = |body: &'static str| {
+        let router = router.clone();
+        async move {
+            router
+                .handle_request(body.as_bytes())
+                .await
+                .map(|b| serde_json::from_slice::<Value>(&b).unwrap())
+        }
+    };
+
+    let resp = call(r#"{"jsonrpc":"2.0","id":1,"method":"math.add","params":{"a":1,"b":2}}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp, serde_json::json!({"jsonrpc":"2.0","id":1,"result":3}));
+
+    // single-element array params
+    let resp = call(r#"{"jsonrpc":"2.0","id":"x","method":"math.add","params":[{"a":3,"b":4}]}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp["result"], 7);
+    assert_eq!(resp["id"], "x");
+
+    let resp = call(r#"{"jsonrpc":"2.0","id":2,"method":"math.ping"}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp["result"], "pong");
+
+    let resp = call(r#"{"jsonrpc":"2.0","id":3,"method":"math.add","params":{"a":1}}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp["error"]["code"], INVALID_PARAMS);
+
+    let resp = call(r#"{"jsonrpc":"2.0","id":4,"method":"math.sub"}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp["error"]["code"], METHOD_NOT_FOUND);
+
+    let resp = call(r#"{"jsonrpc":"1.0","id":5,"method":"math.ping"}"#)
+        .await
+        .unwrap();
+    assert_eq!(resp["error"]["code"], INVALID_REQUEST);
+
+    let resp = call(r#"{"jsonrpc":"2.0","#).await.unwrap();
+    assert_eq!(resp["error"]["code"], PARSE_ERROR);
+    assert_eq!(resp["id"], Value::Null);
+
+    // notifications get no response
+    assert!(call(r#"{"jsonrpc":"2.0","method":"math.ping"}"#)
+        .await
+        .is_none());
+
+    let resp = call(
+        r#"[
+            {"jsonrpc":"2.0","id":1,"method":"math.add","params":{"a":1,"b":1}},
+            {"jsonrpc":"2.0","method":"math.ping"},
+            {"foo":"bar"},
+            {"jsonrpc":"2.0","id":2,"method":"math.ping"}
+        ]"#,
+    )
+    .await
+    .unwrap();
+    let resps = resp.as_array().unwrap();
+    assert_eq!(resps.len(), 3);
+    assert_eq!(resps[0]["result"], 2);
+    assert_eq!(resps[1]["error"]["code"], INVALID_REQUEST);
+    assert_eq!(resps[2]["result"], "pong");
+
+    let resp = call("[]").await.unwrap();
+    assert_eq!(resp["error"]["code"], INVALID_REQUEST);
+
+    let (body, headers) = router
+        .request(
+            &Bytes::from_static(br#"{"jsonrpc":"2.0","id":1,"method":"math.ping"}"#),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(!body.is_empty());
+    assert_eq!(headers[0].values, vec!["application/json"]);
+}
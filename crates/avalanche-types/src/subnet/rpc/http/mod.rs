@@ -1,5 +1,6 @@
 pub mod client;
 pub mod handle;
+pub mod jsonrpc;
 pub mod server;
 
 /// ref: <https://pkg.go.dev/net/http#Handler>
@@ -1,7 +1,93 @@
 //! Support for Prometheus metrics.
+use std::sync::OnceLock;
+
 use crate::proto::pb::io::prometheus::client::{
     Bucket, Counter, Gauge, Histogram, LabelPair, Metric, MetricFamily, Quantile, Summary,
 };
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+/// Namespace of the VM server RPC metrics (e.g., "`rpcchainvm_server_calls_total`").
+pub const VM_RPC_NAMESPACE: &str = "rpcchainvm_server";
+
+/// Latency buckets in seconds of the VM server RPCs.
+pub const VM_RPC_LATENCY_BUCKETS: [f64; 12] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0,
+];
+
+static VM_RPC_METRICS: OnceLock<Option<VmRpcMetrics>> = OnceLock::new();
+
+/// Per-RPC metrics of the VM server, labeled by the RPC method (e.g., "`build_block`").
+#[derive(Debug, Clone)]
+pub struct VmRpcMetrics {
+    /// Total number of RPC calls.
+    pub calls: IntCounterVec,
+    /// Total number of RPC calls that returned an error.
+    pub errors: IntCounterVec,
+    /// RPC latency in seconds.
+    pub latency: HistogramVec,
+}
+
+impl VmRpcMetrics {
+    /// Creates the metrics and registers them to the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry rejects the metrics
+    /// (e.g., the same metrics have already been registered).
+    pub fn register(registry: &Registry) -> prometheus::Result<Self> {
+        let labels = &["method"];
+        let metrics = Self {
+            calls: IntCounterVec::new(
+                Opts::new("calls_total", "Total number of VM server RPC calls")
+                    .namespace(VM_RPC_NAMESPACE),
+                labels,
+            )?,
+            errors: IntCounterVec::new(
+                Opts::new("errors_total", "Total number of failed VM server RPC calls")
+                    .namespace(VM_RPC_NAMESPACE),
+                labels,
+            )?,
+            latency: HistogramVec::new(
+                HistogramOpts::new("latency_seconds", "VM server RPC latency in seconds")
+                    .namespace(VM_RPC_NAMESPACE)
+                    .buckets(VM_RPC_LATENCY_BUCKETS.to_vec()),
+                labels,
+            )?,
+        };
+        registry.register(Box::new(metrics.calls.clone()))?;
+        registry.register(Box::new(metrics.errors.clone()))?;
+        registry.register(Box::new(metrics.latency.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Returns the metrics registered to the default registry,
+    /// which the VM server returns on "gather". Registers them on the first call,
+    /// and returns "None" if the registration fails.
+    #[must_use]
+    pub fn global() -> Option<&'static Self> {
+        VM_RPC_METRICS
+            .get_or_init(|| match Self::register(prometheus::default_registry()) {
+                Ok(metrics) => Some(metrics),
+                Err(e) => {
+                    log::warn!("failed to register VM server RPC metrics: {e}");
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    /// Records a single RPC call.
+    pub fn observe(&self, method: &str, ok: bool, elapsed: std::time::Duration) {
+        let labels = [method];
+        self.calls.with_label_values(&labels).inc();
+        if !ok {
+            self.errors.with_label_values(&labels).inc();
+        }
+        self.latency
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+}
 
 /// A list of `LabelPair`
 #[derive(Clone, Debug)]
@@ -188,3 +274,39 @@ fn test_gather_process() {
         }
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet_metrics -- `subnet::rpc::metrics::test_vm_rpc_metrics` --exact --show-output
+#[test]
+#[cfg(feature = "subnet_metrics")]
+fn test_vm_rpc_metrics() {
+    let registry = Registry::new();
+    let m = VmRpcMetrics::register(&registry).unwrap();
+    assert!(VmRpcMetrics::register(&registry).is_err());
+
+    m.observe("build_block", true, std::time::Duration::from_millis(3));
+    m.observe("build_block", false, std::time::Duration::from_millis(7));
+    assert_eq!(m.calls.with_label_values(&["build_block"]).get(), 2);
+    assert_eq!(m.errors.with_label_values(&["build_block"]).get(), 1);
+    assert_eq!(
+        m.latency
+            .with_label_values(&["build_block"])
+            .get_sample_count(),
+        2
+    );
+
+    let names: Vec<String> = MetricsFamilies::from(&registry.gather())
+        .mfs
+        .into_iter()
+        .filter_map(|mf| mf.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "rpcchainvm_server_calls_total",
+            "rpcchainvm_server_errors_total",
+            "rpcchainvm_server_latency_seconds",
+        ]
+    );
+
+    assert!(VmRpcMetrics::global().is_some());
+}
@@ -4,9 +4,11 @@ pub mod database;
 pub mod errors;
 pub mod health;
 pub mod http;
+pub mod plugin;
 pub mod runtime;
 pub mod snow;
 pub mod snowman;
+pub mod testing;
 pub mod utils;
 pub mod vm;
 
@@ -1,61 +1,192 @@
+//! go-plugin compatible bootstrap of the VM plugin.
+//!
+//! Performs the same handshake as "avalanchego/vms/rpcchainvm#Serve" with go-plugin:
+//! validates the magic cookie, registers the gRPC health service, prints the
+//! handshake line to stdout, and serves the VM until shutdown.
+//! ref. <https://github.com/hashicorp/go-plugin/blob/main/docs/internals.md>
 use std::{
-    io::{Error, ErrorKind, Result},
+    env,
+    io::{Error, ErrorKind, Result, Write},
     net::SocketAddr,
 };
 
 use crate::{
     proto::{
-        grpcutil,
         pb::{
             self,
             vm::vm_server::{Vm, VmServer},
         },
         PROTOCOL_VERSION,
     },
-    subnet::rpc::utils,
+    subnet::rpc::utils::{self, grpc::TlsConfig},
 };
-use jsonrpc_core::futures::FutureExt;
 use tokio::sync::broadcast::Receiver;
-use tonic::transport::server::NamedService;
+use tonic::server::NamedService;
 use tonic_health::server::health_reporter;
 
-/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.9.5/version/constants.go#L15-L17>
-struct HandshakeConfig {
-    protocol_version: &'static str,
+/// Environment variable that go-plugin sets to mark the binary launched as a plugin.
+/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.1/vms/rpcchainvm/grpcutils/util.go>
+pub const MAGIC_COOKIE_KEY: &str = "VM_PLUGIN";
+/// Expected value of "`MAGIC_COOKIE_KEY`".
+pub const MAGIC_COOKIE_VALUE: &str = "dynamic";
+
+/// Environment variable of the app protocol versions the go-plugin client supports,
+/// comma-separated.
+pub const PROTOCOL_VERSIONS_KEY: &str = "PLUGIN_PROTOCOL_VERSIONS";
+
+/// Version of the go-plugin handshake protocol itself.
+pub const CORE_PROTOCOL_VERSION: u32 = 1;
+
+/// ref. <https://github.com/hashicorp/go-plugin/blob/main/client.go>
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct HandshakeConfig {
+    /// App protocol version, which must match avalanchego's rpcchainvm version.
+    pub protocol_version: u32,
+    pub magic_cookie_key: &'static str,
+    pub magic_cookie_value: &'static str,
+}
+
+impl Default for HandshakeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HandshakeConfig {
-    pub fn new() -> Self {
+    #[must_use]
+    pub const fn new() -> Self {
         Self {
             protocol_version: PROTOCOL_VERSION,
+            magic_cookie_key: MAGIC_COOKIE_KEY,
+            magic_cookie_value: MAGIC_COOKIE_VALUE,
         }
     }
+
+    /// Validates the magic cookie and the protocol versions in the environment.
+    ///
+    /// # Errors
+    /// Returns error if the binary is not launched as a plugin,
+    /// or the client does not support the protocol version.
+    pub fn validate_env(&self) -> Result<()> {
+        self.validate(|key| env::var(key).ok())
+    }
+
+    /// Validates the handshake config against the variables from "lookup".
+    fn validate(&self, lookup: impl Fn(&str) -> Option<String>) -> Result<()> {
+        if lookup(self.magic_cookie_key).as_deref() != Some(self.magic_cookie_value) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "this binary is a plugin, which is not meant to be executed directly; \
+                 launch it via avalanchego",
+            ));
+        }
+
+        if let Some(versions) = lookup(PROTOCOL_VERSIONS_KEY) {
+            let supported = versions
+                .split(',')
+                .filter_map(|v| v.trim().parse::<u32>().ok())
+                .any(|v| v == self.protocol_version);
+            if !supported {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "protocol version {} is not supported by the client (supported {versions})",
+                        self.protocol_version
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the handshake line that go-plugin reads from stdout.
+    /// The trailing empty field is the server certificate, only set with go-plugin's auto-TLS.
+    #[must_use]
+    pub fn message(&self, addr: &SocketAddr) -> String {
+        format!(
+            "{CORE_PROTOCOL_VERSION}|{}|tcp|{addr}|grpc|",
+            self.protocol_version
+        )
+    }
 }
 
+/// Health service name that go-plugin checks.
 struct Plugin;
 
 impl NamedService for Plugin {
     const NAME: &'static str = "plugin";
 }
 
-/// serve starts a gRPC server which serves the Vm service and generates the handshake message for plugin support.
-/// Reflection is enabled by default.
-pub async fn serve<V>(vm: V, stop_ch: Receiver<()>) -> Result<()>
+/// Options of the plugin bootstrap.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Version printed on "--version" (e.g., "`my-vm/v0.1.0`").
+    /// Only the protocol version is printed if not set.
+    pub version: Option<String>,
+    /// Defaults to the next available localhost port.
+    pub addr: Option<SocketAddr>,
+    /// Defaults to the config from the environment (see "`TlsConfig::from_env`").
+    pub tls: Option<TlsConfig>,
+    /// Stops the server on the broadcast, in addition to ctrl-c
+    /// (e.g., the channel of "`vm::server::Server`" to stop on the "Shutdown" RPC).
+    pub stop_ch: Option<Receiver<()>>,
+}
+
+/// Returns "true" if the arguments request the version (e.g., "--version").
+fn is_version_flag(args: impl IntoIterator<Item = String>) -> bool {
+    args.into_iter()
+        .skip(1)
+        .any(|arg| matches!(arg.as_str(), "--version" | "-version" | "-v" | "version"))
+}
+
+/// Returns the version line printed on "--version".
+fn version_string(version: Option<&str>) -> String {
+    let protocol = format!("rpcchainvm={PROTOCOL_VERSION}");
+    match version {
+        Some(v) => format!("{v} [{protocol}]"),
+        None => protocol,
+    }
+}
+
+/// Serves the VM as a go-plugin with the default options,
+/// blocking until ctrl-c. Mirrors "avalanchego/vms/rpcchainvm#Serve".
+///
+/// # Errors
+/// Returns error if the binary is not launched as a plugin, or the gRPC server fails.
+pub async fn serve<V>(vm: V) -> Result<()>
 where
     V: Vm,
 {
-    // TODO: Add support for abstract unix sockets once supported by tonic.
-    // ref. https://github.com/hyperium/tonic/issues/966
-    // avalanchego currently only supports plugins listening on IP address.
-    let addr = utils::new_socket_addr();
-
-    serve_with_address(vm, addr, stop_ch).await
+    serve_with_options(vm, Options::default()).await
 }
 
-pub async fn serve_with_address<V>(vm: V, addr: SocketAddr, mut stop_ch: Receiver<()>) -> Result<()>
+/// Serves the VM as a go-plugin, blocking until ctrl-c or the stop signal.
+/// Prints the version and returns without serving on "--version".
+///
+/// # Errors
+/// Returns error if the binary is not launched as a plugin, the TLS config
+/// fails to load, or the gRPC server fails.
+pub async fn serve_with_options<V>(vm: V, opts: Options) -> Result<()>
 where
     V: Vm,
 {
+    if is_version_flag(env::args()) {
+        println!("{}", version_string(opts.version.as_deref()));
+        return Ok(());
+    }
+
+    let handshake_config = HandshakeConfig::new();
+    handshake_config.validate_env()?;
+
+    // TODO: Add support for abstract unix sockets once supported by tonic.
+    // ref. https://github.com/hyperium/tonic/issues/966
+    // avalanchego currently only supports plugins listening on IP address.
+    let addr = opts.addr.unwrap_or_else(utils::new_socket_addr);
+    let tls = match opts.tls {
+        Some(tls) => Some(tls),
+        None => TlsConfig::from_env()?,
+    };
+
     // "go-plugin requires the gRPC Health Checking Service to be registered on your server"
     // ref. https://github.com/hashicorp/go-plugin/blob/master/docs/guide-plugin-write-non-go.md
     // ref. https://github.com/hyperium/tonic/blob/v0.7.1/examples/src/health/server.rs
@@ -78,25 +209,110 @@ where
             )
         })?;
 
-    log::info!("plugin listening on address {:?}", addr);
+    // binds before the handshake, so the client never dials a closed port
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+
+    // loads the TLS config before the handshake, to fail without advertising the address
+    let mut server = utils::grpc::server_with_tls(tls.as_ref())?;
+
+    log::info!("plugin listening on address {addr:?}");
 
     // handshake message must be printed to stdout
     // ref. https://github.com/hashicorp/go-plugin/blob/master/docs/guide-plugin-write-non-go.md#4-output-handshake-information
-    // TODO: remove this once go-plugin is deprecated in avalanchego
-    let handshake_config = HandshakeConfig::new();
-    let handshake_msg = format!("1|{}|tcp|{}|grpc|", handshake_config.protocol_version, addr);
-    println!("{}", handshake_msg);
+    // scoped, so the (non-Send) lock is released before serving
+    {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", handshake_config.message(&addr))?;
+        stdout.flush()?;
+    }
+
+    let mut stop_ch = opts.stop_ch;
+    let shutdown = async move {
+        let stopped = async {
+            match stop_ch.as_mut() {
+                Some(ch) => {
+                    let _ = ch.recv().await;
+                }
+                None => std::future::pending::<()>().await,
+            }
+        };
+        tokio::select! {
+            () = stopped => log::info!("received stop signal"),
+            r = tokio::signal::ctrl_c() => {
+                if let Err(e) = r {
+                    log::warn!("failed to listen for ctrl-c: {e}");
+                }
+                log::info!("received ctrl-c");
+            }
+        }
+    };
 
-    grpcutil::default_server()
+    server
         .add_service(health_svc)
         .add_service(reflection_service)
-        .add_service(VmServer::new(vm))
-        .serve_with_shutdown(addr, stop_ch.recv().map(|_| ()))
-        .max_decoding_message_size(usize::MAX)
-        .max_encoding_message_size(usize::MAX)
+        .add_service(
+            VmServer::new(vm)
+                .max_decoding_message_size(usize::MAX)
+                .max_encoding_message_size(usize::MAX),
+        )
+        .serve_with_incoming_shutdown(incoming, shutdown)
         .await
         .map_err(|e| Error::new(ErrorKind::Other, format!("grpc server failed: {e:?}")))?;
     log::info!("grpc server shutdown complete: {addr}");
 
     Ok(())
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::plugin::test_handshake` --exact --show-output
+#[test]
+fn test_handshake() {
+    use std::collections::HashMap;
+
+    let config = HandshakeConfig::new();
+    let addr: SocketAddr = "127.0.0.1:1234".parse().unwrap();
+    assert_eq!(
+        config.message(&addr),
+        format!("1|{PROTOCOL_VERSION}|tcp|127.0.0.1:1234|grpc|")
+    );
+
+    let lookup = |vars: HashMap<&'static str, String>| move |key: &str| vars.get(key).cloned();
+    assert!(config.validate(lookup(HashMap::new())).is_err());
+    assert!(config
+        .validate(lookup(HashMap::from([(
+            MAGIC_COOKIE_KEY,
+            String::from("static")
+        )])))
+        .is_err());
+    assert!(config
+        .validate(lookup(HashMap::from([(
+            MAGIC_COOKIE_KEY,
+            String::from(MAGIC_COOKIE_VALUE)
+        )])))
+        .is_ok());
+    assert!(config
+        .validate(lookup(HashMap::from([
+            (MAGIC_COOKIE_KEY, String::from(MAGIC_COOKIE_VALUE)),
+            (PROTOCOL_VERSIONS_KEY, format!("1, {PROTOCOL_VERSION}")),
+        ])))
+        .is_ok());
+    assert!(config
+        .validate(lookup(HashMap::from([
+            (MAGIC_COOKIE_KEY, String::from(MAGIC_COOKIE_VALUE)),
+            (PROTOCOL_VERSIONS_KEY, String::from("1")),
+        ])))
+        .is_err());
+
+    let args = |v: &[&str]| v.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(is_version_flag(args(&["my-vm", "--version"])));
+    assert!(!is_version_flag(args(&["my-vm"])));
+    assert!(!is_version_flag(args(&["--version"])));
+    assert_eq!(
+        version_string(Some("my-vm/v0.1.0")),
+        format!("my-vm/v0.1.0 [rpcchainvm={PROTOCOL_VERSION}]")
+    );
+    assert_eq!(
+        version_string(None),
+        format!("rpcchainvm={PROTOCOL_VERSION}")
+    );
+}
@@ -0,0 +1,70 @@
+use std::io::{Error, ErrorKind, Result};
+
+use bytes::Bytes;
+use tonic::transport::Channel;
+
+use crate::{
+    ids,
+    proto::pb::aliasreader::{alias_reader_client, Alias, Id},
+};
+
+#[derive(Clone, Debug)]
+pub struct AliasReaderClient {
+    /// The inner gRPC client for alias lookups
+    inner: alias_reader_client::AliasReaderClient<Channel>,
+}
+
+impl AliasReaderClient {
+    /// Creates a new alias reader client with the given channel
+    #[must_use]
+    pub fn new(client_conn: Channel) -> Self {
+        Self {
+            inner: alias_reader_client::AliasReaderClient::new(client_conn),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl super::AliasReader for AliasReaderClient {
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn lookup(&self, alias: &str) -> Result<ids::Id> {
+        let resp = self
+            .inner
+            .clone()
+            .lookup(Alias {
+                alias: alias.to_string(),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("lookup failed: {e}")))?
+            .into_inner();
+        Ok(ids::Id::from_slice(&resp.id))
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn primary_alias(&self, id: ids::Id) -> Result<String> {
+        let resp = self
+            .inner
+            .clone()
+            .primary_alias(Id {
+                id: Bytes::from(id),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("primary_alias failed: {e}")))?
+            .into_inner();
+        Ok(resp.alias)
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn aliases(&self, id: ids::Id) -> Result<Vec<String>> {
+        let resp = self
+            .inner
+            .clone()
+            .aliases(Id {
+                id: Bytes::from(id),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("aliases failed: {e}")))?
+            .into_inner();
+        Ok(resp.aliases)
+    }
+}
@@ -0,0 +1,121 @@
+//! In-memory alias reader for testing the VM logic without avalanchego.
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+    sync::{Arc, RwLock},
+};
+
+use crate::ids;
+
+#[derive(Debug, Default)]
+struct Inner {
+    ids: BTreeMap<String, ids::Id>,
+    aliases: BTreeMap<ids::Id, Vec<String>>,
+}
+
+/// Implements "`alias_reader::AliasReader`" with the aliases set in memory.
+/// Clones share the same aliases.
+#[derive(Debug, Clone, Default)]
+pub struct AliasReader {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl AliasReader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the alias of the ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias already refers to an ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn alias(&self, id: ids::Id, alias: &str) -> io::Result<()> {
+        let mut inner = self.inner.write().unwrap();
+        if inner.ids.contains_key(alias) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("alias '{alias}' already exists"),
+            ));
+        }
+        inner.ids.insert(alias.to_string(), id);
+        inner.aliases.entry(id).or_default().push(alias.to_string());
+        Ok(())
+    }
+}
+
+fn poisoned(e: impl std::fmt::Display) -> Error {
+    Error::new(ErrorKind::Other, format!("aliases lock poisoned: {e}"))
+}
+
+#[tonic::async_trait]
+impl super::AliasReader for AliasReader {
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn lookup(&self, alias: &str) -> io::Result<ids::Id> {
+        self.inner
+            .read()
+            .map_err(poisoned)?
+            .ids
+            .get(alias)
+            .copied()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    format!("there is no ID with alias '{alias}'"),
+                )
+            })
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn primary_alias(&self, id: ids::Id) -> io::Result<String> {
+        self.inner
+            .read()
+            .map_err(poisoned)?
+            .aliases
+            .get(&id)
+            .and_then(|aliases| aliases.first().cloned())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    format!("there is no alias for ID {id}"),
+                )
+            })
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::alias_reader::AliasReader`] trait.
+    async fn aliases(&self, id: ids::Id) -> io::Result<Vec<String>> {
+        Ok(self
+            .inner
+            .read()
+            .map_err(poisoned)?
+            .aliases
+            .get(&id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::snow::alias_reader::memory::test_alias_reader` --exact --show-output
+#[tokio::test]
+async fn test_alias_reader() {
+    use super::AliasReader as _;
+
+    let x_chain_id = ids::Id::sha256("X");
+    let reader = AliasReader::new();
+    assert!(reader.lookup("X").await.is_err());
+    assert!(reader.primary_alias(x_chain_id).await.is_err());
+    assert!(reader.aliases(x_chain_id).await.unwrap().is_empty());
+
+    reader.alias(x_chain_id, "X").unwrap();
+    reader.alias(x_chain_id, "avm").unwrap();
+    assert!(reader.alias(ids::Id::sha256("C"), "X").is_err());
+
+    assert_eq!(reader.lookup("avm").await.unwrap(), x_chain_id);
+    assert_eq!(reader.primary_alias(x_chain_id).await.unwrap(), "X");
+    assert_eq!(reader.aliases(x_chain_id).await.unwrap(), vec!["X", "avm"]);
+}
@@ -0,0 +1,20 @@
+//! Lookup of the blockchain IDs by their aliases (e.g., "X" for the X-chain).
+pub mod client;
+pub mod memory;
+
+use std::{fmt::Debug, io};
+
+use crate::ids;
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/ids#AliaserReader>
+#[tonic::async_trait]
+pub trait AliasReader: Debug {
+    /// Returns the ID the alias refers to.
+    async fn lookup(&self, alias: &str) -> io::Result<ids::Id>;
+
+    /// Returns the first alias of the ID.
+    async fn primary_alias(&self, id: ids::Id) -> io::Result<String>;
+
+    /// Returns all aliases of the ID, in the order they were added.
+    async fn aliases(&self, id: ids::Id) -> io::Result<Vec<String>>;
+}
@@ -27,6 +27,8 @@ impl AppSenderClient {
     }
 }
 
+/// Cross-chain app messages use the trait defaults, since the rpcchainvm
+/// protocol has no cross-chain RPCs on the app sender service.
 #[tonic::async_trait]
 impl super::AppSender for AppSenderClient {
     /// Send an application-level request.
@@ -44,7 +46,7 @@ impl super::AppSender for AppSenderClient {
     ) -> Result<()> {
         let mut id_bytes: Vec<Bytes> = Vec::with_capacity(node_ids.len());
         for node_id in &node_ids {
-            id_bytes.push(Bytes::from(node_id.to_vec()));
+            id_bytes.push(Bytes::from(*node_id));
         }
 
         self.inner
@@ -73,7 +75,7 @@ impl super::AppSender for AppSenderClient {
         self.inner
             .clone()
             .send_app_response(SendAppResponseMsg {
-                node_id: Bytes::from(node_id.to_vec()),
+                node_id: Bytes::from(node_id),
                 request_id,
                 response: Bytes::from(response),
             })
@@ -1,9 +1,9 @@
 pub mod client;
 pub mod server;
 
-use std::io::Result;
+use std::io::{Error, ErrorKind, Result};
 
-use crate::ids;
+use crate::{ids, proto::PROTOCOL_VERSION};
 
 /// AppSender sends application (Vm) level messages.
 ///
@@ -23,6 +23,45 @@ pub trait AppSender: Send + Sync + CloneBox {
         response: Vec<u8>,
     ) -> Result<()>;
     async fn send_app_gossip(&self, msg: Vec<u8>) -> Result<()>;
+
+    /// Sends an application-level request to the VM of another chain on the same node.
+    /// The VM eventually receives either "`cross_chain_app_response`" or
+    /// "`cross_chain_app_request_failed`" from `[chain_id]` with ID `[request_id]`.
+    ///
+    /// Returns "`ErrorKind::Unsupported`" by default, since the rpcchainvm protocol
+    /// no longer carries cross-chain messages between avalanchego and the plugin.
+    async fn send_cross_chain_app_request(
+        &self,
+        chain_id: ids::Id,
+        request_id: u32,
+        request: Vec<u8>,
+    ) -> Result<()> {
+        let _ = (request_id, request);
+        Err(cross_chain_unsupported(&chain_id))
+    }
+
+    /// Sends an application-level response to the cross-chain request
+    /// received from `[chain_id]` with ID `[request_id]`.
+    ///
+    /// Returns "`ErrorKind::Unsupported`" by default.
+    async fn send_cross_chain_app_response(
+        &self,
+        chain_id: ids::Id,
+        request_id: u32,
+        response: Vec<u8>,
+    ) -> Result<()> {
+        let _ = (request_id, response);
+        Err(cross_chain_unsupported(&chain_id))
+    }
+}
+
+fn cross_chain_unsupported(chain_id: &ids::Id) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        format!(
+            "cross-chain app messages to chain {chain_id} are not supported by rpcchainvm protocol version {PROTOCOL_VERSION}"
+        ),
+    )
 }
 
 pub trait CloneBox {
@@ -46,6 +46,12 @@ pub trait CommonVm: AppHandler + Connector + Checkable {
     async fn set_state(&self, state: State) -> Result<()>;
     async fn shutdown(&self) -> Result<()>;
     async fn version(&self) -> Result<String>;
+    /// Creates the HTTP handlers for the VM-level (chain-independent) APIs.
+    ///
+    /// "avalanchego" removed "CreateStaticHandlers" from the rpcchainvm protocol
+    /// before [`crate::proto::PROTOCOL_VERSION`], so the VM server serves these
+    /// along with [`CommonVm::create_handlers`] under each chain, where the chain
+    /// handler wins on the same extension.
     async fn create_static_handlers(
         &mut self,
     ) -> Result<HashMap<String, HttpHandler<Self::StaticHandler>>>;
@@ -0,0 +1,59 @@
+use std::io::{Error, ErrorKind, Result};
+
+use tonic::transport::Channel;
+
+use crate::{
+    proto::pb::keystore::{keystore_client, GetDatabaseRequest},
+    subnet::rpc::{
+        database::{rpcdb::client::DatabaseClient, BoxedDatabase},
+        utils::grpc,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct KeystoreClient {
+    /// The inner gRPC client for keystore operations
+    inner: keystore_client::KeystoreClient<Channel>,
+}
+
+impl KeystoreClient {
+    /// Creates a new keystore client with the given channel
+    #[must_use]
+    pub fn new(client_conn: Channel) -> Self {
+        Self {
+            inner: keystore_client::KeystoreClient::new(client_conn),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl super::Keystore for KeystoreClient {
+    /// Implements the [`crate::subnet::rpc::snow::keystore::Keystore`] trait.
+    /// Connects to the database server of the user that avalanchego hosts.
+    async fn get_database(&self, username: &str, password: &str) -> Result<BoxedDatabase> {
+        let resp = self
+            .inner
+            .clone()
+            .get_database(GetDatabaseRequest {
+                username: username.to_string(),
+                password: password.to_string(),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("get_database failed: {e}")))?
+            .into_inner();
+
+        let client_conn = grpc::default_client(&resp.server_addr)?
+            .connect()
+            .await
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!(
+                        "failed to create db client conn from: {}: {e}",
+                        resp.server_addr
+                    ),
+                )
+            })?;
+        Ok(DatabaseClient::new_boxed(client_conn))
+    }
+}
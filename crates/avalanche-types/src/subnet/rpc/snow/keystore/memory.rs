@@ -0,0 +1,119 @@
+//! In-memory keystore for testing the VM logic without avalanchego.
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, Error, ErrorKind},
+    sync::{Arc, RwLock},
+};
+
+use crate::subnet::rpc::database::{memdb, BoxedDatabase};
+
+#[derive(Clone)]
+struct User {
+    password: String,
+    db: BoxedDatabase,
+}
+
+/// Implements "`keystore::Keystore`" with an in-memory database per user.
+/// Clones share the same users, so the users can be created
+/// after the keystore is passed to the VM.
+#[derive(Clone, Default)]
+pub struct Keystore {
+    users: Arc<RwLock<BTreeMap<String, User>>>,
+}
+
+impl fmt::Debug for Keystore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let users = self.users.read().map_or(0, |users| users.len());
+        f.debug_struct("Keystore").field("users", &users).finish()
+    }
+}
+
+impl Keystore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the user with an empty database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn create_user(&self, username: &str, password: &str) -> io::Result<()> {
+        let mut users = self.users.write().unwrap();
+        if users.contains_key(username) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("user '{username}' already exists"),
+            ));
+        }
+        users.insert(
+            username.to_string(),
+            User {
+                password: password.to_string(),
+                db: memdb::Database::new_boxed(),
+            },
+        );
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl super::Keystore for Keystore {
+    /// Implements the [`crate::subnet::rpc::snow::keystore::Keystore`] trait.
+    async fn get_database(&self, username: &str, password: &str) -> io::Result<BoxedDatabase> {
+        let users = self
+            .users
+            .read()
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to read users: {e}")))?;
+        match users.get(username) {
+            Some(user) if user.password == password => Ok(user.db.clone()),
+            Some(_) => Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!("incorrect password for user '{username}'"),
+            )),
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("user '{username}' not found"),
+            )),
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::snow::keystore::memory::test_keystore` --exact --show-output
+#[tokio::test]
+async fn test_keystore() {
+    use super::Keystore as _;
+
+    let keystore = Keystore::new();
+    assert_eq!(
+        keystore
+            .get_database("alice", "pw")
+            .await
+            .err()
+            .map(|e| e.kind()),
+        Some(ErrorKind::NotFound)
+    );
+
+    keystore.create_user("alice", "pw").unwrap();
+    assert!(keystore.create_user("alice", "pw").is_err());
+    assert_eq!(
+        keystore
+            .get_database("alice", "wrong")
+            .await
+            .err()
+            .map(|e| e.kind()),
+        Some(ErrorKind::PermissionDenied)
+    );
+
+    // the same database is returned to the clones
+    let mut db = keystore.get_database("alice", "pw").await.unwrap();
+    db.put(b"foo", b"bar").await.unwrap();
+    let db = keystore.clone().get_database("alice", "pw").await.unwrap();
+    assert_eq!(db.get(b"foo").await.unwrap(), b"bar");
+}
@@ -0,0 +1,16 @@
+//! Per-user databases of the blockchain.
+pub mod client;
+pub mod memory;
+
+use std::{fmt::Debug, io};
+
+use crate::subnet::rpc::database::BoxedDatabase;
+
+/// Provides the databases of the keystore users to the blockchain.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/api/keystore#BlockchainKeystore>
+#[tonic::async_trait]
+pub trait Keystore: Debug {
+    /// Returns the database of the user, authenticated by the password.
+    async fn get_database(&self, username: &str, password: &str) -> io::Result<BoxedDatabase>;
+}
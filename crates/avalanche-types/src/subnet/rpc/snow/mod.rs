@@ -1,9 +1,12 @@
 //! Current runtime state of a VM.
+pub mod alias_reader;
 pub mod engine;
+pub mod keystore;
+pub mod shared_memory;
 pub mod validators;
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow#State>
-#[derive(PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum State {
     Initializing = 0,
     StateSyncing = 1,
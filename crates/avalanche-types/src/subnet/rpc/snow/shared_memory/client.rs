@@ -0,0 +1,109 @@
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind, Result},
+};
+
+use bytes::Bytes;
+use tonic::transport::Channel;
+
+use super::{Indexed, Requests};
+use crate::{
+    ids,
+    proto::pb::sharedmemory::{
+        shared_memory_client, ApplyRequest, AtomicRequest, Element, GetRequest, IndexedRequest,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct SharedMemoryClient {
+    /// The inner gRPC client for shared memory operations
+    inner: shared_memory_client::SharedMemoryClient<Channel>,
+}
+
+impl SharedMemoryClient {
+    /// Creates a new shared memory client with the given channel
+    #[must_use]
+    pub fn new(client_conn: Channel) -> Self {
+        Self {
+            inner: shared_memory_client::SharedMemoryClient::new(client_conn)
+                .max_decoding_message_size(usize::MAX)
+                .max_encoding_message_size(usize::MAX),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl super::SharedMemory for SharedMemoryClient {
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn get(&self, peer_chain_id: ids::Id, keys: &[Vec<u8>]) -> Result<Vec<Vec<u8>>> {
+        let resp = self
+            .inner
+            .clone()
+            .get(GetRequest {
+                peer_chain_id: Bytes::from(peer_chain_id),
+                keys: keys.iter().map(|k| Bytes::from(k.clone())).collect(),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("get failed: {e}")))?
+            .into_inner();
+        Ok(resp.values.into_iter().map(|v| v.to_vec()).collect())
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn indexed(
+        &self,
+        peer_chain_id: ids::Id,
+        traits: &[Vec<u8>],
+        start_trait: &[u8],
+        start_key: &[u8],
+        limit: usize,
+    ) -> Result<Indexed> {
+        let resp = self
+            .inner
+            .clone()
+            .indexed(IndexedRequest {
+                peer_chain_id: Bytes::from(peer_chain_id),
+                traits: traits.iter().map(|t| Bytes::from(t.clone())).collect(),
+                start_trait: Bytes::copy_from_slice(start_trait),
+                start_key: Bytes::copy_from_slice(start_key),
+                limit: i32::try_from(limit).unwrap_or(i32::MAX),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("indexed failed: {e}")))?
+            .into_inner();
+        Ok(Indexed {
+            values: resp.values.into_iter().map(|v| v.to_vec()).collect(),
+            last_trait: resp.last_trait.to_vec(),
+            last_key: resp.last_key.to_vec(),
+        })
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn apply(&self, requests: BTreeMap<ids::Id, Requests>) -> Result<()> {
+        let requests = requests
+            .into_iter()
+            .map(|(peer_chain_id, reqs)| AtomicRequest {
+                remove_requests: reqs.remove_requests.into_iter().map(Bytes::from).collect(),
+                put_requests: reqs
+                    .put_requests
+                    .into_iter()
+                    .map(|e| Element {
+                        key: Bytes::from(e.key),
+                        value: Bytes::from(e.value),
+                        traits: e.traits.into_iter().map(Bytes::from).collect(),
+                    })
+                    .collect(),
+                peer_chain_id: Bytes::from(peer_chain_id),
+            })
+            .collect();
+        self.inner
+            .clone()
+            .apply(ApplyRequest {
+                requests,
+                batches: Vec::new(),
+            })
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("apply failed: {e}")))?;
+        Ok(())
+    }
+}
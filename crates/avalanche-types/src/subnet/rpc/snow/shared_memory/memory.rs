@@ -0,0 +1,241 @@
+//! In-memory shared memory for testing the VM logic without avalanchego.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Error, ErrorKind},
+    sync::{Arc, RwLock},
+};
+
+use crate::ids;
+
+use super::{Element, Indexed, Requests};
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Values the peer chains put for this chain, keyed by the peer chain and the key.
+    inbound: BTreeMap<ids::Id, BTreeMap<Vec<u8>, Element>>,
+    /// Values this chain put for the peer chains, keyed by the peer chain and the key.
+    outbound: BTreeMap<ids::Id, BTreeMap<Vec<u8>, Element>>,
+}
+
+/// Implements "`shared_memory::SharedMemory`" of a single chain in memory.
+/// The values the peer chains put for this chain are set with "`put_inbound`",
+/// and the values this chain puts for the peer chains are read with "`outbound`".
+/// Clones share the same memory.
+#[derive(Debug, Clone, Default)]
+pub struct SharedMemory {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl SharedMemory {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts the value for this chain, as if put by the peer chain
+    /// (e.g., the UTXO exported from the peer chain).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn put_inbound(&self, peer_chain_id: ids::Id, element: Element) {
+        self.inner
+            .write()
+            .unwrap()
+            .inbound
+            .entry(peer_chain_id)
+            .or_default()
+            .insert(element.key.clone(), element);
+    }
+
+    /// Returns the values this chain put for the peer chain, ordered by the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    #[must_use]
+    pub fn outbound(&self, peer_chain_id: &ids::Id) -> Vec<Element> {
+        self.inner
+            .read()
+            .unwrap()
+            .outbound
+            .get(peer_chain_id)
+            .map(|elements| elements.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn poisoned(e: impl std::fmt::Display) -> Error {
+    Error::new(
+        ErrorKind::Other,
+        format!("shared memory lock poisoned: {e}"),
+    )
+}
+
+#[tonic::async_trait]
+impl super::SharedMemory for SharedMemory {
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn get(&self, peer_chain_id: ids::Id, keys: &[Vec<u8>]) -> io::Result<Vec<Vec<u8>>> {
+        let inner = self.inner.read().map_err(poisoned)?;
+        let elements = inner.inbound.get(&peer_chain_id);
+        keys.iter()
+            .map(|k| {
+                elements
+                    .and_then(|elements| elements.get(k))
+                    .map(|e| e.value.clone())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::NotFound,
+                            format!("key 0x{} not found", hex::encode(k)),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn indexed(
+        &self,
+        peer_chain_id: ids::Id,
+        traits: &[Vec<u8>],
+        start_trait: &[u8],
+        start_key: &[u8],
+        limit: usize,
+    ) -> io::Result<Indexed> {
+        let inner = self.inner.read().map_err(poisoned)?;
+        let mut indexed = Indexed::default();
+        let Some(elements) = inner.inbound.get(&peer_chain_id) else {
+            return Ok(indexed);
+        };
+
+        // ordered by the trait, then by the key
+        let traits: BTreeSet<&Vec<u8>> = traits.iter().collect();
+        let mut seen = BTreeSet::new();
+        for t in traits {
+            if t.as_slice() < start_trait {
+                continue;
+            }
+            for e in elements.values().filter(|e| e.traits.contains(t)) {
+                if indexed.values.len() >= limit {
+                    return Ok(indexed);
+                }
+                if t.as_slice() == start_trait && e.key.as_slice() <= start_key {
+                    continue;
+                }
+                if !seen.insert(e.key.as_slice()) {
+                    continue;
+                }
+                indexed.values.push(e.value.clone());
+                indexed.last_trait.clone_from(t);
+                indexed.last_key.clone_from(&e.key);
+            }
+        }
+        Ok(indexed)
+    }
+
+    /// Implements the [`crate::subnet::rpc::snow::shared_memory::SharedMemory`] trait.
+    async fn apply(&self, requests: BTreeMap<ids::Id, Requests>) -> io::Result<()> {
+        let mut inner = self.inner.write().map_err(poisoned)?;
+
+        // checks all removals first, so a failing request changes nothing
+        for (peer_chain_id, reqs) in &requests {
+            for k in &reqs.remove_requests {
+                if !inner
+                    .inbound
+                    .get(peer_chain_id)
+                    .is_some_and(|elements| elements.contains_key(k))
+                {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        format!("key 0x{} not found", hex::encode(k)),
+                    ));
+                }
+            }
+        }
+
+        for (peer_chain_id, reqs) in requests {
+            if let Some(elements) = inner.inbound.get_mut(&peer_chain_id) {
+                for k in &reqs.remove_requests {
+                    elements.remove(k);
+                }
+            }
+            let outbound = inner.outbound.entry(peer_chain_id).or_default();
+            for e in reqs.put_requests {
+                outbound.insert(e.key.clone(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::snow::shared_memory::memory::test_shared_memory` --exact --show-output
+#[tokio::test]
+async fn test_shared_memory() {
+    use super::SharedMemory as _;
+
+    let x_chain_id = ids::Id::sha256("X");
+    let element = |key: &[u8], traits: &[&[u8]]| Element {
+        key: key.to_vec(),
+        value: [key, b"-value"].concat(),
+        traits: traits.iter().map(|t| t.to_vec()).collect(),
+    };
+
+    let sm = SharedMemory::new();
+    sm.put_inbound(x_chain_id, element(b"k1", &[b"addr1"]));
+    sm.put_inbound(x_chain_id, element(b"k2", &[b"addr1", b"addr2"]));
+    sm.put_inbound(x_chain_id, element(b"k3", &[b"addr2"]));
+
+    assert_eq!(
+        sm.get(x_chain_id, &[b"k1".to_vec(), b"k3".to_vec()])
+            .await
+            .unwrap(),
+        vec![b"k1-value".to_vec(), b"k3-value".to_vec()]
+    );
+    assert!(sm.get(x_chain_id, &[b"k4".to_vec()]).await.is_err());
+    assert!(sm
+        .get(ids::Id::sha256("C"), &[b"k1".to_vec()])
+        .await
+        .is_err());
+
+    // ordered by the trait, without duplicates
+    let traits = vec![b"addr2".to_vec(), b"addr1".to_vec()];
+    let all = sm.indexed(x_chain_id, &traits, &[], &[], 10).await.unwrap();
+    assert_eq!(
+        all.values,
+        vec![
+            b"k1-value".to_vec(),
+            b"k2-value".to_vec(),
+            b"k3-value".to_vec()
+        ]
+    );
+    assert_eq!(all.last_trait, b"addr2");
+    assert_eq!(all.last_key, b"k3");
+
+    // paginated by the last trait and key
+    let page = sm.indexed(x_chain_id, &traits, &[], &[], 1).await.unwrap();
+    assert_eq!(page.values, vec![b"k1-value".to_vec()]);
+    let page = sm
+        .indexed(x_chain_id, &traits, &page.last_trait, &page.last_key, 1)
+        .await
+        .unwrap();
+    assert_eq!(page.values, vec![b"k2-value".to_vec()]);
+    assert_eq!(page.last_trait, b"addr1");
+    assert_eq!(page.last_key, b"k2");
+
+    // a missing key fails the whole apply
+    let mut requests = BTreeMap::new();
+    requests.insert(
+        x_chain_id,
+        Requests {
+            remove_requests: vec![b"k1".to_vec(), b"k4".to_vec()],
+            put_requests: vec![element(b"k5", &[b"addr3"])],
+        },
+    );
+    assert!(sm.apply(requests.clone()).await.is_err());
+    assert!(sm.outbound(&x_chain_id).is_empty());
+
+    requests.get_mut(&x_chain_id).unwrap().remove_requests = vec![b"k1".to_vec()];
+    sm.apply(requests).await.unwrap();
+    assert!(sm.get(x_chain_id, &[b"k1".to_vec()]).await.is_err());
+    assert_eq!(sm.outbound(&x_chain_id), vec![element(b"k5", &[b"addr3"])]);
+}
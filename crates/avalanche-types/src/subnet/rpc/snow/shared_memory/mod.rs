@@ -0,0 +1,62 @@
+//! Atomic shared memory between the chains (e.g., for the cross-chain
+//! import and export transactions).
+pub mod client;
+pub mod memory;
+
+use std::{collections::BTreeMap, fmt::Debug, io};
+
+use crate::ids;
+
+/// Represents a value put into the shared memory of the peer chain,
+/// indexed by its traits (e.g., the addresses of an exported UTXO).
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/chains/atomic#Element>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub traits: Vec<Vec<u8>>,
+}
+
+/// Represents the operations on the shared memory with a peer chain.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/chains/atomic#Requests>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Requests {
+    /// Keys of the values the peer chain put for this chain.
+    pub remove_requests: Vec<Vec<u8>>,
+    /// Values to put for the peer chain.
+    pub put_requests: Vec<Element>,
+}
+
+/// Represents the values returned by "`SharedMemory::indexed`", with the
+/// last trait and key to continue from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Indexed {
+    pub values: Vec<Vec<u8>>,
+    pub last_trait: Vec<u8>,
+    pub last_key: Vec<u8>,
+}
+
+/// Shared memory of this chain with the other chains.
+///
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/chains/atomic#SharedMemory>
+#[tonic::async_trait]
+pub trait SharedMemory: Debug {
+    /// Returns the values of the keys the peer chain put for this chain.
+    async fn get(&self, peer_chain_id: ids::Id, keys: &[Vec<u8>]) -> io::Result<Vec<Vec<u8>>>;
+
+    /// Returns up to "limit" values the peer chain put for this chain with
+    /// any of the traits, starting after the trait and key (if not empty).
+    async fn indexed(
+        &self,
+        peer_chain_id: ids::Id,
+        traits: &[Vec<u8>],
+        start_trait: &[u8],
+        start_key: &[u8],
+        limit: usize,
+    ) -> io::Result<Indexed>;
+
+    /// Applies the requests of all peer chains atomically.
+    async fn apply(&self, requests: BTreeMap<ids::Id, Requests>) -> io::Result<()>;
+}
@@ -63,7 +63,7 @@ impl super::State for ValidatorStateClient {
             .inner
             .clone()
             .get_subnet_id(GetSubnetIdRequest {
-                chain_id: Bytes::from(chain_id.to_vec()),
+                chain_id: Bytes::from(chain_id),
             })
             .await
             .map_err(|e| Error::new(ErrorKind::Other, format!("get_subnet_id failed: {e}")))?
@@ -81,7 +81,7 @@ impl super::State for ValidatorStateClient {
             .clone()
             .get_validator_set(GetValidatorSetRequest {
                 height,
-                subnet_id: Bytes::from(subnet_id.to_vec()),
+                subnet_id: Bytes::from(subnet_id),
             })
             .await
             .map_err(|e| Error::new(ErrorKind::Other, format!("get_validator_set failed: {e}")))?
@@ -0,0 +1,124 @@
+//! In-memory validator state for testing the VM logic without avalanchego.
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+    sync::{Arc, RwLock},
+};
+
+use crate::ids;
+
+use super::GetValidatorOutput;
+
+#[derive(Debug, Default)]
+struct Inner {
+    minimum_height: u64,
+    current_height: u64,
+    subnet_ids: BTreeMap<ids::Id, ids::Id>,
+    /// Validator sets of each subnet, keyed by the height they take effect at.
+    validator_sets: BTreeMap<ids::Id, BTreeMap<u64, BTreeMap<ids::node::Id, GetValidatorOutput>>>,
+}
+
+/// Implements "`validators::State`" with the values set in memory.
+/// Clones share the same state, so the values can be updated
+/// after the state is passed to the VM.
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl State {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum and current P-chain heights.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn set_heights(&self, minimum_height: u64, current_height: u64) {
+        let mut inner = self.inner.write().unwrap();
+        inner.minimum_height = minimum_height;
+        inner.current_height = current_height;
+    }
+
+    /// Sets the subnet ID of the chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn set_subnet_id(&self, chain_id: ids::Id, subnet_id: ids::Id) {
+        self.inner
+            .write()
+            .unwrap()
+            .subnet_ids
+            .insert(chain_id, subnet_id);
+    }
+
+    /// Sets the validators of the subnet from the height onwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn set_validator_set(
+        &self,
+        subnet_id: ids::Id,
+        height: u64,
+        validators: Vec<GetValidatorOutput>,
+    ) {
+        self.inner
+            .write()
+            .unwrap()
+            .validator_sets
+            .entry(subnet_id)
+            .or_default()
+            .insert(
+                height,
+                validators.into_iter().map(|v| (v.node_id, v)).collect(),
+            );
+    }
+}
+
+#[tonic::async_trait]
+impl super::State for State {
+    async fn get_minimum_height(&self) -> io::Result<u64> {
+        Ok(self.inner.read().unwrap().minimum_height)
+    }
+
+    async fn get_current_height(&self) -> io::Result<u64> {
+        Ok(self.inner.read().unwrap().current_height)
+    }
+
+    async fn get_subnet_id(&self, chain_id: ids::Id) -> io::Result<ids::Id> {
+        self.inner
+            .read()
+            .unwrap()
+            .subnet_ids
+            .get(&chain_id)
+            .copied()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    format!("subnet ID not found for chain {chain_id}"),
+                )
+            })
+    }
+
+    /// Returns the latest validator set of the subnet at or below the height.
+    async fn get_validator_set(
+        &self,
+        height: u64,
+        subnet_id: ids::Id,
+    ) -> io::Result<BTreeMap<ids::node::Id, GetValidatorOutput>> {
+        Ok(self
+            .inner
+            .read()
+            .unwrap()
+            .validator_sets
+            .get(&subnet_id)
+            .and_then(|sets| sets.range(..=height).next_back())
+            .map(|(_, set)| set.clone())
+            .unwrap_or_default())
+    }
+}
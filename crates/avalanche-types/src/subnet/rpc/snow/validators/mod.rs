@@ -1,4 +1,5 @@
 pub mod client;
+pub mod memory;
 
 use std::{collections::BTreeMap, fmt::Debug, io};
 
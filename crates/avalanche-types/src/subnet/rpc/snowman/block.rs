@@ -89,9 +89,12 @@ pub trait BuildBlockWithContextChainVM {
     ) -> Result<<Self as BuildBlockWithContextChainVM>::Block>;
 }
 
+/// Implemented by the blocks that optionally verify against the P-chain context,
+/// and exposed via [`snowman::Block::with_verify_context`].
+///
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/snow/engine/snowman/block#WithVerifyContext>
 #[tonic::async_trait]
-pub trait WithVerifyContext {
+pub trait WithVerifyContext: Send + Sync {
     /// Returns true if \[`verify_with_context`\] should be called.
     /// Returns false if \[verify\] should be called.
     ///
@@ -106,7 +109,7 @@ pub trait WithVerifyContext {
     /// It is guaranteed that the Parent has been successfully verified.
     ///
     /// This method may be called again with a different context.
-    async fn verify_with_context(&self, blk_context: &Context) -> Result<()>;
+    async fn verify_with_context(&mut self, blk_context: &Context) -> Result<()>;
 }
 
 /// Extends the minimal functionalities exposed by [`ChainVm`] for VMs
@@ -130,3 +133,83 @@ pub trait BatchedChainVm {
     /// Attempts to batch parse_block requests.
     async fn batched_parse_block(&self, blocks: &[Vec<u8>]) -> Result<Vec<Self::Block>>;
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::snowman::block::test_with_verify_context` --exact --show-output
+#[tokio::test]
+async fn test_with_verify_context() {
+    use crate::{choices::status::Status, subnet::rpc::consensus::snowman::Decidable};
+
+    #[derive(Default)]
+    struct TestBlock {
+        with_context: bool,
+        verified_height: Option<u64>,
+    }
+
+    #[tonic::async_trait]
+    impl Decidable for TestBlock {
+        async fn id(&self) -> Id {
+            Id::empty()
+        }
+        async fn status(&self) -> Status {
+            Status::Processing
+        }
+        async fn accept(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn reject(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tonic::async_trait]
+    impl snowman::Block for TestBlock {
+        async fn bytes(&self) -> &[u8] {
+            &[]
+        }
+        async fn height(&self) -> u64 {
+            0
+        }
+        async fn timestamp(&self) -> u64 {
+            0
+        }
+        async fn parent(&self) -> Id {
+            Id::empty()
+        }
+        async fn verify(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn with_verify_context(&mut self) -> Option<&mut dyn WithVerifyContext> {
+            if self.with_context {
+                Some(self)
+            } else {
+                None
+            }
+        }
+    }
+
+    #[tonic::async_trait]
+    impl WithVerifyContext for TestBlock {
+        async fn should_verify_with_context(&self) -> Result<bool> {
+            Ok(true)
+        }
+        async fn verify_with_context(&mut self, blk_context: &Context) -> Result<()> {
+            self.verified_height = Some(blk_context.p_chain_height);
+            Ok(())
+        }
+    }
+
+    let mut blk = TestBlock::default();
+    assert!(snowman::Block::with_verify_context(&mut blk).is_none());
+
+    let mut blk = TestBlock {
+        with_context: true,
+        ..Default::default()
+    };
+    let with_ctx = snowman::Block::with_verify_context(&mut blk).unwrap();
+    assert!(with_ctx.should_verify_with_context().await.unwrap());
+    with_ctx
+        .verify_with_context(&Context { p_chain_height: 10 })
+        .await
+        .unwrap();
+    assert_eq!(blk.verified_height, Some(10));
+}
@@ -0,0 +1,440 @@
+//! In-process harness to drive a `ChainVm` without gRPC or avalanchego.
+//!
+//! The engine initializes the VM with an in-memory database, and simulates
+//! the consensus engine of a single node by building, verifying, and accepting
+//! the blocks one at a time.
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{Duration, Utc};
+use tokio::sync::mpsc;
+
+use crate::{
+    ids,
+    subnet::rpc::{
+        consensus::snowman::{Block, Decidable},
+        context::Context,
+        database::{memdb, BoxedDatabase},
+        snow::engine::common::{appsender, message::Message, vm::Fx},
+        snowman::block::{ChainVm, Parser},
+    },
+};
+
+/// Capacity of the channel from the VM to the engine.
+const TO_ENGINE_CAPACITY: usize = 100;
+
+/// Represents the application message sent by the VM via the app sender.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Sent {
+    Request {
+        node_ids: ids::node::Set,
+        request_id: u32,
+        request: Vec<u8>,
+    },
+    Response {
+        node_id: ids::node::Id,
+        request_id: u32,
+        response: Vec<u8>,
+    },
+    Gossip(Vec<u8>),
+    CrossChainRequest {
+        chain_id: ids::Id,
+        request_id: u32,
+        request: Vec<u8>,
+    },
+    CrossChainResponse {
+        chain_id: ids::Id,
+        request_id: u32,
+        response: Vec<u8>,
+    },
+}
+
+/// Records the application messages sent by the VM, instead of sending them
+/// to the network. Clones share the same records.
+#[derive(Debug, Clone, Default)]
+pub struct AppSender {
+    /// Messages sent by the VM, in order.
+    sent: Arc<Mutex<Vec<Sent>>>,
+}
+
+impl AppSender {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns and clears the recorded messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn take(&self) -> Vec<Sent> {
+        std::mem::take(&mut *self.sent.lock().unwrap())
+    }
+
+    /// Returns and clears the recorded gossip messages,
+    /// keeping the other messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn take_gossip(&self) -> Vec<Vec<u8>> {
+        let mut sent = self.sent.lock().unwrap();
+        let (gossip, rest): (Vec<Sent>, Vec<Sent>) = std::mem::take(&mut *sent)
+            .into_iter()
+            .partition(|s| matches!(s, Sent::Gossip(_)));
+        *sent = rest;
+        gossip
+            .into_iter()
+            .filter_map(|s| match s {
+                Sent::Gossip(msg) => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Records the message sent by the VM.
+    fn push(&self, sent: Sent) {
+        self.sent.lock().unwrap().push(sent);
+    }
+}
+
+#[tonic::async_trait]
+impl appsender::AppSender for AppSender {
+    async fn send_app_request(
+        &self,
+        node_ids: ids::node::Set,
+        request_id: u32,
+        request: Vec<u8>,
+    ) -> io::Result<()> {
+        self.push(Sent::Request {
+            node_ids,
+            request_id,
+            request,
+        });
+        Ok(())
+    }
+
+    async fn send_app_response(
+        &self,
+        node_id: ids::node::Id,
+        request_id: u32,
+        response: Vec<u8>,
+    ) -> io::Result<()> {
+        self.push(Sent::Response {
+            node_id,
+            request_id,
+            response,
+        });
+        Ok(())
+    }
+
+    async fn send_app_gossip(&self, msg: Vec<u8>) -> io::Result<()> {
+        self.push(Sent::Gossip(msg));
+        Ok(())
+    }
+
+    async fn send_cross_chain_app_request(
+        &self,
+        chain_id: ids::Id,
+        request_id: u32,
+        request: Vec<u8>,
+    ) -> io::Result<()> {
+        self.push(Sent::CrossChainRequest {
+            chain_id,
+            request_id,
+            request,
+        });
+        Ok(())
+    }
+
+    async fn send_cross_chain_app_response(
+        &self,
+        chain_id: ids::Id,
+        request_id: u32,
+        response: Vec<u8>,
+    ) -> io::Result<()> {
+        self.push(Sent::CrossChainResponse {
+            chain_id,
+            request_id,
+            response,
+        });
+        Ok(())
+    }
+}
+
+/// Simulated consensus engine of a single node, which drives the VM directly.
+/// The async calls borrow the engine mutably, so the returned futures are
+/// "Send" without requiring the VM to be "Sync".
+pub struct Engine<V: ChainVm> {
+    /// VM under test.
+    vm: V,
+    /// In-memory database shared with the VM.
+    db: BoxedDatabase,
+    /// Messages from the VM, set on "initialize".
+    to_engine: Option<mpsc::Receiver<Message>>,
+}
+
+impl<V: ChainVm> Engine<V> {
+    /// Creates the engine with a fresh in-memory database.
+    #[must_use]
+    pub fn new(vm: V) -> Self {
+        Self {
+            vm,
+            db: memdb::Database::new_boxed(),
+            to_engine: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn vm(&self) -> &V {
+        &self.vm
+    }
+
+    pub fn vm_mut(&mut self) -> &mut V {
+        &mut self.vm
+    }
+
+    /// Returns the in-memory database shared with the VM
+    /// (e.g., to inspect the committed state).
+    #[must_use]
+    pub fn db(&self) -> BoxedDatabase {
+        self.db.clone()
+    }
+
+    /// Initializes the VM with the in-memory database.
+    /// Use "`Context::builder`" to create the context without avalanchego.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to initialize.
+    pub async fn initialize(
+        &mut self,
+        ctx: Option<Context<V::ValidatorState>>,
+        genesis_bytes: &[u8],
+        upgrade_bytes: &[u8],
+        config_bytes: &[u8],
+        app_sender: V::AppSender,
+    ) -> io::Result<()> {
+        let (tx, rx) = mpsc::channel(TO_ENGINE_CAPACITY);
+        self.to_engine = Some(rx);
+
+        let fxs: [Fx; 1] = [()];
+        self.vm
+            .initialize(
+                ctx,
+                self.db.clone(),
+                genesis_bytes,
+                upgrade_bytes,
+                config_bytes,
+                tx,
+                &fxs,
+                app_sender,
+            )
+            .await
+    }
+
+    /// Returns the next message the VM sent to the engine, if any.
+    pub fn try_recv(&mut self) -> Option<Message> {
+        self.to_engine.as_mut()?.try_recv().ok()
+    }
+
+    /// Builds a block on the preferred block, then verifies and accepts it,
+    /// as the consensus engine would with no competing blocks.
+    /// Returns the ID of the accepted block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to build, verify, or accept the block.
+    pub async fn build_and_accept(&mut self) -> io::Result<ids::Id> {
+        let mut block = ChainVm::build_block(&self.vm).await?;
+        block.verify().await?;
+
+        let id = block.id().await;
+        self.vm.set_preference(id).await?;
+        block.accept().await?;
+        log::debug!("accepted block {id} at height {}", block.height().await);
+
+        Ok(id)
+    }
+
+    /// Builds and accepts the blocks while the VM signals pending transactions,
+    /// up to "`max_blocks`". Returns the IDs of the accepted blocks in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to build, verify, or accept a block.
+    pub async fn run_until_idle(&mut self, max_blocks: usize) -> io::Result<Vec<ids::Id>> {
+        let mut accepted = Vec::new();
+        while accepted.len() < max_blocks {
+            match self.try_recv() {
+                Some(Message::PendingTxs) => accepted.push(self.build_and_accept().await?),
+                Some(msg) => log::debug!("ignoring message {msg:?}"),
+                None => break,
+            }
+        }
+        Ok(accepted)
+    }
+
+    /// Parses the block from a peer, then verifies and accepts it,
+    /// or rejects it. Returns the ID of the block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to parse, verify, or decide the block.
+    pub async fn deliver_block(&mut self, bytes: &[u8], accept: bool) -> io::Result<ids::Id> {
+        let mut block = Parser::parse_block(&self.vm, bytes).await?;
+        let id = block.id().await;
+
+        if accept {
+            block.verify().await?;
+            self.vm.set_preference(id).await?;
+            block.accept().await?;
+        } else {
+            block.reject().await?;
+        }
+        Ok(id)
+    }
+
+    /// Delivers the gossip message from the peer to the VM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the message.
+    pub async fn app_gossip(&mut self, node_id: &ids::node::Id, msg: &[u8]) -> io::Result<()> {
+        self.vm.app_gossip(node_id, msg).await
+    }
+
+    /// Delivers the request from the peer to the VM, with the deadline from now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the request.
+    pub async fn app_request(
+        &mut self,
+        node_id: &ids::node::Id,
+        request_id: u32,
+        timeout: std::time::Duration,
+        request: &[u8],
+    ) -> io::Result<()> {
+        let timeout = Duration::from_std(timeout)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?;
+        self.vm
+            .app_request(node_id, request_id, Utc::now() + timeout, request)
+            .await
+    }
+
+    /// Delivers the response from the peer to the VM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the response.
+    pub async fn app_response(
+        &mut self,
+        node_id: &ids::node::Id,
+        request_id: u32,
+        response: &[u8],
+    ) -> io::Result<()> {
+        self.vm.app_response(node_id, request_id, response).await
+    }
+
+    /// Delivers the cross-chain request from the VM of another chain,
+    /// with the deadline from now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the request.
+    pub async fn cross_chain_app_request(
+        &mut self,
+        chain_id: &ids::Id,
+        request_id: u32,
+        timeout: std::time::Duration,
+        request: &[u8],
+    ) -> io::Result<()> {
+        let timeout = Duration::from_std(timeout)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?;
+        self.vm
+            .cross_chain_app_request(chain_id, request_id, Utc::now() + timeout, request)
+            .await
+    }
+
+    /// Notifies the VM that the cross-chain request to the chain failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the failure.
+    pub async fn cross_chain_app_request_failed(
+        &mut self,
+        chain_id: &ids::Id,
+        request_id: u32,
+    ) -> io::Result<()> {
+        self.vm
+            .cross_chain_app_request_failed(chain_id, request_id)
+            .await
+    }
+
+    /// Delivers the cross-chain response from the VM of another chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to handle the response.
+    pub async fn cross_chain_app_response(
+        &mut self,
+        chain_id: &ids::Id,
+        request_id: u32,
+        response: &[u8],
+    ) -> io::Result<()> {
+        self.vm
+            .cross_chain_app_response(chain_id, request_id, response)
+            .await
+    }
+
+    /// Shuts down the VM and closes the in-memory database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VM fails to shut down.
+    pub async fn shutdown(self) -> io::Result<()> {
+        self.vm.shutdown().await?;
+        self.db.close().await
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::testing::test_app_sender` --exact --show-output
+#[tokio::test]
+async fn test_app_sender() {
+    use appsender::AppSender as _;
+
+    let sender = AppSender::new();
+    let node_id = ids::node::Id::from_slice(&[1; 20]);
+    sender.send_app_gossip(vec![1, 2, 3]).await.unwrap();
+    sender.send_app_response(node_id, 7, vec![4]).await.unwrap();
+    sender.clone().send_app_gossip(vec![5]).await.unwrap();
+    let chain_id = ids::Id::from_slice(&[2; 32]);
+    sender
+        .send_cross_chain_app_request(chain_id, 8, vec![6])
+        .await
+        .unwrap();
+
+    assert_eq!(sender.take_gossip(), vec![vec![1, 2, 3], vec![5]]);
+    assert_eq!(
+        sender.take(),
+        vec![
+            Sent::Response {
+                node_id,
+                request_id: 7,
+                response: vec![4],
+            },
+            Sent::CrossChainRequest {
+                chain_id,
+                request_id: 8,
+                request: vec![6],
+            },
+        ]
+    );
+    assert!(sender.take().is_empty());
+}
@@ -2,6 +2,7 @@ use crate::proto::google::protobuf::Timestamp;
 
 use std::{
     convert::Infallible,
+    env,
     io::{Error, ErrorKind, Result},
     net::SocketAddr,
     time::Duration,
@@ -68,6 +69,108 @@ pub fn default_server() -> tonic::transport::Server {
         .tcp_keepalive(Some(DEFAULT_KEEP_ALIVE_MIN_TIME))
 }
 
+/// Path of the PEM-encoded server private key, which enables TLS when set.
+pub const TLS_KEY_PATH_KEY: &str = "AVALANCHE_VM_TLS_KEY_PATH";
+/// Path of the PEM-encoded server certificate, which enables TLS when set.
+pub const TLS_CERT_PATH_KEY: &str = "AVALANCHE_VM_TLS_CERT_PATH";
+/// Path of the PEM-encoded CA certificate to verify the client certificates.
+/// If not set, the clients are not authenticated.
+pub const TLS_CLIENT_CA_PATH_KEY: &str = "AVALANCHE_VM_TLS_CLIENT_CA_PATH";
+
+/// Server TLS configuration, for deployments where the VM plugin and
+/// avalanchego run on different hosts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TlsConfig {
+    /// Path of the PEM-encoded server private key.
+    pub key_path: String,
+    /// Path of the PEM-encoded server certificate
+    /// (e.g., generated by "`cert_manager::x509::generate_and_write_pem`").
+    pub cert_path: String,
+    /// Path of the PEM-encoded CA certificate to verify the client certificates.
+    pub client_ca_path: Option<String>,
+}
+
+impl TlsConfig {
+    /// Loads the config from the environment variables
+    /// "`TLS_KEY_PATH_KEY`", "`TLS_CERT_PATH_KEY`", and "`TLS_CLIENT_CA_PATH_KEY`".
+    /// Returns "None" if neither the key nor the cert path is set.
+    ///
+    /// # Errors
+    /// Returns error if only one of the key and cert paths is set.
+    pub fn from_env() -> Result<Option<Self>> {
+        Self::from_lookup(|key| env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Option<Self>> {
+        match (lookup(TLS_KEY_PATH_KEY), lookup(TLS_CERT_PATH_KEY)) {
+            (None, None) => Ok(None),
+            (Some(key_path), Some(cert_path)) => Ok(Some(Self {
+                key_path,
+                cert_path,
+                client_ca_path: lookup(TLS_CLIENT_CA_PATH_KEY),
+            })),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("both {TLS_KEY_PATH_KEY} and {TLS_CERT_PATH_KEY} must be set for TLS"),
+            )),
+        }
+    }
+
+    /// Loads the server identity and the client CA from the files.
+    ///
+    /// # Errors
+    /// Returns error if any of the files fails to load.
+    #[cfg(feature = "subnet_tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "subnet_tls")))]
+    pub fn server_tls_config(&self) -> Result<tonic::transport::ServerTlsConfig> {
+        use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+        let (key, cert) = cert_manager::x509::load_pem_to_vec(&self.key_path, &self.cert_path)?;
+        let mut config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+        if let Some(client_ca_path) = &self.client_ca_path {
+            let ca = std::fs::read(client_ca_path).map_err(|e| {
+                Error::new(
+                    e.kind(),
+                    format!("failed to read client CA '{client_ca_path}': {e}"),
+                )
+            })?;
+            config = config.client_ca_root(Certificate::from_pem(ca));
+        }
+        Ok(config)
+    }
+}
+
+/// Creates a tonic gRPC server with avalanche defaults,
+/// serving TLS if the config is given.
+///
+/// # Errors
+/// Returns error if the TLS config fails to load, or TLS is requested
+/// without the "`subnet_tls`" feature.
+pub fn server_with_tls(tls: Option<&TlsConfig>) -> Result<tonic::transport::Server> {
+    let Some(tls) = tls else {
+        return Ok(default_server());
+    };
+    log::info!(
+        "serving TLS with cert '{}' (client auth {})",
+        tls.cert_path,
+        tls.client_ca_path.is_some()
+    );
+
+    #[cfg(feature = "subnet_tls")]
+    {
+        default_server()
+            .tls_config(tls.server_tls_config()?)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("invalid TLS config: {e}")))
+    }
+    #[cfg(not(feature = "subnet_tls"))]
+    {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "TLS requires the \"subnet_tls\" feature",
+        ))
+    }
+}
+
 /// Creates a tonic Endpoint with avalanche defaults. The endpoint input is
 /// expected in `<ip>:<port>` format.
 ///
@@ -90,13 +193,27 @@ pub struct Server {
 
     /// Server address.
     pub addr: SocketAddr,
+
+    /// Serves TLS if set, otherwise plaintext.
+    pub tls: Option<TlsConfig>,
 }
 
 impl Server {
     /// 创建一个新的 gRPC Server。
     #[must_use]
     pub const fn new(addr: SocketAddr, stop_ch: Receiver<()>) -> Self {
-        Self { stop_ch, addr }
+        Self {
+            stop_ch,
+            addr,
+            tls: None,
+        }
+    }
+
+    /// Serves TLS with the config.
+    #[must_use]
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
     }
 }
 
@@ -108,6 +225,7 @@ impl Server {
     ///
     /// # Errors
     /// 如果服务启动失败，返回错误。
+    /// Returns error if the TLS config fails to load.
     pub fn serve<S>(mut self, svc: S) -> Result<()>
     where
         S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
@@ -117,8 +235,9 @@ impl Server {
             + 'static,
         S::Future: Send + 'static,
     {
+        let mut server = server_with_tls(self.tls.as_ref())?;
         tokio::spawn(async move {
-            default_server()
+            server
                 .add_service(svc)
                 .serve_with_shutdown(self.addr, self.stop_ch.recv().map(|_| ()))
                 .await
@@ -141,3 +260,63 @@ pub fn timestamp_from_time(dt: &DateTime<Utc>) -> Timestamp {
         nanos: i32::try_from(dt.timestamp_subsec_nanos()).unwrap_or(0),
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::utils::grpc::test_tls_config` --exact --show-output
+#[test]
+fn test_tls_config() {
+    use std::collections::HashMap;
+
+    let vars = HashMap::from([
+        (TLS_KEY_PATH_KEY, "/tmp/key.pem"),
+        (TLS_CERT_PATH_KEY, "/tmp/cert.pem"),
+    ]);
+    /// Looks up the variables in the map instead of the environment.
+    fn lookup(vars: HashMap<&'static str, &'static str>) -> impl Fn(&str) -> Option<String> {
+        move |key| vars.get(key).map(ToString::to_string)
+    }
+
+    assert_eq!(
+        TlsConfig::from_lookup(lookup(HashMap::new())).unwrap(),
+        None
+    );
+    assert_eq!(
+        TlsConfig::from_lookup(lookup(vars.clone())).unwrap(),
+        Some(TlsConfig {
+            key_path: String::from("/tmp/key.pem"),
+            cert_path: String::from("/tmp/cert.pem"),
+            client_ca_path: None,
+        })
+    );
+    assert!(TlsConfig::from_lookup(lookup(HashMap::from([(
+        TLS_CERT_PATH_KEY,
+        "/tmp/cert.pem"
+    )])))
+    .is_err());
+
+    assert!(server_with_tls(None).is_ok());
+    #[cfg(not(feature = "subnet_tls"))]
+    assert!(server_with_tls(TlsConfig::from_lookup(lookup(vars)).unwrap().as_ref()).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet_tls -- `subnet::rpc::utils::grpc::test_server_tls_config` --exact --show-output
+#[cfg(feature = "subnet_tls")]
+#[test]
+fn test_server_tls_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = dir.path().join("key.pem").display().to_string();
+    let cert_path = dir.path().join("cert.pem").display().to_string();
+    cert_manager::x509::generate_and_write_pem(None, &key_path, &cert_path).unwrap();
+
+    let tls = TlsConfig {
+        key_path,
+        cert_path: cert_path.clone(),
+        client_ca_path: Some(cert_path),
+    };
+    assert!(server_with_tls(Some(&tls)).is_ok());
+
+    let missing = TlsConfig {
+        client_ca_path: Some(dir.path().join("ca.pem").display().to_string()),
+        ..tls
+    };
+    assert!(missing.server_tls_config().is_err());
+}
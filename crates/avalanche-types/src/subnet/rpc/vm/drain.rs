@@ -0,0 +1,116 @@
+//! Tracks the in-flight RPCs of the VM server for graceful shutdown.
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::Notify;
+
+/// Default time to wait for the in-flight block operations on shutdown.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Counts the in-flight RPCs, and stops admitting new ones once draining.
+#[derive(Debug, Default)]
+pub struct Drain {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl Drain {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admits a new RPC, returning the guard that marks it finished on drop.
+    /// Returns "None" once draining.
+    #[must_use]
+    pub fn enter(self: &Arc<Self>) -> Option<Guard> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.is_draining() {
+            self.exit();
+            return None;
+        }
+        Some(Guard {
+            drain: Arc::clone(self),
+        })
+    }
+
+    /// Stops admitting new RPCs.
+    pub fn start(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    #[must_use]
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of in-flight RPCs.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Waits until all in-flight RPCs finish, or the timeout elapses.
+    /// Returns "true" if drained within the timeout.
+    pub async fn wait(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    fn exit(&self) {
+        if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Marks the admitted RPC finished on drop.
+#[derive(Debug)]
+#[must_use]
+pub struct Guard {
+    drain: Arc<Drain>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.drain.exit();
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::vm::drain::test_drain` --exact --show-output
+#[tokio::test]
+async fn test_drain() {
+    let drain = Arc::new(Drain::new());
+    assert!(drain.wait(Duration::from_millis(10)).await);
+
+    let guard = drain.enter().unwrap();
+    assert_eq!(drain.in_flight(), 1);
+
+    drain.start();
+    assert!(drain.is_draining());
+    assert!(drain.enter().is_none());
+    assert_eq!(drain.in_flight(), 1);
+    assert!(!drain.wait(Duration::from_millis(10)).await);
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+    });
+    assert!(drain.wait(Duration::from_secs(5)).await);
+    assert_eq!(drain.in_flight(), 0);
+}
@@ -0,0 +1,99 @@
+//! Tracks the lifecycle state of the VM to guard the RPCs
+//! that are only valid in normal operations.
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+use crate::subnet::rpc::snow::State;
+
+/// Tracks the state last set by the engine ("Initializing" until then).
+/// Unless permissive, rejects the guarded RPCs (e.g., "`build_block`")
+/// before the VM reaches "`NormalOp`".
+#[derive(Debug)]
+pub struct Lifecycle {
+    state: AtomicI32,
+    permissive: AtomicBool,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            state: AtomicI32::new(State::Initializing as i32),
+            permissive: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Lifecycle {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current state.
+    #[must_use]
+    pub fn state(&self) -> State {
+        State::try_from(self.state.load(Ordering::SeqCst)).unwrap_or(State::Initializing)
+    }
+
+    /// Updates the current state.
+    pub fn set_state(&self, state: State) {
+        log::info!("vm state {} -> {}", self.state().as_str(), state.as_str());
+        self.state.store(state as i32, Ordering::SeqCst);
+    }
+
+    /// Sets "true" to forward the guarded RPCs regardless of the state.
+    pub fn set_permissive(&self, permissive: bool) {
+        self.permissive.store(permissive, Ordering::SeqCst);
+    }
+
+    #[must_use]
+    pub fn is_permissive(&self) -> bool {
+        self.permissive.load(Ordering::SeqCst)
+    }
+
+    /// Admits the RPC only in normal operations, unless permissive.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`FAILED_PRECONDITION`" while initializing, and "UNAVAILABLE"
+    /// while state syncing or bootstrapping so the caller retries later.
+    pub fn check(&self, method: &str) -> std::result::Result<(), tonic::Status> {
+        if self.is_permissive() {
+            return Ok(());
+        }
+        match self.state() {
+            State::NormalOp => Ok(()),
+            State::Initializing => Err(tonic::Status::failed_precondition(format!(
+                "{method} rejected: vm is not initialized"
+            ))),
+            state @ (State::StateSyncing | State::Bootstrapping) => Err(
+                tonic::Status::unavailable(format!("{method} rejected: {}", state.as_str())),
+            ),
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::vm::lifecycle::test_lifecycle` --exact --show-output
+#[test]
+fn test_lifecycle() {
+    let lifecycle = Lifecycle::new();
+    assert!(matches!(lifecycle.state(), State::Initializing));
+    assert_eq!(
+        lifecycle.check("build_block").unwrap_err().code(),
+        tonic::Code::FailedPrecondition
+    );
+
+    lifecycle.set_state(State::Bootstrapping);
+    assert!(matches!(lifecycle.state(), State::Bootstrapping));
+    assert_eq!(
+        lifecycle.check("app_gossip").unwrap_err().code(),
+        tonic::Code::Unavailable
+    );
+
+    lifecycle.set_permissive(true);
+    assert!(lifecycle.check("build_block").is_ok());
+    lifecycle.set_permissive(false);
+
+    lifecycle.set_state(State::NormalOp);
+    assert!(matches!(lifecycle.state(), State::NormalOp));
+    assert!(lifecycle.check("build_block").is_ok());
+}
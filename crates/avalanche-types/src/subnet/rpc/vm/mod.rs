@@ -1,4 +1,6 @@
 //! RPC Chain VM implementation.
+pub mod drain;
+pub mod lifecycle;
 pub mod server;
 
 use std::{
@@ -34,6 +36,7 @@ impl NamedService for HealthServer {
 
 /// The address of the Runtime server is expected to be passed via ENV `runtime::ENGINE_ADDR_KEY`.
 /// This address is used by the Runtime client to send Initialize RPC to server.
+/// The server serves TLS if configured via ENV (see `utils::grpc::TlsConfig::from_env`).
 ///
 // Serve starts the RPC Chain VM server and performs a handshake with the VM runtime service.
 /// 启动 RPC Chain VM server 并与 VM runtime service 握手。
@@ -48,6 +51,7 @@ where
     // ref. https://github.com/hyperium/tonic/issues/966
     // avalanchego currently only supports plugins listening on IP address.
     let vm_server_addr = utils::new_socket_addr();
+    let tls = utils::grpc::TlsConfig::from_env()?;
 
     let runtime_server_addr = env::var(runtime::ENGINE_ADDR_KEY).map_err(|e| {
         Error::new(
@@ -80,14 +84,30 @@ where
         )
     })?;
 
-    serve_with_address(vm, vm_server_addr, stop_ch).await
+    serve_with_tls(vm, vm_server_addr, tls.as_ref(), stop_ch).await
 }
 
 /// 指定地址启动 RPC Chain VM server。
 ///
 /// # Errors
 /// 如果 gRPC 服务创建或启动失败，返回 `io::Error`。
-pub async fn serve_with_address<V>(vm: V, addr: SocketAddr, mut stop_ch: Receiver<()>) -> Result<()>
+pub async fn serve_with_address<V>(vm: V, addr: SocketAddr, stop_ch: Receiver<()>) -> Result<()>
+where
+    V: VmImpl,
+{
+    serve_with_tls(vm, addr, None, stop_ch).await
+}
+
+/// Starts the RPC Chain VM server at the address, serving TLS if the config is given.
+///
+/// # Errors
+/// Returns error if the TLS config fails to load, or the gRPC server fails.
+pub async fn serve_with_tls<V>(
+    vm: V,
+    addr: SocketAddr,
+    tls: Option<&utils::grpc::TlsConfig>,
+    mut stop_ch: Receiver<()>,
+) -> Result<()>
 where
     V: VmImpl,
 {
@@ -110,7 +130,7 @@ where
             )
         })?;
 
-    utils::grpc::default_server()
+    utils::grpc::server_with_tls(tls)?
         .add_service(health_svc)
         .add_service(reflection_service)
         .add_service(VmServer::new(vm))
@@ -5,30 +5,35 @@ use crate::{
     ids,
     proto::pb::{
         self,
-        aliasreader::alias_reader_client::AliasReaderClient,
         google::protobuf::Empty,
-        keystore::keystore_client::KeystoreClient,
         messenger::{messenger_client::MessengerClient, NotifyRequest},
-        sharedmemory::shared_memory_client::SharedMemoryClient,
         vm,
     },
     subnet::rpc::{
         consensus::snowman::{Block, Decidable},
-        context::Context,
+        context::{Context, NetworkUpgrades},
         database::rpcdb::{client::DatabaseClient, error_to_error_code},
-        database::{corruptabledb, manager::DatabaseManager},
+        database::{corruptabledb, manager::DatabaseManager, BoxedDatabase},
         errors,
-        http::server::Server as HttpServer,
+        health::{self, Checkable},
+        http::{handle::Handle, server::Server as HttpServer},
         snow::{
+            alias_reader::client::AliasReaderClient,
             engine::common::{appsender::client::AppSenderClient, message::Message},
+            keystore::client::KeystoreClient,
+            shared_memory::client::SharedMemoryClient,
             validators::client::ValidatorStateClient,
             State,
         },
-        snowman::block::ChainVm,
+        snowman::block::{ChainVm, Context as BlockContext},
         utils::{
             self,
             grpc::{self, timestamp_from_time},
         },
+        vm::{
+            drain::{self, Drain},
+            lifecycle::Lifecycle,
+        },
     },
 };
 use chrono::{TimeZone, Utc};
@@ -51,6 +56,63 @@ pub struct Server<V> {
 
     /// Stop channel broadcast producer.
     pub stop_ch: broadcast::Sender<()>,
+
+    /// Tracks the in-flight block operations to drain on shutdown.
+    pub drain: Arc<Drain>,
+
+    /// Tracks the VM state set by the engine to reject the block building
+    /// and gossip before normal operations.
+    pub lifecycle: Arc<Lifecycle>,
+
+    /// Checks served by the "health" RPC, to register the checks of
+    /// the VM components (e.g., "mempool") with. The Vm itself is
+    /// registered as "vm" on initialize, when the checks start to run.
+    pub health: health::Registry,
+
+    /// Database passed to the Vm on initialize, closed on shutdown.
+    db: Arc<RwLock<Option<BoxedDatabase>>>,
+}
+
+/// Measures a single RPC call, recorded on drop as failed unless
+/// the response is returned via "ok".
+/// The metrics are only recorded with the "`subnet_metrics`" feature.
+#[must_use]
+struct RpcTimer {
+    method: &'static str,
+    start: Instant,
+    ok: bool,
+}
+
+impl RpcTimer {
+    fn start(method: &'static str) -> Self {
+        Self {
+            method,
+            start: Instant::now(),
+            ok: false,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn ok<T>(mut self, resp: T) -> std::result::Result<T, tonic::Status> {
+        self.ok = true;
+        Ok(resp)
+    }
+}
+
+impl Drop for RpcTimer {
+    fn drop(&mut self) {
+        log::debug!(
+            "{} took {:?} (ok {})",
+            self.method,
+            self.start.elapsed(),
+            self.ok
+        );
+
+        #[cfg(feature = "subnet_metrics")]
+        if let Some(m) = crate::subnet::rpc::metrics::VmRpcMetrics::global() {
+            m.observe(self.method, self.ok, self.start.elapsed());
+        }
+    }
 }
 
 impl<V: ChainVm> Server<V> {
@@ -59,11 +121,75 @@ impl<V: ChainVm> Server<V> {
             vm: Arc::new(RwLock::new(vm)),
             #[cfg(feature = "subnet_metrics")]
             #[cfg_attr(docsrs, doc(cfg(feature = "subnet_metrics")))]
-            process_metrics: Arc::new(RwLock::new(prometheus::default_registry().to_owned())),
+            process_metrics: {
+                // registers the RPC metrics before the first "gather"
+                let _ = crate::subnet::rpc::metrics::VmRpcMetrics::global();
+                Arc::new(RwLock::new(prometheus::default_registry().to_owned()))
+            },
             stop_ch,
+            drain: Arc::new(Drain::new()),
+            lifecycle: Arc::new(Lifecycle::new()),
+            health: health::Registry::new(),
+            db: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Set "true" to forward "`build_block`" and "`app_gossip`" regardless of
+    /// the VM state, for the VMs that handle them while bootstrapping.
+    #[must_use]
+    pub fn permissive_state(self, permissive: bool) -> Self {
+        self.lifecycle.set_permissive(permissive);
+        self
+    }
+
+    /// Gracefully shuts down the server: stops admitting new block operations,
+    /// waits up to the timeout for the in-flight ones to finish, shuts down
+    /// the Vm so it commits its state, closes the database, and then notifies
+    /// all gRPC servers to stop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Vm or the database fails to shut down,
+    /// or the stop signal has no receiver. The gRPC servers are notified
+    /// to stop regardless.
+    pub async fn shutdown_with_timeout(&self, timeout: Duration) -> std::io::Result<()> {
+        self.drain.start();
+        if !self.drain.wait(timeout).await {
+            log::warn!(
+                "shutting down with {} in-flight block operations after {timeout:?}",
+                self.drain.in_flight()
+            );
+        }
+
+        let vm_result = self.vm.read().await.shutdown().await;
+        if let Err(e) = &vm_result {
+            log::warn!("failed to shut down vm: {e}");
+        }
+
+        let db = self.db.write().await.take();
+        let db_result = match db {
+            Some(db) => db.close().await,
+            None => Ok(()),
+        };
+        if let Err(e) = &db_result {
+            log::warn!("failed to close database: {e}");
+        }
+
+        // notify all gRPC servers to shutdown
+        self.stop_ch
+            .send(())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+        vm_result.and(db_result)
+    }
+
+    /// Admits a block operation unless the server is draining.
+    fn enter(&self) -> std::result::Result<drain::Guard, tonic::Status> {
+        self.drain
+            .enter()
+            .ok_or_else(|| tonic::Status::unavailable("vm server is shutting down"))
+    }
+
     /// Attempts to get the ancestors of a block from the underlying Vm.
     ///
     /// # Errors
@@ -90,6 +216,43 @@ impl<V: ChainVm> Server<V> {
     }
 }
 
+impl<V> Server<V> {
+    /// Starts the gRPC server serving the HTTP service of the handler,
+    /// and returns the handler to register under the extension "prefix".
+    fn serve_http_handler<H: Handle + 'static>(
+        &self,
+        prefix: String,
+        handler: H,
+    ) -> std::result::Result<vm::Handler, tonic::Status> {
+        let server_addr = utils::new_socket_addr();
+        let server = grpc::Server::new(server_addr, self.stop_ch.subscribe());
+
+        server
+            .serve(pb::http::http_server::HttpServer::new(HttpServer::new(
+                handler,
+            )))
+            .map_err(|e| tonic::Status::unknown(format!("failed to create http service: {e}")))?;
+
+        Ok(vm::Handler {
+            prefix,
+            server_addr: server_addr.to_string(),
+        })
+    }
+}
+
+/// Returns "true" if the block should be verified with the P-chain context.
+async fn should_verify_with_context<B: Block>(
+    block: &mut B,
+) -> std::result::Result<bool, tonic::Status> {
+    match block.with_verify_context() {
+        Some(block) => block
+            .should_verify_with_context()
+            .await
+            .map_err(|e| tonic::Status::unknown(e.to_string())),
+        None => Ok(false),
+    }
+}
+
 #[tonic::async_trait]
 impl<V> Vm for Server<V>
 where
@@ -101,8 +264,9 @@ where
         + Sync
         + 'static,
 {
-    // Cross-chain methods have been removed from the VM trait
-    // These methods are now handled by the app_request, app_request_failed, and app_response methods
+    // Cross-chain app messages were removed from the rpcchainvm protocol, so there
+    // is nothing to route to "CrossChainAppHandler" here; use "testing::Engine"
+    // to deliver them to the VM in-process.
     /// Implements "avalanchego/vms/rpcchainvm#VMServer.Initialize".
     /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.1/vms/rpcchainvm/vm_server.go#L98>
     /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.11.1/vms/rpcchainvm/vm_client.go#L123-L133>
@@ -112,8 +276,13 @@ where
         req: Request<vm::InitializeRequest>,
     ) -> std::result::Result<Response<vm::InitializeResponse>, tonic::Status> {
         log::info!("initialize called");
+        let _guard = self.enter()?;
+        self.lifecycle.set_state(State::Initializing);
+        let timer = RpcTimer::start("initialize");
 
         let req = req.into_inner();
+        let node_id = ids::node::Id::from_initialize_request(&req)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
 
         let db_server_addr = req.db_server_addr.as_str();
         // 合并 db_client_conn 的声明和唯一用途，防止提前 drop
@@ -128,6 +297,7 @@ where
                 })?,
         ));
         let db = db; // 移除错误的.await
+        *self.db.write().await = Some(db.clone());
 
         let server_addr = req.server_addr.as_str();
         let client_conn = utils::grpc::default_client(server_addr)?
@@ -142,9 +312,9 @@ where
         // Multiplexing in tonic is done by cloning the client which is very cheap.
         // ref. https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html#multiplexing-requests
         let mut message = MessengerClient::new(client_conn.clone());
-        let keystore = KeystoreClient::new(client_conn.clone());
-        let shared_memory = SharedMemoryClient::new(client_conn.clone());
-        let bc_lookup = AliasReaderClient::new(client_conn.clone());
+        let keystore = Arc::new(KeystoreClient::new(client_conn.clone()));
+        let shared_memory = Arc::new(SharedMemoryClient::new(client_conn.clone()));
+        let bc_lookup = Arc::new(AliasReaderClient::new(client_conn.clone()));
 
         // 合并 ctx 的声明和唯一用途，避免提前 drop。
         let (tx_engine, mut rx_engine): (mpsc::Sender<Message>, mpsc::Receiver<Message>) =
@@ -178,7 +348,7 @@ where
                     network_id: req.network_id,
                     subnet_id: ids::Id::from_slice(&req.subnet_id),
                     chain_id: ids::Id::from_slice(&req.chain_id),
-                    node_id: ids::node::Id::from_slice(&req.node_id),
+                    node_id,
                     x_chain_id: ids::Id::from_slice(&req.x_chain_id),
                     c_chain_id: ids::Id::from_slice(&req.c_chain_id),
                     avax_asset_id: ids::Id::from_slice(&req.avax_asset_id),
@@ -187,6 +357,11 @@ where
                     bc_lookup,
                     chain_data_dir: req.chain_data_dir,
                     validator_state: ValidatorStateClient::new(client_conn.clone()),
+                    network_upgrades: req
+                        .network_upgrades
+                        .as_ref()
+                        .map(NetworkUpgrades::from)
+                        .unwrap_or_default(),
                 }),
                 db,
                 &req.genesis_bytes,
@@ -213,9 +388,19 @@ where
 
         log::debug!("last_accepted_block id: {last_accepted:?}");
 
-        Ok(Response::new(vm::InitializeResponse {
-            last_accepted_id: Bytes::from(last_accepted.to_vec()),
-            last_accepted_parent_id: Bytes::from(last_accepted_block.parent().await.to_vec()),
+        // run the Vm and component checks in the background from now on
+        match self
+            .health
+            .register("vm", Arc::new(Arc::clone(&self.vm)))
+            .await
+        {
+            Ok(()) => drop(self.health.spawn(self.stop_ch.subscribe())),
+            Err(e) => log::warn!("skipping health checks '{e}'"),
+        }
+
+        timer.ok(Response::new(vm::InitializeResponse {
+            last_accepted_id: Bytes::from(last_accepted),
+            last_accepted_parent_id: Bytes::from(last_accepted_block.parent().await),
             bytes: Bytes::from(last_accepted_block.bytes().await.to_vec()),
             height: last_accepted_block.height().await,
             timestamp: Some(timestamp_from_time(
@@ -240,9 +425,8 @@ where
     ) -> std::result::Result<tonic::Response<pb::google::protobuf::Empty>, tonic::Status> {
         log::debug!("shutdown called");
 
-        // notify all gRPC servers to shutdown
-        self.stop_ch
-            .send(())
+        self.shutdown_with_timeout(drain::DEFAULT_SHUTDOWN_TIMEOUT)
+            .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
 
         Ok(tonic::Response::new(pb::google::protobuf::Empty {}))
@@ -257,14 +441,16 @@ where
     /// with the chain. Each handler has the path:
     /// `\[Address of node]/ext/bc/[chain ID]/[extension\]`
     ///
+    /// The rpcchainvm protocol no longer has "CreateStaticHandlers", so the
+    /// VM-level (chain-independent) handlers of "`create_static_handlers`" are
+    /// served along with the chain handlers, which win on the same extension.
+    ///
     /// Returns a mapping from \[extension\]s to HTTP handlers.
     /// Each extension can specify how locking is managed for convenience.
     ///
     /// For example, if this VM implements an account-based payments system,
     /// it have an extension called `accounts`, where clients could get
     /// information about their accounts.
-    #[allow(clippy::too_many_lines)]
-    #[allow(clippy::too_many_lines)]
     async fn create_handlers(
         &self,
         _req: Request<Empty>,
@@ -273,35 +459,31 @@ where
 
         // get handlers from underlying vm
         // 合并 handlers 的声明和唯一用途，防止提前 drop
-        let handlers = {
+        let (handlers, static_handlers) = {
             let mut inner_vm = self.vm.write().await;
             let h = inner_vm
                 .create_handlers()
                 .await
                 .map_err(|e| tonic::Status::unknown(format!("failed to create handlers: {e}")))?;
+            let sh = inner_vm.create_static_handlers().await.map_err(|e| {
+                tonic::Status::unknown(format!("failed to create static handlers: {e}"))
+            })?;
             drop(inner_vm);
-            h
+            (h, sh)
         };
 
         // create and start gRPC server serving HTTP service for each handler
-        let mut resp_handlers: Vec<vm::Handler> = Vec::with_capacity(handlers.keys().len());
+        let mut resp_handlers: Vec<vm::Handler> =
+            Vec::with_capacity(handlers.len() + static_handlers.len());
+        for (prefix, http_handler) in static_handlers {
+            if handlers.contains_key(&prefix) {
+                log::warn!("static handler '{prefix}' is shadowed by the chain handler");
+                continue;
+            }
+            resp_handlers.push(self.serve_http_handler(prefix, http_handler.handler)?);
+        }
         for (prefix, http_handler) in handlers {
-            let server_addr = utils::new_socket_addr();
-            let server = grpc::Server::new(server_addr, self.stop_ch.subscribe());
-
-            server
-                .serve(pb::http::http_server::HttpServer::new(HttpServer::new(
-                    http_handler.handler,
-                )))
-                .map_err(|e| {
-                    tonic::Status::unknown(format!("failed to create http service: {e}"))
-                })?;
-
-            let resp_handler = vm::Handler {
-                prefix,
-                server_addr: server_addr.to_string(),
-            };
-            resp_handlers.push(resp_handler);
+            resp_handlers.push(self.serve_http_handler(prefix, http_handler.handler)?);
         }
 
         Ok(Response::new(vm::CreateHandlersResponse {
@@ -314,18 +496,22 @@ where
         _req: Request<vm::BuildBlockRequest>,
     ) -> std::result::Result<Response<vm::BuildBlockResponse>, tonic::Status> {
         log::debug!("build_block called");
+        let _guard = self.enter()?;
+        self.lifecycle.check("build_block")?;
+        let timer = RpcTimer::start("build_block");
 
-        let block = self
+        let mut block = self
             .vm
             .write()
             .await
             .build_block()
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
+        let verify_with_context = should_verify_with_context(&mut block).await?;
 
-        Ok(Response::new(vm::BuildBlockResponse {
-            id: Bytes::from(block.id().await.to_vec()),
-            parent_id: Bytes::from(block.parent().await.to_vec()),
+        timer.ok(Response::new(vm::BuildBlockResponse {
+            id: Bytes::from(block.id().await),
+            parent_id: Bytes::from(block.parent().await),
             bytes: Bytes::from(block.bytes().await.to_vec()),
             height: block.height().await,
             timestamp: Some(timestamp_from_time(
@@ -335,7 +521,7 @@ where
                 )
                 .unwrap(),
             )),
-            verify_with_context: false,
+            verify_with_context,
         }))
     }
 
@@ -344,19 +530,22 @@ where
         req: Request<vm::ParseBlockRequest>,
     ) -> std::result::Result<Response<vm::ParseBlockResponse>, tonic::Status> {
         log::debug!("parse_block called");
+        let _guard = self.enter()?;
+        let timer = RpcTimer::start("parse_block");
 
         let req = req.into_inner();
-        let block = self
+        let mut block = self
             .vm
             .write()
             .await
             .parse_block(req.bytes.as_ref())
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
+        let verify_with_context = should_verify_with_context(&mut block).await?;
 
-        Ok(Response::new(vm::ParseBlockResponse {
-            id: Bytes::from(block.id().await.to_vec()),
-            parent_id: Bytes::from(block.parent().await.to_vec()),
+        timer.ok(Response::new(vm::ParseBlockResponse {
+            id: Bytes::from(block.id().await),
+            parent_id: Bytes::from(block.parent().await),
             height: block.height().await,
             timestamp: Some(timestamp_from_time(
                 &Utc.timestamp_opt(
@@ -365,7 +554,7 @@ where
                 )
                 .unwrap(),
             )),
-            verify_with_context: false,
+            verify_with_context,
         }))
     }
 
@@ -392,20 +581,23 @@ where
 
         // determine if response is an error or not
         match inner_vm.get_block(ids::Id::from_slice(&req.id)).await {
-            Ok(block) => Ok(Response::new(vm::GetBlockResponse {
-                parent_id: Bytes::from(block.parent().await.to_vec()),
-                bytes: Bytes::from(block.bytes().await.to_vec()),
-                height: block.height().await,
-                timestamp: Some(timestamp_from_time(
-                    &Utc.timestamp_opt(
-                        i64::try_from(block.timestamp().await).unwrap_or_default(),
-                        0,
-                    )
-                    .unwrap(),
-                )),
-                err: 0,
-                verify_with_context: false,
-            })),
+            Ok(mut block) => {
+                let verify_with_context = should_verify_with_context(&mut block).await?;
+                Ok(Response::new(vm::GetBlockResponse {
+                    parent_id: Bytes::from(block.parent().await),
+                    bytes: Bytes::from(block.bytes().await.to_vec()),
+                    height: block.height().await,
+                    timestamp: Some(timestamp_from_time(
+                        &Utc.timestamp_opt(
+                            i64::try_from(block.timestamp().await).unwrap_or_default(),
+                            0,
+                        )
+                        .unwrap(),
+                    )),
+                    err: 0,
+                    verify_with_context,
+                }))
+            }
             // if an error was found, generate empty response with ErrNotFound code
             // ref: https://github.com/ava-labs/avalanchego/blob/master/vms/
             Err(e) => {
@@ -440,6 +632,7 @@ where
                 .set_state(state)
                 .await
                 .map_err(|e| tonic::Status::unknown(e.to_string()))?;
+            self.lifecycle.set_state(state);
             let last_accepted_id = inner_vm.last_accepted().await?;
             let block = inner_vm
                 .get_block(last_accepted_id)
@@ -450,8 +643,8 @@ where
         };
 
         Ok(Response::new(vm::SetStateResponse {
-            last_accepted_id: Bytes::from(last_accepted_id.to_vec()),
-            last_accepted_parent_id: Bytes::from(block.parent().await.to_vec()),
+            last_accepted_id: Bytes::from(last_accepted_id),
+            last_accepted_parent_id: Bytes::from(block.parent().await),
             height: block.height().await,
             bytes: Bytes::from(block.bytes().await.to_vec()),
             timestamp: Some(timestamp_from_time(
@@ -469,6 +662,7 @@ where
         req: Request<vm::SetPreferenceRequest>,
     ) -> std::result::Result<Response<Empty>, tonic::Status> {
         log::debug!("set_preference called");
+        let _guard = self.enter()?;
 
         let req = req.into_inner();
         self.vm
@@ -488,9 +682,7 @@ where
         log::debug!("health called");
 
         let resp = self
-            .vm
-            .read()
-            .await
+            .health
             .health_check()
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
@@ -621,6 +813,7 @@ where
         req: Request<vm::AppGossipMsg>,
     ) -> std::result::Result<Response<Empty>, tonic::Status> {
         log::debug!("app_gossip called");
+        self.lifecycle.check("app_gossip")?;
 
         let req = req.into_inner();
         let node_id = ids::node::Id::from_slice(&req.node_id);
@@ -639,6 +832,7 @@ where
         req: Request<vm::BlockVerifyRequest>,
     ) -> std::result::Result<Response<vm::BlockVerifyResponse>, tonic::Status> {
         log::debug!("block_verify called");
+        let _guard = self.enter()?;
 
         let req = req.into_inner();
         let mut block = self
@@ -649,10 +843,20 @@ where
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
 
-        block
-            .verify()
-            .await
-            .map_err(|e| tonic::Status::unknown(e.to_string()))?;
+        // ref. "avalanchego/vms/rpcchainvm#VMServer.BlockVerify"
+        if let Some(p_chain_height) = req.p_chain_height {
+            let Some(block) = block.with_verify_context() else {
+                return Err(tonic::Status::invalid_argument(
+                    "p-chain height is set but the block does not verify with context",
+                ));
+            };
+            block
+                .verify_with_context(&BlockContext { p_chain_height })
+                .await
+        } else {
+            block.verify().await
+        }
+        .map_err(|e| tonic::Status::unknown(e.to_string()))?;
 
         Ok(Response::new(vm::BlockVerifyResponse {
             timestamp: Some(timestamp_from_time(
@@ -670,6 +874,8 @@ where
         req: Request<vm::BlockAcceptRequest>,
     ) -> std::result::Result<Response<Empty>, tonic::Status> {
         log::debug!("block_accept called");
+        let _guard = self.enter()?;
+        let timer = RpcTimer::start("block_accept");
 
         let req = req.into_inner();
         let id = ids::Id::from_slice(&req.id);
@@ -687,13 +893,15 @@ where
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
 
-        Ok(Response::new(Empty {}))
+        timer.ok(Response::new(Empty {}))
     }
     async fn block_reject(
         &self,
         req: Request<vm::BlockRejectRequest>,
     ) -> std::result::Result<Response<Empty>, tonic::Status> {
         log::debug!("block_reject called");
+        let _guard = self.enter()?;
+        let timer = RpcTimer::start("block_reject");
 
         let req = req.into_inner();
         let id = ids::Id::from_slice(&req.id);
@@ -711,7 +919,7 @@ where
             .await
             .map_err(|e| tonic::Status::unknown(e.to_string()))?;
 
-        Ok(Response::new(Empty {}))
+        timer.ok(Response::new(Empty {}))
     }
 
     async fn get_ancestors(
@@ -926,7 +1134,7 @@ where
         match inner_vm.get_block_id_at_height(msg.height).await {
             Ok(height) => {
                 return Ok(Response::new(vm::GetBlockIdAtHeightResponse {
-                    blk_id: height.to_vec().into(),
+                    blk_id: height.into(),
                     err: 0,
                 }))
             }
@@ -942,3 +1150,323 @@ where
         }
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::rpc::vm::server::test_create_handlers` --exact --show-output
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_create_handlers() {
+    use std::{
+        collections::HashMap,
+        io::{self, Error, ErrorKind},
+    };
+
+    use crate::{
+        choices::status::Status,
+        proto::http::Element,
+        subnet::rpc::{
+            health::Checkable,
+            snow::engine::common::{
+                engine::{AppHandler, CrossChainAppHandler, NetworkAppHandler},
+                http_handler::{HttpHandler, LockOptions},
+                vm::{CommonVm, Connector, Fx},
+            },
+            snowman::block::{BatchedChainVm, Getter, Parser},
+        },
+    };
+    use chrono::DateTime;
+
+    fn stub<T>() -> io::Result<T> {
+        Err(Error::new(ErrorKind::Unsupported, "stub vm"))
+    }
+
+    /// Replies with its name, to tell which handler is served.
+    #[derive(Clone)]
+    struct Reply(&'static str);
+
+    #[tonic::async_trait]
+    impl Handle for Reply {
+        async fn request(
+            &self,
+            _req: &Bytes,
+            _headers: &[Element],
+        ) -> io::Result<(Bytes, Vec<Element>)> {
+            Ok((Bytes::from_static(self.0.as_bytes()), vec![]))
+        }
+    }
+
+    const fn http_handler(name: &'static str) -> HttpHandler<Reply> {
+        HttpHandler {
+            lock_option: LockOptions::NoLock,
+            handler: Reply(name),
+            server_addr: None,
+        }
+    }
+
+    struct StubBlock;
+
+    #[tonic::async_trait]
+    impl Decidable for StubBlock {
+        async fn id(&self) -> ids::Id {
+            ids::Id::empty()
+        }
+        async fn status(&self) -> Status {
+            Status::Unknown(String::new())
+        }
+        async fn accept(&mut self) -> io::Result<()> {
+            stub()
+        }
+        async fn reject(&mut self) -> io::Result<()> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Block for StubBlock {
+        async fn bytes(&self) -> &[u8] {
+            &[]
+        }
+        async fn height(&self) -> u64 {
+            0
+        }
+        async fn timestamp(&self) -> u64 {
+            0
+        }
+        async fn parent(&self) -> ids::Id {
+            ids::Id::empty()
+        }
+        async fn verify(&mut self) -> io::Result<()> {
+            stub()
+        }
+    }
+
+    /// Serves the "/shared" extension with both the static and chain handlers.
+    struct StubVm;
+
+    #[tonic::async_trait]
+    impl NetworkAppHandler for StubVm {
+        async fn app_request(
+            &self,
+            _node_id: &ids::node::Id,
+            _request_id: u32,
+            _deadline: DateTime<Utc>,
+            _request: &[u8],
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn app_request_failed(
+            &self,
+            _node_id: &ids::node::Id,
+            _request_id: u32,
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn app_response(
+            &self,
+            _node_id: &ids::node::Id,
+            _request_id: u32,
+            _response: &[u8],
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn app_gossip(&self, _node_id: &ids::node::Id, _msg: &[u8]) -> io::Result<()> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl CrossChainAppHandler for StubVm {
+        async fn cross_chain_app_request(
+            &self,
+            _chain_id: &ids::Id,
+            _request_id: u32,
+            _deadline: DateTime<Utc>,
+            _request: &[u8],
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn cross_chain_app_request_failed(
+            &self,
+            _chain_id: &ids::Id,
+            _request_id: u32,
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn cross_chain_app_response(
+            &self,
+            _chain_id: &ids::Id,
+            _request_id: u32,
+            _response: &[u8],
+        ) -> io::Result<()> {
+            stub()
+        }
+    }
+
+    impl AppHandler for StubVm {}
+
+    #[tonic::async_trait]
+    impl Connector for StubVm {
+        async fn connected(&self, _id: &ids::node::Id) -> io::Result<()> {
+            stub()
+        }
+        async fn disconnected(&self, _id: &ids::node::Id) -> io::Result<()> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Checkable for StubVm {
+        async fn health_check(&self) -> io::Result<Vec<u8>> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl CommonVm for StubVm {
+        type DatabaseManager = DatabaseManager;
+        type AppSender = AppSenderClient;
+        type ChainHandler = Reply;
+        type StaticHandler = Reply;
+        type ValidatorState = ValidatorStateClient;
+
+        async fn initialize(
+            &mut self,
+            _ctx: Option<Context<Self::ValidatorState>>,
+            _db_manager: BoxedDatabase,
+            _genesis_bytes: &[u8],
+            _upgrade_bytes: &[u8],
+            _config_bytes: &[u8],
+            _to_engine: mpsc::Sender<Message>,
+            _fxs: &[Fx],
+            _app_sender: Self::AppSender,
+        ) -> io::Result<()> {
+            stub()
+        }
+        async fn set_state(&self, _state: State) -> io::Result<()> {
+            stub()
+        }
+        async fn shutdown(&self) -> io::Result<()> {
+            stub()
+        }
+        async fn version(&self) -> io::Result<String> {
+            stub()
+        }
+        async fn create_static_handlers(
+            &mut self,
+        ) -> io::Result<HashMap<String, HttpHandler<Self::StaticHandler>>> {
+            Ok(HashMap::from([
+                ("/shared".to_string(), http_handler("static")),
+                ("/static".to_string(), http_handler("static")),
+            ]))
+        }
+        async fn create_handlers(
+            &mut self,
+        ) -> io::Result<HashMap<String, HttpHandler<Self::ChainHandler>>> {
+            Ok(HashMap::from([
+                ("/shared".to_string(), http_handler("chain")),
+                ("/chain".to_string(), http_handler("chain")),
+            ]))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl BatchedChainVm for StubVm {
+        type Block = StubBlock;
+
+        async fn get_ancestors(
+            &self,
+            _block_id: ids::Id,
+            _max_block_num: i32,
+            _max_block_size: i32,
+            _max_block_retrival_time: Duration,
+        ) -> io::Result<Vec<Bytes>> {
+            stub()
+        }
+        async fn batched_parse_block(&self, _blocks: &[Vec<u8>]) -> io::Result<Vec<StubBlock>> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Getter for StubVm {
+        type Block = StubBlock;
+
+        async fn get_block(&self, _id: ids::Id) -> io::Result<StubBlock> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Parser for StubVm {
+        type Block = StubBlock;
+
+        async fn parse_block(&self, _bytes: &[u8]) -> io::Result<StubBlock> {
+            stub()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl ChainVm for StubVm {
+        type Block = StubBlock;
+
+        async fn build_block(&self) -> io::Result<StubBlock> {
+            stub()
+        }
+        async fn issue_tx(&self) -> io::Result<StubBlock> {
+            stub()
+        }
+        async fn set_preference(&self, _id: ids::Id) -> io::Result<()> {
+            stub()
+        }
+        async fn last_accepted(&self) -> io::Result<ids::Id> {
+            stub()
+        }
+        async fn verify_height_index(&self) -> io::Result<()> {
+            stub()
+        }
+        async fn get_block_id_at_height(&self, _height: u64) -> io::Result<ids::Id> {
+            stub()
+        }
+        async fn state_sync_enabled(&self) -> io::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    let (stop_ch, _) = broadcast::channel(1);
+    let server = Server::new(StubVm, stop_ch.clone());
+    let resp = server
+        .create_handlers(Request::new(Empty {}))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // the static handler of the same extension is skipped
+    let mut prefixes: Vec<&str> = resp.handlers.iter().map(|h| h.prefix.as_str()).collect();
+    prefixes.sort_unstable();
+    assert_eq!(prefixes, vec!["/chain", "/shared", "/static"]);
+
+    // each extension is served by the expected handler
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    for (prefix, expected) in [
+        ("/shared", "chain"),
+        ("/chain", "chain"),
+        ("/static", "static"),
+    ] {
+        let handler = resp.handlers.iter().find(|h| h.prefix == prefix).unwrap();
+        let mut client =
+            pb::http::http_client::HttpClient::connect(format!("http://{}", handler.server_addr))
+                .await
+                .unwrap();
+        let reply = client
+            .handle_simple(pb::http::HandleSimpleHttpRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            reply.body,
+            Bytes::from_static(expected.as_bytes()),
+            "{prefix}"
+        );
+    }
+
+    let _ = stop_ch.send(());
+}
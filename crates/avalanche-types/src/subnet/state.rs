@@ -0,0 +1,183 @@
+//! Block storage of the VMs, keyed by block ID and accepted height.
+use std::io::{self, Error, ErrorKind};
+
+use crate::{
+    ids,
+    subnet::rpc::{database::BoxedDatabase, errors},
+};
+
+/// Prefix of the keys of the block bytes, keyed by block ID.
+const BLOCK_PREFIX: u8 = b'b';
+/// Prefix of the keys of the accepted block IDs, keyed by height.
+const HEIGHT_PREFIX: u8 = b'h';
+/// Key of the last accepted block ID.
+const LAST_ACCEPTED_KEY: &[u8] = b"last_accepted";
+
+/// Persists the blocks by ID, and indexes the accepted blocks by height.
+/// The index and the last accepted block are updated in the same batch
+/// as the accepted block, so they are never out of sync.
+#[derive(Clone)]
+pub struct BlockStore {
+    db: BoxedDatabase,
+}
+
+impl BlockStore {
+    #[must_use]
+    pub fn new(db: BoxedDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Stores the block bytes (e.g., once verified), without indexing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to write.
+    pub async fn put_block(&self, id: &ids::Id, bytes: &[u8]) -> io::Result<()> {
+        let mut batch = self.db.new_batch().await?;
+        batch.put(&block_key(id), bytes).await?;
+        batch.write().await
+    }
+
+    /// Returns the block bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns "`errors::Error::NotFound`" if the block is not stored.
+    pub async fn get_block(&self, id: &ids::Id) -> io::Result<Vec<u8>> {
+        self.db.get(&block_key(id)).await
+    }
+
+    /// Returns "true" if the block is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn has_block(&self, id: &ids::Id) -> io::Result<bool> {
+        self.db.has(&block_key(id)).await
+    }
+
+    /// Deletes the block (e.g., once rejected).
+    /// Accepted blocks stay indexed by height.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to write.
+    pub async fn delete_block(&self, id: &ids::Id) -> io::Result<()> {
+        let mut batch = self.db.new_batch().await?;
+        batch.delete(&block_key(id)).await?;
+        batch.write().await
+    }
+
+    /// Stores the accepted block, indexes it at the height,
+    /// and sets it as the last accepted block, atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if another block is already accepted at the height,
+    /// or the database fails to read or write.
+    pub async fn accept(&self, id: &ids::Id, height: u64, bytes: &[u8]) -> io::Result<()> {
+        match self.get_block_id_at_height(height).await {
+            Ok(accepted) if accepted != *id => {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("block {accepted} already accepted at height {height}, not {id}"),
+                ));
+            }
+            Ok(_) => {}
+            Err(e) if errors::is_not_found(&e) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut batch = self.db.new_batch().await?;
+        batch.put(&block_key(id), bytes).await?;
+        batch.put(&height_key(height), id.as_ref()).await?;
+        batch.put(LAST_ACCEPTED_KEY, id.as_ref()).await?;
+        batch.write().await
+    }
+
+    /// Returns the ID of the block accepted at the height.
+    /// Backs "`ChainVm::get_block_id_at_height`".
+    ///
+    /// # Errors
+    ///
+    /// Returns "`errors::Error::NotFound`" if no block is accepted at the height.
+    pub async fn get_block_id_at_height(&self, height: u64) -> io::Result<ids::Id> {
+        let b = self.db.get(&height_key(height)).await?;
+        to_id(&b)
+    }
+
+    /// Returns the ID of the last accepted block,
+    /// or "None" if no block is accepted yet (e.g., before the genesis is stored).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to read.
+    pub async fn last_accepted(&self) -> io::Result<Option<ids::Id>> {
+        match self.db.get(LAST_ACCEPTED_KEY).await {
+            Ok(b) => Ok(Some(to_id(&b)?)),
+            Err(e) if errors::is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn block_key(id: &ids::Id) -> Vec<u8> {
+    let mut k = Vec::with_capacity(1 + ids::LEN);
+    k.push(BLOCK_PREFIX);
+    k.extend_from_slice(id.as_ref());
+    k
+}
+
+/// Big-endian, so the heights are ordered when iterating the keys.
+fn height_key(height: u64) -> Vec<u8> {
+    let mut k = Vec::with_capacity(1 + 8);
+    k.push(HEIGHT_PREFIX);
+    k.extend_from_slice(&height.to_be_bytes());
+    k
+}
+
+fn to_id(b: &[u8]) -> io::Result<ids::Id> {
+    if b.len() != ids::LEN {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("invalid block ID length {}", b.len()),
+        ));
+    }
+    Ok(ids::Id::from_slice(b))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features subnet -- `subnet::state::test_block_store` --exact --show-output
+#[tokio::test]
+async fn test_block_store() {
+    use crate::subnet::rpc::database::memdb;
+
+    let store = BlockStore::new(memdb::Database::new_boxed());
+    assert!(store.last_accepted().await.unwrap().is_none());
+
+    let genesis = ids::Id::from_slice(&[1; 32]);
+    store.accept(&genesis, 0, b"genesis").await.unwrap();
+    assert_eq!(store.last_accepted().await.unwrap(), Some(genesis));
+    assert_eq!(store.get_block_id_at_height(0).await.unwrap(), genesis);
+    // idempotent
+    store.accept(&genesis, 0, b"genesis").await.unwrap();
+
+    let (a, b) = (ids::Id::from_slice(&[2; 32]), ids::Id::from_slice(&[3; 32]));
+    store.put_block(&a, b"a").await.unwrap();
+    store.put_block(&b, b"b").await.unwrap();
+    assert!(store.has_block(&a).await.unwrap());
+    assert!(errors::is_not_found(
+        &store.get_block_id_at_height(1).await.unwrap_err()
+    ));
+
+    store.accept(&a, 1, b"a").await.unwrap();
+    assert_eq!(
+        store.accept(&b, 1, b"b").await.unwrap_err().kind(),
+        ErrorKind::AlreadyExists
+    );
+    store.delete_block(&b).await.unwrap();
+    assert!(!store.has_block(&b).await.unwrap());
+
+    assert_eq!(store.get_block(&a).await.unwrap(), b"a");
+    assert_eq!(store.get_block_id_at_height(1).await.unwrap(), a);
+    assert_eq!(store.last_accepted().await.unwrap(), Some(a));
+}
@@ -1,11 +1,13 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     fs::{self, File},
     io::{self, Error, ErrorKind, Write},
     path::Path,
+    str::FromStr,
 };
 
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/core#Genesis>
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#ChainConfig>
@@ -148,6 +150,29 @@ impl Genesis {
         Ok(subnet_evm_genesis)
     }
 
+    /// Validates the chain config and its precompile configs,
+    /// so that subnet-evm does not reject the genesis after the chain is created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any config is invalid.
+    pub fn validate(&self) -> io::Result<()> {
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        config.validate()?;
+
+        if let Some(gas_limit) = config.fee_config.as_ref().and_then(|c| c.gas_limit) {
+            if primitive_types::U256::from(gas_limit) != self.gas_limit {
+                return Err(invalid(format!(
+                    "genesis gas limit {} does not match fee config gas limit {gas_limit}",
+                    self.gas_limit
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Encodes the genesis to JSON.
     ///
     /// # Errors
@@ -243,6 +268,12 @@ pub struct ChainConfig {
     /// ref. <https://docs.avax.network/subnets/customize-a-subnet>
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_manager_config: Option<FeeManagerConfig>,
+    /// ref. <https://docs.avax.network/subnets/customize-a-subnet>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reward_manager_config: Option<RewardManagerConfig>,
+    /// ref. <https://docs.avax.network/build/cross-chain/awm/overview>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warp_config: Option<WarpConfig>,
 }
 
 impl Default for ChainConfig {
@@ -279,10 +310,44 @@ impl Default for ChainConfig {
             contract_native_minter_config: None,
             tx_allow_list_config: None,
             fee_manager_config: None,
+            reward_manager_config: None,
+            warp_config: None,
         }
     }
 }
 
+impl ChainConfig {
+    /// Validates the fee config and the precompile configs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any config is invalid.
+    pub fn validate(&self) -> io::Result<()> {
+        if let Some(c) = &self.fee_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.contract_deployer_allow_list_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.contract_native_minter_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.tx_allow_list_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.fee_manager_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.reward_manager_config {
+            c.validate()?;
+        }
+        if let Some(c) = &self.warp_config {
+            c.validate()?;
+        }
+        Ok(())
+    }
+}
+
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/commontype#FeeConfig>
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#pkg-variables>
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
@@ -340,6 +405,43 @@ impl Default for FeeConfig {
     }
 }
 
+impl FeeConfig {
+    /// Validates the fee config, where all fields must be set.
+    /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/commontype#FeeConfig.Verify>
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any field is missing or out of range.
+    pub fn validate(&self) -> io::Result<()> {
+        let required = |name: &str, v: Option<u64>| {
+            v.ok_or_else(|| invalid(format!("fee config '{name}' is not set")))
+        };
+        let positive = |name: &str, v: Option<u64>| {
+            let v = required(name, v)?;
+            if v == 0 {
+                return Err(invalid(format!("fee config '{name}' must be positive")));
+            }
+            Ok(v)
+        };
+
+        positive("gasLimit", self.gas_limit)?;
+        positive("targetBlockRate", self.target_block_rate)?;
+        required("minBaseFee", self.min_base_fee)?;
+        positive("targetGas", self.target_gas)?;
+        positive("baseFeeChangeDenominator", self.base_fee_change_denominator)?;
+        required("blockGasCostStep", self.block_gas_cost_step)?;
+
+        let min_block_gas_cost = required("minBlockGasCost", self.min_block_gas_cost)?;
+        let max_block_gas_cost = required("maxBlockGasCost", self.max_block_gas_cost)?;
+        if min_block_gas_cost > max_block_gas_cost {
+            return Err(invalid(format!(
+                "fee config 'minBlockGasCost' {min_block_gas_cost} exceeds 'maxBlockGasCost' {max_block_gas_cost}"
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Contract deployer allow list configuration.
 ///
 /// ref. <https://github.com/ava-labs/subnet-evm/blob/master/precompile/contract_deployer_allow_list.go>
@@ -350,6 +452,12 @@ impl Default for FeeConfig {
 pub struct ContractDeployerAllowListConfig {
     #[serde(rename = "adminAddresses", skip_serializing_if = "Option::is_none")]
     pub allow_list_admins: Option<Vec<String>>,
+    /// Addresses that can enable/disable the other addresses, but not the admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_addresses: Option<Vec<String>>,
+    /// Addresses that are allowed to use the precompile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_addresses: Option<Vec<String>>,
 
     /// Timestamp for the upgrade.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -363,6 +471,8 @@ impl Default for ContractDeployerAllowListConfig {
     fn default() -> Self {
         Self {
             allow_list_admins: None,
+            manager_addresses: None,
+            enabled_addresses: None,
             block_timestamp: Some(0),
             disable: None,
         }
@@ -374,11 +484,25 @@ impl Default for ContractDeployerAllowListConfig {
 /// ref. <https://github.com/ava-labs/subnet-evm/blob/master/precompile/contract_native_minter.go>
 /// ref. <https://github.com/ava-labs/subnet-evm/blob/master/precompile/upgradeable.go>
 /// ref. <https://github.com/ava-labs/subnet-evm/blob/master/params/precompile_config.go>
+#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractNativeMinterConfig {
     #[serde(rename = "adminAddresses", skip_serializing_if = "Option::is_none")]
     pub allow_list_admins: Option<Vec<String>>,
+    /// Addresses that can enable/disable the other addresses, but not the admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_addresses: Option<Vec<String>>,
+    /// Addresses that are allowed to use the precompile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_addresses: Option<Vec<String>>,
+
+    /// Amounts minted to the addresses when the precompile activates.
+    #[serde_as(
+        as = "Option<BTreeMap<_, crate::codec::serde::hex_0x_primitive_types_u256::Hex0xU256>>"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_mint: Option<BTreeMap<String, primitive_types::U256>>,
 
     /// Timestamp for the upgrade.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -393,6 +517,9 @@ impl Default for ContractNativeMinterConfig {
     fn default() -> Self {
         Self {
             allow_list_admins: None,
+            manager_addresses: None,
+            enabled_addresses: None,
+            initial_mint: None,
             block_timestamp: Some(0),
             disable: None,
         }
@@ -409,6 +536,12 @@ impl Default for ContractNativeMinterConfig {
 pub struct TxAllowListConfig {
     #[serde(rename = "adminAddresses", skip_serializing_if = "Option::is_none")]
     pub allow_list_admins: Option<Vec<String>>,
+    /// Addresses that can enable/disable the other addresses, but not the admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_addresses: Option<Vec<String>>,
+    /// Addresses that are allowed to use the precompile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_addresses: Option<Vec<String>>,
 
     /// Timestamp for the upgrade.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -422,6 +555,8 @@ impl Default for TxAllowListConfig {
     fn default() -> Self {
         Self {
             allow_list_admins: None,
+            manager_addresses: None,
+            enabled_addresses: None,
             block_timestamp: Some(0),
             disable: None,
         }
@@ -438,6 +573,17 @@ impl Default for TxAllowListConfig {
 pub struct FeeManagerConfig {
     #[serde(rename = "adminAddresses", skip_serializing_if = "Option::is_none")]
     pub allow_list_admins: Option<Vec<String>>,
+    /// Addresses that can enable/disable the other addresses, but not the admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_addresses: Option<Vec<String>>,
+    /// Addresses that are allowed to use the precompile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_addresses: Option<Vec<String>>,
+
+    /// Fee config set when the precompile activates,
+    /// overriding the fee config of the chain config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_fee_config: Option<FeeConfig>,
 
     /// Timestamp for the upgrade.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -451,12 +597,270 @@ impl Default for FeeManagerConfig {
     fn default() -> Self {
         Self {
             allow_list_admins: None,
+            manager_addresses: None,
+            enabled_addresses: None,
+            initial_fee_config: None,
+            block_timestamp: Some(0),
+            disable: None,
+        }
+    }
+}
+
+/// Reward manager configuration.
+///
+/// ref. <https://github.com/ava-labs/subnet-evm/blob/master/precompile/contracts/rewardmanager/config.go>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardManagerConfig {
+    #[serde(rename = "adminAddresses", skip_serializing_if = "Option::is_none")]
+    pub allow_list_admins: Option<Vec<String>>,
+    /// Addresses that can enable/disable the other addresses, but not the admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager_addresses: Option<Vec<String>>,
+    /// Addresses that are allowed to use the precompile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_addresses: Option<Vec<String>>,
+
+    /// Reward config set when the precompile activates.
+    /// If none, the fees are burned (or sent to the coinbase if "`allow_fee_recipients`"
+    /// is set in the chain config).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_reward_config: Option<InitialRewardConfig>,
+
+    /// Timestamp for the upgrade.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_timestamp: Option<u64>,
+    /// Set to "true" for the upgrade to deactivate the precompile and reset its storage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable: Option<bool>,
+}
+
+impl Default for RewardManagerConfig {
+    fn default() -> Self {
+        Self {
+            allow_list_admins: None,
+            manager_addresses: None,
+            enabled_addresses: None,
+            initial_reward_config: None,
+            block_timestamp: Some(0),
+            disable: None,
+        }
+    }
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/precompile/contracts/rewardmanager#InitialRewardConfig>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitialRewardConfig {
+    /// Set to "true" to send the fees to the block producer's fee recipient.
+    #[serde(default)]
+    pub allow_fee_recipients: bool,
+    /// Address that receives all fees, mutually exclusive with "`allow_fee_recipients`".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reward_address: Option<String>,
+}
+
+/// Warp messaging configuration.
+///
+/// ref. <https://github.com/ava-labs/subnet-evm/blob/master/precompile/contracts/warp/config.go>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WarpConfig {
+    /// Percentage of the stake weight that must sign a warp message,
+    /// within [67, 100]. Zero uses the default (67).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_numerator: Option<u64>,
+    /// Set to "true" to require the primary network validators to sign
+    /// the messages from the primary network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_primary_network_signers: Option<bool>,
+
+    /// Timestamp for the upgrade.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_timestamp: Option<u64>,
+    /// Set to "true" for the upgrade to deactivate the precompile and reset its storage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable: Option<bool>,
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/precompile/contracts/warp#pkg-constants>
+pub const WARP_QUORUM_NUMERATOR_MIN: u64 = 67;
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/precompile/contracts/warp#pkg-constants>
+pub const WARP_QUORUM_DENOMINATOR: u64 = 100;
+
+impl Default for WarpConfig {
+    fn default() -> Self {
+        Self {
+            quorum_numerator: None,
+            require_primary_network_signers: None,
             block_timestamp: Some(0),
             disable: None,
         }
     }
 }
 
+impl ContractDeployerAllowListConfig {
+    /// Validates the addresses of the allow list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any address is invalid or has more than one role.
+    pub fn validate(&self) -> io::Result<()> {
+        validate_allow_list(
+            "contractDeployerAllowListConfig",
+            self.allow_list_admins.as_deref(),
+            self.manager_addresses.as_deref(),
+            self.enabled_addresses.as_deref(),
+        )
+    }
+}
+
+impl ContractNativeMinterConfig {
+    /// Validates the addresses of the allow list and the initial mint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any address is invalid or has more than one role,
+    /// or if any initial mint amount is zero.
+    pub fn validate(&self) -> io::Result<()> {
+        validate_allow_list(
+            "contractNativeMinterConfig",
+            self.allow_list_admins.as_deref(),
+            self.manager_addresses.as_deref(),
+            self.enabled_addresses.as_deref(),
+        )?;
+        for (addr, amount) in self.initial_mint.iter().flatten() {
+            validate_address("contractNativeMinterConfig", addr)?;
+            if amount.is_zero() {
+                return Err(invalid(format!(
+                    "contractNativeMinterConfig initial mint to '{addr}' must be positive"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TxAllowListConfig {
+    /// Validates the addresses of the allow list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any address is invalid or has more than one role.
+    pub fn validate(&self) -> io::Result<()> {
+        validate_allow_list(
+            "txAllowListConfig",
+            self.allow_list_admins.as_deref(),
+            self.manager_addresses.as_deref(),
+            self.enabled_addresses.as_deref(),
+        )
+    }
+}
+
+impl FeeManagerConfig {
+    /// Validates the addresses of the allow list and the initial fee config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any address is invalid or has more than one role,
+    /// or if the initial fee config is invalid.
+    pub fn validate(&self) -> io::Result<()> {
+        validate_allow_list(
+            "feeManagerConfig",
+            self.allow_list_admins.as_deref(),
+            self.manager_addresses.as_deref(),
+            self.enabled_addresses.as_deref(),
+        )?;
+        if let Some(c) = &self.initial_fee_config {
+            c.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl RewardManagerConfig {
+    /// Validates the addresses of the allow list and the initial reward config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any address is invalid or has more than one role,
+    /// or if the initial reward config enables both fee recipients and a reward address.
+    pub fn validate(&self) -> io::Result<()> {
+        validate_allow_list(
+            "rewardManagerConfig",
+            self.allow_list_admins.as_deref(),
+            self.manager_addresses.as_deref(),
+            self.enabled_addresses.as_deref(),
+        )?;
+        if let Some(c) = &self.initial_reward_config {
+            if let Some(addr) = &c.reward_address {
+                validate_address("rewardManagerConfig", addr)?;
+                if c.allow_fee_recipients {
+                    return Err(invalid(
+                        "rewardManagerConfig cannot enable both fee recipients and reward address"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WarpConfig {
+    /// Validates the quorum numerator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quorum numerator is out of range.
+    pub fn validate(&self) -> io::Result<()> {
+        match self.quorum_numerator {
+            Some(n)
+                if n != 0 && !(WARP_QUORUM_NUMERATOR_MIN..=WARP_QUORUM_DENOMINATOR).contains(&n) =>
+            {
+                Err(invalid(format!(
+                    "warpConfig quorum numerator {n} must be within [{WARP_QUORUM_NUMERATOR_MIN}, {WARP_QUORUM_DENOMINATOR}]"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Validates the allow list addresses, where each address can only have one role.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/precompile/allowlist#AllowListConfig.Verify>
+fn validate_allow_list(
+    name: &str,
+    admins: Option<&[String]>,
+    managers: Option<&[String]>,
+    enabled: Option<&[String]>,
+) -> io::Result<()> {
+    let mut seen = HashSet::new();
+    for addr in [admins, managers, enabled].into_iter().flatten().flatten() {
+        validate_address(name, addr)?;
+        if !seen.insert(addr.trim_start_matches("0x").to_lowercase()) {
+            return Err(invalid(format!(
+                "{name} address '{addr}' is duplicated or has more than one role"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates the hex-encoded Ethereum address.
+fn validate_address(name: &str, addr: &str) -> io::Result<()> {
+    let hex = addr.trim_start_matches("0x");
+    if hex.len() != 40 || primitive_types::H160::from_str(hex).is_err() {
+        return Err(invalid(format!("{name} has invalid address '{addr}'")));
+    }
+    Ok(())
+}
+
+/// Returns the error for an invalid config.
+pub(super) fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/core#GenesisAlloc>
 /// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/core#GenesisAccount>
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
@@ -552,3 +956,124 @@ fn test_parse() {
     let d = d.encode_json().unwrap();
     log::info!("{d}");
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- subnet_evm::genesis::test_precompile_configs --exact --show-output
+#[test]
+fn test_precompile_configs() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let admin = String::from("0x8db97C7cEcE249c2b98bDC0226Cc4C2A57BF52FC");
+    let enabled = String::from("0x6f0f6DA1852857d7789f68a28bba866671f3880D");
+
+    let mut genesis = Genesis::new(&[enabled.clone()]).unwrap();
+    let config = genesis.config.as_mut().unwrap();
+    config.tx_allow_list_config = Some(TxAllowListConfig {
+        allow_list_admins: Some(vec![admin.clone()]),
+        enabled_addresses: Some(vec![enabled.clone()]),
+        ..Default::default()
+    });
+    config.contract_native_minter_config = Some(ContractNativeMinterConfig {
+        allow_list_admins: Some(vec![admin.clone()]),
+        initial_mint: Some(BTreeMap::from([(
+            enabled.clone(),
+            primitive_types::U256::from(1_000_000_u64),
+        )])),
+        ..Default::default()
+    });
+    config.fee_manager_config = Some(FeeManagerConfig {
+        allow_list_admins: Some(vec![admin.clone()]),
+        initial_fee_config: Some(FeeConfig::default()),
+        ..Default::default()
+    });
+    config.reward_manager_config = Some(RewardManagerConfig {
+        allow_list_admins: Some(vec![admin.clone()]),
+        initial_reward_config: Some(InitialRewardConfig {
+            allow_fee_recipients: false,
+            reward_address: Some(enabled),
+        }),
+        ..Default::default()
+    });
+    config.warp_config = Some(WarpConfig {
+        quorum_numerator: Some(67),
+        ..Default::default()
+    });
+    genesis.validate().unwrap();
+
+    // the genesis data of "CreateChainTx" round-trips
+    let genesis_data = genesis.to_bytes().unwrap();
+    let decoded: Genesis = serde_json::from_slice(&genesis_data).unwrap();
+    assert_eq!(decoded, genesis);
+
+    let d = String::from_utf8(genesis_data).unwrap();
+    log::info!("{d}");
+    assert!(d.contains(r#""txAllowListConfig":{"adminAddresses":["0x8db97C7cEcE249c2b98bDC0226Cc4C2A57BF52FC"],"enabledAddresses":["0x6f0f6DA1852857d7789f68a28bba866671f3880D"],"blockTimestamp":0}"#));
+    assert!(d.contains(r#""initialMint":{"0x6f0f6DA1852857d7789f68a28bba866671f3880D":"0xf4240"}"#));
+    assert!(d.contains(r#""warpConfig":{"quorumNumerator":67,"blockTimestamp":0}"#));
+
+    // address with more than one role
+    let mut invalid_genesis = genesis.clone();
+    invalid_genesis
+        .config
+        .as_mut()
+        .unwrap()
+        .tx_allow_list_config
+        .as_mut()
+        .unwrap()
+        .manager_addresses = Some(vec![admin.to_lowercase()]);
+    assert!(invalid_genesis.validate().is_err());
+
+    // malformed address
+    let mut invalid_genesis = genesis.clone();
+    invalid_genesis
+        .config
+        .as_mut()
+        .unwrap()
+        .tx_allow_list_config
+        .as_mut()
+        .unwrap()
+        .enabled_addresses = Some(vec![String::from("0x1234")]);
+    assert!(invalid_genesis.validate().is_err());
+
+    // both fee recipients and reward address
+    let mut invalid_genesis = genesis.clone();
+    invalid_genesis
+        .config
+        .as_mut()
+        .unwrap()
+        .reward_manager_config
+        .as_mut()
+        .unwrap()
+        .initial_reward_config
+        .as_mut()
+        .unwrap()
+        .allow_fee_recipients = true;
+    assert!(invalid_genesis.validate().is_err());
+
+    // warp quorum below the minimum
+    let mut invalid_genesis = genesis.clone();
+    invalid_genesis.config.as_mut().unwrap().warp_config = Some(WarpConfig {
+        quorum_numerator: Some(50),
+        ..Default::default()
+    });
+    assert!(invalid_genesis.validate().is_err());
+
+    // incomplete initial fee config
+    let mut invalid_genesis = genesis.clone();
+    invalid_genesis
+        .config
+        .as_mut()
+        .unwrap()
+        .fee_manager_config
+        .as_mut()
+        .unwrap()
+        .initial_fee_config = Some(FeeConfig {
+        target_gas: None,
+        ..Default::default()
+    });
+    assert!(invalid_genesis.validate().is_err());
+
+    // gas limit mismatch
+    let mut invalid_genesis = genesis;
+    invalid_genesis.gas_limit = primitive_types::U256::from(1);
+    assert!(invalid_genesis.validate().is_err());
+}
@@ -1,3 +1,4 @@
 //! Subnet-EVM configuration.
 pub mod chain_config;
 pub mod genesis;
+pub mod upgrade;
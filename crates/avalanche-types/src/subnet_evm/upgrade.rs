@@ -0,0 +1,562 @@
+//! Subnet-EVM network upgrade file ("upgrade.json").
+//!
+//! ref. <https://docs.avax.network/subnets/upgrade/customize-a-subnet#network-upgrades-enabledisable-precompiles>
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Error, ErrorKind, Write},
+    path::Path,
+};
+
+use super::genesis::{
+    invalid, ChainConfig, ContractDeployerAllowListConfig, ContractNativeMinterConfig,
+    FeeManagerConfig, RewardManagerConfig, TxAllowListConfig, WarpConfig,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Represents the "upgrade.json" in the subnet-evm chain config directory
+/// (e.g., "{chain-config-dir}/{blockchain-id}/upgrade.json").
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#UpgradeConfig>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Upgrade {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precompile_upgrades: Option<Vec<PrecompileUpgrade>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_upgrades: Option<Vec<StateUpgrade>>,
+}
+
+/// Represents the precompiles that can be activated in the network upgrades.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Precompile {
+    ContractDeployerAllowList,
+    ContractNativeMinter,
+    TxAllowList,
+    FeeManager,
+    RewardManager,
+    Warp,
+}
+
+impl Precompile {
+    /// Returns the config key of the precompile in the genesis and the upgrade file.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ContractDeployerAllowList => "contractDeployerAllowListConfig",
+            Self::ContractNativeMinter => "contractNativeMinterConfig",
+            Self::TxAllowList => "txAllowListConfig",
+            Self::FeeManager => "feeManagerConfig",
+            Self::RewardManager => "rewardManagerConfig",
+            Self::Warp => "warpConfig",
+        }
+    }
+
+    /// Returns the upgrade that deactivates the precompile and resets its storage
+    /// at "`block_timestamp`".
+    #[must_use]
+    pub fn disable_at(&self, block_timestamp: u64) -> PrecompileUpgrade {
+        match self {
+            Self::ContractDeployerAllowList => PrecompileUpgrade::ContractDeployerAllowListConfig(
+                ContractDeployerAllowListConfig {
+                    block_timestamp: Some(block_timestamp),
+                    disable: Some(true),
+                    ..Default::default()
+                },
+            ),
+            Self::ContractNativeMinter => {
+                PrecompileUpgrade::ContractNativeMinterConfig(ContractNativeMinterConfig {
+                    block_timestamp: Some(block_timestamp),
+                    disable: Some(true),
+                    ..Default::default()
+                })
+            }
+            Self::TxAllowList => PrecompileUpgrade::TxAllowListConfig(TxAllowListConfig {
+                block_timestamp: Some(block_timestamp),
+                disable: Some(true),
+                ..Default::default()
+            }),
+            Self::FeeManager => PrecompileUpgrade::FeeManagerConfig(FeeManagerConfig {
+                block_timestamp: Some(block_timestamp),
+                disable: Some(true),
+                ..Default::default()
+            }),
+            Self::RewardManager => PrecompileUpgrade::RewardManagerConfig(RewardManagerConfig {
+                block_timestamp: Some(block_timestamp),
+                disable: Some(true),
+                ..Default::default()
+            }),
+            Self::Warp => PrecompileUpgrade::WarpConfig(WarpConfig {
+                block_timestamp: Some(block_timestamp),
+                disable: Some(true),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the precompiles that are active at genesis.
+    fn activated_in(genesis: &ChainConfig) -> Vec<Self> {
+        let enabled = |disable: Option<Option<bool>>| disable.is_some_and(|d| d != Some(true));
+        [
+            (
+                Self::ContractDeployerAllowList,
+                enabled(
+                    genesis
+                        .contract_deployer_allow_list_config
+                        .as_ref()
+                        .map(|c| c.disable),
+                ),
+            ),
+            (
+                Self::ContractNativeMinter,
+                enabled(
+                    genesis
+                        .contract_native_minter_config
+                        .as_ref()
+                        .map(|c| c.disable),
+                ),
+            ),
+            (
+                Self::TxAllowList,
+                enabled(genesis.tx_allow_list_config.as_ref().map(|c| c.disable)),
+            ),
+            (
+                Self::FeeManager,
+                enabled(genesis.fee_manager_config.as_ref().map(|c| c.disable)),
+            ),
+            (
+                Self::RewardManager,
+                enabled(genesis.reward_manager_config.as_ref().map(|c| c.disable)),
+            ),
+            (
+                Self::Warp,
+                enabled(genesis.warp_config.as_ref().map(|c| c.disable)),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(p, enabled)| enabled.then_some(p))
+        .collect()
+    }
+}
+
+/// Represents a precompile activation or deactivation, encoded as a single-key
+/// object (e.g., `{"txAllowListConfig": {"blockTimestamp": 1668950000, ...}}`).
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#PrecompileUpgrade>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum PrecompileUpgrade {
+    ContractDeployerAllowListConfig(ContractDeployerAllowListConfig),
+    ContractNativeMinterConfig(ContractNativeMinterConfig),
+    TxAllowListConfig(TxAllowListConfig),
+    FeeManagerConfig(FeeManagerConfig),
+    RewardManagerConfig(RewardManagerConfig),
+    WarpConfig(WarpConfig),
+}
+
+impl PrecompileUpgrade {
+    #[must_use]
+    pub const fn precompile(&self) -> Precompile {
+        match self {
+            Self::ContractDeployerAllowListConfig(_) => Precompile::ContractDeployerAllowList,
+            Self::ContractNativeMinterConfig(_) => Precompile::ContractNativeMinter,
+            Self::TxAllowListConfig(_) => Precompile::TxAllowList,
+            Self::FeeManagerConfig(_) => Precompile::FeeManager,
+            Self::RewardManagerConfig(_) => Precompile::RewardManager,
+            Self::WarpConfig(_) => Precompile::Warp,
+        }
+    }
+
+    #[must_use]
+    pub const fn block_timestamp(&self) -> Option<u64> {
+        match self {
+            Self::ContractDeployerAllowListConfig(c) => c.block_timestamp,
+            Self::ContractNativeMinterConfig(c) => c.block_timestamp,
+            Self::TxAllowListConfig(c) => c.block_timestamp,
+            Self::FeeManagerConfig(c) => c.block_timestamp,
+            Self::RewardManagerConfig(c) => c.block_timestamp,
+            Self::WarpConfig(c) => c.block_timestamp,
+        }
+    }
+
+    /// Returns "true" if the upgrade deactivates the precompile.
+    #[must_use]
+    pub fn is_disable(&self) -> bool {
+        let disable = match self {
+            Self::ContractDeployerAllowListConfig(c) => c.disable,
+            Self::ContractNativeMinterConfig(c) => c.disable,
+            Self::TxAllowListConfig(c) => c.disable,
+            Self::FeeManagerConfig(c) => c.disable,
+            Self::RewardManagerConfig(c) => c.disable,
+            Self::WarpConfig(c) => c.disable,
+        };
+        disable == Some(true)
+    }
+
+    /// Validates the precompile config, skipped for deactivations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is invalid.
+    pub fn validate(&self) -> io::Result<()> {
+        if self.is_disable() {
+            return Ok(());
+        }
+        match self {
+            Self::ContractDeployerAllowListConfig(c) => c.validate(),
+            Self::ContractNativeMinterConfig(c) => c.validate(),
+            Self::TxAllowListConfig(c) => c.validate(),
+            Self::FeeManagerConfig(c) => c.validate(),
+            Self::RewardManagerConfig(c) => c.validate(),
+            Self::WarpConfig(c) => c.validate(),
+        }
+    }
+}
+
+/// Represents the state modifications at the upgrade timestamp.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#StateUpgrade>
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StateUpgrade {
+    pub block_timestamp: u64,
+    /// MUST BE ordered by its key in order for all nodes to have the same JSON outputs.
+    pub accounts: BTreeMap<String, StateUpgradeAccount>,
+}
+
+/// ref. <https://pkg.go.dev/github.com/ava-labs/subnet-evm/params#StateUpgradeAccount>
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StateUpgradeAccount {
+    /// Hex-encoded bytecode to replace the account code with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<String, String>>,
+    /// Amount added to the account balance.
+    #[serde_as(as = "Option<crate::codec::serde::hex_0x_primitive_types_u256::Hex0xU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_change: Option<primitive_types::U256>,
+}
+
+impl Upgrade {
+    /// Validates the precompile upgrades, where the precompiles active at
+    /// genesis are taken from "`genesis`" if any.
+    /// The upgrades must be ordered by their timestamps, and each precompile
+    /// must alternate between activations and deactivations.
+    /// ref. <https://github.com/ava-labs/subnet-evm/blob/master/params/precompile_upgrade.go>
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any upgrade is invalid or out of order.
+    pub fn validate(&self, genesis: Option<&ChainConfig>) -> io::Result<()> {
+        let mut active = genesis.map(Precompile::activated_in).unwrap_or_default();
+        let mut last_timestamps: BTreeMap<Precompile, u64> = BTreeMap::new();
+        let mut last_timestamp = 0;
+
+        for (i, upgrade) in self.precompile_upgrades.iter().flatten().enumerate() {
+            let precompile = upgrade.precompile();
+            let Some(timestamp) = upgrade.block_timestamp() else {
+                return Err(invalid(format!(
+                    "precompile upgrade {i} ({}) has no block timestamp",
+                    precompile.as_str()
+                )));
+            };
+            if timestamp < last_timestamp {
+                return Err(invalid(format!(
+                    "precompile upgrade {i} ({}) timestamp {timestamp} is before the previous upgrade timestamp {last_timestamp}",
+                    precompile.as_str()
+                )));
+            }
+            if let Some(prev) = last_timestamps.get(&precompile) {
+                if timestamp <= *prev {
+                    return Err(invalid(format!(
+                        "precompile upgrade {i} ({}) timestamp {timestamp} is not after its previous upgrade timestamp {prev}",
+                        precompile.as_str()
+                    )));
+                }
+            }
+
+            let is_active = active.contains(&precompile);
+            if upgrade.is_disable() {
+                if !is_active {
+                    return Err(invalid(format!(
+                        "precompile upgrade {i} disables {} which is not active",
+                        precompile.as_str()
+                    )));
+                }
+                active.retain(|p| *p != precompile);
+            } else {
+                if is_active {
+                    return Err(invalid(format!(
+                        "precompile upgrade {i} enables {} which is already active",
+                        precompile.as_str()
+                    )));
+                }
+                upgrade.validate()?;
+                active.push(precompile);
+            }
+
+            last_timestamps.insert(precompile, timestamp);
+            last_timestamp = timestamp;
+        }
+
+        let mut last_timestamp = 0;
+        for (i, upgrade) in self.state_upgrades.iter().flatten().enumerate() {
+            if upgrade.block_timestamp <= last_timestamp {
+                return Err(invalid(format!(
+                    "state upgrade {i} timestamp {} must be after the previous upgrade timestamp {last_timestamp}",
+                    upgrade.block_timestamp
+                )));
+            }
+            last_timestamp = upgrade.block_timestamp;
+        }
+        Ok(())
+    }
+
+    /// Encodes the upgrade to JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding fails.
+    pub fn encode_json(&self) -> io::Result<String> {
+        serde_json::to_string(&self)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize JSON {e}")))
+    }
+
+    /// Loads the upgrade from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn load(file_path: &str) -> io::Result<Self> {
+        log::info!("loading subnet-evm upgrade from '{file_path}'");
+        let d = fs::read(file_path)?;
+        serde_json::from_slice(&d)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("failed to decode JSON {e}")))
+    }
+
+    /// Syncs the upgrade to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn sync(&self, file_path: &str) -> io::Result<()> {
+        log::info!("syncing subnet-evm upgrade to '{file_path}'");
+        let path = Path::new(file_path);
+        if let Some(parent_dir) = path.parent() {
+            log::info!("creating parent dir '{}'", parent_dir.display());
+            fs::create_dir_all(parent_dir)?;
+        }
+
+        let d = serde_json::to_vec(self)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize JSON {e}")))?;
+
+        let mut f = File::create(file_path)?;
+        f.write_all(&d)?;
+
+        Ok(())
+    }
+}
+
+/// Builds the upgrade file, validated against the genesis chain config if set.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    pub genesis: Option<ChainConfig>,
+    pub precompile_upgrades: Vec<PrecompileUpgrade>,
+    pub state_upgrades: Vec<StateUpgrade>,
+}
+
+impl Builder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the genesis chain config, to validate the upgrades
+    /// against the precompiles that are active at genesis.
+    #[must_use]
+    pub fn genesis(mut self, genesis: &ChainConfig) -> Self {
+        self.genesis = Some(genesis.clone());
+        self
+    }
+
+    /// Appends the precompile upgrade (e.g., an activation at a future timestamp).
+    #[must_use]
+    pub fn precompile_upgrade(mut self, upgrade: PrecompileUpgrade) -> Self {
+        self.precompile_upgrades.push(upgrade);
+        self
+    }
+
+    /// Appends the deactivation of the precompile at "`block_timestamp`".
+    #[must_use]
+    pub fn disable(mut self, precompile: Precompile, block_timestamp: u64) -> Self {
+        self.precompile_upgrades
+            .push(precompile.disable_at(block_timestamp));
+        self
+    }
+
+    /// Appends the state upgrade.
+    #[must_use]
+    pub fn state_upgrade(mut self, upgrade: StateUpgrade) -> Self {
+        self.state_upgrades.push(upgrade);
+        self
+    }
+
+    /// Builds the upgrade.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upgrades are invalid or out of order.
+    pub fn build(self) -> io::Result<Upgrade> {
+        let upgrade = Upgrade {
+            precompile_upgrades: if self.precompile_upgrades.is_empty() {
+                None
+            } else {
+                Some(self.precompile_upgrades)
+            },
+            state_upgrades: if self.state_upgrades.is_empty() {
+                None
+            } else {
+                Some(self.state_upgrades)
+            },
+        };
+        upgrade.validate(self.genesis.as_ref())?;
+        Ok(upgrade)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features="subnet_evm" -- subnet_evm::upgrade::test_upgrade --exact --show-output
+#[test]
+fn test_upgrade() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let admin = String::from("0x8db97C7cEcE249c2b98bDC0226Cc4C2A57BF52FC");
+
+    // ref. <https://docs.avax.network/subnets/upgrade/customize-a-subnet#network-upgrades-enabledisable-precompiles>
+    let upgrade: Upgrade = serde_json::from_str(
+        r#"
+{
+    "precompileUpgrades": [
+        {
+            "feeManagerConfig": {
+                "blockTimestamp": 1668950000,
+                "adminAddresses": ["0x8db97C7cEcE249c2b98bDC0226Cc4C2A57BF52FC"]
+            }
+        },
+        {
+            "txAllowListConfig": {
+                "blockTimestamp": 1668960000,
+                "disable": true
+            }
+        }
+    ],
+    "stateUpgrades": [
+        {
+            "blockTimestamp": 1668970000,
+            "accounts": {
+                "0x71562b71999873DB5b286dF957af199Ec94617F7": {
+                    "balanceChange": "0x52B7D2DCC80CD2E4000000"
+                }
+            }
+        }
+    ]
+}
+"#,
+    )
+    .unwrap();
+
+    let genesis = ChainConfig {
+        tx_allow_list_config: Some(TxAllowListConfig {
+            allow_list_admins: Some(vec![admin.clone()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let expected = Builder::new()
+        .genesis(&genesis)
+        .precompile_upgrade(PrecompileUpgrade::FeeManagerConfig(FeeManagerConfig {
+            allow_list_admins: Some(vec![admin.clone()]),
+            block_timestamp: Some(1_668_950_000),
+            ..Default::default()
+        }))
+        .disable(Precompile::TxAllowList, 1_668_960_000)
+        .state_upgrade(StateUpgrade {
+            block_timestamp: 1_668_970_000,
+            accounts: BTreeMap::from([(
+                String::from("0x71562b71999873DB5b286dF957af199Ec94617F7"),
+                StateUpgradeAccount {
+                    balance_change: Some(
+                        primitive_types::U256::from_str_radix("52B7D2DCC80CD2E4000000", 16)
+                            .unwrap(),
+                    ),
+                    ..Default::default()
+                },
+            )]),
+        })
+        .build()
+        .unwrap();
+    assert_eq!(upgrade, expected);
+
+    // round-trips through the file
+    let tmp_path = random_manager::tmp_path(10, Some(".json")).unwrap();
+    expected.sync(&tmp_path).unwrap();
+    let loaded = Upgrade::load(&tmp_path).unwrap();
+    assert_eq!(loaded, expected);
+    fs::remove_file(tmp_path).unwrap();
+
+    let d = expected.encode_json().unwrap();
+    log::info!("{d}");
+    assert!(d.contains(r#"{"txAllowListConfig":{"blockTimestamp":1668960000,"disable":true}}"#));
+
+    // disabling a precompile that is not active at genesis
+    assert!(Builder::new()
+        .disable(Precompile::TxAllowList, 1_668_960_000)
+        .build()
+        .is_err());
+
+    // enabling an active precompile
+    assert!(Builder::new()
+        .genesis(&genesis)
+        .precompile_upgrade(PrecompileUpgrade::TxAllowListConfig(TxAllowListConfig {
+            allow_list_admins: Some(vec![admin]),
+            block_timestamp: Some(1_668_950_000),
+            ..Default::default()
+        }))
+        .build()
+        .is_err());
+
+    // out of order
+    assert!(Builder::new()
+        .precompile_upgrade(PrecompileUpgrade::WarpConfig(WarpConfig {
+            block_timestamp: Some(1_668_960_000),
+            ..Default::default()
+        }))
+        .disable(Precompile::Warp, 1_668_950_000)
+        .build()
+        .is_err());
+
+    // re-enabling after disabling
+    assert!(Builder::new()
+        .precompile_upgrade(PrecompileUpgrade::WarpConfig(WarpConfig {
+            block_timestamp: Some(1_668_950_000),
+            ..Default::default()
+        }))
+        .disable(Precompile::Warp, 1_668_960_000)
+        .precompile_upgrade(PrecompileUpgrade::WarpConfig(WarpConfig {
+            quorum_numerator: Some(80),
+            block_timestamp: Some(1_668_970_000),
+            ..Default::default()
+        }))
+        .build()
+        .is_ok());
+
+    // invalid config
+    assert!(Builder::new()
+        .precompile_upgrade(PrecompileUpgrade::TxAllowListConfig(TxAllowListConfig {
+            allow_list_admins: Some(vec![String::from("0x1234")]),
+            block_timestamp: Some(1_668_950_000),
+            ..Default::default()
+        }))
+        .build()
+        .is_err());
+}
@@ -0,0 +1,529 @@
+//! Proptest strategies for the arbitrary but valid transactions
+//! (sorted and unique owner addresses, in-range thresholds, sorted signature indices).
+use crate::{
+    avm,
+    ids::{self, node, short},
+    key, platformvm, txs,
+};
+use proptest::{collection::vec, prelude::*, test_runner::TestRunner};
+
+/// Returns the test runner with the fixed seed, so failures reproduce across runs.
+#[must_use]
+pub fn runner() -> TestRunner {
+    TestRunner::deterministic()
+}
+
+/// Generates the arbitrary 32-byte Id.
+pub fn id() -> impl Strategy<Value = ids::Id> {
+    any::<[u8; ids::LEN]>().prop_map(|b| ids::Id::from_slice(&b))
+}
+
+/// Generates the arbitrary 20-byte short Id.
+pub fn short_id() -> impl Strategy<Value = short::Id> {
+    any::<[u8; short::LEN]>().prop_map(|b| short::Id::from_slice(&b))
+}
+
+/// Generates the owners with 1 to 5 sorted unique addresses.
+pub fn output_owners() -> impl Strategy<Value = key::secp256k1::txs::OutputOwners> {
+    (any::<u64>(), vec(short_id(), 1..=5))
+        .prop_flat_map(|(locktime, mut addresses)| {
+            addresses.sort();
+            addresses.dedup();
+            let n = u32::try_from(addresses.len()).unwrap_or(1);
+            (Just(locktime), 1..=n, Just(addresses))
+        })
+        .prop_map(|(locktime, threshold, addresses)| {
+            key::secp256k1::txs::OutputOwners::new(locktime, threshold, &addresses)
+        })
+}
+
+/// Generates the secp256k1fx transfer output of a non-zero amount.
+pub fn transferable_output() -> impl Strategy<Value = txs::transferable::Output> {
+    (id(), 1..u64::MAX, output_owners()).prop_map(|(asset_id, amount, owners)| {
+        txs::transferable::Output {
+            asset_id,
+            transfer_output: Some(key::secp256k1::txs::transfer::Output::new(amount, owners)),
+            ..Default::default()
+        }
+    })
+}
+
+/// Generates the secp256k1fx transfer input of a non-zero amount.
+///
+/// # Panics
+///
+/// Panics if "`utxo::Id::new`" fails to derive the UTXO Id, which does not
+/// happen for the generated 32-byte tx Ids.
+pub fn transferable_input() -> impl Strategy<Value = txs::transferable::Input> {
+    (
+        id(),
+        any::<u32>(),
+        id(),
+        1..u64::MAX,
+        vec(any::<u32>(), 1..=3),
+    )
+        .prop_map(|(tx_id, output_index, asset_id, amount, mut sig_indices)| {
+            sig_indices.sort_unstable();
+            sig_indices.dedup();
+            txs::transferable::Input {
+                utxo_id: txs::utxo::Id::new(tx_id.as_ref(), output_index, false)
+                    .expect("unexpected utxo::Id::new failure"),
+                asset_id,
+                transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                    amount,
+                    sig_indices,
+                }),
+                ..Default::default()
+            }
+        })
+}
+
+/// Generates the base transaction with up to 4 inputs and outputs.
+pub fn base_tx() -> impl Strategy<Value = txs::Tx> {
+    (
+        any::<u32>(),
+        id(),
+        vec(transferable_input(), 0..=4),
+        vec(transferable_output(), 0..=4),
+        vec(any::<u8>(), 0..=32),
+    )
+        .prop_map(
+            |(network_id, blockchain_id, inputs, outputs, memo)| txs::Tx {
+                network_id,
+                blockchain_id,
+                transferable_inputs: Some(inputs),
+                transferable_outputs: Some(outputs),
+                memo: Some(memo),
+                ..Default::default()
+            },
+        )
+}
+
+/// Generates the X-chain base transaction.
+pub fn avm_base_tx() -> impl Strategy<Value = avm::txs::Tx> {
+    base_tx().prop_map(avm::txs::Tx::new)
+}
+
+/// Generates the P-chain create subnet transaction.
+pub fn create_subnet_tx() -> impl Strategy<Value = platformvm::txs::create_subnet::Tx> {
+    (base_tx(), output_owners()).prop_map(|(base_tx, owner)| platformvm::txs::create_subnet::Tx {
+        owner,
+        ..platformvm::txs::create_subnet::Tx::new(base_tx)
+    })
+}
+
+/// Generates the arbitrary node Id.
+pub fn node_id() -> impl Strategy<Value = node::Id> {
+    any::<[u8; node::LEN]>().prop_map(|b| node::Id::from_slice(&b))
+}
+
+/// Generates 1 to 3 sorted unique signature indices.
+pub fn sig_indices() -> impl Strategy<Value = Vec<u32>> {
+    vec(any::<u32>(), 1..=3).prop_map(|mut sig_indices| {
+        sig_indices.sort_unstable();
+        sig_indices.dedup();
+        sig_indices
+    })
+}
+
+/// Generates the subnet authorization input.
+pub fn subnet_auth() -> impl Strategy<Value = key::secp256k1::txs::Input> {
+    sig_indices().prop_map(|sig_indices| key::secp256k1::txs::Input { sig_indices })
+}
+
+/// Generates the staker whose end time is after its start time.
+pub fn validator() -> impl Strategy<Value = platformvm::txs::Validator> {
+    (node_id(), 0..u64::MAX / 2, 1..u64::MAX / 2, 1..u64::MAX).prop_map(
+        |(node_id, start, duration, weight)| platformvm::txs::Validator {
+            node_id,
+            start,
+            end: start + duration,
+            weight,
+        },
+    )
+}
+
+/// Generates the BLS proof of possession of the valid lengths
+/// (the bytes are not a valid key, which the codec does not check).
+pub fn proof_of_possession() -> impl Strategy<Value = key::bls::ProofOfPossession> {
+    (vec(any::<u8>(), 48), vec(any::<u8>(), 96)).prop_map(|(public_key, proof_of_possession)| {
+        key::bls::ProofOfPossession {
+            public_key,
+            proof_of_possession,
+            ..Default::default()
+        }
+    })
+}
+
+/// Generates the X-chain export transaction with 1 to 4 exported outputs.
+pub fn avm_export_tx() -> impl Strategy<Value = avm::txs::export::Tx> {
+    (base_tx(), id(), vec(transferable_output(), 1..=4)).prop_map(
+        |(base_tx, destination_chain_id, outputs)| avm::txs::export::Tx {
+            destination_chain_id,
+            destination_chain_transferable_outputs: Some(outputs),
+            ..avm::txs::export::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the X-chain import transaction with 1 to 4 imported inputs.
+pub fn avm_import_tx() -> impl Strategy<Value = avm::txs::import::Tx> {
+    (base_tx(), id(), vec(transferable_input(), 1..=4)).prop_map(
+        |(base_tx, source_chain_id, inputs)| avm::txs::import::Tx {
+            source_chain_id,
+            source_chain_transferable_inputs: Some(inputs),
+            ..avm::txs::import::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the P-chain add validator transaction with 1 to 2 staked outputs.
+pub fn add_validator_tx() -> impl Strategy<Value = platformvm::txs::add_validator::Tx> {
+    (
+        base_tx(),
+        validator(),
+        vec(transferable_output(), 1..=2),
+        output_owners(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(base_tx, validator, stake_outputs, rewards_owner, shares)| {
+                platformvm::txs::add_validator::Tx {
+                    validator,
+                    stake_transferable_outputs: Some(stake_outputs),
+                    rewards_owner,
+                    shares,
+                    ..platformvm::txs::add_validator::Tx::new(base_tx)
+                }
+            },
+        )
+}
+
+/// Generates the P-chain add subnet validator transaction.
+pub fn add_subnet_validator_tx() -> impl Strategy<Value = platformvm::txs::add_subnet_validator::Tx>
+{
+    (base_tx(), validator(), id(), subnet_auth()).prop_map(
+        |(base_tx, validator, subnet_id, subnet_auth)| platformvm::txs::add_subnet_validator::Tx {
+            validator: platformvm::txs::add_subnet_validator::Validator {
+                validator,
+                subnet_id,
+            },
+            subnet_auth,
+            ..platformvm::txs::add_subnet_validator::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the primary network validator with the BLS signer,
+/// or the subnet validator with the empty signer.
+pub fn add_permissionless_validator_tx(
+) -> impl Strategy<Value = platformvm::txs::add_permissionless_validator::Tx> {
+    (
+        base_tx(),
+        validator(),
+        prop_oneof![
+            (Just(ids::Id::empty()), proof_of_possession().prop_map(Some)),
+            (id(), Just(None)),
+        ],
+        vec(transferable_output(), 1..=2),
+        output_owners(),
+        output_owners(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(
+                base_tx,
+                validator,
+                (subnet_id, signer),
+                stake_outputs,
+                validator_rewards_owner,
+                delegator_rewards_owner,
+                delegation_shares,
+            )| platformvm::txs::add_permissionless_validator::Tx {
+                validator,
+                subnet_id,
+                signer,
+                stake_transferable_outputs: Some(stake_outputs),
+                validator_rewards_owner,
+                delegator_rewards_owner,
+                delegation_shares,
+                ..platformvm::txs::add_permissionless_validator::Tx::new(base_tx)
+            },
+        )
+}
+
+/// Generates the P-chain add delegator transaction with 1 to 2 staked outputs.
+pub fn add_delegator_tx() -> impl Strategy<Value = platformvm::txs::add_delegator::Tx> {
+    (
+        base_tx(),
+        validator(),
+        vec(transferable_output(), 1..=2),
+        output_owners(),
+    )
+        .prop_map(|(base_tx, validator, stake_outputs, rewards_owner)| {
+            platformvm::txs::add_delegator::Tx {
+                validator,
+                stake_transferable_outputs: Some(stake_outputs),
+                rewards_owner,
+                ..platformvm::txs::add_delegator::Tx::new(base_tx)
+            }
+        })
+}
+
+/// Generates the primary network or subnet permissionless delegator transaction.
+pub fn add_permissionless_delegator_tx(
+) -> impl Strategy<Value = platformvm::txs::add_permissionless_delegator::Tx> {
+    (
+        base_tx(),
+        validator(),
+        prop_oneof![Just(ids::Id::empty()), id()],
+        vec(transferable_output(), 1..=2),
+        output_owners(),
+    )
+        .prop_map(
+            |(base_tx, validator, subnet_id, stake_outputs, delegation_rewards_owner)| {
+                platformvm::txs::add_permissionless_delegator::Tx {
+                    validator,
+                    subnet_id,
+                    stake_transferable_outputs: Some(stake_outputs),
+                    delegation_rewards_owner,
+                    ..platformvm::txs::add_permissionless_delegator::Tx::new(base_tx)
+                }
+            },
+        )
+}
+
+/// Generates the P-chain create chain transaction.
+pub fn create_chain_tx() -> impl Strategy<Value = platformvm::txs::create_chain::Tx> {
+    (
+        base_tx(),
+        id(),
+        "[a-zA-Z0-9]{1,32}",
+        id(),
+        vec(id(), 0..=2),
+        vec(any::<u8>(), 0..=64),
+        subnet_auth(),
+    )
+        .prop_map(
+            |(base_tx, subnet_id, chain_name, vm_id, mut fx_ids, genesis_data, subnet_auth)| {
+                fx_ids.sort();
+                fx_ids.dedup();
+                platformvm::txs::create_chain::Tx {
+                    subnet_id,
+                    chain_name,
+                    vm_id,
+                    fx_ids: Some(fx_ids),
+                    genesis_data,
+                    subnet_auth,
+                    ..platformvm::txs::create_chain::Tx::new(base_tx)
+                }
+            },
+        )
+}
+
+/// Generates the P-chain export transaction with 1 to 4 exported outputs.
+pub fn p_export_tx() -> impl Strategy<Value = platformvm::txs::export::Tx> {
+    (base_tx(), id(), vec(transferable_output(), 1..=4)).prop_map(
+        |(base_tx, destination_chain_id, outputs)| platformvm::txs::export::Tx {
+            destination_chain_id,
+            destination_chain_transferable_outputs: Some(outputs),
+            ..platformvm::txs::export::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the P-chain import transaction with 1 to 4 imported inputs.
+pub fn p_import_tx() -> impl Strategy<Value = platformvm::txs::import::Tx> {
+    (base_tx(), id(), vec(transferable_input(), 1..=4)).prop_map(
+        |(base_tx, source_chain_id, inputs)| platformvm::txs::import::Tx {
+            source_chain_id,
+            source_chain_transferable_inputs: Some(inputs),
+            ..platformvm::txs::import::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the P-chain remove subnet validator transaction.
+pub fn remove_subnet_validator_tx(
+) -> impl Strategy<Value = platformvm::txs::remove_subnet_validator::Tx> {
+    (base_tx(), node_id(), id(), subnet_auth()).prop_map(
+        |(base_tx, node_id, subnet_id, subnet_auth)| platformvm::txs::remove_subnet_validator::Tx {
+            node_id,
+            subnet_id,
+            subnet_auth,
+            ..platformvm::txs::remove_subnet_validator::Tx::new(base_tx)
+        },
+    )
+}
+
+/// Generates the C-chain EVM output of an atomic import.
+#[cfg(feature = "coreth")]
+pub fn evm_output() -> impl Strategy<Value = crate::coreth::atomic::EvmOutput> {
+    (any::<[u8; 20]>(), 1..u64::MAX, id()).prop_map(|(address, amount, asset_id)| {
+        crate::coreth::atomic::EvmOutput {
+            address: primitive_types::H160::from(address),
+            amount,
+            asset_id,
+        }
+    })
+}
+
+/// Generates the C-chain EVM input of an atomic export.
+#[cfg(feature = "coreth")]
+pub fn evm_input() -> impl Strategy<Value = crate::coreth::atomic::EvmInput> {
+    (any::<[u8; 20]>(), 1..u64::MAX, id(), any::<u64>()).prop_map(
+        |(address, amount, asset_id, nonce)| crate::coreth::atomic::EvmInput {
+            address: primitive_types::H160::from(address),
+            amount,
+            asset_id,
+            nonce,
+        },
+    )
+}
+
+/// Generates the C-chain atomic import transaction.
+#[cfg(feature = "coreth")]
+pub fn atomic_import_tx() -> impl Strategy<Value = crate::coreth::atomic::ImportTx> {
+    (
+        any::<u32>(),
+        id(),
+        id(),
+        vec(transferable_input(), 1..=4),
+        vec(evm_output(), 1..=4),
+    )
+        .prop_map(
+            |(network_id, blockchain_id, source_chain, imported_inputs, outs)| {
+                crate::coreth::atomic::ImportTx {
+                    network_id,
+                    blockchain_id,
+                    source_chain,
+                    imported_inputs,
+                    outs,
+                    ..Default::default()
+                }
+            },
+        )
+}
+
+/// Generates the C-chain atomic export transaction.
+#[cfg(feature = "coreth")]
+pub fn atomic_export_tx() -> impl Strategy<Value = crate::coreth::atomic::ExportTx> {
+    (
+        any::<u32>(),
+        id(),
+        id(),
+        vec(evm_input(), 1..=4),
+        vec(transferable_output(), 1..=4),
+    )
+        .prop_map(
+            |(network_id, blockchain_id, destination_chain, ins, exported_outputs)| {
+                crate::coreth::atomic::ExportTx {
+                    network_id,
+                    blockchain_id,
+                    destination_chain,
+                    ins,
+                    exported_outputs,
+                    ..Default::default()
+                }
+            },
+        )
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features testutils -- `testutils::strategies::test_round_trip` --exact --show-output
+#[test]
+fn test_round_trip() {
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+    let no_keys = Vec::<Vec<key::secp256k1::private_key::Key>>::new;
+
+    // packing must not depend on the fields serde skips
+    macro_rules! round_trip {
+        ($strategy:expr, $t:ty, |$tx:ident| $metadata:expr) => {
+            runner()
+                .run(&$strategy, |tx| {
+                    let mut $tx = tx.clone();
+                    ab!($tx.sign(no_keys())).unwrap();
+                    let signed = $metadata.unwrap().tx_bytes_with_no_signature;
+
+                    let mut $tx: $t =
+                        serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+                    ab!($tx.sign(no_keys())).unwrap();
+                    prop_assert_eq!(signed, $metadata.unwrap().tx_bytes_with_no_signature);
+                    Ok(())
+                })
+                .unwrap();
+        };
+    }
+
+    round_trip!(avm_base_tx(), avm::txs::Tx, |tx| tx.base_tx.metadata);
+    round_trip!(avm_export_tx(), avm::txs::export::Tx, |tx| tx
+        .base_tx
+        .metadata);
+    round_trip!(avm_import_tx(), avm::txs::import::Tx, |tx| tx
+        .base_tx
+        .metadata);
+    round_trip!(
+        add_validator_tx(),
+        platformvm::txs::add_validator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(
+        add_delegator_tx(),
+        platformvm::txs::add_delegator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(
+        add_permissionless_delegator_tx(),
+        platformvm::txs::add_permissionless_delegator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(
+        add_subnet_validator_tx(),
+        platformvm::txs::add_subnet_validator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(
+        add_permissionless_validator_tx(),
+        platformvm::txs::add_permissionless_validator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(create_chain_tx(), platformvm::txs::create_chain::Tx, |tx| {
+        tx.base_tx.metadata
+    });
+    round_trip!(
+        create_subnet_tx(),
+        platformvm::txs::create_subnet::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    round_trip!(p_export_tx(), platformvm::txs::export::Tx, |tx| tx
+        .base_tx
+        .metadata);
+    round_trip!(p_import_tx(), platformvm::txs::import::Tx, |tx| tx
+        .base_tx
+        .metadata);
+    round_trip!(
+        remove_subnet_validator_tx(),
+        platformvm::txs::remove_subnet_validator::Tx,
+        |tx| tx.base_tx.metadata
+    );
+    #[cfg(feature = "coreth")]
+    {
+        round_trip!(atomic_import_tx(), crate::coreth::atomic::ImportTx, |tx| tx
+            .metadata);
+        round_trip!(atomic_export_tx(), crate::coreth::atomic::ExportTx, |tx| tx
+            .metadata);
+    }
+
+    runner()
+        .run(&create_subnet_tx(), |tx| {
+            let owners = &tx.owner;
+            prop_assert!(owners.threshold >= 1);
+            prop_assert!(usize::try_from(owners.threshold).unwrap() <= owners.addresses.len());
+            prop_assert!(owners.addresses.windows(2).all(|w| w[0] < w[1]));
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,405 @@
+//! Bootstraps a local network by spawning the avalanchego binaries directly,
+//! for the simple integration tests that do not need avalanche-network-runner
+//! (see [`super::network_runner`]).
+//!
+//! Each node gets its own staking certificate, all nodes are the initial
+//! stakers of a custom genesis pre-funding the "ewoq" key, and each node
+//! bootstraps from the nodes started before it.
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use crate::{
+    avalanchego::{
+        config::Flags,
+        genesis::{Genesis, Staker},
+    },
+    errors::{Error, Result},
+    ids::node,
+    jsonrpc::client::health as client_health,
+    key::secp256k1::{self, ReadOnly},
+};
+use tokio::{
+    process::{Child, Command},
+    time::{sleep, Duration, Instant},
+};
+
+/// Default number of nodes in the network.
+pub const DEFAULT_NUM_NODES: usize = 5;
+
+/// Default HTTP port of the first node. Each node takes two ports
+/// (HTTP and staking) from here.
+pub const DEFAULT_BASE_PORT: u16 = 9650;
+
+/// Options of [`LocalNetwork::start`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Path to the avalanchego binary.
+    pub exec_path: String,
+    pub num_nodes: usize,
+    /// Network Id of the custom genesis.
+    pub network_id: u32,
+    /// Directory to write the genesis, certificates, databases and logs to.
+    pub root_dir: PathBuf,
+    /// HTTP port of the first node, the others follow.
+    pub base_port: u16,
+    /// Logging level of the nodes (e.g., "INFO").
+    pub log_level: Option<String>,
+    pub plugin_dir: Option<String>,
+    /// Other flags of all nodes by their names without "--".
+    pub extra_flags: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Creates the options of a five-node network with the default ports.
+    #[must_use]
+    pub fn new(exec_path: &str, root_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            exec_path: exec_path.to_string(),
+            num_nodes: DEFAULT_NUM_NODES,
+            network_id: crate::constants::DEFAULT_CUSTOM_NETWORK_ID,
+            root_dir: root_dir.into(),
+            base_port: DEFAULT_BASE_PORT,
+            log_level: None,
+            plugin_dir: None,
+            extra_flags: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn num_nodes(mut self, num_nodes: usize) -> Self {
+        self.num_nodes = num_nodes;
+        self
+    }
+
+    #[must_use]
+    pub const fn network_id(mut self, network_id: u32) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn base_port(mut self, base_port: u16) -> Self {
+        self.base_port = base_port;
+        self
+    }
+
+    #[must_use]
+    pub fn log_level(mut self, log_level: &str) -> Self {
+        self.log_level = Some(log_level.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn plugin_dir(mut self, plugin_dir: &str) -> Self {
+        self.plugin_dir = Some(plugin_dir.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn extra_flag(mut self, name: &str, value: &str) -> Self {
+        self.extra_flags.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Returns the HTTP and staking ports of the node.
+    fn ports(&self, idx: usize) -> Result<(u16, u16)> {
+        let http_port = u16::try_from(idx * 2)
+            .ok()
+            .and_then(|offset| self.base_port.checked_add(offset))
+            .ok_or_else(|| Error::Other {
+                message: format!("no port left for node {idx} from {}", self.base_port),
+                retryable: false,
+            })?;
+        let staking_port = http_port.checked_add(1).ok_or_else(|| Error::Other {
+            message: format!("no staking port left for node {idx}"),
+            retryable: false,
+        })?;
+        Ok((http_port, staking_port))
+    }
+}
+
+/// Represents a running node of the network.
+#[derive(Debug)]
+pub struct Node {
+    pub name: String,
+    pub node_id: node::Id,
+    pub http_port: u16,
+    pub staking_port: u16,
+    /// Flags the node was launched with.
+    pub flags: Flags,
+    /// Killed on drop.
+    child: Child,
+}
+
+impl Node {
+    /// Returns the HTTP URI of the node (e.g., "<http://127.0.0.1:9650>").
+    #[must_use]
+    pub fn uri(&self) -> String {
+        format!("http://127.0.0.1:{}", self.http_port)
+    }
+}
+
+/// Represents the running local network. The nodes are killed on drop.
+#[derive(Debug)]
+pub struct LocalNetwork {
+    pub config: Config,
+    pub genesis_file: PathBuf,
+    pub nodes: Vec<Node>,
+}
+
+impl LocalNetwork {
+    /// Writes the certificates and the genesis, and spawns the nodes.
+    /// The network is not healthy yet when this returns,
+    /// see `wait_for_healthy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the files cannot be written,
+    /// or if any node fails to spawn.
+    pub fn start(config: Config) -> Result<Self> {
+        if config.num_nodes == 0 {
+            return Err(Error::Other {
+                message: "no node to start".to_string(),
+                retryable: false,
+            });
+        }
+        fs::create_dir_all(&config.root_dir).map_err(|e| io_error(&config.root_dir, &e))?;
+
+        let mut node_ids = Vec::with_capacity(config.num_nodes);
+        for idx in 0..config.num_nodes {
+            let dir = node_dir(&config, idx);
+            fs::create_dir_all(&dir).map_err(|e| io_error(&dir, &e))?;
+            let (key_path, cert_path) = (dir.join("staker.key"), dir.join("staker.crt"));
+            let (node_id, _) =
+                node::Id::load_or_generate_pem(&path_str(&key_path)?, &path_str(&cert_path)?)
+                    .map_err(|e| io_error(&cert_path, &e))?;
+            node_ids.push(node_id);
+        }
+
+        let genesis_file = config.root_dir.join("genesis.json");
+        let genesis = genesis_with_stakers(config.network_id, &node_ids)?;
+        genesis
+            .sync(&path_str(&genesis_file)?)
+            .map_err(|e| io_error(&genesis_file, &e))?;
+
+        let mut network = Self {
+            config,
+            genesis_file,
+            nodes: Vec::with_capacity(node_ids.len()),
+        };
+        for (idx, node_id) in node_ids.into_iter().enumerate() {
+            let node = network.spawn_node(idx, node_id)?;
+            network.nodes.push(node);
+        }
+        Ok(network)
+    }
+
+    /// Spawns the node, bootstrapping from the nodes already spawned.
+    fn spawn_node(&self, idx: usize, node_id: node::Id) -> Result<Node> {
+        let dir = node_dir(&self.config, idx);
+        let (http_port, staking_port) = self.config.ports(idx)?;
+
+        let flags = Flags {
+            network_id: Some(self.config.network_id),
+            genesis_file: Some(path_str(&self.genesis_file)?),
+            data_dir: Some(path_str(&dir)?),
+            log_dir: Some(path_str(&dir.join("logs"))?),
+            log_level: self.config.log_level.clone(),
+            http_port: Some(http_port),
+            public_ip: Some("127.0.0.1".to_string()),
+            staking_port: Some(staking_port),
+            staking_tls_key_file: Some(path_str(&dir.join("staker.key"))?),
+            staking_tls_cert_file: Some(path_str(&dir.join("staker.crt"))?),
+            bootstrap_ips: self
+                .nodes
+                .iter()
+                .map(|n| format!("127.0.0.1:{}", n.staking_port))
+                .collect(),
+            bootstrap_ids: self.nodes.iter().map(|n| n.node_id).collect(),
+            plugin_dir: self.config.plugin_dir.clone(),
+            extra: self.config.extra_flags.clone(),
+            ..Default::default()
+        };
+        flags.validate().map_err(|e| Error::Other {
+            message: format!("invalid flags for node {idx} ({e})"),
+            retryable: false,
+        })?;
+        let args = flags.to_args().map_err(|e| Error::Other {
+            message: format!("failed to_args ({e})"),
+            retryable: false,
+        })?;
+
+        let log_path = dir.join("avalanchego.log");
+        let stdout = File::create(&log_path).map_err(|e| io_error(&log_path, &e))?;
+        let stderr = stdout.try_clone().map_err(|e| io_error(&log_path, &e))?;
+
+        let name = format!("node{}", idx + 1);
+        log::info!("spawning {name} ({node_id}) on port {http_port} with {args:?}");
+        let child = Command::new(&self.config.exec_path)
+            .args(&args)
+            .stdout(Stdio::from(stdout))
+            .stderr(Stdio::from(stderr))
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| Error::Other {
+                message: format!("failed to spawn '{}' ({e})", self.config.exec_path),
+                retryable: false,
+            })?;
+
+        Ok(Node {
+            name,
+            node_id,
+            http_port,
+            staking_port,
+            flags,
+            child,
+        })
+    }
+
+    /// Returns the HTTP URIs of the nodes.
+    #[must_use]
+    pub fn uris(&self) -> Vec<String> {
+        self.nodes.iter().map(Node::uri).collect()
+    }
+
+    /// Polls the health of all nodes until all are healthy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any node exits,
+    /// or if the network is not healthy in time.
+    pub async fn wait_for_healthy(
+        &mut self,
+        poll_interval: Duration,
+        poll_timeout: Duration,
+    ) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let mut healthy = 0;
+            for node in &mut self.nodes {
+                if let Ok(Some(status)) = node.child.try_wait() {
+                    return Err(Error::Other {
+                        message: format!("{} exited with {status}", node.name),
+                        retryable: false,
+                    });
+                }
+                match client_health::check(Arc::new(node.uri()), true).await {
+                    Ok(resp) if resp.healthy => healthy += 1,
+                    Ok(_) => log::info!("{} is not healthy yet", node.name),
+                    Err(e) => log::debug!("{} is not reachable yet '{e}'", node.name),
+                }
+            }
+            if healthy == self.nodes.len() {
+                log::info!("all {healthy} node(s) are healthy");
+                return Ok(());
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed.gt(&poll_timeout) {
+                return Err(Error::API {
+                    message: format!(
+                        "{healthy} of {} node(s) healthy in {elapsed:?}",
+                        self.nodes.len()
+                    ),
+                    retryable: true,
+                });
+            }
+            sleep(poll_interval).await;
+        }
+    }
+
+    /// Kills all nodes. The data directories are kept for debugging.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any node fails to be killed.
+    pub async fn stop(&mut self) -> Result<()> {
+        for node in &mut self.nodes {
+            log::info!("stopping {}", node.name);
+            node.child.kill().await.map_err(|e| Error::Other {
+                message: format!("failed to kill {} ({e})", node.name),
+                retryable: false,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the genesis pre-funding the "ewoq" key,
+/// with all nodes as the initial stakers.
+fn genesis_with_stakers(network_id: u32, node_ids: &[node::Id]) -> Result<Genesis> {
+    let ewoq = secp256k1::EWOQ_KEY.to_private_key()?;
+    let mut genesis =
+        Genesis::new(network_id, std::slice::from_ref(&ewoq)).map_err(|e| Error::Other {
+            message: format!("failed Genesis::new ({e})"),
+            retryable: false,
+        })?;
+
+    let reward_address = ewoq.hrp_address(network_id, "X")?;
+    genesis.initial_stakers = Some(
+        node_ids
+            .iter()
+            .map(|node_id| Staker {
+                node_id: Some(node_id.to_string()),
+                reward_address: Some(reward_address.clone()),
+                ..Default::default()
+            })
+            .collect(),
+    );
+    Ok(genesis)
+}
+
+/// Returns the directory of the node.
+fn node_dir(config: &Config, idx: usize) -> PathBuf {
+    config.root_dir.join(format!("node{}", idx + 1))
+}
+
+/// Returns the path as UTF-8.
+fn path_str(p: &Path) -> Result<String> {
+    p.to_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| Error::Other {
+            message: format!("non-UTF-8 path {}", p.display()),
+            retryable: false,
+        })
+}
+
+/// Converts the file operation error.
+fn io_error(p: &Path, e: &std::io::Error) -> Error {
+    Error::Other {
+        message: format!("failed file operation on {} ({e})", p.display()),
+        retryable: false,
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features avalanchego,jsonrpc_client -- `tooling::local_network::test_genesis_and_ports` --exact --show-output
+#[test]
+fn test_genesis_and_ports() {
+    let node_ids = [
+        node::Id::from_slice(&[1; 20]),
+        node::Id::from_slice(&[2; 20]),
+    ];
+    let genesis = genesis_with_stakers(1337, &node_ids).unwrap();
+    let stakers = genesis.initial_stakers.unwrap();
+    assert_eq!(stakers.len(), 2);
+    assert_eq!(stakers[1].node_id, Some(node_ids[1].to_string()));
+    assert!(stakers
+        .iter()
+        .all(|s| s.reward_address.as_deref().unwrap().starts_with("X-")));
+    assert_eq!(genesis.initial_staked_funds.unwrap().len(), 1);
+
+    let config = Config::new("/tmp/avalanchego", "/tmp/network").base_port(9650);
+    assert_eq!(config.num_nodes, DEFAULT_NUM_NODES);
+    assert_eq!(config.ports(0).unwrap(), (9650, 9651));
+    assert_eq!(config.ports(4).unwrap(), (9658, 9659));
+    assert!(config.base_port(u16::MAX).ports(1).is_err());
+    assert_eq!(
+        node_dir(&Config::new("", "/tmp/network"), 0),
+        PathBuf::from("/tmp/network/node1")
+    );
+}
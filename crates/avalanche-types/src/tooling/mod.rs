@@ -0,0 +1,11 @@
+//! Higher-level tooling built on the API clients and the codec.
+#[cfg(feature = "avalanchego")]
+#[cfg_attr(docsrs, doc(cfg(feature = "avalanchego")))]
+pub mod local_network;
+pub mod network_runner;
+pub mod scanner;
+pub mod validator_watch;
+
+#[cfg(feature = "wallet")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wallet")))]
+pub mod subnet_deployer;
@@ -0,0 +1,462 @@
+//! Typed client of avalanche-network-runner to orchestrate local networks
+//! from the test suites. Talks to the gRPC gateway of the runner server
+//! ("--grpc-gateway-port", JSON over HTTP) with the messages of its
+//! "rpcpb/rpc.proto" control service.
+//! ref. <https://github.com/ava-labs/avalanche-network-runner/blob/main/rpcpb/rpc.proto>
+use std::collections::HashMap;
+
+use crate::{
+    errors::{Error, Result},
+    ids,
+    jsonrpc::client::http,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::time::{sleep, Duration, Instant};
+
+/// ref. "rpcpb.StartRequest"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StartRequest {
+    /// Path to the avalanchego binary.
+    pub exec_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_nodes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    /// JSON-encoded avalanchego flags of all nodes
+    /// (e.g., "`avalanchego::config::Flags::encode_json`").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_node_config: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_data_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blockchain_specs: Vec<BlockchainSpec>,
+    /// JSON-encoded avalanchego flags by the node name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_node_configs: HashMap<String, String>,
+    /// Chain configs by the chain alias or Id.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub chain_configs: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub subnet_configs: HashMap<String, String>,
+    #[serde(default)]
+    pub reassign_ports_if_used: bool,
+    #[serde(default)]
+    pub dynamic_ports: bool,
+}
+
+/// ref. "rpcpb.BlockchainSpec"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainSpec {
+    /// VM name, or the VM Id in CB58.
+    pub vm_name: String,
+    /// Path to the genesis file, or the genesis in JSON.
+    pub genesis: String,
+    /// Existing subnet Id to create the chain on.
+    /// If none, creates a new subnet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_config: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain_alias: Option<String>,
+}
+
+/// ref. "rpcpb.AddNodeRequest"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AddNodeRequest {
+    pub name: String,
+    /// Path to the avalanchego binary, if none, same as the running nodes.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub exec_path: String,
+    /// JSON-encoded avalanchego flags of the node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_config: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub chain_configs: HashMap<String, String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub plugin_dir: String,
+}
+
+/// ref. "rpcpb.SubnetSpec"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SubnetSpec {
+    /// Names of the nodes to validate the subnet, if empty, all nodes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub participants: Vec<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub subnet_config: String,
+}
+
+/// ref. "rpcpb.ClusterInfo"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClusterInfo {
+    pub node_names: Vec<String>,
+    pub node_infos: HashMap<String, NodeInfo>,
+    pub pid: i32,
+    pub root_data_dir: String,
+    pub healthy: bool,
+    /// Custom chains by the chain Id.
+    pub custom_chains: HashMap<String, CustomChainInfo>,
+}
+
+impl ClusterInfo {
+    /// Returns the HTTP URIs of the nodes, in the order of the node names.
+    #[must_use]
+    pub fn uris(&self) -> Vec<String> {
+        self.node_names
+            .iter()
+            .filter_map(|name| self.node_infos.get(name))
+            .map(|info| info.uri.clone())
+            .collect()
+    }
+}
+
+/// ref. "rpcpb.NodeInfo"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NodeInfo {
+    pub name: String,
+    pub exec_path: String,
+    /// HTTP URI (e.g., "<http://127.0.0.1:9650>").
+    pub uri: String,
+    /// Node Id (e.g., "NodeID-...").
+    pub id: String,
+    pub log_dir: String,
+    pub db_dir: String,
+    pub plugin_dir: String,
+    pub paused: bool,
+}
+
+/// ref. "rpcpb.CustomChainInfo"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CustomChainInfo {
+    pub chain_name: String,
+    pub vm_id: String,
+    #[serde(rename = "pchainId")]
+    pub subnet_id: String,
+    pub chain_id: String,
+}
+
+/// ref. "rpcpb.PingResponse"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PingResponse {
+    pub pid: i32,
+}
+
+/// ref. "rpcpb.StartResponse"
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StartResponse {
+    pub cluster_info: Option<ClusterInfo>,
+    /// Ids of the chains created from the blockchain specs.
+    pub chain_ids: Vec<String>,
+}
+
+/// Responses of the control calls that only return the cluster.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ClusterResponse {
+    /// Cluster after the call.
+    cluster_info: Option<ClusterInfo>,
+}
+
+/// ref. "rpcpb.URIsResponse"
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct UrisResponse {
+    /// HTTP URIs of the nodes.
+    uris: Vec<String>,
+}
+
+/// ref. "rpcpb.CreateSubnetsResponse"
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct CreateSubnetsResponse {
+    /// Ids of the created subnets, in the order of the specs.
+    subnet_ids: Vec<String>,
+}
+
+/// Error body of the gRPC gateway.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct GatewayError {
+    /// gRPC status code.
+    code: i32,
+    /// Error message.
+    message: String,
+}
+
+/// Client of the network-runner gRPC gateway.
+#[derive(Debug, Clone)]
+pub struct Client {
+    /// Gateway endpoint (e.g., "<http://127.0.0.1:8081>").
+    pub gateway: String,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(gateway: &str) -> Self {
+        Self {
+            gateway: gateway.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Pings the server, and returns its process Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server is unreachable.
+    pub async fn ping(&self) -> Result<i32> {
+        let resp: PingResponse = self.call("/v1/ping", &serde_json::json!({})).await?;
+        Ok(resp.pid)
+    }
+
+    /// Starts the network. The network is not healthy yet when the call
+    /// returns, see `wait_for_healthy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network fails to start (e.g., already started).
+    pub async fn start(&self, req: &StartRequest) -> Result<StartResponse> {
+        log::info!(
+            "starting network with {:?} node(s) via {}",
+            req.num_nodes,
+            self.gateway
+        );
+        self.call("/v1/control/start", req).await
+    }
+
+    /// Returns the cluster, with "healthy" set once all nodes are healthy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network is not started.
+    pub async fn health(&self) -> Result<ClusterInfo> {
+        let resp: ClusterResponse = self
+            .call("/v1/control/health", &serde_json::json!({}))
+            .await?;
+        cluster_info(resp)
+    }
+
+    /// Returns the HTTP URIs of the nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network is not started.
+    pub async fn uris(&self) -> Result<Vec<String>> {
+        let resp: UrisResponse = self
+            .call("/v1/control/uris", &serde_json::json!({}))
+            .await?;
+        Ok(resp.uris)
+    }
+
+    /// Adds a node to the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node fails to start (e.g., name taken).
+    pub async fn add_node(&self, req: &AddNodeRequest) -> Result<ClusterInfo> {
+        log::info!("adding node '{}' via {}", req.name, self.gateway);
+        let resp: ClusterResponse = self.call("/v1/control/addnode", req).await?;
+        cluster_info(resp)
+    }
+
+    /// Removes (stops) the node from the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node is not found.
+    pub async fn remove_node(&self, name: &str) -> Result<ClusterInfo> {
+        log::info!("removing node '{name}' via {}", self.gateway);
+        let resp: ClusterResponse = self
+            .call(
+                "/v1/control/removenode",
+                &serde_json::json!({ "name": name }),
+            )
+            .await?;
+        cluster_info(resp)
+    }
+
+    /// Creates the subnets, and returns their Ids in the order of the specs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subnets fail to be created.
+    pub async fn create_subnets(&self, specs: Vec<SubnetSpec>) -> Result<Vec<ids::Id>> {
+        log::info!("creating {} subnet(s) via {}", specs.len(), self.gateway);
+        let resp: CreateSubnetsResponse = self
+            .call(
+                "/v1/control/createsubnets",
+                &serde_json::json!({ "subnetSpecs": specs }),
+            )
+            .await?;
+        resp.subnet_ids
+            .iter()
+            .map(|id| {
+                id.parse().map_err(|e| Error::Other {
+                    message: format!("invalid subnet Id '{id}' ({e})"),
+                    retryable: false,
+                })
+            })
+            .collect()
+    }
+
+    /// Stops the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network is not started.
+    pub async fn stop(&self) -> Result<ClusterInfo> {
+        log::info!("stopping network via {}", self.gateway);
+        let resp: ClusterResponse = self
+            .call("/v1/control/stop", &serde_json::json!({}))
+            .await?;
+        cluster_info(resp)
+    }
+
+    /// Polls the health until all nodes are healthy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the network is not healthy in time.
+    pub async fn wait_for_healthy(
+        &self,
+        poll_interval: Duration,
+        poll_timeout: Duration,
+    ) -> Result<ClusterInfo> {
+        let start = Instant::now();
+        loop {
+            match self.health().await {
+                Ok(cluster) if cluster.healthy => {
+                    log::info!(
+                        "network is healthy with {} node(s)",
+                        cluster.node_names.len()
+                    );
+                    return Ok(cluster);
+                }
+                Ok(_) => log::info!("network is not healthy yet"),
+                Err(e) => log::warn!("failed to check network health '{e}'"),
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed.gt(&poll_timeout) {
+                return Err(Error::API {
+                    message: format!("network not healthy in {elapsed:?}"),
+                    retryable: true,
+                });
+            }
+            sleep(poll_interval).await;
+        }
+    }
+
+    /// Posts the JSON request to the gateway path.
+    async fn call<Req: Serialize + Sync, Resp: DeserializeOwned>(
+        &self,
+        path: &str,
+        req: &Req,
+    ) -> Result<Resp> {
+        let url = format!("{}{path}", self.gateway);
+        let body = serde_json::to_string(req).map_err(|e| Error::Other {
+            message: format!("failed serde_json::to_string '{e}'"),
+            retryable: false,
+        })?;
+
+        let resp = http::current()?
+            .post_json(&url, body)?
+            .send()
+            .await
+            .map_err(|e| Error::API {
+                message: format!("failed reqwest::Client.send '{e}'"),
+                retryable: e.is_connect() || e.is_timeout(),
+            })?;
+        let status = resp.status();
+        let out = resp.bytes().await.map_err(|e| Error::API {
+            message: format!("failed reqwest response bytes '{e}'"),
+            retryable: false,
+        })?;
+
+        if !status.is_success() {
+            let e: GatewayError = serde_json::from_slice(&out).unwrap_or_default();
+            return Err(Error::API {
+                message: format!(
+                    "{path} failed with status {status} (code {}, '{}')",
+                    e.code, e.message
+                ),
+                retryable: status.is_server_error(),
+            });
+        }
+        serde_json::from_slice(&out).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_slice '{e}'"),
+            retryable: false,
+        })
+    }
+}
+
+/// Returns the cluster of the response.
+fn cluster_info(resp: ClusterResponse) -> Result<ClusterInfo> {
+    resp.cluster_info
+        .ok_or_else(|| Error::UnexpectedNone("ClusterInfo".to_string()))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `tooling::network_runner::test_messages` --exact --show-output
+#[test]
+fn test_messages() {
+    let req = StartRequest {
+        exec_path: "/tmp/avalanchego".to_string(),
+        num_nodes: Some(5),
+        blockchain_specs: vec![BlockchainSpec {
+            vm_name: "subnetevm".to_string(),
+            genesis: "/tmp/genesis.json".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        serde_json::json!({
+            "execPath": "/tmp/avalanchego",
+            "numNodes": 5,
+            "blockchainSpecs": [{ "vmName": "subnetevm", "genesis": "/tmp/genesis.json" }],
+            "reassignPortsIfUsed": false,
+            "dynamicPorts": false,
+        })
+    );
+
+    // the gateway omits the default values
+    let resp: ClusterResponse = serde_json::from_str(
+        r#"{
+    "clusterInfo": {
+        "nodeNames": ["node1", "node2"],
+        "nodeInfos": {
+            "node2": { "name": "node2", "uri": "http://127.0.0.1:9652", "id": "NodeID-2" },
+            "node1": { "name": "node1", "uri": "http://127.0.0.1:9650", "id": "NodeID-1", "paused": true }
+        },
+        "pid": 123,
+        "healthy": true,
+        "customChains": {
+            "2Xa...": { "chainName": "subnetevm", "vmId": "srEX...", "pchainId": "29u...", "chainId": "2Xa..." }
+        }
+    }
+}"#,
+    )
+    .unwrap();
+    let cluster = cluster_info(resp).unwrap();
+    assert!(cluster.healthy);
+    assert_eq!(
+        cluster.uris(),
+        vec!["http://127.0.0.1:9650", "http://127.0.0.1:9652"]
+    );
+    assert!(cluster.node_infos["node1"].paused);
+    assert_eq!(cluster.custom_chains["2Xa..."].subnet_id, "29u...");
+
+    assert!(cluster_info(ClusterResponse::default()).is_err());
+}
@@ -0,0 +1,293 @@
+//! Scans the accepted X-chain transactions from the index API for the
+//! balance-affecting events of the watched addresses (e.g., for explorers
+//! or exchange deposit watchers).
+//! Requires the node to run with "--index-enabled".
+use std::collections::{HashMap, HashSet};
+
+pub use crate::avm::txs::decode::{decode_tx, DecodedTx, Input, Output};
+use crate::{
+    errors::{Error, Result},
+    ids::{self, short},
+    jsonrpc::{client::index as index_client, index},
+};
+use chrono::{DateTime, Utc};
+
+/// Default number of containers fetched per "index.getContainerRange" call.
+pub const DEFAULT_BATCH_SIZE: u64 = 1024;
+
+/// Represents how the event affects the balance of the address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// A new UTXO owned by the address.
+    Received,
+    /// A UTXO owned by the address was consumed.
+    /// Only reported for the UTXOs the scanner has seen being created.
+    Spent,
+    /// Funds of the address exported to another chain
+    /// (the UTXO is created in the shared memory of the destination chain).
+    Exported { destination_chain_id: ids::Id },
+}
+
+/// Represents a balance-affecting event of a watched address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub address: short::Id,
+    pub asset_id: ids::Id,
+    pub amount: u64,
+    /// Transaction that created or consumed the UTXO.
+    pub tx_id: ids::Id,
+    /// UTXO created or consumed ("tx ID", "output index").
+    pub utxo: (ids::Id, u32),
+    /// Position of the transaction in the index.
+    pub index: u64,
+    /// Time when the node accepted the transaction.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Walks the X-chain transaction index, and tracks the UTXOs of the watched
+/// addresses to report when they are spent.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    http_rpc: String,
+    watched: HashSet<short::Id>,
+    next_index: u64,
+    batch_size: u64,
+    /// Unspent outputs of the watched addresses, seen while scanning.
+    utxos: HashMap<(ids::Id, u32), Output>,
+}
+
+impl Scanner {
+    /// Creates a scanner starting from the first accepted transaction.
+    #[must_use]
+    pub fn new(http_rpc: &str, watched: &[short::Id]) -> Self {
+        Self {
+            http_rpc: http_rpc.to_string(),
+            watched: watched.iter().cloned().collect(),
+            next_index: 0,
+            batch_size: DEFAULT_BATCH_SIZE,
+            utxos: HashMap::new(),
+        }
+    }
+
+    /// Sets the index to resume scanning from (e.g., a saved "`next_index`").
+    /// The UTXOs created before are not tracked, so their spends are not reported.
+    #[must_use]
+    pub const fn start_index(mut self, start_index: u64) -> Self {
+        self.next_index = start_index;
+        self
+    }
+
+    #[must_use]
+    pub const fn batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Returns the index of the next transaction to scan.
+    #[must_use]
+    pub const fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Returns the tracked unspent outputs of the watched addresses.
+    #[must_use]
+    pub const fn utxos(&self) -> &HashMap<(ids::Id, u32), Output> {
+        &self.utxos
+    }
+
+    /// Fetches the next batch of accepted transactions and returns their events.
+    /// Returns no event once caught up with the last accepted transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index API fails or a transaction fails to decode.
+    pub async fn poll(&mut self) -> Result<Vec<Event>> {
+        let last = index_client::get_last_accepted(&self.http_rpc, index::Index::XTx).await?;
+        let Some(last) = last.result else {
+            return Err(api_error("getLastAccepted"));
+        };
+        if self.next_index > last.index {
+            return Ok(Vec::new());
+        }
+
+        let num_to_fetch = (last.index - self.next_index + 1).min(self.batch_size.max(1));
+        let resp = index_client::get_container_range(
+            &self.http_rpc,
+            index::Index::XTx,
+            self.next_index,
+            num_to_fetch,
+        )
+        .await?;
+        let Some(result) = resp.result else {
+            return Err(api_error("getContainerRange"));
+        };
+
+        let mut events = Vec::new();
+        for container in &result.containers {
+            events.extend(self.process(container)?);
+        }
+        Ok(events)
+    }
+
+    /// Decodes the accepted transaction and returns its events,
+    /// spends first and then the new outputs in the order of output index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the container fails to decode.
+    pub fn process(&mut self, container: &index::Container) -> Result<Vec<Event>> {
+        let d = container.decode_bytes().map_err(|e| Error::Other {
+            message: format!("failed to decode container {} '{e}'", container.id),
+            retryable: false,
+        })?;
+        let tx = decode_tx(&d)?;
+        let tx_id = container.id;
+
+        let mut events = Vec::new();
+        let mut push = |kind, output: &Output, utxo| {
+            for address in &output.addresses {
+                if self.watched.contains(address) {
+                    events.push(Event {
+                        kind,
+                        address: address.clone(),
+                        asset_id: output.asset_id,
+                        amount: output.amount,
+                        tx_id,
+                        utxo,
+                        index: container.index,
+                        timestamp: container.timestamp,
+                    });
+                }
+            }
+        };
+
+        for input in &tx.inputs {
+            if let Some(output) = self.utxos.remove(&input.utxo) {
+                push(EventKind::Spent, &output, input.utxo);
+            }
+        }
+        for (i, output) in tx.outputs.iter().enumerate() {
+            let Some(output) = output else { continue };
+            let utxo = (tx_id, u32::try_from(i)?);
+            push(EventKind::Received, output, utxo);
+            if output.addresses.iter().any(|a| self.watched.contains(a)) {
+                self.utxos.insert(utxo, output.clone());
+            }
+        }
+        if let Some(destination_chain_id) = tx.destination_chain_id {
+            // exported UTXOs are indexed after the outputs
+            let offset = tx.outputs.len();
+            for (i, output) in tx.exported_outputs.iter().enumerate() {
+                let Some(output) = output else { continue };
+                let utxo = (tx_id, u32::try_from(offset + i)?);
+                push(
+                    EventKind::Exported {
+                        destination_chain_id,
+                    },
+                    output,
+                    utxo,
+                );
+            }
+        }
+
+        self.next_index = self.next_index.max(container.index + 1);
+        Ok(events)
+    }
+}
+
+fn api_error(method: &str) -> Error {
+    Error::API {
+        message: format!("index.{method} returned no result"),
+        retryable: false,
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `tooling::scanner::test_scanner` --exact --show-output
+#[test]
+fn test_scanner() {
+    use crate::{codec, formatting, key, txs};
+
+    let watched = short::Id::from_slice(&[1; 20]);
+    let other = short::Id::from_slice(&[2; 20]);
+    let asset_id = ids::Id::from_slice(&[9; 32]);
+    let output = |amount: u64, addresses: Vec<short::Id>| txs::transferable::Output {
+        asset_id,
+        transfer_output: Some(key::secp256k1::txs::transfer::Output {
+            amount,
+            output_owners: key::secp256k1::txs::OutputOwners {
+                locktime: 0,
+                threshold: 1,
+                addresses,
+            },
+        }),
+        ..Default::default()
+    };
+    let container = |index: u64, id: ids::Id, tx: &txs::Tx| {
+        let packer = tx
+            .pack(&codec::x_registry().unwrap(), codec::VERSION, 0)
+            .unwrap();
+        let hex = formatting::encode_hex_with_checksum(&packer.take_bytes());
+        index::Container {
+            id,
+            bytes: format!("0x{hex}"),
+            timestamp: Utc::now(),
+            encoding: "hex".to_string(),
+            index,
+        }
+    };
+
+    let tx1 = ids::Id::from_slice(&[1; 32]);
+    let base_tx = txs::Tx {
+        transferable_outputs: Some(vec![
+            output(100, vec![other.clone()]),
+            output(50, vec![watched.clone()]),
+        ]),
+        transferable_inputs: Some(Vec::new()),
+        memo: Some(Vec::new()),
+        ..Default::default()
+    };
+    let decoded = decode_tx(
+        &base_tx
+            .pack(&codec::x_registry().unwrap(), codec::VERSION, 0)
+            .unwrap()
+            .take_bytes(),
+    )
+    .unwrap();
+    assert_eq!(decoded.type_id, 0);
+    assert_eq!(decoded.outputs.len(), 2);
+    assert_eq!(decoded.outputs[1].as_ref().unwrap().amount, 50);
+
+    let mut scanner = Scanner::new("http://127.0.0.1:9650", &[watched]);
+    let events = scanner.process(&container(0, tx1, &base_tx)).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, EventKind::Received);
+    assert_eq!(events[0].amount, 50);
+    assert_eq!(events[0].utxo, (tx1, 1));
+    assert_eq!(scanner.next_index(), 1);
+    assert_eq!(scanner.utxos().len(), 1);
+
+    // spends the watched UTXO
+    let tx2 = ids::Id::from_slice(&[2; 32]);
+    let spend_tx = txs::Tx {
+        transferable_outputs: Some(vec![output(40, vec![other])]),
+        transferable_inputs: Some(vec![txs::transferable::Input {
+            utxo_id: txs::utxo::Id::new(tx1.as_ref(), 1, false).unwrap(),
+            asset_id,
+            transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                amount: 50,
+                sig_indices: vec![0],
+            }),
+            ..Default::default()
+        }]),
+        memo: Some(Vec::new()),
+        ..Default::default()
+    };
+    let events = scanner.process(&container(1, tx2, &spend_tx)).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, EventKind::Spent);
+    assert_eq!(events[0].utxo, (tx1, 1));
+    assert_eq!(events[0].tx_id, tx2);
+    assert!(scanner.utxos().is_empty());
+    assert_eq!(scanner.next_index(), 2);
+}
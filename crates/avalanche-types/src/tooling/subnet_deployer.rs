@@ -0,0 +1,515 @@
+//! Deploys a subnet blockchain as one resumable workflow with the wallet
+//! P-chain builders: create subnet → add subnet validators → create chain
+//! → wait for the validators to validate the blockchain.
+//!
+//! Each issued transaction and completed step is recorded in a [`Checkpoint`]
+//! (synced to a file if configured), so rerunning a failed deployment waits
+//! for the issued transactions and skips the completed steps instead of
+//! creating another subnet or blockchain.
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+use crate::{
+    errors::{Error, Result},
+    ids::{self, node},
+    jsonrpc::client::p as client_p,
+    key, platformvm, wallet,
+};
+use serde::{Deserialize, Serialize};
+use tokio::time::Duration;
+
+/// Progress of the deployment, updated as each step completes.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Checkpoint {
+    /// Set once the create subnet transaction is issued, until committed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_subnet_tx_id: Option<ids::Id>,
+    /// Set once the subnet is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnet_id: Option<ids::Id>,
+    /// Validators added to the subnet, with the add transaction Id
+    /// (empty if the node was already a subnet validator).
+    #[serde(default)]
+    pub validators: BTreeMap<node::Id, ids::Id>,
+    /// Set once the create chain transaction is issued, until committed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_chain_tx_id: Option<ids::Id>,
+    /// Set once the blockchain is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockchain_id: Option<ids::Id>,
+    /// Set once all the validators validate the blockchain.
+    #[serde(default)]
+    pub validating: bool,
+}
+
+impl Checkpoint {
+    /// Loads the checkpoint from a file, or returns an empty checkpoint
+    /// if the file does not exist (i.e., a new deployment).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(file_path: &str) -> Result<Self> {
+        if !Path::new(file_path).exists() {
+            log::info!("no checkpoint found at {file_path}, starting a new deployment");
+            return Ok(Self::default());
+        }
+        log::info!("loading checkpoint from {file_path}");
+
+        let f = File::open(file_path).map_err(|e| Error::Other {
+            message: format!("failed to open {file_path} ({e})"),
+            retryable: false,
+        })?;
+        serde_json::from_reader(f).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_reader {e}"),
+            retryable: false,
+        })
+    }
+
+    /// Syncs the checkpoint to a file, by writing and flushing a temporary
+    /// file and renaming it into place, so a crash never leaves a partially
+    /// written checkpoint behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written to.
+    pub fn sync(&self, file_path: &str) -> std::io::Result<()> {
+        log::info!("syncing checkpoint to '{file_path}'");
+        let parent_dir = Path::new(file_path).parent();
+        if let Some(parent_dir) = parent_dir {
+            fs::create_dir_all(parent_dir)?;
+        }
+
+        let d = serde_json::to_vec_pretty(self).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("failed to serialize JSON {e}"),
+            )
+        })?;
+        let tmp_path = format!("{file_path}.tmp");
+        let mut f = File::create(&tmp_path)?;
+        f.write_all(&d)?;
+        f.sync_all()?;
+        drop(f);
+        fs::rename(&tmp_path, file_path)?;
+
+        // persists the rename itself
+        if let Some(parent_dir) = parent_dir.filter(|p| !p.as_os_str().is_empty()) {
+            File::open(parent_dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Returns "true" if all the steps are completed for the validators.
+    #[must_use]
+    pub fn is_complete(&self, validators: &[Validator]) -> bool {
+        self.subnet_id.is_some()
+            && validators
+                .iter()
+                .all(|v| self.validators.contains_key(&v.node_id))
+            && self.blockchain_id.is_some()
+            && self.validating
+    }
+}
+
+/// Node to add as a subnet validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validator {
+    /// Must already be a primary network validator.
+    pub node_id: node::Id,
+    /// Base HTTP URL of the node, to confirm the node validates the blockchain.
+    /// If none, the node is not polled.
+    pub http_rpc: Option<String>,
+}
+
+impl Validator {
+    #[must_use]
+    pub const fn new(node_id: node::Id, http_rpc: Option<String>) -> Self {
+        Self { node_id, http_rpc }
+    }
+}
+
+/// Represents the subnet blockchain deployment.
+#[derive(Clone, Debug)]
+pub struct Deployer<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: wallet::p::P<T>,
+
+    /// VM Id of the blockchain.
+    pub vm_id: ids::Id,
+    pub chain_name: String,
+    pub genesis_data: Vec<u8>,
+
+    pub validators: Vec<Validator>,
+    /// Weight of each subnet validator.
+    pub validator_weight: u64,
+    /// Subnet validation period in days.
+    pub validate_period_in_days: u64,
+
+    /// File to sync the checkpoint to after each step.
+    /// If none, the checkpoint is only kept in memory.
+    pub checkpoint_path: Option<String>,
+
+    /// Wait between each poll intervals for acceptance and validating.
+    pub poll_interval: Duration,
+    /// Maximum duration for polling each step.
+    pub poll_timeout: Duration,
+}
+
+impl<T> Deployer<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    #[must_use]
+    pub fn new(p: &wallet::p::P<T>) -> Self {
+        Self {
+            inner: p.clone(),
+            vm_id: ids::Id::empty(),
+            chain_name: String::new(),
+            genesis_data: Vec::new(),
+            validators: Vec::new(),
+            validator_weight: 1000,
+            validate_period_in_days: 14,
+            checkpoint_path: None,
+            poll_interval: Duration::from_secs(1),
+            poll_timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Sets the VM Id.
+    #[must_use]
+    pub const fn vm_id(mut self, vm_id: ids::Id) -> Self {
+        self.vm_id = vm_id;
+        self
+    }
+
+    /// Sets the VM Id from the VM name (non-hashed, up to 32 bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is longer than 32 bytes.
+    pub fn vm_name(mut self, vm_name: &str) -> Result<Self> {
+        if vm_name.len() > ids::LEN {
+            return Err(Error::Other {
+                message: format!(
+                    "non-hashed VM name must be <= 32 bytes, found {}",
+                    vm_name.len()
+                ),
+                retryable: false,
+            });
+        }
+        self.vm_id = ids::Id::from_slice(vm_name.as_bytes());
+        Ok(self)
+    }
+
+    /// Sets the chain name.
+    #[must_use]
+    pub fn chain_name(mut self, chain_name: String) -> Self {
+        self.chain_name = chain_name;
+        self
+    }
+
+    /// Sets the genesis.
+    #[must_use]
+    pub fn genesis_data(mut self, genesis_data: Vec<u8>) -> Self {
+        self.genesis_data = genesis_data;
+        self
+    }
+
+    /// Sets the subnet validators.
+    #[must_use]
+    pub fn validators(mut self, validators: Vec<Validator>) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    /// Sets the weight of each subnet validator.
+    #[must_use]
+    pub const fn validator_weight(mut self, validator_weight: u64) -> Self {
+        self.validator_weight = validator_weight;
+        self
+    }
+
+    /// Sets the subnet validation period in days.
+    #[must_use]
+    pub const fn validate_period_in_days(mut self, days: u64) -> Self {
+        self.validate_period_in_days = days;
+        self
+    }
+
+    /// Sets the checkpoint file.
+    #[must_use]
+    pub fn checkpoint_path(mut self, checkpoint_path: String) -> Self {
+        self.checkpoint_path = Some(checkpoint_path);
+        self
+    }
+
+    /// Sets the poll wait time between intervals.
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the poll timeout.
+    #[must_use]
+    pub const fn poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+
+    /// Runs the deployment from the checkpoint file (or from scratch if none),
+    /// and returns the final checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be loaded or any step fails.
+    /// Rerunning after the failure resumes from the last completed step.
+    pub async fn deploy(&self) -> Result<Checkpoint> {
+        let mut checkpoint = match &self.checkpoint_path {
+            Some(p) => Checkpoint::load(p)?,
+            None => Checkpoint::default(),
+        };
+        Box::pin(self.resume(&mut checkpoint)).await?;
+        Ok(checkpoint)
+    }
+
+    /// Runs the steps not completed in the checkpoint, updating (and syncing)
+    /// the checkpoint as each step completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid or any step fails.
+    pub async fn resume(&self, checkpoint: &mut Checkpoint) -> Result<()> {
+        self.validate()?;
+
+        let subnet_id = if let Some(subnet_id) = checkpoint.subnet_id {
+            log::info!("skipping create subnet, already created {subnet_id}");
+            subnet_id
+        } else {
+            // the subnet Id is the create subnet transaction Id
+            let tx_id = if let Some(tx_id) = checkpoint.create_subnet_tx_id {
+                log::info!("skipping create subnet, already issued {tx_id}");
+                tx_id
+            } else {
+                let tx_id = self.inner.create_subnet().issue().await?;
+                checkpoint.create_subnet_tx_id = Some(tx_id);
+                self.sync(checkpoint)?;
+                tx_id
+            };
+            if !self.wait_for_committed(tx_id).await? {
+                checkpoint.create_subnet_tx_id = None;
+                self.sync(checkpoint)?;
+                return Err(Error::Other {
+                    message: format!("create subnet {tx_id} not committed, rerun to issue again"),
+                    retryable: true,
+                });
+            }
+
+            log::info!("created subnet {tx_id}");
+            checkpoint.create_subnet_tx_id = None;
+            checkpoint.subnet_id = Some(tx_id);
+            self.sync(checkpoint)?;
+            tx_id
+        };
+
+        for v in &self.validators {
+            if checkpoint.validators.contains_key(&v.node_id) {
+                log::info!("skipping add subnet validator, already added {}", v.node_id);
+                continue;
+            }
+            // safe to issue again after a restart, the node already
+            // validating the subnet is skipped
+            let (tx_id, added) = Box::pin(
+                self.inner
+                    .add_subnet_validator()
+                    .node_id(v.node_id)
+                    .subnet_id(subnet_id)
+                    .weight(self.validator_weight)
+                    .validate_period_in_days(self.validate_period_in_days, 60)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .check_acceptance(true)
+                    .issue(),
+            )
+            .await?;
+            if added {
+                log::info!("added subnet validator {} ({tx_id})", v.node_id);
+            } else {
+                log::info!("{} is already a subnet validator", v.node_id);
+            }
+            checkpoint.validators.insert(v.node_id, tx_id);
+            self.sync(checkpoint)?;
+        }
+
+        let blockchain_id = if let Some(blockchain_id) = checkpoint.blockchain_id {
+            log::info!("skipping create chain, already created {blockchain_id}");
+            blockchain_id
+        } else {
+            // the blockchain Id is the create chain transaction Id
+            let tx_id = if let Some(tx_id) = checkpoint.create_chain_tx_id {
+                log::info!("skipping create chain, already issued {tx_id}");
+                tx_id
+            } else {
+                let tx_id = self
+                    .inner
+                    .create_chain()
+                    .subnet_id(subnet_id)
+                    .vm_id(self.vm_id)
+                    .chain_name(self.chain_name.clone())
+                    .genesis_data(self.genesis_data.clone())
+                    .issue()
+                    .await?;
+                checkpoint.create_chain_tx_id = Some(tx_id);
+                self.sync(checkpoint)?;
+                tx_id
+            };
+            if !self.wait_for_committed(tx_id).await? {
+                checkpoint.create_chain_tx_id = None;
+                self.sync(checkpoint)?;
+                return Err(Error::Other {
+                    message: format!("create chain {tx_id} not committed, rerun to issue again"),
+                    retryable: true,
+                });
+            }
+
+            log::info!("created blockchain {tx_id}");
+            checkpoint.create_chain_tx_id = None;
+            checkpoint.blockchain_id = Some(tx_id);
+            self.sync(checkpoint)?;
+            tx_id
+        };
+
+        if checkpoint.validating {
+            log::info!("skipping waiting, blockchain {blockchain_id} already validating");
+            return Ok(());
+        }
+        for v in &self.validators {
+            let Some(http_rpc) = &v.http_rpc else {
+                log::info!("skipping waiting for {} (no endpoint)", v.node_id);
+                continue;
+            };
+            log::info!("waiting for {} to validate {blockchain_id}", v.node_id);
+            self.inner
+                .inner
+                .scoped(client_p::wait_for_blockchain_validating(
+                    http_rpc,
+                    blockchain_id,
+                    self.poll_interval,
+                    self.poll_timeout,
+                ))
+                .await?;
+        }
+        checkpoint.validating = true;
+        self.sync(checkpoint)?;
+
+        log::info!("deployed blockchain {blockchain_id} in subnet {subnet_id}");
+        Ok(())
+    }
+
+    /// Checks the deployment configuration before issuing any transaction.
+    fn validate(&self) -> Result<()> {
+        if self.vm_id.is_empty() {
+            return Err(Error::Other {
+                message: "empty VM Id".to_string(),
+                retryable: false,
+            });
+        }
+        if self.chain_name.is_empty() {
+            return Err(Error::Other {
+                message: "empty chain name".to_string(),
+                retryable: false,
+            });
+        }
+        if self.validators.is_empty() {
+            return Err(Error::Other {
+                message: "no subnet validator".to_string(),
+                retryable: false,
+            });
+        }
+        Ok(())
+    }
+
+    /// Waits for the issued transaction to be decided, and returns "false"
+    /// if aborted or dropped (i.e., safe to issue again).
+    async fn wait_for_committed(&self, tx_id: ids::Id) -> Result<bool> {
+        let (_, http_rpc) = self.inner.inner.pick_base_http_url();
+        let status = self
+            .inner
+            .inner
+            .scoped(client_p::wait_for_tx_decided(
+                &http_rpc,
+                tx_id,
+                self.poll_interval,
+                self.poll_timeout,
+            ))
+            .await?;
+        Ok(status == platformvm::txs::status::Status::Committed)
+    }
+
+    /// Syncs the checkpoint to the file, if configured.
+    fn sync(&self, checkpoint: &Checkpoint) -> Result<()> {
+        let Some(p) = &self.checkpoint_path else {
+            return Ok(());
+        };
+        checkpoint.sync(p).map_err(|e| Error::Other {
+            message: format!("failed to sync checkpoint to {p} ({e})"),
+            retryable: false,
+        })
+    }
+}
+
+impl<T> wallet::p::P<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    /// Returns the subnet blockchain deployer.
+    #[must_use]
+    pub fn subnet_deployer(&self) -> Deployer<T> {
+        Deployer::new(self)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client,wallet -- `tooling::subnet_deployer::test_checkpoint` --exact --show-output
+#[test]
+fn test_checkpoint() {
+    use std::str::FromStr;
+
+    let node_id = node::Id::from_str("NodeID-7Xhw2mDxuDS44j42TCB6U5579esbSt3Lg").unwrap();
+    let validators = vec![Validator::new(node_id, None)];
+
+    let mut checkpoint = Checkpoint::default();
+    assert!(!checkpoint.is_complete(&validators));
+
+    checkpoint.subnet_id = Some(ids::Id::from_slice(&[1; 32]));
+    checkpoint.validators.insert(node_id, ids::Id::empty());
+    checkpoint.blockchain_id = Some(ids::Id::from_slice(&[2; 32]));
+    assert!(!checkpoint.is_complete(&validators));
+    checkpoint.validating = true;
+    assert!(checkpoint.is_complete(&validators));
+
+    // a validator added to the configuration after the deployment
+    let mut more = validators;
+    more.push(Validator::new(node::Id::from_slice(&[3; 20]), None));
+    assert!(!checkpoint.is_complete(&more));
+
+    let dir = tempfile::tempdir().unwrap();
+    let p = dir.path().join("deploy").join("checkpoint.json");
+    let p = p.to_str().unwrap();
+    assert_eq!(Checkpoint::load(p).unwrap(), Checkpoint::default());
+    checkpoint.sync(p).unwrap();
+    assert_eq!(Checkpoint::load(p).unwrap(), checkpoint);
+
+    // the issued transactions are kept across the restarts
+    checkpoint.create_chain_tx_id = Some(ids::Id::from_slice(&[4; 32]));
+    checkpoint.sync(p).unwrap();
+    assert_eq!(Checkpoint::load(p).unwrap(), checkpoint);
+    assert!(!Path::new(&format!("{p}.tmp")).exists());
+}
@@ -0,0 +1,262 @@
+//! Watches the validator set of a subnet (or the primary network) by polling
+//! "platform.getValidatorsAt" as the P-chain height advances, and streams the
+//! differences between the sets (e.g., for relayers and warp signature
+//! aggregators to keep their validator caches fresh).
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{
+    errors::{Error, Result},
+    ids::{self, node},
+    jsonrpc::{client::p as client_p, platformvm::ValidatorAt},
+};
+use futures::{stream, Stream};
+use tokio::time::{sleep, Duration};
+
+/// Represents a change of a single validator between two heights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The validator joined the set.
+    Added {
+        node_id: node::Id,
+        validator: ValidatorAt,
+    },
+    /// The validator left the set.
+    Removed {
+        node_id: node::Id,
+        validator: ValidatorAt,
+    },
+    /// The validator stayed in the set with a different weight.
+    WeightChanged {
+        node_id: node::Id,
+        old_weight: u64,
+        new_weight: u64,
+    },
+}
+
+impl Change {
+    #[must_use]
+    pub const fn node_id(&self) -> &node::Id {
+        match self {
+            Self::Added { node_id, .. }
+            | Self::Removed { node_id, .. }
+            | Self::WeightChanged { node_id, .. } => node_id,
+        }
+    }
+}
+
+/// Represents the changes of the validator set between two heights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    /// None for the first diff of the watch, which adds the whole set.
+    pub from_height: Option<u64>,
+    pub to_height: u64,
+    /// Changes ordered by the node Id.
+    pub changes: Vec<Change>,
+}
+
+/// Returns the changes from the previous to the next validator set,
+/// ordered by the node Id.
+#[must_use]
+pub fn diff<S: BuildHasher, T: BuildHasher>(
+    prev: &HashMap<node::Id, ValidatorAt, S>,
+    next: &HashMap<node::Id, ValidatorAt, T>,
+) -> Vec<Change> {
+    let mut changes = BTreeMap::new();
+    for (node_id, v) in next {
+        match prev.get(node_id) {
+            None => {
+                changes.insert(
+                    *node_id,
+                    Change::Added {
+                        node_id: *node_id,
+                        validator: v.clone(),
+                    },
+                );
+            }
+            Some(old) if old.weight != v.weight => {
+                changes.insert(
+                    *node_id,
+                    Change::WeightChanged {
+                        node_id: *node_id,
+                        old_weight: old.weight,
+                        new_weight: v.weight,
+                    },
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for (node_id, v) in prev {
+        if !next.contains_key(node_id) {
+            changes.insert(
+                *node_id,
+                Change::Removed {
+                    node_id: *node_id,
+                    validator: v.clone(),
+                },
+            );
+        }
+    }
+    changes.into_values().collect()
+}
+
+/// Options of [`watch`].
+#[derive(Debug, Clone, Copy)]
+pub struct WatchOptions {
+    /// Subnet to watch. If none, watches the primary network validators.
+    pub subnet_id: Option<ids::Id>,
+    /// Height to start watching from. If none, starts from the current height.
+    pub start_height: Option<u64>,
+    /// Wait between each "platform.getHeight" poll.
+    pub poll_interval: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            subnet_id: None,
+            start_height: None,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// State of the watch stream.
+struct Watch {
+    /// Node to poll.
+    http_rpc: String,
+    /// Watch options.
+    opts: WatchOptions,
+    /// Height of the last fetched set (None before the first set).
+    height: Option<u64>,
+    /// Last fetched set.
+    validators: HashMap<node::Id, ValidatorAt>,
+    /// "true" once the first poll is made.
+    polled: bool,
+}
+
+/// Streams the validator set diffs as the P-chain height advances.
+/// The first diff adds the whole set at the start height, so the caches
+/// can be built from the stream alone. The heights accepted between two
+/// polls are coalesced into one diff, and polls without any change in
+/// the set are not emitted.
+///
+/// The stream never ends. Failed polls are yielded as errors and retried
+/// after the poll interval, so the caller decides whether to stop.
+pub fn watch(http_rpc: &str, opts: WatchOptions) -> impl Stream<Item = Result<Diff>> {
+    let state = Watch {
+        http_rpc: http_rpc.to_string(),
+        opts,
+        height: None,
+        validators: HashMap::new(),
+        polled: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            // no wait for the first poll
+            if state.polled {
+                sleep(state.opts.poll_interval).await;
+            }
+            state.polled = true;
+
+            match poll(&mut state).await {
+                Ok(Some(diff)) => return Some((Ok(diff), state)),
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("failed to poll validators '{e}'");
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+/// Fetches the set at the next height to diff, and returns the diff if
+/// the set changed since the last emitted set.
+async fn poll(state: &mut Watch) -> Result<Option<Diff>> {
+    let to_height = if let (None, Some(start_height)) = (state.height, state.opts.start_height) {
+        start_height
+    } else {
+        let resp = client_p::get_height(&state.http_rpc).await?;
+        let height = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetHeightResult".to_string()))?
+            .height;
+        if state.height.is_some_and(|h| height <= h) {
+            return Ok(None);
+        }
+        height
+    };
+
+    let resp =
+        client_p::get_validators_at(&state.http_rpc, to_height, state.opts.subnet_id.as_ref())
+            .await?;
+    let validators = resp
+        .result
+        .ok_or_else(|| Error::UnexpectedNone("GetValidatorsAtResult".to_string()))?
+        .validators;
+
+    let changes = diff(&state.validators, &validators);
+    let from_height = state.height.replace(to_height);
+    state.validators = validators;
+    if from_height.is_some() && changes.is_empty() {
+        log::debug!("no validator change at height {to_height}");
+        return Ok(None);
+    }
+    log::info!(
+        "{} validator change(s) from height {from_height:?} to {to_height}",
+        changes.len()
+    );
+    Ok(Some(Diff {
+        from_height,
+        to_height,
+        changes,
+    }))
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features jsonrpc_client -- `tooling::validator_watch::test_diff` --exact --show-output
+#[test]
+fn test_diff() {
+    let (a, b, c) = (
+        node::Id::from_slice(&[1; 20]),
+        node::Id::from_slice(&[2; 20]),
+        node::Id::from_slice(&[3; 20]),
+    );
+    let weight = |weight| ValidatorAt {
+        public_key: None,
+        weight,
+    };
+
+    let prev = HashMap::from([(a, weight(10)), (b, weight(20))]);
+    let next = HashMap::from([(b, weight(25)), (c, weight(30))]);
+
+    assert!(diff(&prev, &prev).is_empty());
+    assert_eq!(
+        diff(&prev, &next),
+        vec![
+            Change::Removed {
+                node_id: a,
+                validator: weight(10),
+            },
+            Change::WeightChanged {
+                node_id: b,
+                old_weight: 20,
+                new_weight: 25,
+            },
+            Change::Added {
+                node_id: c,
+                validator: weight(30),
+            },
+        ]
+    );
+
+    let changes = diff(&HashMap::new(), &next);
+    assert_eq!(changes.len(), 2);
+    assert!(changes
+        .iter()
+        .all(|change| matches!(change, Change::Added { .. })));
+    assert_eq!(changes[0].node_id(), &b);
+}
@@ -0,0 +1,142 @@
+//! Typed memo data for the base transaction (e.g., a payment reference
+//! or an "OP_RETURN"-style payload), validated when the memo is built.
+use crate::errors::{Error, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Maximum memo size accepted by the X-chain and P-chain.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#MaxMemoSize>
+pub const MAX_LEN: usize = 256;
+
+/// Represents the memo bytes of at most "`MAX_LEN`" bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Memo(Vec<u8>);
+
+impl Memo {
+    /// Creates the memo from the raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes exceed "`MAX_LEN`".
+    pub fn new(d: impl Into<Vec<u8>>) -> Result<Self> {
+        let d = d.into();
+        if d.len() > MAX_LEN {
+            return Err(Error::Other {
+                message: format!("memo size {} exceeds the limit {MAX_LEN}", d.len()),
+                retryable: false,
+            });
+        }
+        Ok(Self(d))
+    }
+
+    /// Creates the memo from the UTF-8 text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text exceeds "`MAX_LEN`" bytes.
+    pub fn from_text(s: &str) -> Result<Self> {
+        Self::new(s.as_bytes())
+    }
+
+    /// Creates the memo from the value encoded as compact JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value fails to encode or exceeds "`MAX_LEN`" bytes.
+    pub fn from_json<T: Serialize>(v: &T) -> Result<Self> {
+        let d = serde_json::to_vec(v).map_err(|e| Error::Other {
+            message: format!("failed serde_json::to_vec '{e}'"),
+            retryable: false,
+        })?;
+        Self::new(d)
+    }
+
+    /// Decodes the memo as UTF-8 text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the memo is not valid UTF-8.
+    pub fn to_text(&self) -> Result<&str> {
+        std::str::from_utf8(&self.0).map_err(|e| Error::Other {
+            message: format!("memo is not UTF-8 '{e}'"),
+            retryable: false,
+        })
+    }
+
+    /// Decodes the memo as the JSON-encoded value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the memo is not the JSON of "T".
+    pub fn to_json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_slice(&self.0).map_err(|e| Error::Other {
+            message: format!("failed serde_json::from_slice '{e}'"),
+            retryable: false,
+        })
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl TryFrom<Vec<u8>> for Memo {
+    type Error = Error;
+
+    fn try_from(d: Vec<u8>) -> Result<Self> {
+        Self::new(d)
+    }
+}
+
+impl From<Memo> for Vec<u8> {
+    fn from(memo: Memo) -> Self {
+        memo.0
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `txs::memo::test_memo` --exact --show-output
+#[test]
+fn test_memo() {
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+    struct Payment {
+        invoice: String,
+        amount: u64,
+    }
+
+    let payment = Payment {
+        invoice: String::from("INV-0001"),
+        amount: 1000,
+    };
+    let memo = Memo::from_json(&payment).unwrap();
+    assert_eq!(memo.to_json::<Payment>().unwrap(), payment);
+    assert_eq!(
+        memo.to_text().unwrap(),
+        r#"{"invoice":"INV-0001","amount":1000}"#
+    );
+
+    let mut tx = crate::txs::Tx::default();
+    tx.set_memo(memo.clone());
+    assert_eq!(tx.memo().unwrap(), Some(memo));
+
+    assert!(Memo::new(vec![0; MAX_LEN]).is_ok());
+    assert!(Memo::new(vec![0; MAX_LEN + 1]).is_err());
+    assert!(Memo::from_text(&"a".repeat(MAX_LEN + 1)).is_err());
+    assert!(Memo::new(vec![0xff]).unwrap().to_text().is_err());
+}
@@ -1,4 +1,5 @@
 //! Definitions of Avalanche transaction types.
+pub mod memo;
 pub mod raw;
 pub mod transferable;
 pub mod utxo;
@@ -6,7 +7,7 @@ pub mod utxo;
 use super::{
     codec::{self, serde::hex_0x_bytes::Hex0xBytes},
     errors::{Error, Result},
-    hash, ids, key, packer, platformvm,
+    hash, ids, packer,
 };
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -17,6 +18,16 @@ use serde_with::serde_as;
 pub struct Tx {
     #[serde(skip)]
     pub metadata: Option<Metadata>, // skip serialization due to serialize:"false"
+    /// Codec version to pack the transaction with for signing
+    /// (e.g., the version of the historical transaction it was unpacked from).
+    /// Kept in JSON (e.g., when passed between the co-signers)
+    /// unless the default version.
+    #[serde(
+        rename = "codecVersion",
+        default = "default_codec_version",
+        skip_serializing_if = "is_default_codec_version"
+    )]
+    pub codec_version: u16,
 
     #[serde(rename = "networkID")]
     pub network_id: u32,
@@ -37,6 +48,7 @@ impl Default for Tx {
     fn default() -> Self {
         Self {
             metadata: None,
+            codec_version: codec::VERSION,
             network_id: 0,
             blockchain_id: ids::Id::empty(),
             transferable_inputs: None,
@@ -46,12 +58,36 @@ impl Default for Tx {
     }
 }
 
+/// Defaults the codec version when deserialized from JSON.
+const fn default_codec_version() -> u16 {
+    codec::VERSION
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_default_codec_version(v: &u16) -> bool {
+    *v == codec::VERSION
+}
+
 impl Tx {
     #[must_use]
     pub fn type_name() -> String {
         "avm.BaseTx".to_string()
     }
 
+    /// Sets the memo, already validated against "`memo::MAX_LEN`".
+    pub fn set_memo(&mut self, memo: memo::Memo) {
+        self.memo = Some(memo.into_bytes());
+    }
+
+    /// Returns the memo, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the memo exceeds "`memo::MAX_LEN`".
+    pub fn memo(&self) -> Result<Option<memo::Memo>> {
+        self.memo.clone().map(memo::Memo::new).transpose()
+    }
+
     /// 返回类型ID。
     ///
     /// # Panics
@@ -83,23 +119,22 @@ impl Tx {
     /// ref. "avalanchego/codec/linearcodec.linearCodec.MarshalInto"
     /// ref. "avalanchego/codec/reflectcodec.genericCodec.MarshalInto"
     ///
-    /// # Panics
-    ///
-    /// 如果 `transferable_outputs` 或 `transferable_inputs` 或 `memo` 为 `Some` 但内部值为 `None`，则会 panic。
+    /// The nested types (e.g., the outputs and inputs) are packed with
+    /// their type Ids in the codec version of the registry.
     ///
     /// # Errors
     ///
     /// 如果打包过程中出现错误，则返回错误。
-    /// Packs the transaction into a byte array.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the packing fails.
-    #[allow(clippy::too_many_lines)]
-    pub fn pack(&self, codec_version: u16, type_id: u32) -> Result<packer::Packer> {
+    pub fn pack(
+        &self,
+        registry: &codec::Registry,
+        codec_version: u16,
+        type_id: u32,
+    ) -> Result<packer::Packer> {
         // ref. "avalanchego/codec.manager.Marshal", "vms/avm.newCustomCodecs"
         // ref. "math.MaxInt32" and "constants.DefaultByteSliceCap" in Go
-        let packer = packer::Packer::new((1 << 31) - 1, 128);
+        // reuse the pooled buffers to save the allocations in bulk signing
+        let packer = packer::pool::shared().get((1 << 31) - 1);
 
         // codec version
         // ref. "avalanchego/codec.manager.Marshal"
@@ -118,114 +153,7 @@ impl Tx {
             packer.pack_u32(u32::try_from(transferable_outputs.len())?)?;
 
             for transferable_output in transferable_outputs {
-                // "TransferableOutput.Asset" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableOutput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#Asset
-                packer.pack_bytes(transferable_output.asset_id.as_ref())?;
-
-                // fx_id is serialize:"false" thus skipping serialization
-
-                // decide the type
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
-                if transferable_output.transfer_output.is_none()
-                    && transferable_output.stakeable_lock_out.is_none()
-                {
-                    return Err(Error::Other {
-                        message: "unexpected Nones in TransferableOutput transfer_output and stakeable_lock_out".to_string(),
-                        retryable: false,
-                    });
-                }
-                let type_id_transferable_out = {
-                    if transferable_output.transfer_output.is_some() {
-                        key::secp256k1::txs::transfer::Output::type_id()
-                    } else {
-                        platformvm::txs::StakeableLockOut::type_id()
-                    }
-                };
-                // marshal type ID for "key::secp256k1::txs::transfer::Output" or "platformvm::txs::StakeableLockOut"
-                packer.pack_u32(type_id_transferable_out)?;
-
-                match type_id_transferable_out {
-                    7 => {
-                        // "key::secp256k1::txs::transfer::Output"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        let transfer_output = transferable_output
-                            .transfer_output
-                            .clone()
-                            .ok_or_else(|| Error::Other {
-                                message: "transfer_output is None".to_string(),
-                                retryable: false,
-                            })?;
-
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(transfer_output.amount)?;
-
-                        // "secp256k1fx.TransferOutput.OutputOwners" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferOutput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        packer.pack_u64(transfer_output.output_owners.locktime)?;
-                        packer.pack_u32(transfer_output.output_owners.threshold)?;
-                        packer.pack_u32(u32::try_from(
-                            transfer_output.output_owners.addresses.len(),
-                        )?)?;
-                        for addr in &transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    22 => {
-                        // "platformvm::txs::StakeableLockOut"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        let stakeable_lock_out = transferable_output
-                            .stakeable_lock_out
-                            .clone()
-                            .ok_or_else(|| Error::Other {
-                                message: "stakeable_lock_out is None".to_string(),
-                                retryable: false,
-                            })?;
-
-                        // marshal "platformvm::txs::StakeableLockOut.locktime" field
-                        packer.pack_u64(stakeable_lock_out.locktime)?;
-
-                        // secp256k1fx.TransferOutput type ID
-                        packer.pack_u32(7)?;
-
-                        // "platformvm.StakeableLockOut.TransferOutput" is struct and serialize:"true"
-                        // but embedded inline in the struct "StakeableLockOut"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#OutputOwners
-                        //
-                        // marshal "secp256k1fx.TransferOutput.Amt" field
-                        packer.pack_u64(stakeable_lock_out.transfer_output.amount)?;
-                        packer
-                            .pack_u64(stakeable_lock_out.transfer_output.output_owners.locktime)?;
-                        packer
-                            .pack_u32(stakeable_lock_out.transfer_output.output_owners.threshold)?;
-                        packer.pack_u32(u32::try_from(
-                            stakeable_lock_out
-                                .transfer_output
-                                .output_owners
-                                .addresses
-                                .len(),
-                        )?)?;
-                        for addr in &stakeable_lock_out.transfer_output.output_owners.addresses {
-                            packer.pack_bytes(addr.as_ref())?;
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Other {
-                            message: format!(
-                            "unexpected type ID {type_id_transferable_out} for TransferableOutput"
-                        ),
-                            retryable: false,
-                        })
-                    }
-                }
+                transferable_output.pack_into(&packer, registry, codec_version)?;
             }
         } else {
             packer.pack_u32(0_u32)?;
@@ -236,113 +164,7 @@ impl Tx {
             packer.pack_u32(u32::try_from(transferable_inputs.len())?)?;
 
             for transferable_input in transferable_inputs {
-                // "TransferableInput.UTXOID" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableInput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#UTXOID
-                packer.pack_bytes(transferable_input.utxo_id.tx_id.as_ref())?;
-                packer.pack_u32(transferable_input.utxo_id.output_index)?;
-
-                // "TransferableInput.Asset" is struct and serialize:"true"
-                // but embedded inline in the struct "TransferableInput"
-                // so no need to encode type ID
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#Asset
-                packer.pack_bytes(transferable_input.asset_id.as_ref())?;
-
-                // fx_id is serialize:"false" thus skipping serialization
-
-                // decide the type
-                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
-                if transferable_input.transfer_input.is_none()
-                    && transferable_input.stakeable_lock_in.is_none()
-                {
-                    return Err(Error::Other {
-                        message: "unexpected Nones in TransferableInput transfer_input and stakeable_lock_in".to_string(),
-                        retryable: false,
-                    });
-                }
-                let type_id_transferable_in = {
-                    if transferable_input.transfer_input.is_some() {
-                        key::secp256k1::txs::transfer::Input::type_id()
-                    } else {
-                        platformvm::txs::StakeableLockIn::type_id()
-                    }
-                };
-                // marshal type ID for "key::secp256k1::txs::transfer::Input" or "platformvm::txs::StakeableLockIn"
-                packer.pack_u32(type_id_transferable_in)?;
-
-                match type_id_transferable_in {
-                    5 => {
-                        // "key::secp256k1::txs::transfer::Input"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        let transfer_input =
-                            transferable_input.transfer_input.clone().ok_or_else(|| {
-                                Error::Other {
-                                    message: "transfer_input is None".to_string(),
-                                    retryable: false,
-                                }
-                            })?;
-
-                        // marshal "secp256k1fx.TransferInput.Amt" field
-                        packer.pack_u64(transfer_input.amount)?;
-
-                        // "secp256k1fx.TransferInput.Input" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferInput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        packer.pack_u32(u32::try_from(transfer_input.sig_indices.len())?)?;
-                        for idx in &transfer_input.sig_indices {
-                            packer.pack_u32(*idx)?;
-                        }
-                    }
-                    21 => {
-                        // "platformvm::txs::StakeableLockIn"
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockIn
-                        let stakeable_lock_in = transferable_input
-                            .stakeable_lock_in
-                            .clone()
-                            .ok_or_else(|| Error::Other {
-                                message: "stakeable_lock_in is None".to_string(),
-                                retryable: false,
-                            })?;
-
-                        // marshal "platformvm::txs::StakeableLockIn.locktime" field
-                        packer.pack_u64(stakeable_lock_in.locktime)?;
-
-                        // "platformvm.StakeableLockIn.TransferableIn" is struct and serialize:"true"
-                        // but embedded inline in the struct "StakeableLockIn"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockIn
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        //
-                        // marshal "secp256k1fx.TransferInput.Amt" field
-                        packer.pack_u64(stakeable_lock_in.transfer_input.amount)?;
-                        //
-                        // "secp256k1fx.TransferInput.Input" is struct and serialize:"true"
-                        // but embedded inline in the struct "TransferInput"
-                        // so no need to encode type ID
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
-                        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#Input
-                        packer.pack_u32(u32::try_from(
-                            stakeable_lock_in.transfer_input.sig_indices.len(),
-                        )?)?;
-                        for idx in &stakeable_lock_in.transfer_input.sig_indices {
-                            packer.pack_u32(*idx)?;
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Other {
-                            message: format!(
-                                "unexpected type ID {type_id_transferable_in} for TransferableInput"
-                            ),
-                            retryable: false,
-                        })
-                    }
-                }
+                transferable_input.pack_into(&packer, registry, codec_version)?;
             }
         } else {
             packer.pack_u32(0_u32)?;
@@ -414,7 +236,7 @@ fn test_base_tx_serialization() {
         ..Tx::default()
     };
     let unsigned_tx_packer = unsigned_tx
-        .pack(0, Tx::type_id())
+        .pack(&codec::x_registry().unwrap(), 0, Tx::type_id())
         .expect("failed to pack unsigned_tx");
     let unsigned_tx_bytes = unsigned_tx_packer.take_bytes();
 
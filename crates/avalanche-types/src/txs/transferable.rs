@@ -1,6 +1,10 @@
 use std::cmp::Ordering;
 
-use crate::{ids, key, platformvm, txs};
+use crate::{
+    codec,
+    errors::{Error, Result},
+    ids, key, packer, platformvm, txs,
+};
 use serde::{Deserialize, Serialize};
 
 /// 可转移的输出。
@@ -42,6 +46,66 @@ impl Default for Output {
     }
 }
 
+impl Output {
+    /// Packs the output with the type Ids of the codec version.
+    /// "`fx_id`" is serialize:"false" thus skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither output is set, or a type is not
+    /// registered in the codec version.
+    pub fn pack_into(
+        &self,
+        packer: &packer::Packer,
+        registry: &codec::Registry,
+        codec_version: u16,
+    ) -> Result<()> {
+        // "TransferableOutput.Asset" is struct and serialize:"true"
+        // but embedded inline in the struct "TransferableOutput"
+        // so no need to encode type ID
+        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableOutput
+        packer.pack_bytes(self.asset_id.as_ref())?;
+
+        let transfer_output_type_id = registry.resolve_type_id(
+            codec_version,
+            &key::secp256k1::txs::transfer::Output::type_name(),
+        )?;
+        let transfer_output = match (&self.transfer_output, &self.stakeable_lock_out) {
+            (Some(transfer_output), _) => transfer_output,
+            (None, Some(stakeable_lock_out)) => {
+                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
+                packer.pack_u32(registry.resolve_type_id(
+                    codec_version,
+                    &platformvm::txs::StakeableLockOut::type_name(),
+                )?)?;
+                packer.pack_u64(stakeable_lock_out.locktime)?;
+                &stakeable_lock_out.transfer_output
+            }
+            (None, None) => {
+                return Err(Error::Other {
+                    message: "unexpected Nones in TransferableOutput transfer_output and stakeable_lock_out".to_string(),
+                    retryable: false,
+                });
+            }
+        };
+
+        // "secp256k1fx.TransferOutput.OutputOwners" is struct and serialize:"true"
+        // but embedded inline in the struct "TransferOutput"
+        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferOutput
+        packer.pack_u32(transfer_output_type_id)?;
+        packer.pack_u64(transfer_output.amount)?;
+        packer.pack_u64(transfer_output.output_owners.locktime)?;
+        packer.pack_u32(transfer_output.output_owners.threshold)?;
+        packer.pack_u32(u32::try_from(
+            transfer_output.output_owners.addresses.len(),
+        )?)?;
+        for addr in &transfer_output.output_owners.addresses {
+            packer.pack_bytes(addr.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#SortTransferableOutputs>
 impl Ord for Output {
     fn cmp(&self, other: &Self) -> Ordering {
@@ -421,6 +485,69 @@ impl Default for Input {
     }
 }
 
+impl Input {
+    /// Packs the input with the type Ids of the codec version.
+    /// "`fx_id`" is serialize:"false" thus skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither input is set, or a type is not
+    /// registered in the codec version.
+    pub fn pack_into(
+        &self,
+        packer: &packer::Packer,
+        registry: &codec::Registry,
+        codec_version: u16,
+    ) -> Result<()> {
+        // "TransferableInput.UTXOID" and "TransferableInput.Asset" are structs
+        // and serialize:"true" but embedded inline in the struct "TransferableInput"
+        // so no need to encode type ID
+        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#TransferableInput
+        packer.pack_bytes(self.utxo_id.tx_id.as_ref())?;
+        packer.pack_u32(self.utxo_id.output_index)?;
+        packer.pack_bytes(self.asset_id.as_ref())?;
+
+        let transfer_input_type_id = registry.resolve_type_id(
+            codec_version,
+            &key::secp256k1::txs::transfer::Input::type_name(),
+        )?;
+        let transfer_input = match (&self.transfer_input, &self.stakeable_lock_in) {
+            (Some(transfer_input), _) => {
+                packer.pack_u32(transfer_input_type_id)?;
+                transfer_input
+            }
+            (None, Some(stakeable_lock_in)) => {
+                // the embedded "TransferableIn" is a struct, thus no type ID
+                // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockIn
+                packer.pack_u32(registry.resolve_type_id(
+                    codec_version,
+                    &platformvm::txs::StakeableLockIn::type_name(),
+                )?)?;
+                packer.pack_u64(stakeable_lock_in.locktime)?;
+                &stakeable_lock_in.transfer_input
+            }
+            (None, None) => {
+                return Err(Error::Other {
+                    message:
+                        "unexpected Nones in TransferableInput transfer_input and stakeable_lock_in"
+                            .to_string(),
+                    retryable: false,
+                });
+            }
+        };
+
+        // "secp256k1fx.TransferInput.Input" is struct and serialize:"true"
+        // but embedded inline in the struct "TransferInput"
+        // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/secp256k1fx#TransferInput
+        packer.pack_u64(transfer_input.amount)?;
+        packer.pack_u32(u32::try_from(transfer_input.sig_indices.len())?)?;
+        for idx in &transfer_input.sig_indices {
+            packer.pack_u32(*idx)?;
+        }
+        Ok(())
+    }
+}
+
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#SortTransferableInputs>
 ///
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#SortTransferableInputsWithSigners>
@@ -504,3 +631,44 @@ fn test_sort_transferable_inputs() {
     assert!(cmp_manager::is_sorted_and_unique(&sorted_inputs));
     assert_eq!(inputs, sorted_inputs);
 }
+
+/// Sorts the inputs and reorders the signers in the same way,
+/// so that each signer list stays aligned with its input.
+/// The signers must be in the same order and of the same length as the inputs.
+/// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#SortTransferableInputsWithSigners>
+#[must_use]
+pub fn sort_inputs_with_signers<T>(
+    ins: Vec<Input>,
+    signers: Vec<Vec<T>>,
+) -> (Vec<Input>, Vec<Vec<T>>) {
+    debug_assert_eq!(ins.len(), signers.len());
+
+    let mut pairs: Vec<(Input, Vec<T>)> = ins.into_iter().zip(signers).collect();
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    pairs.into_iter().unzip()
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- txs::transferable::test_sort_inputs_with_signers --exact --show-output
+#[test]
+fn test_sort_inputs_with_signers() {
+    let mut ins: Vec<Input> = Vec::new();
+    let mut signers: Vec<Vec<u8>> = Vec::new();
+    for i in (0..5).rev() {
+        ins.push(Input {
+            utxo_id: txs::utxo::Id {
+                tx_id: ids::Id::from_slice(&[i, 1, 2, 3]),
+                output_index: u32::from(i),
+                ..txs::utxo::Id::default()
+            },
+            ..Input::default()
+        });
+        signers.push(vec![i, i]);
+    }
+
+    let (ins, signers) = sort_inputs_with_signers(ins, signers);
+    assert!(cmp_manager::is_sorted_and_unique(&ins));
+    for (i, input) in ins.iter().enumerate() {
+        assert_eq!(input.utxo_id.output_index, u32::try_from(i).unwrap());
+        assert_eq!(signers[i], vec![u8::try_from(i).unwrap(); 2]);
+    }
+}
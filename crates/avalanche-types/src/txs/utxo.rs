@@ -1,13 +1,16 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
 
 use crate::{
     codec,
     errors::{Error, Result},
     formatting,
     ids::{self, short},
-    key, packer, platformvm,
+    jsonrpc, key, packer, platformvm, txs,
 };
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
 
 /// ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/components/avax#UTXOID>
 #[derive(Debug, Serialize, Deserialize, Eq, Clone)]
@@ -207,6 +210,22 @@ impl Utxo {
         // ref. "math.MaxInt32" and "constants.DefaultByteSliceCap" in Go
         let packer = packer::Packer::new((1 << 31) - 1, 128);
 
+        // the output types are registered with the same type Ids in the X and
+        // P-chain codecs, and only the P-chain one registers "StakeableLockOut"
+        let (transfer_output_type_id, stakeable_lock_out_type_id) = {
+            let registry = codec::p_registry()?;
+            (
+                registry.resolve_type_id(
+                    codec_version,
+                    &key::secp256k1::txs::transfer::Output::type_name(),
+                )?,
+                registry.resolve_type_id(
+                    codec_version,
+                    &platformvm::txs::StakeableLockOut::type_name(),
+                )?,
+            )
+        };
+
         // codec version
         // ref. "avalanchego/codec.manager.Marshal"
         packer.pack_u16(codec_version)?;
@@ -217,7 +236,7 @@ impl Utxo {
         packer.pack_bytes(self.asset_id.as_ref())?;
 
         if let Some(out) = &self.transfer_output {
-            packer.pack_u32(key::secp256k1::txs::transfer::Output::type_id())?;
+            packer.pack_u32(transfer_output_type_id)?;
             packer.pack_u64(out.amount)?;
 
             packer.pack_u64(out.output_owners.locktime)?;
@@ -228,10 +247,10 @@ impl Utxo {
                 packer.pack_bytes(addr.as_ref())?;
             }
         } else if let Some(lock_out) = &self.stakeable_lock_out {
-            packer.pack_u32(platformvm::txs::StakeableLockOut::type_id())?;
+            packer.pack_u32(stakeable_lock_out_type_id)?;
             packer.pack_u64(lock_out.locktime)?;
 
-            packer.pack_u32(key::secp256k1::txs::transfer::Output::type_id())?;
+            packer.pack_u32(transfer_output_type_id)?;
             packer.pack_u64(lock_out.transfer_output.amount)?;
 
             packer.pack_u64(lock_out.transfer_output.output_owners.locktime)?;
@@ -258,7 +277,15 @@ impl Utxo {
     pub fn unpack(d: &[u8]) -> Result<Self> {
         let packer = packer::Packer::load_bytes_for_unpack(d.len() + 1024, d);
 
-        let _codec_version = packer.unpack_u16()?;
+        // the UTXOs of both the X and P-chain are unpacked here
+        let codec_version = packer.unpack_u16()?;
+        let (x_registry, p_registry) = (codec::x_registry()?, codec::p_registry()?);
+        if !x_registry.is_supported(codec_version) && !p_registry.is_supported(codec_version) {
+            return Err(Error::Other {
+                message: format!("unknown codec version {codec_version}"),
+                retryable: false,
+            });
+        }
 
         // must unpack in the order of struct
         let tx_id_bytes = packer.unpack_bytes(ids::LEN)?;
@@ -278,19 +305,24 @@ impl Utxo {
         // "*platformvm.StakeableLockOut" which embeds "*secp256k1fx.TransferOutput"-- type ID 22
         // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
         let type_id_verify_state = packer.unpack_u32()?;
-        match type_id_verify_state {
-            7 | 22 => {}
-            _ => {
-                return Err(Error::Other {
-                    message: format!("unknown type ID for verify.State {type_id_verify_state}"),
-                    retryable: false,
-                })
-            }
+        let type_name = p_registry
+            .type_name(codec_version, type_id_verify_state)
+            .or_else(|| x_registry.type_name(codec_version, type_id_verify_state))
+            .unwrap_or_default()
+            .to_string();
+        drop((x_registry, p_registry));
+        let is_stakeable_lock_out = type_name == platformvm::txs::StakeableLockOut::type_name();
+        if !is_stakeable_lock_out && type_name != key::secp256k1::txs::transfer::Output::type_name()
+        {
+            return Err(Error::Other {
+                message: format!("unknown type ID for verify.State {type_id_verify_state}"),
+                retryable: false,
+            });
         }
 
         // ref. https://pkg.go.dev/github.com/ava-labs/avalanchego/vms/platformvm#StakeableLockOut
         let stakeable_lock_out = {
-            if type_id_verify_state == 22 {
+            if is_stakeable_lock_out {
                 let stakeable_lock_out_locktime = packer.unpack_u64()?;
 
                 // "*secp256k1fx.TransferOutput" -- type ID 7
@@ -389,3 +421,318 @@ fn test_utxo_unpack_hex() {
 
     println!("{utxo:?}");
 }
+
+/// Represents a set of unspent outputs keyed by the UTXO ID
+/// (e.g., a wallet cache or the expected state of a VM in tests).
+/// Serializes as the list of "0x"-prefixed hex-encoded UTXOs, as in "getUTXOs".
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Set {
+    utxos: BTreeMap<Id, Utxo>,
+}
+
+/// Represents the difference between two UTXO sets.
+/// A UTXO whose contents changed is both removed and added.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Diff {
+    /// UTXOs only in the other set.
+    pub added: Vec<Utxo>,
+    /// UTXOs only in this set.
+    pub removed: Vec<Utxo>,
+}
+
+impl Diff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Set {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.utxos.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.utxos.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, utxo_id: &Id) -> bool {
+        self.utxos.contains_key(utxo_id)
+    }
+
+    #[must_use]
+    pub fn get(&self, utxo_id: &Id) -> Option<&Utxo> {
+        self.utxos.get(utxo_id)
+    }
+
+    /// Returns the UTXOs in the order of UTXO ID.
+    #[must_use]
+    pub fn iter(&self) -> impl Iterator<Item = &Utxo> {
+        self.utxos.values()
+    }
+
+    /// Adds the UTXO, returning the previous one with the same ID.
+    pub fn insert(&mut self, utxo: Utxo) -> Option<Utxo> {
+        self.utxos.insert(utxo.utxo_id.clone(), utxo)
+    }
+
+    pub fn remove(&mut self, utxo_id: &Id) -> Option<Utxo> {
+        self.utxos.remove(utxo_id)
+    }
+
+    /// Applies the accepted transaction: consumes its inputs and adds its outputs,
+    /// indexed in the order of the outputs. The set is unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an input is missing or consumed twice,
+    /// or if an output already exists in the set.
+    pub fn apply(&mut self, tx_id: &ids::Id, tx: &txs::Tx) -> Result<()> {
+        let inputs = tx.transferable_inputs.as_deref().unwrap_or_default();
+        let outputs = tx.transferable_outputs.as_deref().unwrap_or_default();
+
+        let mut consumed = BTreeSet::new();
+        for input in inputs {
+            if !self.utxos.contains_key(&input.utxo_id) {
+                return Err(Error::Other {
+                    message: format!(
+                        "tx {tx_id} consumes missing UTXO {}:{}",
+                        input.utxo_id.tx_id, input.utxo_id.output_index
+                    ),
+                    retryable: false,
+                });
+            }
+            if !consumed.insert(input.utxo_id.clone()) {
+                return Err(Error::Other {
+                    message: format!(
+                        "tx {tx_id} consumes UTXO {}:{} twice",
+                        input.utxo_id.tx_id, input.utxo_id.output_index
+                    ),
+                    retryable: false,
+                });
+            }
+        }
+
+        let mut produced = Vec::with_capacity(outputs.len());
+        for (i, output) in outputs.iter().enumerate() {
+            let utxo_id = Id::new(tx_id.as_ref(), u32::try_from(i)?, false)?;
+            if self.utxos.contains_key(&utxo_id) {
+                return Err(Error::Other {
+                    message: format!("tx {tx_id} output {i} already exists"),
+                    retryable: false,
+                });
+            }
+            produced.push(Utxo {
+                utxo_id,
+                asset_id: output.asset_id,
+                transfer_output: output.transfer_output.clone(),
+                stakeable_lock_out: output.stakeable_lock_out.clone(),
+            });
+        }
+
+        for utxo_id in &consumed {
+            self.utxos.remove(utxo_id);
+        }
+        for utxo in produced {
+            self.insert(utxo);
+        }
+        Ok(())
+    }
+
+    /// Returns the UTXOs to add and remove to turn this set into the other.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Diff {
+        let mut diff = Diff::default();
+        for (utxo_id, utxo) in &self.utxos {
+            if other.utxos.get(utxo_id) != Some(utxo) {
+                diff.removed.push(utxo.clone());
+            }
+        }
+        for (utxo_id, utxo) in &other.utxos {
+            if self.utxos.get(utxo_id) != Some(utxo) {
+                diff.added.push(utxo.clone());
+            }
+        }
+        diff
+    }
+
+    /// Collects the UTXOs from the pages of "avm.getUTXOs" responses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a page does not match its "numFetched",
+    /// or if the pages overlap (e.g., the UTXOs changed while paginating).
+    pub fn from_get_utxos_pages(pages: &[jsonrpc::avm::GetUtxosResult]) -> Result<Self> {
+        let mut set = Self::new();
+        for (i, page) in pages.iter().enumerate() {
+            let utxos = page.utxos.as_deref().unwrap_or_default();
+            if utxos.len() != page.num_fetched as usize {
+                return Err(Error::Other {
+                    message: format!(
+                        "page {i} has {} UTXOs but numFetched {}",
+                        utxos.len(),
+                        page.num_fetched
+                    ),
+                    retryable: false,
+                });
+            }
+            for utxo in utxos {
+                if set.insert(utxo.clone()).is_some() {
+                    return Err(Error::Other {
+                        message: format!(
+                            "page {i} repeats UTXO {}:{}",
+                            utxo.utxo_id.tx_id, utxo.utxo_id.output_index
+                        ),
+                        retryable: true,
+                    });
+                }
+            }
+        }
+        Ok(set)
+    }
+
+    /// Checks the set against the UTXOs fetched from "avm.getUTXOs",
+    /// returning the changes needed to match the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pages are inconsistent.
+    pub fn reconcile(&self, pages: &[jsonrpc::avm::GetUtxosResult]) -> Result<Diff> {
+        let fetched = Self::from_get_utxos_pages(pages)?;
+        Ok(self.diff(&fetched))
+    }
+}
+
+impl Serialize for Set {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.utxos.len()))?;
+        for utxo in self.utxos.values() {
+            let hex = utxo.to_hex().map_err(serde::ser::Error::custom)?;
+            seq.serialize_element(&hex)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Set {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hexes = Vec::<String>::deserialize(deserializer)?;
+        let mut set = Self::new();
+        for hex in hexes {
+            let utxo = Utxo::from_hex(&hex).map_err(serde::de::Error::custom)?;
+            if set.insert(utxo).is_some() {
+                return Err(serde::de::Error::custom(format!("duplicate UTXO {hex}")));
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- txs::utxo::test_set --exact --show-output
+#[test]
+fn test_set() {
+    let asset_id = ids::Id::from_slice(&[9; 32]);
+    let transfer_output = |amount: u64| key::secp256k1::txs::transfer::Output {
+        amount,
+        output_owners: key::secp256k1::txs::OutputOwners {
+            locktime: 0,
+            threshold: 1,
+            addresses: vec![short::Id::from_slice(&[1; 20])],
+        },
+    };
+    let output = |amount: u64| txs::transferable::Output {
+        asset_id,
+        transfer_output: Some(transfer_output(amount)),
+        ..Default::default()
+    };
+
+    let genesis_tx_id = ids::Id::from_slice(&[1; 32]);
+    let mut set = Set::new();
+    set.apply(
+        &genesis_tx_id,
+        &txs::Tx {
+            transferable_outputs: Some(vec![output(100), output(200)]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(set.len(), 2);
+    let before = set.clone();
+
+    let spent_utxo_id = Id::new(genesis_tx_id.as_ref(), 0, false).unwrap();
+    let spend = txs::Tx {
+        transferable_inputs: Some(vec![txs::transferable::Input {
+            utxo_id: spent_utxo_id.clone(),
+            asset_id,
+            transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                amount: 100,
+                sig_indices: vec![0],
+            }),
+            ..Default::default()
+        }]),
+        transferable_outputs: Some(vec![output(90)]),
+        ..Default::default()
+    };
+    let tx_id = ids::Id::from_slice(&[2; 32]);
+    set.apply(&tx_id, &spend).unwrap();
+    assert_eq!(set.len(), 2);
+    assert!(!set.contains(&spent_utxo_id));
+    assert!(set.contains(&Id::new(tx_id.as_ref(), 0, false).unwrap()));
+
+    // double spend leaves the set unchanged
+    let after = set.clone();
+    assert!(set.apply(&ids::Id::from_slice(&[3; 32]), &spend).is_err());
+    assert_eq!(set, after);
+
+    let diff = before.diff(&set);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].utxo_id, spent_utxo_id);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].transfer_output, Some(transfer_output(90)));
+    assert!(set.diff(&set).is_empty());
+
+    // serializes as the "getUTXOs" hex list
+    let json = serde_json::to_string(&set).unwrap();
+    let parsed: Set = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, set);
+
+    // paginated "getUTXOs" results
+    let utxos: Vec<Utxo> = set.iter().cloned().collect();
+    let pages = vec![
+        jsonrpc::avm::GetUtxosResult {
+            num_fetched: 1,
+            utxos: Some(vec![utxos[0].clone()]),
+            ..Default::default()
+        },
+        jsonrpc::avm::GetUtxosResult {
+            num_fetched: 1,
+            utxos: Some(vec![utxos[1].clone()]),
+            ..Default::default()
+        },
+    ];
+    assert!(set.reconcile(&pages).unwrap().is_empty());
+    assert_eq!(before.reconcile(&pages).unwrap(), diff);
+
+    let overlapping = vec![pages[0].clone(), pages[0].clone()];
+    assert!(set.reconcile(&overlapping).is_err());
+    let mismatched = vec![jsonrpc::avm::GetUtxosResult {
+        num_fetched: 2,
+        utxos: Some(vec![utxos[0].clone()]),
+        ..Default::default()
+    }];
+    assert!(Set::from_get_utxos_pages(&mismatched).is_err());
+}
@@ -0,0 +1,203 @@
+//! Chain alias resolution with caching.
+use std::{collections::HashMap, fmt, str::FromStr, sync::RwLock};
+
+use crate::ids;
+
+/// Represents the chain alias used in the API paths (e.g., "/ext/bc/X")
+/// and the address prefixes (e.g., "X-avax1...").
+/// ref. <https://docs.avax.network/build/avalanchego-apis/admin#adminaliaschain>
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ChainAlias {
+    /// Exchange chain (also aliased "avm").
+    X,
+    /// Platform chain (also aliased "platform").
+    P,
+    /// Contract chain (also aliased "evm").
+    C,
+    /// Alias set by "admin.aliasChain" or the chain ID itself.
+    Custom(String),
+}
+
+impl ChainAlias {
+    /// Returns the alias string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::X => "X",
+            Self::P => "P",
+            Self::C => "C",
+            Self::Custom(s) => s.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for ChainAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ChainAlias {
+    type Err = std::convert::Infallible;
+
+    /// Parses the alias, mapping the VM aliases of the primary network chains
+    /// (e.g., "avm", "platform", "evm") to their chain aliases.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "X" | "avm" => Self::X,
+            "P" | "platform" => Self::P,
+            "C" | "evm" => Self::C,
+            _ => Self::Custom(s.to_string()),
+        })
+    }
+}
+
+impl From<&str> for ChainAlias {
+    fn from(s: &str) -> Self {
+        match Self::from_str(s) {
+            Ok(alias) => alias,
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// Resolves the chain aliases to their chain IDs, caching the results.
+/// The chain IDs of "X" and "C" differ per network, so use one resolver per network.
+#[derive(Debug)]
+pub struct Resolver {
+    cache: RwLock<HashMap<ChainAlias, ids::Id>>,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    /// Creates a resolver with the P-chain ID, which is the same on all networks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::new(HashMap::from([(ChainAlias::P, ids::Id::empty())])),
+        }
+    }
+
+    /// Returns the cached chain ID of the alias.
+    /// Returns the chain ID itself if the alias is a chain ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    #[must_use]
+    pub fn get(&self, alias: &ChainAlias) -> Option<ids::Id> {
+        if let ChainAlias::Custom(s) = alias {
+            if let Ok(id) = ids::Id::from_str(s) {
+                return Some(id);
+            }
+        }
+        self.cache.read().unwrap().get(alias).copied()
+    }
+
+    /// Caches the chain ID of the alias (e.g., from the genesis).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    pub fn insert(&self, alias: ChainAlias, chain_id: ids::Id) {
+        self.cache.write().unwrap().insert(alias, chain_id);
+    }
+
+    /// Returns the cached alias of the chain ID, preferring the primary network
+    /// chain aliases (e.g., to format the addresses of the chain).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    #[must_use]
+    pub fn alias_of(&self, chain_id: &ids::Id) -> Option<ChainAlias> {
+        let cache = self.cache.read().unwrap();
+        [ChainAlias::X, ChainAlias::P, ChainAlias::C]
+            .into_iter()
+            .find(|alias| cache.get(alias) == Some(chain_id))
+            .or_else(|| {
+                cache
+                    .iter()
+                    .filter(|(_, id)| *id == chain_id)
+                    .map(|(alias, _)| alias.clone())
+                    .min_by(|a, b| a.as_str().cmp(b.as_str()))
+            })
+    }
+
+    /// Resolves the alias via "info.getBlockchainID" unless it is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the alias is unknown to the node.
+    #[cfg(feature = "jsonrpc_client")]
+    pub async fn resolve(
+        &self,
+        http_rpc: &str,
+        alias: &ChainAlias,
+    ) -> crate::errors::Result<ids::Id> {
+        if let Some(chain_id) = self.get(alias) {
+            return Ok(chain_id);
+        }
+
+        let resp =
+            crate::jsonrpc::client::info::get_blockchain_id(http_rpc, alias.as_str()).await?;
+        let chain_id = resp
+            .result
+            .ok_or_else(|| {
+                crate::errors::Error::UnexpectedNone("GetBlockchainIdResult".to_string())
+            })?
+            .blockchain_id;
+        log::debug!("resolved chain alias {alias} to {chain_id}");
+
+        self.insert(alias.clone(), chain_id);
+        Ok(chain_id)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `utils::aliases::test_resolver` --exact --show-output
+#[test]
+fn test_resolver() {
+    assert_eq!(ChainAlias::from("X"), ChainAlias::X);
+    assert_eq!(ChainAlias::from("avm"), ChainAlias::X);
+    assert_eq!(ChainAlias::from("platform"), ChainAlias::P);
+    assert_eq!(ChainAlias::from("evm"), ChainAlias::C);
+    assert_eq!(
+        ChainAlias::from("devnet"),
+        ChainAlias::Custom(String::from("devnet"))
+    );
+    assert_eq!(ChainAlias::from("C").to_string(), "C");
+
+    let resolver = Resolver::new();
+    assert_eq!(resolver.get(&ChainAlias::P), Some(ids::Id::empty()));
+    assert_eq!(resolver.get(&ChainAlias::X), None);
+
+    // mainnet X-chain
+    let x = ids::Id::from_str("2oYMBNV4eNHyqk2fjjV5nVQLDbtmNJzq5s3qs3Lo6ftnC6FByM").unwrap();
+    resolver.insert(ChainAlias::X, x);
+    resolver.insert(ChainAlias::from("avm-alias"), x);
+    assert_eq!(resolver.get(&ChainAlias::X), Some(x));
+    assert_eq!(resolver.alias_of(&x), Some(ChainAlias::X));
+    assert_eq!(resolver.alias_of(&ids::Id::empty()), Some(ChainAlias::P));
+
+    let subnet_chain =
+        ids::Id::from_str("sV6o671RtkGBcno1FiaDbVcFv2sG5aVXMZYzKdP4VQAWmJQnM").unwrap();
+    assert_eq!(resolver.alias_of(&subnet_chain), None);
+    resolver.insert(ChainAlias::from("devnet"), subnet_chain);
+    assert_eq!(
+        resolver.alias_of(&subnet_chain),
+        Some(ChainAlias::Custom(String::from("devnet")))
+    );
+
+    // chain IDs resolve to themselves
+    assert_eq!(
+        resolver.get(&ChainAlias::from(
+            "sV6o671RtkGBcno1FiaDbVcFv2sG5aVXMZYzKdP4VQAWmJQnM"
+        )),
+        Some(subnet_chain)
+    );
+}
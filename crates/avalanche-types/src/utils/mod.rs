@@ -1,3 +1,5 @@
-//! Utilities for URLs and versioning.
+//! Utilities for URLs, versioning, sampling and chain aliases.
+pub mod aliases;
+pub mod sampler;
 pub mod urls;
 pub mod version;
@@ -0,0 +1,372 @@
+//! Uniform and weighted samplers, used to sample validators by stake.
+//!
+//! ref. <https://pkg.go.dev/github.com/ava-labs/avalanchego/utils/sampler>
+use std::collections::HashMap;
+
+use crate::errors::{Error, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Samples integers in the range [0, length) without replacement.
+/// Sampling "count" values costs O(count) regardless of the length.
+///
+/// ref. "avalanchego/utils/sampler.uniformReplacer"
+#[derive(Debug, Clone)]
+pub struct Uniform {
+    /// source of randomness
+    rng: StdRng,
+    /// number of values to sample from
+    length: u64,
+    /// swapped values from the lazy Fisher-Yates shuffle
+    drawn: HashMap<u64, u64>,
+    /// number of values drawn since the last reset
+    drawn_count: u64,
+}
+
+impl Uniform {
+    /// Creates a new sampler seeded from the OS.
+    #[must_use]
+    pub fn new(length: u64) -> Self {
+        Self::from_rng(length, StdRng::from_entropy())
+    }
+
+    /// Creates a new sampler with the deterministic seed,
+    /// so the same seed always yields the same samples (e.g., for tests).
+    #[must_use]
+    pub fn with_seed(length: u64, seed: u64) -> Self {
+        Self::from_rng(length, StdRng::seed_from_u64(seed))
+    }
+
+    /// Creates a new sampler with the given randomness source.
+    fn from_rng(length: u64, rng: StdRng) -> Self {
+        Self {
+            rng,
+            length,
+            drawn: HashMap::new(),
+            drawn_count: 0,
+        }
+    }
+
+    /// Sets the number of values to sample from, and resets the drawn values.
+    /// ref. "avalanchego/utils/sampler.Uniform.Initialize"
+    pub fn initialize(&mut self, length: u64) {
+        self.length = length;
+        self.reset();
+    }
+
+    /// Re-seeds the sampler, and resets the drawn values.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.reset();
+    }
+
+    /// Returns the number of values to sample from.
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns true if there are no values to sample from.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Makes all values available to sample again.
+    /// ref. "avalanchego/utils/sampler.Uniform.Reset"
+    pub fn reset(&mut self) {
+        self.drawn.clear();
+        self.drawn_count = 0;
+    }
+
+    /// Returns "count" distinct values, after resetting the drawn values.
+    /// ref. "avalanchego/utils/sampler.Uniform.Sample"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if "count" exceeds the number of values.
+    pub fn sample(&mut self, count: usize) -> Result<Vec<u64>> {
+        self.reset();
+        let mut rs = Vec::with_capacity(count);
+        for _ in 0..count {
+            rs.push(self.next_index()?);
+        }
+        Ok(rs)
+    }
+
+    /// Returns the next value that has not been drawn since the last reset.
+    /// ref. "avalanchego/utils/sampler.Uniform.Next"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all values have been drawn.
+    pub fn next_index(&mut self) -> Result<u64> {
+        if self.drawn_count >= self.length {
+            return Err(Error::Other {
+                message: format!("out of range; all {} values drawn", self.length),
+                retryable: false,
+            });
+        }
+
+        // swap the drawn value with the first undrawn value
+        let draw = self.rng.gen_range(self.drawn_count..self.length);
+        let ret = self.drawn.get(&draw).copied().unwrap_or(draw);
+        let replace = self
+            .drawn
+            .get(&self.drawn_count)
+            .copied()
+            .unwrap_or(self.drawn_count);
+        self.drawn.insert(draw, replace);
+        self.drawn_count += 1;
+
+        Ok(ret)
+    }
+}
+
+/// Maps a value in the range [0, total weight) to the index of its weight.
+///
+/// ref. "avalanchego/utils/sampler.weightedBest"
+#[derive(Debug, Clone, Default)]
+pub struct Weighted {
+    /// running sum of the weights
+    cumulative: Vec<u64>,
+}
+
+impl Weighted {
+    /// Creates a new sampler over the weights.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn new(weights: &[u64]) -> Result<Self> {
+        let mut w = Self::default();
+        w.initialize(weights)?;
+        Ok(w)
+    }
+
+    /// Sets the weights to sample from.
+    /// ref. "avalanchego/utils/sampler.Weighted.Initialize"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn initialize(&mut self, weights: &[u64]) -> Result<()> {
+        let mut cumulative = Vec::with_capacity(weights.len());
+        let mut total = 0_u64;
+        for w in weights {
+            total = total.checked_add(*w).ok_or_else(|| Error::Other {
+                message: "total weight overflows u64".to_string(),
+                retryable: false,
+            })?;
+            cumulative.push(total);
+        }
+        self.cumulative = cumulative;
+        Ok(())
+    }
+
+    /// Returns the sum of all weights.
+    #[must_use]
+    pub fn total_weight(&self) -> u64 {
+        self.cumulative.last().copied().unwrap_or(0)
+    }
+
+    /// Returns the index of the weight that covers the value.
+    /// ref. "avalanchego/utils/sampler.Weighted.Sample"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not less than the total weight.
+    pub fn sample(&self, value: u64) -> Result<usize> {
+        if value >= self.total_weight() {
+            return Err(Error::Other {
+                message: format!(
+                    "out of range; value {value} >= total weight {}",
+                    self.total_weight()
+                ),
+                retryable: false,
+            });
+        }
+        // first index whose running sum exceeds the value,
+        // which skips zero weights
+        Ok(self.cumulative.partition_point(|c| *c <= value))
+    }
+}
+
+/// Samples indices proportionally to their weights, where each unit of
+/// weight is drawn at most once. An index can be returned multiple times,
+/// up to its weight (e.g., a validator with more stake gets more votes).
+///
+/// ref. "avalanchego/utils/sampler.weightedWithoutReplacementGeneric"
+#[derive(Debug, Clone)]
+pub struct WeightedWithoutReplacement {
+    /// draws the units of weight
+    u: Uniform,
+    /// maps the units of weight to the indices
+    w: Weighted,
+}
+
+impl WeightedWithoutReplacement {
+    /// Creates a new sampler over the weights, seeded from the OS.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn new(weights: &[u64]) -> Result<Self> {
+        let w = Weighted::new(weights)?;
+        Ok(Self {
+            u: Uniform::new(w.total_weight()),
+            w,
+        })
+    }
+
+    /// Creates a new sampler over the weights with the deterministic seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn with_seed(weights: &[u64], seed: u64) -> Result<Self> {
+        let w = Weighted::new(weights)?;
+        Ok(Self {
+            u: Uniform::with_seed(w.total_weight(), seed),
+            w,
+        })
+    }
+
+    /// Sets the weights to sample from.
+    /// ref. "avalanchego/utils/sampler.WeightedWithoutReplacement.Initialize"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the total weight overflows.
+    pub fn initialize(&mut self, weights: &[u64]) -> Result<()> {
+        self.w.initialize(weights)?;
+        self.u.initialize(self.w.total_weight());
+        Ok(())
+    }
+
+    /// Re-seeds the sampler.
+    pub fn seed(&mut self, seed: u64) {
+        self.u.seed(seed);
+    }
+
+    /// Returns the sum of all weights.
+    #[must_use]
+    pub fn total_weight(&self) -> u64 {
+        self.w.total_weight()
+    }
+
+    /// Returns "count" indices sampled by weight.
+    /// ref. "avalanchego/utils/sampler.WeightedWithoutReplacement.Sample"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if "count" exceeds the total weight.
+    pub fn sample(&mut self, count: usize) -> Result<Vec<usize>> {
+        self.u.reset();
+        let mut indices = Vec::with_capacity(count);
+        for _ in 0..count {
+            let weight = self.u.next_index()?;
+            indices.push(self.w.sample(weight)?);
+        }
+        Ok(indices)
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `utils::sampler::test_uniform` --exact --show-output
+/// ref. "avalanchego/utils/sampler.UniformInitializeMaxUint64Test"
+#[test]
+fn test_uniform() {
+    let mut s = Uniform::with_seed(10, 1);
+    assert_eq!(s.len(), 10);
+
+    let mut vals = s.sample(10).unwrap();
+    vals.sort_unstable();
+    assert_eq!(vals, (0..10).collect::<Vec<u64>>());
+
+    // out of range
+    assert!(s.sample(11).is_err());
+    assert!(s.next_index().is_err());
+
+    // "sample" resets
+    assert_eq!(s.sample(3).unwrap().len(), 3);
+
+    // "next_index" draws until exhausted
+    s.reset();
+    let mut vals: Vec<u64> = (0..10).map(|_| s.next_index().unwrap()).collect();
+    assert!(s.next_index().is_err());
+    vals.sort_unstable();
+    assert_eq!(vals, (0..10).collect::<Vec<u64>>());
+
+    // does not allocate the full range
+    let mut s = Uniform::with_seed(u64::MAX, 1);
+    let vals = s.sample(100).unwrap();
+    let uniq: std::collections::HashSet<u64> = vals.iter().copied().collect();
+    assert_eq!(uniq.len(), 100);
+
+    let mut s = Uniform::new(0);
+    assert!(s.is_empty());
+    assert!(s.sample(0).unwrap().is_empty());
+    assert!(s.sample(1).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `utils::sampler::test_uniform_seed` --exact --show-output
+#[test]
+fn test_uniform_seed() {
+    let mut s1 = Uniform::with_seed(1000, 42);
+    let mut s2 = Uniform::with_seed(1000, 42);
+    let vals = s1.sample(50).unwrap();
+    assert_eq!(vals, s2.sample(50).unwrap());
+
+    // reseeding restarts the sequence
+    s1.seed(42);
+    assert_eq!(vals, s1.sample(50).unwrap());
+
+    let mut s3 = Uniform::with_seed(1000, 43);
+    assert_ne!(vals, s3.sample(50).unwrap());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `utils::sampler::test_weighted` --exact --show-output
+/// ref. "avalanchego/utils/sampler.WeightedSingletonTest"
+#[test]
+fn test_weighted() {
+    let w = Weighted::new(&[1, 0, 2, 0, 3]).unwrap();
+    assert_eq!(w.total_weight(), 6);
+    let expected = [0, 2, 2, 4, 4, 4];
+    for (value, index) in expected.iter().enumerate() {
+        assert_eq!(w.sample(value as u64).unwrap(), *index);
+    }
+    assert!(w.sample(6).is_err());
+
+    let w = Weighted::new(&[]).unwrap();
+    assert!(w.sample(0).is_err());
+
+    // overflow
+    assert!(Weighted::new(&[u64::MAX, 1]).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib -- `utils::sampler::test_weighted_without_replacement` --exact --show-output
+/// ref. "avalanchego/utils/sampler.WeightedWithoutReplacementOutOfRangeTest"
+#[test]
+fn test_weighted_without_replacement() {
+    let mut s = WeightedWithoutReplacement::with_seed(&[1, 0, 2], 7).unwrap();
+    assert_eq!(s.total_weight(), 3);
+
+    // every unit of weight is drawn exactly once
+    let mut indices = s.sample(3).unwrap();
+    indices.sort_unstable();
+    assert_eq!(indices, vec![0, 2, 2]);
+
+    // out of range
+    assert!(s.sample(4).is_err());
+
+    // singleton
+    s.initialize(&[0, 5, 0]).unwrap();
+    assert_eq!(s.sample(5).unwrap(), vec![1; 5]);
+
+    // deterministic
+    let weights: Vec<u64> = (1..=100).collect();
+    let mut s1 = WeightedWithoutReplacement::with_seed(&weights, 1).unwrap();
+    let mut s2 = WeightedWithoutReplacement::with_seed(&weights, 1).unwrap();
+    let indices = s1.sample(20).unwrap();
+    assert_eq!(indices, s2.sample(20).unwrap());
+    assert!(indices.iter().all(|i| *i < 100));
+}
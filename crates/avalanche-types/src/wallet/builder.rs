@@ -0,0 +1,853 @@
+//! Shared UTXO selection and change handling for the chain wallet builders.
+use std::{cmp, fmt, str::FromStr};
+
+use crate::{
+    errors::{Error, Result},
+    formatting,
+    ids::{self, short},
+    key, platformvm, txs,
+};
+use rand::{seq::SliceRandom, thread_rng};
+
+/// Defines the order in which the spendable UTXOs are consumed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum SelectionStrategy {
+    /// Consumes UTXOs in the order returned by the node (same as avalanchego wallet).
+    #[default]
+    Sequential,
+    /// Consumes the largest UTXOs first, minimizing the number of inputs
+    /// (and thus the transaction size and fee).
+    LargestFirst,
+    /// Consumes UTXOs in random order, so that consecutive transactions
+    /// are less likely to conflict on the same UTXOs.
+    Random,
+}
+
+impl SelectionStrategy {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sequential => "sequential",
+            Self::LargestFirst => "largest-first",
+            Self::Random => "random",
+        }
+    }
+
+    /// Returns the UTXOs in the order to consume.
+    #[must_use]
+    pub fn order<'a>(&self, utxos: &'a [txs::utxo::Utxo]) -> Vec<&'a txs::utxo::Utxo> {
+        let mut ordered: Vec<&txs::utxo::Utxo> = utxos.iter().collect();
+        match self {
+            Self::Sequential => {}
+            Self::LargestFirst => {
+                // stable sort to keep the node order among the same amounts
+                ordered.sort_by_key(|u| cmp::Reverse(utxo_amount(u)));
+            }
+            Self::Random => ordered.shuffle(&mut thread_rng()),
+        }
+        ordered
+    }
+}
+
+impl fmt::Display for SelectionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SelectionStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sequential" => Ok(Self::Sequential),
+            "largest-first" => Ok(Self::LargestFirst),
+            "random" => Ok(Self::Random),
+            _ => Err(Error::Other {
+                message: format!("unknown UTXO selection strategy '{s}'"),
+                retryable: false,
+            }),
+        }
+    }
+}
+
+/// Returns the amount of the UTXO (zero if it is neither transfer nor stakeable lock output).
+fn utxo_amount(utxo: &txs::utxo::Utxo) -> u64 {
+    if let Some(out) = &utxo.transfer_output {
+        return out.amount;
+    }
+    utxo.stakeable_lock_out
+        .as_ref()
+        .map_or(0, |out| out.transfer_output.amount)
+}
+
+/// Represents the inputs consumed to burn an amount, along with the change
+/// returned to the wallet owner.
+#[derive(Debug, Clone)]
+pub struct Spent<T> {
+    /// Consumed inputs, sorted.
+    pub inputs: Vec<txs::transferable::Input>,
+    /// Signers of each input, in the same order as "inputs".
+    pub signers: Vec<Vec<T>>,
+    /// Change outputs, sorted.
+    pub change_outputs: Vec<txs::transferable::Output>,
+}
+
+/// Represents the inputs consumed to stake and burn amounts (P-chain),
+/// along with the staked outputs and the change returned to the owners.
+#[derive(Debug, Clone)]
+pub struct Staked<T> {
+    /// Consumed inputs, sorted.
+    pub inputs: Vec<txs::transferable::Input>,
+    /// Signers of each input, in the same order as "inputs".
+    pub signers: Vec<Vec<T>>,
+    /// Change outputs, sorted.
+    pub returned_outputs: Vec<txs::transferable::Output>,
+    /// Staked outputs, sorted.
+    pub staked_outputs: Vec<txs::transferable::Output>,
+}
+
+/// Represents the atomic UTXOs consumed by the import transaction.
+#[derive(Debug, Clone)]
+pub struct Imported<T> {
+    /// Consumed inputs, sorted.
+    pub inputs: Vec<txs::transferable::Input>,
+    /// Signers of each input, in the same order as "inputs".
+    pub signers: Vec<Vec<T>>,
+    /// Total amount of the consumed inputs.
+    pub amount: u64,
+}
+
+/// Selects the UTXOs to spend and computes the change outputs,
+/// shared by the X and P chain wallet builders.
+///
+/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.9.4/wallet/chain/x/builder.go> "spend"
+#[derive(Debug, Clone)]
+pub struct TxBuilder<'a, T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub keychain: &'a key::secp256k1::keychain::Keychain<T>,
+    /// Asset to spend (e.g., AVAX).
+    pub asset_id: ids::Id,
+    /// Address that receives the change.
+    pub change_address: short::Id,
+    pub strategy: SelectionStrategy,
+}
+
+impl<'a, T> TxBuilder<'a, T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    #[must_use]
+    pub fn new(
+        keychain: &'a key::secp256k1::keychain::Keychain<T>,
+        asset_id: ids::Id,
+        change_address: short::Id,
+    ) -> Self {
+        Self {
+            keychain,
+            asset_id,
+            change_address,
+            strategy: SelectionStrategy::default(),
+        }
+    }
+
+    /// Sets the UTXO selection strategy.
+    #[must_use]
+    pub const fn strategy(mut self, strategy: SelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the output of the asset to the address, unlocked with threshold 1.
+    #[must_use]
+    pub fn output(&self, amount: u64, address: short::Id) -> txs::transferable::Output {
+        self.output_with_owners(
+            amount,
+            key::secp256k1::txs::OutputOwners {
+                locktime: 0,
+                threshold: 1,
+                addresses: vec![address],
+            },
+        )
+    }
+
+    /// Returns the output of the asset to the owners (e.g., a multisig output
+    /// with threshold > 1). The owner addresses are sorted as required by the VM.
+    #[must_use]
+    pub fn output_with_owners(
+        &self,
+        amount: u64,
+        mut output_owners: key::secp256k1::txs::OutputOwners,
+    ) -> txs::transferable::Output {
+        output_owners.addresses.sort();
+        txs::transferable::Output {
+            asset_id: self.asset_id,
+            transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                amount,
+                output_owners,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the change output to the wallet owner.
+    #[must_use]
+    pub fn change_output(&self, amount: u64) -> txs::transferable::Output {
+        self.output(amount, self.change_address.clone())
+    }
+
+    /// Consumes unlocked UTXOs of the asset until "`amount_to_burn`"
+    /// (e.g., outputs plus fee) is covered, and returns the excess as change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spendable UTXOs do not cover the amount.
+    pub fn spend(
+        &self,
+        utxos: &[txs::utxo::Utxo],
+        amount_to_burn: u64,
+        now_unix: u64,
+    ) -> Result<Spent<T>> {
+        self.spend_with(utxos, amount_to_burn, |out| {
+            self.keychain.spend(out, now_unix)
+        })
+    }
+
+    /// Same as "spend", but also consumes the UTXOs that need signatures from
+    /// the co-signers (e.g., multisig UTXOs with threshold > 1), as long as
+    /// the keychain holds one of the owner keys.
+    /// The signers are the addresses expected to sign each input,
+    /// to build the partially signed transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spendable UTXOs do not cover the amount.
+    pub fn spend_with_cosigners(
+        &self,
+        utxos: &[txs::utxo::Utxo],
+        amount_to_burn: u64,
+        now_unix: u64,
+    ) -> Result<Spent<short::Id>> {
+        self.spend_with(utxos, amount_to_burn, |out| {
+            let (sig_indices, addrs) = self
+                .keychain
+                .match_threshold_with_cosigners(&out.output_owners, now_unix)?;
+            Some((
+                key::secp256k1::txs::transfer::Input {
+                    amount: out.amount,
+                    sig_indices,
+                },
+                addrs,
+            ))
+        })
+    }
+
+    /// Consumes the UTXOs of the asset to stake "`amount_to_stake`" and to burn
+    /// "`amount_to_burn`" (e.g., fee). The locked UTXOs are staked first with
+    /// their locktime kept, and the unlocked UTXOs cover the rest.
+    ///
+    /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.9.4/vms/platformvm/utxo/handler.go#L169> "Spend"
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spendable UTXOs do not cover the amounts.
+    pub fn spend_and_stake(
+        &self,
+        utxos: &[txs::utxo::Utxo],
+        amount_to_stake: u64,
+        amount_to_burn: u64,
+        now_unix: u64,
+    ) -> Result<Staked<T>> {
+        let utxos = self.strategy.order(utxos);
+
+        let mut inputs: Vec<txs::transferable::Input> = Vec::new();
+        let mut returned_outputs: Vec<txs::transferable::Output> = Vec::new();
+        let mut staked_outputs: Vec<txs::transferable::Output> = Vec::new();
+        let mut signers: Vec<Vec<T>> = Vec::new();
+
+        // amount of AVAX that has been staked
+        let mut amount_staked = 0_u64;
+
+        // consume locked UTXOs
+        for utxo in &utxos {
+            // no need to consume more locked AVAX
+            // because it already has consumed more than the target stake amount
+            if amount_staked >= amount_to_stake {
+                break;
+            }
+            if utxo.asset_id != self.asset_id {
+                continue;
+            }
+
+            // check "*platformvm.StakeableLockOut"
+            let Some(out) = &utxo.stakeable_lock_out else {
+                // output is not locked, thus handle this in the next iteration
+                continue;
+            };
+            if out.locktime <= now_unix {
+                // output is no longer locked, thus handle in the next iteration
+                continue;
+            }
+
+            let Some((transfer_input, in_signers)) =
+                self.keychain.spend(&out.transfer_output, now_unix)
+            else {
+                // cannot spend the output, move onto next
+                continue;
+            };
+
+            let mut remaining_value = transfer_input.amount;
+            let amount_to_stake_now = cmp::min(
+                amount_to_stake - amount_staked, // amount we still need to stake
+                remaining_value,                 // amount available to stake
+            );
+            amount_staked += amount_to_stake_now;
+            remaining_value -= amount_to_stake_now;
+
+            inputs.push(txs::transferable::Input {
+                utxo_id: utxo.utxo_id.clone(),
+                asset_id: utxo.asset_id,
+                stakeable_lock_in: Some(platformvm::txs::StakeableLockIn {
+                    locktime: out.locktime,
+                    transfer_input,
+                }),
+                ..Default::default()
+            });
+            signers.push(in_signers);
+
+            let locked_output = |amount: u64| txs::transferable::Output {
+                asset_id: utxo.asset_id,
+                stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
+                    locktime: out.locktime,
+                    transfer_output: key::secp256k1::txs::transfer::Output {
+                        amount,
+                        output_owners: out.transfer_output.output_owners.clone(),
+                    },
+                }),
+                ..Default::default()
+            };
+            staked_outputs.push(locked_output(amount_to_stake_now));
+            if remaining_value > 0 {
+                // this input provided more value than was needed to be locked
+                // some must be returned
+                returned_outputs.push(locked_output(remaining_value));
+            }
+        }
+
+        // amount of AVAX that has been burned
+        let mut amount_burned = 0_u64;
+
+        for utxo in &utxos {
+            // have staked/burned more AVAX than we need
+            // thus no need to consume more AVAX
+            if amount_burned >= amount_to_burn && amount_staked >= amount_to_stake {
+                break;
+            }
+            if utxo.asset_id != self.asset_id {
+                continue;
+            }
+
+            let out = match (&utxo.transfer_output, &utxo.stakeable_lock_out) {
+                (Some(out), _) => out,
+                // output is currently locked, so this output cannot be burned
+                // or it may have already been consumed above
+                (None, Some(locked)) if locked.locktime <= now_unix => &locked.transfer_output,
+                _ => continue,
+            };
+            let Some((transfer_input, in_signers)) = self.keychain.spend(out, now_unix) else {
+                // cannot spend the output, move onto next
+                continue;
+            };
+
+            // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L763
+            let mut remaining_value = transfer_input.amount;
+            let amount_to_burn_now = cmp::min(
+                amount_to_burn - amount_burned, // amount we still need to burn
+                remaining_value,                // amount available to burn
+            );
+            amount_burned += amount_to_burn_now;
+            remaining_value -= amount_to_burn_now;
+
+            let amount_to_stake_now = cmp::min(
+                amount_to_stake - amount_staked, // amount we still need to stake
+                remaining_value,                 // amount available to stake
+            );
+            amount_staked += amount_to_stake_now;
+            remaining_value -= amount_to_stake_now;
+
+            inputs.push(txs::transferable::Input {
+                utxo_id: utxo.utxo_id.clone(),
+                asset_id: utxo.asset_id,
+                transfer_input: Some(transfer_input),
+                ..Default::default()
+            });
+            signers.push(in_signers);
+
+            if amount_to_stake_now > 0 {
+                // some of this input was put for staking
+                staked_outputs.push(self.change_output(amount_to_stake_now));
+            }
+            if remaining_value > 0 {
+                // this input had extra value, so some must be returned
+                returned_outputs.push(self.change_output(remaining_value));
+            }
+        }
+
+        log::info!(
+            "provided keys have balance (unlocked/burned amount so far, locked/staked amount so far) ({amount_burned}, {amount_staked}) and need ({amount_to_burn}, {amount_to_stake})"
+        );
+        if amount_burned < amount_to_burn || amount_staked < amount_to_stake {
+            return Err(Error::Other {
+                message: format!(
+                    "provided keys have balance (unlocked/burned amount so far, locked/staked amount so far) ({amount_burned}, {amount_staked}) but need ({amount_to_burn}, {amount_to_stake})"
+                ),
+                retryable: false,
+            });
+        }
+
+        // signers must be reordered with the inputs, since each input
+        // may be owned by a different set of keys in the keychain
+        let (inputs, signers) = txs::transferable::sort_inputs_with_signers(inputs, signers);
+        returned_outputs.sort();
+        staked_outputs.sort();
+
+        Ok(Staked {
+            inputs,
+            signers,
+            returned_outputs,
+            staked_outputs,
+        })
+    }
+
+    /// Consumes all spendable atomic UTXOs of the asset exported from
+    /// the source chain (the selection strategy does not apply).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no UTXO is spendable or the total amount overflows.
+    pub fn import(&self, utxos: &[txs::utxo::Utxo], now_unix: u64) -> Result<Imported<T>> {
+        let mut amount = 0_u64;
+        let mut inputs: Vec<txs::transferable::Input> = Vec::new();
+        let mut signers: Vec<Vec<T>> = Vec::new();
+        for utxo in utxos {
+            if utxo.asset_id != self.asset_id {
+                continue;
+            }
+            let Some(out) = &utxo.transfer_output else {
+                continue;
+            };
+            let Some((transfer_input, in_signers)) = self.keychain.spend(out, now_unix) else {
+                // cannot spend the output, move onto next
+                continue;
+            };
+
+            amount = amount
+                .checked_add(transfer_input.amount)
+                .ok_or_else(|| Error::Other {
+                    message: "import amount overflows".to_string(),
+                    retryable: false,
+                })?;
+            inputs.push(txs::transferable::Input {
+                utxo_id: utxo.utxo_id.clone(),
+                asset_id: utxo.asset_id,
+                transfer_input: Some(transfer_input),
+                ..Default::default()
+            });
+            signers.push(in_signers);
+        }
+        if inputs.is_empty() {
+            return Err(Error::Other {
+                message: "no spendable funds were found".to_string(),
+                retryable: false,
+            });
+        }
+
+        let (inputs, signers) = txs::transferable::sort_inputs_with_signers(inputs, signers);
+        Ok(Imported {
+            inputs,
+            signers,
+            amount,
+        })
+    }
+
+    fn spend_with<S, F>(
+        &self,
+        utxos: &[txs::utxo::Utxo],
+        amount_to_burn: u64,
+        spend_output: F,
+    ) -> Result<Spent<S>>
+    where
+        F: Fn(
+            &key::secp256k1::txs::transfer::Output,
+        ) -> Option<(key::secp256k1::txs::transfer::Input, Vec<S>)>,
+    {
+        let mut inputs: Vec<txs::transferable::Input> = Vec::new();
+        let mut signers: Vec<Vec<S>> = Vec::new();
+        let mut change_outputs: Vec<txs::transferable::Output> = Vec::new();
+
+        let mut remaining_amount_to_burn = amount_to_burn;
+        for utxo in self.strategy.order(utxos) {
+            // consumed enough, no need to burn more
+            if remaining_amount_to_burn == 0 {
+                break;
+            }
+            if utxo.asset_id != self.asset_id {
+                continue;
+            }
+            let Some(out) = &utxo.transfer_output else {
+                continue;
+            };
+            let Some((input, in_signers)) = spend_output(out) else {
+                // cannot spend the output (locked or threshold not met), move onto next
+                continue;
+            };
+
+            inputs.push(txs::transferable::Input {
+                utxo_id: utxo.utxo_id.clone(),
+                asset_id: utxo.asset_id,
+                transfer_input: Some(input),
+                ..Default::default()
+            });
+            signers.push(in_signers);
+
+            // burn any value that should be burned
+            let amount_to_burn = cmp::min(
+                remaining_amount_to_burn, // amount we still need to burn
+                out.amount,               // amount available to burn
+            );
+            remaining_amount_to_burn -= amount_to_burn;
+
+            let remaining_amount = out.amount - amount_to_burn;
+            if remaining_amount > 0 {
+                // this input had extra value, so some must be returned
+                change_outputs.push(self.change_output(remaining_amount));
+            }
+        }
+        if remaining_amount_to_burn > 0 {
+            return Err(Error::Other {
+                message: format!(
+                    "insufficient spendable funds ({remaining_amount_to_burn} AVAX short)"
+                ),
+                retryable: false,
+            });
+        }
+
+        // make sure it does not incur "tx has 1 credentials but 2 inputs. Should be same" error
+        let (inputs, signers) = txs::transferable::sort_inputs_with_signers(inputs, signers);
+        change_outputs.sort();
+
+        Ok(Spent {
+            inputs,
+            signers,
+            change_outputs,
+        })
+    }
+}
+
+/// Represents a transaction that was built and signed but not issued,
+/// e.g., to validate wallet flows without spending funds.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DryRun {
+    pub tx_id: ids::Id,
+    pub tx_bytes_with_signatures: Vec<u8>,
+    /// All consumed inputs, including the imported inputs.
+    pub inputs: Vec<txs::transferable::Input>,
+    /// All produced outputs, including the exported and staked outputs.
+    pub outputs: Vec<txs::transferable::Output>,
+}
+
+impl DryRun {
+    /// Creates the dry run result from the signed base transaction and the
+    /// inputs/outputs outside of the base transaction (e.g., imported inputs,
+    /// exported or staked outputs).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is not signed.
+    pub fn new(
+        base_tx: &txs::Tx,
+        other_inputs: &[txs::transferable::Input],
+        other_outputs: &[txs::transferable::Output],
+    ) -> Result<Self> {
+        let metadata = base_tx
+            .metadata
+            .as_ref()
+            .ok_or_else(|| Error::UnexpectedNone("unsigned tx metadata".to_string()))?;
+
+        let mut inputs = base_tx.transferable_inputs.clone().unwrap_or_default();
+        inputs.extend_from_slice(other_inputs);
+        let mut outputs = base_tx.transferable_outputs.clone().unwrap_or_default();
+        outputs.extend_from_slice(other_outputs);
+
+        Ok(Self {
+            tx_id: metadata.id,
+            tx_bytes_with_signatures: metadata.tx_bytes_with_signatures.clone(),
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the signed transaction in hex with checksum, as issued to the node.
+    #[must_use]
+    pub fn hex_tx(&self) -> String {
+        formatting::encode_hex_with_checksum(&self.tx_bytes_with_signatures)
+    }
+
+    /// Returns the amount burned as fee (consumed minus produced).
+    #[must_use]
+    pub fn burned(&self) -> u64 {
+        let consumed: u64 = self.inputs.iter().map(input_amount).sum();
+        let produced: u64 = self.outputs.iter().map(output_amount).sum();
+        consumed.saturating_sub(produced)
+    }
+}
+
+/// Human-readable breakdown of the inputs and outputs.
+impl fmt::Display for DryRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "tx_id: {}", self.tx_id)?;
+        writeln!(f, "tx_size: {} bytes", self.tx_bytes_with_signatures.len())?;
+        writeln!(f, "inputs ({}):", self.inputs.len())?;
+        for input in &self.inputs {
+            writeln!(
+                f,
+                "  - {}:{} amount {} (asset {})",
+                input.utxo_id.tx_id,
+                input.utxo_id.output_index,
+                input_amount(input),
+                input.asset_id
+            )?;
+        }
+        writeln!(f, "outputs ({}):", self.outputs.len())?;
+        for output in &self.outputs {
+            let owners = output
+                .transfer_output
+                .as_ref()
+                .or_else(|| {
+                    output
+                        .stakeable_lock_out
+                        .as_ref()
+                        .map(|o| &o.transfer_output)
+                })
+                .map(|o| &o.output_owners);
+            let addresses = owners.map_or_else(Vec::new, |o| {
+                o.addresses.iter().map(ToString::to_string).collect()
+            });
+            writeln!(
+                f,
+                "  - amount {} to {:?} (asset {}, locked {})",
+                output_amount(output),
+                addresses,
+                output.asset_id,
+                output.stakeable_lock_out.is_some()
+            )?;
+        }
+        write!(f, "burned: {}", self.burned())
+    }
+}
+
+/// Returns the amount of the transferable input.
+fn input_amount(input: &txs::transferable::Input) -> u64 {
+    if let Some(i) = &input.transfer_input {
+        return i.amount;
+    }
+    input
+        .stakeable_lock_in
+        .as_ref()
+        .map_or(0, |i| i.transfer_input.amount)
+}
+
+/// Returns the amount of the transferable output.
+fn output_amount(output: &txs::transferable::Output) -> u64 {
+    if let Some(o) = &output.transfer_output {
+        return o.amount;
+    }
+    output
+        .stakeable_lock_out
+        .as_ref()
+        .map_or(0, |o| o.transfer_output.amount)
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features wallet -- `wallet::builder::test_tx_builder` --exact --show-output
+#[test]
+fn test_tx_builder() {
+    use crate::key::secp256k1::{private_key::Key, ReadOnly};
+
+    let k = Key::generate().unwrap();
+    let addr = k.short_address().unwrap();
+    let keychain = key::secp256k1::keychain::Keychain::new(vec![k]);
+
+    let asset_id = ids::Id::from_slice(&[1]);
+    let utxo = |idx: u32, amount: u64, asset_id: ids::Id| txs::utxo::Utxo {
+        utxo_id: txs::utxo::Id {
+            output_index: idx,
+            ..Default::default()
+        },
+        asset_id,
+        transfer_output: Some(key::secp256k1::txs::transfer::Output {
+            amount,
+            output_owners: key::secp256k1::txs::OutputOwners {
+                locktime: 0,
+                threshold: 1,
+                addresses: vec![addr.clone()],
+            },
+        }),
+        ..Default::default()
+    };
+    let utxos = vec![
+        utxo(0, 10, asset_id),
+        utxo(1, 30, asset_id),
+        utxo(2, 1_000, ids::Id::from_slice(&[2])),
+        utxo(3, 20, asset_id),
+    ];
+
+    let ordered: Vec<u32> = SelectionStrategy::LargestFirst
+        .order(&utxos)
+        .iter()
+        .map(|u| u.utxo_id.output_index)
+        .collect();
+    assert_eq!(ordered, vec![2, 1, 3, 0]);
+    assert_eq!(SelectionStrategy::Random.order(&utxos).len(), utxos.len());
+
+    // sequential consumes 10 and 30, returning 15 as change
+    let builder = TxBuilder::new(&keychain, asset_id, addr.clone());
+    let spent = builder.spend(&utxos, 25, 0).unwrap();
+    assert_eq!(spent.inputs.len(), 2);
+    assert_eq!(spent.signers.len(), 2);
+    assert_eq!(spent.change_outputs, vec![builder.change_output(15)]);
+
+    // largest-first consumes only 30, returning 5 as change
+    let builder = builder.strategy(SelectionStrategy::LargestFirst);
+    let spent = builder.spend(&utxos, 25, 0).unwrap();
+    assert_eq!(spent.inputs.len(), 1);
+    assert_eq!(spent.inputs[0].utxo_id.output_index, 1);
+    assert_eq!(spent.change_outputs, vec![builder.change_output(5)]);
+
+    // exact amount returns no change
+    let spent = builder.spend(&utxos, 60, 0).unwrap();
+    assert_eq!(spent.inputs.len(), 3);
+    assert!(spent.change_outputs.is_empty());
+
+    assert!(builder.spend(&utxos, 61, 0).is_err());
+
+    assert_eq!(
+        SelectionStrategy::from_str("largest-first").unwrap(),
+        SelectionStrategy::LargestFirst
+    );
+    assert!(SelectionStrategy::from_str("smallest-first").is_err());
+
+    let base_tx = txs::Tx {
+        transferable_inputs: Some(spent.inputs),
+        transferable_outputs: Some(spent.change_outputs),
+        ..Default::default()
+    };
+    assert!(DryRun::new(&base_tx, &[], &[]).is_err());
+
+    let base_tx = txs::Tx {
+        metadata: Some(txs::Metadata {
+            id: ids::Id::from_slice(&[3]),
+            tx_bytes_with_no_signature: vec![],
+            tx_bytes_with_signatures: vec![1, 2, 3],
+        }),
+        ..base_tx
+    };
+    let dry_run = DryRun::new(&base_tx, &[], &[builder.change_output(50)]).unwrap();
+    assert_eq!(dry_run.inputs.len(), 3);
+    assert_eq!(dry_run.outputs.len(), 1);
+    assert_eq!(dry_run.burned(), 10);
+    assert!(dry_run.to_string().contains("burned: 10"));
+
+    // multisig output with the co-signer
+    let cosigner = Key::generate().unwrap().short_address().unwrap();
+    let owners = key::secp256k1::txs::OutputOwners {
+        locktime: 0,
+        threshold: 2,
+        addresses: vec![cosigner.clone(), addr.clone()],
+    };
+    let multisig = builder.output_with_owners(100, owners.clone());
+    let mut sorted = owners.addresses.clone();
+    sorted.sort();
+    assert_eq!(
+        multisig.transfer_output.unwrap().output_owners.addresses,
+        sorted
+    );
+
+    let multisig_utxos = vec![txs::utxo::Utxo {
+        transfer_output: Some(key::secp256k1::txs::transfer::Output {
+            amount: 100,
+            output_owners: owners,
+        }),
+        ..utxo(4, 0, asset_id)
+    }];
+    assert!(builder.spend(&multisig_utxos, 50, 0).is_err());
+    let spent = builder
+        .spend_with_cosigners(&multisig_utxos, 50, 0)
+        .unwrap();
+    assert_eq!(spent.signers, vec![vec![cosigner, addr.clone()]]);
+    assert_eq!(
+        spent.inputs[0].transfer_input.as_ref().unwrap().sig_indices,
+        vec![0, 1]
+    );
+    assert_eq!(spent.change_outputs, vec![builder.change_output(50)]);
+
+    // imports all spendable UTXOs of the asset
+    let imported = builder.import(&utxos, 0).unwrap();
+    assert_eq!(imported.inputs.len(), 3);
+    assert_eq!(imported.signers.len(), 3);
+    assert_eq!(imported.amount, 60);
+    assert!(builder.import(&utxos[2..3], 0).is_err());
+
+    // stakes the locked UTXO first, and then the unlocked UTXOs cover the rest
+    let locked = |amount: u64| txs::utxo::Utxo {
+        transfer_output: None,
+        stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
+            locktime: 100,
+            transfer_output: key::secp256k1::txs::transfer::Output {
+                amount,
+                output_owners: key::secp256k1::txs::OutputOwners {
+                    locktime: 0,
+                    threshold: 1,
+                    addresses: vec![addr.clone()],
+                },
+            },
+        }),
+        ..utxo(5, 0, asset_id)
+    };
+    let locked_output = |amount: u64| txs::transferable::Output {
+        asset_id,
+        stakeable_lock_out: locked(amount).stakeable_lock_out,
+        ..Default::default()
+    };
+    let mut staking_utxos = utxos;
+    staking_utxos.push(locked(40));
+
+    let builder = builder.strategy(SelectionStrategy::Sequential);
+    let staked = builder.spend_and_stake(&staking_utxos, 50, 5, 10).unwrap();
+    assert_eq!(staked.inputs.len(), 3);
+    assert_eq!(staked.signers.len(), 3);
+    let mut expected = vec![
+        locked_output(40),
+        builder.change_output(5),
+        builder.change_output(5),
+    ];
+    expected.sort();
+    assert_eq!(staked.staked_outputs, expected);
+    assert_eq!(staked.returned_outputs, vec![builder.change_output(25)]);
+
+    // the unstaked amount of the locked UTXO is returned still locked
+    let staked = builder.spend_and_stake(&staking_utxos, 30, 5, 10).unwrap();
+    assert_eq!(staked.inputs.len(), 2);
+    assert_eq!(staked.staked_outputs, vec![locked_output(30)]);
+    let mut expected = vec![locked_output(10), builder.change_output(5)];
+    expected.sort();
+    assert_eq!(staked.returned_outputs, expected);
+
+    // unlocked once the locktime passes
+    let staked = builder.spend_and_stake(&[locked(40)], 0, 5, 100).unwrap();
+    assert!(staked.inputs[0].transfer_input.is_some());
+    assert_eq!(staked.returned_outputs, vec![builder.change_output(35)]);
+
+    assert!(builder.spend_and_stake(&staking_utxos, 100, 5, 10).is_err());
+}
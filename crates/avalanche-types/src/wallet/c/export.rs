@@ -0,0 +1,242 @@
+use crate::{
+    coreth::atomic,
+    errors::{Error, Result},
+    formatting, ids,
+    jsonrpc::client::{c as client_c, evm as client_evm},
+    key, txs,
+};
+use primitive_types::U256;
+use tokio::time::Duration;
+
+/// Represents C-chain atomic "Export" transaction, which burns AVAX of the
+/// wallet EVM account and exports it to the destination chain.
+/// ref. <https://github.com/ava-labs/coreth/blob/master/plugin/evm/export_tx.go> "`newExportTx`".
+#[derive(Clone, Debug)]
+pub struct Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: crate::wallet::c::C<T>,
+
+    /// Export destination blockchain id.
+    pub destination_blockchain_id: ids::Id,
+
+    /// Transfer amount in nAVAX, not including the fee.
+    pub amount: u64,
+
+    /// Base fee in wei to compute the fee with.
+    /// If none, fetches the current base fee.
+    pub base_fee: Option<U256>,
+
+    /// Set "true" to poll transfer status after issuance for its acceptance.
+    pub check_acceptance: bool,
+
+    /// Initial wait duration before polling for acceptance.
+    pub poll_initial_wait: Duration,
+    /// Wait between each poll intervals for acceptance.
+    pub poll_interval: Duration,
+    /// Maximum duration for polling.
+    pub poll_timeout: Duration,
+
+    /// Set to true to return transaction Id for "issue" in dry mode.
+    pub dry_mode: bool,
+}
+
+impl<T> Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    #[must_use]
+    pub fn new(c: &crate::wallet::c::C<T>) -> Self {
+        Self {
+            inner: c.clone(),
+            destination_blockchain_id: ids::Id::empty(),
+            amount: 0,
+            base_fee: None,
+            check_acceptance: false,
+            poll_initial_wait: Duration::from_millis(500),
+            poll_interval: Duration::from_millis(700),
+            poll_timeout: Duration::from_secs(300),
+            dry_mode: false,
+        }
+    }
+
+    /// Sets the destination blockchain Id.
+    #[must_use]
+    pub const fn destination_blockchain_id(mut self, blockchain_id: ids::Id) -> Self {
+        self.destination_blockchain_id = blockchain_id;
+        self
+    }
+
+    /// Sets the transfer amount.
+    #[must_use]
+    pub const fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Sets the base fee in wei.
+    #[must_use]
+    pub const fn base_fee(mut self, base_fee: U256) -> Self {
+        self.base_fee = Some(base_fee);
+        self
+    }
+
+    /// Sets the check acceptance boolean flag.
+    #[must_use]
+    pub const fn check_acceptance(mut self, check_acceptance: bool) -> Self {
+        self.check_acceptance = check_acceptance;
+        self
+    }
+
+    /// Sets the initial poll wait time.
+    #[must_use]
+    pub const fn poll_initial_wait(mut self, poll_initial_wait: Duration) -> Self {
+        self.poll_initial_wait = poll_initial_wait;
+        self
+    }
+
+    /// Sets the poll wait time between intervals.
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the poll timeout.
+    #[must_use]
+    pub const fn poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+
+    /// Sets the dry mode boolean flag.
+    #[must_use]
+    pub const fn dry_mode(mut self, dry_mode: bool) -> Self {
+        self.dry_mode = dry_mode;
+        self
+    }
+
+    /// Issues the export transaction and returns the transaction Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to be issued or if checking acceptance times out.
+    pub async fn issue(&self) -> Result<ids::Id> {
+        let picked_http_rpc = self.inner.inner.pick_base_http_url();
+        log::info!(
+            "exporting {} AVAX from {} to {} via {}",
+            self.amount,
+            self.inner.inner.h160_address,
+            self.destination_blockchain_id,
+            picked_http_rpc.1
+        );
+
+        let tx = self.signed_tx(&picked_http_rpc.1).await?;
+        let metadata = tx
+            .metadata
+            .ok_or_else(|| Error::UnexpectedNone("signed export tx metadata".to_string()))?;
+        if self.dry_mode {
+            return Ok(metadata.id);
+        }
+
+        let hex_tx = formatting::encode_hex_with_checksum(&metadata.tx_bytes_with_signatures);
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_c::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
+        let tx_id = resp
+            .result
+            .ok_or_else(|| Error::API {
+                message: "failed to issue export tx (no result)".to_string(),
+                retryable: false,
+            })?
+            .tx_id;
+        log::info!("{tx_id} successfully issued");
+
+        if !self.check_acceptance {
+            log::debug!("skipping checking acceptance...");
+            return Ok(tx_id);
+        }
+        self.inner
+            .inner
+            .scoped(crate::wallet::c::wait_for_acceptance(
+                &picked_http_rpc.1,
+                &tx_id,
+                self.poll_initial_wait,
+                self.poll_interval,
+                self.poll_timeout,
+            ))
+            .await?;
+
+        Ok(tx_id)
+    }
+
+    /// Builds and signs the transaction with the nonce and base fee fetched
+    /// via "`http_rpc`". The EVM input covers the amount and the fee.
+    async fn signed_tx(&self, http_rpc: &str) -> Result<atomic::ExportTx> {
+        let rpc_ep = crate::wallet::c::C::<T>::rpc_ep(http_rpc);
+        let nonce = self
+            .inner
+            .inner
+            .scoped(client_evm::get_transaction_count(
+                &rpc_ep,
+                self.inner.inner.h160_address,
+            ))
+            .await?;
+        let base_fee = match self.base_fee {
+            Some(base_fee) => base_fee,
+            None => {
+                self.inner
+                    .inner
+                    .scoped(client_evm::base_fee(&rpc_ep))
+                    .await?
+            }
+        };
+
+        let mut tx = atomic::ExportTx {
+            network_id: self.inner.inner.network_id,
+            blockchain_id: self.inner.blockchain_id().await?,
+            destination_chain: self.destination_blockchain_id,
+            ins: vec![atomic::EvmInput {
+                address: self.inner.inner.h160_address,
+                amount: self.amount,
+                asset_id: self.inner.inner.avax_asset_id,
+                nonce,
+            }],
+            exported_outputs: vec![txs::transferable::Output {
+                asset_id: self.inner.inner.avax_asset_id,
+                transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                    amount: self.amount,
+                    output_owners: key::secp256k1::txs::OutputOwners {
+                        locktime: 0,
+                        threshold: 1,
+                        addresses: vec![self.inner.inner.short_address.clone()],
+                    },
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // the size of the transaction does not depend on the amounts
+        let gas = tx.gas_used(&[1])?;
+        let tx_fee = atomic::fee(gas, base_fee)?;
+        log::info!(
+            "exporting {} AVAX with tx fee {tx_fee} (gas {gas})",
+            self.amount
+        );
+        tx.ins[0].amount = self
+            .amount
+            .checked_add(tx_fee)
+            .ok_or_else(|| Error::Other {
+                message: format!("export amount {} with fee {tx_fee} overflows", self.amount),
+                retryable: false,
+            })?;
+
+        tx.sign(vec![vec![self.inner.inner.keychain.keys[0].clone()]])
+            .await?;
+        Ok(tx)
+    }
+}
@@ -0,0 +1,261 @@
+use std::time::SystemTime;
+
+use crate::{
+    coreth::atomic,
+    errors::{Error, Result},
+    formatting, ids,
+    jsonrpc::client::{c as client_c, evm as client_evm},
+    key, txs,
+};
+use primitive_types::U256;
+use tokio::time::Duration;
+
+/// Represents C-chain atomic "Import" transaction, which imports the AVAX
+/// exported from the source chain into the wallet EVM account.
+/// ref. <https://github.com/ava-labs/coreth/blob/master/plugin/evm/import_tx.go> "`newImportTx`".
+#[derive(Clone, Debug)]
+pub struct Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: crate::wallet::c::C<T>,
+
+    /// Source blockchain id.
+    pub source_blockchain_id: ids::Id,
+
+    /// Base fee in wei to compute the fee with.
+    /// If none, fetches the current base fee.
+    pub base_fee: Option<U256>,
+
+    /// Set "true" to poll transfer status after issuance for its acceptance.
+    pub check_acceptance: bool,
+
+    /// Initial wait duration before polling for acceptance.
+    pub poll_initial_wait: Duration,
+    /// Wait between each poll intervals for acceptance.
+    pub poll_interval: Duration,
+    /// Maximum duration for polling.
+    pub poll_timeout: Duration,
+
+    /// Set to true to return transaction Id for "issue" in dry mode.
+    pub dry_mode: bool,
+}
+
+impl<T> Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    #[must_use]
+    pub fn new(c: &crate::wallet::c::C<T>) -> Self {
+        Self {
+            inner: c.clone(),
+            source_blockchain_id: ids::Id::empty(),
+            base_fee: None,
+            check_acceptance: false,
+            poll_initial_wait: Duration::from_millis(500),
+            poll_interval: Duration::from_millis(700),
+            poll_timeout: Duration::from_secs(300),
+            dry_mode: false,
+        }
+    }
+
+    /// Sets the source blockchain Id.
+    #[must_use]
+    pub const fn source_blockchain_id(mut self, blockchain_id: ids::Id) -> Self {
+        self.source_blockchain_id = blockchain_id;
+        self
+    }
+
+    /// Sets the base fee in wei.
+    #[must_use]
+    pub const fn base_fee(mut self, base_fee: U256) -> Self {
+        self.base_fee = Some(base_fee);
+        self
+    }
+
+    /// Sets the check acceptance boolean flag.
+    #[must_use]
+    pub const fn check_acceptance(mut self, check_acceptance: bool) -> Self {
+        self.check_acceptance = check_acceptance;
+        self
+    }
+
+    /// Sets the initial poll wait time.
+    #[must_use]
+    pub const fn poll_initial_wait(mut self, poll_initial_wait: Duration) -> Self {
+        self.poll_initial_wait = poll_initial_wait;
+        self
+    }
+
+    /// Sets the poll wait time between intervals.
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the poll timeout.
+    #[must_use]
+    pub const fn poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+
+    /// Sets the dry mode boolean flag.
+    #[must_use]
+    pub const fn dry_mode(mut self, dry_mode: bool) -> Self {
+        self.dry_mode = dry_mode;
+        self
+    }
+
+    /// Estimates the fee of importing one single-signature UTXO to the wallet
+    /// owner at the base fee, which is deducted from the imported amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base fee cannot be fetched.
+    pub async fn estimate_fee(&self) -> Result<u64> {
+        let tx = atomic::ImportTx {
+            imported_inputs: vec![txs::transferable::Input {
+                asset_id: self.inner.inner.avax_asset_id,
+                transfer_input: Some(key::secp256k1::txs::transfer::Input {
+                    amount: 0,
+                    sig_indices: vec![0],
+                }),
+                ..Default::default()
+            }],
+            outs: vec![atomic::EvmOutput::default()],
+            ..Default::default()
+        };
+        let base_fee = match self.base_fee {
+            Some(base_fee) => base_fee,
+            None => self.inner.base_fee().await?,
+        };
+        atomic::fee(tx.gas_used(&[1])?, base_fee)
+    }
+
+    /// Issues the import transaction and returns the transaction Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is nothing to import, if the transaction
+    /// fails to be issued, or if checking acceptance times out.
+    pub async fn issue(&self) -> Result<ids::Id> {
+        let picked_http_rpc = self.inner.inner.pick_base_http_url();
+        log::info!(
+            "importing from {} via {}",
+            self.source_blockchain_id,
+            picked_http_rpc.1
+        );
+
+        let tx = self.signed_tx(&picked_http_rpc.1).await?;
+        let metadata = tx
+            .metadata
+            .ok_or_else(|| Error::UnexpectedNone("signed import tx metadata".to_string()))?;
+        if self.dry_mode {
+            return Ok(metadata.id);
+        }
+
+        let hex_tx = formatting::encode_hex_with_checksum(&metadata.tx_bytes_with_signatures);
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_c::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
+        let tx_id = resp
+            .result
+            .ok_or_else(|| Error::API {
+                message: "failed to issue import tx (no result)".to_string(),
+                retryable: false,
+            })?
+            .tx_id;
+        log::info!("{tx_id} successfully issued");
+
+        if !self.check_acceptance {
+            log::debug!("skipping checking acceptance...");
+            return Ok(tx_id);
+        }
+        self.inner
+            .inner
+            .scoped(crate::wallet::c::wait_for_acceptance(
+                &picked_http_rpc.1,
+                &tx_id,
+                self.poll_initial_wait,
+                self.poll_interval,
+                self.poll_timeout,
+            ))
+            .await?;
+
+        Ok(tx_id)
+    }
+
+    /// Builds and signs the transaction with the atomic UTXOs and base fee
+    /// fetched via "`http_rpc`". The fee is deducted from the imported amount.
+    async fn signed_tx(&self, http_rpc: &str) -> Result<atomic::ImportTx> {
+        let c_address = self.inner.c_address()?;
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_c::get_atomic_utxos(
+                http_rpc,
+                &c_address,
+                &self.source_blockchain_id.to_string(),
+            ))
+            .await?;
+        let utxos = resp
+            .result
+            .and_then(|r| r.utxos)
+            .ok_or_else(|| Error::UnexpectedNone("GetUtxosResult utxos".to_string()))?;
+        log::debug!("fetched {} atomic UTXOs for {c_address}", utxos.len());
+
+        let now_unix = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("unexpected None duration_since")
+            .as_secs();
+
+        let imported = self.inner.inner.tx_builder().import(&utxos, now_unix)?;
+        let (import_inputs, signers) = (imported.inputs, imported.signers);
+        let import_amount = imported.amount;
+
+        let base_fee = match self.base_fee {
+            Some(base_fee) => base_fee,
+            None => {
+                self.inner
+                    .inner
+                    .scoped(client_evm::base_fee(&crate::wallet::c::C::<T>::rpc_ep(
+                        http_rpc,
+                    )))
+                    .await?
+            }
+        };
+
+        let mut tx = atomic::ImportTx {
+            network_id: self.inner.inner.network_id,
+            blockchain_id: self.inner.blockchain_id().await?,
+            source_chain: self.source_blockchain_id,
+            imported_inputs: import_inputs,
+            outs: vec![atomic::EvmOutput {
+                address: self.inner.inner.h160_address,
+                amount: import_amount,
+                asset_id: self.inner.inner.avax_asset_id,
+            }],
+            ..Default::default()
+        };
+
+        // the size of the transaction does not depend on the amounts
+        let num_signatures: Vec<usize> = signers.iter().map(Vec::len).collect();
+        let gas = tx.gas_used(&num_signatures)?;
+        let tx_fee = atomic::fee(gas, base_fee)?;
+        log::info!("importing total {import_amount} AVAX with tx fee {tx_fee} (gas {gas})");
+        if import_amount <= tx_fee {
+            return Err(Error::Other {
+                message: format!("import amount {import_amount} does not cover tx fee {tx_fee}"),
+                retryable: false,
+            });
+        }
+        tx.outs[0].amount = import_amount - tx_fee;
+
+        tx.sign(signers).await?;
+        Ok(tx)
+    }
+}
@@ -0,0 +1,141 @@
+pub mod export;
+pub mod import;
+
+use crate::{
+    choices::status::Status,
+    errors::{Error, Result},
+    ids,
+    jsonrpc::client::{c as client_c, evm as client_evm},
+    key,
+    utils::aliases::ChainAlias,
+    wallet,
+};
+use primitive_types::U256;
+use tokio::time::{sleep, Duration, Instant};
+
+impl<T> wallet::Wallet<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    /// Returns the C-chain wallet for the atomic (cross-chain) transactions.
+    #[must_use]
+    pub fn c(&self) -> C<T> {
+        C {
+            inner: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct C<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: crate::wallet::Wallet<T>,
+}
+
+impl<T> C<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    /// Returns the C-chain EVM endpoint of the base HTTP URL.
+    #[must_use]
+    pub fn rpc_ep(http_rpc: &str) -> String {
+        format!("{http_rpc}/ext/bc/C/rpc")
+    }
+
+    /// Returns the "C-" address of the wallet owner, which owns the
+    /// atomic UTXOs exported to the C-chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be formatted.
+    pub fn c_address(&self) -> Result<String> {
+        self.inner.keychain.keys[0].hrp_address(self.inner.network_id, "C")
+    }
+
+    /// Resolves the C-chain blockchain Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias cannot be resolved on any endpoint.
+    pub async fn blockchain_id(&self) -> Result<ids::Id> {
+        self.inner.blockchain_id(&ChainAlias::C).await
+    }
+
+    /// Fetches the base fee (in wei) that the atomic transactions burn gas at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base fee cannot be fetched from any endpoint.
+    pub async fn base_fee(&self) -> Result<U256> {
+        self.inner
+            .with_failover(|http_rpc| async move {
+                client_evm::base_fee(&Self::rpc_ep(&http_rpc)).await
+            })
+            .await
+    }
+
+    /// Builds the C-chain export transaction.
+    #[must_use]
+    pub fn export(&self) -> export::Tx<T> {
+        export::Tx::new(self)
+    }
+
+    /// Builds the C-chain import transaction.
+    #[must_use]
+    pub fn import(&self) -> import::Tx<T> {
+        import::Tx::new(self)
+    }
+}
+
+/// Polls "avax.getAtomicTxStatus" until the atomic transaction is accepted.
+///
+/// # Errors
+///
+/// Returns an error if the transaction is dropped or not accepted in time.
+pub(crate) async fn wait_for_acceptance(
+    http_rpc: &str,
+    tx_id: &ids::Id,
+    poll_initial_wait: Duration,
+    poll_interval: Duration,
+    poll_timeout: Duration,
+) -> Result<()> {
+    // enough time for txs processing
+    log::info!("initial waiting {poll_initial_wait:?}");
+    sleep(poll_initial_wait).await;
+
+    log::info!("polling to confirm atomic transaction");
+    let start = Instant::now();
+    loop {
+        let elapsed = start.elapsed();
+        if elapsed.gt(&poll_timeout) {
+            return Err(Error::API {
+                message: "failed to check acceptance in time".to_string(),
+                retryable: true,
+            });
+        }
+
+        let resp = client_c::get_atomic_tx_status(http_rpc, &tx_id.to_string()).await?;
+        let status = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetTxStatusResult".to_string()))?
+            .status;
+        match status {
+            Status::Accepted => {
+                log::info!("{tx_id} successfully accepted");
+                return Ok(());
+            }
+            Status::Unknown(s) if s == "Dropped" => {
+                return Err(Error::API {
+                    message: format!("{tx_id} dropped"),
+                    retryable: false,
+                });
+            }
+            status => {
+                log::warn!("{tx_id} {status} (not accepted yet in {http_rpc}, elapsed {elapsed:?})")
+            }
+        }
+        sleep(poll_interval).await;
+    }
+}
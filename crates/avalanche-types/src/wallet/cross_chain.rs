@@ -0,0 +1,452 @@
+//! Moves AVAX between the X, P and C chains of the primary network
+//! by exporting on the source chain and importing on the destination chain,
+//! and inspects the exported UTXOs still awaiting import in the shared memory.
+use std::{collections::BTreeSet, fmt};
+
+use crate::{
+    errors::{Error, Result},
+    ids,
+    jsonrpc::client::{c as client_c, p as client_p, x as client_x},
+    key, txs,
+    utils::aliases::ChainAlias,
+    wallet,
+};
+use tokio::time::Duration;
+
+/// Primary network chain that holds the wallet AVAX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chain {
+    X,
+    P,
+    C,
+}
+
+impl Chain {
+    /// Returns all the chains of the primary network.
+    #[must_use]
+    pub const fn all() -> [Self; 3] {
+        [Self::X, Self::P, Self::C]
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::X => write!(f, "X"),
+            Self::P => write!(f, "P"),
+            Self::C => write!(f, "C"),
+        }
+    }
+}
+
+/// Result of the cross-chain transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub export_tx_id: ids::Id,
+    pub import_tx_id: ids::Id,
+    /// Amount exported from the source chain, including the import fee
+    /// of the destination chain. The export fee is burned on top of it.
+    pub exported: u64,
+    /// Estimated fee of the import, deducted from the exported amount.
+    pub import_fee: u64,
+}
+
+/// Atomic UTXOs exported from one chain to the wallet owner on another chain,
+/// that are in the shared memory and not imported yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pending {
+    pub from: Chain,
+    pub to: Chain,
+    pub utxos: Vec<txs::utxo::Utxo>,
+}
+
+impl Pending {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.utxos.is_empty()
+    }
+
+    /// Returns the total amount of the asset in the pending UTXOs.
+    #[must_use]
+    pub fn amount(&self, asset_id: &ids::Id) -> u64 {
+        self.utxos
+            .iter()
+            .filter(|utxo| utxo.asset_id == *asset_id)
+            .filter_map(|utxo| {
+                utxo.transfer_output
+                    .as_ref()
+                    .map(|out| out.amount)
+                    .or_else(|| {
+                        utxo.stakeable_lock_out
+                            .as_ref()
+                            .map(|out| out.transfer_output.amount)
+                    })
+            })
+            .fold(0_u64, u64::saturating_add)
+    }
+
+    /// Returns the Ids of the export transactions that created the pending UTXOs, sorted.
+    #[must_use]
+    pub fn export_tx_ids(&self) -> Vec<ids::Id> {
+        self.utxos
+            .iter()
+            .map(|utxo| utxo.utxo_id.tx_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl fmt::Display for Pending {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}: {} UTXO(s) from export(s) {:?}",
+            self.from,
+            self.to,
+            self.utxos.len(),
+            self.export_tx_ids()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        )
+    }
+}
+
+impl<T> wallet::Wallet<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    /// Returns the orchestrator of the cross-chain transfers.
+    #[must_use]
+    pub fn cross_chain(&self) -> CrossChain<T> {
+        CrossChain {
+            inner: self.clone(),
+            poll_interval: Duration::from_millis(700),
+            poll_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Exports AVAX on the source chain, waits for its acceptance,
+/// and imports it on the destination chain.
+#[derive(Clone, Debug)]
+pub struct CrossChain<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: wallet::Wallet<T>,
+
+    /// Wait between each poll intervals for acceptance.
+    pub poll_interval: Duration,
+    /// Maximum duration for polling each transaction.
+    pub poll_timeout: Duration,
+}
+
+impl<T> CrossChain<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    /// Sets the poll wait time between intervals.
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the poll timeout.
+    #[must_use]
+    pub const fn poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+
+    /// Transfers "amount" of AVAX (in nAVAX) from the source chain to the same
+    /// owner on the destination chain, and returns both transaction Ids.
+    ///
+    /// The destination chain deducts its import fee from the imported UTXOs,
+    /// so the export includes the estimated import fee for the destination to
+    /// receive "amount". The source chain burns its export fee on top of it.
+    /// On the C-chain, the import fee follows the base fee, so the received
+    /// amount may differ slightly if the base fee moves in between.
+    ///
+    /// The import also consumes any other UTXOs already exported to the
+    /// destination from the source chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chains are the same, if the export is not
+    /// accepted in time, or if the import fails. If the import fails after
+    /// the export is accepted, the funds stay in the shared memory and can be
+    /// imported later with the destination chain import builder.
+    pub async fn transfer(&self, from: Chain, to: Chain, amount: u64) -> Result<Transfer> {
+        if from == to {
+            return Err(Error::Other {
+                message: format!("cannot transfer from {from} to the same chain"),
+                retryable: false,
+            });
+        }
+        if amount == 0 {
+            return Err(Error::Other {
+                message: "cannot transfer zero amount".to_string(),
+                retryable: false,
+            });
+        }
+
+        let from_chain_id = self.blockchain_id(from).await?;
+        let to_chain_id = self.blockchain_id(to).await?;
+
+        let import_fee = self.estimate_import_fee(to).await?;
+        let exported = amount.checked_add(import_fee).ok_or_else(|| Error::Other {
+            message: format!("transfer amount {amount} with import fee {import_fee} overflows"),
+            retryable: false,
+        })?;
+        log::info!(
+            "transferring {amount} AVAX from {from} to {to} (exporting {exported} with import fee {import_fee})"
+        );
+
+        let export_tx_id = self.export(from, to_chain_id, exported).await?;
+        log::info!("{from}-chain export {export_tx_id} accepted, importing to {to}-chain");
+
+        let import_tx_id = match self.import(to, from_chain_id).await {
+            Ok(tx_id) => tx_id,
+            Err(e) => {
+                // show what is stuck in the shared memory
+                let stuck = self.pending(from, to).await.map_or_else(
+                    |pe| format!("failed to inspect pending UTXOs '{}'", pe.message()),
+                    |pending| {
+                        format!(
+                            "{} nAVAX pending ({pending})",
+                            pending.amount(&self.inner.avax_asset_id)
+                        )
+                    },
+                );
+                return Err(Error::Other {
+                    message: format!(
+                        "export {export_tx_id} accepted but failed to import to {to}-chain '{}', {stuck}",
+                        e.message()
+                    ),
+                    retryable: e.retryable(),
+                });
+            }
+        };
+
+        Ok(Transfer {
+            export_tx_id,
+            import_tx_id,
+            exported,
+            import_fee,
+        })
+    }
+
+    /// Fetches the UTXOs exported from "from" to the wallet owner on "to"
+    /// that are awaiting import, to find the transfers stuck in between.
+    /// All pages are fetched, following the end-index cursors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chains are the same or the UTXOs cannot be
+    /// fetched from any endpoint.
+    pub async fn pending(&self, from: Chain, to: Chain) -> Result<Pending> {
+        if from == to {
+            return Err(Error::Other {
+                message: format!("no atomic UTXOs from {from} to the same chain"),
+                retryable: false,
+            });
+        }
+        let source_chain = self.blockchain_id(from).await?.to_string();
+        let address = match to {
+            Chain::X => self.inner.x_address.clone(),
+            Chain::P => self.inner.p_address.clone(),
+            Chain::C => self.inner.c().c_address()?,
+        };
+
+        let utxos = self
+            .inner
+            .with_failover(|http_rpc| {
+                let (address, source_chain) = (&address, &source_chain);
+                async move {
+                    match to {
+                        Chain::X => {
+                            let resp = client_x::get_atomic_utxos(&http_rpc, address, source_chain)
+                                .await?;
+                            Ok(resp.result.and_then(|r| r.utxos).unwrap_or_default())
+                        }
+                        Chain::P => {
+                            let resp = client_p::get_atomic_utxos(&http_rpc, address, source_chain)
+                                .await?;
+                            Ok(resp.result.and_then(|r| r.utxos).unwrap_or_default())
+                        }
+                        Chain::C => {
+                            let resp = client_c::get_atomic_utxos(&http_rpc, address, source_chain)
+                                .await?;
+                            Ok(resp.result.and_then(|r| r.utxos).unwrap_or_default())
+                        }
+                    }
+                }
+            })
+            .await?;
+        log::info!("{} pending UTXOs from {from} to {to}", utxos.len());
+
+        Ok(Pending { from, to, utxos })
+    }
+
+    /// Fetches the pending UTXOs of every pair of the chains,
+    /// and returns the non-empty ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the UTXOs of any pair cannot be fetched.
+    pub async fn pending_all(&self) -> Result<Vec<Pending>> {
+        let mut all = Vec::new();
+        for from in Chain::all() {
+            for to in Chain::all() {
+                if from == to {
+                    continue;
+                }
+                let pending = self.pending(from, to).await?;
+                if !pending.is_empty() {
+                    all.push(pending);
+                }
+            }
+        }
+        Ok(all)
+    }
+
+    async fn blockchain_id(&self, chain: Chain) -> Result<ids::Id> {
+        match chain {
+            Chain::X => Ok(self.inner.blockchain_id_x),
+            Chain::P => Ok(self.inner.blockchain_id_p),
+            Chain::C => self.inner.blockchain_id(&ChainAlias::C).await,
+        }
+    }
+
+    async fn estimate_import_fee(&self, to: Chain) -> Result<u64> {
+        match to {
+            // ref. "avalanchego/vms/avm.ImportTx" burns the static fee
+            Chain::X => Ok(self.inner.tx_fee),
+            Chain::P => self.inner.p().import().estimate_fee().await,
+            Chain::C => self.inner.c().import().estimate_fee().await,
+        }
+    }
+
+    /// Issues the export and waits for its acceptance.
+    async fn export(&self, from: Chain, to_chain_id: ids::Id, amount: u64) -> Result<ids::Id> {
+        match from {
+            Chain::X => {
+                self.inner
+                    .x()
+                    .export()
+                    .destination_blockchain_id(to_chain_id)
+                    .amount(amount)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+            Chain::P => {
+                self.inner
+                    .p()
+                    .export()
+                    .destination_blockchain_id(to_chain_id)
+                    .amount(amount)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+            Chain::C => {
+                self.inner
+                    .c()
+                    .export()
+                    .destination_blockchain_id(to_chain_id)
+                    .amount(amount)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+        }
+    }
+
+    /// Issues the import and waits for its acceptance.
+    async fn import(&self, to: Chain, from_chain_id: ids::Id) -> Result<ids::Id> {
+        match to {
+            Chain::X => {
+                self.inner
+                    .x()
+                    .import()
+                    .source_blockchain_id(from_chain_id)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+            Chain::P => {
+                self.inner
+                    .p()
+                    .import()
+                    .source_blockchain_id(from_chain_id)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+            Chain::C => {
+                self.inner
+                    .c()
+                    .import()
+                    .source_blockchain_id(from_chain_id)
+                    .check_acceptance(true)
+                    .poll_interval(self.poll_interval)
+                    .poll_timeout(self.poll_timeout)
+                    .issue()
+                    .await
+            }
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features wallet -- `wallet::cross_chain::test_pending` --exact --show-output
+#[test]
+fn test_pending() {
+    let avax = ids::Id::from_slice(&[1; 32]);
+    let other = ids::Id::from_slice(&[2; 32]);
+    let utxo = |tx_id: u8, output_index: u32, asset_id: ids::Id, amount: u64| txs::utxo::Utxo {
+        utxo_id: txs::utxo::Id {
+            tx_id: ids::Id::from_slice(&[tx_id; 32]),
+            output_index,
+            ..Default::default()
+        },
+        asset_id,
+        transfer_output: Some(key::secp256k1::txs::transfer::Output {
+            amount,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let pending = Pending {
+        from: Chain::X,
+        to: Chain::C,
+        utxos: vec![
+            utxo(9, 0, avax, 100),
+            utxo(3, 1, avax, 20),
+            utxo(9, 1, avax, 3),
+            utxo(3, 2, other, 1_000),
+        ],
+    };
+    assert!(!pending.is_empty());
+    assert_eq!(pending.amount(&avax), 123);
+    assert_eq!(pending.amount(&other), 1_000);
+    assert_eq!(
+        pending.export_tx_ids(),
+        vec![ids::Id::from_slice(&[3; 32]), ids::Id::from_slice(&[9; 32])]
+    );
+    assert!(pending.to_string().starts_with("X -> C: 4 UTXO(s)"));
+}
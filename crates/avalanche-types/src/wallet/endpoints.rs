@@ -0,0 +1,383 @@
+//! Health-aware endpoint picking for the wallet.
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Default number of consecutive failures before an endpoint is quarantined.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Default duration of the first quarantine.
+pub const DEFAULT_QUARANTINE_DURATION: Duration = Duration::from_secs(10);
+
+/// Default maximum duration of a quarantine, after repeated quarantines.
+pub const DEFAULT_MAX_QUARANTINE_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Weight of the latest latency sample in the moving average, out of 10.
+const LATENCY_SAMPLE_WEIGHT: u32 = 3;
+
+/// Configures when the endpoints are quarantined.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Policy {
+    /// Number of consecutive failures before the endpoint is quarantined.
+    /// Zero disables the quarantine.
+    pub failure_threshold: u32,
+    /// Duration of the first quarantine, doubled on every consecutive
+    /// quarantine of the same endpoint.
+    pub quarantine_duration: Duration,
+    /// Upper bound of the quarantine duration.
+    pub max_quarantine_duration: Duration,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            quarantine_duration: DEFAULT_QUARANTINE_DURATION,
+            max_quarantine_duration: DEFAULT_MAX_QUARANTINE_DURATION,
+        }
+    }
+}
+
+/// Represents the changes of the endpoint health, reported to the observer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// The endpoint has been quarantined after consecutive failures,
+    /// and is not picked until the quarantine expires.
+    Quarantined {
+        url: String,
+        consecutive_failures: u32,
+        duration: Duration,
+    },
+    /// The quarantined endpoint succeeded again.
+    Restored { url: String },
+    /// The request failed on one endpoint and is retried on another.
+    Failover { from: String, to: String },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Quarantined {
+                url,
+                consecutive_failures,
+                duration,
+            } => write!(
+                f,
+                "quarantined {url} for {duration:?} after {consecutive_failures} consecutive failures"
+            ),
+            Self::Restored { url } => write!(f, "restored {url}"),
+            Self::Failover { from, to } => write!(f, "failed over from {from} to {to}"),
+        }
+    }
+}
+
+/// Called on every endpoint health event (e.g., to count failovers in tests).
+#[derive(Clone)]
+pub struct Observer(Arc<dyn Fn(&Event) + Send + Sync>);
+
+impl Observer {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&Event) + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+/// Snapshot of the health of an endpoint.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub url: String,
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    /// Number of times the endpoint has been quarantined in a row,
+    /// reset on the next success.
+    pub quarantines: u32,
+    /// Moving average of the successful request latencies.
+    pub latency: Option<Duration>,
+    pub quarantined_until: Option<Instant>,
+}
+
+impl Stats {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            successes: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            quarantines: 0,
+            latency: None,
+            quarantined_until: None,
+        }
+    }
+
+    /// Returns the ratio of failed requests, or zero if no request was made.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn error_rate(&self) -> f64 {
+        let total = self.successes + self.failures;
+        if total == 0 {
+            return 0.0;
+        }
+        self.failures as f64 / total as f64
+    }
+
+    /// Returns "true" if the endpoint is quarantined at "now".
+    #[must_use]
+    pub fn is_quarantined(&self, now: Instant) -> bool {
+        self.quarantined_until.is_some_and(|until| now < until)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    cursor: usize,
+    stats: Vec<Stats>,
+}
+
+/// Tracks the health of the wallet endpoints, and picks them in roundrobin
+/// while skipping the quarantined ones.
+#[derive(Debug)]
+pub struct Endpoints {
+    urls: Vec<String>,
+    policy: Policy,
+    state: Mutex<State>,
+    observer: Option<Observer>,
+}
+
+impl Endpoints {
+    #[must_use]
+    pub fn new(urls: &[String], policy: Policy, observer: Option<Observer>) -> Self {
+        Self {
+            urls: urls.to_vec(),
+            policy,
+            state: Mutex::new(State {
+                cursor: 0,
+                stats: urls.iter().map(|u| Stats::new(u)).collect(),
+            }),
+            observer,
+        }
+    }
+
+    #[must_use]
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    #[must_use]
+    pub const fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// Picks the next endpoint in roundrobin that is not quarantined.
+    /// If all endpoints are quarantined, picks the one whose quarantine
+    /// expires first, so the requests keep going to the most likely recovered one.
+    /// Returns the pair of an index and its corresponding endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned or there is no endpoint.
+    #[must_use]
+    pub fn pick(&self) -> (usize, String) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let n = self.urls.len();
+
+        let picked = (0..n)
+            .map(|i| (state.cursor + i) % n)
+            .find(|&i| !state.stats[i].is_quarantined(now))
+            .unwrap_or_else(|| {
+                (0..n)
+                    .min_by_key(|&i| state.stats[i].quarantined_until)
+                    .unwrap()
+            });
+        state.cursor = (picked + 1) % n;
+
+        (picked, self.urls[picked].clone())
+    }
+
+    /// Records the successful request to the endpoint at "idx".
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    pub fn report_success(&self, idx: usize, latency: Duration) {
+        let restored = {
+            let mut state = self.state.lock().unwrap();
+            let Some(s) = state.stats.get_mut(idx) else {
+                return;
+            };
+            s.successes += 1;
+            s.consecutive_failures = 0;
+            s.quarantines = 0;
+            s.latency = Some(s.latency.map_or(latency, |avg| {
+                (latency * LATENCY_SAMPLE_WEIGHT + avg * (10 - LATENCY_SAMPLE_WEIGHT)) / 10
+            }));
+            s.quarantined_until.take().is_some()
+        };
+        if restored {
+            log::info!("endpoint {} restored", self.urls[idx]);
+            self.notify(&Event::Restored {
+                url: self.urls[idx].clone(),
+            });
+        }
+    }
+
+    /// Records the failed request to the endpoint at "idx",
+    /// and quarantines the endpoint once it reaches the failure threshold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    pub fn report_failure(&self, idx: usize) {
+        let quarantined = {
+            let mut state = self.state.lock().unwrap();
+            let Some(s) = state.stats.get_mut(idx) else {
+                return;
+            };
+            s.failures += 1;
+            s.consecutive_failures += 1;
+
+            if self.policy.failure_threshold == 0
+                || s.consecutive_failures < self.policy.failure_threshold
+                || s.is_quarantined(Instant::now())
+            {
+                None
+            } else {
+                let duration = self
+                    .policy
+                    .quarantine_duration
+                    .saturating_mul(2_u32.saturating_pow(s.quarantines))
+                    .min(self.policy.max_quarantine_duration);
+                s.quarantines = s.quarantines.saturating_add(1);
+                s.quarantined_until = Some(Instant::now() + duration);
+                Some((s.consecutive_failures, duration))
+            }
+        };
+        if let Some((consecutive_failures, duration)) = quarantined {
+            log::warn!(
+                "endpoint {} quarantined for {duration:?} after {consecutive_failures} consecutive failures",
+                self.urls[idx]
+            );
+            self.notify(&Event::Quarantined {
+                url: self.urls[idx].clone(),
+                consecutive_failures,
+                duration,
+            });
+        }
+    }
+
+    /// Records that the request failed on the endpoint at "from"
+    /// and is retried on the endpoint at "to".
+    pub fn report_failover(&self, from: usize, to: usize) {
+        log::warn!("failing over from {} to {}", self.urls[from], self.urls[to]);
+        self.notify(&Event::Failover {
+            from: self.urls[from].clone(),
+            to: self.urls[to].clone(),
+        });
+    }
+
+    /// Returns the snapshot of the health of all endpoints,
+    /// in the same order of the URLs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn stats(&self) -> Vec<Stats> {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    fn notify(&self, event: &Event) {
+        if let Some(observer) = &self.observer {
+            (observer.0)(event);
+        }
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features wallet -- `wallet::endpoints::test_endpoints` --exact --show-output
+#[test]
+fn test_endpoints() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let observed = Arc::clone(&events);
+    let urls = vec![
+        String::from("http://a:9650"),
+        String::from("http://b:9650"),
+        String::from("http://c:9650"),
+    ];
+    let eps = Endpoints::new(
+        &urls,
+        Policy {
+            failure_threshold: 2,
+            quarantine_duration: Duration::from_secs(60),
+            max_quarantine_duration: Duration::from_secs(60),
+        },
+        Some(Observer::new(move |e: &Event| {
+            observed.lock().unwrap().push(e.clone());
+        })),
+    );
+
+    // roundrobin while healthy
+    assert_eq!(eps.pick().0, 0);
+    assert_eq!(eps.pick().0, 1);
+    assert_eq!(eps.pick().0, 2);
+    assert_eq!(eps.pick().0, 0);
+
+    // "b" is quarantined after two consecutive failures and skipped
+    eps.report_failure(1);
+    assert!(events.lock().unwrap().is_empty());
+    eps.report_failure(1);
+    assert_eq!(
+        events.lock().unwrap()[0],
+        Event::Quarantined {
+            url: urls[1].clone(),
+            consecutive_failures: 2,
+            duration: Duration::from_secs(60),
+        }
+    );
+    assert_eq!(eps.pick().0, 2);
+    assert_eq!(eps.pick().0, 0);
+    assert_eq!(eps.pick().0, 2);
+
+    // all quarantined, picks the earliest to expire
+    eps.report_failure(0);
+    eps.report_failure(0);
+    eps.report_failure(2);
+    eps.report_failure(2);
+    assert_eq!(eps.pick().0, 1);
+
+    // restored on success
+    eps.report_success(1, Duration::from_millis(100));
+    eps.report_success(1, Duration::from_millis(200));
+    assert_eq!(
+        events.lock().unwrap().last().unwrap(),
+        &Event::Restored {
+            url: urls[1].clone()
+        }
+    );
+    assert_eq!(eps.pick().0, 1);
+    assert_eq!(eps.pick().0, 1);
+
+    let stats = eps.stats();
+    assert_eq!(stats[1].successes, 2);
+    assert_eq!(stats[1].failures, 2);
+    assert_eq!(stats[1].consecutive_failures, 0);
+    assert_eq!(stats[1].latency, Some(Duration::from_millis(130)));
+    assert!((stats[1].error_rate() - 0.5).abs() < f64::EPSILON);
+    assert!(stats[0].quarantined_until.is_some());
+
+    eps.report_failover(0, 1);
+    assert_eq!(
+        events.lock().unwrap().last().unwrap().to_string(),
+        "failed over from http://a:9650 to http://b:9650"
+    );
+}
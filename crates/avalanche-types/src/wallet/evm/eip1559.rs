@@ -213,6 +213,17 @@ where
         self
     }
 
+    /// Sets the fees suggested by "`evm::fees::Estimator`",
+    /// so the transaction keeps up with the congestion.
+    #[cfg(feature = "evm")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "evm")))]
+    #[must_use]
+    pub const fn fees(mut self, estimate: &crate::evm::fees::Estimate) -> Self {
+        self.max_priority_fee_per_gas = Some(estimate.max_priority_fee_per_gas);
+        self.max_fee_per_gas = Some(estimate.max_fee_per_gas);
+        self
+    }
+
     /// Overwrites all gas and fee parameters to mark this transaction as urgent.
     #[must_use]
     pub fn urgent(mut self) -> Self {
@@ -3,8 +3,8 @@ pub mod eip1559;
 use std::{ops::Div, sync::Arc, time::Duration};
 
 use crate::{
-    errors::{Error, Result},
-    jsonrpc::client::evm as jsonrpc_client_evm,
+    errors::Result,
+    jsonrpc::client::{evm as jsonrpc_client_evm, http},
     key, wallet,
 };
 use ethers::{
@@ -17,8 +17,6 @@ use ethers::{
 use ethers_providers::{Http, HttpRateLimitRetryPolicy, Provider, RetryClient};
 use lazy_static::lazy_static;
 use primitive_types::U256;
-use reqwest::ClientBuilder;
-use url::Url;
 
 #[derive(Clone, Debug)]
 pub struct Evm<T, S>
@@ -61,8 +59,13 @@ where
     ///
     /// Returns an error if the balance fetch fails.
     pub async fn balance(&self) -> Result<U256> {
-        let cur_balance =
-            jsonrpc_client_evm::get_balance(&self.chain_rpc_url, self.inner.h160_address).await?;
+        let cur_balance = self
+            .inner
+            .scoped(jsonrpc_client_evm::get_balance(
+                &self.chain_rpc_url,
+                self.inner.h160_address,
+            ))
+            .await?;
         Ok(cur_balance)
     }
 }
@@ -82,14 +85,9 @@ where
         S: ethers_signers::Signer + Clone,
         S::Error: 'static,
     {
-        // TODO: make timeouts + retries configurable
-        let provider = new_provider(
-            chain_rpc_url,
-            Duration::from_secs(15),
-            Duration::from_secs(30),
-            5,
-            Duration::from_secs(3),
-        )?;
+        // TODO: make retries configurable
+        let provider =
+            new_provider_with_http(&self.http, chain_rpc_url, 5, Duration::from_secs(3))?;
         let provider_arc = Arc::new(provider);
 
         let nonce_middleware = new_middleware(&provider_arc, eth_signer, chain_id)?;
@@ -124,35 +122,34 @@ pub fn new_provider(
     max_retries: u32,
     backoff_timeout: Duration,
 ) -> Result<Provider<RetryClient<Http>>> {
-    let u = Url::parse(chain_rpc_url).map_err(|e| Error::Other {
-        message: format!("failed to parse chain RPC URL {e}"),
-        retryable: false,
-    })?;
-
-    let http_cli = ClientBuilder::new()
-        .user_agent(env!("CARGO_PKG_NAME"))
+    let http = http::Http::builder()
         .connect_timeout(connect_timeout)
-        .connection_verbose(true)
         .timeout(request_timeout)
-        .danger_accept_invalid_certs(true) // make this configurable
-        .build()
-        .map_err(|e| {
-            // TODO: check retryable
-            Error::Other {
-                message: format!("failed reqwest::ClientBuilder.build '{e}'"),
-                retryable: false,
-            }
-        })?;
+        .build()?;
+    new_provider_with_http(&http, chain_rpc_url, max_retries, backoff_timeout)
+}
 
+/// Same as "`new_provider`", but sends the requests via the client
+/// (e.g., the client of the wallet), with its timeouts and TLS.
+///
+/// # Errors
+///
+/// Returns an error if the chain RPC URL is invalid.
+pub fn new_provider_with_http(
+    http: &http::Http,
+    chain_rpc_url: &str,
+    max_retries: u32,
+    backoff_timeout: Duration,
+) -> Result<Provider<RetryClient<Http>>> {
     // TODO: make "HttpRateLimitRetryPolicy" configurable
     let retry_client = RetryClient::new(
-        Http::new_with_client(u, http_cli),
+        http.ethers_transport(chain_rpc_url)?,
         Box::new(HttpRateLimitRetryPolicy),
         max_retries,
         u64::try_from(backoff_timeout.as_millis()).unwrap_or(u64::MAX),
     );
 
-    let provider = Provider::new(retry_client).interval(Duration::from_millis(2000u64));
+    let provider = Provider::new(retry_client).interval(http::PROVIDER_POLL_INTERVAL);
     Ok(provider)
 }
 
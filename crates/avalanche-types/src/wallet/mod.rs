@@ -1,4 +1,9 @@
 //! Wallets for Avalanche.
+pub mod builder;
+pub mod c;
+pub mod cross_chain;
+pub mod endpoints;
+pub mod offline;
 pub mod p;
 pub mod x;
 
@@ -6,16 +11,15 @@ pub mod x;
 #[cfg_attr(docsrs, doc(cfg(feature = "wallet_evm")))]
 pub mod evm;
 
-use std::{
-    fmt,
-    sync::{Arc, Mutex},
-};
+use std::{fmt, future::Future, sync::Arc, time::Instant};
 
 use crate::{
-    errors::Result,
+    constants,
+    errors::{Error, Result},
     ids::{self, short},
-    jsonrpc::client::{info as api_info, x as api_x},
-    key, utils,
+    jsonrpc::client::{http, info as api_info, p as api_p, x as api_x},
+    key, platformvm,
+    utils::{self, aliases::ChainAlias},
 };
 
 #[derive(Debug, Clone)]
@@ -25,7 +29,17 @@ pub struct Wallet<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone
 
     /// Base HTTP URLs without RPC endpoint path.
     pub base_http_urls: Vec<String>,
-    pub base_http_url_cursor: Arc<Mutex<usize>>, // to roundrobin
+    /// Health of the base HTTP URLs, shared by the clones of the wallet.
+    pub endpoints: Arc<endpoints::Endpoints>,
+
+    /// Maximum number of in-flight requests when querying all endpoints.
+    pub max_concurrent_requests: usize,
+
+    /// HTTP client of all requests of the wallet.
+    pub http: http::Http,
+
+    /// Order in which the UTXOs are consumed when building transactions.
+    pub utxo_selection: builder::SelectionStrategy,
 
     pub network_id: u32,
     pub network_name: String,
@@ -38,6 +52,8 @@ pub struct Wallet<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone
 
     pub blockchain_id_x: ids::Id,
     pub blockchain_id_p: ids::Id,
+    /// Chain IDs of the resolved chain aliases, shared by the clones of the wallet.
+    pub aliases: Arc<utils::aliases::Resolver>,
 
     pub avax_asset_id: ids::Id,
 
@@ -49,6 +65,9 @@ pub struct Wallet<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone
     pub create_subnet_tx_fee: u64,
     /// Transaction fee to create a new blockchain.
     pub create_blockchain_tx_fee: u64,
+    /// Dynamic fee configuration of the P-chain, if the network has activated
+    /// dynamic fees. If none, the static fees above are used instead.
+    pub p_fee_config: Option<platformvm::fees::Config>,
 }
 
 /// ref. <https://doc.rust-lang.org/std/string/trait.ToString.html>
@@ -61,6 +80,13 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "key_type: {}", self.key_type.as_str())?;
         writeln!(f, "http_rpcs: {:?}", self.base_http_urls)?;
+        writeln!(f, "endpoint_policy: {:?}", self.endpoints.policy())?;
+        writeln!(
+            f,
+            "max_concurrent_requests: {}",
+            self.max_concurrent_requests
+        )?;
+        writeln!(f, "utxo_selection: {}", self.utxo_selection)?;
         writeln!(f, "network_id: {}", self.network_id)?;
         writeln!(f, "network_name: {}", self.network_name)?;
 
@@ -86,7 +112,8 @@ where
             f,
             "create_blockchain_tx_fee: {}",
             self.create_blockchain_tx_fee
-        )
+        )?;
+        writeln!(f, "p_fee_config: {:?}", self.p_fee_config)
     }
 }
 
@@ -94,28 +121,85 @@ impl<T> Wallet<T>
 where
     T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
 {
-    /// Picks one endpoint in roundrobin, and updates the cursor for next calls.
+    /// Picks one endpoint in roundrobin, skipping the quarantined ones,
+    /// and updates the cursor for next calls.
     /// Returns the pair of an index and its corresponding endpoint.
-    /// Picks a base HTTP URL from the list of available URLs.
     ///
     /// # Panics
     ///
     /// Panics if the mutex is poisoned.
     #[must_use]
     pub fn pick_base_http_url(&self) -> (usize, String) {
-        let picked;
-        let http_rpc;
-        {
-            let mut idx = self.base_http_url_cursor.lock().unwrap();
-            picked = *idx;
-            http_rpc = self.base_http_urls[picked].clone();
-            *idx = (picked + 1) % self.base_http_urls.len();
-        }
-
+        let (picked, http_rpc) = self.endpoints.pick();
         log::debug!("picked base http URL {http_rpc} at index {picked}");
         (picked, http_rpc)
     }
 
+    /// Calls "f" with a picked base HTTP URL, and retries with the next
+    /// healthy endpoint if it fails, trying each endpoint at most once.
+    /// The latency and errors are recorded to quarantine the failing endpoints.
+    /// Only use this for idempotent requests (e.g., not for issuing transactions).
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error if the call fails on all endpoints.
+    pub async fn with_failover<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let mut prev: Option<usize> = None;
+        let mut last_err = None;
+        for _ in 0..self.base_http_urls.len().max(1) {
+            let (idx, http_rpc) = self.pick_base_http_url();
+            if let Some(from) = prev {
+                if from == idx {
+                    // every other endpoint is quarantined
+                    break;
+                }
+                self.endpoints.report_failover(from, idx);
+            }
+
+            let start = Instant::now();
+            match self.scoped(f(http_rpc)).await {
+                Ok(r) => {
+                    self.endpoints.report_success(idx, start.elapsed());
+                    return Ok(r);
+                }
+                Err(e) => {
+                    log::warn!("request failed on endpoint at index {idx} ({e})");
+                    self.endpoints.report_failure(idx);
+                    prev = Some(idx);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::Other {
+            message: "no endpoint available".to_string(),
+            retryable: false,
+        }))
+    }
+
+    /// Runs the future with the HTTP client of the wallet,
+    /// so all its requests are sent via the client.
+    pub async fn scoped<F: Future>(&self, f: F) -> F::Output {
+        http::scope(self.http.clone(), f).await
+    }
+
+    /// Resolves the chain alias (e.g., "C" or a custom subnet chain alias)
+    /// to its chain ID, caching the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias cannot be resolved on any endpoint.
+    pub async fn blockchain_id(&self, alias: &ChainAlias) -> Result<ids::Id> {
+        if let Some(chain_id) = self.aliases.get(alias) {
+            return Ok(chain_id);
+        }
+        self.with_failover(|http_rpc| async move { self.aliases.resolve(&http_rpc, alias).await })
+            .await
+    }
+
     /// Returns the P-chain wallet.
     #[must_use]
     pub fn p(&self) -> p::P<T> {
@@ -123,12 +207,35 @@ where
             inner: self.clone(),
         }
     }
+
+    /// Returns the transaction builder that spends AVAX from the wallet keychain
+    /// with the configured UTXO selection strategy.
+    #[must_use]
+    pub fn tx_builder(&self) -> builder::TxBuilder<'_, T> {
+        builder::TxBuilder::new(
+            &self.keychain,
+            self.avax_asset_id,
+            self.short_address.clone(),
+        )
+        .strategy(self.utxo_selection)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Builder<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync> {
     pub key: T,
+    /// Additional keys for spending UTXOs owned by multiple addresses
+    /// (e.g., multisig outputs with threshold > 1).
+    pub additional_keys: Vec<T>,
     pub base_http_urls: Vec<String>,
+    pub max_concurrent_requests: usize,
+    pub utxo_selection: builder::SelectionStrategy,
+    pub endpoint_policy: endpoints::Policy,
+    pub endpoint_observer: Option<endpoints::Observer>,
+    /// HTTP client of the wallet, the shared client if none.
+    pub http: Option<http::Http>,
+    /// Authentication of the base HTTP URLs that require it, keyed by the URL.
+    pub endpoint_auths: Vec<(String, http::Auth)>,
     pub only_evm: bool,
 }
 
@@ -139,7 +246,14 @@ where
     pub fn new(key: &T) -> Self {
         Self {
             key: key.clone(),
+            additional_keys: Vec::new(),
             base_http_urls: Vec::new(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            utxo_selection: builder::SelectionStrategy::default(),
+            endpoint_policy: endpoints::Policy::default(),
+            endpoint_observer: None,
+            http: None,
+            endpoint_auths: Vec::new(),
             only_evm: false,
         }
     }
@@ -167,6 +281,63 @@ where
         self
     }
 
+    /// Adds a key to the wallet keychain, so that the wallet can sign for
+    /// UTXOs that also require this key. The change outputs still go to
+    /// the address of the primary key.
+    #[must_use]
+    pub fn additional_key(mut self, key: &T) -> Self {
+        self.additional_keys.push(key.clone());
+        self
+    }
+
+    /// Sets the maximum number of in-flight requests when querying all endpoints
+    /// (e.g., fetching balances). Zero is treated as one.
+    #[must_use]
+    pub const fn max_concurrent_requests(mut self, n: usize) -> Self {
+        self.max_concurrent_requests = n;
+        self
+    }
+
+    /// Sets the order in which the UTXOs are consumed when building transactions.
+    #[must_use]
+    pub const fn utxo_selection(mut self, strategy: builder::SelectionStrategy) -> Self {
+        self.utxo_selection = strategy;
+        self
+    }
+
+    /// Sets when the failing endpoints are quarantined.
+    #[must_use]
+    pub const fn endpoint_policy(mut self, policy: endpoints::Policy) -> Self {
+        self.endpoint_policy = policy;
+        self
+    }
+
+    /// Sets the observer of the endpoint health events
+    /// (e.g., for the e2e tests to track failovers).
+    #[must_use]
+    pub fn endpoint_observer(mut self, observer: endpoints::Observer) -> Self {
+        self.endpoint_observer = Some(observer);
+        self
+    }
+
+    /// Sets the HTTP client of all requests of the wallet
+    /// (e.g., with the custom TLS or timeouts), instead of the shared one.
+    #[must_use]
+    pub fn http(mut self, http: http::Http) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// Sets the authentication of the requests to the endpoint
+    /// (e.g., the API key of a managed node provider).
+    /// Applied on "build" to the requests of this wallet to the same host and port,
+    /// other clients of this process are not affected.
+    #[must_use]
+    pub fn endpoint_auth(mut self, url: &str, auth: http::Auth) -> Self {
+        self.endpoint_auths.push((url.to_string(), auth));
+        self
+    }
+
     #[must_use]
     pub const fn only_evm(mut self) -> Self {
         self.only_evm = true;
@@ -200,7 +371,8 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if the wallet creation fails.
+    /// Returns an error if the wallet creation fails
+    /// or any endpoint authentication is invalid.
     ///
     /// # Panics
     ///
@@ -211,8 +383,20 @@ where
             self.base_http_urls.len()
         );
 
-        let keychain = key::secp256k1::keychain::Keychain::new(vec![self.key.clone()]);
+        let mut http = match &self.http {
+            Some(http) => http.clone(),
+            None => http::shared()?.clone(),
+        };
+        for (url, auth) in &self.endpoint_auths {
+            http = http.with_auth(url, auth.clone())?;
+        }
+
+        let mut keychain = key::secp256k1::keychain::Keychain::new(vec![self.key.clone()]);
+        for k in &self.additional_keys {
+            keychain.add(k.clone())?;
+        }
         let h160_address = keychain.keys[0].h160_address();
+        let aliases = utils::aliases::Resolver::new();
 
         let (
             network_id,
@@ -223,6 +407,7 @@ where
             tx_fee,
             create_subnet_tx_fee,
             create_blockchain_tx_fee,
+            p_fee_config,
         ) = if self.only_evm {
             log::warn!("wallet is only used for EVM thus skipping querying info API");
             (
@@ -234,51 +419,82 @@ where
                 0,
                 0,
                 0,
+                None,
             )
         } else {
-            let resp = api_info::get_network_id(&self.base_http_urls[0]).await?;
-            let network_id = resp.result.unwrap().network_id;
-            let resp = api_info::get_network_name(&self.base_http_urls[0]).await?;
-            let network_name = resp.result.unwrap().network_name;
-
-            let resp = api_info::get_blockchain_id(&self.base_http_urls[0], "X").await?;
-            let blockchain_id_x = resp.result.unwrap().blockchain_id;
-
-            let resp = api_info::get_blockchain_id(&self.base_http_urls[0], "P").await?;
-            let blockchain_id_p = resp.result.unwrap().blockchain_id;
-
-            let resp = api_x::get_asset_description(&self.base_http_urls[0], "AVAX").await?;
-            let resp = resp
-                .result
-                .expect("unexpected None GetAssetDescriptionResult");
-            let avax_asset_id = resp.asset_id;
-
-            let resp = api_info::get_tx_fee(&self.base_http_urls[0]).await?;
-            let get_tx_fee_result = resp.result.unwrap();
-            let tx_fee = get_tx_fee_result.tx_fee;
-            let create_subnet_tx_fee = get_tx_fee_result.create_subnet_tx_fee;
-            let create_blockchain_tx_fee = get_tx_fee_result.create_blockchain_tx_fee;
-
-            (
-                network_id,
-                network_name,
-                blockchain_id_x,
-                blockchain_id_p,
-                avax_asset_id,
-                tx_fee,
-                create_subnet_tx_fee,
-                create_blockchain_tx_fee,
-            )
+            // the wallet client is not built yet
+            http::scope(http.clone(), async {
+                let resp = api_info::get_network_id(&self.base_http_urls[0]).await?;
+                let network_id = resp.result.unwrap().network_id;
+                let resp = api_info::get_network_name(&self.base_http_urls[0]).await?;
+                let network_name = resp.result.unwrap().network_name;
+
+                let blockchain_id_x = aliases
+                    .resolve(&self.base_http_urls[0], &ChainAlias::X)
+                    .await?;
+                let blockchain_id_p = aliases
+                    .resolve(&self.base_http_urls[0], &ChainAlias::P)
+                    .await?;
+
+                // well-known networks need no lookup
+                let avax_asset_id = if let Some(id) =
+                    constants::network(network_id).and_then(|n| n.avax_asset_id)
+                {
+                    id
+                } else {
+                    let resp =
+                        api_x::get_asset_description(&self.base_http_urls[0], "AVAX").await?;
+                    resp.result
+                        .expect("unexpected None GetAssetDescriptionResult")
+                        .asset_id
+                };
+
+                let resp = api_info::get_tx_fee(&self.base_http_urls[0]).await?;
+                let get_tx_fee_result = resp.result.unwrap();
+                let tx_fee = get_tx_fee_result.tx_fee;
+                let create_subnet_tx_fee = get_tx_fee_result.create_subnet_tx_fee;
+                let create_blockchain_tx_fee = get_tx_fee_result.create_blockchain_tx_fee;
+
+                // older nodes do not serve "platform.getFeeConfig"
+                // (no dynamic fees), thus fall back to the static fees
+                let p_fee_config = match api_p::get_fee_config(&self.base_http_urls[0]).await {
+                    Ok(resp) => resp.result,
+                    Err(e @ Error::JsonRpc { .. }) => {
+                        log::info!(
+                            "P-chain dynamic fees not available ({}), using static fees",
+                            e.message()
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        log::warn!("failed to get P-chain fee config ({e}), using static fees");
+                        None
+                    }
+                };
+
+                Ok::<_, Error>((
+                    network_id,
+                    network_name,
+                    blockchain_id_x,
+                    blockchain_id_p,
+                    avax_asset_id,
+                    tx_fee,
+                    create_subnet_tx_fee,
+                    create_blockchain_tx_fee,
+                    p_fee_config,
+                ))
+            })
+            .await?
         };
 
         // 先获取所有依赖变量，避免 let/await 嵌套在结构体初始化内
         let x_address = self
             .key
-            .hrp_address(network_id, "X")
+            .hrp_address(network_id, ChainAlias::X.as_str())
             .expect("hrp_address X failed");
         let p_address = self
             .key
-            .hrp_address(network_id, "P")
+            .hrp_address(network_id, ChainAlias::P.as_str())
             .expect("hrp_address P failed");
         let short_address = self.key.short_address().expect("short_address failed");
 
@@ -287,7 +503,14 @@ where
             keychain,
 
             base_http_urls: self.base_http_urls.clone(),
-            base_http_url_cursor: std::sync::Arc::new(std::sync::Mutex::new(0)),
+            endpoints: Arc::new(endpoints::Endpoints::new(
+                &self.base_http_urls,
+                self.endpoint_policy,
+                self.endpoint_observer.clone(),
+            )),
+            max_concurrent_requests: self.max_concurrent_requests.max(1),
+            http,
+            utxo_selection: self.utxo_selection,
             eth_address: self.key.eth_address(),
             h160_address,
 
@@ -298,11 +521,13 @@ where
             short_address,
             blockchain_id_x,
             blockchain_id_p,
+            aliases: Arc::new(aliases),
             avax_asset_id,
             tx_fee,
             add_primary_network_validator_fee: ADD_PRIMARY_NETWORK_VALIDATOR_FEE,
             create_subnet_tx_fee,
             create_blockchain_tx_fee,
+            p_fee_config,
         };
 
         log::info!("initiated the wallet:\n{w}");
@@ -311,5 +536,8 @@ where
     }
 }
 
+/// Default maximum number of in-flight requests when querying all endpoints.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
 /// ref. <https://docs.avax.network/learn/platform-overview/transaction-fees/#fee-schedule>
 pub const ADD_PRIMARY_NETWORK_VALIDATOR_FEE: u64 = 0;
@@ -0,0 +1,410 @@
+//! Offline signing of the wallet transactions, in three phases:
+//! an online machine builds the "`UnsignedTx`" with the UTXOs from the node,
+//! an air-gapped machine reviews its "`Summary`" and signs it with the private
+//! keys (or Ledger), and an online machine issues the "`SignedTx`".
+//! Both are transferred as JSON. Only the X-chain transactions are supported.
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    avm::txs::{decode, partial::PartiallySignedTx},
+    codec::{self, serde::hex_0x_bytes::Hex0xBytes},
+    errors::{Error, Result},
+    formatting, ids,
+    jsonrpc::client::x as client_x,
+    key, txs,
+    utils::aliases::ChainAlias,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Represents the transaction built without signatures.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTx {
+    pub network_id: u32,
+    /// Chain to issue the transaction to (only "X").
+    pub chain_alias: String,
+    /// Codec-marshaled unsigned transaction (e.g., "avm.Tx.Unsigned"),
+    /// with the addresses to sign each input (one credential per input).
+    pub tx: PartiallySignedTx,
+}
+
+impl UnsignedTx {
+    /// Creates the unsigned transaction from the base transaction whose metadata
+    /// has the unsigned bytes (e.g., after signing with no signer),
+    /// and the signers of each input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain is not supported, the metadata is missing,
+    /// the signer addresses are unavailable, or the signers do not match the inputs.
+    pub fn new<T: key::secp256k1::ReadOnly>(
+        network_id: u32,
+        chain_alias: &str,
+        base_tx: &txs::Tx,
+        signers: &[Vec<T>],
+    ) -> Result<Self> {
+        check_chain_alias(chain_alias)?;
+        let metadata = base_tx
+            .metadata
+            .as_ref()
+            .ok_or_else(|| Error::UnexpectedNone("unsigned tx metadata".to_string()))?;
+
+        let mut signer_addrs = Vec::with_capacity(signers.len());
+        for keys in signers {
+            signer_addrs.push(
+                keys.iter()
+                    .map(key::secp256k1::ReadOnly::short_address)
+                    .collect::<Result<Vec<_>>>()?,
+            );
+        }
+        let tx = Self {
+            network_id,
+            chain_alias: chain_alias.to_string(),
+            tx: PartiallySignedTx::from_unsigned_bytes(
+                metadata.tx_bytes_with_no_signature.clone(),
+                signer_addrs,
+            )?,
+        };
+        tx.summary()?;
+        Ok(tx)
+    }
+
+    /// Decodes the unsigned bytes, and returns what the transaction does
+    /// to review before signing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain is not supported, or the bytes are not
+    /// a valid transaction of the network and chain.
+    pub fn summary(&self) -> Result<Summary> {
+        check_chain_alias(&self.chain_alias)?;
+        self.tx.validate()?;
+
+        let tx = self.tx.decode()?;
+        if tx.network_id != self.network_id {
+            return Err(Error::Other {
+                message: format!(
+                    "network ID {} in the transaction but expected {}",
+                    tx.network_id, self.network_id
+                ),
+                retryable: false,
+            });
+        }
+        let type_name = codec::x_registry()?
+            .type_name(tx.codec_version, tx.type_id)
+            .unwrap_or_default()
+            .to_string();
+        if !["avm.BaseTx", "avm.ImportTx", "avm.ExportTx"].contains(&type_name.as_str()) {
+            return Err(Error::Other {
+                message: format!(
+                    "unsupported transaction type ID {} for offline signing",
+                    tx.type_id
+                ),
+                retryable: false,
+            });
+        }
+        Ok(Summary {
+            chain_alias: self.chain_alias.clone(),
+            type_name,
+            tx,
+        })
+    }
+
+    /// Reviews the summary of the decoded transaction (logged), signs all
+    /// inputs with the keys, and returns the transaction to issue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is invalid, a signer key is missing,
+    /// or the signing fails.
+    #[allow(clippy::future_not_send)]
+    pub async fn sign<T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Sync>(
+        &self,
+        keys: &[T],
+    ) -> Result<SignedTx> {
+        self.sign_with_confirm(keys, |summary| {
+            log::info!("signing {summary}");
+            true
+        })
+        .await
+    }
+
+    /// Same as "sign", but only signs if "confirm" approves the summary of the
+    /// decoded transaction (e.g., after showing it to the user).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is invalid, not confirmed,
+    /// a signer key is missing, or the signing fails.
+    #[allow(clippy::future_not_send)]
+    pub async fn sign_with_confirm<T, F>(&self, keys: &[T], confirm: F) -> Result<SignedTx>
+    where
+        T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Sync,
+        F: FnOnce(&Summary) -> bool,
+    {
+        let summary = self.summary()?;
+        if !confirm(&summary) {
+            return Err(Error::Other {
+                message: "signing the transaction was not confirmed".to_string(),
+                retryable: false,
+            });
+        }
+
+        let mut tx = self.tx.clone();
+        tx.sign(keys).await?;
+        let metadata = tx.finalize()?;
+        Ok(SignedTx {
+            network_id: self.network_id,
+            chain_alias: self.chain_alias.clone(),
+            tx_id: metadata.id,
+            tx_bytes: metadata.tx_bytes_with_signatures,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        to_json(self)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid.
+    pub fn from_json(d: &str) -> Result<Self> {
+        from_json(d)
+    }
+}
+
+/// Represents what the unsigned transaction does, decoded from its bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub chain_alias: String,
+    /// Type of the unsigned transaction (e.g., "avm.ExportTx").
+    pub type_name: String,
+    pub tx: decode::DecodedTx,
+}
+
+impl Summary {
+    /// Returns the amount burned (the fee) per asset,
+    /// spent by the inputs but not by the outputs.
+    #[must_use]
+    pub fn burned(&self) -> BTreeMap<ids::Id, u64> {
+        let mut spent: BTreeMap<ids::Id, u64> = BTreeMap::new();
+        for input in self.tx.inputs.iter().chain(&self.tx.imported_inputs) {
+            let v = spent.entry(input.asset_id).or_default();
+            *v = v.saturating_add(input.amount);
+        }
+        for output in self
+            .tx
+            .outputs
+            .iter()
+            .chain(&self.tx.exported_outputs)
+            .flatten()
+        {
+            let v = spent.entry(output.asset_id).or_default();
+            *v = v.saturating_sub(output.amount);
+        }
+        spent.retain(|_, v| *v > 0);
+        spent
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} on {}-chain {} (network ID {})",
+            self.type_name, self.chain_alias, self.tx.blockchain_id, self.tx.network_id
+        )?;
+        if let Some(chain_id) = self.tx.source_chain_id {
+            writeln!(f, "importing from {chain_id}")?;
+        }
+        for input in self.tx.inputs.iter().chain(&self.tx.imported_inputs) {
+            writeln!(
+                f,
+                "input: {} of {} from UTXO {}:{}",
+                input.amount, input.asset_id, input.utxo.0, input.utxo.1
+            )?;
+        }
+        let fmt_output = |f: &mut fmt::Formatter<'_>, prefix: &str, o: &decode::Output| {
+            writeln!(
+                f,
+                "{prefix}: {} of {} to {:?} (threshold {}, locktime {})",
+                o.amount, o.asset_id, o.addresses, o.threshold, o.locktime
+            )
+        };
+        for output in self.tx.outputs.iter().flatten() {
+            fmt_output(f, "output", output)?;
+        }
+        if let Some(chain_id) = self.tx.destination_chain_id {
+            for output in self.tx.exported_outputs.iter().flatten() {
+                fmt_output(f, &format!("exported to {chain_id}"), output)?;
+            }
+        }
+        for (asset_id, amount) in self.burned() {
+            writeln!(f, "burned: {amount} of {asset_id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the signed transaction ready to issue.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTx {
+    pub network_id: u32,
+    pub chain_alias: String,
+    #[serde(rename = "txID")]
+    pub tx_id: ids::Id,
+    #[serde_as(as = "Hex0xBytes")]
+    pub tx_bytes: Vec<u8>,
+}
+
+impl SignedTx {
+    /// Returns the signed transaction in hex with checksum, as issued to the node.
+    #[must_use]
+    pub fn hex_tx(&self) -> String {
+        formatting::encode_hex_with_checksum(&self.tx_bytes)
+    }
+
+    /// Issues the transaction to the chain via "`http_rpc`",
+    /// and returns the transaction Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain is not supported, the issuance fails,
+    /// or the node returns a different transaction Id.
+    pub async fn issue(&self, http_rpc: &str) -> Result<ids::Id> {
+        check_chain_alias(&self.chain_alias)?;
+        let tx_id =
+            client_x::issue_raw_tx(http_rpc, &self.tx_bytes, formatting::Encoding::Hex).await?;
+        if tx_id != self.tx_id {
+            return Err(Error::API {
+                message: format!("issued tx {tx_id} but expected {}", self.tx_id),
+                retryable: false,
+            });
+        }
+        Ok(tx_id)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        to_json(self)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid.
+    pub fn from_json(d: &str) -> Result<Self> {
+        from_json(d)
+    }
+}
+
+/// Only the X-chain builders produce the unsigned transactions.
+fn check_chain_alias(chain_alias: &str) -> Result<()> {
+    if ChainAlias::from(chain_alias) != ChainAlias::X {
+        return Err(Error::Other {
+            message: format!(
+                "unsupported chain alias '{chain_alias}', only X-chain transactions are signed offline"
+            ),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
+fn to_json<T: Serialize>(v: &T) -> Result<String> {
+    serde_json::to_string(v).map_err(|e| Error::Other {
+        message: format!("failed serde_json::to_string '{e}'"),
+        retryable: false,
+    })
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(d: &str) -> Result<T> {
+    serde_json::from_str(d).map_err(|e| Error::Other {
+        message: format!("failed serde_json::from_str '{e}'"),
+        retryable: false,
+    })
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features wallet -- `wallet::offline::test_offline_signing` --exact --show-output
+#[test]
+fn test_offline_signing() {
+    use crate::{avm, key::secp256k1::ReadOnly};
+
+    macro_rules! ab {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    let k1 = key::secp256k1::private_key::Key::generate().unwrap();
+    let k2 = key::secp256k1::private_key::Key::generate().unwrap();
+    let input = |idx: u32, sig_indices: Vec<u32>| txs::transferable::Input {
+        utxo_id: txs::utxo::Id::new(&[1; 32], idx, false).unwrap(),
+        asset_id: ids::Id::from_slice(&[2]),
+        transfer_input: Some(key::secp256k1::txs::transfer::Input {
+            amount: 100,
+            sig_indices,
+        }),
+        ..Default::default()
+    };
+    let base_tx = txs::Tx {
+        network_id: 10,
+        blockchain_id: ids::Id::from_slice(&[3]),
+        transferable_inputs: Some(vec![input(0, vec![0]), input(1, vec![0, 1])]),
+        transferable_outputs: Some(Vec::new()),
+        memo: Some(Vec::new()),
+        ..Default::default()
+    };
+    let signers = vec![vec![k1.clone()], vec![k1.clone(), k2.clone()]];
+
+    // online: build
+    let mut unsigned = avm::txs::Tx::new(base_tx.clone());
+    ab!(unsigned.sign(Vec::<Vec<key::secp256k1::private_key::Key>>::new())).unwrap();
+    assert!(UnsignedTx::new(10, "P", &unsigned.base_tx, &signers).is_err());
+    assert!(UnsignedTx::new(10, "X", &unsigned.base_tx, &signers[..1]).is_err());
+    let unsigned = UnsignedTx::new(10, "X", &unsigned.base_tx, &signers).unwrap();
+    assert_eq!(
+        unsigned.tx.signers[1],
+        vec![k1.short_address().unwrap(), k2.short_address().unwrap()]
+    );
+
+    // offline: review and sign
+    let unsigned = UnsignedTx::from_json(&unsigned.to_json().unwrap()).unwrap();
+    let summary = unsigned.summary().unwrap();
+    assert_eq!(summary.type_name, "avm.BaseTx");
+    assert_eq!(summary.tx.inputs.len(), 2);
+    assert_eq!(summary.burned().get(&ids::Id::from_slice(&[2])), Some(&200));
+    assert!(summary.to_string().contains("burned: 200 of"));
+
+    // the bytes are checked against the network and chain
+    let mut tampered = unsigned.clone();
+    tampered.network_id = 1;
+    assert!(ab!(tampered.sign(&[k1.clone(), k2.clone()])).is_err());
+    tampered.network_id = 10;
+    tampered.chain_alias = "C".to_string();
+    assert!(ab!(tampered.sign(&[k1.clone(), k2.clone()])).is_err());
+
+    assert!(ab!(unsigned.sign_with_confirm(&[k1.clone(), k2.clone()], |_| false)).is_err());
+    assert!(ab!(unsigned.sign(&[k1.clone()])).is_err());
+    let signed = ab!(unsigned.sign(&[k2, k1])).unwrap();
+
+    // same as signing online
+    let mut expected = avm::txs::Tx::new(base_tx);
+    ab!(expected.sign(signers)).unwrap();
+    let metadata = expected.base_tx.metadata.unwrap();
+    assert_eq!(signed.tx_bytes, metadata.tx_bytes_with_signatures);
+    assert_eq!(signed.tx_id, metadata.id);
+
+    let mut signed = SignedTx::from_json(&signed.to_json().unwrap()).unwrap();
+    assert_eq!(signed.tx_id, metadata.id);
+
+    signed.chain_alias = "P".to_string();
+    assert!(ab!(signed.issue("http://127.0.0.1:9650")).is_err());
+}
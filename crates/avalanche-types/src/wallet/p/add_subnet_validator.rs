@@ -212,45 +212,7 @@ where
             return Ok((ids::Id::empty(), false));
         }
 
-        let cur_balance_p = self.inner.balance().await?;
-        if cur_balance_p < self.inner.inner.tx_fee {
-            return Err(Error::Other {
-                message: format!("key address {} (balance {} nano-AVAX, network {}) does not have enough to cover stake amount + fee {}", self.inner.inner.p_address, cur_balance_p, self.inner.inner.network_name, self.inner.inner.tx_fee),
-                retryable: false,
-            });
-        }
-        log::info!(
-            "{} current P-chain balance {}",
-            self.inner.inner.p_address,
-            cur_balance_p
-        );
-
-        let (ins, unstaked_outs, _, signers) = self.inner.spend(0, self.inner.inner.tx_fee).await?;
-        let (subnet_auth, subnet_signers) = self.inner.authorize(self.subnet_id).await?;
-
-        let mut tx = platformvm::txs::add_subnet_validator::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(unstaked_outs),
-                transferable_inputs: Some(ins),
-                ..Default::default()
-            },
-            validator: platformvm::txs::add_subnet_validator::Validator {
-                validator: platformvm::txs::Validator {
-                    node_id: self.node_id,
-                    start: self.start_time.timestamp() as u64,
-                    end: self.end_time.timestamp() as u64,
-                    weight: self.weight,
-                },
-                subnet_id: self.subnet_id,
-            },
-            // if "sig_indices" empty, it errors with "unauthorized subnet modification: input has less signers than expected"
-            subnet_auth,
-            ..Default::default()
-        };
-        let all_signers = [signers, subnet_signers].concat();
-        tx.sign(all_signers).await?;
+        let tx = self.signed_tx().await?;
 
         if self.dry_mode {
             return Ok((tx.base_tx.metadata.unwrap().id, false));
@@ -258,26 +220,31 @@ where
 
         let tx_bytes_with_signatures = tx.base_tx.metadata.unwrap().tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_p::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
-        if let Some(e) = resp.error {
+        let resp = match self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await
+        {
+            Ok(resp) => resp,
             // handle duplicate validator
             // ref. "avalanchego/vms/platformvm/txs/executor" "verifyAddValidatorTx"
-            let already_validator = e.message.contains("duplicate validation");
-            if already_validator {
+            Err(Error::JsonRpc { message, .. }) if message.contains("duplicate validation") => {
                 log::warn!(
                     "node Id {} is already a subnet validator -- returning empty tx Id ({})",
                     self.node_id,
-                    e.message
+                    message
                 );
                 return Ok((ids::Id::empty(), false));
             }
-
-            return Err(Error::API {
-                message: format!("failed to issue add subnet validator transaction {e:?}"),
-                retryable: false,
-            });
-        }
+            Err(e) => {
+                log::warn!(
+                    "failed to issue add subnet validator transaction ({})",
+                    e.message()
+                );
+                return Err(e);
+            }
+        };
 
         let tx_id = resp.result.unwrap().tx_id;
         log::info!("{tx_id} successfully issued");
@@ -299,7 +266,14 @@ where
                 break;
             }
 
-            let resp = client_p::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == platformvm::txs::status::Status::Committed {
@@ -358,4 +332,65 @@ where
 
         Ok((tx_id, true))
     }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::add_subnet_validator::Tx> {
+        let cur_balance_p = self.inner.balance().await?;
+        if cur_balance_p < self.inner.inner.tx_fee {
+            return Err(Error::Other {
+                message: format!("key address {} (balance {} nano-AVAX, network {}) does not have enough to cover stake amount + fee {}", self.inner.inner.p_address, cur_balance_p, self.inner.inner.network_name, self.inner.inner.tx_fee),
+                retryable: false,
+            });
+        }
+        log::info!(
+            "{} current P-chain balance {}",
+            self.inner.inner.p_address,
+            cur_balance_p
+        );
+
+        let (subnet_auth, subnet_signers) = self.inner.authorize(self.subnet_id).await?;
+        let intrinsic = platformvm::fees::ADD_SUBNET_VALIDATOR_TX_COMPLEXITY
+            .checked_add(&platformvm::fees::auth_complexity(&subnet_auth))?;
+        let (ins, unstaked_outs, _, signers, _) = self
+            .inner
+            .spend_with_fee(0, self.inner.inner.tx_fee, intrinsic)
+            .await?;
+
+        let mut tx = platformvm::txs::add_subnet_validator::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            validator: platformvm::txs::add_subnet_validator::Validator {
+                validator: platformvm::txs::Validator {
+                    node_id: self.node_id,
+                    start: self.start_time.timestamp() as u64,
+                    end: self.end_time.timestamp() as u64,
+                    weight: self.weight,
+                },
+                subnet_id: self.subnet_id,
+            },
+            // if "sig_indices" empty, it errors with "unauthorized subnet modification: input has less signers than expected"
+            subnet_auth,
+            ..Default::default()
+        };
+        let all_signers = [signers, subnet_signers].concat();
+        tx.sign(all_signers).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(&tx.base_tx, &[], &[])
+    }
 }
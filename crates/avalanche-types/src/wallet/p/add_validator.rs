@@ -48,6 +48,12 @@ where
 
     /// Set to true to return transaction Id for "issue" in dry mode.
     pub dry_mode: bool,
+
+    /// Staking parameters to validate the stake amount, period, and
+    /// reward fee against before issuing the transaction.
+    /// Defaults to the parameters of the wallet network if known
+    /// (none for custom networks, which skips the validation).
+    pub staking_config: Option<platformvm::reward::StakingConfig>,
 }
 
 impl<T> Tx<T>
@@ -89,6 +95,7 @@ where
             poll_interval: Duration::from_secs(1),
             poll_timeout: Duration::from_secs(300),
             dry_mode: false,
+            staking_config: platformvm::reward::StakingConfig::for_network(p.inner.network_id),
         }
     }
 
@@ -187,6 +194,65 @@ where
         self
     }
 
+    /// Sets the staking parameters to validate against (none to skip the validation).
+    #[must_use]
+    pub const fn staking_config(
+        mut self,
+        staking_config: Option<platformvm::reward::StakingConfig>,
+    ) -> Self {
+        self.staking_config = staking_config;
+        self
+    }
+
+    /// Returns the validate period between the start and end time.
+    fn stake_duration(&self) -> Duration {
+        (self.end_time - self.start_time)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Validates the stake amount, period, and reward fee against the staking parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them is out of the allowed range.
+    pub fn validate(&self) -> Result<()> {
+        let Some(staking_config) = self.staking_config else {
+            return Ok(());
+        };
+        staking_config.validate_validator(
+            self.stake_amount,
+            self.stake_duration(),
+            self.reward_fee_percent * 10000,
+        )
+    }
+
+    /// Returns the expected reward in nano-AVAX for the stake amount and period,
+    /// based on the current supply of the network (before the delegation fees).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current supply cannot be fetched.
+    pub async fn expected_reward(&self) -> Result<u64> {
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_p::get_current_supply(
+                &self.inner.inner.pick_base_http_url().1,
+            ))
+            .await?;
+        let current_supply = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetCurrentSupplyResult".to_string()))?
+            .supply;
+
+        let reward_config = self
+            .staking_config
+            .map(|c| c.reward_config)
+            .unwrap_or_default();
+        Ok(reward_config.calculate(self.stake_duration(), self.stake_amount, current_supply))
+    }
+
     /// Issues the add validator transaction and returns the transaction Id.
     /// The boolean return represents whether the `add_validator` request was
     /// successfully issued or not (regardless of its acceptance).
@@ -221,51 +287,7 @@ where
             return Ok((ids::Id::empty(), false));
         }
 
-        let cur_balance_p = self.inner.balance().await?;
-        if cur_balance_p < self.stake_amount + self.inner.inner.add_primary_network_validator_fee {
-            return Err(Error::Other {
-                message: format!("key address {} (balance {} nano-AVAX, network {}) does not have enough to cover stake amount + fee {}", self.inner.inner.p_address, cur_balance_p, self.inner.inner.network_name, self.stake_amount + self.inner.inner.add_primary_network_validator_fee),
-                retryable: false,
-            });
-        }
-        log::info!(
-            "{} current P-chain balance {}",
-            self.inner.inner.p_address,
-            cur_balance_p
-        );
-
-        let (ins, unstaked_outs, staked_outs, signers) = self
-            .inner
-            .spend(
-                self.stake_amount,
-                self.inner.inner.add_primary_network_validator_fee,
-            )
-            .await?;
-
-        let mut tx = platformvm::txs::add_validator::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(unstaked_outs),
-                transferable_inputs: Some(ins),
-                ..Default::default()
-            },
-            validator: platformvm::txs::Validator {
-                node_id: self.node_id,
-                start: self.start_time.timestamp() as u64,
-                end: self.end_time.timestamp() as u64,
-                weight: self.stake_amount,
-            },
-            stake_transferable_outputs: Some(staked_outs),
-            rewards_owner: key::secp256k1::txs::OutputOwners {
-                locktime: 0,
-                threshold: 1,
-                addresses: vec![self.inner.inner.short_address.clone()],
-            },
-            shares: self.reward_fee_percent * 10000,
-            ..Default::default()
-        };
-        tx.sign(signers).await?;
+        let tx = self.signed_tx().await?;
 
         if self.dry_mode {
             return Ok((tx.base_tx.metadata.unwrap().id, false));
@@ -273,26 +295,31 @@ where
 
         let tx_bytes_with_signatures = tx.base_tx.metadata.unwrap().tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_p::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
-        if let Some(e) = resp.error {
-            let already_validator = e
-                .message
-                .contains("attempted to issue duplicate validation for");
-            if already_validator {
+        let resp = match self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(Error::JsonRpc { message, .. })
+                if message.contains("attempted to issue duplicate validation for") =>
+            {
                 log::warn!(
                     "node Id {} is already a validator -- returning empty tx Id ({})",
                     self.node_id,
-                    e.message
+                    message
                 );
                 return Ok((ids::Id::empty(), false));
             }
-
-            return Err(Error::API {
-                message: format!("failed to issue add validator transaction {e:?}"),
-                retryable: false,
-            });
-        }
+            Err(e) => {
+                log::warn!(
+                    "failed to issue add validator transaction ({})",
+                    e.message()
+                );
+                return Err(e);
+            }
+        };
 
         let tx_id = resp.result.unwrap().tx_id;
         log::info!("{tx_id} successfully issued");
@@ -314,7 +341,14 @@ where
                 break;
             }
 
-            let resp = client_p::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == platformvm::txs::status::Status::Committed {
@@ -373,4 +407,74 @@ where
 
         Ok((tx_id, true))
     }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::add_validator::Tx> {
+        self.validate()?;
+
+        let cur_balance_p = self.inner.balance().await?;
+        if cur_balance_p < self.stake_amount + self.inner.inner.add_primary_network_validator_fee {
+            return Err(Error::Other {
+                message: format!("key address {} (balance {} nano-AVAX, network {}) does not have enough to cover stake amount + fee {}", self.inner.inner.p_address, cur_balance_p, self.inner.inner.network_name, self.stake_amount + self.inner.inner.add_primary_network_validator_fee),
+                retryable: false,
+            });
+        }
+        log::info!(
+            "{} current P-chain balance {}",
+            self.inner.inner.p_address,
+            cur_balance_p
+        );
+
+        // "AddValidatorTx" is not accepted once the network activates dynamic fees,
+        // so it always burns the static fee (use "add_permissionless_validator")
+        let (ins, unstaked_outs, staked_outs, signers) = self
+            .inner
+            .spend(
+                self.stake_amount,
+                self.inner.inner.add_primary_network_validator_fee,
+            )
+            .await?;
+
+        let mut tx = platformvm::txs::add_validator::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            validator: platformvm::txs::Validator {
+                node_id: self.node_id,
+                start: self.start_time.timestamp() as u64,
+                end: self.end_time.timestamp() as u64,
+                weight: self.stake_amount,
+            },
+            stake_transferable_outputs: Some(staked_outs),
+            rewards_owner: key::secp256k1::txs::OutputOwners {
+                locktime: 0,
+                threshold: 1,
+                addresses: vec![self.inner.inner.short_address.clone()],
+            },
+            shares: self.reward_fee_percent * 10000,
+            ..Default::default()
+        };
+        tx.sign(signers).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(
+            &tx.base_tx,
+            &[],
+            tx.stake_transferable_outputs.as_deref().unwrap_or_default(),
+        )
+    }
 }
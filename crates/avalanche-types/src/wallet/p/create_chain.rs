@@ -143,28 +143,7 @@ where
             picked_http_rpc.1
         );
 
-        let (ins, unstaked_outs, _, signers) = self
-            .inner
-            .spend(0, self.inner.inner.create_blockchain_tx_fee)
-            .await?;
-        let (subnet_auth, subnet_signers) = self.inner.authorize(self.subnet_id).await?;
-
-        let mut tx = platformvm::txs::create_chain::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(unstaked_outs),
-                transferable_inputs: Some(ins),
-                ..Default::default()
-            },
-            subnet_id: self.subnet_id,
-            chain_name: self.chain_name.clone(),
-            vm_id: self.vm_id,
-            genesis_data: self.genesis_data.clone(),
-            subnet_auth,
-            ..Default::default()
-        };
-        tx.sign([signers, subnet_signers].concat()).await?;
+        let tx = self.signed_tx().await?;
 
         if self.dry_mode {
             return Ok(tx.base_tx.metadata.unwrap().id);
@@ -172,14 +151,11 @@ where
 
         let tx_bytes_with_signatures = tx.base_tx.metadata.unwrap().tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_p::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
-        if let Some(e) = resp.error {
-            return Err(Error::API {
-                message: format!("failed to issue create chain transaction {e:?}"),
-                retryable: false,
-            });
-        }
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
 
         let tx_id = resp.result.unwrap().tx_id;
         log::info!("{tx_id} successfully issued");
@@ -201,7 +177,14 @@ where
                 break;
             }
 
-            let resp = client_p::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == platformvm::txs::status::Status::Committed {
@@ -228,4 +211,49 @@ where
 
         Ok(tx_id)
     }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::create_chain::Tx> {
+        let (subnet_auth, subnet_signers) = self.inner.authorize(self.subnet_id).await?;
+        let intrinsic = platformvm::fees::CREATE_CHAIN_TX_COMPLEXITY
+            .checked_add(&platformvm::fees::bytes_complexity(
+                self.chain_name.as_bytes(),
+            ))?
+            .checked_add(&platformvm::fees::bytes_complexity(&self.genesis_data))?
+            .checked_add(&platformvm::fees::auth_complexity(&subnet_auth))?;
+        let (ins, unstaked_outs, _, signers, _) = self
+            .inner
+            .spend_with_fee(0, self.inner.inner.create_blockchain_tx_fee, intrinsic)
+            .await?;
+
+        let mut tx = platformvm::txs::create_chain::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            subnet_id: self.subnet_id,
+            chain_name: self.chain_name.clone(),
+            vm_id: self.vm_id,
+            genesis_data: self.genesis_data.clone(),
+            subnet_auth,
+            ..Default::default()
+        };
+        tx.sign([signers, subnet_signers].concat()).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(&tx.base_tx, &[], &[])
+    }
 }
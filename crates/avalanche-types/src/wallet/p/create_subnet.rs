@@ -120,27 +120,7 @@ where
         let picked_http_rpc = self.inner.inner.pick_base_http_url();
         log::info!("creating a new subnet via {}", picked_http_rpc.1);
 
-        let (ins, unstaked_outs, _, signers) = self
-            .inner
-            .spend(0, self.inner.inner.create_subnet_tx_fee)
-            .await?;
-
-        let mut tx = platformvm::txs::create_subnet::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(unstaked_outs),
-                transferable_inputs: Some(ins),
-                ..Default::default()
-            },
-            owner: key::secp256k1::txs::OutputOwners {
-                locktime: 0,
-                threshold: self.threshold,
-                addresses: self.control_keys.clone(),
-            },
-            ..Default::default()
-        };
-        tx.sign(signers).await?;
+        let tx = self.signed_tx().await?;
 
         if self.dry_mode {
             return Ok(tx.base_tx.metadata.unwrap().id);
@@ -148,14 +128,11 @@ where
 
         let tx_bytes_with_signatures = tx.base_tx.metadata.unwrap().tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_p::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
-        if let Some(e) = resp.error {
-            return Err(Error::API {
-                message: format!("failed to issue create subnet transaction {e:?}"),
-                retryable: false,
-            });
-        }
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
 
         let tx_id = resp.result.unwrap().tx_id;
         log::info!("{tx_id} successfully issued");
@@ -177,7 +154,14 @@ where
                 break;
             }
 
-            let resp = client_p::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == platformvm::txs::status::Status::Committed {
@@ -204,4 +188,45 @@ where
 
         Ok(tx_id)
     }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::create_subnet::Tx> {
+        let owner = key::secp256k1::txs::OutputOwners {
+            locktime: 0,
+            threshold: self.threshold,
+            addresses: self.control_keys.clone(),
+        };
+        let intrinsic = platformvm::fees::CREATE_SUBNET_TX_COMPLEXITY
+            .checked_add(&platformvm::fees::owners_complexity(&owner))?;
+        let (ins, unstaked_outs, _, signers, _) = self
+            .inner
+            .spend_with_fee(0, self.inner.inner.create_subnet_tx_fee, intrinsic)
+            .await?;
+
+        let mut tx = platformvm::txs::create_subnet::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            owner,
+            ..Default::default()
+        };
+        tx.sign(signers).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(&tx.base_tx, &[], &[])
+    }
 }
@@ -128,32 +128,7 @@ where
             picked_http_rpc.1
         );
 
-        let (ins, unstaked_outs, _, signers) = self.inner.spend(0, self.inner.inner.tx_fee).await?;
-
-        let mut tx = platformvm::txs::export::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(unstaked_outs),
-                transferable_inputs: Some(ins),
-                ..Default::default()
-            },
-            destination_chain_id: self.destination_blockchain_id,
-            destination_chain_transferable_outputs: Some(vec![txs::transferable::Output {
-                asset_id: self.inner.inner.avax_asset_id,
-                transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                    amount: self.amount,
-                    output_owners: key::secp256k1::txs::OutputOwners {
-                        locktime: 0,
-                        threshold: 1,
-                        addresses: vec![self.inner.inner.short_address.clone()],
-                    },
-                }),
-                ..Default::default()
-            }]),
-            ..Default::default()
-        };
-        tx.sign(signers).await?;
+        let tx = self.signed_tx().await?;
 
         if self.dry_mode {
             return Ok(tx.base_tx.metadata.unwrap().id);
@@ -161,14 +136,11 @@ where
 
         let tx_bytes_with_signatures = tx.base_tx.metadata.unwrap().tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_p::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
-        if let Some(e) = resp.error {
-            return Err(Error::API {
-                message: format!("failed to issue export transaction {e:?}"),
-                retryable: false,
-            });
-        }
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
 
         let tx_id = resp.result.unwrap().tx_id;
         log::info!("{tx_id} successfully issued");
@@ -190,7 +162,14 @@ where
                 break;
             }
 
-            let resp = client_p::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == platformvm::txs::status::Status::Committed {
@@ -217,4 +196,59 @@ where
 
         Ok(tx_id)
     }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::export::Tx> {
+        let exported_outs = vec![txs::transferable::Output {
+            asset_id: self.inner.inner.avax_asset_id,
+            transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                amount: self.amount,
+                output_owners: key::secp256k1::txs::OutputOwners {
+                    locktime: 0,
+                    threshold: 1,
+                    addresses: vec![self.inner.inner.short_address.clone()],
+                },
+            }),
+            ..Default::default()
+        }];
+        let intrinsic = platformvm::fees::EXPORT_TX_COMPLEXITY
+            .checked_add(&platformvm::fees::outputs_complexity(&exported_outs)?)?;
+        let (ins, unstaked_outs, _, signers, _) = self
+            .inner
+            .spend_with_fee(0, self.inner.inner.tx_fee, intrinsic)
+            .await?;
+
+        let mut tx = platformvm::txs::export::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            destination_chain_id: self.destination_blockchain_id,
+            destination_chain_transferable_outputs: Some(exported_outs),
+            ..Default::default()
+        };
+        tx.sign(signers).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(
+            &tx.base_tx,
+            &[],
+            tx.destination_chain_transferable_outputs
+                .as_deref()
+                .unwrap_or_default(),
+        )
+    }
 }
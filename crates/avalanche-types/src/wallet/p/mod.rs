@@ -5,8 +5,9 @@ pub mod create_chain;
 pub mod create_subnet;
 pub mod export;
 pub mod import;
+pub mod remove_subnet_validator;
 
-use std::{cmp, time::SystemTime};
+use std::time::SystemTime;
 
 use crate::{
     errors::{Error, Result},
@@ -14,6 +15,11 @@ use crate::{
     jsonrpc::client::p as client_p,
     key, platformvm, txs,
 };
+use futures::{stream, StreamExt};
+
+/// Maximum number of re-spends to cover the dynamic fee,
+/// in case the spent inputs and outputs change with the fee.
+const MAX_SPEND_ATTEMPTS: usize = 5;
 
 #[derive(Clone, Debug)]
 pub struct P<T>
@@ -31,33 +37,39 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if the API call fails.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the result is missing.
+    /// Returns an error if the API call fails or the result is missing.
     pub async fn balance_with_endpoint(&self, http_rpc: &str) -> Result<u64> {
-        let resp = client_p::get_balance(http_rpc, &self.inner.p_address).await?;
+        let resp = self
+            .inner
+            .scoped(client_p::get_balance(http_rpc, &self.inner.p_address))
+            .await?;
         let cur_balance = resp
             .result
-            .expect("unexpected None GetBalanceResult")
+            .ok_or_else(|| Error::UnexpectedNone("GetBalanceResult".to_string()))?
             .balance;
         Ok(cur_balance)
     }
 
     /// Fetches the current balance of the wallet owner from all endpoints
-    /// in the same order of "`self.http_rpcs`".
+    /// in the same order of "`self.http_rpcs`", with at most
+    /// "`max_concurrent_requests`" requests in flight.
     ///
     /// # Errors
     ///
-    /// Returns an error if the balance fetch fails for any endpoint.
+    /// Returns the first error if any endpoint fails,
+    /// use "`balances_partial`" to get the balances of the others.
     pub async fn balances(&self) -> Result<Vec<u64>> {
-        let mut balances = Vec::new();
-        for http_rpc in &self.inner.base_http_urls {
-            let balance = self.balance_with_endpoint(http_rpc).await?;
-            balances.push(balance);
-        }
-        Ok(balances)
+        self.balances_partial().await.into_iter().collect()
+    }
+
+    /// Same as "`balances`", but each endpoint returns its own result,
+    /// so one failing endpoint does not fail the whole batch.
+    pub async fn balances_partial(&self) -> Vec<Result<u64>> {
+        stream::iter(self.inner.base_http_urls.iter())
+            .map(|http_rpc| self.balance_with_endpoint(http_rpc))
+            .buffered(self.inner.max_concurrent_requests.max(1))
+            .collect()
+            .await
     }
 
     /// Fetches the current balance of the wallet owner.
@@ -66,7 +78,8 @@ where
     ///
     /// Returns an error if the balance fetch fails.
     pub async fn balance(&self) -> Result<u64> {
-        self.balance_with_endpoint(&self.inner.pick_base_http_url().1)
+        self.inner
+            .with_failover(|http_rpc| async move { self.balance_with_endpoint(&http_rpc).await })
             .await
     }
 
@@ -77,8 +90,12 @@ where
     ///
     /// Returns an error if the UTXOs fetch fails or if the response is invalid.
     pub async fn utxos(&self) -> Result<Vec<txs::utxo::Utxo>> {
-        let resp =
-            client_p::get_utxos(&self.inner.pick_base_http_url().1, &self.inner.p_address).await?;
+        let resp = self
+            .inner
+            .with_failover(|http_rpc| async move {
+                client_p::get_utxos(&http_rpc, &self.inner.p_address).await
+            })
+            .await?;
         let utxos = resp
             .result
             .ok_or(Error::UnexpectedNoneGetUtxosResult)?
@@ -97,8 +114,12 @@ where
     ///
     /// Panics if the response result or validators field is None.
     pub async fn is_primary_network_validator(&self, node_id: &node::Id) -> Result<bool> {
-        let resp =
-            client_p::get_primary_network_validators(&self.inner.pick_base_http_url().1).await?;
+        let resp = self
+            .inner
+            .scoped(client_p::get_primary_network_validators(
+                &self.inner.pick_base_http_url().1,
+            ))
+            .await?;
         let resp = resp
             .result
             .expect("unexpected None GetCurrentValidatorResult");
@@ -126,11 +147,13 @@ where
         node_id: &node::Id,
         subnet_id: &ids::Id,
     ) -> Result<bool> {
-        let resp = client_p::get_subnet_validators(
-            &self.inner.pick_base_http_url().1,
-            &subnet_id.to_string(),
-        )
-        .await?;
+        let resp = self
+            .inner
+            .scoped(client_p::get_subnet_validators(
+                &self.inner.pick_base_http_url().1,
+                &subnet_id.to_string(),
+            ))
+            .await?;
         let resp = resp
             .result
             .expect("unexpected None GetCurrentValidatorResult");
@@ -158,221 +181,127 @@ where
         Vec<Vec<T>>,
     )> {
         let utxos = self.utxos().await?;
+        self.spend_from(&utxos, amount, fee)
+    }
 
+    /// Same as "spend", but selects from the given UTXOs
+    /// instead of fetching them.
+    fn spend_from(
+        &self,
+        utxos: &[txs::utxo::Utxo],
+        amount: u64,
+        fee: u64,
+    ) -> Result<(
+        Vec<txs::transferable::Input>,
+        Vec<txs::transferable::Output>,
+        Vec<txs::transferable::Output>,
+        Vec<Vec<T>>,
+    )> {
         let now_unix = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .expect("unexpected None duration_since")
             .as_secs();
 
-        let mut ins: Vec<txs::transferable::Input> = Vec::new();
-        let mut returned_outputs: Vec<txs::transferable::Output> = Vec::new();
-        let mut staked_outputs: Vec<txs::transferable::Output> = Vec::new();
-        let mut signers: Vec<Vec<T>> = Vec::new();
-
-        // amount of AVAX that has been staked
-        let mut amount_staked: u64 = 0_u64;
-
-        // consume locked UTXOs
-        for utxo in &utxos {
-            // no need to consume more locked AVAX
-            // because it already has consumed more than the target stake amount
-            if amount_staked >= amount {
-                break;
-            }
-
-            // only staking avax so ignore other assets
-            if utxo.asset_id != self.inner.avax_asset_id {
-                continue;
-            }
-
-            // check "*platformvm.StakeableLockOut"
-            if utxo.stakeable_lock_out.is_none() {
-                // output is not locked, thus handle this in the next iteration
-                continue;
-            }
-
-            // check locktime
-            let out = utxo.stakeable_lock_out.clone().unwrap();
-            if out.locktime <= now_unix {
-                // output is no longer locked, thus handle in the next iteration
-                continue;
-            }
-
-            // check "*secp256k1fx.TransferOutput"
-            let inner = out.clone().transfer_output;
-            let res = self.inner.keychain.spend(&inner, now_unix);
-            if res.is_none() {
-                // cannot spend the output, move onto next
-                continue;
-            }
-            let (transfer_input, in_signers) = res.unwrap();
-
-            let mut remaining_value = transfer_input.amount;
-            let amount_to_stake = cmp::min(
-                amount - amount_staked, // amount we still need to stake
-                remaining_value,        // amount available to stake
-            );
-            amount_staked += amount_to_stake;
-            remaining_value -= amount_to_stake;
-
-            // add input to the consumed inputs
-            ins.push(txs::transferable::Input {
-                utxo_id: utxo.utxo_id.clone(),
-                asset_id: utxo.asset_id,
-                stakeable_lock_in: Some(platformvm::txs::StakeableLockIn {
-                    locktime: out.locktime,
-                    transfer_input,
-                }),
-                ..txs::transferable::Input::default()
-            });
-
-            // add output to the staked outputs
-            staked_outputs.push(txs::transferable::Output {
-                asset_id: utxo.asset_id,
-                stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
-                    locktime: out.clone().locktime,
-                    transfer_output: key::secp256k1::txs::transfer::Output {
-                        amount: remaining_value,
-                        output_owners: out.clone().transfer_output.output_owners,
-                    },
-                }),
-                ..txs::transferable::Output::default()
-            });
-
-            if remaining_value > 0 {
-                // this input provided more value than was needed to be locked
-                // some must be returned
-                returned_outputs.push(txs::transferable::Output {
-                    asset_id: utxo.asset_id,
-                    stakeable_lock_out: Some(platformvm::txs::StakeableLockOut {
-                        locktime: out.clone().locktime,
-                        transfer_output: key::secp256k1::txs::transfer::Output {
-                            amount: amount_to_stake,
-                            output_owners: out.clone().transfer_output.output_owners,
-                        },
-                    }),
-                    ..txs::transferable::Output::default()
-                });
-            }
-
-            signers.push(in_signers);
-        }
-
-        // amount of AVAX that has been burned
-        let mut amount_burned = 0_u64;
-
-        for utxo in &utxos {
-            // have staked/burned more AVAX than we need
-            // thus no need to consume more AVAX
-            if amount_burned >= fee && amount_staked >= amount {
-                break;
-            }
+        let staked = self
+            .inner
+            .tx_builder()
+            .spend_and_stake(utxos, amount, fee, now_unix)?;
+        Ok((
+            staked.inputs,
+            staked.returned_outputs,
+            staked.staked_outputs,
+            staked.signers,
+        ))
+    }
 
-            // only burn AVAX, thus ignore other assets
-            if utxo.asset_id != self.inner.avax_asset_id {
-                continue;
-            }
+    /// Returns the current gas price of the P-chain dynamic fees.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fee state cannot be fetched
+    /// (e.g., the network has not activated dynamic fees).
+    pub async fn gas_price(&self) -> Result<u64> {
+        let resp = self
+            .inner
+            .scoped(client_p::get_fee_state(&self.inner.pick_base_http_url().1))
+            .await?;
+        let price = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetFeeStateResult".to_string()))?
+            .price;
+        Ok(price)
+    }
 
-            let (skip, out) = {
-                if utxo.transfer_output.is_some() {
-                    let out = utxo.transfer_output.clone().unwrap();
-                    (false, out)
-                } else {
-                    let inner = utxo.stakeable_lock_out.clone().unwrap();
-                    (inner.locktime > now_unix, inner.transfer_output)
-                }
-            };
-            // output is currently locked, so this output cannot be burned
-            // or it may have already been consumed above
-            if skip {
-                continue;
-            }
+    /// Returns the fee to burn for the transaction complexity at the current
+    /// gas price, or the "`static_fee`" if the network has not activated dynamic fees.
+    async fn fee(&self, static_fee: u64, complexity: &platformvm::fees::Dimensions) -> Result<u64> {
+        let Some(fee_config) = self.inner.p_fee_config else {
+            return Ok(static_fee);
+        };
+        fee_config
+            .calculator(self.gas_price().await?)
+            .fee(complexity)
+    }
 
-            let res = self.inner.keychain.spend(&out, now_unix);
-            if res.is_none() {
-                // cannot spend the output, move onto next
-                continue;
-            }
-            let (transfer_input, in_signers) = res.unwrap();
-
-            // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L763
-            let mut remaining_value = transfer_input.amount;
-            let amount_to_burn = cmp::min(
-                fee - amount_burned, // amount we still need to burn
-                remaining_value,     // amount available to burn
-            );
-            amount_burned += amount_to_burn;
-            remaining_value -= amount_to_burn;
-
-            let amount_to_stake = cmp::min(
-                amount - amount_staked, // amount we still need to stake
-                remaining_value,        // amount available to stake
-            );
-            amount_staked += amount_to_stake;
-            remaining_value -= amount_to_stake;
-
-            // add the input to the consumed inputs
-            ins.push(txs::transferable::Input {
-                utxo_id: utxo.utxo_id.clone(),
-                asset_id: utxo.asset_id,
-                transfer_input: Some(transfer_input),
-                ..txs::transferable::Input::default()
-            });
-
-            if amount_to_stake > 0 {
-                // some of this input was put for staking
-                staked_outputs.push(txs::transferable::Output {
-                    asset_id: utxo.asset_id,
-                    transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                        amount: amount_to_stake,
-                        output_owners: key::secp256k1::txs::OutputOwners {
-                            locktime: 0,
-                            threshold: 1,
-                            addresses: vec![self.inner.short_address.clone()],
-                        },
-                    }),
-                    ..txs::transferable::Output::default()
-                });
-            }
+    /// Spends UTXOs to stake "amount" and to burn the transaction fee,
+    /// and returns the burned fee along with the "spend" outputs.
+    ///
+    /// If the network has activated dynamic fees, the fee is computed from the
+    /// transaction complexity ("intrinsic" plus the spent inputs and outputs)
+    /// at the current gas price. Since the spent inputs and outputs depend on
+    /// the fee, it re-spends until the burned fee covers the complexity.
+    /// Otherwise, it burns the "`static_fee`".
+    ///
+    /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.12.0/wallet/chain/p/builder/builder.go> "spend"
+    async fn spend_with_fee(
+        &self,
+        amount: u64,
+        static_fee: u64,
+        intrinsic: platformvm::fees::Dimensions,
+    ) -> Result<(
+        Vec<txs::transferable::Input>,
+        Vec<txs::transferable::Output>,
+        Vec<txs::transferable::Output>,
+        Vec<Vec<T>>,
+        u64,
+    )> {
+        let Some(fee_config) = self.inner.p_fee_config else {
+            let (ins, returned_outputs, staked_outputs, signers) =
+                self.spend(amount, static_fee).await?;
+            return Ok((ins, returned_outputs, staked_outputs, signers, static_fee));
+        };
 
-            if remaining_value > 0 {
-                // this input had extra value, so some must be returned
-                returned_outputs.push(txs::transferable::Output {
-                    asset_id: utxo.asset_id,
-                    transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                        amount: remaining_value,
-                        output_owners: key::secp256k1::txs::OutputOwners {
-                            locktime: 0,
-                            threshold: 1,
-                            addresses: vec![self.inner.short_address.clone()],
-                        },
-                    }),
-                    ..txs::transferable::Output::default()
-                });
+        // fetch the UTXOs once, and only reselect from them with the updated fee
+        let utxos = self.utxos().await?;
+        let calculator = fee_config.calculator(self.gas_price().await?);
+        let mut fee = calculator.fee(&intrinsic)?;
+        for _ in 0..MAX_SPEND_ATTEMPTS {
+            let (ins, returned_outputs, staked_outputs, signers) =
+                self.spend_from(&utxos, amount, fee)?;
+
+            let complexity = intrinsic
+                .checked_add(&platformvm::fees::inputs_complexity(&ins)?)?
+                .checked_add(&platformvm::fees::outputs_complexity(&returned_outputs)?)?
+                .checked_add(&platformvm::fees::outputs_complexity(&staked_outputs)?)?;
+            let required_fee = calculator.fee(&complexity)?;
+            if required_fee <= fee {
+                log::info!(
+                    "burning dynamic fee {fee} for complexity {complexity:?} at gas price {}",
+                    calculator.price
+                );
+                return Ok((ins, returned_outputs, staked_outputs, signers, fee));
             }
 
-            signers.push(in_signers);
+            log::info!("fee {fee} does not cover complexity {complexity:?} (requires {required_fee}), re-spending");
+            fee = required_fee;
         }
 
-        log::info!(
-            "provided keys have balance (unlocked/burned amount so far, locked/staked amount so far) ({amount_burned}, {amount_staked}) and need ({fee}, {amount})"
-        );
-        if amount_burned < fee || amount_staked < amount {
-            return Err(Error::Other {
-                message: format!(
-                    "provided keys have balance (unlocked/burned amount so far, locked/staked amount so far) ({amount_burned}, {amount_staked}) but need ({fee}, {amount})"
-                ),
-                retryable: false,
-            });
-        }
-
-        // TODO: for now just ignore "signers" in the sorting
-        // since the wallet currently only supports one key
-        ins.sort();
-        returned_outputs.sort();
-        staked_outputs.sort();
-
-        Ok((ins, returned_outputs, staked_outputs, signers))
+        Err(Error::Other {
+            message: format!(
+                "failed to cover dynamic fee after {MAX_SPEND_ATTEMPTS} attempts (last fee {fee})"
+            ),
+            retryable: true,
+        })
     }
 
     /// ref. <https://github.com/ava-labs/avalanchego/blob/v1.9.4/vms/platformvm/utxo/handler.go#L411> "Authorize"
@@ -384,8 +313,13 @@ where
     ) -> Result<(key::secp256k1::txs::Input, Vec<Vec<T>>)> {
         log::info!("authorizing subnet {subnet_id}");
 
-        let tx =
-            client_p::get_tx(&self.inner.pick_base_http_url().1, &subnet_id.to_string()).await?;
+        let tx = self
+            .inner
+            .scoped(client_p::get_tx(
+                &self.inner.pick_base_http_url().1,
+                &subnet_id.to_string(),
+            ))
+            .await?;
         if let Some(tx_result) = tx.result {
             let output_owners = tx_result.tx.unsigned_tx.output_owners;
 
@@ -447,6 +381,12 @@ where
         add_subnet_validator::Tx::new(self)
     }
 
+    /// Removes a validator from a permissioned subnet before its end time.
+    #[must_use]
+    pub fn remove_subnet_validator(&self) -> remove_subnet_validator::Tx<T> {
+        remove_subnet_validator::Tx::new(self)
+    }
+
     /// Once the subnet validators are added, each virtual machine must create
     /// its own blockchain and use the chain Id as the RPC endpoint.
     #[must_use]
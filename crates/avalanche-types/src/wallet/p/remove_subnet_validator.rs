@@ -0,0 +1,282 @@
+use crate::{
+    errors::{Error, Result},
+    formatting,
+    ids::{self, node},
+    jsonrpc::client::p as client_p,
+    key, platformvm, txs,
+};
+use tokio::time::{sleep, Duration, Instant};
+
+/// Represents P-chain `RemoveSubnetValidator` transaction.
+///
+/// ref. <https://github.com/ava-labs/avalanchego/blob/v1.12.0/wallet/chain/p/builder/builder.go> `NewRemoveSubnetValidatorTx`
+#[derive(Clone, Debug)]
+pub struct Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    pub inner: crate::wallet::p::P<T>,
+
+    pub node_id: node::Id,
+    pub subnet_id: ids::Id,
+
+    /// Set "true" to poll transaction status after issuance for its acceptance.
+    pub check_acceptance: bool,
+
+    /// Initial wait duration before polling for acceptance.
+    pub poll_initial_wait: Duration,
+    /// Wait between each poll intervals for acceptance.
+    pub poll_interval: Duration,
+    /// Maximum duration for polling.
+    pub poll_timeout: Duration,
+
+    /// Set to true to return transaction Id for "issue" in dry mode.
+    pub dry_mode: bool,
+}
+
+impl<T> Tx<T>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    #[must_use]
+    pub fn new(p: &crate::wallet::p::P<T>) -> Self {
+        Self {
+            inner: p.clone(),
+            node_id: node::Id::empty(),
+            subnet_id: ids::Id::empty(),
+            check_acceptance: false,
+            poll_initial_wait: Duration::from_millis(1500),
+            poll_interval: Duration::from_secs(1),
+            poll_timeout: Duration::from_secs(300),
+            dry_mode: false,
+        }
+    }
+
+    /// Sets the subnet validator node Id to remove.
+    #[must_use]
+    pub const fn node_id(mut self, node_id: node::Id) -> Self {
+        self.node_id = node_id;
+        self
+    }
+
+    /// Sets the subnet Id.
+    #[must_use]
+    pub const fn subnet_id(mut self, subnet_id: ids::Id) -> Self {
+        self.subnet_id = subnet_id;
+        self
+    }
+
+    /// Sets the check acceptance boolean flag.
+    #[must_use]
+    pub const fn check_acceptance(mut self, check_acceptance: bool) -> Self {
+        self.check_acceptance = check_acceptance;
+        self
+    }
+
+    /// Sets the initial poll wait time.
+    #[must_use]
+    pub const fn poll_initial_wait(mut self, poll_initial_wait: Duration) -> Self {
+        self.poll_initial_wait = poll_initial_wait;
+        self
+    }
+
+    /// Sets the poll wait time between intervals.
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the poll timeout.
+    #[must_use]
+    pub const fn poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+
+    /// Sets the dry mode boolean flag.
+    #[must_use]
+    pub const fn dry_mode(mut self, dry_mode: bool) -> Self {
+        self.dry_mode = dry_mode;
+        self
+    }
+
+    /// Issues the remove subnet validator transaction and returns the transaction Id.
+    /// The boolean return represents whether the `remove_subnet_validator` request was
+    /// successfully issued or not (regardless of its acceptance).
+    /// If the node is not a subnet validator, it returns an empty Id and false.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to be issued or if the acceptance check fails.
+    pub async fn issue(&self) -> Result<(ids::Id, bool)> {
+        let picked_http_rpc = self.inner.inner.pick_base_http_url();
+        log::info!(
+            "removing {} from subnet {} validators via {}",
+            self.node_id,
+            self.subnet_id,
+            picked_http_rpc.1
+        );
+
+        let is_validator = self
+            .inner
+            .is_subnet_validator(&self.node_id, &self.subnet_id)
+            .await?;
+        if !is_validator {
+            log::warn!(
+                "node Id {} is not a subnet validator -- returning empty tx Id",
+                self.node_id
+            );
+            return Ok((ids::Id::empty(), false));
+        }
+
+        let tx = self.signed_tx().await?;
+        let metadata = tx.base_tx.metadata.ok_or_else(|| {
+            Error::UnexpectedNone("signed remove subnet validator tx metadata".to_string())
+        })?;
+        if self.dry_mode {
+            return Ok((metadata.id, false));
+        }
+
+        let hex_tx = formatting::encode_hex_with_checksum(&metadata.tx_bytes_with_signatures);
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_p::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
+
+        let tx_id = resp
+            .result
+            .ok_or_else(|| Error::API {
+                message: "failed to issue remove subnet validator tx (no result)".to_string(),
+                retryable: false,
+            })?
+            .tx_id;
+        log::info!("{tx_id} successfully issued");
+
+        if !self.check_acceptance {
+            log::debug!("skipping checking acceptance...");
+            return Ok((tx_id, true));
+        }
+
+        // enough time for txs processing
+        log::info!("initial waiting {:?}", self.poll_initial_wait);
+        sleep(self.poll_initial_wait).await;
+
+        log::info!("polling to confirm remove subnet validator transaction");
+        let (start, mut success) = (Instant::now(), false);
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed.gt(&self.poll_timeout) {
+                break;
+            }
+
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_p::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
+            let status = resp
+                .result
+                .ok_or_else(|| Error::UnexpectedNone("GetTxStatusResult".to_string()))?
+                .status;
+            if status == platformvm::txs::status::Status::Committed {
+                log::info!("{tx_id} successfully committed");
+                success = true;
+                break;
+            }
+
+            log::warn!(
+                "{} {} (not accepted yet in {}, elapsed {:?})",
+                tx_id,
+                status,
+                picked_http_rpc.1,
+                elapsed
+            );
+            sleep(self.poll_interval).await;
+        }
+        if !success {
+            return Err(Error::API {
+                message: "failed to check acceptance in time".to_string(),
+                retryable: true,
+            });
+        }
+
+        log::info!("polling to confirm subnet validator removal");
+        success = false;
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed.gt(&self.poll_timeout) {
+                break;
+            }
+
+            let is_validator = self
+                .inner
+                .is_subnet_validator(&self.node_id, &self.subnet_id)
+                .await?;
+            if !is_validator {
+                log::info!("node Id {} is no longer a subnet validator", self.node_id);
+                success = true;
+                break;
+            }
+
+            log::warn!(
+                "node Id {} is still a subnet validator (elapsed {:?})",
+                self.node_id,
+                elapsed
+            );
+            sleep(self.poll_interval).await;
+        }
+        if !success {
+            return Err(Error::API {
+                message: "failed to check subnet validator removal in time".to_string(),
+                retryable: true,
+            });
+        }
+
+        Ok((tx_id, true))
+    }
+
+    /// Builds and signs the transaction.
+    async fn signed_tx(&self) -> Result<platformvm::txs::remove_subnet_validator::Tx> {
+        let (subnet_auth, subnet_signers) = self.inner.authorize(self.subnet_id).await?;
+        let intrinsic = platformvm::fees::REMOVE_SUBNET_VALIDATOR_TX_COMPLEXITY
+            .checked_add(&platformvm::fees::auth_complexity(&subnet_auth))?;
+        let (ins, unstaked_outs, _, signers, _) = self
+            .inner
+            .spend_with_fee(0, self.inner.inner.tx_fee, intrinsic)
+            .await?;
+
+        let mut tx = platformvm::txs::remove_subnet_validator::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_p,
+                transferable_outputs: Some(unstaked_outs),
+                transferable_inputs: Some(ins),
+                ..Default::default()
+            },
+            node_id: self.node_id,
+            subnet_id: self.subnet_id,
+            subnet_auth,
+            ..Default::default()
+        };
+        let all_signers = [signers, subnet_signers].concat();
+        tx.sign(all_signers).await?;
+
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self.signed_tx().await?;
+        crate::wallet::builder::DryRun::new(&tx.base_tx, &[], &[])
+    }
+}
@@ -112,102 +112,7 @@ where
             picked_http_rpc.1
         );
 
-        // TODO: paginate next results
-        let utxos = client_x::get_utxos(&picked_http_rpc.1, &self.inner.inner.x_address).await?;
-        let utxos_result = utxos.result.unwrap();
-        let utxos = utxos_result.utxos.unwrap();
-        log::debug!(
-            "fetched UTXOs for inputs: numFetched {:?}, endIndex {:?} and {} UTXOs",
-            utxos_result.num_fetched,
-            utxos_result.end_index,
-            utxos.len()
-        );
-
-        // ref. "avalanchego/vms/avm#Service.SendMultiple"
-        let now_unix = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("unexpected None duration_since")
-            .as_secs();
-
-        let mut import_amount = 0u64;
-        let mut import_inputs: Vec<txs::transferable::Input> = Vec::new();
-        let mut signers: Vec<Vec<T>> = Vec::new();
-
-        for utxo in &utxos {
-            if utxo.asset_id != self.inner.inner.avax_asset_id {
-                continue;
-            }
-
-            if let Some(out) = &utxo.transfer_output {
-                let res = self.inner.inner.keychain.spend(out, now_unix);
-                if res.is_none() {
-                    // cannot spend the output, move onto next
-                    continue;
-                }
-                let (transfer_input, in_signers) = res.unwrap();
-
-                // TODO: check overflow
-                import_amount += transfer_input.amount;
-
-                // add input to the consumed inputs
-                import_inputs.push(txs::transferable::Input {
-                    utxo_id: utxo.utxo_id.clone(),
-                    asset_id: utxo.asset_id,
-                    transfer_input: Some(transfer_input),
-                    ..txs::transferable::Input::default()
-                });
-                signers.push(in_signers);
-            }
-        }
-
-        if import_inputs.is_empty() {
-            return Err(Error::Other {
-                message: "no spendable funds were found".to_string(),
-                retryable: false,
-            });
-        }
-
-        // TODO: check import amount with tx fee
-        log::info!(
-            "importing total {} AVAX with tx fee {}",
-            import_amount,
-            self.inner.inner.tx_fee
-        );
-        import_amount -= self.inner.inner.tx_fee;
-
-        let outputs: Vec<txs::transferable::Output> = vec![
-            // receiver
-            txs::transferable::Output {
-                asset_id: self.inner.inner.avax_asset_id,
-                transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                    amount: import_amount,
-                    output_owners: key::secp256k1::txs::OutputOwners {
-                        locktime: 0,
-                        threshold: 1,
-                        addresses: vec![self.inner.inner.short_address.clone()],
-                    },
-                }),
-                ..Default::default()
-            },
-        ];
-
-        log::debug!(
-            "baseTx has {} inputs and {} outputs",
-            import_inputs.len(),
-            outputs.len()
-        );
-        let mut tx = avm::txs::import::Tx {
-            base_tx: txs::Tx {
-                network_id: self.inner.inner.network_id,
-                blockchain_id: self.inner.inner.blockchain_id_p,
-                transferable_outputs: Some(outputs),
-                ..Default::default()
-            },
-            source_chain_id: self.source_blockchain_id,
-            source_chain_transferable_inputs: Some(import_inputs),
-            ..Default::default()
-        };
-        tx.sign(signers).await?;
+        let tx = self.signed_tx(&picked_http_rpc.1).await?;
 
         if self.dry_mode {
             return Ok(tx.base_tx.metadata.unwrap().id);
@@ -220,11 +125,14 @@ where
             .unwrap()
             .tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_x::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_x::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
         if resp.result.is_none() {
             return Err(Error::API {
-                message: format!("failed to issue import tx {:?} (no result)", resp.error),
+                message: "failed to issue import tx (no result)".to_string(),
                 retryable: false,
             });
         }
@@ -249,7 +157,14 @@ where
                 break;
             }
 
-            let resp = client_x::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_x::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == Status::Accepted {
@@ -276,4 +191,132 @@ where
 
         Ok(tx_id)
     }
+
+    /// Builds and signs the transaction with the UTXOs fetched via "`http_rpc`".
+    async fn signed_tx(&self, http_rpc: &str) -> Result<avm::txs::import::Tx> {
+        let (mut tx, signers) = self.build_tx(http_rpc).await?;
+        tx.sign(signers).await?;
+        Ok(tx)
+    }
+
+    /// Builds the unsigned transaction to sign offline (e.g., on an air-gapped
+    /// machine with "`wallet::offline::UnsignedTx::sign`"), with the UTXOs
+    /// fetched from a picked endpoint. Only the addresses of the wallet keys
+    /// are used, so the keys may be watch-only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built.
+    pub async fn unsigned_tx(&self) -> Result<crate::wallet::offline::UnsignedTx> {
+        let (mut tx, signers) = self
+            .build_tx(&self.inner.inner.pick_base_http_url().1)
+            .await?;
+
+        // signing with no signer only packs the unsigned bytes
+        tx.sign(Vec::<Vec<T>>::new()).await?;
+        crate::wallet::offline::UnsignedTx::new(
+            self.inner.inner.network_id,
+            "X",
+            &tx.base_tx,
+            &signers,
+        )
+    }
+
+    /// Builds the transaction with the UTXOs fetched via "`http_rpc`",
+    /// and returns the signers of each input.
+    async fn build_tx(&self, http_rpc: &str) -> Result<(avm::txs::import::Tx, Vec<Vec<T>>)> {
+        // the exported UTXOs are in the shared memory, not in the X-chain UTXO set
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_x::get_atomic_utxos(
+                http_rpc,
+                &self.inner.inner.x_address,
+                &self.source_blockchain_id.to_string(),
+            ))
+            .await?;
+        let utxos_result = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetUtxosResult".to_string()))?;
+        let utxos = utxos_result
+            .utxos
+            .ok_or_else(|| Error::UnexpectedNone("Utxos from GetUtxosResult".to_string()))?;
+        log::debug!(
+            "fetched UTXOs for inputs: numFetched {:?}, endIndex {:?} and {} UTXOs",
+            utxos_result.num_fetched,
+            utxos_result.end_index,
+            utxos.len()
+        );
+
+        // ref. "avalanchego/vms/avm#Service.SendMultiple"
+        let now_unix = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("unexpected None duration_since")
+            .as_secs();
+
+        let imported = self.inner.inner.tx_builder().import(&utxos, now_unix)?;
+        let (import_inputs, signers) = (imported.inputs, imported.signers);
+        let mut import_amount = imported.amount;
+
+        // TODO: check import amount with tx fee
+        log::info!(
+            "importing total {} AVAX with tx fee {}",
+            import_amount,
+            self.inner.inner.tx_fee
+        );
+        import_amount -= self.inner.inner.tx_fee;
+
+        let outputs: Vec<txs::transferable::Output> = vec![
+            // receiver
+            txs::transferable::Output {
+                asset_id: self.inner.inner.avax_asset_id,
+                transfer_output: Some(key::secp256k1::txs::transfer::Output {
+                    amount: import_amount,
+                    output_owners: key::secp256k1::txs::OutputOwners {
+                        locktime: 0,
+                        threshold: 1,
+                        addresses: vec![self.inner.inner.short_address.clone()],
+                    },
+                }),
+                ..Default::default()
+            },
+        ];
+
+        log::debug!(
+            "baseTx has {} inputs and {} outputs",
+            import_inputs.len(),
+            outputs.len()
+        );
+        let tx = avm::txs::import::Tx {
+            base_tx: txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_x,
+                transferable_outputs: Some(outputs),
+                ..Default::default()
+            },
+            source_chain_id: self.source_blockchain_id,
+            source_chain_transferable_inputs: Some(import_inputs),
+            ..Default::default()
+        };
+        Ok((tx, signers))
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self
+            .signed_tx(&self.inner.inner.pick_base_http_url().1)
+            .await?;
+        crate::wallet::builder::DryRun::new(
+            &tx.base_tx,
+            tx.source_chain_transferable_inputs
+                .as_deref()
+                .unwrap_or_default(),
+            &[],
+        )
+    }
 }
@@ -2,7 +2,14 @@ pub mod export;
 pub mod import;
 pub mod transfer;
 
-use crate::{errors::Result, jsonrpc::client::x as client_x, key, txs, wallet};
+use crate::{
+    avm,
+    errors::{Error, Result},
+    formatting, ids,
+    jsonrpc::client::x as client_x,
+    key, txs, wallet,
+};
+use futures::{stream, StreamExt};
 
 impl<T> wallet::Wallet<T>
 where
@@ -33,32 +40,38 @@ where
     ///
     /// # Errors
     /// 查询失败时返回错误。
-    ///
-    /// # Panics
-    ///
-    /// Panics if the result is None.
     pub async fn balance_with_endpoint(&self, http_rpc: &str) -> Result<u64> {
-        let resp = client_x::get_balance(http_rpc, &self.inner.x_address).await?;
+        let resp = self
+            .inner
+            .scoped(client_x::get_balance(http_rpc, &self.inner.x_address))
+            .await?;
         let cur_balance = resp
             .result
-            .expect("unexpected None GetBalanceResult")
+            .ok_or_else(|| Error::UnexpectedNone("GetBalanceResult".to_string()))?
             .balance;
         Ok(cur_balance)
     }
 
     /// Fetches the current balance of the wallet owner from all endpoints
-    /// in the same order of "`self.http_rpcs`".
-    /// 获取 X 链所有资产余额。
+    /// in the same order of "`self.http_rpcs`", with at most
+    /// "`max_concurrent_requests`" requests in flight.
     ///
     /// # Errors
-    /// 查询失败时返回错误。
+    ///
+    /// Returns the first error if any endpoint fails,
+    /// use "`balances_partial`" to get the balances of the others.
     pub async fn balances(&self) -> Result<Vec<u64>> {
-        let mut balances = Vec::new();
-        for http_rpc in &self.inner.base_http_urls {
-            let balance = self.balance_with_endpoint(http_rpc).await?;
-            balances.push(balance);
-        }
-        Ok(balances)
+        self.balances_partial().await.into_iter().collect()
+    }
+
+    /// Same as "`balances`", but each endpoint returns its own result,
+    /// so one failing endpoint does not fail the whole batch.
+    pub async fn balances_partial(&self) -> Vec<Result<u64>> {
+        stream::iter(self.inner.base_http_urls.iter())
+            .map(|http_rpc| self.balance_with_endpoint(http_rpc))
+            .buffered(self.inner.max_concurrent_requests.max(1))
+            .collect()
+            .await
     }
 
     /// Fetches the current balance of the wallet owner.
@@ -67,7 +80,8 @@ where
     /// # Errors
     /// 查询失败时返回错误。
     pub async fn balance(&self) -> Result<u64> {
-        self.balance_with_endpoint(&self.inner.pick_base_http_url().1)
+        self.inner
+            .with_failover(|http_rpc| async move { self.balance_with_endpoint(&http_rpc).await })
             .await
     }
 
@@ -83,18 +97,49 @@ where
         // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/vms/platformvm/spend.go#L39 "stake"
         // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L355 "AddValidator"
         // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L614 "stake"
-        let resp =
-            client_x::get_utxos(&self.inner.pick_base_http_url().1, &self.inner.p_address).await?;
+        let resp = self
+            .inner
+            .with_failover(|http_rpc| async move {
+                client_x::get_utxos(&http_rpc, &self.inner.p_address).await
+            })
+            .await?;
         let utxos = resp
             .result
-            .ok_or_else(|| crate::errors::Error::UnexpectedNone("GetUtxosResult".to_string()))?
+            .ok_or_else(|| Error::UnexpectedNone("GetUtxosResult".to_string()))?
             .utxos
-            .ok_or_else(|| {
-                crate::errors::Error::UnexpectedNone("Utxos from GetUtxosResult".to_string())
-            })?;
+            .ok_or_else(|| Error::UnexpectedNone("Utxos from GetUtxosResult".to_string()))?;
         Ok(utxos)
     }
 
+    /// Issues the co-signed transaction once all signatures are collected,
+    /// and returns the transaction Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any signature is missing or the issuance fails.
+    pub async fn issue_partially_signed_tx(
+        &self,
+        tx: &avm::txs::partial::PartiallySignedTx,
+    ) -> Result<ids::Id> {
+        let metadata = tx.finalize()?;
+        let hex_tx = formatting::encode_hex_with_checksum(&metadata.tx_bytes_with_signatures);
+
+        let (_, http_rpc) = self.inner.pick_base_http_url();
+        let resp = self
+            .inner
+            .scoped(client_x::issue_tx(&http_rpc, &hex_tx))
+            .await?;
+        let tx_id = resp
+            .result
+            .ok_or_else(|| Error::API {
+                message: "failed to issue tx (no result)".to_string(),
+                retryable: false,
+            })?
+            .tx_id;
+        log::info!("{tx_id} successfully issued");
+        Ok(tx_id)
+    }
+
     /// 构建 X 链转账交易。
     #[must_use]
     pub fn transfer(&self) -> transfer::Tx<T> {
@@ -113,3 +158,50 @@ where
         import::Tx::new(self)
     }
 }
+
+/// `RUST_LOG=debug` cargo test --package avalanche-types --lib --features wallet -- `wallet::x::test_balances_partial` --exact --show-output
+#[tokio::test]
+async fn test_balances_partial() {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    // serves the canned "avm.getBalance" response to every request
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let live = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":{"balance":"1000","utxoIDs":[]}}"#;
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let _ = stream.read(&mut buf).await.unwrap();
+            let resp = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(resp.as_bytes()).await.unwrap();
+        }
+    });
+
+    // nothing listens on the released port
+    let dead = {
+        let l = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", l.local_addr().unwrap())
+    };
+
+    let key = key::secp256k1::TEST_KEYS[0].clone();
+    let w = wallet::Builder::new(&key)
+        .base_http_urls(&[live, dead])
+        .only_evm()
+        .build()
+        .await
+        .unwrap();
+
+    let balances = w.x().balances_partial().await;
+    assert_eq!(balances.len(), 2);
+    assert_eq!(*balances[0].as_ref().unwrap(), 1000);
+    assert!(balances[1].is_err());
+
+    assert!(w.x().balances().await.is_err());
+}
@@ -1,4 +1,4 @@
-use std::{cmp, time::SystemTime};
+use std::time::SystemTime;
 
 use crate::{
     avm,
@@ -20,6 +20,9 @@ where
 
     /// Transfer fund receiver address.
     pub receiver: short::Id,
+    /// Transfer fund receivers with threshold (e.g., multisig with threshold > 1).
+    /// Overrides "receiver" if set.
+    pub receiver_owners: Option<key::secp256k1::txs::OutputOwners>,
 
     /// Transfer amount.
     pub amount: u64,
@@ -47,6 +50,7 @@ where
         Self {
             inner: x.clone(),
             receiver: short::Id::empty(),
+            receiver_owners: None,
             amount: 0,
             check_acceptance: false,
             poll_initial_wait: Duration::from_millis(500),
@@ -63,6 +67,13 @@ where
         self
     }
 
+    /// Sets the transfer fund receivers with threshold, to send to a multisig output.
+    #[must_use]
+    pub fn receiver_owners(mut self, receiver_owners: key::secp256k1::txs::OutputOwners) -> Self {
+        self.receiver_owners = Some(receiver_owners);
+        self
+    }
+
     /// Sets the transfer amount.
     #[must_use]
     pub const fn amount(mut self, amount: u64) -> Self {
@@ -124,119 +135,7 @@ where
             picked_http_rpc.1
         );
 
-        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/wallet/chain/p/builder.go
-        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/vms/platformvm/add_validator_tx.go#L263
-        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/vms/platformvm/spend.go#L39 "stake"
-        // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L355 "AddValidator"
-        // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L614 "stake"
-        // TODO: paginate next results
-        let utxos = client_x::get_utxos(&picked_http_rpc.1, &self.inner.inner.x_address).await?;
-        let utxos_result = utxos.result.unwrap();
-        let utxos = utxos_result.utxos.unwrap();
-        log::debug!(
-            "fetched UTXOs for inputs: numFetched {:?}, endIndex {:?} and {} UTXOs",
-            utxos_result.num_fetched,
-            utxos_result.end_index,
-            utxos.len()
-        );
-
-        let mut inputs: Vec<txs::transferable::Input> = Vec::new();
-        let mut outputs: Vec<txs::transferable::Output> = vec![
-            // receiver
-            txs::transferable::Output {
-                asset_id: self.inner.inner.avax_asset_id,
-                transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                    amount: self.amount,
-                    output_owners: key::secp256k1::txs::OutputOwners {
-                        locktime: 0,
-                        threshold: 1,
-                        addresses: vec![self.receiver.clone()],
-                    },
-                }),
-                ..Default::default()
-            },
-        ];
-
-        // ref. "avalanchego/wallet/chain/x"
-        // "math.Add64(toBurn[assetID], out.Out.Amount())"
-        let mut remaining_amount_to_burn = self.amount + self.inner.inner.tx_fee;
-
-        // ref. "avalanchego/vms/avm#Service.SendMultiple"
-        let now_unix = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("unexpected None duration_since")
-            .as_secs();
-
-        for utxo in &utxos {
-            if utxo.asset_id != self.inner.inner.avax_asset_id {
-                continue;
-            }
-
-            // consumed enough, no need to burn more
-            if remaining_amount_to_burn == 0 {
-                continue;
-            }
-
-            if let Some(out) = &utxo.transfer_output {
-                let (input, _) = self.inner.inner.keychain.spend(out, now_unix).unwrap();
-
-                inputs.push(txs::transferable::Input {
-                    utxo_id: utxo.utxo_id.clone(),
-                    asset_id: utxo.asset_id,
-                    transfer_input: Some(input),
-                    ..Default::default()
-                });
-
-                // burn any value that should be burned
-                let amount_to_burn = cmp::min(
-                    remaining_amount_to_burn, // amount we still need to burn
-                    out.amount,               // amount available to burn
-                );
-                remaining_amount_to_burn -= amount_to_burn;
-
-                let remaining_amount = out.amount - amount_to_burn;
-                if remaining_amount > 0 {
-                    // this input had extra value, so some must be returned
-                    outputs.push(txs::transferable::Output {
-                        asset_id: self.inner.inner.avax_asset_id,
-                        transfer_output: Some(key::secp256k1::txs::transfer::Output {
-                            amount: remaining_amount,
-                            output_owners: key::secp256k1::txs::OutputOwners {
-                                locktime: 0,
-                                threshold: 1,
-                                addresses: vec![self.inner.inner.short_address.clone()],
-                            },
-                        }),
-                        ..Default::default()
-                    });
-                }
-            }
-        }
-        inputs.sort();
-        outputs.sort();
-
-        // make sure it does not incur "tx has 1 credentials but 2 inputs. Should be same" error
-        let mut signers: Vec<Vec<T>> = Vec::new();
-        for _ in 0..inputs.len() {
-            signers.push(vec![self.inner.inner.keychain.keys[0].clone()]);
-        }
-        if inputs.len() > 1 {
-            log::debug!("signing for multiple inputs ({} inputs)", inputs.len());
-        }
-
-        log::debug!(
-            "baseTx has {} inputs and {} outputs",
-            inputs.len(),
-            outputs.len()
-        );
-        let mut tx = avm::txs::Tx::new(txs::Tx {
-            network_id: self.inner.inner.network_id,
-            blockchain_id: self.inner.inner.blockchain_id_x,
-            transferable_outputs: Some(outputs),
-            transferable_inputs: Some(inputs.clone()),
-            ..Default::default()
-        });
-        tx.sign(signers).await?;
+        let tx = self.signed_tx(&picked_http_rpc.1).await?;
 
         if self.dry_mode {
             return Ok(tx.base_tx.metadata.expect("tx.base_tx.metadata is None").id);
@@ -249,11 +148,14 @@ where
             .unwrap()
             .tx_bytes_with_signatures;
         let hex_tx = formatting::encode_hex_with_checksum(&tx_bytes_with_signatures);
-        let resp = client_x::issue_tx(&picked_http_rpc.1, &hex_tx).await?;
-
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_x::issue_tx(&picked_http_rpc.1, &hex_tx))
+            .await?;
         if resp.result.is_none() {
             return Err(Error::API {
-                message: format!("failed to issue tx {:?} (no result)", resp.error),
+                message: "failed to issue tx (no result)".to_string(),
                 retryable: false,
             });
         }
@@ -278,7 +180,14 @@ where
                 break;
             }
 
-            let resp = client_x::get_tx_status(&picked_http_rpc.1, &tx_id.to_string()).await?;
+            let resp = self
+                .inner
+                .inner
+                .scoped(client_x::get_tx_status(
+                    &picked_http_rpc.1,
+                    &tx_id.to_string(),
+                ))
+                .await?;
 
             let status = resp.result.unwrap().status;
             if status == Status::Accepted {
@@ -305,4 +214,175 @@ where
 
         Ok(tx_id)
     }
+
+    /// Builds and signs the transaction with the UTXOs fetched via "`http_rpc`".
+    async fn signed_tx(&self, http_rpc: &str) -> Result<avm::txs::Tx> {
+        let (mut tx, signers) = self.build_tx(http_rpc).await?;
+        tx.sign(signers).await?;
+        Ok(tx)
+    }
+
+    /// Builds the unsigned transaction to sign offline (e.g., on an air-gapped
+    /// machine with "`wallet::offline::UnsignedTx::sign`"), with the UTXOs
+    /// fetched from a picked endpoint. Only the addresses of the wallet keys
+    /// are used, so the keys may be watch-only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built.
+    pub async fn unsigned_tx(&self) -> Result<crate::wallet::offline::UnsignedTx> {
+        let (mut tx, signers) = self
+            .build_tx(&self.inner.inner.pick_base_http_url().1)
+            .await?;
+
+        // signing with no signer only packs the unsigned bytes
+        tx.sign(Vec::<Vec<T>>::new()).await?;
+        crate::wallet::offline::UnsignedTx::new(
+            self.inner.inner.network_id,
+            "X",
+            &tx.base_tx,
+            &signers,
+        )
+    }
+
+    /// Builds the transaction with the UTXOs fetched via "`http_rpc`",
+    /// and returns the signers of each input.
+    async fn build_tx(&self, http_rpc: &str) -> Result<(avm::txs::Tx, Vec<Vec<T>>)> {
+        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/wallet/chain/p/builder.go
+        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/vms/platformvm/add_validator_tx.go#L263
+        // ref. https://github.com/ava-labs/avalanchego/blob/v1.7.9/vms/platformvm/spend.go#L39 "stake"
+        // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L355 "AddValidator"
+        // ref. https://github.com/ava-labs/subnet-cli/blob/6bbe9f4aff353b812822af99c08133af35dbc6bd/client/p.go#L614 "stake"
+        let resp = self
+            .inner
+            .inner
+            .scoped(client_x::get_utxos(http_rpc, &self.inner.inner.x_address))
+            .await?;
+        let utxos_result = resp
+            .result
+            .ok_or_else(|| Error::UnexpectedNone("GetUtxosResult".to_string()))?;
+        let utxos = utxos_result
+            .utxos
+            .ok_or_else(|| Error::UnexpectedNone("Utxos from GetUtxosResult".to_string()))?;
+        log::debug!(
+            "fetched UTXOs for inputs: numFetched {:?}, endIndex {:?} and {} UTXOs",
+            utxos_result.num_fetched,
+            utxos_result.end_index,
+            utxos.len()
+        );
+
+        // ref. "avalanchego/wallet/chain/x"
+        // "math.Add64(toBurn[assetID], out.Out.Amount())"
+        let amount_to_burn = self.amount + self.inner.inner.tx_fee;
+
+        // ref. "avalanchego/vms/avm#Service.SendMultiple"
+        let now_unix = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("unexpected None duration_since")
+            .as_secs();
+
+        let tx_builder = self.inner.inner.tx_builder();
+        let spent = tx_builder.spend(&utxos, amount_to_burn, now_unix)?;
+        let (inputs, signers) = (spent.inputs, spent.signers);
+
+        let mut outputs = spent.change_outputs;
+        outputs.push(self.receiver_output(&tx_builder));
+        outputs.sort();
+
+        if inputs.len() > 1 {
+            log::debug!("signing for multiple inputs ({} inputs)", inputs.len());
+        }
+
+        log::debug!(
+            "baseTx has {} inputs and {} outputs",
+            inputs.len(),
+            outputs.len()
+        );
+        let tx = avm::txs::Tx::new(txs::Tx {
+            network_id: self.inner.inner.network_id,
+            blockchain_id: self.inner.inner.blockchain_id_x,
+            transferable_outputs: Some(outputs),
+            transferable_inputs: Some(inputs),
+            ..Default::default()
+        });
+        Ok((tx, signers))
+    }
+
+    fn receiver_output(
+        &self,
+        tx_builder: &crate::wallet::builder::TxBuilder<'_, T>,
+    ) -> txs::transferable::Output {
+        self.receiver_owners.as_ref().map_or_else(
+            || tx_builder.output(self.amount, self.receiver.clone()),
+            |owners| tx_builder.output_with_owners(self.amount, owners.clone()),
+        )
+    }
+
+    /// Builds the transaction that may spend the multisig UTXOs, and signs
+    /// it with the wallet keys. The co-signers complete the signatures
+    /// (e.g., after exporting it with "`PartiallySignedTx::to_json`")
+    /// before issuing it with "`X::issue_partially_signed_tx`".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the UTXOs cannot be fetched or do not cover the amount.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system time is before the UNIX epoch.
+    pub async fn partially_signed_tx(&self) -> Result<avm::txs::partial::PartiallySignedTx> {
+        let resp = self
+            .inner
+            .inner
+            .with_failover(|http_rpc| async move {
+                client_x::get_utxos(&http_rpc, &self.inner.inner.x_address).await
+            })
+            .await?;
+        let utxos = resp
+            .result
+            .and_then(|r| r.utxos)
+            .ok_or_else(|| Error::UnexpectedNone("Utxos from GetUtxosResult".to_string()))?;
+
+        let now_unix = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("unexpected None duration_since")
+            .as_secs();
+
+        let tx_builder = self.inner.inner.tx_builder();
+        let spent = tx_builder.spend_with_cosigners(
+            &utxos,
+            self.amount + self.inner.inner.tx_fee,
+            now_unix,
+        )?;
+
+        let mut outputs = spent.change_outputs;
+        outputs.push(self.receiver_output(&tx_builder));
+        outputs.sort();
+
+        let mut tx = avm::txs::partial::PartiallySignedTx::new(
+            txs::Tx {
+                network_id: self.inner.inner.network_id,
+                blockchain_id: self.inner.inner.blockchain_id_x,
+                transferable_outputs: Some(outputs),
+                transferable_inputs: Some(spent.inputs),
+                ..Default::default()
+            },
+            spent.signers,
+        )?;
+        tx.sign(&self.inner.inner.keychain.keys).await?;
+        Ok(tx)
+    }
+
+    /// Builds and signs the transaction without issuing it, and returns the
+    /// signed bytes, transaction Id, and the breakdown of inputs and outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be built or signed.
+    pub async fn dry_run(&self) -> Result<crate::wallet::builder::DryRun> {
+        let tx = self
+            .signed_tx(&self.inner.inner.pick_base_http_url().1)
+            .await?;
+        crate::wallet::builder::DryRun::new(&tx.base_tx, &[], &[])
+    }
 }
@@ -0,0 +1,337 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+};
+
+use crate::{common, report, spec::Spec};
+use avalanche_types::{
+    jsonrpc::client::evm as client_evm,
+    key::{self, secp256k1::kms::aws::eth_signer::Signer as KmsAwsSigner},
+    wallet,
+};
+use aws_manager::kms;
+use ethers::{
+    abi::{self, Token},
+    providers::Middleware,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, TransactionReceipt, TransactionRequest,
+        H256, U256, U64,
+    },
+    utils::{hex, keccak256},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+pub const NAME: &str = "C_CONTRACT_TRANSFERS";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// Number of the token transfers after the contract deployment.
+    pub transfers: usize,
+    pub check_acceptance: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            transfers: DEFAULT_TRANSFERS,
+            check_acceptance: true,
+        }
+    }
+}
+
+const DEFAULT_TRANSFERS: usize = 5;
+
+/// Creation bytecode of a minimal ERC20 token, hand-assembled to keep the
+/// test free of the solc toolchain. Implements "balanceOf(address)" and
+/// "transfer(address,uint256)" (reverts on insufficient balance), and mints
+/// the whole supply to the deployer, emitting the ERC20 "Transfer" events.
+const ERC20_BYTECODE: &str = "69d3c21bcecceda1000000335569d3c21bcecceda10000006000523360007fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60206000a361009e806100516000396000f3600436106100245760003560e01c8063a9059cbb1461003757806370a0823114610029575b600080fd5b506004355460005260206000f35b50602435335481811061002457819003335560043573ffffffffffffffffffffffffffffffffffffffff1680548201815581600052337fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60206000a3600160005260206000f3";
+
+/// Token supply minted to the deployer (1,000,000 tokens with 18 decimals).
+const ERC20_TOTAL_SUPPLY: &str = "d3c21bcecceda1000000";
+
+/// ref. "`transfer(address,uint256)`"
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// ref. "`balanceOf(address)`"
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Deploys an ERC20 contract on the C-chain, sends token transfers,
+/// and validates the receipts, the "Transfer" event logs, and the balances.
+/// Unlike the simple transfers, the transactions carry calldata
+/// so the gas is estimated by the node rather than fixed to 21,000.
+pub async fn run(spec: Arc<RwLock<Spec>>) -> io::Result<()> {
+    let spec_rlocked = spec.read().await;
+
+    let (transfers, check_acceptance) = if let Some(cfg) = &spec_rlocked.c_contract_transfers {
+        (cfg.transfers, cfg.check_acceptance)
+    } else {
+        (DEFAULT_TRANSFERS, false)
+    };
+
+    let network_id = spec_rlocked.status.clone().unwrap().network_id;
+    let http_rpc_eps = spec_rlocked.rpc_endpoints.clone();
+    let key_infos = spec_rlocked.key_infos.clone();
+    if key_infos.len() < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("only {} keys (requires >=2 keys)", key_infos.len()),
+        ));
+    }
+
+    let chain_id = client_evm::chain_id(format!("{}/ext/bc/C/rpc", http_rpc_eps[0]).as_str())
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to get chainId for C-chain '{e}'"),
+            )
+        })?;
+
+    log::info!(
+        "{}: network Id {}, chain Id {}, rpc endpoints {:?}",
+        NAME,
+        network_id,
+        chain_id,
+        http_rpc_eps
+    );
+
+    let loaded_keys_with_balance =
+        common::load_keys_with_balance(key_infos, false, network_id, http_rpc_eps[0].as_str())
+            .await?;
+    let from_idx = loaded_keys_with_balance
+        .c_balances
+        .iter()
+        .position(|b| !b.is_zero())
+        .ok_or_else(|| Error::new(ErrorKind::Other, "no key found with balance to deploy"))?;
+    let to_idx = (from_idx + 1) % loaded_keys_with_balance.key_infos.len();
+
+    let from_key_info = &loaded_keys_with_balance.key_infos[from_idx];
+    let to_h160_addr = loaded_keys_with_balance.key_infos[to_idx].h160_address;
+    let chain_rpc_url = format!("{}/ext/bc/C/rpc", http_rpc_eps[0]);
+
+    if from_key_info.key_type == key::secp256k1::KeyType::AwsKms {
+        let shared_config = aws_manager::load_config(None, None, None).await;
+        let kms_manager = kms::Manager::new(&shared_config);
+
+        let key = avalanche_types::key::secp256k1::kms::aws::Key::from_arn(
+            kms_manager.clone(),
+            &from_key_info.id.clone().unwrap(),
+        )
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to load KMS key '{e}'")))?;
+
+        let w = wallet::Builder::new(&key)
+            .base_http_url(http_rpc_eps[0].clone())
+            .endpoint_observer(common::endpoint_observer())
+            .build()
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build wallet '{e}'")))?;
+        let signer = KmsAwsSigner::new(key, chain_id)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to create signer '{e}'")))?;
+        let evm_wallet = w
+            .evm(&signer, chain_rpc_url.as_str(), chain_id)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to create evm '{e}'")))?;
+
+        deploy_and_transfer(&evm_wallet, to_h160_addr, transfers, check_acceptance).await
+    } else {
+        let pk = from_key_info.to_private_key();
+        let w = wallet::Builder::new(&pk)
+            .base_http_url(http_rpc_eps[0].clone())
+            .endpoint_observer(common::endpoint_observer())
+            .build()
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build wallet '{e}'")))?;
+
+        let signer: ethers_signers::LocalWallet = pk.to_ethers_core_signing_key().into();
+        let evm_wallet = w
+            .evm(&signer, chain_rpc_url.as_str(), chain_id)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to create evm '{e}'")))?;
+
+        deploy_and_transfer(&evm_wallet, to_h160_addr, transfers, check_acceptance).await
+    }
+}
+
+async fn deploy_and_transfer<T, S>(
+    evm_wallet: &wallet::evm::Evm<T, S>,
+    to: Address,
+    transfers: usize,
+    check_acceptance: bool,
+) -> io::Result<()>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+    S: ethers_signers::Signer + Clone,
+    S::Error: 'static,
+{
+    let from = evm_wallet.inner.h160_address;
+
+    println!("\n\n\n---\ndeploying ERC20 contract from {from:?}");
+    let bytecode = hex::decode(ERC20_BYTECODE)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("invalid bytecode '{e}'")))?;
+    let deploy_tx_id = evm_wallet
+        .eip1559()
+        .data(bytecode)
+        .urgent()
+        .check_acceptance(check_acceptance)
+        .check_receipt(true)
+        .submit()
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to deploy contract '{e}'")))?;
+
+    let receipt = fetch_receipt(evm_wallet, deploy_tx_id).await?;
+    let contract_addr = receipt.contract_address.ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("no contract address in receipt of 0x{deploy_tx_id:x}"),
+        )
+    })?;
+    let total_supply = U256::from_str_radix(ERC20_TOTAL_SUPPLY, 16).unwrap();
+    check_transfer_log(&receipt, contract_addr, Address::zero(), from, total_supply)?;
+    log::info!("deployed ERC20 contract {contract_addr:?} with tx 0x{deploy_tx_id:x}");
+    report::record_tx_id(format!("0x{deploy_tx_id:x}"));
+
+    let to_before = balance_of(evm_wallet, contract_addr, to).await?;
+    let mut sent = U256::zero();
+    for i in 0..transfers {
+        let amount = U256::from(1_000 * (i as u64 + 1));
+        println!("\n\n\n---\n[TRANSFER #{i:02}] sending {amount} tokens to {to:?}");
+
+        let mut calldata = TRANSFER_SELECTOR.to_vec();
+        calldata.extend(abi::encode(&[Token::Address(to), Token::Uint(amount)]));
+
+        let tx_id = evm_wallet
+            .eip1559()
+            .recipient(contract_addr)
+            .data(calldata)
+            .urgent()
+            .check_acceptance(check_acceptance)
+            .check_receipt(true)
+            .submit()
+            .await
+            .map_err(|e| {
+                Error::new(ErrorKind::Other, format!("failed to transfer tokens '{e}'"))
+            })?;
+
+        let receipt = fetch_receipt(evm_wallet, tx_id).await?;
+        check_transfer_log(&receipt, contract_addr, from, to, amount)?;
+        sent += amount;
+        log::info!("transferred {amount} tokens with tx 0x{tx_id:x}");
+        report::record_tx_id(format!("0x{tx_id:x}"));
+    }
+
+    let from_after = balance_of(evm_wallet, contract_addr, from).await?;
+    let to_after = balance_of(evm_wallet, contract_addr, to).await?;
+    if from_after != total_supply - sent || to_after != to_before + sent {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "unexpected token balances after sending {sent} (from {from_after}, to {to_before} -> {to_after})"
+            ),
+        ));
+    }
+
+    log::info!("DONE TRANSFERS {transfers} (sent {sent} tokens via contract {contract_addr:?})");
+    Ok(())
+}
+
+/// Fetches the receipt via "`eth_getTransactionReceipt`",
+/// and makes sure the transaction succeeded.
+async fn fetch_receipt<T, S>(
+    evm_wallet: &wallet::evm::Evm<T, S>,
+    tx_id: H256,
+) -> io::Result<TransactionReceipt>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+    S: ethers_signers::Signer + Clone,
+    S::Error: 'static,
+{
+    let receipt = evm_wallet
+        .provider
+        .get_transaction_receipt(tx_id)
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed eth_getTransactionReceipt '{e}'"),
+            )
+        })?
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("no receipt for 0x{tx_id:x}")))?;
+
+    if receipt.status != Some(U64::from(1)) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("tx 0x{tx_id:x} failed with status {:?}", receipt.status),
+        ));
+    }
+    Ok(receipt)
+}
+
+/// Makes sure the receipt has exactly one ERC20 "Transfer" event
+/// emitted by the contract, with the expected parties and amount.
+fn check_transfer_log(
+    receipt: &TransactionReceipt,
+    contract_addr: Address,
+    from: Address,
+    to: Address,
+    amount: U256,
+) -> io::Result<()> {
+    let transfer_topic = H256::from(keccak256("Transfer(address,address,uint256)"));
+    let expected_topics = vec![transfer_topic, H256::from(from), H256::from(to)];
+
+    if receipt.logs.len() != 1 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "expected 1 log in receipt of 0x{:x}, got {}",
+                receipt.transaction_hash,
+                receipt.logs.len()
+            ),
+        ));
+    }
+    let log = &receipt.logs[0];
+    if log.address != contract_addr
+        || log.topics != expected_topics
+        || U256::from_big_endian(&log.data) != amount
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "unexpected Transfer log in receipt of 0x{:x} ({log:?})",
+                receipt.transaction_hash
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Calls "balanceOf" on the contract via "`eth_call`".
+async fn balance_of<T, S>(
+    evm_wallet: &wallet::evm::Evm<T, S>,
+    contract_addr: Address,
+    owner: Address,
+) -> io::Result<U256>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+    S: ethers_signers::Signer + Clone,
+    S::Error: 'static,
+{
+    let mut calldata = BALANCE_OF_SELECTOR.to_vec();
+    calldata.extend(abi::encode(&[Token::Address(owner)]));
+
+    let tx: TypedTransaction = TransactionRequest::new()
+        .to(contract_addr)
+        .data(calldata)
+        .into();
+    let output =
+        evm_wallet.provider.call(&tx, None).await.map_err(|e| {
+            Error::new(ErrorKind::Other, format!("failed eth_call balanceOf '{e}'"))
+        })?;
+    if output.len() != 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unexpected balanceOf output length {}", output.len()),
+        ));
+    }
+    Ok(U256::from_big_endian(&output))
+}
@@ -1 +1,2 @@
+pub mod contract_transfers;
 pub mod simple_transfers;
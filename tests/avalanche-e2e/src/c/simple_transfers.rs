@@ -4,7 +4,7 @@ use std::{
     sync::Arc,
 };
 
-use crate::{common, spec::Spec};
+use crate::{chaos, common, report, spec::Spec};
 use avalanche_types::{
     jsonrpc::client::evm as client_evm,
     key::{self, secp256k1::kms::aws::eth_signer::Signer as KmsAwsSigner},
@@ -84,19 +84,29 @@ pub async fn run(spec: Arc<RwLock<Spec>>) -> io::Result<()> {
             "\n\n\n---\n[ROUND #{:02}] making C-chain transfer (to permute {to_permute})",
             i
         );
-        match make_single_transfer(
+
+        // the wallet may pick the disrupted node, but the
+        // balances and the EVM calls go to the live ones
+        let disruption = chaos::disrupt(&spec_rlocked, i).await?;
+        let live_eps = chaos::live_endpoints(&disruption, &http_rpc_eps);
+        let ep_idx = i % live_eps.len();
+        let res = make_single_transfer(
             network_id,
             chain_id,
-            i % http_rpc_eps.len(),
-            http_rpc_eps.clone(),
+            ep_idx,
+            live_eps.clone(),
+            &chaos::wallet_endpoints(&spec_rlocked, &http_rpc_eps, &live_eps[ep_idx]),
             spec_rlocked.key_infos.clone(),
             to_permute,
             gas_price,
             gas_limit,
             check_acceptance,
         )
-        .await
-        {
+        .await;
+        if let Some(d) = disruption {
+            d.recover().await?;
+        }
+        match res {
             Ok(_) => {
                 success += 1;
             }
@@ -127,6 +137,7 @@ async fn make_single_transfer(
     chain_id: primitive_types::U256,
     ep_idx: usize,
     http_rpc_eps: Vec<String>,
+    wallet_http_rpcs: &[String],
     key_infos: Vec<key::secp256k1::Info>,
     permute_keys: bool,
     _gas_price: Option<u64>,
@@ -209,7 +220,8 @@ async fn make_single_transfer(
         .unwrap();
 
         let w = wallet::Builder::new(&key)
-            .base_http_url(http_rpc_eps[ep_idx].clone())
+            .base_http_urls(wallet_http_rpcs)
+            .endpoint_observer(common::endpoint_observer())
             .build()
             .await
             .unwrap();
@@ -245,10 +257,12 @@ async fn make_single_transfer(
             "evm ethers wallet SUCCESS with transaction id 0x{:x}",
             txid2
         );
+        report::record_tx_id(format!("0x{txid2:x}"));
     } else {
         let w =
             wallet::Builder::new(&loaded_keys_with_balance.key_infos[from_idx].to_private_key())
-                .base_http_url(http_rpc_eps[ep_idx].clone())
+                .base_http_urls(wallet_http_rpcs)
+                .endpoint_observer(common::endpoint_observer())
                 .build()
                 .await
                 .unwrap();
@@ -285,6 +299,7 @@ async fn make_single_transfer(
         }
 
         log::info!("evm ethers wallet SUCCESS with transaction id {}", txid2);
+        report::record_tx_id(format!("0x{txid2:x}"));
     };
 
     Ok(())
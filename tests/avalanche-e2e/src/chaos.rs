@@ -0,0 +1,310 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    time::Duration,
+};
+
+use crate::spec::Spec;
+use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Instant};
+
+/// Chaos action to disrupt a node between the scenario rounds.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Restarts the node process.
+    RestartNode,
+    /// Stops the node (e.g., a subnet validator) for "`downtime_seconds`"
+    /// and then starts it back.
+    PauseNode,
+}
+
+/// Chaos configuration, only supported with the network-runner RPC server.
+/// The round-based scenarios disrupt a node every "`every_rounds`" rounds,
+/// run the round against every endpoint (so the wallet has to fail over),
+/// and wait for the network to recover before the next round.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// Disrupts a node before every N-th round (never before the first one).
+    pub every_rounds: usize,
+    /// Actions to take in turn.
+    pub actions: Vec<Action>,
+    /// Names of the network-runner nodes to disrupt.
+    /// If empty, picks a random node in the cluster.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub node_names: Vec<String>,
+    /// Seconds the paused node stays down.
+    pub downtime_seconds: u64,
+    /// Maximum seconds to wait for the network to become healthy after an action.
+    pub recovery_timeout_seconds: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            every_rounds: DEFAULT_EVERY_ROUNDS,
+            actions: vec![Action::RestartNode, Action::PauseNode],
+            node_names: Vec::new(),
+            downtime_seconds: DEFAULT_DOWNTIME_SECONDS,
+            recovery_timeout_seconds: DEFAULT_RECOVERY_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+const DEFAULT_EVERY_ROUNDS: usize = 3;
+const DEFAULT_DOWNTIME_SECONDS: u64 = 10;
+const DEFAULT_RECOVERY_TIMEOUT_SECONDS: u64 = 300;
+
+impl Config {
+    pub fn validate(&self) -> io::Result<()> {
+        if self.every_rounds == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "zero chaos every_rounds",
+            ));
+        }
+        if self.actions.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "empty chaos actions"));
+        }
+        Ok(())
+    }
+
+    /// Returns the action to take before the round, if any.
+    pub fn action_for_round(&self, round: usize) -> Option<Action> {
+        if self.every_rounds == 0 || self.actions.is_empty() {
+            return None;
+        }
+        if round == 0 || round % self.every_rounds != 0 {
+            return None;
+        }
+        Some(self.actions[(round / self.every_rounds - 1) % self.actions.len()])
+    }
+}
+
+/// Represents an in-flight node disruption.
+#[derive(Debug, Clone)]
+pub struct Disruption {
+    network_runner_endpoint: String,
+    action: Action,
+    node_name: String,
+    /// RPC endpoint of the disrupted node.
+    pub node_uri: String,
+    started: Instant,
+    downtime: Duration,
+    recovery_timeout: Duration,
+}
+
+/// Disrupts a node before the round if the spec has chaos enabled
+/// and the round is due, returning the disruption to recover after the round.
+pub async fn disrupt(spec: &Spec, round: usize) -> io::Result<Option<Disruption>> {
+    let Some(cfg) = &spec.chaos else {
+        return Ok(None);
+    };
+    let Some(action) = cfg.action_for_round(round) else {
+        return Ok(None);
+    };
+    let Some(ep) = spec
+        .status
+        .as_ref()
+        .and_then(|s| s.network_runner_endpoint.clone())
+    else {
+        log::warn!("no network-runner endpoint -- skipping chaos action {action:?}");
+        return Ok(None);
+    };
+
+    let cli = avalanche_network_runner_sdk::Client::new(&ep).await;
+    let status = cli.status().await?;
+    let node_infos = status
+        .cluster_info
+        .ok_or_else(|| Error::new(ErrorKind::Other, "no cluster info from network-runner"))?
+        .node_infos;
+
+    let candidates: Vec<&String> = if cfg.node_names.is_empty() {
+        node_infos.keys().collect()
+    } else {
+        cfg.node_names.iter().collect()
+    };
+    let node_name = candidates
+        .choose(&mut thread_rng())
+        .map(|s| s.to_string())
+        .ok_or_else(|| Error::new(ErrorKind::Other, "no node to disrupt"))?;
+    let node_uri = node_infos
+        .get(&node_name)
+        .map(|info| info.uri.clone())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("node '{node_name}' not found in the cluster"),
+            )
+        })?;
+
+    println!("\n\n\n---\n[CHAOS] {action:?} '{node_name}' ({node_uri}) before round #{round:02}");
+    match action {
+        Action::RestartNode => {
+            cli.restart_node(avalanche_network_runner_sdk::RestartNodeRequest {
+                name: node_name.clone(),
+                ..Default::default()
+            })
+            .await?;
+        }
+        Action::PauseNode => {
+            cli.pause_node(avalanche_network_runner_sdk::PauseNodeRequest {
+                name: node_name.clone(),
+            })
+            .await?;
+        }
+    }
+
+    Ok(Some(Disruption {
+        network_runner_endpoint: ep,
+        action,
+        node_name,
+        node_uri,
+        started: Instant::now(),
+        downtime: Duration::from_secs(cfg.downtime_seconds),
+        recovery_timeout: Duration::from_secs(cfg.recovery_timeout_seconds),
+    }))
+}
+
+impl Disruption {
+    /// Brings the node back (if paused) and waits for the network to become healthy.
+    pub async fn recover(self) -> io::Result<()> {
+        let cli = avalanche_network_runner_sdk::Client::new(&self.network_runner_endpoint).await;
+
+        if self.action == Action::PauseNode {
+            let elapsed = self.started.elapsed();
+            if elapsed < self.downtime {
+                sleep(self.downtime - elapsed).await;
+            }
+            log::info!("[CHAOS] resuming '{}'", self.node_name);
+            cli.resume_node(avalanche_network_runner_sdk::ResumeNodeRequest {
+                name: self.node_name.clone(),
+            })
+            .await?;
+        }
+
+        let start = Instant::now();
+        loop {
+            match cli.health().await {
+                Ok(_) => {
+                    log::info!(
+                        "[CHAOS] network healthy after {:?} on '{}' (took {:?})",
+                        self.action,
+                        self.node_name,
+                        start.elapsed()
+                    );
+                    return Ok(());
+                }
+                Err(e) => {
+                    if start.elapsed() > self.recovery_timeout {
+                        return Err(Error::new(
+                            ErrorKind::TimedOut,
+                            format!(
+                                "network not healthy {:?} after {:?} on '{}' ({e})",
+                                self.recovery_timeout, self.action, self.node_name
+                            ),
+                        ));
+                    }
+                    log::warn!("[CHAOS] not healthy yet {e}");
+                    sleep(Duration::from_secs(5)).await;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the endpoints that are not disrupted,
+/// to pick the round endpoint and to check the acceptance.
+pub fn live_endpoints(disruption: &Option<Disruption>, http_rpc_eps: &[String]) -> Vec<String> {
+    let live: Vec<String> = http_rpc_eps
+        .iter()
+        .filter(|ep| disruption.as_ref().map_or(true, |d| &d.node_uri != *ep))
+        .cloned()
+        .collect();
+    if live.is_empty() {
+        http_rpc_eps.to_vec()
+    } else {
+        live
+    }
+}
+
+/// Returns the wallet endpoints with the preferred one first.
+/// With chaos enabled, includes every endpoint so the wallet
+/// fails over from the disrupted node.
+pub fn wallet_endpoints(spec: &Spec, http_rpc_eps: &[String], preferred: &str) -> Vec<String> {
+    let mut eps = vec![preferred.to_string()];
+    if spec.chaos.is_some() {
+        eps.extend(http_rpc_eps.iter().filter(|ep| *ep != preferred).cloned());
+    }
+    eps
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-e2e -- chaos::test_config --exact \
+/// --show-output
+#[test]
+fn test_config() {
+    let cfg = Config::default();
+    assert!(cfg.validate().is_ok());
+
+    let actions: Vec<Option<Action>> = (0..10).map(|r| cfg.action_for_round(r)).collect();
+    assert_eq!(
+        actions,
+        vec![
+            None,
+            None,
+            None,
+            Some(Action::RestartNode),
+            None,
+            None,
+            Some(Action::PauseNode),
+            None,
+            None,
+            Some(Action::RestartNode),
+        ]
+    );
+
+    let d = "
+every_rounds: 2
+actions:
+- pause_node
+node_names:
+- node1
+downtime_seconds: 5
+recovery_timeout_seconds: 60
+";
+    let parsed: Config = serde_yaml::from_str(d).unwrap();
+    assert_eq!(
+        parsed,
+        Config {
+            every_rounds: 2,
+            actions: vec![Action::PauseNode],
+            node_names: vec!["node1".to_string()],
+            downtime_seconds: 5,
+            recovery_timeout_seconds: 60,
+        }
+    );
+
+    assert!(Config {
+        every_rounds: 0,
+        ..Default::default()
+    }
+    .validate()
+    .is_err());
+    assert!(Config {
+        actions: Vec::new(),
+        ..Default::default()
+    }
+    .validate()
+    .is_err());
+
+    let eps: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let mut spec = Spec::default();
+    assert_eq!(wallet_endpoints(&spec, &eps, "b"), vec!["b".to_string()]);
+    spec.chaos = Some(Config::default());
+    assert_eq!(
+        wallet_endpoints(&spec, &eps, "b"),
+        vec!["b".to_string(), "a".to_string(), "c".to_string()]
+    );
+    assert_eq!(live_endpoints(&None, &eps), eps);
+}
@@ -1,11 +1,11 @@
 use std::{
     io::{self, stdout, Error, ErrorKind},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use crate::{
-    c, flags, logs,
+    c, flags, info, logs, p, report, scheduler,
     spec::{self, Spec, Status},
     x,
 };
@@ -104,6 +104,24 @@ pub async fn execute(opts: flags::Options) -> io::Result<()> {
         _ => unreachable!("unknown rpc_endpoint_kind"),
     };
 
+    if !spec.rpc_endpoint_auths.is_empty() {
+        // all requests below go via the shared client
+        spec.rpc_endpoint_auths
+            .iter()
+            .fold(
+                avalanche_types::jsonrpc::client::http::Http::builder(),
+                |builder, (endpoint, auth)| builder.auth(endpoint, auth.clone()),
+            )
+            .build()
+            .and_then(avalanche_types::jsonrpc::client::http::set_shared)
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("failed to set endpoint auths ({e})"),
+                )
+            })?;
+    }
+
     let orig_rpc_endpoints = spec.rpc_endpoints.clone();
 
     spec.rpc_endpoints = rpc_eps.clone();
@@ -149,87 +167,39 @@ pub async fn execute(opts: flags::Options) -> io::Result<()> {
             spec.status.clone().unwrap().randomized_scenarios.clone()
         }
     };
-    let parallelize = spec.parallelize;
-    let spec_arc = Arc::new(RwLock::new(spec.clone()));
-
-    if !parallelize {
-        for (i, s) in scenerios.iter().enumerate() {
-            execute!(
-                stdout(),
-                SetForegroundColor(Color::Cyan),
-                Print(format!("\n\n[{:03}] scenerio '{}':\n", i, s.as_str())),
-                ResetColor
-            )?;
-            match s.as_str() {
-                x::simple_transfers::NAME => {
-                    x::simple_transfers::run(spec_arc.clone()).await?;
-                }
-                x::exports::NAME => {
-                    x::exports::run(spec_arc.clone()).await?;
-                }
-                x::byzantine::conflicting_transfers::NAME => {
-                    x::byzantine::conflicting_transfers::run(spec_arc.clone()).await?;
-                }
-                x::byzantine::conflicting_parallel_transfers::NAME => {
-                    x::byzantine::conflicting_parallel_transfers::run(spec_arc.clone()).await?;
-                }
-                c::simple_transfers::NAME => {
-                    c::simple_transfers::run(spec_arc.clone()).await?;
-                }
-                _ => {
-                    return Err(Error::new(
-                        ErrorKind::Other,
-                        format!("unknown scenario '{}'", s),
-                    ))
-                }
-            }
-        }
+    let dag = scheduler::Dag::new(&scenerios, &spec.scenario_constraints)?;
+    let max_concurrency = if spec.parallelize {
+        spec.max_concurrency.unwrap_or(0)
     } else {
-        let mut handles = vec![];
-        for (i, s) in scenerios.iter().enumerate() {
-            execute!(
-                stdout(),
-                SetForegroundColor(Color::Cyan),
-                Print(format!("\n\n[{:03}] scenerio '{}':\n", i, s.as_str())),
-                ResetColor
-            )?;
-            match s.as_str() {
-                x::simple_transfers::NAME => {
-                    handles.push(tokio::spawn(x::simple_transfers::run(spec_arc.clone())));
-                }
-                x::byzantine::conflicting_transfers::NAME => {
-                    handles.push(tokio::spawn(x::byzantine::conflicting_transfers::run(
-                        spec_arc.clone(),
-                    )));
-                }
-                x::byzantine::conflicting_parallel_transfers::NAME => {
-                    handles.push(tokio::spawn(
-                        x::byzantine::conflicting_parallel_transfers::run(spec_arc.clone()),
-                    ));
-                }
-                c::simple_transfers::NAME => {
-                    handles.push(tokio::spawn(c::simple_transfers::run(spec_arc.clone())));
-                }
-                _ => {
-                    return Err(Error::new(
-                        ErrorKind::Other,
-                        format!("unknown scenario '{}'", s),
-                    ))
-                }
+        1
+    };
+    let spec_arc = Arc::new(RwLock::new(spec.clone()));
+    let results = Arc::new(Mutex::new(report::Report {
+        network_id: Some(network_id),
+        ..Default::default()
+    }));
+
+    let res = dag
+        .execute(max_concurrency, |s| {
+            let (spec_arc, results) = (spec_arc.clone(), results.clone());
+            async move {
+                let start = Instant::now();
+                let (res, tx_ids) = report::collect_tx_ids(run_scenario(s.clone(), spec_arc)).await;
+                results
+                    .lock()
+                    .unwrap()
+                    .record(&s, start.elapsed(), tx_ids, &res);
+                res
             }
-        }
+        })
+        .await;
 
-        log::info!("blocking on handles via JoinHandle");
-        for handle in handles {
-            let r = handle.await.map_err(|e| {
-                Error::new(ErrorKind::Other, format!("failed await on JoinHandle {e}"))
-            })?;
-            match r {
-                Ok(_) => {}
-                Err(e) => return Err(Error::new(ErrorKind::Other, format!("case failed {e}"))),
-            }
-        }
+    if let Some(report_path) = &opts.report_path {
+        let mut report = results.lock().unwrap().clone();
+        report.skip_missing(&scenerios);
+        report.write(report_path)?;
     }
+    res?;
 
     if is_anr {
         stop_anr_network(&anr_ep).await?;
@@ -250,6 +220,34 @@ pub async fn execute(opts: flags::Options) -> io::Result<()> {
     Ok(())
 }
 
+/// Runs the scenario by its Id.
+async fn run_scenario(s: String, spec_arc: Arc<RwLock<Spec>>) -> io::Result<()> {
+    execute!(
+        stdout(),
+        SetForegroundColor(Color::Cyan),
+        Print(format!("\n\nscenerio '{}':\n", s.as_str())),
+        ResetColor
+    )?;
+    match s.as_str() {
+        info::smoke::NAME => info::smoke::run(spec_arc).await,
+        x::simple_transfers::NAME => x::simple_transfers::run(spec_arc).await,
+        x::exports::NAME => x::exports::run(spec_arc).await,
+        x::byzantine::conflicting_transfers::NAME => {
+            x::byzantine::conflicting_transfers::run(spec_arc).await
+        }
+        x::byzantine::conflicting_parallel_transfers::NAME => {
+            x::byzantine::conflicting_parallel_transfers::run(spec_arc).await
+        }
+        c::simple_transfers::NAME => c::simple_transfers::run(spec_arc).await,
+        c::contract_transfers::NAME => c::contract_transfers::run(spec_arc).await,
+        p::subnet_validators::NAME => p::subnet_validators::run(spec_arc).await,
+        _ => Err(Error::new(
+            ErrorKind::Other,
+            format!("unknown scenario '{}'", s),
+        )),
+    }
+}
+
 /// Launches a local network via avalanche-network-runner RPC server.
 async fn launch_anr_network(
     ep: &str,
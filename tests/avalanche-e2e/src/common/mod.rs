@@ -1,106 +1,174 @@
-use std::{ops::Div, str::FromStr};
+use std::io::{self, Error, ErrorKind};
 
 use avalanche_types::{
     errors::Result,
     jsonrpc::client::{evm as avalanche_sdk_evm, p as avalanche_sdk_p, x as avalanche_sdk_x},
-    key, units,
+    key, wallet,
 };
+use futures::{stream, StreamExt};
 use rand::{seq::SliceRandom, thread_rng};
 
+/// Default maximum number of in-flight balance requests.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Returns the wallet endpoint observer that logs the quarantines and failovers,
+/// so the endpoint failures show up in the e2e test output.
+pub fn endpoint_observer() -> wallet::endpoints::Observer {
+    wallet::endpoints::Observer::new(|event| log::warn!("[wallet endpoints] {event}"))
+}
+
 pub struct LoadedKeysWithBalance {
     pub key_infos: Vec<key::secp256k1::Info>,
+    /// Addresses of each key on the network, in the order of "key_infos".
+    pub addrs: Vec<key::secp256k1::KeyAddresses>,
 
-    pub x_addrs: Vec<String>,
     pub x_balances: Vec<u64>,
-
-    pub p_addrs: Vec<String>,
     pub p_balances: Vec<u64>,
-
-    pub c_addrs: Vec<String>,
     pub c_balances: Vec<primitive_types::U256>,
+
+    /// Errors from fetching balances, with the index of the key.
+    /// The balances of a failed key are left as zero,
+    /// so it is never picked as a funded key.
+    pub fetch_errors: Vec<(usize, String)>,
 }
 
 impl LoadedKeysWithBalance {
-    pub fn new(key_infos: Vec<key::secp256k1::Info>) -> Self {
-        let mut loaded_keys = Self {
+    /// Creates the list with zero balances.
+    /// Fails if the addresses of any key cannot be derived for the network.
+    pub fn new(key_infos: Vec<key::secp256k1::Info>, network_id: u32) -> io::Result<Self> {
+        let mut book =
+            key::secp256k1::AddressBook::from_infos(&key_infos, &[network_id]).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("failed to derive addresses for network Id {network_id} ({e})"),
+                )
+            })?;
+        let addrs = book.0.remove(&network_id).unwrap_or_default();
+
+        let n = key_infos.len();
+        Ok(Self {
             key_infos,
-            x_addrs: Vec::new(),
-            x_balances: Vec::new(),
-            p_addrs: Vec::new(),
-            p_balances: Vec::new(),
-            c_addrs: Vec::new(),
-            c_balances: Vec::new(),
-        };
-
-        loaded_keys.x_addrs = loaded_keys
-            .key_infos
-            .iter()
-            .map(|k| k.addresses.get(&network_id).unwrap().x.clone())
-            .collect();
-
-        loaded_keys.p_addrs = loaded_keys
-            .key_infos
-            .iter()
-            .map(|k| k.addresses.get(&network_id).unwrap().p.clone())
-            .collect();
-
-        loaded_keys.c_addrs = loaded_keys
-            .key_infos
-            .iter()
-            .map(|k| k.eth_address.clone())
-            .collect();
-
-        loaded_keys
+            addrs,
+            x_balances: vec![0; n],
+            p_balances: vec![0; n],
+            c_balances: vec![primitive_types::U256::zero(); n],
+            fetch_errors: Vec::new(),
+        })
     }
 
-    pub async fn load_balances(&mut self, network_id: u32, http_rpc: &str) -> io::Result<()> {
-        if self.permute_keys {
-            self.permute();
-        }
-
-        let (x_balances, p_balances, c_balances) = if network_id == 1 {
-            get_mainnet_balances(&self.key_infos, http_rpc).await?
-        } else {
-            get_local_balances(&self.key_infos, http_rpc).await?
-        };
-
-        self.x_balances = x_balances;
-        self.p_balances = p_balances;
-        self.c_balances = c_balances;
-
-        Ok(())
+    /// Shuffles the keys, keeping the addresses and balances aligned.
+    pub fn permute(&mut self) {
+        let mut idx: Vec<usize> = (0..self.key_infos.len()).collect();
+        idx.shuffle(&mut thread_rng());
+
+        self.key_infos = idx.iter().map(|i| self.key_infos[*i].clone()).collect();
+        self.addrs = idx.iter().map(|i| self.addrs[*i].clone()).collect();
+        self.x_balances = idx.iter().map(|i| self.x_balances[*i]).collect();
+        self.p_balances = idx.iter().map(|i| self.p_balances[*i]).collect();
+        self.c_balances = idx.iter().map(|i| self.c_balances[*i]).collect();
     }
 
-    pub fn permute(&mut self) {
-        self.key_infos.shuffle(&mut thread_rng());
-        self.x_addrs.shuffle(&mut thread_rng());
-        self.p_addrs.shuffle(&mut thread_rng());
-        self.c_addrs.shuffle(&mut thread_rng());
+    /// Fetches the X/P-chain balances of all keys, with at most
+    /// "max_concurrent_requests" keys queried at a time,
+    /// and the C-chain balances of all keys in a single batch request.
+    /// A failing key is recorded in "fetch_errors" and does not fail the batch.
+    pub async fn load_balances(&mut self, http_rpc: &str, max_concurrent_requests: usize) {
+        let results: Vec<Result<(u64, u64)>> = stream::iter(0..self.key_infos.len())
+            .map(|i| fetch_balances(http_rpc, &self.addrs[i]))
+            .buffered(max_concurrent_requests.max(1))
+            .collect()
+            .await;
+
+        let eth_addrs: Vec<primitive_types::H160> =
+            self.key_infos.iter().map(|k| k.h160_address).collect();
+        let c_balances =
+            avalanche_sdk_evm::get_balances(&format!("{http_rpc}/ext/bc/C/rpc"), &eth_addrs).await;
+
+        self.fetch_errors.clear();
+        for (i, res) in results.into_iter().enumerate() {
+            let res = res.and_then(|(x, p)| match &c_balances {
+                Ok(c) => Ok((x, p, c[i])),
+                Err(e) => Err(e.clone()),
+            });
+            match res {
+                Ok((x, p, c)) => {
+                    self.x_balances[i] = x;
+                    self.p_balances[i] = p;
+                    self.c_balances[i] = c;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "failed to fetch balances for key {} ({})",
+                        self.key_infos[i].short_address,
+                        e
+                    );
+                    self.x_balances[i] = 0;
+                    self.p_balances[i] = 0;
+                    self.c_balances[i] = primitive_types::U256::zero();
+                    self.fetch_errors.push((i, e.to_string()));
+                }
+            }
+        }
     }
 }
 
+/// Fetches the X/P-chain balances of a single key.
+async fn fetch_balances(
+    http_rpc: &str,
+    addrs: &key::secp256k1::KeyAddresses,
+) -> Result<(u64, u64)> {
+    let x_balance = avalanche_sdk_x::get_balance(http_rpc, &addrs.x)
+        .await?
+        .result
+        .map_or(0, |r| r.balance);
+    let p_balance = avalanche_sdk_p::get_balance(http_rpc, &addrs.p)
+        .await?
+        .result
+        .map_or(0, |r| r.balance);
+    Ok((x_balance, p_balance))
+}
+
 /// Load the signing hot keys and fetch their balances.
-/// TODO: parallelize fetch
 pub async fn load_keys_with_balance(
     key_infos: Vec<key::secp256k1::Info>,
     permute_keys: bool,
     network_id: u32,
     http_rpc: &str,
 ) -> io::Result<LoadedKeysWithBalance> {
-    let mut loaded_keys = LoadedKeysWithBalance::new(key_infos);
+    load_keys_with_balance_concurrency(
+        key_infos,
+        permute_keys,
+        network_id,
+        http_rpc,
+        DEFAULT_MAX_CONCURRENT_REQUESTS,
+    )
+    .await
+}
+
+/// Same as "load_keys_with_balance" with the configurable number
+/// of in-flight balance requests.
+pub async fn load_keys_with_balance_concurrency(
+    key_infos: Vec<key::secp256k1::Info>,
+    permute_keys: bool,
+    network_id: u32,
+    http_rpc: &str,
+    max_concurrent_requests: usize,
+) -> io::Result<LoadedKeysWithBalance> {
+    let mut loaded_keys = LoadedKeysWithBalance::new(key_infos, network_id)?;
     if permute_keys {
         loaded_keys.permute();
     }
 
-    let (x_balances, p_balances, c_balances) = if network_id == 1 {
-        get_mainnet_balances(&loaded_keys.key_infos, http_rpc).await?
-    } else {
-        get_local_balances(&loaded_keys.key_infos, http_rpc).await?
-    };
-
-    loaded_keys.x_balances = x_balances;
-    loaded_keys.p_balances = p_balances;
-    loaded_keys.c_balances = c_balances;
+    loaded_keys
+        .load_balances(http_rpc, max_concurrent_requests)
+        .await;
+    if !loaded_keys.fetch_errors.is_empty() {
+        log::warn!(
+            "failed to fetch balances for {} out of {} keys",
+            loaded_keys.fetch_errors.len(),
+            loaded_keys.key_infos.len()
+        );
+    }
 
     Ok(loaded_keys)
 }
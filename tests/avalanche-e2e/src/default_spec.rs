@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    io::{self, stdout},
+    io::{self, stdout, Error, ErrorKind},
 };
 
 use crate::{
@@ -42,6 +42,25 @@ pub fn command() -> Command {
                 .value_parser(value_parser!(u32))
                 .default_value("1337"),
         )
+        .arg(
+            Arg::new("KEYS_TO_GENERATE")
+                .long("keys-to-generate")
+                .help("Sets the number of keys to generate")
+                .required(false)
+                .num_args(1)
+                .value_parser(value_parser!(usize))
+                .default_value("30"),
+        )
+        .arg(
+            Arg::new("KEYS_SEED")
+                .long("keys-seed")
+                .help(
+                    "Sets the seed (any string) to generate the same keys in every run, \
+                     NEVER use them to hold real funds",
+                )
+                .required(false)
+                .num_args(1),
+        )
         .arg(
             Arg::new("SIGN_WITH_KMS_AWS")
                 .long("sign-with-kms-aws")
@@ -79,6 +98,8 @@ pub struct Options {
     pub network_id: u32,
 
     pub keys_to_generate: usize,
+    /// Generates the keys deterministically if set.
+    pub keys_seed: Option<[u8; 32]>,
     pub sign_with_kms_aws: bool,
 
     pub network_runner_grpc_endpoint: Option<String>,
@@ -114,14 +135,23 @@ pub async fn execute(opts: flags::Options, sub_opts: Options) -> io::Result<()>
         sub_opts.keys_to_generate
     };
 
+    // only generated if seeded, so the same seed always writes the same keys
+    let seeded_keys = if sub_opts.keys_seed.is_some() {
+        avalanche_types::key::secp256k1::generate_n(keys_to_generate, sub_opts.keys_seed)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed generate_n '{e}'")))?
+    } else {
+        Vec::new()
+    };
+
     let mut key_infos: Vec<avalanche_types::key::secp256k1::Info> = Vec::new();
     for i in 0..keys_to_generate {
         let ki = {
             // first key, just use hot "ewoq" key
             // to use the prefunds from network runner genesis
             if i == 0 {
-                avalanche_types::key::secp256k1::TEST_KEYS[i]
-                    .clone()
+                avalanche_types::key::secp256k1::EWOQ_KEY
+                    .to_private_key()
+                    .unwrap()
                     .to_info(sub_opts.network_id)
                     .unwrap()
             } else if sub_opts.sign_with_kms_aws {
@@ -141,6 +171,8 @@ pub async fn execute(opts: flags::Options, sub_opts: Options) -> io::Result<()>
                 let key_info = key.to_info(sub_opts.network_id).unwrap();
                 println!("key_info: {}", key_info);
                 key_info
+            } else if let Some(k) = seeded_keys.get(i) {
+                k.to_info(sub_opts.network_id).unwrap()
             } else if i < avalanche_types::key::secp256k1::TEST_KEYS.len() {
                 avalanche_types::key::secp256k1::TEST_KEYS[i]
                     .clone()
@@ -4,6 +4,8 @@ pub struct Options {
     pub log_level: String,
     pub spec_path: String,
     pub skip_prompt: bool,
+    /// Directory to write the JSON and JUnit XML reports of the scenario results.
+    pub report_path: Option<String>,
 }
 
 pub fn command() -> Command {
@@ -24,4 +26,11 @@ pub fn command() -> Command {
                 .num_args(1)
                 .default_value("info"),
         )
+        .arg(
+            Arg::new("REPORT_PATH")
+                .long("report-path")
+                .help("Sets the directory to write JSON and JUnit XML reports of the test results")
+                .required(false)
+                .num_args(1),
+        )
 }
@@ -0,0 +1,110 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+};
+
+use crate::spec::Spec;
+use avalanche_types::jsonrpc::client::info as client_info;
+use tokio::sync::RwLock;
+
+pub const NAME: &str = "INFO_SMOKE";
+
+/// Calls the info APIs on every node and makes sure
+/// the responses are parsed into the typed results.
+pub async fn run(spec: Arc<RwLock<Spec>>) -> io::Result<()> {
+    let spec_rlocked = spec.read().await;
+
+    let network_id = spec_rlocked.status.clone().unwrap().network_id;
+    let http_rpc_eps = spec_rlocked.rpc_endpoints.clone();
+    log::info!(
+        "{}: network Id {}, rpc endpoints {:?}",
+        NAME,
+        network_id,
+        http_rpc_eps
+    );
+
+    let (mut success, mut failure) = (0_u64, 0_u64);
+    for ep in http_rpc_eps.iter() {
+        println!("\n\n\n---\n[{ep}] checking info APIs");
+        match check_info(ep, network_id).await {
+            Ok(_) => {
+                success += 1;
+            }
+            Err(e) => {
+                failure += 1;
+                if !spec_rlocked.ignore_errors {
+                    return Err(e);
+                } else {
+                    log::warn!("ignoring error {e}");
+                }
+            }
+        }
+    }
+
+    log::info!(
+        "DONE ENDPOINTS {}, SUCCESS {}, FAILURE {}",
+        http_rpc_eps.len(),
+        success,
+        failure
+    );
+    Ok(())
+}
+
+async fn check_info(http_rpc: &str, network_id: u32) -> io::Result<()> {
+    let resp = client_info::get_network_id(http_rpc)
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_network_id '{e}'")))?;
+    let got = resp
+        .result
+        .ok_or_else(|| Error::new(ErrorKind::Other, "empty get_network_id result"))?
+        .network_id;
+    if got != network_id {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("unexpected network Id {got} (expected {network_id})"),
+        ));
+    }
+
+    let resp = client_info::get_node_version(http_rpc)
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_node_version '{e}'")))?;
+    let version = resp
+        .result
+        .ok_or_else(|| Error::new(ErrorKind::Other, "empty get_node_version result"))?;
+    if version.vm_versions.platform.is_empty() {
+        return Err(Error::new(ErrorKind::Other, "empty platform vm version"));
+    }
+    log::info!(
+        "{http_rpc}: version {}, vm versions {:?}",
+        version.version,
+        version.vm_versions
+    );
+
+    let resp = client_info::peers(http_rpc, None)
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed peers '{e}'")))?;
+    let peers = resp
+        .result
+        .ok_or_else(|| Error::new(ErrorKind::Other, "empty peers result"))?;
+    log::info!("{http_rpc}: {} peer(s)", peers.num_peers);
+
+    // non-validators return an error
+    match client_info::uptime(http_rpc, None).await {
+        Ok(resp) => {
+            let uptime = resp
+                .result
+                .ok_or_else(|| Error::new(ErrorKind::Other, "empty uptime response"))?;
+            log::info!(
+                "{http_rpc}: rewarding stake {}%, weighted average {}%",
+                uptime.rewarding_stake_percentage,
+                uptime.weighted_average_percentage
+            );
+        }
+        Err(e @ avalanche_types::errors::Error::JsonRpc { .. }) => {
+            log::warn!("{http_rpc}: uptime not available '{}'", e.message());
+        }
+        Err(e) => return Err(Error::new(ErrorKind::Other, format!("failed uptime '{e}'"))),
+    }
+
+    Ok(())
+}
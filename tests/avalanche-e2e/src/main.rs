@@ -1,10 +1,14 @@
 pub mod c;
+pub mod chaos;
 pub mod command;
 pub mod common;
 pub mod default_spec;
 pub mod flags;
+pub mod info;
 pub mod logs;
 pub mod p;
+pub mod report;
+pub mod scheduler;
 pub mod spec;
 pub mod x;
 
@@ -39,6 +43,13 @@ async fn main() {
                 .required(false)
                 .num_args(1),
         )
+        .arg(
+            Arg::new("REPORT_PATH")
+                .long("report-path")
+                .help("Sets the directory to write JSON and JUnit XML reports of the test results")
+                .required(false)
+                .num_args(1),
+        )
         .subcommand(default_spec::command())
         .get_matches();
 
@@ -52,6 +63,12 @@ async fn main() {
                 .get_one::<String>("NETWORK_RUNNER_GRPC_ENDPOINT")
                 .map(String::from);
 
+            let keys_seed = sub_matches.get_one::<String>("KEYS_SEED").map(|s| {
+                let mut seed = [0_u8; 32];
+                seed.copy_from_slice(&avalanche_types::hash::sha256(s.as_bytes()));
+                seed
+            });
+
             let network_id = *sub_matches.get_one::<u32>("NETWORK_ID").unwrap_or(&1337);
 
             let avalanchego_path = {
@@ -77,6 +94,7 @@ async fn main() {
                         .unwrap_or(&String::new())
                         .clone(),
                     skip_prompt: matches.get_flag("SKIP_PROMPT"),
+                    report_path: matches.get_one::<String>("REPORT_PATH").map(String::from),
                 },
                 default_spec::Options {
                     randomize: sub_matches.get_flag("RANDOMIZE"),
@@ -84,6 +102,7 @@ async fn main() {
                     ignore_errors: sub_matches.get_flag("IGNORE_ERRORS"),
                     network_id,
                     keys_to_generate,
+                    keys_seed,
                     sign_with_kms_aws: sub_matches.get_flag("SIGN_WITH_KMS_AWS"),
                     network_runner_grpc_endpoint,
                     network_runner_avalanchego_path: avalanchego_path,
@@ -104,6 +123,7 @@ async fn main() {
                     .unwrap_or(&String::new())
                     .clone(),
                 skip_prompt: matches.get_flag("SKIP_PROMPT"),
+                report_path: matches.get_one::<String>("REPORT_PATH").map(String::from),
             })
             .await
             .expect("failed to execute command");
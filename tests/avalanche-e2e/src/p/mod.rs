@@ -1 +1,2 @@
+pub mod subnet_validators;
 pub mod transfer;
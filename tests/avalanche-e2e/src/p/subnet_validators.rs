@@ -0,0 +1,227 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+};
+
+use crate::{common, report, spec::Spec};
+use avalanche_types::{
+    ids::{self, node},
+    jsonrpc::client::{info as client_info, p as client_p},
+    key, wallet,
+};
+use aws_manager::kms;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+pub const NAME: &str = "P_SUBNET_VALIDATORS";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// Weight of the permissioned subnet validator.
+    pub weight: u64,
+    /// Seconds from now when the subnet validator starts validating.
+    pub start_offset_seconds: u64,
+    /// Validate period in days (must be within the primary network validation).
+    pub validate_days: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            weight: DEFAULT_WEIGHT,
+            start_offset_seconds: DEFAULT_START_OFFSET_SECONDS,
+            validate_days: DEFAULT_VALIDATE_DAYS,
+        }
+    }
+}
+
+const DEFAULT_WEIGHT: u64 = 1000;
+const DEFAULT_START_OFFSET_SECONDS: u64 = 60;
+const DEFAULT_VALIDATE_DAYS: u64 = 14;
+
+/// Creates a subnet, adds a primary network validator as its permissioned
+/// validator, waits for it to start validating, then removes it,
+/// asserting the subnet validator set via "platform.getCurrentValidators"
+/// after each step.
+pub async fn run(spec: Arc<RwLock<Spec>>) -> io::Result<()> {
+    let spec_rlocked = spec.read().await;
+
+    let cfg = spec_rlocked.p_subnet_validators.clone().unwrap_or_default();
+
+    let network_id = spec_rlocked.status.clone().unwrap().network_id;
+    let http_rpc_eps = spec_rlocked.rpc_endpoints.clone();
+    let http_rpc = http_rpc_eps[0].clone();
+    log::info!(
+        "{}: network Id {}, rpc endpoints {:?}",
+        NAME,
+        network_id,
+        http_rpc_eps
+    );
+
+    // subnet validators must validate the primary network,
+    // so pick the node behind the endpoint (a genesis validator)
+    let node_id = client_info::get_node_id(&http_rpc)
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_node_id '{e}'")))?
+        .result
+        .ok_or_else(|| Error::new(ErrorKind::Other, "unexpected None GetNodeIdResult"))?
+        .node_id;
+
+    let loaded_keys_with_balance = common::load_keys_with_balance(
+        spec_rlocked.key_infos.clone(),
+        false,
+        network_id,
+        &http_rpc,
+    )
+    .await?;
+    let from_idx = loaded_keys_with_balance
+        .p_balances
+        .iter()
+        .position(|b| *b > 0)
+        .ok_or_else(|| Error::new(ErrorKind::Other, "no key found with P-chain balance"))?;
+    let key_info = &loaded_keys_with_balance.key_infos[from_idx];
+
+    if key_info.key_type == key::secp256k1::KeyType::AwsKms {
+        let shared_config = aws_manager::load_config(None, None, None).await;
+        let kms_manager = kms::Manager::new(&shared_config);
+
+        let key = avalanche_types::key::secp256k1::kms::aws::Key::from_arn(
+            kms_manager.clone(),
+            &key_info.id.clone().unwrap(),
+        )
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to load KMS key '{e}'")))?;
+        let w = wallet::Builder::new(&key)
+            .base_http_url(http_rpc.clone())
+            .endpoint_observer(common::endpoint_observer())
+            .build()
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build wallet '{e}'")))?;
+
+        validator_lifecycle(&w, &http_rpc, node_id, &cfg).await
+    } else {
+        let w = wallet::Builder::new(&key_info.to_private_key())
+            .base_http_url(http_rpc.clone())
+            .endpoint_observer(common::endpoint_observer())
+            .build()
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build wallet '{e}'")))?;
+
+        validator_lifecycle(&w, &http_rpc, node_id, &cfg).await
+    }
+}
+
+async fn validator_lifecycle<T>(
+    w: &wallet::Wallet<T>,
+    http_rpc: &str,
+    node_id: node::Id,
+    cfg: &Config,
+) -> io::Result<()>
+where
+    T: key::secp256k1::ReadOnly + key::secp256k1::SignOnly + Clone + Send + Sync,
+{
+    println!("\n\n\n---\ncreating a subnet");
+    let subnet_id = w
+        .p()
+        .create_subnet()
+        .check_acceptance(true)
+        .issue()
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed create_subnet '{e}'")))?;
+    log::info!("created subnet {subnet_id}");
+    report::record_tx_id(subnet_id);
+    assert_subnet_validators(http_rpc, &subnet_id, &[]).await?;
+
+    println!("\n\n\n---\nadding {node_id} as subnet {subnet_id} validator");
+    let (add_tx_id, added) = w
+        .p()
+        .add_subnet_validator()
+        .node_id(node_id)
+        .subnet_id(subnet_id)
+        .weight(cfg.weight)
+        .validate_period_in_days(cfg.validate_days, cfg.start_offset_seconds)
+        .check_acceptance(true)
+        .issue()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed add_subnet_validator '{e}'"),
+            )
+        })?;
+    if !added {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("{node_id} unexpectedly already validates the new subnet {subnet_id}"),
+        ));
+    }
+    log::info!("added subnet validator with tx {add_tx_id}");
+    report::record_tx_id(add_tx_id);
+    assert_subnet_validators(http_rpc, &subnet_id, &[(node_id, cfg.weight)]).await?;
+
+    println!("\n\n\n---\nremoving {node_id} from subnet {subnet_id} validators");
+    let (remove_tx_id, removed) = w
+        .p()
+        .remove_subnet_validator()
+        .node_id(node_id)
+        .subnet_id(subnet_id)
+        .check_acceptance(true)
+        .issue()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed remove_subnet_validator '{e}'"),
+            )
+        })?;
+    if !removed {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("{node_id} is not a subnet {subnet_id} validator to remove"),
+        ));
+    }
+    log::info!("removed subnet validator with tx {remove_tx_id}");
+    report::record_tx_id(remove_tx_id);
+    assert_subnet_validators(http_rpc, &subnet_id, &[]).await?;
+
+    log::info!("DONE subnet {subnet_id} validator lifecycle for {node_id}");
+    Ok(())
+}
+
+/// Makes sure the current validators of the subnet are
+/// exactly the expected node Ids with the weights.
+async fn assert_subnet_validators(
+    http_rpc: &str,
+    subnet_id: &ids::Id,
+    expected: &[(node::Id, u64)],
+) -> io::Result<()> {
+    let resp = client_p::get_subnet_validators(http_rpc, &subnet_id.to_string())
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed platform.getCurrentValidators '{e}'"),
+            )
+        })?;
+    let mut current: Vec<(node::Id, u64)> = resp
+        .result
+        .and_then(|r| r.validators)
+        .unwrap_or_default()
+        .iter()
+        .map(|v| (v.node_id, v.weight.unwrap_or_default()))
+        .collect();
+    current.sort();
+
+    let mut expected = expected.to_vec();
+    expected.sort();
+    if current != expected {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("subnet {subnet_id} validators {current:?} != expected {expected:?}"),
+        ));
+    }
+
+    log::info!("subnet {subnet_id} has expected validators {current:?}");
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use std::{
+    cell::RefCell,
+    fmt::Write as _,
+    fs,
+    future::Future,
+    io::{self, Error, ErrorKind},
+    path::Path,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// File name of the JSON report in the report directory.
+pub const JSON_FILE_NAME: &str = "report.json";
+/// File name of the JUnit XML report in the report directory.
+pub const JUNIT_FILE_NAME: &str = "junit.xml";
+
+tokio::task_local! {
+    /// Transaction Ids issued by the scenario running in the current task.
+    static TX_IDS: RefCell<Vec<String>>;
+}
+
+/// Records the transaction Id issued by the current scenario,
+/// so the report can link the results to the chain state.
+/// No-op outside of "`collect_tx_ids`" (e.g., in unit tests).
+pub fn record_tx_id(tx_id: impl ToString) {
+    let _ = TX_IDS.try_with(|ids| ids.borrow_mut().push(tx_id.to_string()));
+}
+
+/// Runs the scenario future, returning its output
+/// along with the transaction Ids it recorded.
+pub async fn collect_tx_ids<F: Future>(fut: F) -> (F::Output, Vec<String>) {
+    TX_IDS
+        .scope(RefCell::new(Vec::new()), async move {
+            let out = fut.await;
+            (out, TX_IDS.with(|ids| ids.take()))
+        })
+        .await
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Passed,
+    Failed,
+    /// Never started (e.g., a dependency failed).
+    Skipped,
+}
+
+/// Represents the result of a scenario run.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ScenarioResult {
+    pub name: String,
+    pub status: Status,
+    pub duration_seconds: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tx_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Represents the results of the e2e run, in the order of completion.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<u32>,
+    pub results: Vec<ScenarioResult>,
+}
+
+impl Report {
+    pub fn record(
+        &mut self,
+        name: &str,
+        duration: Duration,
+        tx_ids: Vec<String>,
+        res: &io::Result<()>,
+    ) {
+        self.results.push(ScenarioResult {
+            name: name.to_string(),
+            status: if res.is_ok() {
+                Status::Passed
+            } else {
+                Status::Failed
+            },
+            duration_seconds: duration.as_secs_f64(),
+            tx_ids,
+            error: res.as_ref().err().map(|e| e.to_string()),
+        });
+    }
+
+    /// Marks the scenarios with no result as skipped.
+    pub fn skip_missing(&mut self, scenarios: &[String]) {
+        for s in scenarios {
+            if self.results.iter().any(|r| &r.name == s) {
+                continue;
+            }
+            self.results.push(ScenarioResult {
+                name: s.clone(),
+                status: Status::Skipped,
+                duration_seconds: 0.0,
+                tx_ids: Vec::new(),
+                error: None,
+            });
+        }
+    }
+
+    fn count(&self, status: Status) -> usize {
+        self.results.iter().filter(|r| r.status == status).count()
+    }
+
+    /// Converts to string in JSON format.
+    pub fn encode_json(&self) -> io::Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to serialize Report to JSON {e}"),
+            )
+        })
+    }
+
+    /// Converts to string in JUnit XML format, with one test case per scenario.
+    pub fn encode_junit(&self) -> String {
+        let total_seconds: f64 = self.results.iter().map(|r| r.duration_seconds).sum();
+        let (tests, failures, skipped) = (
+            self.results.len(),
+            self.count(Status::Failed),
+            self.count(Status::Skipped),
+        );
+
+        let mut d = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            d,
+            "<testsuites name=\"{APP}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{total_seconds:.3}\">",
+            APP = crate::APP_NAME,
+        );
+        let _ = writeln!(
+            d,
+            "  <testsuite name=\"{APP}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{total_seconds:.3}\">",
+            APP = crate::APP_NAME,
+        );
+        for r in &self.results {
+            let _ = write!(
+                d,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">",
+                escape_xml(&r.name),
+                crate::APP_NAME,
+                r.duration_seconds
+            );
+            match r.status {
+                Status::Passed => {}
+                Status::Failed => {
+                    let msg = escape_xml(r.error.as_deref().unwrap_or_default());
+                    let _ = write!(d, "\n      <failure message=\"{msg}\">{msg}</failure>");
+                }
+                Status::Skipped => d.push_str("\n      <skipped/>"),
+            }
+            if !r.tx_ids.is_empty() {
+                let _ = write!(
+                    d,
+                    "\n      <system-out>tx ids: {}</system-out>",
+                    escape_xml(&r.tx_ids.join(", "))
+                );
+            }
+            if r.status != Status::Passed || !r.tx_ids.is_empty() {
+                d.push_str("\n    ");
+            }
+            d.push_str("</testcase>\n");
+        }
+        d.push_str("  </testsuite>\n</testsuites>\n");
+        d
+    }
+
+    /// Writes the JSON and JUnit XML reports to the directory.
+    pub fn write(&self, report_dir: &str) -> io::Result<()> {
+        log::info!("writing report to '{report_dir}'");
+        let dir = Path::new(report_dir);
+        fs::create_dir_all(dir)?;
+
+        fs::write(dir.join(JSON_FILE_NAME), self.encode_json()?)?;
+        fs::write(dir.join(JUNIT_FILE_NAME), self.encode_junit())?;
+        Ok(())
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-e2e -- report::test_report --exact \
+/// --show-output
+#[tokio::test]
+async fn test_report() {
+    let (res, tx_ids) = collect_tx_ids(async {
+        record_tx_id("tx1");
+        record_tx_id("tx2");
+        Ok::<(), Error>(())
+    })
+    .await;
+    assert_eq!(tx_ids, vec!["tx1".to_string(), "tx2".to_string()]);
+    // no-op outside of the scope
+    record_tx_id("tx3");
+
+    let mut report = Report {
+        network_id: Some(1337),
+        ..Default::default()
+    };
+    report.record("A", Duration::from_millis(1500), tx_ids, &res);
+    report.record(
+        "B",
+        Duration::from_secs(2),
+        Vec::new(),
+        &Err(Error::new(ErrorKind::Other, "balance <0 & \"stuck\"")),
+    );
+    report.skip_missing(&["A".to_string(), "B".to_string(), "C".to_string()]);
+
+    let statuses: Vec<Status> = report.results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![Status::Passed, Status::Failed, Status::Skipped]
+    );
+
+    let parsed: Report = serde_json::from_str(&report.encode_json().unwrap()).unwrap();
+    assert_eq!(parsed, report);
+
+    let junit = report.encode_junit();
+    assert!(junit.contains("tests=\"3\" failures=\"1\" skipped=\"1\" time=\"3.500\""));
+    assert!(junit.contains("<system-out>tx ids: tx1, tx2</system-out>"));
+    assert!(junit.contains("message=\"balance &lt;0 &amp; &quot;stuck&quot;\""));
+    assert!(junit.contains("<testcase name=\"C\" classname=\"avalanche-e2e\" time=\"0.000\">\n      <skipped/>\n    </testcase>"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let report_dir = dir.path().join("out");
+    report.write(report_dir.to_str().unwrap()).unwrap();
+    assert!(report_dir.join(JSON_FILE_NAME).exists());
+    assert!(report_dir.join(JUNIT_FILE_NAME).exists());
+}
@@ -0,0 +1,294 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    io::{self, Error, ErrorKind},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+
+/// Scheduling constraints of a scenario.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Constraints {
+    /// Scenarios that must succeed before this scenario starts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Resource tags (e.g., "p-chain") this scenario uses.
+    /// Scenarios sharing any tag never run at the same time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+}
+
+/// Dependency graph of the scenarios to run.
+#[derive(Debug, Clone)]
+pub struct Dag {
+    /// Scenarios in the preferred start order (e.g., randomized).
+    order: Vec<String>,
+    constraints: HashMap<String, Constraints>,
+}
+
+impl Dag {
+    /// Builds the graph, making sure every dependency is a scheduled
+    /// scenario and the dependencies have no cycle.
+    pub fn new(
+        scenarios: &[String],
+        constraints: &BTreeMap<String, Constraints>,
+    ) -> io::Result<Self> {
+        let scheduled: HashSet<&String> = scenarios.iter().collect();
+        if scheduled.len() != scenarios.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("duplicate scenarios in {scenarios:?}"),
+            ));
+        }
+        for (name, c) in constraints {
+            for dep in &c.depends_on {
+                if !scheduled.contains(dep) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("scenario '{name}' depends on unscheduled scenario '{dep}'"),
+                    ));
+                }
+            }
+        }
+
+        let dag = Self {
+            order: scenarios.to_vec(),
+            constraints: constraints
+                .iter()
+                .filter(|(name, _)| scheduled.contains(name))
+                .map(|(name, c)| (name.clone(), c.clone()))
+                .collect(),
+        };
+
+        // Kahn's algorithm; any scenario left unvisited is on a cycle
+        let mut done = HashSet::new();
+        loop {
+            let ready: Vec<&String> = dag
+                .order
+                .iter()
+                .filter(|s| !done.contains(*s) && dag.deps_done(s, &done))
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for s in ready {
+                done.insert(s.clone());
+            }
+        }
+        if done.len() != dag.order.len() {
+            let cyclic: Vec<&String> = dag.order.iter().filter(|s| !done.contains(*s)).collect();
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("cyclic scenario dependencies among {cyclic:?}"),
+            ));
+        }
+
+        Ok(dag)
+    }
+
+    fn deps_done(&self, name: &str, done: &HashSet<String>) -> bool {
+        self.constraints
+            .get(name)
+            .map_or(true, |c| c.depends_on.iter().all(|d| done.contains(d)))
+    }
+
+    fn resources(&self, name: &str) -> &[String] {
+        self.constraints
+            .get(name)
+            .map_or(&[], |c| c.resources.as_slice())
+    }
+
+    /// Returns the scenarios that can start now, in the preferred order,
+    /// given the finished and the running ones and the free slots.
+    fn next_runnable(
+        &self,
+        done: &HashSet<String>,
+        running: &HashSet<String>,
+        slots: usize,
+    ) -> Vec<String> {
+        let mut held: HashSet<&String> = running
+            .iter()
+            .flat_map(|s| self.resources(s).iter())
+            .collect();
+
+        let mut runnable = Vec::new();
+        for s in &self.order {
+            if runnable.len() >= slots {
+                break;
+            }
+            if done.contains(s) || running.contains(s) || !self.deps_done(s, done) {
+                continue;
+            }
+            let resources = self.resources(s);
+            if resources.iter().any(|r| held.contains(r)) {
+                continue;
+            }
+            held.extend(resources.iter());
+            runnable.push(s.clone());
+        }
+        runnable
+    }
+
+    /// Runs the scenarios with at most "`max_concurrency`" at a time
+    /// (zero means no limit), starting each one once its dependencies succeed
+    /// and no running scenario holds any of its resources.
+    /// Returns the first failure, aborting the running scenarios
+    /// without starting the remaining ones.
+    pub async fn execute<F, Fut>(&self, max_concurrency: usize, run: F) -> io::Result<()>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = io::Result<()>> + Send + 'static,
+    {
+        let limit = if max_concurrency == 0 {
+            self.order.len().max(1)
+        } else {
+            max_concurrency
+        };
+
+        let mut done = HashSet::new();
+        let mut running = HashSet::new();
+        let mut handles = JoinSet::new();
+        while done.len() < self.order.len() {
+            for s in self.next_runnable(&done, &running, limit - running.len()) {
+                log::info!("starting scenario '{s}' (running {running:?})");
+                let fut = run(s.clone());
+                running.insert(s.clone());
+                handles.spawn(async move { (s, fut.await) });
+            }
+
+            let Some(joined) = handles.join_next().await else {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "no runnable scenario left (unexpected scheduler state)",
+                ));
+            };
+            let (s, r) = joined.map_err(|e| {
+                Error::new(ErrorKind::Other, format!("failed await on JoinHandle {e}"))
+            })?;
+            running.remove(&s);
+            if let Err(e) = r {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("scenario '{s}' failed {e}"),
+                ));
+            }
+            log::info!("scenario '{s}' succeeded");
+            done.insert(s);
+        }
+        Ok(())
+    }
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-e2e -- scheduler::test_dag --exact \
+/// --show-output
+#[test]
+fn test_dag() {
+    let scenarios: Vec<String> = ["A", "B", "C"].iter().map(|s| s.to_string()).collect();
+    let deps = |pairs: &[(&str, &[&str])]| -> BTreeMap<String, Constraints> {
+        pairs
+            .iter()
+            .map(|(s, deps)| {
+                (
+                    s.to_string(),
+                    Constraints {
+                        depends_on: deps.iter().map(|d| d.to_string()).collect(),
+                        resources: Vec::new(),
+                    },
+                )
+            })
+            .collect()
+    };
+
+    assert!(Dag::new(&scenarios, &BTreeMap::new()).is_ok());
+    assert!(Dag::new(&scenarios, &deps(&[("C", &["A", "B"]), ("B", &["A"])])).is_ok());
+
+    // unscheduled dependency
+    assert!(Dag::new(&scenarios, &deps(&[("C", &["D"])])).is_err());
+    // cycle
+    assert!(Dag::new(
+        &scenarios,
+        &deps(&[("A", &["C"]), ("C", &["B"]), ("B", &["A"])])
+    )
+    .is_err());
+    // duplicate
+    let dup: Vec<String> = vec!["A".to_string(), "A".to_string()];
+    assert!(Dag::new(&dup, &BTreeMap::new()).is_err());
+}
+
+/// `RUST_LOG=debug` cargo test --package avalanche-e2e -- scheduler::test_execute --exact \
+/// --show-output
+#[tokio::test]
+async fn test_execute() {
+    use std::sync::{Arc, Mutex};
+    use tokio::time::{sleep, Duration};
+
+    let scenarios: Vec<String> = ["A", "B", "C", "D"].iter().map(|s| s.to_string()).collect();
+    let constraints = BTreeMap::from([
+        (
+            "A".to_string(),
+            Constraints {
+                resources: vec!["p-chain".to_string()],
+                ..Default::default()
+            },
+        ),
+        (
+            "B".to_string(),
+            Constraints {
+                resources: vec!["p-chain".to_string()],
+                ..Default::default()
+            },
+        ),
+        (
+            "D".to_string(),
+            Constraints {
+                depends_on: vec!["C".to_string()],
+                ..Default::default()
+            },
+        ),
+    ]);
+    let dag = Dag::new(&scenarios, &constraints).unwrap();
+
+    // records (scenario, started) events
+    let events: Arc<Mutex<Vec<(String, bool)>>> = Arc::new(Mutex::new(Vec::new()));
+    let run = |s: String| {
+        let events = events.clone();
+        async move {
+            events.lock().unwrap().push((s.clone(), true));
+            sleep(Duration::from_millis(20)).await;
+            events.lock().unwrap().push((s, false));
+            Ok(())
+        }
+    };
+    dag.execute(0, run).await.unwrap();
+
+    let events = events.lock().unwrap().clone();
+    assert_eq!(events.len(), 8);
+    let pos = |s: &str, started: bool| {
+        events
+            .iter()
+            .position(|e| e.0 == s && e.1 == started)
+            .unwrap()
+    };
+    // "A" and "C" start right away, "B" waits for "A" to release "p-chain"
+    assert!(pos("B", true) > pos("A", false));
+    assert!(pos("C", true) < pos("A", false));
+    // "D" waits for "C"
+    assert!(pos("D", true) > pos("C", false));
+
+    // failure stops the dependents
+    let started: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let run = |s: String| {
+        let started = started.clone();
+        async move {
+            started.lock().unwrap().push(s.clone());
+            if s == "C" {
+                return Err(Error::new(ErrorKind::Other, "failed"));
+            }
+            Ok(())
+        }
+    };
+    assert!(dag.execute(1, run).await.is_err());
+    assert!(!started.lock().unwrap().contains(&"D".to_string()));
+}
@@ -1,17 +1,21 @@
 use std::{
+    collections::BTreeMap,
     fs::{self, File},
     io::{self, Error, ErrorKind, Write},
     path::Path,
     string::String,
 };
 
-use crate::{c, x};
-use avalanche_types::key;
+use crate::{c, chaos, info, p, scheduler, x};
+use avalanche_types::{jsonrpc::client::http, key};
 use serde::{Deserialize, Serialize};
 
 pub const RPC_ENDPOINT_KIND_NETWORK_RUNNER_RPC_SERVER: &str = "network-runner-rpc-server";
 pub const RPC_ENDPOINT_KIND_AVALANCHEGO_RPC_ENDPOINT: &str = "avalanchego-rpc-endpoint";
 
+/// Resource tag of the scenarios that issue P-chain transactions.
+pub const RESOURCE_P_CHAIN: &str = "p-chain";
+
 /// Represents the e2e test specification.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -23,6 +27,10 @@ pub struct Spec {
     /// May initially be set network runner server endpoints
     /// but later to be updated with avalanche RPC endpoints.
     pub rpc_endpoints: Vec<String>,
+    /// Authentication of the RPC endpoints that require it
+    /// (e.g., managed node providers), keyed by the endpoint URL.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rpc_endpoint_auths: BTreeMap<String, http::Auth>,
 
     /// If empty, it downloads the latest from the github release page.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -38,11 +46,19 @@ pub struct Spec {
     pub randomize: bool,
     /// "true" to run tests in parallel.
     pub parallelize: bool,
+    /// Maximum number of scenarios running at a time when parallelized.
+    /// If empty, runs all independent scenarios at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
     /// "true" to ignore errors in the tests.
     pub ignore_errors: bool,
 
     /// Represents test scenario Ids.
     pub scenarios: Vec<String>,
+    /// Dependencies and resource tags of the scenarios, keyed by scenario Id.
+    /// Scenarios sharing a resource tag are never run at the same time.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scenario_constraints: BTreeMap<String, scheduler::Constraints>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub x_simple_transfers: Option<x::simple_transfers::Config>,
@@ -50,6 +66,15 @@ pub struct Spec {
     pub x_exports: Option<x::exports::Config>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub c_simple_transfers: Option<c::simple_transfers::Config>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub c_contract_transfers: Option<c::contract_transfers::Config>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p_subnet_validators: Option<p::subnet_validators::Config>,
+
+    /// Node disruptions between the scenario rounds.
+    /// Only supported with "network-runner-rpc-server".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chaos: Option<chaos::Config>,
 
     /// Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +86,7 @@ impl Default for Spec {
         Self {
             rpc_endpoint_kind: String::from(RPC_ENDPOINT_KIND_NETWORK_RUNNER_RPC_SERVER),
             rpc_endpoints: Vec::new(),
+            rpc_endpoint_auths: BTreeMap::new(),
 
             avalanchego_path: None,
             avalanchego_plugin_dir: None,
@@ -69,19 +95,34 @@ impl Default for Spec {
 
             randomize: false,
             parallelize: false,
+            max_concurrency: None,
             ignore_errors: false,
 
             scenarios: vec![
+                info::smoke::NAME.to_string(),
                 x::simple_transfers::NAME.to_string(),
                 c::simple_transfers::NAME.to_string(),
+                c::contract_transfers::NAME.to_string(),
+                p::subnet_validators::NAME.to_string(),
                 //
                 // TODO: not working
                 // x::exports::NAME.to_string(),
             ],
+            scenario_constraints: BTreeMap::from([(
+                p::subnet_validators::NAME.to_string(),
+                scheduler::Constraints {
+                    resources: vec![RESOURCE_P_CHAIN.to_string()],
+                    ..Default::default()
+                },
+            )]),
 
             x_simple_transfers: Some(x::simple_transfers::Config::default()),
             x_exports: Some(x::exports::Config::default()),
             c_simple_transfers: Some(c::simple_transfers::Config::default()),
+            c_contract_transfers: Some(c::contract_transfers::Config::default()),
+            p_subnet_validators: Some(p::subnet_validators::Config::default()),
+
+            chaos: None,
 
             status: None,
         }
@@ -149,10 +190,36 @@ impl Spec {
             }
         }
 
+        for (endpoint, auth) in &self.rpc_endpoint_auths {
+            auth.validate().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("invalid auth for '{endpoint}' ({e})"),
+                )
+            })?;
+        }
+
         if self.key_infos.is_empty() {
             return Err(Error::new(ErrorKind::InvalidInput, "empty key_infos"));
         }
 
+        if self.max_concurrency == Some(0) {
+            return Err(Error::new(ErrorKind::InvalidInput, "zero max_concurrency"));
+        }
+        scheduler::Dag::new(&self.scenarios, &self.scenario_constraints)?;
+
+        if let Some(cfg) = &self.chaos {
+            if self.rpc_endpoint_kind != RPC_ENDPOINT_KIND_NETWORK_RUNNER_RPC_SERVER {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "chaos requires rpc_endpoint_kind '{RPC_ENDPOINT_KIND_NETWORK_RUNNER_RPC_SERVER}'"
+                    ),
+                ));
+            }
+            cfg.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -167,6 +234,10 @@ rpc_endpoints:
 - a
 - b
 - c
+rpc_endpoint_auths:
+  https://api.example.com:
+    kind: bearer
+    token: secret
 
 key_infos:
 - key_type: hot
@@ -204,6 +275,12 @@ scenarios:
     let expected = Spec {
         rpc_endpoint_kind: String::from(RPC_ENDPOINT_KIND_NETWORK_RUNNER_RPC_SERVER),
         rpc_endpoints: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        rpc_endpoint_auths: BTreeMap::from([(
+            "https://api.example.com".to_string(),
+            http::Auth::Bearer {
+                token: "secret".to_string(),
+            },
+        )]),
 
         avalanchego_path: None,
         avalanchego_plugin_dir: None,
@@ -212,13 +289,19 @@ scenarios:
 
         randomize: true,
         parallelize: false,
+        max_concurrency: None,
         ignore_errors: false,
 
         scenarios: vec![x::simple_transfers::NAME.to_string()],
+        scenario_constraints: BTreeMap::new(),
 
         x_simple_transfers: None,
         x_exports: None,
         c_simple_transfers: None,
+        c_contract_transfers: None,
+        p_subnet_validators: None,
+
+        chaos: None,
 
         status: None,
     };
@@ -4,7 +4,7 @@ use std::{
     time::Duration,
 };
 
-use crate::{common, spec::Spec};
+use crate::{common, report, spec::Spec};
 use avalanche_types::{
     choices::status::Status,
     jsonrpc::client::{p as avalanche_sdk_p, x as avalanche_sdk_x},
@@ -150,6 +150,7 @@ async fn make_single_export(
 
     let w = wallet::Builder::new(&loaded_keys_with_balance.key_infos[from_idx].to_private_key())
         .base_http_url(http_rpc.to_string())
+        .endpoint_observer(common::endpoint_observer())
         .build()
         .await
         .unwrap();
@@ -226,6 +227,7 @@ async fn make_single_export(
     }
 
     log::info!("SUCCESS with transaction id {tx_id})");
+    report::record_tx_id(tx_id);
 
     let tx_id = w
         .p()
@@ -235,6 +237,7 @@ async fn make_single_export(
         .issue()
         .await
         .unwrap();
+    report::record_tx_id(tx_id);
 
     // enough time for txs processing
     sleep(Duration::from_secs(7)).await;
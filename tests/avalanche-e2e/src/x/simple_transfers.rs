@@ -5,7 +5,7 @@ use std::{
     time::Duration,
 };
 
-use crate::{common, spec::Spec};
+use crate::{chaos, common, report, spec::Spec};
 use avalanche_types::{
     choices::status::Status, jsonrpc::client::x as avalanche_sdk_x, key, wallet,
 };
@@ -64,16 +64,26 @@ pub async fn run(spec: Arc<RwLock<Spec>>) -> io::Result<()> {
             "\n\n\n---\n[ROUND #{:02}] making X-chain transfer (to permute {to_permute})",
             i
         );
-        match make_single_transfer(
+
+        // the wallet may pick the disrupted node, but the
+        // balances and the acceptance are checked on the live ones
+        let disruption = chaos::disrupt(&spec_rlocked, i).await?;
+        let live_eps = chaos::live_endpoints(&disruption, &http_rpc_eps);
+        let http_rpc = live_eps[i % live_eps.len()].as_str();
+        let res = make_single_transfer(
             network_id,
-            http_rpc_eps.clone(),
-            http_rpc_eps[i % http_rpc_eps.len()].as_str(),
+            live_eps.clone(),
+            http_rpc,
+            &chaos::wallet_endpoints(&spec_rlocked, &http_rpc_eps, http_rpc),
             key_infos.clone(),
             to_permute,
             check_acceptance,
         )
-        .await
-        {
+        .await;
+        if let Some(d) = disruption {
+            d.recover().await?;
+        }
+        match res {
             Ok(_) => {
                 success += 1;
             }
@@ -107,6 +117,7 @@ async fn make_single_transfer(
     network_id: u32,
     http_rpc_eps: Vec<String>,
     http_rpc: &str,
+    wallet_http_rpcs: &[String],
     key_infos: Vec<key::secp256k1::Info>,
     permute_keys: bool,
     check_acceptance: bool,
@@ -183,7 +194,8 @@ async fn make_single_transfer(
         .unwrap();
 
         let w = wallet::Builder::new(&key)
-            .base_http_url(http_rpc.to_string())
+            .base_http_urls(wallet_http_rpcs)
+            .endpoint_observer(common::endpoint_observer())
             .build()
             .await
             .unwrap();
@@ -206,7 +218,8 @@ async fn make_single_transfer(
     } else {
         let w =
             wallet::Builder::new(&loaded_keys_with_balance.key_infos[from_idx].to_private_key())
-                .base_http_url(http_rpc.to_string())
+                .base_http_urls(wallet_http_rpcs)
+                .endpoint_observer(common::endpoint_observer())
                 .build()
                 .await
                 .unwrap();
@@ -337,5 +350,6 @@ async fn make_single_transfer(
     }
 
     log::info!("SUCCESS with transaction id {tx_id})");
+    report::record_tx_id(tx_id);
     Ok(())
 }